@@ -0,0 +1,355 @@
+//! Tracking of playing sounds and the display objects that own them.
+
+use crate::backend::audio::{
+    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
+};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{Collect, CollectionContext};
+use std::sync::Arc;
+
+/// Keeps track of the sounds started by the movie, so that the sound transforms of the display
+/// objects that played them can be applied.
+///
+/// The effective transform of a sound is the global sound transform multiplied by the sound
+/// transforms of its owner and all of the owner's ancestors. Effective transforms are cached,
+/// and only recalculated after a sound transform changes.
+pub struct AudioManager<'gc> {
+    /// The sounds that are currently playing.
+    sounds: Vec<ActiveSound<'gc>>,
+
+    /// The sound transform applied to all sounds (`SoundMixer.soundTransform` in AS3).
+    global_sound_transform: SoundTransform,
+
+    /// Whether a sound transform has changed since the effective transforms were calculated.
+    transforms_dirty: bool,
+}
+
+/// A sound instance or stream that was started by the movie.
+struct ActiveSound<'gc> {
+    /// The handle of the playing instance in the audio backend.
+    instance: SoundInstanceHandle,
+
+    /// The sound definition this instance is playing.
+    /// `None` if this is a stream sound.
+    sound: Option<SoundHandle>,
+
+    /// The display object that played this sound.
+    /// `None` if this sound is only affected by the global sound transform.
+    owner: Option<DisplayObject<'gc>>,
+
+    /// The movie whose timeline or code started this sound.
+    movie: Option<Arc<SwfMovie>>,
+
+    /// The last effective transform sent to the audio backend.
+    transform: SoundTransform,
+}
+
+unsafe impl<'gc> Collect for AudioManager<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        for sound in &self.sounds {
+            sound.owner.trace(cc);
+        }
+    }
+}
+
+impl<'gc> AudioManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            sounds: Vec::new(),
+            global_sound_transform: SoundTransform::default(),
+            transforms_dirty: false,
+        }
+    }
+
+    /// Starts playing an event sound owned by the given display object, on behalf of the given
+    /// movie.
+    pub fn start_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        sound: SoundHandle,
+        settings: &swf::SoundInfo,
+        owner: Option<DisplayObject<'gc>>,
+        movie: Option<Arc<SwfMovie>>,
+    ) -> Option<SoundInstanceHandle> {
+        let instance = audio.start_sound(sound, settings).ok()?;
+        self.add_sound(audio, instance, Some(sound), owner, movie);
+        Some(instance)
+    }
+
+    /// Starts playing the stream sound of a movie clip.
+    pub fn start_stream(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        owner: DisplayObject<'gc>,
+        clip_frame: u16,
+        clip_data: SwfSlice,
+        stream_info: &swf::SoundStreamHead,
+    ) -> Option<AudioStreamHandle> {
+        let stream = audio
+            .start_stream(owner.id(), clip_frame, clip_data, stream_info)
+            .ok()?;
+        self.add_sound(audio, stream, None, Some(owner), owner.movie());
+        Some(stream)
+    }
+
+    fn add_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        instance: SoundInstanceHandle,
+        sound: Option<SoundHandle>,
+        owner: Option<DisplayObject<'gc>>,
+        movie: Option<Arc<SwfMovie>>,
+    ) {
+        let transform = self.effective_sound_transform(owner);
+        if transform != SoundTransform::default() {
+            audio.set_sound_transform(instance, transform);
+        }
+        self.sounds.push(ActiveSound {
+            instance,
+            sound,
+            owner,
+            movie,
+            transform,
+        });
+    }
+
+    /// Stops a playing sound instance.
+    pub fn stop_sound(&mut self, audio: &mut dyn AudioBackend, instance: SoundInstanceHandle) {
+        audio.stop_sound(instance);
+        self.sounds.retain(|sound| sound.instance != instance);
+    }
+
+    /// Stops a playing stream sound.
+    pub fn stop_stream(&mut self, audio: &mut dyn AudioBackend, stream: AudioStreamHandle) {
+        audio.stop_stream(stream);
+        self.sounds.retain(|sound| sound.instance != stream);
+    }
+
+    /// Stops all playing instances of a sound.
+    pub fn stop_sounds_with_handle(&mut self, audio: &mut dyn AudioBackend, handle: SoundHandle) {
+        audio.stop_sounds_with_handle(handle);
+        self.sounds.retain(|sound| sound.sound != Some(handle));
+    }
+
+    /// Stops all sounds played by a display object or its children.
+    pub fn stop_sounds_with_display_object(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        display_object: DisplayObject<'gc>,
+    ) {
+        self.sounds.retain(|sound| {
+            let mut node = sound.owner;
+            while let Some(owner) = node {
+                if DisplayObject::ptr_eq(owner, display_object) {
+                    audio.stop_sound(sound.instance);
+                    return false;
+                }
+                node = owner.parent();
+            }
+            true
+        });
+    }
+
+    /// Stops all sounds started by a movie, such as when it is unloaded.
+    pub fn stop_sounds_with_movie(&mut self, audio: &mut dyn AudioBackend, movie: &Arc<SwfMovie>) {
+        self.sounds.retain(|sound| match &sound.movie {
+            Some(sound_movie) if Arc::ptr_eq(sound_movie, movie) => {
+                audio.stop_sound(sound.instance);
+                false
+            }
+            _ => true,
+        });
+    }
+
+    /// The number of sounds currently playing.
+    pub fn num_sounds(&self) -> usize {
+        self.sounds.len()
+    }
+
+    /// Stops all playing sounds.
+    pub fn stop_all_sounds(&mut self, audio: &mut dyn AudioBackend) {
+        audio.stop_all_sounds();
+        self.sounds.clear();
+    }
+
+    /// The sound transform applied to all sounds.
+    pub fn global_sound_transform(&self) -> SoundTransform {
+        self.global_sound_transform
+    }
+
+    pub fn set_global_sound_transform(&mut self, transform: SoundTransform) {
+        self.global_sound_transform = transform;
+        self.transforms_dirty = true;
+    }
+
+    /// Marks the effective transforms of all sounds as out of date.
+    /// Called whenever the sound transform of a display object changes.
+    pub fn invalidate_sound_transforms(&mut self) {
+        self.transforms_dirty = true;
+    }
+
+    /// Returns the transform that applies to sounds owned by the given display object.
+    pub fn effective_sound_transform(&self, owner: Option<DisplayObject<'gc>>) -> SoundTransform {
+        effective_sound_transform(self.global_sound_transform, owner)
+    }
+
+    /// Forgets sounds that have finished playing, and sends updated effective transforms to the
+    /// audio backend if a sound transform has changed.
+    pub fn update_sounds(&mut self, audio: &mut dyn AudioBackend) {
+        self.sounds
+            .retain(|sound| audio.is_sound_playing(sound.instance));
+
+        if self.transforms_dirty {
+            self.transforms_dirty = false;
+            let global_sound_transform = self.global_sound_transform;
+            for sound in &mut self.sounds {
+                let transform = effective_sound_transform(global_sound_transform, sound.owner);
+                if transform != sound.transform {
+                    sound.transform = transform;
+                    audio.set_sound_transform(sound.instance, transform);
+                }
+            }
+        }
+    }
+}
+
+impl<'gc> Default for AudioManager<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Multiplies the sound transforms of a display object and all of its ancestors.
+fn effective_sound_transform<'gc>(
+    global_sound_transform: SoundTransform,
+    owner: Option<DisplayObject<'gc>>,
+) -> SoundTransform {
+    let mut transform = SoundTransform::default();
+    let mut node = owner;
+    while let Some(display_object) = node {
+        transform = display_object.sound_transform() * transform;
+        node = display_object.parent();
+    }
+    global_sound_transform * transform
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm1::test_utils::with_avm;
+    use crate::backend::audio::{swf, SoundTransform};
+    use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+    use crate::tag_utils::{SwfMovie, SwfSlice};
+    use std::sync::Arc;
+
+    #[test]
+    fn parent_volume_silences_child_sounds() {
+        with_avm(19, |activation, _this| {
+            let context = &mut activation.context;
+            let parent: DisplayObject<'_> =
+                MovieClip::new(SwfSlice::empty(context.swf.clone()), context.gc_context).into();
+            let child: DisplayObject<'_> =
+                MovieClip::new(SwfSlice::empty(context.swf.clone()), context.gc_context).into();
+            child.set_parent(context.gc_context, Some(parent));
+
+            let muted = SoundTransform {
+                volume: 0.0,
+                ..Default::default()
+            };
+            parent.set_sound_transform(context, muted);
+
+            let transform = context.audio_manager.effective_sound_transform(Some(child));
+            assert_eq!(transform.volume, 0.0);
+            assert_eq!(transform.apply([0.5, -0.5]), [0.0, 0.0]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn nested_volumes_multiply() {
+        with_avm(19, |activation, _this| {
+            let context = &mut activation.context;
+            let parent: DisplayObject<'_> =
+                MovieClip::new(SwfSlice::empty(context.swf.clone()), context.gc_context).into();
+            let child: DisplayObject<'_> =
+                MovieClip::new(SwfSlice::empty(context.swf.clone()), context.gc_context).into();
+            child.set_parent(context.gc_context, Some(parent));
+
+            let half = SoundTransform {
+                volume: 0.5,
+                ..Default::default()
+            };
+            parent.set_sound_transform(context, half);
+            child.set_sound_transform(context, half);
+            context.audio_manager.set_global_sound_transform(half);
+
+            let transform = context.audio_manager.effective_sound_transform(Some(child));
+            assert_eq!(transform.volume, 0.125);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn unloading_a_movie_stops_its_sounds() {
+        with_avm(19, |activation, _this| {
+            let context = &mut activation.context;
+            let parent = Arc::new(SwfMovie::empty(19));
+            let child = Arc::new(SwfMovie::empty(19));
+            let sound = context
+                .audio
+                .register_sound(&swf::Sound {
+                    id: 1,
+                    format: swf::SoundFormat {
+                        compression: swf::AudioCompression::Uncompressed,
+                        sample_rate: 44100,
+                        is_stereo: false,
+                        is_16_bit: true,
+                    },
+                    num_samples: 0,
+                    data: vec![],
+                })
+                .unwrap();
+            let settings = swf::SoundInfo {
+                event: swf::SoundEvent::Start,
+                in_sample: None,
+                out_sample: None,
+                num_loops: 0xffff,
+                envelope: None,
+            };
+
+            for movie in &[&parent, &child, &child] {
+                context.audio_manager.start_sound(
+                    context.audio,
+                    sound,
+                    &settings,
+                    None,
+                    Some(Arc::clone(movie)),
+                );
+            }
+            assert_eq!(context.audio_manager.num_sounds(), 3);
+
+            context
+                .audio_manager
+                .stop_sounds_with_movie(context.audio, &child);
+            assert_eq!(context.audio_manager.num_sounds(), 1);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn pan_routes_fully_left_or_right() {
+        let mut left = SoundTransform::default();
+        left.set_pan(-1.0);
+        assert_eq!(left.apply([0.5, 0.25]), [0.5, 0.0]);
+        assert_eq!(left.pan(), -1.0);
+
+        let mut right = SoundTransform::default();
+        right.set_pan(1.0);
+        assert_eq!(right.apply([0.5, 0.25]), [0.0, 0.25]);
+        assert_eq!(right.pan(), 1.0);
+
+        // A parent's pan applies on top of its child's.
+        assert_eq!((right * left).apply([0.5, 0.25]), [0.0, 0.0]);
+    }
+}
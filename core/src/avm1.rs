@@ -11,7 +11,7 @@ use crate::tag_utils::SwfSlice;
 
 #[cfg(test)]
 #[macro_use]
-mod test_utils;
+pub(crate) mod test_utils;
 
 pub mod activation;
 pub mod debug;
@@ -23,7 +23,6 @@ pub mod object;
 mod property;
 mod scope;
 mod string;
-mod timer;
 mod value;
 
 #[cfg(test)]
@@ -41,7 +40,6 @@ pub use object::{Object, ObjectPtr, TObject};
 use scope::Scope;
 use smallvec::alloc::borrow::Cow;
 pub use string::AvmString;
-pub use timer::Timers;
 pub use value::Value;
 
 macro_rules! avm_debug {
@@ -163,6 +161,11 @@ impl<'gc> Avm1<'gc> {
     /// Add a stack frame that executes code in timeline scope
     ///
     /// This creates a new frame stack.
+    /// Sets the version of Flash Player that is reported to movies.
+    pub fn set_player_version(&mut self, player_version: u8) {
+        self.player_version = player_version;
+    }
+
     pub fn run_stack_frame_for_action<S: Into<Cow<'static, str>>>(
         active_clip: DisplayObject<'gc>,
         name: S,
@@ -391,6 +394,11 @@ impl<'gc> Avm1<'gc> {
         }
     }
 
+    /// Whether the AVM has been halted, and no longer executes actions.
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
     fn push(&mut self, value: impl Into<Value<'gc>>) {
         let value = value.into();
         avm_debug!(self, "Stack push {}: {:?}", self.stack.len(), value);
@@ -409,6 +417,16 @@ impl<'gc> Avm1<'gc> {
         value
     }
 
+    /// The number of values currently on the operand stack.
+    fn stack_len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Discard any values above the given stack depth.
+    fn truncate_stack(&mut self, len: usize) {
+        self.stack.truncate(len);
+    }
+
     /// Obtain the value of `_global`.
     pub fn global_object(&self) -> Value<'gc> {
         Value::Object(self.globals)
@@ -424,6 +442,11 @@ impl<'gc> Avm1<'gc> {
         &self.prototypes
     }
 
+    /// Obtain the functions used to make an object an `AsBroadcaster`.
+    pub fn broadcaster_functions(&self) -> BroadcasterFunctions<'gc> {
+        self.broadcaster_functions
+    }
+
     pub fn max_recursion_depth(&self) -> u16 {
         self.max_recursion_depth
     }
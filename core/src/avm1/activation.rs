@@ -10,6 +10,8 @@ use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
 use crate::ecma_conversions::f64_to_wrapping_u32;
+use crate::loader::encode_form_data;
+use crate::stage::StageQuality;
 use crate::tag_utils::SwfSlice;
 use crate::{avm_error, avm_warn};
 use enumset::EnumSet;
@@ -22,7 +24,6 @@ use std::cell::{Ref, RefMut};
 use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::{Action, CatchVar, Function, TryBlock};
-use url::form_urlencoded;
 
 macro_rules! avm_debug {
     ($avm: expr, $($arg:tt)*) => (
@@ -927,7 +928,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value = self.context.avm1.pop();
         let name_val = self.context.avm1.pop();
         let name = name_val.coerce_to_string(self)?;
-        let scope = self.scope_cell();
+        let scope = Scope::nearest_non_with_scope(self.scope_cell());
         scope
             .write(self.context.gc_context)
             .locals()
@@ -940,7 +941,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         // Otherwise, the property is unchanged.
         let name_val = self.context.avm1.pop();
         let name = name_val.coerce_to_string(self)?;
-        let scope = self.scope_cell();
+        let scope = Scope::nearest_non_with_scope(self.scope_cell());
         if !scope.read().locals().has_property(self, &name) {
             scope
                 .write(self.context.gc_context)
@@ -1128,7 +1129,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn action_get_time(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
-        let time = self.context.navigator.time_since_launch().as_millis() as u32;
+        let time = self.context.timers.time();
         self.context.avm1.push(time);
         Ok(FrameControl::Continue)
     }
@@ -1172,7 +1173,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
 
         if let Some(fscommand) = fscommand::parse(url) {
-            fscommand::handle(fscommand, self)?;
+            fscommand::handle(fscommand, target, self)?;
         } else {
             self.context
                 .navigator
@@ -1195,7 +1196,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let url = url_val.coerce_to_string(self)?;
 
         if let Some(fscommand) = fscommand::parse(&url) {
-            fscommand::handle(fscommand, self)?;
+            let args = target.coerce_to_string(self)?;
+            fscommand::handle(fscommand, &args, self)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -1218,7 +1220,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                     .unwrap()
                     .object()
                     .coerce_to_object(self);
-                let (url, opts) = self.locals_into_request_options(
+                let vars_object = self.target_clip_object();
+                let (url, opts) = self.object_into_request_options(
+                    vars_object,
                     Cow::Borrowed(&url),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
@@ -1235,7 +1239,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return Ok(FrameControl::Continue);
         } else if is_target_sprite {
             if let Some(clip_target) = clip_target {
-                let (url, opts) = self.locals_into_request_options(
+                let vars_object = self.target_clip_object();
+                let (url, opts) = self.object_into_request_options(
+                    vars_object,
                     Cow::Borrowed(&url),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
@@ -1278,7 +1284,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return Ok(FrameControl::Continue);
         } else {
             let vars = match NavigationMethod::from_send_vars_method(swf_method) {
-                Some(method) => Some((method, self.locals_into_form_values())),
+                Some(method) => {
+                    let vars_object = self.target_clip_object();
+                    Some((method, self.object_into_form_values(vars_object)))
+                }
                 None => None,
             };
 
@@ -1895,7 +1904,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn action_stop_sounds(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
-        self.context.audio.stop_all_sounds();
+        self.context
+            .audio_manager
+            .stop_all_sounds(self.context.audio);
         Ok(FrameControl::Continue)
     }
 
@@ -2014,7 +2025,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn toggle_quality(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Noop for now? Could chang anti-aliasing on render backend.
+        // Toggles between low and high quality, as with `_highquality`.
+        *self.context.quality = match *self.context.quality {
+            StageQuality::Low => StageQuality::High,
+            _ => StageQuality::Low,
+        };
         Ok(FrameControl::Continue)
     }
 
@@ -2135,37 +2150,41 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         try_block: &TryBlock,
         parent_data: &SwfSlice,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        let stack_len = self.context.avm1.stack_len();
         let mut result = self.run_actions(parent_data.to_subslice(try_block.try_actions).unwrap());
 
         if let Some((catch_vars, actions)) = &try_block.catch {
-            if let Err(Error::ThrownValue(value)) = &result {
-                let mut activation = Activation::from_action(
-                    self.context.reborrow(),
-                    self.id.child("[Catch]"),
-                    self.swf_version,
-                    self.scope,
-                    self.constant_pool,
-                    self.base_clip,
-                    self.this,
-                    self.arguments,
-                );
-
-                match catch_vars {
-                    CatchVar::Var(name) => activation.set_variable(name, value.to_owned())?,
-                    CatchVar::Register(id) => {
-                        activation.set_current_register(*id, value.to_owned())
+            result = match result {
+                Err(Error::ThrownValue(value)) => {
+                    // Drop anything the try block left behind when it was interrupted.
+                    self.context.avm1.truncate_stack(stack_len);
+
+                    // The catch block shares the scope and registers of the try block,
+                    // so that a caught value stored in a register is visible to the
+                    // enclosing function.
+                    let scope = self.scope;
+                    let mut activation = self.with_new_scope("[Catch]", scope);
+                    match catch_vars {
+                        CatchVar::Var(name) => activation.set_variable(name, value)?,
+                        CatchVar::Register(id) => activation.set_current_register(*id, value),
                     }
-                }
 
-                result = activation.run_actions(parent_data.to_subslice(actions).unwrap());
-            }
+                    activation.run_actions(parent_data.to_subslice(actions).unwrap())
+                }
+                result => result,
+            };
         }
 
         if let Some(actions) = try_block.finally {
-            if let ReturnType::Explicit(value) =
-                self.run_actions(parent_data.to_subslice(actions).unwrap())?
-            {
-                return Ok(FrameControl::Return(ReturnType::Explicit(value)));
+            // The finally block runs whether or not the try and catch blocks threw,
+            // unless the movie has been halted.
+            let is_halting = matches!(&result, Err(e) if e.is_halting());
+            if !is_halting {
+                if let ReturnType::Explicit(value) =
+                    self.run_actions(parent_data.to_subslice(actions).unwrap())?
+                {
+                    return Ok(FrameControl::Return(ReturnType::Explicit(value)));
+                }
             }
         }
 
@@ -2208,14 +2227,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     /// This is necessary to support form submission from Flash via a couple of
     /// legacy methods, such as the `ActionGetURL2` opcode or `getURL` function.
     ///
+    /// Only the object's own properties are sent, in the order they were defined.
+    /// Functions are left out.
+    ///
     /// WARNING: This does not support user defined virtual properties!
     pub fn object_into_form_values(&mut self, object: Object<'gc>) -> IndexMap<String, String> {
         let mut form_values = IndexMap::new();
-        let keys = object.get_keys(self);
+        let mut keys = object.get_keys(self);
+        keys.retain(|k| object.has_own_property(self, k));
+        // `get_keys` lists the newest properties first.
+        keys.reverse();
 
         for k in keys {
             let v = object.get(&k, self);
 
+            if let Ok(Value::Object(o)) = v {
+                if o.as_executable().is_some() {
+                    continue;
+                }
+            }
+
             //TODO: What happens if an error occurs inside a virtual property?
             form_values.insert(
                 k,
@@ -2232,6 +2263,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     /// Construct request options for a fetch operation that may sends object properties as
     /// form data in the request body or URL.
+    ///
+    /// The form data is encoded in the system codepage if `System.useCodepage` is set.
     pub fn object_into_request_options<'c>(
         &mut self,
         object: Object<'gc>,
@@ -2241,9 +2274,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         match method {
             Some(method) => {
                 let vars = self.object_into_form_values(object);
-                let qstring = form_urlencoded::Serializer::new(String::new())
-                    .extend_pairs(vars.iter())
-                    .finish();
+                let qstring = encode_form_data(vars.iter(), self.context.system.use_codepage);
 
                 match method {
                     NavigationMethod::GET if url.find('?').is_none() => (
@@ -2267,28 +2298,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
     }
 
-    /// Convert the current locals pool into a set of form values.
-    ///
-    /// This is necessary to support form submission from Flash via a couple of
-    /// legacy methods, such as the `ActionGetURL2` opcode or `getURL` function.
+    /// The object holding the variables of the current target clip.
     ///
-    /// WARNING: This does not support user defined virtual properties!
-    pub fn locals_into_form_values(&mut self) -> IndexMap<String, String> {
-        let scope = self.scope_cell();
-        let locals = scope.read().locals_cell();
-        self.object_into_form_values(locals)
-    }
-
-    /// Construct request options for a fetch operation that may send locals as
-    /// form data in the request body or URL.
-    pub fn locals_into_request_options<'c>(
-        &mut self,
-        url: Cow<'c, str>,
-        method: Option<NavigationMethod>,
-    ) -> (Cow<'c, str>, RequestOptions) {
-        let scope = self.scope_cell();
-        let locals = scope.read().locals_cell();
-        self.object_into_request_options(locals, url, method)
+    /// `ActionGetURL2` sends these variables as form data.
+    pub fn target_clip_object(&mut self) -> Object<'gc> {
+        self.target_clip_or_root().object().coerce_to_object(self)
     }
 
     /// Resolves a target value to a display object, relative to a starting display object.
@@ -14,12 +14,19 @@ pub fn parse(url: &str) -> Option<&str> {
     }
 }
 
-/// TODO: FSCommand URL handling
+/// Forward an FSCommand to the frontend's FSCommand provider.
 pub fn handle<'gc>(
     fscommand: &str,
+    args: &str,
     activation: &mut Activation<'_, 'gc, '_>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unhandled FSCommand: {}", fscommand);
+    if !activation
+        .context
+        .external_interface
+        .invoke_fs_command(fscommand, args)
+    {
+        avm_warn!(activation, "Unhandled FSCommand: {}", fscommand);
+    }
 
     //This should be an error.
     Ok(())
@@ -254,6 +254,19 @@ impl<'gc> Executable<'gc> {
                     callee.into(),
                     DontEnum.into(),
                 );
+                // The caller is the function whose activation called us, or
+                // `null` when called from a frame or event script.
+                let caller = if let Some(caller_arguments) = activation.arguments {
+                    caller_arguments.get("callee", activation)?
+                } else {
+                    Value::Null
+                };
+                arguments.define_value(
+                    activation.context.gc_context,
+                    "caller",
+                    caller,
+                    DontEnum.into(),
+                );
 
                 if !af.suppress_arguments {
                     for i in 0..args.len() {
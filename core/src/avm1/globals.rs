@@ -3,6 +3,7 @@ use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::display_object::TDisplayObject;
 use enumset::EnumSet;
 use gc_arena::Collect;
 use gc_arena::MutationContext;
@@ -26,15 +27,20 @@ mod external_interface;
 mod function;
 mod key;
 mod load_vars;
+mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+mod net_connection;
+mod net_stream;
 pub(crate) mod number;
 mod object;
 mod point;
+mod print_job;
 mod rectangle;
+mod selection;
 pub(crate) mod shared_object;
 mod sound;
 mod stage;
@@ -46,7 +52,9 @@ pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
 mod transform;
+pub(crate) mod video;
 mod xml;
+mod xml_socket;
 
 pub fn random<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -239,11 +247,13 @@ pub fn create_timer<'gc>(
     is_timeout: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
     // `setInterval` was added in Flash Player 6 but is not version-gated.
-    use crate::avm1::timer::TimerCallback;
+    use crate::timer::TimerCallback;
     let (callback, i) = match args.get(0) {
-        Some(Value::Object(o)) if o.as_executable().is_some() => (TimerCallback::Function(*o), 1),
+        Some(Value::Object(o)) if o.as_executable().is_some() => {
+            (TimerCallback::Avm1Function(*o), 1)
+        }
         Some(Value::Object(o)) => (
-            TimerCallback::Method {
+            TimerCallback::Avm1Method {
                 this: *o,
                 method_name: args
                     .get(1)
@@ -266,10 +276,11 @@ pub fn create_timer<'gc>(
         vec![]
     };
 
+    let movie = activation.base_clip().movie();
     let id = activation
         .context
         .timers
-        .add_timer(callback, interval, params, is_timeout);
+        .add_timer(callback, interval, params, is_timeout, movie);
 
     Ok(id.into())
 }
@@ -342,6 +353,7 @@ pub struct SystemPrototypes<'gc> {
     pub blur_filter: Object<'gc>,
     pub blur_filter_constructor: Object<'gc>,
     pub date: Object<'gc>,
+    pub video: Object<'gc>,
 }
 
 /// Initialize default global scope and builtins for an AVM1 instance.
@@ -386,6 +398,17 @@ pub fn create_globals<'gc>(
         boolean::create_proto(gc_context, object_proto, function_proto);
     let load_vars_proto: Object<'gc> =
         load_vars::create_proto(gc_context, object_proto, function_proto);
+    let xml_socket_proto: Object<'gc> =
+        xml_socket::create_proto(gc_context, object_proto, function_proto);
+    let local_connection_proto: Object<'gc> =
+        local_connection::create_proto(gc_context, object_proto, function_proto);
+    let print_job_proto: Object<'gc> =
+        print_job::create_proto(gc_context, object_proto, function_proto);
+    let video_proto: Object<'gc> = video::create_proto(gc_context, object_proto, function_proto);
+    let net_connection_proto: Object<'gc> =
+        net_connection::create_proto(gc_context, object_proto, function_proto);
+    let net_stream_proto: Object<'gc> =
+        net_stream::create_proto(gc_context, object_proto, function_proto);
     let matrix_proto: Object<'gc> = matrix::create_proto(gc_context, object_proto, function_proto);
     let point_proto: Object<'gc> = point::create_proto(gc_context, object_proto, function_proto);
     let rectangle_proto: Object<'gc> =
@@ -492,6 +515,42 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         xml_proto,
     );
+    let xml_socket = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(xml_socket::constructor),
+        Some(function_proto),
+        xml_socket_proto,
+    );
+    let local_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(local_connection::constructor),
+        Some(function_proto),
+        local_connection_proto,
+    );
+    let print_job = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(print_job::constructor),
+        Some(function_proto),
+        print_job_proto,
+    );
+    let video = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(video::constructor),
+        Some(function_proto),
+        video_proto,
+    );
+    let net_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_connection::constructor),
+        Some(function_proto),
+        net_connection_proto,
+    );
+    let net_stream = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_stream::constructor),
+        Some(function_proto),
+        net_stream_proto,
+    );
     let string = string::create_string_object(gc_context, string_proto, Some(function_proto));
     let number = number::create_number_object(gc_context, number_proto, Some(function_proto));
     let boolean = boolean::create_boolean_object(gc_context, boolean_proto, Some(function_proto));
@@ -610,6 +669,22 @@ pub fn create_globals<'gc>(
     );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), DontEnum.into());
     globals.define_value(gc_context, "XML", xml.into(), DontEnum.into());
+    globals.define_value(gc_context, "XMLSocket", xml_socket.into(), DontEnum.into());
+    globals.define_value(
+        gc_context,
+        "LocalConnection",
+        local_connection.into(),
+        DontEnum.into(),
+    );
+    globals.define_value(gc_context, "PrintJob", print_job.into(), DontEnum.into());
+    globals.define_value(gc_context, "Video", video.into(), DontEnum.into());
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        net_connection.into(),
+        DontEnum.into(),
+    );
+    globals.define_value(gc_context, "NetStream", net_stream.into(), DontEnum.into());
     globals.define_value(gc_context, "String", string.into(), DontEnum.into());
     globals.define_value(gc_context, "Number", number.into(), DontEnum.into());
     globals.define_value(gc_context, "Boolean", boolean.into(), DontEnum.into());
@@ -710,6 +785,18 @@ pub fn create_globals<'gc>(
         )),
         DontEnum.into(),
     );
+    globals.define_value(
+        gc_context,
+        "Selection",
+        Value::Object(selection::create_selection_object(
+            gc_context,
+            Some(object_proto),
+            Some(function_proto),
+            broadcaster_functions,
+            array_proto,
+        )),
+        DontEnum.into(),
+    );
     globals.define_value(
         gc_context,
         "Stage",
@@ -836,6 +923,7 @@ pub fn create_globals<'gc>(
             blur_filter: blur_filter_proto,
             blur_filter_constructor: blur_filter,
             date: date_proto,
+            video: video_proto,
         },
         globals.into(),
         broadcaster_functions,
@@ -125,16 +125,19 @@ pub fn broadcast_internal<'gc>(
     let listeners = this.get("_listeners", activation)?;
 
     if let Value::Object(listeners) = listeners {
-        let len = listeners.length();
-        for i in 0..len {
-            let listener = listeners.array_element(i);
+        // Listeners may add or remove listeners while handling the message, which
+        // doesn't affect who receives this broadcast.
+        let listeners: Vec<_> = (0..listeners.length())
+            .map(|i| listeners.array_element(i))
+            .collect();
 
+        for listener in &listeners {
             if let Value::Object(listener) = listener {
                 listener.call_method(method_name, call_args, activation)?;
             }
         }
 
-        Ok(len > 0)
+        Ok(!listeners.is_empty())
     } else {
         Ok(false)
     }
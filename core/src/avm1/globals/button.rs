@@ -14,6 +14,7 @@ pub fn create_proto<'gc>(
     let object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
+    display_object::define_scaling_grid_property(gc_context, object, fn_proto);
 
     object.into()
 }
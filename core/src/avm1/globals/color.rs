@@ -137,7 +137,7 @@ fn set_rgb<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(target) = target(activation, this)? {
-        let mut color_transform = target.color_transform_mut(activation.context.gc_context);
+        // Coerce before borrowing the transform, as `valueOf` may run arbitrary code.
         let rgb = args
             .get(0)
             .unwrap_or(&Value::Undefined)
@@ -146,6 +146,7 @@ fn set_rgb<'gc>(
         let g = (((rgb >> 8) & 0xff) as f32) / 255.0;
         let b = ((rgb & 0xff) as f32) / 255.0;
 
+        let mut color_transform = target.color_transform_mut(activation.context.gc_context);
         color_transform.r_mult = 0.0;
         color_transform.g_mult = 0.0;
         color_transform.b_mult = 0.0;
@@ -164,52 +165,68 @@ fn set_transform<'gc>(
     // TODO: These map from the 0-100% range for mult and the -255-255 range for addition used by ActionScript
     // to the 16-bit range used by the internal representations of the Flash Player.
     // This will get slightly simpler when we change ColorTransform to the proper representation (see #193).
-    fn set_color_mult<'gc>(
+    fn color_mult<'gc>(
         activation: &mut Activation<'_, 'gc, '_>,
         transform: Object<'gc>,
         property: &str,
-        out: &mut f32,
-    ) -> Result<(), Error<'gc>> {
+    ) -> Result<Option<f32>, Error<'gc>> {
         // The parameters are set only if the property exists on the object itself (prototype excluded).
         if transform.has_own_property(activation, property) {
             let n = transform
                 .get(property, activation)?
                 .coerce_to_f64(activation)?;
-            *out = f32::from(crate::ecma_conversions::f64_to_wrapping_i16(n * 2.56)) / 256.0
+            Ok(Some(
+                f32::from(crate::ecma_conversions::f64_to_wrapping_i16(n * 2.56)) / 256.0,
+            ))
+        } else {
+            Ok(None)
         }
-        Ok(())
     }
 
-    fn set_color_add<'gc>(
+    fn color_add<'gc>(
         activation: &mut Activation<'_, 'gc, '_>,
         transform: Object<'gc>,
         property: &str,
-        out: &mut f32,
-    ) -> Result<(), Error<'gc>> {
+    ) -> Result<Option<f32>, Error<'gc>> {
         // The parameters are set only if the property exists on the object itself (prototype excluded).
         if transform.has_own_property(activation, property) {
             let n = transform
                 .get(property, activation)?
                 .coerce_to_f64(activation)?;
-            *out = f32::from(crate::ecma_conversions::f64_to_wrapping_i16(n)) / 255.0
+            Ok(Some(
+                f32::from(crate::ecma_conversions::f64_to_wrapping_i16(n)) / 255.0,
+            ))
+        } else {
+            Ok(None)
         }
-        Ok(())
     }
 
     if let Some(target) = target(activation, this)? {
-        let mut color_transform = target.color_transform_mut(activation.context.gc_context);
         let transform = args
             .get(0)
             .unwrap_or(&Value::Undefined)
             .coerce_to_object(activation);
-        set_color_mult(activation, transform, "ra", &mut color_transform.r_mult)?;
-        set_color_mult(activation, transform, "ga", &mut color_transform.g_mult)?;
-        set_color_mult(activation, transform, "ba", &mut color_transform.b_mult)?;
-        set_color_mult(activation, transform, "aa", &mut color_transform.a_mult)?;
-        set_color_add(activation, transform, "rb", &mut color_transform.r_add)?;
-        set_color_add(activation, transform, "gb", &mut color_transform.g_add)?;
-        set_color_add(activation, transform, "bb", &mut color_transform.b_add)?;
-        set_color_add(activation, transform, "ab", &mut color_transform.a_add)?;
+
+        // Read every component up front; the getters may run arbitrary code, including code
+        // that inspects this clip's transform, so we can't hold a borrow on it yet.
+        let r_mult = color_mult(activation, transform, "ra")?;
+        let g_mult = color_mult(activation, transform, "ga")?;
+        let b_mult = color_mult(activation, transform, "ba")?;
+        let a_mult = color_mult(activation, transform, "aa")?;
+        let r_add = color_add(activation, transform, "rb")?;
+        let g_add = color_add(activation, transform, "gb")?;
+        let b_add = color_add(activation, transform, "bb")?;
+        let a_add = color_add(activation, transform, "ab")?;
+
+        let mut color_transform = target.color_transform_mut(activation.context.gc_context);
+        color_transform.r_mult = r_mult.unwrap_or(color_transform.r_mult);
+        color_transform.g_mult = g_mult.unwrap_or(color_transform.g_mult);
+        color_transform.b_mult = b_mult.unwrap_or(color_transform.b_mult);
+        color_transform.a_mult = a_mult.unwrap_or(color_transform.a_mult);
+        color_transform.r_add = r_add.unwrap_or(color_transform.r_add);
+        color_transform.g_add = g_add.unwrap_or(color_transform.g_add);
+        color_transform.b_add = b_add.unwrap_or(color_transform.b_add);
+        color_transform.a_add = a_add.unwrap_or(color_transform.a_add);
     }
 
     Ok(Value::Undefined)
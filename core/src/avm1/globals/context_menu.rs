@@ -4,6 +4,9 @@ use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::Object;
 use crate::avm1::{ScriptObject, Value};
+use crate::avm_warn;
+use crate::context_menu::{self, ContextMenuCallback, ContextMenuItem, ContextMenuState};
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
 use gc_arena::MutationContext;
 
 pub fn constructor<'gc>(
@@ -130,6 +133,148 @@ pub fn hide_builtin_items<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Build the context menu for an object, given the `ContextMenu` found on it or
+/// one of its parents.
+///
+/// The menu's `onSelect` handler is called first, so that the movie may update
+/// the items before they are shown. Custom items are listed above the
+/// built-in player items.
+pub fn make_context_menu_state<'gc>(
+    menu: Option<(DisplayObject<'gc>, Object<'gc>)>,
+    root: Option<MovieClip<'gc>>,
+    show_built_in: bool,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> ContextMenuState<'gc> {
+    let mut state = ContextMenuState::new();
+    let mut built_in_items = None;
+
+    if let Some((target, menu)) = menu {
+        if let Ok(Value::Object(callback)) = menu.get("onSelect", activation) {
+            let _ = callback.call(
+                "[Context Menu Callback]",
+                activation,
+                menu,
+                None,
+                &[target.object(), menu.into()],
+            );
+        }
+
+        if let Ok(Value::Object(items)) = menu.get("builtInItems", activation) {
+            built_in_items = Some(items);
+        }
+
+        if let Ok(Value::Object(custom_items)) = menu.get("customItems", activation) {
+            let mut custom_count = 0;
+            for i in 0..custom_items.length() {
+                if custom_count >= context_menu::MAX_CUSTOM_ITEMS {
+                    break;
+                }
+
+                if let Value::Object(item) = custom_items.array_element(i) {
+                    if let Some((info, callback)) = make_custom_item(target, item, activation) {
+                        state.push(info, callback);
+                        custom_count += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let root = match root {
+        Some(root) if show_built_in && root.total_frames() > 1 => root,
+        _ => return state,
+    };
+
+    let is_built_in_shown = |name: &str, activation: &mut Activation<'_, 'gc, '_>| {
+        let swf_version = activation.current_swf_version();
+        built_in_items
+            .map(|items| {
+                items
+                    .get(name, activation)
+                    .map(|v| v.as_bool(swf_version))
+                    .unwrap_or(true)
+            })
+            .unwrap_or(true)
+    };
+
+    let mut separator_before = !state.info().is_empty();
+    let mut built_ins = vec![];
+    if is_built_in_shown("play", activation) {
+        built_ins.push(("Play", root.playing(), ContextMenuCallback::Play));
+    }
+    if is_built_in_shown("rewind", activation) {
+        built_ins.push(("Rewind", false, ContextMenuCallback::Rewind));
+    }
+    if is_built_in_shown("forward_back", activation) {
+        built_ins.push(("Forward", false, ContextMenuCallback::Forward));
+        built_ins.push(("Back", false, ContextMenuCallback::Back));
+    }
+
+    for (caption, checked, callback) in built_ins {
+        state.push(
+            ContextMenuItem {
+                caption: caption.to_string(),
+                enabled: true,
+                separator_before,
+                checked,
+            },
+            callback,
+        );
+        separator_before = false;
+    }
+
+    state
+}
+
+/// Read a custom `ContextMenuItem`, returning `None` if it should not be
+/// shown.
+fn make_custom_item<'gc>(
+    target: DisplayObject<'gc>,
+    item: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Option<(ContextMenuItem, ContextMenuCallback<'gc>)> {
+    let swf_version = activation.current_swf_version();
+    let visible = item.get("visible", activation).ok()?.as_bool(swf_version);
+    if !visible {
+        return None;
+    }
+
+    let caption = item
+        .get("caption", activation)
+        .ok()?
+        .coerce_to_string(activation)
+        .ok()?
+        .to_string();
+    if !context_menu::is_valid_custom_caption(&caption) {
+        avm_warn!(activation, "Ignoring context menu item \"{}\"", caption);
+        return None;
+    }
+
+    let enabled = item.get("enabled", activation).ok()?.as_bool(swf_version);
+    let separator_before = item
+        .get("separatorBefore", activation)
+        .ok()?
+        .as_bool(swf_version);
+    let callback = match item.get("onSelect", activation).ok()? {
+        Value::Object(callback) => callback,
+        _ => return None,
+    };
+
+    Some((
+        ContextMenuItem {
+            caption,
+            enabled,
+            separator_before,
+            checked: false,
+        },
+        ContextMenuCallback::Avm1 {
+            target,
+            item,
+            callback,
+        },
+    ))
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
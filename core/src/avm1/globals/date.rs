@@ -4,7 +4,9 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::date_object::DateObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, TObject, Value};
-use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, Offset, TimeZone, Timelike, Utc,
+};
 use enumset::EnumSet;
 use gc_arena::{Collect, MutationContext};
 use num_traits::ToPrimitive;
@@ -18,7 +20,7 @@ macro_rules! implement_local_getters {
                 |activation: &mut Activation<'_, 'gc, '_>, this, _args| -> Result<Value<'gc>, Error<'gc>> {
                     if let Some(this) = this.as_date_object() {
                         if let Some(date) = this.date_time() {
-                            let local = date.with_timezone(&activation.context.locale.get_timezone());
+                            let local = date.with_timezone(&local_timezone(activation, Some(date)));
                             Ok($fn(&local).into())
                         } else {
                             Ok(NAN.into())
@@ -79,6 +81,14 @@ macro_rules! implement_utc_getters {
     };
 }
 
+/// The offset of the host timezone at the given date, or the current offset for an invalid date.
+fn local_timezone(activation: &Activation<'_, '_, '_>, date: Option<DateTime<Utc>>) -> FixedOffset {
+    match date {
+        Some(date) => activation.context.locale.get_timezone_at(date),
+        None => activation.context.locale.get_timezone(),
+    }
+}
+
 enum YearType {
     Full,
     Adjust(Box<dyn Fn(i64) -> i64>),
@@ -120,6 +130,7 @@ struct DateAdjustment<
     second: Option<Option<f64>>,
     millisecond: Option<Option<f64>>,
     ignore_next: bool,
+    is_local: bool,
 }
 
 impl<'builder, 'activation_a, 'gc, 'gc_context, T: TimeZone>
@@ -141,6 +152,20 @@ impl<'builder, 'activation_a, 'gc, 'gc_context, T: TimeZone>
             second: None,
             millisecond: None,
             ignore_next: false,
+            is_local: false,
+        }
+    }
+
+    /// Adjusts a date using local time fields. `timezone` should be the offset of the host
+    /// timezone at the date being adjusted; the result is corrected if a daylight saving time
+    /// change lies in between.
+    fn new_local(
+        activation: &'builder mut Activation<'activation_a, 'gc, 'gc_context>,
+        timezone: &'builder T,
+    ) -> Self {
+        Self {
+            is_local: true,
+            ..Self::new(activation, timezone)
         }
     }
 
@@ -534,7 +559,14 @@ impl<'builder, 'activation_a, 'gc, 'gc_context, T: TimeZone>
                 .and_hms_opt(0, 0, 0)
                 .map(|date| date.checked_add_signed(duration))
             {
-                return Some(result.with_timezone(&Utc));
+                let offset = result.offset().fix();
+                let result = result.with_timezone(&Utc);
+                if self.is_local {
+                    let new_offset = self.activation.context.locale.get_timezone_at(result);
+                    let correction = offset.local_minus_utc() - new_offset.local_minus_utc();
+                    return result.checked_add_signed(Duration::seconds(correction.into()));
+                }
+                return Some(result);
             }
         }
 
@@ -574,7 +606,7 @@ fn constructor<'gc>(
                 Some(timezone.ymd(0, 1, 1).and_hms(0, 0, 0).into()),
             );
 
-            DateAdjustment::new(activation, &timezone)
+            DateAdjustment::new_local(activation, &timezone)
                 .year_opt(args.get(0))?
                 .month_opt(args.get(1))?
                 .day_opt(args.get(2))?
@@ -644,7 +676,7 @@ fn to_string<'gc>(
     let date = this.date_time();
 
     if let Some(date) = date {
-        let local = date.with_timezone(&activation.context.locale.get_timezone());
+        let local = date.with_timezone(&local_timezone(activation, Some(date)));
         Ok(AvmString::new(
             activation.context.gc_context,
             local.format("%a %b %-d %T GMT%z %-Y").to_string(),
@@ -661,7 +693,7 @@ fn get_timezone_offset<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let date = if let Some(date) = this.date_time() {
-        date.with_timezone(&activation.context.locale.get_timezone())
+        date.with_timezone(&local_timezone(activation, Some(date)))
     } else {
         return Ok(NAN.into());
     };
@@ -680,8 +712,8 @@ fn set_date<'gc>(
         this.set_date_time(activation.context.gc_context, None);
         Ok(NAN.into())
     } else {
-        let timezone = activation.context.locale.get_timezone();
-        let timestamp = DateAdjustment::new(activation, &timezone)
+        let timezone = local_timezone(activation, this.date_time());
+        let timestamp = DateAdjustment::new_local(activation, &timezone)
             .day(args.get(0))?
             .apply(this);
         Ok(timestamp.into())
@@ -709,8 +741,8 @@ fn set_year<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .year(args.get(0))?
         .adjust_year(|year| {
             if year >= 0 && year < 100 {
@@ -728,8 +760,8 @@ fn set_hours<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .hour(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -754,8 +786,8 @@ fn set_milliseconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .millisecond(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -777,8 +809,8 @@ fn set_minutes<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .minute_or(args.get(0), -2147483648.0)?
         .apply(this);
     Ok(timestamp.into())
@@ -802,8 +834,8 @@ fn set_month<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .month_or(args.get(0), 0.0)?
         .day_opt(args.get(1))?
         .apply(this);
@@ -827,8 +859,8 @@ fn set_seconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .second(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -871,8 +903,8 @@ fn set_full_year<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timezone = local_timezone(activation, this.date_time());
+    let timestamp = DateAdjustment::new_local(activation, &timezone)
         .year(args.get(0))?
         .month_opt(args.get(1))?
         .day_opt(args.get(2))?
@@ -2,12 +2,13 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
-use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::function::{Executable, FunctionObject, NativeFunction};
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::display_object::{DisplayObject, TDisplayObject};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
+use swf::{Rectangle, Twips};
 
 /// Depths used/returned by ActionScript are offset by this amount from depths used inside the SWF/by the VM.
 /// The depth of objects placed on the timeline in the Flash IDE start from 0 in the SWF,
@@ -107,6 +108,196 @@ pub fn define_display_object_proto<'gc>(
         )),
         DontDelete | ReadOnly | DontEnum,
     );
+
+    let tab_properties: &[(&str, NativeFunction<'gc>, NativeFunction<'gc>)] = &[
+        ("tabEnabled", tab_enabled, set_tab_enabled),
+        ("tabIndex", tab_index, set_tab_index),
+        ("tabChildren", tab_children, set_tab_children),
+    ];
+    for &(name, getter, setter) in tab_properties {
+        object.add_property(
+            gc_context,
+            name,
+            FunctionObject::function(
+                gc_context,
+                Executable::Native(getter),
+                Some(fn_proto),
+                fn_proto,
+            ),
+            Some(FunctionObject::function(
+                gc_context,
+                Executable::Native(setter),
+                Some(fn_proto),
+                fn_proto,
+            )),
+            DontDelete | DontEnum,
+        );
+    }
+}
+
+/// Adds the `scale9Grid` property to the given prototype.
+///
+/// Only movie clips and buttons can be scaled with a grid.
+pub fn define_scaling_grid_property<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    object: ScriptObject<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    object.add_property(
+        gc_context,
+        "scale9Grid",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(scale_9_grid),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_scale_9_grid),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        DontDelete | DontEnum,
+    );
+}
+
+/// Implements the `scale9Grid` getter, which returns a new `Rectangle`, or `undefined` if
+/// there is no grid.
+pub fn scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(rect) = this
+        .as_display_object()
+        .and_then(|display_object| display_object.scaling_grid())
+    {
+        let args = [
+            Value::Number(rect.x_min.to_pixels()),
+            Value::Number(rect.y_min.to_pixels()),
+            Value::Number((rect.x_max - rect.x_min).to_pixels()),
+            Value::Number((rect.y_max - rect.y_min).to_pixels()),
+        ];
+        let constructor = activation.context.avm1.prototypes.rectangle_constructor;
+        return Ok(constructor.construct(activation, &args)?.into());
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements the `scale9Grid` setter. The grid is copied from any object with `x`, `y`,
+/// `width` and `height` properties; `undefined` or `null` removes it.
+pub fn set_scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let rect = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Object(object) => {
+                let x = object.get("x", activation)?.coerce_to_f64(activation)?;
+                let y = object.get("y", activation)?.coerce_to_f64(activation)?;
+                let width = object.get("width", activation)?.coerce_to_f64(activation)?;
+                let height = object
+                    .get("height", activation)?
+                    .coerce_to_f64(activation)?;
+                if [x, y, width, height].iter().any(|value| !value.is_finite()) {
+                    return Ok(Value::Undefined);
+                }
+                Some(Rectangle {
+                    x_min: Twips::from_pixels(x),
+                    x_max: Twips::from_pixels(x + width.max(0.0)),
+                    y_min: Twips::from_pixels(y),
+                    y_max: Twips::from_pixels(y + height.max(0.0)),
+                })
+            }
+            _ => None,
+        };
+        display_object.set_scaling_grid(activation.context.gc_context, rect);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements the `tabEnabled` getter, which is `undefined` until it is set.
+pub fn tab_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|display_object| display_object.tab_enabled())
+        .map(Value::Bool)
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_tab_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let value = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined => None,
+            value => Some(value.as_bool(activation.current_swf_version())),
+        };
+        display_object.set_tab_enabled(activation.context.gc_context, value);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements the `tabIndex` getter, which is `undefined` until it is set.
+pub fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|display_object| display_object.tab_index())
+        .map(Value::from)
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let value = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_i32(activation)?),
+        };
+        display_object.set_tab_index(activation.context.gc_context, value);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn tab_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .map(|display_object| Value::Bool(display_object.tab_children()))
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_tab_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let value = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .as_bool(activation.current_swf_version());
+        display_object.set_tab_children(activation.context.gc_context, value);
+    }
+    Ok(Value::Undefined)
 }
 
 pub fn get_parent<'gc>(
@@ -17,7 +17,7 @@ pub fn is_down<'gc>(
         .and_then(|v| v.coerce_to_f64(activation).ok())
         .and_then(|k| KeyCode::try_from(k as u8).ok())
     {
-        Ok(activation.context.input.is_key_down(key).into())
+        Ok(activation.context.keyboard.is_key_down(key).into())
     } else {
         Ok(false.into())
     }
@@ -28,10 +28,42 @@ pub fn get_code<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let code: u8 = activation.context.input.get_last_key_code().into();
+    let code: u8 = activation.context.keyboard.last_key_code().into();
     Ok(code.into())
 }
 
+/// Implements `Key.getAscii`, the character code of the last key pressed or
+/// released, or 0 if it didn't produce a character.
+pub fn get_ascii<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ascii = activation
+        .context
+        .keyboard
+        .last_key_char()
+        .map_or(0, |c| c as u32);
+    Ok(ascii.into())
+}
+
+/// Implements `Key.isToggled`, which is only ever true for Caps Lock and Num Lock.
+pub fn is_toggled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(key) = args
+        .get(0)
+        .and_then(|v| v.coerce_to_f64(activation).ok())
+        .and_then(|k| KeyCode::try_from(k as u8).ok())
+    {
+        Ok(activation.context.keyboard.is_toggled(key).into())
+    } else {
+        Ok(false.into())
+    }
+}
+
 pub fn create_key_object<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Option<Object<'gc>>,
@@ -166,6 +198,14 @@ pub fn create_key_object<'gc>(
         fn_proto,
     );
 
+    key.force_set_function(
+        "isToggled",
+        is_toggled,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
     key.force_set_function(
         "getCode",
         get_code,
@@ -174,5 +214,13 @@ pub fn create_key_object<'gc>(
         fn_proto,
     );
 
+    key.force_set_function(
+        "getAscii",
+        get_ascii,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
     key.into()
 }
@@ -1,5 +1,5 @@
 //! AVM1 LoadVars object
-//! TODO: bytesLoaded, bytesTotal, contentType, addRequestHeader
+//! TODO: bytesLoaded, bytesTotal
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
@@ -88,7 +88,7 @@ pub fn create_proto<'gc>(
     object.define_value(
         gc_context,
         "contentType",
-        "application/x-www-form-url-encoded".into(),
+        "application/x-www-form-urlencoded".into(),
         DontDelete | DontEnum | ReadOnly,
     );
 
@@ -119,15 +119,135 @@ pub fn create_proto<'gc>(
     object.into()
 }
 
-fn add_request_header<'gc>(
+/// Headers that movies are not allowed to set on outgoing requests.
+const RESTRICTED_HEADERS: &[&str] = &[
+    "accept-charset",
+    "accept-encoding",
+    "accept-ranges",
+    "age",
+    "allow",
+    "allowed",
+    "connection",
+    "content-length",
+    "content-location",
+    "content-range",
+    "date",
+    "delete",
+    "etag",
+    "expect",
+    "get",
+    "head",
+    "host",
+    "if-modified-since",
+    "keep-alive",
+    "last-modified",
+    "location",
+    "max-forwards",
+    "options",
+    "origin",
+    "post",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "public",
+    "put",
+    "range",
+    "referer",
+    "request-range",
+    "retry-after",
+    "server",
+    "te",
+    "trace",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "uri",
+    "user-agent",
+    "vary",
+    "via",
+    "warning",
+    "www-authenticate",
+    "x-flash-version",
+];
+
+/// Implements `addRequestHeader` for both `LoadVars` and `XML`.
+///
+/// Headers are stored as name/value pairs in the hidden `_customHeaders`
+/// array, and are sent with any later `send` or `sendAndLoad` request.
+pub fn add_request_header<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "LoadVars.addRequestHeader: Unimplemented");
+    let new_headers = match args {
+        [Value::Object(array), ..] => array.array(),
+        [name, value, ..] => vec![name.clone(), value.clone()],
+        _ => return Ok(Value::Undefined),
+    };
+
+    let headers = match this.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers,
+        _ => {
+            let headers = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            this.define_value(
+                activation.context.gc_context,
+                "_customHeaders",
+                Value::Object(headers),
+                Attribute::DontEnum.into(),
+            );
+            headers
+        }
+    };
+
+    for pair in new_headers.chunks_exact(2) {
+        // Only string names and values are accepted.
+        if let [Value::String(name), Value::String(value)] = pair {
+            if RESTRICTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                avm_warn!(
+                    activation,
+                    "addRequestHeader: Cannot set restricted header {}",
+                    name
+                );
+                continue;
+            }
+
+            let length = headers.length();
+            headers.set_array_element(length, Value::String(*name), activation.context.gc_context);
+            headers.set_array_element(
+                length + 1,
+                Value::String(*value),
+                activation.context.gc_context,
+            );
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Collect the headers added to an object by `addRequestHeader`.
+pub fn request_headers<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let headers = match object.get("_customHeaders", activation)? {
+        Value::Object(headers) => headers.array(),
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut out = Vec::with_capacity(headers.len() / 2);
+    for pair in headers.chunks_exact(2) {
+        out.push((
+            pair[0].coerce_to_string(activation)?.to_string(),
+            pair[1].coerce_to_string(activation)?.to_string(),
+        ));
+    }
+    Ok(out)
+}
+
 fn decode<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -323,8 +443,20 @@ fn spawn_load_var_fetch<'gc>(
     send_object: Option<(Object<'gc>, NavigationMethod)>,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let (url, request_options) = if let Some((send_object, method)) = send_object {
-        // Send properties from `send_object`.
-        activation.object_into_request_options(send_object, Cow::Borrowed(&url), Some(method))
+        // Send properties from `send_object`, using its content type and headers.
+        let content_type = send_object
+            .get("contentType", activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+        let headers = request_headers(activation, send_object)?;
+        let (url, request_options) =
+            activation.object_into_request_options(send_object, Cow::Borrowed(&url), Some(method));
+        (
+            url,
+            request_options
+                .with_content_type(content_type)
+                .with_headers(headers),
+        )
     } else {
         // Not sending any parameters.
         (Cow::Borrowed(url.as_str()), RequestOptions::get())
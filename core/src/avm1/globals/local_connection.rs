@@ -0,0 +1,166 @@
+//! AVM1 LocalConnection object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, Value};
+use crate::avm_warn;
+use crate::local_connection::qualified_name;
+use gc_arena::MutationContext;
+
+/// Methods of `LocalConnection` that can't be called with `send`.
+const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "domain",
+    "allowDomain",
+    "allowInsecureDomain",
+    "onStatus",
+];
+
+/// Implements `LocalConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "domain",
+        domain,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+/// The domain of the movie, as used to qualify connection names.
+fn movie_domain(activation: &mut Activation<'_, '_, '_>) -> String {
+    activation
+        .context
+        .swf
+        .url()
+        .and_then(|url| url::Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(|host| host.to_string()))
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => name.to_string(),
+        _ => return Ok(false.into()),
+    };
+
+    // Receivers can't pick the domain they listen on.
+    if name.is_empty() || name.contains(':') {
+        return Ok(false.into());
+    }
+
+    let domain = movie_domain(activation);
+    let name = qualified_name(&domain, &name, false);
+    if !activation
+        .context
+        .local_connections
+        .connect(name.clone(), this, domain)
+    {
+        avm_warn!(
+            activation,
+            "LocalConnection.connect: {} is already in use",
+            name
+        );
+        return Ok(false.into());
+    }
+
+    Ok(true.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(name)), Some(Value::String(method))) => {
+            (name.to_string(), method.to_string())
+        }
+        _ => return Ok(false.into()),
+    };
+
+    if name.is_empty() || method.is_empty() || RESERVED_METHODS.contains(&method.as_str()) {
+        return Ok(false.into());
+    }
+
+    let domain = movie_domain(activation);
+    let name = qualified_name(&domain, &name, true);
+    activation.context.local_connections.send(
+        this,
+        domain,
+        name,
+        method,
+        args.get(2..).unwrap_or_default().to_vec(),
+    );
+
+    Ok(true.into())
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.local_connections.close(this);
+
+    Ok(Value::Undefined)
+}
+
+fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let domain = movie_domain(activation);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
@@ -18,8 +18,8 @@ use crate::tag_utils::SwfSlice;
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 use swf::{
-    FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread, LineCapStyle,
-    LineJoinStyle, LineStyle, Twips,
+    BlendMode, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
 };
 
 /// Implements `MovieClip`
@@ -161,11 +161,13 @@ pub fn create_proto<'gc>(
     let mut object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
+    display_object::define_scaling_grid_property(gc_context, object, fn_proto);
 
     with_movie_clip!(
         gc_context,
         object,
         Some(fn_proto),
+        "attachAudio" => attach_audio,
         "attachMovie" => attach_movie,
         "createEmptyMovieClip" => create_empty_movie_clip,
         "createTextField" => create_text_field,
@@ -199,12 +201,15 @@ pub fn create_proto<'gc>(
         "curveTo" => curve_to,
         "endFill" => end_fill,
         "lineStyle" => line_style,
+        "lineGradientStyle" => line_gradient_style,
         "clear" => clear
     );
 
     with_movie_clip_props!(
         proto, gc_context, fn_proto,
         "transform" => [transform, set_transform],
+        "cacheAsBitmap" => [cache_as_bitmap, set_cache_as_bitmap],
+        "blendMode" => [blend_mode, set_blend_mode],
     );
 
     object.into()
@@ -317,86 +322,124 @@ fn begin_gradient_fill<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let (Some(method), Some(colors), Some(alphas), Some(ratios), Some(matrix)) = (
+    if args.len() >= 5 {
+        if let Some(style) = gradient_fill_style(activation, args, "beginGradientFill")? {
+            movie_clip.set_fill_style(&mut activation.context, Some(style));
+        }
+    } else {
+        movie_clip.set_fill_style(&mut activation.context, None);
+    }
+    Ok(Value::Undefined)
+}
+
+fn line_gradient_style<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if args.len() >= 5 {
+        if let Some(style) = gradient_fill_style(activation, args, "lineGradientStyle")? {
+            movie_clip.set_line_fill_style(&mut activation.context, Some(style));
+        }
+    } else {
+        movie_clip.set_line_fill_style(&mut activation.context, None);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Builds a gradient fill from the arguments shared by `beginGradientFill` and
+/// `lineGradientStyle`: `(fillType, colors, alphas, ratios, matrix, spreadMethod,
+/// interpolationMethod, focalPointRatio)`.
+///
+/// Returns `None` with a warning if the arguments don't describe a valid gradient.
+fn gradient_fill_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    method_name: &str,
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let (method, colors, alphas, ratios, matrix) = match (
         args.get(0),
         args.get(1),
         args.get(2),
         args.get(3),
         args.get(4),
     ) {
-        let method = method.coerce_to_string(activation)?;
-        let colors = colors.coerce_to_object(activation).array();
-        let alphas = alphas.coerce_to_object(activation).array();
-        let ratios = ratios.coerce_to_object(activation).array();
-        let matrix_object = matrix.coerce_to_object(activation);
-        if colors.len() != alphas.len() || colors.len() != ratios.len() {
-            avm_warn!(
-                activation,
-                "beginGradientFill() received different sized arrays for colors, alphas and ratios"
-            );
-            return Ok(Value::Undefined);
+        (Some(method), Some(colors), Some(alphas), Some(ratios), Some(matrix)) => {
+            (method, colors, alphas, ratios, matrix)
         }
-        let mut records = Vec::with_capacity(colors.len());
-        for i in 0..colors.len() {
-            let ratio = ratios[i].coerce_to_f64(activation)?.min(255.0).max(0.0);
-            let rgb = colors[i].coerce_to_u32(activation)?;
-            let alpha = alphas[i].coerce_to_f64(activation)?.min(100.0).max(0.0);
-            records.push(GradientRecord {
-                ratio: ratio as u8,
-                color: Color::from_rgb(rgb, (alpha / 100.0 * 255.0) as u8),
-            });
-        }
-        let matrix = gradient_object_to_matrix(matrix_object, activation)?;
-        let spread = match args
-            .get(5)
-            .and_then(|v| v.coerce_to_string(activation).ok())
-            .as_deref()
-        {
-            Some("reflect") => GradientSpread::Reflect,
-            Some("repeat") => GradientSpread::Repeat,
-            _ => GradientSpread::Pad,
-        };
-        let interpolation = match args
-            .get(6)
-            .and_then(|v| v.coerce_to_string(activation).ok())
-            .as_deref()
-        {
-            Some("linearRGB") => GradientInterpolation::LinearRGB,
-            _ => GradientInterpolation::RGB,
-        };
+        _ => return Ok(None),
+    };
+    let method = method.coerce_to_string(activation)?;
+    let colors = colors.coerce_to_object(activation).array();
+    let alphas = alphas.coerce_to_object(activation).array();
+    let ratios = ratios.coerce_to_object(activation).array();
+    let matrix_object = matrix.coerce_to_object(activation);
+    if colors.len() != alphas.len() || colors.len() != ratios.len() {
+        avm_warn!(
+            activation,
+            "{}() received different sized arrays for colors, alphas and ratios",
+            method_name
+        );
+        return Ok(None);
+    }
+    let mut records = Vec::with_capacity(colors.len());
+    for i in 0..colors.len() {
+        let ratio = ratios[i].coerce_to_f64(activation)?.min(255.0).max(0.0);
+        let rgb = colors[i].coerce_to_u32(activation)?;
+        let alpha = alphas[i].coerce_to_f64(activation)?.min(100.0).max(0.0);
+        records.push(GradientRecord {
+            ratio: ratio as u8,
+            color: Color::from_rgb(rgb, (alpha / 100.0 * 255.0) as u8),
+        });
+    }
+    let matrix = gradient_object_to_matrix(matrix_object, activation)?;
+    let spread = match args
+        .get(5)
+        .and_then(|v| v.coerce_to_string(activation).ok())
+        .as_deref()
+    {
+        Some("reflect") => GradientSpread::Reflect,
+        Some("repeat") => GradientSpread::Repeat,
+        _ => GradientSpread::Pad,
+    };
+    let interpolation = match args
+        .get(6)
+        .and_then(|v| v.coerce_to_string(activation).ok())
+        .as_deref()
+    {
+        Some("linearRGB") => GradientInterpolation::LinearRGB,
+        _ => GradientInterpolation::RGB,
+    };
 
-        let gradient = Gradient {
-            matrix,
-            spread,
-            interpolation,
-            records,
-        };
-        let style = match method.as_ref() {
-            "linear" => FillStyle::LinearGradient(gradient),
-            "radial" => {
-                if let Some(focal_point) = args.get(7) {
-                    FillStyle::FocalGradient {
-                        gradient,
-                        focal_point: focal_point.coerce_to_f64(activation)? as f32,
-                    }
-                } else {
-                    FillStyle::RadialGradient(gradient)
+    let gradient = Gradient {
+        matrix,
+        spread,
+        interpolation,
+        records,
+    };
+    let style = match method.as_ref() {
+        "linear" => FillStyle::LinearGradient(gradient),
+        "radial" => {
+            if let Some(focal_point) = args.get(7) {
+                FillStyle::FocalGradient {
+                    gradient,
+                    focal_point: focal_point.coerce_to_f64(activation)? as f32,
                 }
+            } else {
+                FillStyle::RadialGradient(gradient)
             }
-            other => {
-                avm_warn!(
-                    activation,
-                    "beginGradientFill() received invalid fill type {:?}",
-                    other
-                );
-                return Ok(Value::Undefined);
-            }
-        };
-        movie_clip.set_fill_style(&mut activation.context, Some(style));
-    } else {
-        movie_clip.set_fill_style(&mut activation.context, None);
-    }
-    Ok(Value::Undefined)
+        }
+        other => {
+            avm_warn!(
+                activation,
+                "{}() received invalid fill type {:?}",
+                method_name,
+                other
+            );
+            return Ok(None);
+        }
+    };
+    Ok(Some(style))
 }
 
 fn move_to<'gc>(
@@ -480,6 +523,16 @@ fn clear<'gc>(
     Ok(Value::Undefined)
 }
 
+fn attach_audio<'gc>(
+    _movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // There are no microphones or NetStreams to play audio from yet.
+    avm_warn!(activation, "MovieClip.attachAudio: Unimplemented");
+    Ok(Value::Undefined)
+}
+
 fn attach_movie<'gc>(
     mut movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -674,10 +727,13 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
             activation.context.gc_context,
             &*movie_clip.color_transform(),
         );
-        new_clip.as_movie_clip().unwrap().set_clip_actions(
+        let new_movie_clip = new_clip.as_movie_clip().unwrap();
+        new_movie_clip.set_clip_actions(
             activation.context.gc_context,
             movie_clip.clip_actions().to_vec(),
         );
+        // Graphics drawn with the Drawing API are copied as well.
+        new_movie_clip.copy_drawing(&mut activation.context, movie_clip);
         // TODO: Any other properties we should copy...?
         // Definitely not ScriptObject properties.
 
@@ -696,21 +752,19 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
 }
 
 fn get_bytes_loaded<'gc>(
-    _movie_clip: MovieClip<'gc>,
+    movie_clip: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO find a correct value
-    Ok(1.0.into())
+    Ok(movie_clip.loaded_bytes().into())
 }
 
 fn get_bytes_total<'gc>(
-    _movie_clip: MovieClip<'gc>,
+    movie_clip: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO find a correct value
-    Ok(1.0.into())
+    Ok(movie_clip.total_bytes().into())
 }
 
 fn get_next_highest_depth<'gc>(
@@ -1016,9 +1070,8 @@ fn get_rect<'gc>(
 
 #[allow(unused_must_use)] //can't use errors yet
 pub fn get_url<'gc>(
-    _movie_clip: MovieClip<'gc>,
+    movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
-
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     use crate::avm1::fscommand;
@@ -1027,7 +1080,11 @@ pub fn get_url<'gc>(
     if let Some(url_val) = args.get(0) {
         let url = url_val.coerce_to_string(activation)?;
         if let Some(fscommand) = fscommand::parse(&url) {
-            fscommand::handle(fscommand, activation);
+            let args = match args.get(1) {
+                Some(args) => args.coerce_to_string(activation)?.to_string(),
+                None => "".to_string(),
+            };
+            fscommand::handle(fscommand, &args, activation);
             return Ok(Value::Undefined);
         }
 
@@ -1041,7 +1098,8 @@ pub fn get_url<'gc>(
             Some(Value::String(s)) if *s == "POST" => Some(NavigationMethod::POST),
             _ => None,
         };
-        let vars_method = method.map(|m| (m, activation.locals_into_form_values()));
+        let vars_object = movie_clip.object().coerce_to_object(activation);
+        let vars_method = method.map(|m| (m, activation.object_into_form_values(vars_object)));
 
         activation
             .context
@@ -1094,7 +1152,9 @@ fn load_movie<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
+    let vars_object = target.object().coerce_to_object(activation);
+    let (url, opts) =
+        activation.object_into_request_options(vars_object, Cow::Borrowed(&url), method);
     let fetch = activation.context.navigator.fetch(&url, opts);
     let process = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone().unwrap(),
@@ -1118,9 +1178,9 @@ fn load_variables<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
     let target = target.object().coerce_to_object(activation);
+    let (url, opts) = activation.object_into_request_options(target, Cow::Borrowed(&url), method);
+    let fetch = activation.context.navigator.fetch(&url, opts);
     let process = activation.context.load_manager.load_form_into_object(
         activation.context.player.clone().unwrap(),
         target,
@@ -1137,8 +1197,7 @@ fn unload_movie<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    target.unload(&mut activation.context);
-    target.replace_with_movie(activation.context.gc_context, None);
+    target.unload_movie(&mut activation.context);
 
     Ok(Value::Undefined)
 }
@@ -1161,3 +1220,76 @@ fn set_transform<'gc>(
     crate::avm1::globals::transform::apply_to_display_object(activation, transform, this.into())?;
     Ok(())
 }
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_bitmap_cached().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let value = value.as_bool(activation.current_swf_version());
+    this.set_is_bitmap_cached(activation.context.gc_context, value);
+    Ok(())
+}
+
+/// The names of the blend modes, in the order of their numeric values starting at 1.
+const BLEND_MODES: [(&str, BlendMode); 14] = [
+    ("normal", BlendMode::Normal),
+    ("layer", BlendMode::Layer),
+    ("multiply", BlendMode::Multiply),
+    ("screen", BlendMode::Screen),
+    ("lighten", BlendMode::Lighten),
+    ("darken", BlendMode::Darken),
+    ("difference", BlendMode::Difference),
+    ("add", BlendMode::Add),
+    ("subtract", BlendMode::Subtract),
+    ("invert", BlendMode::Invert),
+    ("alpha", BlendMode::Alpha),
+    ("erase", BlendMode::Erase),
+    ("overlay", BlendMode::Overlay),
+    ("hardlight", BlendMode::HardLight),
+];
+
+fn blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let blend_mode = this.blend_mode();
+    let name = BLEND_MODES
+        .iter()
+        .find(|(_, mode)| *mode == blend_mode)
+        .map(|(name, _)| *name)
+        .unwrap_or("normal");
+    Ok(name.into())
+}
+
+fn set_blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    // Blend modes can be set by name or by their numeric value. Invalid values are ignored.
+    let blend_mode = if let Value::Number(n) = value {
+        if (1.0..=BLEND_MODES.len() as f64).contains(&n) {
+            Some(BLEND_MODES[n as usize - 1].1)
+        } else {
+            None
+        }
+    } else {
+        let name = value.coerce_to_string(activation)?;
+        BLEND_MODES
+            .iter()
+            .find(|(mode_name, _)| *mode_name == name.as_str())
+            .map(|(_, mode)| *mode)
+    };
+    if let Some(blend_mode) = blend_mode {
+        this.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+    Ok(())
+}
@@ -8,7 +8,7 @@ use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, Value};
 use crate::backend::navigator::RequestOptions;
-use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
 
@@ -32,6 +32,35 @@ pub fn constructor<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Find the clip targeted by a `MovieClipLoader` method.
+///
+/// The target may be a movie clip, a level number, or a target path such as
+/// `"_level1"` or `"holder.inner"`. If `create_levels` is set, levels that
+/// don't exist yet are created.
+fn resolve_target_clip<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Value<'gc>,
+    create_levels: bool,
+) -> Result<Option<MovieClip<'gc>>, Error<'gc>> {
+    let level_id = match &target {
+        Value::Number(level_id) if *level_id >= 0.0 => Some(*level_id as u32),
+        Value::String(path) if path.starts_with("_level") => path[6..].parse().ok(),
+        _ => None,
+    };
+
+    let clip = match (target, level_id) {
+        (_, Some(level_id)) if create_levels => Some(activation.resolve_level(level_id)),
+        (_, Some(level_id)) => activation.context.levels.get(&level_id).copied(),
+        (Value::Undefined, _) | (Value::Null, _) => None,
+        (target, None) => {
+            let start = activation.target_clip_or_root();
+            activation.resolve_target_display_object(start, target)?
+        }
+    };
+
+    Ok(clip.and_then(|clip| clip.as_movie_clip()))
+}
+
 pub fn load_clip<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -41,25 +70,20 @@ pub fn load_clip<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let target = args.get(1).cloned().unwrap_or(Value::Undefined);
 
-    if let Value::Object(target) = target {
-        if let Some(movieclip) = target
-            .as_display_object()
-            .and_then(|dobj| dobj.as_movie_clip())
-        {
-            let fetch = activation
-                .context
-                .navigator
-                .fetch(&url, RequestOptions::get());
-            let process = activation.context.load_manager.load_movie_into_clip(
-                activation.context.player.clone().unwrap(),
-                DisplayObject::MovieClip(movieclip),
-                fetch,
-                url.to_string(),
-                Some(this),
-            );
-
-            activation.context.navigator.spawn_future(process);
-        }
+    if let Some(movieclip) = resolve_target_clip(activation, target, true)? {
+        let fetch = activation
+            .context
+            .navigator
+            .fetch(&url, RequestOptions::get());
+        let process = activation.context.load_manager.load_movie_into_clip(
+            activation.context.player.clone().unwrap(),
+            DisplayObject::MovieClip(movieclip),
+            fetch,
+            url.to_string(),
+            Some(this),
+        );
+
+        activation.context.navigator.spawn_future(process);
 
         Ok(true.into())
     } else {
@@ -74,16 +98,10 @@ pub fn unload_clip<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let target = args.get(0).cloned().unwrap_or(Value::Undefined);
 
-    if let Value::Object(target) = target {
-        if let Some(mut movieclip) = target
-            .as_display_object()
-            .and_then(|dobj| dobj.as_movie_clip())
-        {
-            movieclip.unload(&mut activation.context);
-            movieclip.replace_with_movie(activation.context.gc_context, None);
+    if let Some(mut movieclip) = resolve_target_clip(activation, target, false)? {
+        movieclip.unload_movie(&mut activation.context);
 
-            return Ok(true.into());
-        }
+        return Ok(true.into());
     }
 
     Ok(false.into())
@@ -96,33 +114,39 @@ pub fn get_progress<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let target = args.get(0).cloned().unwrap_or(Value::Undefined);
 
-    if let Value::Object(target) = target {
-        if let Some(movieclip) = target
-            .as_display_object()
-            .and_then(|dobj| dobj.as_movie_clip())
+    if let Some(movieclip) = resolve_target_clip(activation, target, false)? {
+        // While a load is in progress, report its numbers rather than those
+        // of the content that is about to be replaced.
+        let (bytes_loaded, bytes_total) = match activation
+            .context
+            .load_manager
+            .movie_clip_progress(movieclip.into())
         {
-            let ret_obj = ScriptObject::object(activation.context.gc_context, None);
-            ret_obj.define_value(
-                activation.context.gc_context,
-                "bytesLoaded",
-                movieclip
-                    .movie()
-                    .map(|mv| (mv.data().len() + 21).into())
-                    .unwrap_or(Value::Undefined),
-                EnumSet::empty(),
-            );
-            ret_obj.define_value(
-                activation.context.gc_context,
-                "bytesTotal",
-                movieclip
+            Some((loaded, total)) => (loaded.into(), total.into()),
+            None => {
+                let length = movieclip
                     .movie()
                     .map(|mv| (mv.data().len() + 21).into())
-                    .unwrap_or(Value::Undefined),
-                EnumSet::empty(),
-            );
-
-            return Ok(ret_obj.into());
-        }
+                    .unwrap_or(Value::Undefined);
+                (length.clone(), length)
+            }
+        };
+
+        let ret_obj = ScriptObject::object(activation.context.gc_context, None);
+        ret_obj.define_value(
+            activation.context.gc_context,
+            "bytesLoaded",
+            bytes_loaded,
+            EnumSet::empty(),
+        );
+        ret_obj.define_value(
+            activation.context.gc_context,
+            "bytesTotal",
+            bytes_total,
+            EnumSet::empty(),
+        );
+
+        return Ok(ret_obj.into());
     }
 
     Ok(Value::Undefined)
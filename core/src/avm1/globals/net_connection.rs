@@ -0,0 +1,117 @@
+//! AVM1 NetConnection object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm_warn;
+use crate::net_stream::net_status_info;
+use gc_arena::MutationContext;
+
+/// Implements `NetConnection`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    set_connected(activation, this, false);
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+/// Update the read-only `isConnected` property of a `NetConnection`.
+fn set_connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    connected: bool,
+) {
+    use Attribute::*;
+
+    this.define_value(
+        activation.context.gc_context,
+        "isConnected",
+        connected.into(),
+        DontDelete | DontEnum | ReadOnly,
+    );
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Only `null` connections, which play progressive video files from a web server,
+/// are supported. The connection succeeds immediately, before `connect` returns.
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let uri = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let success = match uri {
+        Value::Null | Value::Undefined => true,
+        uri => {
+            let uri = uri.coerce_to_string(activation)?;
+            if uri.as_str() == "null" {
+                true
+            } else {
+                avm_warn!(
+                    activation,
+                    "NetConnection.connect: Connecting to {} is not supported",
+                    uri
+                );
+                false
+            }
+        }
+    };
+
+    set_connected(activation, this, success);
+    let info = if success {
+        net_status_info(activation, "NetConnection.Connect.Success", "status")
+    } else {
+        net_status_info(activation, "NetConnection.Connect.Failed", "error")
+    };
+    this.call_method("onStatus", &[info.into()], activation)?;
+
+    Ok(success.into())
+}
+
+/// Implements `NetConnection.close`.
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if this.get("isConnected", activation)? == Value::Bool(true) {
+        set_connected(activation, this, false);
+        let info = net_status_info(activation, "NetConnection.Connect.Closed", "status");
+        this.call_method("onStatus", &[info.into()], activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,204 @@
+//! AVM1 NetStream object
+//!
+//! Playback is shared with the AVM2 `NetStream`, see `crate::net_stream`. Status changes call the
+//! `onStatus` method of the stream, and script data like `onMetaData` is also delivered to
+//! methods of the stream itself, as AVM1 has no separate client object.
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject, NativeFunction};
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::net_stream::NetStreams;
+use gc_arena::MutationContext;
+
+/// Implements `NetStream`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.net_streams.add_stream(this.into());
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    let methods: &[(&str, NativeFunction<'gc>)] = &[
+        ("play", play),
+        ("pause", pause),
+        ("seek", seek),
+        ("close", close),
+        ("setBufferTime", set_buffer_time),
+    ];
+
+    for &(name, method) in methods {
+        object.force_set_function(
+            name,
+            method,
+            gc_context,
+            DontDelete | DontEnum | ReadOnly,
+            Some(fn_proto),
+        );
+    }
+
+    let properties: &[(&str, NativeFunction<'gc>)] = &[
+        ("time", time),
+        ("bufferLength", buffer_length),
+        ("bufferTime", buffer_time),
+        // Files are only played once they are fully downloaded, so both are the same.
+        ("bytesLoaded", bytes_loaded),
+        ("bytesTotal", bytes_loaded),
+    ];
+
+    for &(name, getter) in properties {
+        object.add_property(
+            gc_context,
+            name,
+            FunctionObject::function(
+                gc_context,
+                Executable::Native(getter),
+                Some(fn_proto),
+                fn_proto,
+            ),
+            None,
+            DontDelete | DontEnum | ReadOnly,
+        );
+    }
+
+    object.into()
+}
+
+/// Implements `NetStream.play`.
+fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Null | Value::Undefined => {}
+        url => {
+            let url = url.coerce_to_string(activation)?;
+            NetStreams::play(&mut activation.context, this.into(), &url);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.pause`.
+///
+/// Without an argument, this toggles between pausing and resuming.
+fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    match args.get(0) {
+        None | Some(Value::Undefined) => activation.context.net_streams.toggle_pause(this.into()),
+        Some(flag) if flag.as_bool(activation.current_swf_version()) => {
+            activation.context.net_streams.pause(this.into())
+        }
+        Some(_) => activation.context.net_streams.resume(this.into()),
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.seek`.
+fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let offset = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    activation
+        .context
+        .net_streams
+        .seek(this.into(), offset.max(0.0));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.close`.
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .net_streams
+        .close(activation.context.video, this.into());
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.setBufferTime`.
+fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let buffer_time = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    activation
+        .context
+        .net_streams
+        .set_buffer_time(this.into(), buffer_time);
+
+    Ok(Value::Undefined)
+}
+
+fn time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.net_streams.time(this.into()).into())
+}
+
+fn buffer_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .net_streams
+        .buffer_length(this.into())
+        .into())
+}
+
+fn buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .net_streams
+        .buffer_time(this.into())
+        .into())
+}
+
+fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.context.net_streams.bytes_loaded(this.into()) as f64).into())
+}
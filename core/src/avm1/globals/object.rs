@@ -136,6 +136,10 @@ fn value_of<'gc>(
 }
 
 /// Implements `Object.registerClass`
+///
+/// Instances of the exported symbol created afterwards, whether by
+/// `attachMovie`, `duplicateMovieClip` or the timeline, are constructed with
+/// the given class. Passing `null` unregisters the class.
 pub fn register_class<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -143,6 +147,10 @@ pub fn register_class<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(class_name) = args.get(0).cloned() {
         let class_name = class_name.coerce_to_string(activation)?;
+        let constructor = match args.get(1) {
+            None | Some(Value::Undefined) | Some(Value::Null) => None,
+            Some(constructor) => Some(constructor.coerce_to_object(activation)),
+        };
         if let Some(movie) = activation.base_clip().movie() {
             if let Some(Character::MovieClip(movie_clip)) = activation
                 .context
@@ -150,14 +158,8 @@ pub fn register_class<'gc>(
                 .library_for_movie_mut(movie)
                 .get_character_by_export_name(&class_name)
             {
-                if let Some(constructor) = args.get(1) {
-                    movie_clip.set_avm1_constructor(
-                        activation.context.gc_context,
-                        Some(constructor.coerce_to_object(activation)),
-                    );
-                } else {
-                    movie_clip.set_avm1_constructor(activation.context.gc_context, None);
-                }
+                movie_clip.set_avm1_constructor(activation.context.gc_context, constructor);
+                return Ok(true.into());
             } else {
                 log::warn!(
                     "Tried to register_class on an unknown export {}",
@@ -170,7 +172,7 @@ pub fn register_class<'gc>(
     } else {
         log::warn!("Tried to register_class with an unknown class");
     }
-    Ok(Value::Undefined)
+    Ok(false.into())
 }
 
 /// Implements `Object.prototype.watch`
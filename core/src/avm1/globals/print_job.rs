@@ -0,0 +1,169 @@
+//! AVM1 PrintJob object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm_warn;
+use crate::backend::input::PrintOrientation;
+use crate::bounding_box::BoundingBox;
+use crate::prelude::*;
+use crate::print_job::PrintPage;
+use gc_arena::MutationContext;
+
+/// Implements `PrintJob`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "start",
+        start,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "addPage",
+        add_page,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // `None` means there is no printer, or the user cancelled the print dialog.
+    let format = match activation.context.input.start_print_job() {
+        Some(format) => format,
+        None => return Ok(false.into()),
+    };
+
+    this.set("paperWidth", format.paper_width.into(), activation)?;
+    this.set("paperHeight", format.paper_height.into(), activation)?;
+    this.set("pageWidth", format.page_width.into(), activation)?;
+    this.set("pageHeight", format.page_height.into(), activation)?;
+    let orientation = match format.orientation {
+        PrintOrientation::Portrait => "portrait",
+        PrintOrientation::Landscape => "landscape",
+    };
+    this.set("orientation", orientation.into(), activation)?;
+
+    activation.context.print_jobs.start(this, format);
+
+    Ok(true.into())
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The target is either a level number or a movie clip.
+    let target = match args.get(0) {
+        Some(Value::Number(level)) => activation.context.levels.get(&(*level as u32)).copied(),
+        Some(target) => {
+            let start = activation.target_clip_or_root();
+            activation.resolve_target_display_object(start, target.clone())?
+        }
+        None => None,
+    };
+    let target = match target {
+        Some(target) => target,
+        None => return Ok(false.into()),
+    };
+
+    let area = match args.get(1) {
+        Some(Value::Object(area)) => print_area(activation, *area)?,
+        _ => None,
+    };
+
+    let print_as_bitmap = match args.get(2) {
+        Some(Value::Object(options)) => options
+            .get("printAsBitmap", activation)?
+            .as_bool(activation.current_swf_version()),
+        _ => false,
+    };
+
+    if !matches!(args.get(3), None | Some(Value::Undefined)) {
+        avm_warn!(
+            activation,
+            "PrintJob.addPage: Printing a specific frame is not yet implemented"
+        );
+    }
+
+    let page = PrintPage {
+        target,
+        area,
+        print_as_bitmap,
+    };
+    Ok(activation.context.print_jobs.add_page(this, page).into())
+}
+
+/// Read the `printArea` argument of `addPage`, which is given in pixels.
+///
+/// Returns `None` if any of its edges are missing, in which case the whole target is printed.
+fn print_area<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    area: Object<'gc>,
+) -> Result<Option<BoundingBox>, Error<'gc>> {
+    let mut edges = [Twips::new(0); 4];
+    for (edge, name) in edges.iter_mut().zip(&["xMin", "xMax", "yMin", "yMax"]) {
+        match area.get(name, activation)? {
+            Value::Undefined | Value::Null => return Ok(None),
+            value => *edge = Twips::from_pixels(value.coerce_to_f64(activation)?),
+        }
+    }
+
+    let [x_min, x_max, y_min, y_max] = edges;
+    Ok(Some(BoundingBox {
+        x_min,
+        x_max,
+        y_min,
+        y_max,
+        valid: x_min < x_max && y_min < y_max,
+    }))
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.context.print_jobs.send(this) {
+        // The pages are printed with the next rendered frame.
+        *activation.context.needs_render = true;
+    } else {
+        avm_warn!(activation, "PrintJob.send: start() was not called");
+    }
+
+    Ok(Value::Undefined)
+}
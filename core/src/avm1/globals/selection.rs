@@ -0,0 +1,193 @@
+//! `Selection` impl
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, Value};
+use crate::display_object::{EditText, TDisplayObject, TextSelection};
+use gc_arena::MutationContext;
+
+/// Implements `Selection.getFocus`, which returns the target path of the
+/// focused object, or `null` if nothing has focus.
+pub fn get_focus<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match activation.context.focus_tracker.get() {
+        Some(focus) => AvmString::new(activation.context.gc_context, focus.path()).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements `Selection.setFocus`, which accepts a display object or a
+/// target path. `null` and `undefined` remove focus.
+pub fn set_focus<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let focus_tracker = activation.context.focus_tracker;
+    let highlight = focus_tracker.is_highlighted();
+
+    match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => {
+            focus_tracker.set(None, &mut activation.context, false);
+            Ok(true.into())
+        }
+        target => {
+            let start_clip = activation.target_clip_or_root();
+            match activation.resolve_target_display_object(start_clip, target)? {
+                Some(focus) => {
+                    focus_tracker.set(Some(focus), &mut activation.context, highlight);
+                    Ok(true.into())
+                }
+                None => Ok(false.into()),
+            }
+        }
+    }
+}
+
+/// The text field that has focus, if any.
+fn focused_text_field<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Option<EditText<'gc>> {
+    activation
+        .context
+        .focus_tracker
+        .get()
+        .and_then(|focus| focus.as_edit_text())
+}
+
+/// Implements `Selection.getBeginIndex`, which returns -1 if no text field
+/// has focus.
+pub fn get_begin_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(
+        match focused_text_field(activation).and_then(|text| text.selection()) {
+            Some(selection) => selection.start().into(),
+            None => (-1).into(),
+        },
+    )
+}
+
+/// Implements `Selection.getEndIndex`, which returns -1 if no text field has
+/// focus.
+pub fn get_end_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(
+        match focused_text_field(activation).and_then(|text| text.selection()) {
+            Some(selection) => selection.end().into(),
+            None => (-1).into(),
+        },
+    )
+}
+
+/// Implements `Selection.getCaretIndex`, which returns -1 if no text field
+/// has focus.
+pub fn get_caret_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(
+        match focused_text_field(activation).and_then(|text| text.selection()) {
+            Some(selection) => selection.caret().into(),
+            None => (-1).into(),
+        },
+    )
+}
+
+/// Implements `Selection.setSelection`, which selects a span of the focused
+/// text field and moves the caret to its end. Indices are clamped to the
+/// text.
+pub fn set_selection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text_field) = focused_text_field(activation) {
+        let start = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?
+            .max(0) as usize;
+        let end = match args.get(1) {
+            Some(end) => end.coerce_to_i32(activation)?.max(0) as usize,
+            None => start,
+        };
+        text_field.set_selection(
+            Some(TextSelection::for_range(start, end)),
+            activation.context.gc_context,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_selection_object<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Option<Object<'gc>>,
+    fn_proto: Option<Object<'gc>>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+    array_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut selection = ScriptObject::object(gc_context, proto);
+
+    broadcaster_functions.initialize(gc_context, selection.into(), array_proto);
+
+    selection.force_set_function(
+        "getFocus",
+        get_focus,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.force_set_function(
+        "setFocus",
+        set_focus,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.force_set_function(
+        "getBeginIndex",
+        get_begin_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.force_set_function(
+        "getEndIndex",
+        get_end_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.force_set_function(
+        "getCaretIndex",
+        get_caret_index,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.force_set_function(
+        "setSelection",
+        set_selection,
+        gc_context,
+        Attribute::DontEnum | Attribute::DontDelete | Attribute::ReadOnly,
+        fn_proto,
+    );
+
+    selection.into()
+}
@@ -64,7 +64,7 @@ fn recursive_serialize<'gc>(
 /// Deserialize an Object and any children from a JSON object
 /// It would be best if this was implemented via serde but due to avm and context it can't
 /// Undefined fields aren't deserialized
-fn recursive_deserialize<'gc>(
+pub fn recursive_deserialize<'gc>(
     json_obj: JsonValue,
     activation: &mut Activation<'_, 'gc, '_>,
     object: Object<'gc>,
@@ -1,12 +1,13 @@
 //! AVM1 Sound object
-//! TODO: Sound position, transform, loadSound
+//! TODO: loadSound
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property::Attribute::*;
-use crate::avm1::{Object, SoundObject, TObject, Value};
+use crate::avm1::{Object, ScriptObject, SoundObject, TObject, Value};
 use crate::avm_warn;
+use crate::backend::audio::SoundTransform;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
@@ -96,7 +97,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "get_transform",
+        "getTransform",
         get_transform,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -104,7 +105,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "get_volume",
+        "getVolume",
         get_volume,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -112,7 +113,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "load_sound",
+        "loadSound",
         load_sound,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -133,7 +134,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "set_pan",
+        "setPan",
         set_pan,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -141,7 +142,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "set_transform",
+        "setTransform",
         set_transform,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -149,7 +150,7 @@ pub fn create_proto<'gc>(
     );
 
     object.as_script_object().unwrap().force_set_function(
-        "set_volume",
+        "setVolume",
         set_volume,
         gc_context,
         DontDelete | ReadOnly | DontEnum,
@@ -264,29 +265,53 @@ fn get_bytes_total<'gc>(
 
 fn get_pan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getPan: Unimplemented");
-    Ok(0.into())
+    if let Some(sound_object) = this.as_sound_object() {
+        let transform = sound_transform(activation, sound_object);
+        Ok((f64::from(transform.pan()) * 100.0).round().into())
+    } else {
+        avm_warn!(activation, "Sound.getPan: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn get_transform<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getTransform: Unimplemented");
-    Ok(Value::Undefined)
+    if let Some(sound_object) = this.as_sound_object() {
+        let transform = sound_transform(activation, sound_object);
+        let out = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        let percent = |n: f32| (f64::from(n) * 100.0).round();
+        out.set("ll", percent(transform.left_to_left).into(), activation)?;
+        out.set("lr", percent(transform.left_to_right).into(), activation)?;
+        out.set("rl", percent(transform.right_to_left).into(), activation)?;
+        out.set("rr", percent(transform.right_to_right).into(), activation)?;
+        Ok(out.into())
+    } else {
+        avm_warn!(activation, "Sound.getTransform: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn get_volume<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.getVolume: Unimplemented");
-    Ok(100.into())
+    if let Some(sound_object) = this.as_sound_object() {
+        let transform = sound_transform(activation, sound_object);
+        Ok((f64::from(transform.volume) * 100.0).round().into())
+    } else {
+        avm_warn!(activation, "Sound.getVolume: this is not a Sound");
+        Ok(Value::Undefined)
+    }
 }
 
 fn id3<'gc>(
@@ -318,12 +343,14 @@ fn position<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
         if let Some(sound_object) = this.as_sound_object() {
-            // TODO: The position is "sticky"; even if the sound is no longer playing, it should return
-            // the previous valid position.
-            // Needs some audio backend work for this.
             if sound_object.sound().is_some() {
-                if let Some(_sound_instance) = sound_object.sound_instance() {
-                    avm_warn!(activation, "Sound.position: Unimplemented");
+                // The position is "sticky"; once the sound stops playing, the last position
+                // it played at is returned.
+                if let Some(position) = sound_object
+                    .sound_instance()
+                    .and_then(|instance| activation.context.audio.get_sound_position(instance))
+                {
+                    sound_object.set_position(activation.context.gc_context, position);
                 }
                 return Ok(sound_object.position().into());
             }
@@ -336,31 +363,117 @@ fn position<'gc>(
 
 fn set_pan<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setPan: Unimplemented");
+    if let Some(sound_object) = this.as_sound_object() {
+        let pan = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_f64(activation)?;
+        let mut transform = sound_transform(activation, sound_object);
+        transform.set_pan((pan / 100.0) as f32);
+        set_sound_transform(activation, sound_object, transform);
+    } else {
+        avm_warn!(activation, "Sound.setPan: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
 fn set_transform<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setTransform: Unimplemented");
+    fn percent<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        transform: Object<'gc>,
+        property: &str,
+    ) -> Result<Option<f32>, Error<'gc>> {
+        // The parameters are set only if the property exists on the object itself (prototype excluded).
+        if transform.has_own_property(activation, property) {
+            let n = transform
+                .get(property, activation)?
+                .coerce_to_f64(activation)?;
+            Ok(Some((n / 100.0) as f32))
+        } else {
+            Ok(None)
+        }
+    }
+
+    if let Some(sound_object) = this.as_sound_object() {
+        let object = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation);
+        let mut transform = sound_transform(activation, sound_object);
+        if let Some(n) = percent(activation, object, "ll")? {
+            transform.left_to_left = n;
+        }
+        if let Some(n) = percent(activation, object, "lr")? {
+            transform.left_to_right = n;
+        }
+        if let Some(n) = percent(activation, object, "rl")? {
+            transform.right_to_left = n;
+        }
+        if let Some(n) = percent(activation, object, "rr")? {
+            transform.right_to_right = n;
+        }
+        set_sound_transform(activation, sound_object, transform);
+    } else {
+        avm_warn!(activation, "Sound.setTransform: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
 fn set_volume<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Sound.setVolume: Unimplemented");
+    if let Some(sound_object) = this.as_sound_object() {
+        let volume = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_f64(activation)?;
+        let mut transform = sound_transform(activation, sound_object);
+        transform.volume = (volume / 100.0) as f32;
+        set_sound_transform(activation, sound_object, transform);
+    } else {
+        avm_warn!(activation, "Sound.setVolume: this is not a Sound");
+    }
     Ok(Value::Undefined)
 }
 
+/// Returns the sound transform controlled by a `Sound` object.
+/// This is the transform of the clip that owns the `Sound`, or the global transform if it has
+/// no owner.
+fn sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sound_object: SoundObject<'gc>,
+) -> SoundTransform {
+    if let Some(owner) = sound_object.owner() {
+        owner.sound_transform()
+    } else {
+        activation.context.audio_manager.global_sound_transform()
+    }
+}
+
+fn set_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    sound_object: SoundObject<'gc>,
+    transform: SoundTransform,
+) {
+    if let Some(owner) = sound_object.owner() {
+        owner.set_sound_transform(&mut activation.context, transform);
+    } else {
+        activation
+            .context
+            .audio_manager
+            .set_global_sound_transform(transform);
+    }
+}
+
 fn start<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -384,7 +497,9 @@ fn start<'gc>(
     use swf::{SoundEvent, SoundInfo};
     if let Some(sound_object) = this.as_sound_object() {
         if let Some(sound) = sound_object.sound() {
-            let sound_instance = activation.context.audio.start_sound(
+            let movie = activation.base_clip().movie();
+            let sound_instance = activation.context.audio_manager.start_sound(
+                activation.context.audio,
                 sound,
                 &SoundInfo {
                     event: SoundEvent::Start,
@@ -397,8 +512,10 @@ fn start<'gc>(
                     num_loops: loops,
                     envelope: None,
                 },
+                sound_object.owner(),
+                movie,
             );
-            if let Ok(sound_instance) = sound_instance {
+            if let Some(sound_instance) = sound_instance {
                 sound_object
                     .set_sound_instance(activation.context.gc_context, Some(sound_instance));
             }
@@ -433,7 +550,10 @@ fn stop<'gc>(
                     .get_character_by_export_name(&name)
                 {
                     // Stop all sounds with the given name.
-                    activation.context.audio.stop_sounds_with_handle(*sound);
+                    activation
+                        .context
+                        .audio_manager
+                        .stop_sounds_with_handle(activation.context.audio, *sound);
                 } else {
                     avm_warn!(activation, "Sound.stop: Sound '{}' not found", name);
                 }
@@ -444,15 +564,18 @@ fn stop<'gc>(
                     name
                 )
             }
-        } else if let Some(_owner) = sound.owner() {
+        } else if let Some(owner) = sound.owner() {
             // Usage 2: Stop all sound running within a given clip.
-            // TODO: We just stop the last played sound for now.
-            if let Some(sound_instance) = sound.sound_instance() {
-                activation.context.audio.stop_sound(sound_instance);
-            }
+            activation
+                .context
+                .audio_manager
+                .stop_sounds_with_display_object(activation.context.audio, owner);
         } else {
             // Usage 3: If there is no owner and no name, this call acts like `stopAllSounds()`.
-            activation.context.audio.stop_all_sounds();
+            activation
+                .context
+                .audio_manager
+                .stop_all_sounds(activation.context.audio);
         }
     } else {
         avm_warn!(activation, "Sound.stop: this is not a Sound");
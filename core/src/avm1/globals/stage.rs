@@ -6,8 +6,9 @@ use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::property::Attribute;
-use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_warn;
+use crate::stage::StageAlign;
 use gc_arena::MutationContext;
 
 pub fn create_stage_object<'gc>(
@@ -109,16 +110,20 @@ fn align<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.align: unimplemented");
-    Ok("".into())
+    let align = StageAlign::format(*activation.context.stage_align);
+    Ok(AvmString::new(activation.context.gc_context, align).into())
 }
 
 fn set_align<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.align: unimplemented");
+    let align = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    *activation.context.stage_align = StageAlign::parse(&align);
     Ok(Value::Undefined)
 }
 
@@ -135,16 +140,23 @@ fn scale_mode<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.scaleMode: unimplemented");
-    Ok("noScale".into())
+    let scale_mode = activation.context.scale_mode.to_string();
+    Ok(AvmString::new(activation.context.gc_context, scale_mode).into())
 }
 
 fn set_scale_mode<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.scaleMode: unimplemented");
+    let scale_mode = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    // Unknown scale modes are ignored.
+    if let Ok(scale_mode) = scale_mode.parse() {
+        *activation.context.scale_mode = scale_mode;
+    }
     Ok(Value::Undefined)
 }
 
@@ -250,15 +250,17 @@ fn from_char_code<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     // TODO: Unpaired surrogates will be replace with Unicode replacement char.
-    let mut out = String::with_capacity(args.len());
+    // Surrogate pairs passed as consecutive arguments are decoded together.
+    let mut code_units = Vec::with_capacity(args.len());
     for arg in args {
         let i = arg.coerce_to_u16(activation)?;
         if i == 0 {
             // Stop at a null-terminator.
             break;
         }
-        out.push(utf16_code_unit_to_char(i));
+        code_units.push(i);
     }
+    let out = utf16_iter_to_string(code_units.into_iter());
     Ok(AvmString::new(activation.context.gc_context, out).into())
 }
 
@@ -402,8 +404,6 @@ fn split<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
-    let delimiter_val = args.get(0).unwrap_or(&Value::Undefined);
-    let delimiter = delimiter_val.coerce_to_string(activation)?;
     let limit = match args.get(1) {
         None | Some(Value::Undefined) => std::usize::MAX,
         Some(n) => std::cmp::max(0, n.coerce_to_i32(activation)?) as usize,
@@ -412,6 +412,16 @@ fn split<'gc>(
         activation.context.gc_context,
         Some(activation.context.avm1.prototypes.array),
     );
+    let delimiter = match args.get(0) {
+        None | Some(Value::Undefined) => {
+            // Without a delimiter, the whole string is the only element.
+            if limit > 0 {
+                array.set_array_element(0, this.into(), activation.context.gc_context);
+            }
+            return Ok(array.into());
+        }
+        Some(delimiter) => delimiter.coerce_to_string(activation)?,
+    };
     if !delimiter.is_empty() {
         for (i, token) in this.split(delimiter.as_ref()).take(limit).enumerate() {
             array.set_array_element(
@@ -424,6 +434,7 @@ fn split<'gc>(
         // When using an empty "" delimiter, Rust's str::split adds an extra beginning and trailing item, but Flash does not.
         // e.g., split("foo", "") returns ["", "f", "o", "o", ""] in Rust but ["f, "o", "o"] in Flash.
         // Special case this to match Flash's behavior.
+        // TODO: Flash splits surrogate pairs into their two halves, which `str` can't hold.
         for (i, token) in this.chars().take(limit).enumerate() {
             array.set_array_element(
                 i,
@@ -556,7 +567,7 @@ fn string_index(i: i32, len: usize) -> usize {
 }
 
 /// Normalizes an wrapping index paramter used in `String` functions such as `slice`.
-/// Negative values will count backwards from `len`.
+/// Negative values will count backwards from `len`, clamping to the start of the string.
 /// The returned index will be within the range of `[0, len]`.
 fn string_wrapping_index(i: i32, len: usize) -> usize {
     if i >= 0 {
@@ -567,11 +578,11 @@ fn string_wrapping_index(i: i32, len: usize) -> usize {
             len
         }
     } else {
-        let i = (-i) as usize;
+        let i = (-(i as i64)) as usize;
         if i <= len {
             len - i
         } else {
-            len
+            0
         }
     }
 }
@@ -594,3 +605,28 @@ fn utf16_code_unit_to_char(c: u16) -> char {
         .unwrap()
         .unwrap_or(char::REPLACEMENT_CHARACTER)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        activation
+            .context
+            .avm1
+            .global_object_cell()
+            .get("String", activation)
+            .unwrap()
+            .coerce_to_object(activation)
+    }
+
+    test_method!(from_char_code, "fromCharCode", setup,
+        [6, 19] => {
+            [] => "",
+            [72, 105] => "Hi",
+            [65, 0, 66] => "A",
+            [0xd83d, 0xde00] => "\u{1f600}",
+            [0xd83d] => "\u{fffd}"
+        }
+    );
+}
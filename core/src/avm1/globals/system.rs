@@ -77,6 +77,19 @@ impl fmt::Display for OperatingSystem {
     }
 }
 
+impl OperatingSystem {
+    /// The operating system that Ruffle itself is running on.
+    pub fn host() -> Self {
+        if cfg!(target_os = "windows") {
+            OperatingSystem::WindowsUnknown
+        } else if cfg!(target_os = "macos") {
+            OperatingSystem::MacOS
+        } else {
+            OperatingSystem::Linux
+        }
+    }
+}
+
 /// The available player manufacturers
 pub enum Manufacturer {
     Windows,
@@ -86,6 +99,18 @@ pub enum Manufacturer {
 }
 
 impl Manufacturer {
+    /// The manufacturer string matching the operating system Ruffle is
+    /// running on.
+    pub fn host() -> Self {
+        if cfg!(target_os = "windows") {
+            Manufacturer::Windows
+        } else if cfg!(target_os = "macos") {
+            Manufacturer::Macintosh
+        } else {
+            Manufacturer::Linux
+        }
+    }
+
     pub fn get_manufacturer_string(&self, version: u8) -> String {
         let os_part = match self {
             Manufacturer::Windows => "Windows",
@@ -112,6 +137,7 @@ impl Manufacturer {
 }
 
 /// The language of the host os
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Language {
     Czech,
     Danish,
@@ -168,6 +194,41 @@ impl Language {
             Language::Turkish => "tr",
         }
     }
+
+    /// Determine the language from a locale string, such as a browser's
+    /// `navigator.language` or a POSIX `LANG` value (e.g. `en-US`, `pt_BR.UTF-8`).
+    pub fn from_locale(locale: &str) -> Self {
+        let locale = locale.to_ascii_lowercase();
+        let mut parts = locale.split(|c| c == '-' || c == '_' || c == '.');
+        let language = parts.next().unwrap_or_default();
+        let region = parts.next().unwrap_or_default();
+
+        match language {
+            "cs" => Language::Czech,
+            "da" => Language::Danish,
+            "nl" => Language::Dutch,
+            "en" => Language::English,
+            "fi" => Language::Finnish,
+            "fr" => Language::French,
+            "de" => Language::German,
+            "hu" => Language::Hungarian,
+            "it" => Language::Italian,
+            "ja" => Language::Japanese,
+            "ko" => Language::Korean,
+            "no" | "nb" | "nn" => Language::Norwegian,
+            "pl" => Language::Polish,
+            "pt" => Language::Portuguese,
+            "ru" => Language::Russian,
+            "zh" if region == "tw" || region == "hk" || region == "hant" => {
+                Language::TraditionalChinese
+            }
+            "zh" => Language::SimplifiedChinese,
+            "es" => Language::Spanish,
+            "sv" => Language::Swedish,
+            "tr" => Language::Turkish,
+            _ => Language::Unknown,
+        }
+    }
 }
 
 /// The supported colors of the screen
@@ -187,11 +248,13 @@ impl fmt::Display for ScreenColor {
     }
 }
 /// The type of the player
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PlayerType {
     StandAlone,
     External,
     PlugIn,
     ActiveX,
+    Desktop,
 }
 
 impl fmt::Display for PlayerType {
@@ -201,6 +264,7 @@ impl fmt::Display for PlayerType {
             PlayerType::External => "External",
             PlayerType::PlugIn => "PlugIn",
             PlayerType::ActiveX => "ActiveX",
+            PlayerType::Desktop => "Desktop",
         })
     }
 }
@@ -270,6 +334,12 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// The domains given to `System.security.allowDomain`
+    pub allowed_domains: Vec<String>,
+    /// The domains given to `System.security.allowInsecureDomain`
+    pub insecure_allowed_domains: Vec<String>,
+    /// The URLs given to `System.security.loadPolicyFile`
+    pub policy_files: Vec<String>,
 }
 
 impl SystemProperties {
@@ -381,7 +451,7 @@ impl Default for SystemProperties {
             exact_settings: true,
             //TODO: default to false on fp>=7, true <= 6
             use_codepage: false,
-            capabilities: EnumSet::empty(),
+            capabilities: SystemCapabilities::Audio | SystemCapabilities::MP3,
             player_type: PlayerType::StandAlone,
             screen_color: ScreenColor::Color,
             // TODO: note for fp <7 this should be the locale and the ui lang for >= 7, on windows
@@ -389,11 +459,14 @@ impl Default for SystemProperties {
             screen_resolution: (0, 0),
             aspect_ratio: 1_f32,
             dpi: 1_f32,
-            manufacturer: Manufacturer::Linux,
-            os: OperatingSystem::Linux,
+            manufacturer: Manufacturer::host(),
+            os: OperatingSystem::host(),
             sandbox_type: SandboxType::LocalTrusted,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            allowed_domains: Vec::new(),
+            insecure_allowed_domains: Vec::new(),
+            policy_files: Vec::new(),
         }
     }
 }
@@ -431,7 +504,7 @@ pub fn show_settings<'gc>(
 
     avm_warn!(
         activation,
-        "System.showSettings({:?}) not not implemented",
+        "System.showSettings({:?}) not implemented",
         panel
     );
     Ok(Value::Undefined)
@@ -578,3 +651,61 @@ pub fn create<'gc>(
 
     system.into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn language_from_locale() {
+        assert_eq!(Language::from_locale("en-US"), Language::English);
+        assert_eq!(Language::from_locale("pt_BR.UTF-8"), Language::Portuguese);
+        assert_eq!(Language::from_locale("zh-CN"), Language::SimplifiedChinese);
+        assert_eq!(Language::from_locale("zh-TW"), Language::TraditionalChinese);
+        assert_eq!(Language::from_locale("nb-NO"), Language::Norwegian);
+        assert_eq!(Language::from_locale("tlh"), Language::Unknown);
+    }
+
+    #[test]
+    fn version_string_follows_player_version() {
+        with_avm(10, |activation, _root| -> Result<(), Error> {
+            let system = SystemProperties {
+                manufacturer: Manufacturer::Windows,
+                ..Default::default()
+            };
+            assert_eq!(
+                system.get_version_string(activation.context.avm1),
+                "WIN 10,0,0,0"
+            );
+
+            let system = SystemProperties {
+                manufacturer: Manufacturer::Macintosh,
+                ..Default::default()
+            };
+            assert_eq!(
+                system.get_version_string(activation.context.avm1),
+                "MAC 10,0,0,0"
+            );
+            Ok(())
+        });
+        with_avm(32, |activation, _root| -> Result<(), Error> {
+            let system = SystemProperties {
+                manufacturer: Manufacturer::Linux,
+                ..Default::default()
+            };
+            assert_eq!(
+                system.get_version_string(activation.context.avm1),
+                "LNX 32,0,0,0"
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn player_type_names() {
+        assert_eq!(PlayerType::StandAlone.to_string(), "StandAlone");
+        assert_eq!(PlayerType::PlugIn.to_string(), "PlugIn");
+        assert_eq!(PlayerType::Desktop.to_string(), "Desktop");
+    }
+}
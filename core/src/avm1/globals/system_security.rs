@@ -8,37 +8,56 @@ use enumset::EnumSet;
 use gc_arena::MutationContext;
 use std::convert::Into;
 
+/// Coerces every argument to a string, as the domains or URLs given to a security call.
+fn string_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Vec<String>, Error<'gc>> {
+    args.iter()
+        .map(|arg| Ok(arg.coerce_to_string(activation)?.to_string()))
+        .collect()
+}
+
+/// Implements `System.security.allowDomain`.
+///
+/// There is no sandbox yet, so every domain may already script this movie. The domains are only
+/// recorded.
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.allowDomain() not implemented");
-    Ok(Value::Undefined)
+    let domains = string_args(activation, args)?;
+    activation.context.system.allowed_domains.extend(domains);
+    Ok(true.into())
 }
 
+/// Implements `System.security.allowInsecureDomain`, which is recorded like `allowDomain`.
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
-    Ok(Value::Undefined)
+    let domains = string_args(activation, args)?;
+    activation
+        .context
+        .system
+        .insecure_allowed_domains
+        .extend(domains);
+    Ok(true.into())
 }
 
+/// Implements `System.security.loadPolicyFile`.
+///
+/// Loads aren't restricted by policy files yet, so the URL is only recorded.
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
-    Ok(Value::Undefined)
+    let urls = string_args(activation, args)?;
+    activation.context.system.policy_files.extend(urls);
+    Ok(true.into())
 }
 
 fn escape_domain<'gc>(
@@ -1,6 +1,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::globals::display_object;
 use crate::avm1::property::Attribute::*;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
@@ -278,6 +279,195 @@ pub fn set_multiline<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_scroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scroll = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_scroll(scroll, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn maxscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.maxscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn bottom_scroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.bottom_scroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn hscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.hscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_hscroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let hscroll = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_hscroll(hscroll, &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn maxhscroll<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.maxhscroll().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn mouse_wheel_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_mouse_wheel_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let enabled = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .as_bool(activation.current_swf_version());
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_mouse_wheel_enabled(activation.context.gc_context, enabled);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn condense_white<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(etext.condense_white().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_condense_white<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let condense_white = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .as_bool(activation.current_swf_version());
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_condense_white(activation.context.gc_context, condense_white);
+    }
+
+    Ok(Value::Undefined)
+}
+
 fn variable<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -396,6 +586,129 @@ pub fn set_auto_size<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(if etext.is_editable() {
+            "input".into()
+        } else {
+            "dynamic".into()
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let field_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        // Unknown types are ignored.
+        if field_type.eq_ignore_ascii_case("input") {
+            etext.set_editable(true, activation.context.gc_context);
+        } else if field_type.eq_ignore_ascii_case("dynamic") {
+            etext.set_editable(false, activation.context.gc_context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        return Ok(match etext.restrict() {
+            Some(restrict) => AvmString::new(activation.context.gc_context, restrict).into(),
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_restrict<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let restrict = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Undefined | Value::Null => None,
+        value => Some(value.coerce_to_string(activation)?),
+    };
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_restrict(restrict.as_deref(), activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn max_chars<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        // Fields without a limit report `null`.
+        return Ok(match etext.max_chars() {
+            0 => Value::Null,
+            max_chars => max_chars.into(),
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_max_chars<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let max_chars = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(etext) = this
+        .as_display_object()
+        .and_then(|dobj| dobj.as_edit_text())
+    {
+        etext.set_max_chars(max_chars.max(0), activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
@@ -613,6 +926,178 @@ pub fn attach_virtual_properties<'gc>(
         )),
         ReadOnly.into(),
     );
+    object.add_property(
+        gc_context,
+        "scroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(scroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_scroll),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(maxscroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "bottomScroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bottom_scroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "hscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(hscroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_hscroll),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxhscroll",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(maxhscroll),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "mouseWheelEnabled",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(mouse_wheel_enabled),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_mouse_wheel_enabled),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "type",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(get_type),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_type),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "restrict",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(restrict),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_restrict),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "maxChars",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(max_chars),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_max_chars),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+    object.add_property(
+        gc_context,
+        "condenseWhite",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(condense_white),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_condense_white),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        ReadOnly.into(),
+    );
+}
+
+/// Make a text field object an `AsBroadcaster`.
+///
+/// Text fields start out listening to themselves, which is how handlers such
+/// as `onScroller` defined on the field itself get called.
+pub fn initialize_broadcaster<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    object: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+    array_proto: Object<'gc>,
+) {
+    broadcaster_functions.initialize(gc_context, object, array_proto);
+
+    let listeners = ScriptObject::array(gc_context, Some(array_proto));
+    listeners.set_array_element(0, object.into(), gc_context);
+    object.define_value(gc_context, "_listeners", listeners.into(), DontEnum.into());
 }
 
 fn get_new_text_format<'gc>(
@@ -0,0 +1,186 @@
+//! Video prototype
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject, NativeFunction};
+use crate::avm1::globals::display_object;
+use crate::avm1::property::Attribute::*;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::display_object::{TDisplayObject, Video};
+use gc_arena::MutationContext;
+
+macro_rules! with_video {
+    ( $gc_context: ident, $object:ident, $fn_proto: expr, $($name:expr => $fn:expr),* ) => {{
+        $(
+            $object.force_set_function(
+                $name,
+                |activation: &mut Activation<'_, 'gc, '_>, this, args| -> Result<Value<'gc>, Error<'gc>> {
+                    if let Some(video) = this.as_display_object().and_then(|o| o.as_video()) {
+                        return $fn(video, activation, args);
+                    }
+                    Ok(Value::Undefined)
+                } as crate::avm1::function::NativeFunction<'gc>,
+                $gc_context,
+                DontDelete | ReadOnly | DontEnum,
+                $fn_proto
+            );
+        )*
+    }};
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    display_object::define_display_object_proto(gc_context, object, fn_proto);
+
+    with_video!(
+        gc_context,
+        object,
+        Some(fn_proto),
+        "attachVideo" => attach_video,
+        "clear" => clear
+    );
+
+    let properties: &[(&str, NativeFunction<'gc>, Option<NativeFunction<'gc>>)] = &[
+        ("width", width, None),
+        ("height", height, None),
+        ("smoothing", smoothing, Some(set_smoothing)),
+        ("deblocking", deblocking, Some(set_deblocking)),
+    ];
+
+    for &(name, getter, setter) in properties {
+        object.add_property(
+            gc_context,
+            name,
+            FunctionObject::function(
+                gc_context,
+                Executable::Native(getter),
+                Some(fn_proto),
+                fn_proto,
+            ),
+            setter.map(|setter| {
+                FunctionObject::function(
+                    gc_context,
+                    Executable::Native(setter),
+                    Some(fn_proto),
+                    fn_proto,
+                )
+            }),
+            DontDelete | DontEnum,
+        );
+    }
+
+    object.into()
+}
+
+/// Implements `Video` constructor.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+fn as_video<'gc>(this: Object<'gc>) -> Option<Video<'gc>> {
+    this.as_display_object().and_then(|o| o.as_video())
+}
+
+/// Implements `Video.attachVideo`.
+///
+/// Anything other than a `NetStream`, such as `null`, detaches the current stream.
+/// Cameras are not supported.
+fn attach_video<'gc>(
+    video: Video<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let source = match args.get(0) {
+        Some(Value::Object(source)) => Some((*source).into()),
+        _ => None,
+    };
+    activation
+        .context
+        .net_streams
+        .attach_video(activation.context.gc_context, video, source);
+
+    Ok(Value::Undefined)
+}
+
+fn clear<'gc>(
+    video: Video<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    video.clear(activation.context.gc_context);
+
+    Ok(Value::Undefined)
+}
+
+fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(as_video(this).map_or(Value::Undefined, |video| video.frame_size().0.into()))
+}
+
+fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(as_video(this).map_or(Value::Undefined, |video| video.frame_size().1.into()))
+}
+
+fn smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(as_video(this).map_or(Value::Undefined, |video| video.smoothing().into()))
+}
+
+fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = as_video(this) {
+        let smoothing = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .as_bool(activation.current_swf_version());
+        video.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn deblocking<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(as_video(this).map_or(Value::Undefined, |video| video.deblocking().into()))
+}
+
+fn set_deblocking<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = as_video(this) {
+        let deblocking = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_i32(activation)?;
+        video.set_deblocking(activation.context.gc_context, deblocking);
+    }
+
+    Ok(Value::Undefined)
+}
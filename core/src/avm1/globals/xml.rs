@@ -3,6 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::load_vars;
 use crate::avm1::object::script_object::ScriptObject;
 use crate::avm1::object::xml_object::XMLObject;
 use crate::avm1::property::Attribute::*;
@@ -10,23 +11,19 @@ use crate::avm1::{AvmString, Object, TObject, Value};
 use crate::avm_warn;
 use crate::backend::navigator::RequestOptions;
 use crate::xml;
-use crate::xml::{XMLDocument, XMLNode};
+use crate::xml::{ParseError, XMLDocument, XMLNode};
 use enumset::EnumSet;
 use gc_arena::MutationContext;
-use quick_xml::Error as ParseError;
+use quick_xml::Error as QXError;
 
 pub const XML_NO_ERROR: f64 = 0.0;
-#[allow(dead_code)]
 pub const XML_CDATA_NOT_TERMINATED: f64 = -2.0;
 pub const XML_DECL_NOT_TERMINATED: f64 = -3.0;
-#[allow(dead_code)]
 pub const XML_DOCTYPE_NOT_TERMINATED: f64 = -4.0;
-#[allow(dead_code)]
 pub const XML_COMMENT_NOT_TERMINATED: f64 = -5.0;
 pub const XML_ELEMENT_MALFORMED: f64 = -6.0;
 pub const XML_OUT_OF_MEMORY: f64 = -7.0;
 pub const XML_ATTRIBUTE_NOT_TERMINATED: f64 = -8.0;
-#[allow(dead_code)]
 pub const XML_MISMATCHED_START: f64 = -9.0;
 pub const XML_MISMATCHED_END: f64 = -10.0;
 
@@ -40,6 +37,19 @@ fn is_as2_compatible(node: XMLNode<'_>) -> bool {
     node.is_document_root() || node.is_element() || node.is_text()
 }
 
+/// Read the `ignoreWhite` flag of an XML document object.
+///
+/// The flag only takes effect when parsing; changing it has no effect on
+/// nodes that were already parsed.
+fn ignore_white<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<bool, Error<'gc>> {
+    Ok(this
+        .get("ignoreWhite", activation)?
+        .as_bool(activation.current_swf_version()))
+}
+
 /// XMLNode constructor
 pub fn xmlnode_constructor<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -224,7 +234,10 @@ pub fn xmlnode_to_string<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(node) = this.as_xml_node() {
-        let result = node.into_string(&mut is_as2_compatible);
+        let result = document_prologue(node).and_then(|mut prologue| {
+            prologue.push_str(&node.into_string(&mut is_as2_compatible)?);
+            Ok(prologue)
+        });
 
         return Ok(AvmString::new(
             activation.context.gc_context,
@@ -239,6 +252,27 @@ pub fn xmlnode_to_string<'gc>(
     Ok("".into())
 }
 
+/// Generate the XML and DOCTYPE declarations that start a document's string
+/// form.
+///
+/// Nodes other than document roots have no prologue.
+fn document_prologue(node: XMLNode<'_>) -> Result<String, xml::Error> {
+    let mut prologue = String::new();
+
+    if node.is_document_root() {
+        let document = node.document();
+        if let Some(xmldecl) = document.xmldecl_string()? {
+            prologue.push_str(&xmldecl);
+        }
+
+        if let Some(doctype) = document.doctype() {
+            prologue.push_str(&doctype.into_string(&mut |_| true)?);
+        }
+    }
+
+    Ok(prologue)
+}
+
 pub fn xmlnode_local_name<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -781,8 +815,13 @@ pub fn xml_constructor<'gc>(
             xmlnode.introduce_script_object(activation.context.gc_context, this);
             this_node.swap(activation.context.gc_context, xmlnode);
 
-            if let Err(e) = this_node.replace_with_str(activation.context.gc_context, string, true)
-            {
+            let ignore_white = ignore_white(activation, this)?;
+            if let Err(e) = this_node.replace_with_str(
+                activation.context.gc_context,
+                string,
+                true,
+                ignore_white,
+            ) {
                 avm_warn!(
                     activation,
                     "Couldn't replace_with_str inside of XML constructor: {}",
@@ -884,7 +923,13 @@ pub fn xml_parse_xml<'gc>(
             }
         }
 
-        let result = node.replace_with_str(activation.context.gc_context, &xmlstring, true);
+        let ignore_white = ignore_white(activation, this)?;
+        let result = node.replace_with_str(
+            activation.context.gc_context,
+            &xmlstring,
+            true,
+            ignore_white,
+        );
         if let Err(e) = result {
             avm_warn!(activation, "XML parsing error: {}", e);
         }
@@ -929,6 +974,51 @@ pub fn xml_load<'gc>(
     }
 }
 
+pub fn xml_send_and_load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let target = match args.get(1) {
+        Some(&Value::Object(target)) => target,
+        _ => return Ok(false.into()),
+    };
+
+    let target_node = match target.as_xml_node() {
+        Some(node) if url != Value::Null => node,
+        _ => return Ok(false.into()),
+    };
+
+    let url = url.coerce_to_string(activation)?;
+    let body = this
+        .call_method("toString", &[], activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let content_type = this
+        .get("contentType", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let headers = load_vars::request_headers(activation, this)?;
+    let request_options =
+        RequestOptions::post(Some((body.into_bytes(), content_type))).with_headers(headers);
+
+    target.set("loaded", false.into(), activation)?;
+
+    let fetch = activation.context.navigator.fetch(&url, request_options);
+    let target_clip = activation.target_clip_or_root();
+    let process = activation.context.load_manager.load_xml_into_node(
+        activation.context.player.clone().unwrap(),
+        target_node,
+        target_clip,
+        fetch,
+    );
+
+    activation.context.navigator.spawn_future(process);
+
+    Ok(true.into())
+}
+
 pub fn xml_on_data<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -1020,23 +1110,41 @@ pub fn xml_status<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(node) = this.as_xml_node() {
-        return match node.document().last_parse_error() {
-            None => Ok(XML_NO_ERROR.into()),
-            Some(err) => match err.ref_error() {
-                ParseError::UnexpectedEof(_) => Ok(Value::Number(XML_ELEMENT_MALFORMED)),
-                ParseError::EndEventMismatch { .. } => Ok(Value::Number(XML_MISMATCHED_END)),
-                ParseError::XmlDeclWithoutVersion(_) => Ok(Value::Number(XML_DECL_NOT_TERMINATED)),
-                ParseError::NameWithQuote(_) => Ok(Value::Number(XML_ELEMENT_MALFORMED)),
-                ParseError::NoEqAfterName(_) => Ok(Value::Number(XML_ELEMENT_MALFORMED)),
-                ParseError::UnquotedValue(_) => Ok(Value::Number(XML_ATTRIBUTE_NOT_TERMINATED)),
-                ParseError::DuplicatedAttribute(_, _) => Ok(Value::Number(XML_ELEMENT_MALFORMED)),
-                _ => Ok(Value::Number(XML_OUT_OF_MEMORY)), //Not accounted for:
-                                                           //ParseError::UnexpectedToken(_)
-                                                           //ParseError::UnexpectedBang
-                                                           //ParseError::TextNotFound
-                                                           //ParseError::EscapeError(_)
+        return Ok(match node.document().last_parse_error() {
+            None => XML_NO_ERROR,
+            Some(ParseError::UnclosedElement(_)) => XML_MISMATCHED_START,
+            Some(ParseError::UnmatchedEndTag(_)) => XML_MISMATCHED_END,
+            Some(ParseError::Reader(err)) => match &*err {
+                // The reader names the construct that was cut off by the end
+                // of the document.
+                QXError::UnexpectedEof(construct) => {
+                    let construct = construct.to_ascii_lowercase();
+                    if construct.contains("cdata") {
+                        XML_CDATA_NOT_TERMINATED
+                    } else if construct.contains("doctype") {
+                        XML_DOCTYPE_NOT_TERMINATED
+                    } else if construct.contains("comment") {
+                        XML_COMMENT_NOT_TERMINATED
+                    } else if construct.contains("decl") {
+                        XML_DECL_NOT_TERMINATED
+                    } else {
+                        XML_ELEMENT_MALFORMED
+                    }
+                }
+                QXError::EndEventMismatch { .. } => XML_MISMATCHED_END,
+                QXError::XmlDeclWithoutVersion(_) => XML_DECL_NOT_TERMINATED,
+                QXError::NameWithQuote(_)
+                | QXError::NoEqAfterName(_)
+                | QXError::DuplicatedAttribute(_, _)
+                | QXError::UnexpectedToken(_)
+                | QXError::UnexpectedBang => XML_ELEMENT_MALFORMED,
+                QXError::UnquotedValue(_) => XML_ATTRIBUTE_NOT_TERMINATED,
+                _ => XML_OUT_OF_MEMORY, //Not accounted for:
+                                        //QXError::TextNotFound
+                                        //QXError::EscapeError(_)
             },
-        };
+        }
+        .into());
     }
 
     Ok(Value::Undefined)
@@ -1133,6 +1241,26 @@ pub fn create_xml_proto<'gc>(
         EnumSet::empty(),
         Some(fn_proto),
     );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "sendAndLoad",
+        xml_send_and_load,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "addRequestHeader",
+        load_vars::add_request_header,
+        gc_context,
+        EnumSet::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.define_value(
+        gc_context,
+        "contentType",
+        "application/x-www-form-urlencoded".into(),
+        EnumSet::empty(),
+    );
 
     xml_proto
 }
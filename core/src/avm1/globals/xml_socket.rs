@@ -0,0 +1,147 @@
+//! AVM1 XMLSocket object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm_warn;
+use gc_arena::MutationContext;
+
+/// Implements `XMLSocket`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    use Attribute::*;
+
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        DontDelete | DontEnum | ReadOnly,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // A null or undefined host means the host the movie was loaded from.
+    let host = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => activation
+            .context
+            .swf
+            .url()
+            .and_then(|url| url::Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(|host| host.to_string()))
+            .unwrap_or_else(|| "localhost".to_string()),
+        Some(host) => host.coerce_to_string(activation)?.to_string(),
+    };
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    // Flash Player refuses to connect to privileged ports.
+    if !(1024.0..=65535.0).contains(&port) {
+        avm_warn!(activation, "XMLSocket.connect: Invalid port {}", port);
+        return Ok(false.into());
+    }
+
+    activation
+        .context
+        .sockets
+        .connect(activation.context.navigator, this, host, port as u16);
+
+    Ok(true.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let data = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    // Messages are terminated by a null byte.
+    let mut data = data.as_bytes().to_vec();
+    data.push(0);
+
+    if !activation.context.sockets.send(this, data) {
+        avm_warn!(activation, "XMLSocket.send: Socket is not connected");
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.sockets.close(this);
+
+    Ok(Value::Undefined)
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation parses the message and forwards it to `onXML`.
+    let src = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let xml_constructor = activation
+        .context
+        .avm1
+        .global_object_cell()
+        .get("XML", activation)?
+        .coerce_to_object(activation);
+    let xml = xml_constructor.construct(activation, &[src])?;
+
+    this.call_method("onXML", &[xml.into()], activation)?;
+
+    Ok(Value::Undefined)
+}
@@ -838,24 +838,33 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
 mod tests {
     use super::*;
 
+    use crate::audio_manager::AudioManager;
     use crate::avm1::activation::ActivationIdentifier;
     use crate::avm1::function::Executable;
     use crate::avm1::globals::system::SystemProperties;
     use crate::avm1::property::Attribute::*;
-    use crate::avm1::{Avm1, Timers};
+    use crate::avm1::Avm1;
     use crate::avm2::Avm2;
     use crate::backend::audio::NullAudioBackend;
+    use crate::backend::font::NullFontProvider;
     use crate::backend::input::NullInputBackend;
     use crate::backend::locale::NullLocaleBackend;
     use crate::backend::navigator::NullNavigatorBackend;
     use crate::backend::render::NullRenderer;
     use crate::backend::storage::MemoryStorageBackend;
+    use crate::backend::video::NullVideoBackend;
     use crate::context::UpdateContext;
     use crate::display_object::MovieClip;
+    use crate::focus_tracker::FocusTracker;
     use crate::library::Library;
     use crate::loader::LoadManager;
+    use crate::local_connection::LocalConnections;
+    use crate::net_stream::NetStreams;
     use crate::prelude::*;
+    use crate::print_job::PrintJobs;
+    use crate::socket::Sockets;
     use crate::tag_utils::{SwfMovie, SwfSlice};
+    use crate::timer::Timers;
     use gc_arena::rootless_arena;
     use rand::{rngs::SmallRng, SeedableRng};
     use std::collections::{BTreeMap, HashMap};
@@ -886,7 +895,9 @@ mod tests {
                 rng: &mut SmallRng::from_seed([0u8; 16]),
                 action_queue: &mut crate::context::ActionQueue::new(),
                 audio: &mut NullAudioBackend::new(),
+                audio_manager: &mut AudioManager::new(),
                 input: &mut NullInputBackend::new(),
+                keyboard: &mut Default::default(),
                 background_color: &mut Color {
                     r: 0,
                     g: 0,
@@ -897,11 +908,20 @@ mod tests {
                 navigator: &mut NullNavigatorBackend::new(),
                 renderer: &mut NullRenderer::new(),
                 locale: &mut NullLocaleBackend::new(),
+                font_provider: &mut NullFontProvider::new(),
+                video: &mut NullVideoBackend::new(),
                 system_prototypes: avm1.prototypes().clone(),
                 mouse_hovered_object: None,
+                focus_tracker: FocusTracker::new(gc_context),
                 mouse_position: &(Twips::new(0), Twips::new(0)),
                 drag_object: &mut None,
+                mouse_cursor: &mut Default::default(),
                 stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
+                view_matrix: Default::default(),
+                scale_mode: &mut Default::default(),
+                stage_align: &mut Default::default(),
+                quality: &mut Default::default(),
+                frame_rate: &mut 12.0,
                 player: None,
                 load_manager: &mut LoadManager::new(),
                 system: &mut SystemProperties::default(),
@@ -910,10 +930,16 @@ mod tests {
                 shared_objects: &mut HashMap::new(),
                 unbound_text_fields: &mut Vec::new(),
                 timers: &mut Timers::new(),
+                sockets: &mut Sockets::new(),
+                local_connections: &mut LocalConnections::new(),
+                print_jobs: &mut PrintJobs::new(),
+                net_streams: &mut NetStreams::new(),
                 needs_render: &mut false,
                 avm1: &mut avm1,
                 avm2: &mut avm2,
                 external_interface: &mut Default::default(),
+                current_context_menu: &mut None,
+                is_user_event: false,
             };
 
             root.post_instantiation(&mut context, root, None, false, false);
@@ -10,6 +10,7 @@ use crate::avm_warn;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, EditText, MovieClip};
 use crate::property_map::PropertyMap;
+use crate::stage::StageQuality;
 use enumset::EnumSet;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::borrow::Cow;
@@ -844,33 +845,52 @@ fn high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
-    Ok(1.into())
+    let high_quality = match *activation.context.quality {
+        StageQuality::Low => 0,
+        StageQuality::Medium | StageQuality::High => 1,
+        StageQuality::Best => 2,
+    };
+    Ok(high_quality.into())
 }
 
 fn set_high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
+    let quality = match val.coerce_to_i32(activation)? {
+        0 => StageQuality::Low,
+        1 => StageQuality::High,
+        2 => StageQuality::Best,
+        _ => return Ok(()),
+    };
+    *activation.context.quality = quality;
     Ok(())
 }
 
 fn focus_rect<'gc>(
-    activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Ok(Value::Null)
+    // Root movies show the focus rectangle unless told otherwise;
+    // other clips defer to their root until `_focusrect` is set on them.
+    match this.focus_rect() {
+        Some(focus_rect) => Ok(focus_rect.into()),
+        None if this.parent().is_none() => Ok(true.into()),
+        None => Ok(Value::Null),
+    }
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    let focus_rect = match val {
+        Value::Undefined | Value::Null => None,
+        val => Some(val.as_bool(activation.current_swf_version())),
+    };
+    this.set_focus_rect(activation.context.gc_context, focus_rect);
     Ok(())
 }
 
@@ -895,16 +915,19 @@ fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
-    Ok("HIGH".into())
+    let quality = activation.context.quality.to_string();
+    Ok(AvmString::new(activation.context.gc_context, quality).into())
 }
 
 fn set_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
+    // Unknown qualities are ignored.
+    if let Ok(quality) = val.coerce_to_string(activation)?.parse() {
+        *activation.context.quality = quality;
+    }
     Ok(())
 }
 
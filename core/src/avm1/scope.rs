@@ -202,6 +202,28 @@ impl<'gc> Scope<'gc> {
         }
     }
 
+    /// Returns the nearest scope in a scope chain that was not added by a
+    /// `with` block.
+    ///
+    /// Variables declared with `var` inside a `with` block are defined on this
+    /// scope, not on the `with` object.
+    pub fn nearest_non_with_scope(mut scope: GcCell<'gc, Self>) -> GcCell<'gc, Self> {
+        loop {
+            let parent = {
+                let read = scope.read();
+                if read.class != ScopeClass::With {
+                    return scope;
+                }
+                read.parent
+            };
+
+            match parent {
+                Some(parent) => scope = parent,
+                None => return scope,
+            }
+        }
+    }
+
     /// Returns a reference to the current local scope object.
     pub fn locals(&self) -> &Object<'gc> {
         &self.values
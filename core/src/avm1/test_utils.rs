@@ -1,20 +1,29 @@
+use crate::audio_manager::AudioManager;
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::error::Error;
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object, Timers, UpdateContext};
+use crate::avm1::{Avm1, Object, UpdateContext};
 use crate::avm2::Avm2;
 use crate::backend::audio::NullAudioBackend;
+use crate::backend::font::NullFontProvider;
 use crate::backend::input::NullInputBackend;
 use crate::backend::locale::NullLocaleBackend;
 use crate::backend::navigator::NullNavigatorBackend;
 use crate::backend::render::NullRenderer;
 use crate::backend::storage::MemoryStorageBackend;
+use crate::backend::video::NullVideoBackend;
 use crate::context::ActionQueue;
 use crate::display_object::{MovieClip, TDisplayObject};
+use crate::focus_tracker::FocusTracker;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
+use crate::net_stream::NetStreams;
 use crate::prelude::*;
+use crate::print_job::PrintJobs;
+use crate::socket::Sockets;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::timer::Timers;
 use gc_arena::{rootless_arena, MutationContext};
 use rand::{rngs::SmallRng, SeedableRng};
 use std::collections::{BTreeMap, HashMap};
@@ -46,7 +55,9 @@ where
             levels: &mut levels,
             rng: &mut SmallRng::from_seed([0u8; 16]),
             audio: &mut NullAudioBackend::new(),
+            audio_manager: &mut AudioManager::new(),
             input: &mut NullInputBackend::new(),
+            keyboard: &mut Default::default(),
             action_queue: &mut ActionQueue::new(),
             background_color: &mut Color {
                 r: 0,
@@ -58,11 +69,20 @@ where
             navigator: &mut NullNavigatorBackend::new(),
             renderer: &mut NullRenderer::new(),
             locale: &mut NullLocaleBackend::new(),
+            font_provider: &mut NullFontProvider::new(),
+            video: &mut NullVideoBackend::new(),
             system_prototypes: avm1.prototypes().clone(),
             mouse_hovered_object: None,
+            focus_tracker: FocusTracker::new(gc_context),
             mouse_position: &(Twips::new(0), Twips::new(0)),
             drag_object: &mut None,
+            mouse_cursor: &mut Default::default(),
             stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
+            view_matrix: Default::default(),
+            scale_mode: &mut Default::default(),
+            stage_align: &mut Default::default(),
+            quality: &mut Default::default(),
+            frame_rate: &mut 12.0,
             player: None,
             load_manager: &mut LoadManager::new(),
             system: &mut SystemProperties::default(),
@@ -71,10 +91,16 @@ where
             shared_objects: &mut HashMap::new(),
             unbound_text_fields: &mut Vec::new(),
             timers: &mut Timers::new(),
+            sockets: &mut Sockets::new(),
+            local_connections: &mut LocalConnections::new(),
+            print_jobs: &mut PrintJobs::new(),
+            net_streams: &mut NetStreams::new(),
             needs_render: &mut false,
             avm1: &mut avm1,
             avm2: &mut avm2,
             external_interface: &mut Default::default(),
+            current_context_menu: &mut None,
+            is_user_event: false,
         };
         root.post_instantiation(&mut context, root, None, false, false);
         root.set_name(context.gc_context, "");
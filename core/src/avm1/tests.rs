@@ -1,23 +1,71 @@
 use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::test_utils::with_avm;
-use crate::avm1::TObject;
+use crate::avm1::{ScriptObject, TObject, Value};
+use crate::backend::navigator::NavigationMethod;
+use std::borrow::Cow;
 
 #[test]
-fn locals_into_form_values() {
+fn object_into_form_values() {
     with_avm(19, |activation, _this| -> Result<(), Error> {
         let my_locals = activation.scope().locals().to_owned();
         my_locals
             .set("value1", "string".into(), activation)
             .unwrap();
         my_locals.set("value2", 2.0.into(), activation).unwrap();
-        let my_local_values = activation.locals_into_form_values();
+        let my_local_values = activation.object_into_form_values(my_locals);
 
         assert_eq!(my_local_values.len(), 2);
         assert_eq!(my_local_values.get("value1"), Some(&"string".to_string()));
         assert_eq!(my_local_values.get("value2"), Some(&"2".to_string()));
         assert_eq!(
             my_local_values.keys().cloned().collect::<Vec<String>>(),
-            vec!["value2".to_string(), "value1".to_string()]
+            vec!["value1".to_string(), "value2".to_string()]
+        );
+
+        Ok(())
+    });
+}
+
+#[test]
+fn object_into_request_options() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        let object = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        object.set("name", "Ruffle Player".into(), activation)?;
+        object.set("score", 100.into(), activation)?;
+        let callback = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(|_, _, _| Ok(Value::Undefined)),
+            Some(activation.context.avm1.prototypes.function),
+            activation.context.avm1.prototypes.object,
+        );
+        object.set("onData", callback.into(), activation)?;
+
+        let (url, opts) = activation.object_into_request_options(
+            object.into(),
+            Cow::Borrowed("submit.php?id=1"),
+            Some(NavigationMethod::GET),
+        );
+        assert_eq!(url, "submit.php?id=1&name=Ruffle+Player&score=100");
+        assert_eq!(opts.method(), NavigationMethod::GET);
+        assert_eq!(opts.body(), &None);
+
+        let (url, opts) = activation.object_into_request_options(
+            object.into(),
+            Cow::Borrowed("submit.php"),
+            Some(NavigationMethod::POST),
+        );
+        assert_eq!(url, "submit.php");
+        assert_eq!(opts.method(), NavigationMethod::POST);
+        assert_eq!(
+            opts.body(),
+            &Some((
+                b"name=Ruffle+Player&score=100".to_vec(),
+                "application/x-www-form-urlencoded".to_string()
+            ))
         );
 
         Ok(())
@@ -1,16 +1,24 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
 use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::XmlSettings;
+use crate::avm2::globals::flash::display::loaderinfo;
+use crate::avm2::globals::flash::utils::timer;
 use crate::avm2::globals::SystemPrototypes;
-use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::object::{ScriptObject, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::Value;
 use crate::context::UpdateContext;
-use crate::tag_utils::SwfSlice;
-use gc_arena::{Collect, GcCell, MutationContext};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::touch::MultitouchInputMode;
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 
 #[macro_export]
@@ -23,22 +31,44 @@ macro_rules! avm_debug {
 }
 
 mod activation;
+mod amf;
+mod array;
+mod bitmapdata;
+mod bytearray;
 mod class;
+mod dictionary;
+mod display;
+mod domain;
+mod e4x;
+mod events;
 mod function;
 mod globals;
+mod matrix3d;
 mod method;
 mod names;
+mod net_stream;
 mod object;
 mod property;
 mod property_map;
 mod return_value;
 mod scope;
 mod script;
+mod shared_object;
 mod slot;
 mod string;
 mod traits;
 mod value;
 
+pub use crate::avm2::bytearray::ObjectEncoding;
+pub use crate::avm2::display::{
+    broadcast_frame_event, construct_document_class, mouse_down, mouse_up, run_frame_scripts,
+    text_change, text_input, text_link,
+};
+pub use crate::avm2::domain::Domain;
+pub use crate::avm2::globals::flash::display::loaderinfo::LoadedContentInfo;
+pub use crate::avm2::net_stream::send_net_stream_events;
+pub use crate::avm2::object::Object;
+
 /// Boxed error alias.
 ///
 /// As AVM2 is a far stricter VM than AVM1, this may eventually be replaced
@@ -55,9 +85,49 @@ pub struct Avm2<'gc> {
     /// Global scope object.
     globals: Object<'gc>,
 
+    /// The domain that holds all player globals.
+    global_domain: Domain<'gc>,
+
     /// System prototypes.
     system_prototypes: Option<SystemPrototypes<'gc>>,
 
+    /// Classes registered with `flash.net.registerClassAlias`, by alias.
+    class_aliases: HashMap<String, Object<'gc>>,
+
+    /// Shared objects opened with `SharedObject.getLocal`, by storage key.
+    shared_objects: HashMap<String, Object<'gc>>,
+
+    /// How touch input is reported, as set by `Multitouch.inputMode`.
+    multitouch_input_mode: MultitouchInputMode,
+
+    /// The static settings of the `XML` class.
+    xml_settings: XmlSettings,
+
+    /// The bytecode methods currently being executed, innermost last.
+    call_stack: Vec<Gc<'gc, BytecodeMethod<'gc>>>,
+
+    /// Incremented whenever any domain's memory is replaced, so that
+    /// activations know to look up their domain memory again.
+    domain_memory_generation: u32,
+
+    /// The `Stage`, once a script has asked for it.
+    stage: Option<Object<'gc>>,
+
+    /// The object the mouse last went down on, which is clicked if the mouse
+    /// also goes up on it.
+    mouse_down_target: Option<Object<'gc>>,
+
+    /// The display objects, and the stage, that have listened for a broadcast
+    /// event such as `enterFrame`, in the order they first did.
+    ///
+    /// They are kept, and keep hearing about each frame, even when they are
+    /// not on the display list.
+    broadcast_list: Vec<Object<'gc>>,
+
+    /// Whether movie clips' frame scripts are being run, so that gotos wait
+    /// for the next pass instead of running the scripts they reach.
+    running_frame_scripts: bool,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -66,11 +136,23 @@ impl<'gc> Avm2<'gc> {
     /// Construct a new AVM interpreter.
     pub fn new(mc: MutationContext<'gc, '_>) -> Self {
         let globals = ScriptObject::bare_object(mc);
+        let global_domain = Domain::global_domain(mc, globals);
 
         Self {
             stack: Vec::new(),
             globals,
+            global_domain,
             system_prototypes: None,
+            class_aliases: HashMap::new(),
+            shared_objects: HashMap::new(),
+            multitouch_input_mode: MultitouchInputMode::default(),
+            xml_settings: XmlSettings::default(),
+            call_stack: Vec::new(),
+            domain_memory_generation: 0,
+            stage: None,
+            mouse_down_target: None,
+            broadcast_list: Vec::new(),
+            running_frame_scripts: false,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -100,29 +182,58 @@ impl<'gc> Avm2<'gc> {
         init_activation.run_stack_frame_for_script(script)
     }
 
+    /// Return the domain that the scripts of a movie are loaded into.
+    ///
+    /// Movies that were not loaded into a particular domain get their own
+    /// child of the player globals domain.
+    pub fn movie_domain(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        movie: Arc<SwfMovie>,
+    ) -> Domain<'gc> {
+        let library = context.library.library_for_movie_mut(movie);
+        if let Some(domain) = library.avm2_domain() {
+            return domain;
+        }
+
+        let domain = Domain::movie_domain(context.gc_context, context.avm2.global_domain);
+        library.set_avm2_domain(domain);
+        domain
+    }
+
     /// Load an ABC file embedded in a `SwfSlice`.
     ///
-    /// The `SwfSlice` must resolve to the contents of an ABC file.
+    /// The `SwfSlice` must resolve to the contents of an ABC file. Its
+    /// definitions are exported into the given `domain`; names that are
+    /// already defined in the domain or its parents are not redefined.
     pub fn load_abc(
         abc: SwfSlice,
         _abc_name: &str,
         _lazy_init: bool,
+        domain: Domain<'gc>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) -> Result<(), Error> {
         let mut read = Reader::new(abc.as_ref());
 
         let abc_file = Rc::new(read.read()?);
-        let tunit = TranslationUnit::from_abc(abc_file.clone(), context.gc_context);
+        let tunit =
+            TranslationUnit::from_abc(abc_file.clone(), abc.movie.clone(), context.gc_context);
 
         for i in (0..abc_file.scripts.len()).rev() {
             let script = tunit.load_script(i as u32, context.avm2, context.gc_context)?;
-            let mut globals = context.avm2.globals();
+            let mut globals = domain.globals();
             let scope = Scope::push_scope(None, globals, context.gc_context);
             let mut null_activation = Activation::from_nothing(context.reborrow());
 
             // TODO: Lazyinit means we shouldn't do this until traits are
             // actually mentioned...
             for trait_entry in script.read().traits()?.iter() {
+                let name = trait_entry.name().clone();
+                if domain.has_definition(&name) {
+                    log::warn!("Ignoring redefinition of {:?}", name);
+                    continue;
+                }
+
+                domain.export_definition(name, null_activation.context.gc_context);
                 globals.install_foreign_trait(
                     &mut null_activation,
                     trait_entry.clone(),
@@ -139,10 +250,183 @@ impl<'gc> Avm2<'gc> {
         Ok(())
     }
 
+    /// Fire the `open` and `progress` events of a `LoaderInfo` whose content
+    /// has been fetched.
+    pub fn loader_info_open(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        url: &str,
+        length: usize,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        if let Err(e) = loaderinfo::loader_info_open(&mut activation, loader_info, url, length) {
+            log::error!("Error starting load of {}: {}", url, e);
+        }
+    }
+
+    /// Update the `LoaderInfo` of a `Loader` whose content has finished
+    /// loading, and fire its `init` and `complete` events.
+    pub fn loader_info_complete(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        url: &str,
+        content: LoadedContentInfo<'_>,
+        domain: Domain<'gc>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        if let Err(e) =
+            loaderinfo::loader_info_complete(&mut activation, loader_info, content, domain)
+        {
+            log::error!("Error finishing load of {}: {}", url, e);
+        }
+    }
+
+    /// Fire the error event of a `LoaderInfo` whose movie failed to load.
+    pub fn loader_info_error(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        loader_info: Object<'gc>,
+        url: &str,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        if let Err(e) = loaderinfo::loader_info_error(&mut activation, loader_info, url) {
+            log::error!("Error reporting failed load of {}: {}", url, e);
+        }
+    }
+
+    /// Fire the events of a `Timer` whose delay has elapsed.
+    pub fn timer_tick(context: &mut UpdateContext<'_, 'gc, '_>, target: Object<'gc>) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        if let Err(e) = timer::tick(&mut activation, target) {
+            log::error!("Error firing timer events: {}", e);
+        }
+    }
+
     pub fn globals(&self) -> Object<'gc> {
         self.globals
     }
 
+    /// Return the domain that holds all player globals.
+    pub fn global_domain(&self) -> Domain<'gc> {
+        self.global_domain
+    }
+
+    /// The current generation of domain memory, for activations to check
+    /// their cached domain memory against.
+    pub fn domain_memory_generation(&self) -> u32 {
+        self.domain_memory_generation
+    }
+
+    /// Invalidate the domain memory cached by all activations.
+    pub fn invalidate_domain_memory(&mut self) {
+        self.domain_memory_generation = self.domain_memory_generation.wrapping_add(1);
+    }
+
+    /// Record that a bytecode method has started executing.
+    pub fn push_call(&mut self, method: Gc<'gc, BytecodeMethod<'gc>>) {
+        self.call_stack.push(method);
+    }
+
+    /// Record that the innermost bytecode method has finished executing.
+    pub fn pop_call(&mut self) {
+        self.call_stack.pop();
+    }
+
+    /// The movie that the innermost bytecode method being executed was loaded
+    /// from, if any.
+    pub fn current_movie(&self) -> Option<Arc<SwfMovie>> {
+        self.call_stack
+            .last()
+            .map(|method| method.translation_unit().movie())
+    }
+
+    /// Describe the methods currently being executed, innermost first, in the
+    /// format of `Error.getStackTrace`.
+    pub fn stack_trace(&self) -> String {
+        self.call_stack
+            .iter()
+            .rev()
+            .map(|method| format!("\n\tat {}()", method.display_name()))
+            .collect()
+    }
+
+    pub fn multitouch_input_mode(&self) -> MultitouchInputMode {
+        self.multitouch_input_mode
+    }
+
+    pub fn set_multitouch_input_mode(&mut self, mode: MultitouchInputMode) {
+        self.multitouch_input_mode = mode;
+    }
+
+    pub fn xml_settings(&self) -> XmlSettings {
+        self.xml_settings
+    }
+
+    pub fn xml_settings_mut(&mut self) -> &mut XmlSettings {
+        &mut self.xml_settings
+    }
+
+    /// Register a class under an alias, so that its instances keep their type
+    /// when serialized to AMF.
+    pub fn register_class_alias(&mut self, alias: String, class: Object<'gc>) {
+        self.class_aliases.insert(alias, class);
+    }
+
+    /// Look up the class registered under an alias.
+    pub fn class_by_alias(&self, alias: &str) -> Option<Object<'gc>> {
+        self.class_aliases.get(alias).copied()
+    }
+
+    /// Look up the alias a class was registered under, if any.
+    pub fn alias_for_class(&self, class: GcCell<'gc, Class<'gc>>) -> Option<&str> {
+        self.class_aliases
+            .iter()
+            .find(|(_, constr)| {
+                constr
+                    .as_class()
+                    .map_or(false, |other| GcCell::ptr_eq(other, class))
+            })
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    /// Look up the open shared object stored under a key.
+    fn shared_object(&self, key: &str) -> Option<Object<'gc>> {
+        self.shared_objects.get(key).copied()
+    }
+
+    fn register_shared_object(&mut self, key: String, object: Object<'gc>) {
+        self.shared_objects.insert(key, object);
+    }
+
+    /// All open shared objects.
+    fn shared_objects(&self) -> Vec<Object<'gc>> {
+        self.shared_objects.values().copied().collect()
+    }
+
+    /// Write every open shared object to storage.
+    pub fn flush_shared_objects(context: &mut UpdateContext<'_, 'gc, '_>) {
+        shared_object::flush_all(context)
+    }
+
+    /// The name and encoding of the open shared object stored under a key,
+    /// if any.
+    pub fn shared_object_header(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        key: &str,
+    ) -> Option<(String, ObjectEncoding)> {
+        shared_object::header_of(context, key)
+    }
+
+    /// Merge imported data into the open shared object stored under a key.
+    ///
+    /// Returns `false` if no such shared object is open.
+    pub fn import_shared_object(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        key: &str,
+        json: &json::JsonValue,
+    ) -> bool {
+        shared_object::import(context, key, json)
+    }
+
     /// Push a value onto the operand stack.
     fn push(&mut self, value: impl Into<Value<'gc>>) {
         let value = value.into();
@@ -163,6 +447,18 @@ impl<'gc> Avm2<'gc> {
         value
     }
 
+    /// Retrieve a value from the operand stack without removing it.
+    ///
+    /// A `depth` of zero refers to the top-most value.
+    fn peek(&self, depth: usize) -> Value<'gc> {
+        self.stack
+            .len()
+            .checked_sub(depth + 1)
+            .and_then(|index| self.stack.get(index))
+            .cloned()
+            .unwrap_or(Value::Undefined)
+    }
+
     fn pop_args(&mut self, arg_count: u32) -> Vec<Value<'gc>> {
         let mut args = Vec::with_capacity(arg_count as usize);
         args.resize(arg_count as usize, Value::Undefined);
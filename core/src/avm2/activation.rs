@@ -1,14 +1,17 @@
 //! Activation frames
 
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::e4x;
+use crate::avm2::globals::flash::utils::proxy;
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::names::{Multiname, Namespace, QName};
-use crate::avm2::object::{FunctionObject, NamespaceObject, ScriptObject};
+use crate::avm2::object::{ArrayObject, FunctionObject, NamespaceObject, ScriptObject, XMLObject};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
-use crate::avm2::value::Value;
+use crate::avm2::value::{integer_modulo, integer_multiply, integer_negate, Value};
 use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
@@ -100,6 +103,19 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// This will not be available if this is not a method call.
     base_proto: Option<Object<'gc>>,
 
+    /// The namespace URI that unqualified E4X names refer to, as set by
+    /// `default xml namespace`.
+    ///
+    /// A `default_xml_namespace` of `None` indicates the public namespace.
+    default_xml_namespace: Option<AvmString<'gc>>,
+
+    /// The domain memory used by the fast memory opcodes, as of the given
+    /// `Avm2::domain_memory_generation`.
+    ///
+    /// This is looked up on first use and again whenever the memory is
+    /// replaced, rather than on every opcode.
+    domain_memory: Option<(u32, Option<Object<'gc>>)>,
+
     pub context: UpdateContext<'a, 'gc, 'gc_context>,
 }
 
@@ -124,6 +140,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
             base_proto: None,
+            default_xml_namespace: None,
+            domain_memory: None,
             context,
         }
     }
@@ -158,6 +176,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto: None,
+            default_xml_namespace: None,
+            domain_memory: None,
             context,
         })
     }
@@ -203,6 +223,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
             base_proto,
+            default_xml_namespace: None,
+            domain_memory: None,
             context,
         })
     }
@@ -279,6 +301,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.context.avm2
     }
 
+    /// The namespace URI that unqualified E4X names refer to, if it is not
+    /// the public namespace.
+    pub fn default_xml_namespace(&self) -> Option<AvmString<'gc>> {
+        self.default_xml_namespace
+    }
+
     /// Set the return value.
     pub fn set_return_value(&mut self, value: Value<'gc>) {
         self.return_value = Some(value);
@@ -357,6 +385,49 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Multiname::from_abc_multiname_static(method.translation_unit(), index, mc)
     }
 
+    /// Produce the name argument passed to a `Proxy` method for a multiname
+    /// that did not resolve on the proxy itself.
+    fn proxy_name(&self, multiname: &Multiname<'gc>) -> Result<Value<'gc>, Error> {
+        let local_name: Result<AvmString<'gc>, Error> = multiname
+            .local_name()
+            .ok_or_else(|| "Cannot access a proxy property using any name".into());
+
+        Ok(local_name?.into())
+    }
+
+    /// Pop the receiver and key of a late-bound property access on a
+    /// `Dictionary` whose key is an object.
+    ///
+    /// Object keys are matched by identity rather than by name, so they must
+    /// be taken off the stack before the multiname would coerce them into a
+    /// string. The stack is left untouched for any other kind of access.
+    fn pop_dictionary_key(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: &Index<AbcMultiname>,
+    ) -> Option<(Object<'gc>, Object<'gc>)> {
+        let abc = method.translation_unit().abc();
+        let is_late_bound = matches!(
+            (index.0 as usize)
+                .checked_sub(1)
+                .and_then(|i| abc.constant_pool.multinames.get(i)),
+            Some(AbcMultiname::MultinameL { .. }) | Some(AbcMultiname::MultinameLA { .. })
+        );
+        if !is_late_bound {
+            return None;
+        }
+
+        match (self.context.avm2.peek(1), self.context.avm2.peek(0)) {
+            (Value::Object(receiver), Value::Object(key)) if receiver.as_dictionary().is_some() => {
+                self.context.avm2.pop();
+                self.context.avm2.pop();
+
+                Some((receiver, key))
+            }
+            _ => None,
+        }
+    }
+
     /// Retrieve a method entry from the current ABC file's method table.
     fn table_method(
         &mut self,
@@ -469,6 +540,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
                 Op::NewActivation => self.op_new_activation(),
                 Op::NewObject { num_args } => self.op_new_object(num_args),
+                Op::NewArray { num_args } => self.op_new_array(num_args),
                 Op::NewFunction { index } => self.op_new_function(method, index),
                 Op::NewClass { index } => self.op_new_class(method, index),
                 Op::CoerceA => self.op_coerce_a(),
@@ -499,6 +571,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::Multiply => self.op_multiply(),
                 Op::MultiplyI => self.op_multiply_i(),
                 Op::Negate => self.op_negate(),
+                Op::Li8 => self.op_li8(),
+                Op::Li16 => self.op_li16(),
+                Op::Li32 => self.op_li32(),
+                Op::Lf32 => self.op_lf32(),
+                Op::Lf64 => self.op_lf64(),
+                Op::Si8 => self.op_si8(),
+                Op::Si16 => self.op_si16(),
+                Op::Si32 => self.op_si32(),
+                Op::Sf32 => self.op_sf32(),
+                Op::Sf64 => self.op_sf64(),
+                Op::Sxi1 => self.op_sxi1(),
+                Op::Sxi8 => self.op_sxi8(),
+                Op::Sxi16 => self.op_sxi16(),
                 Op::NegateI => self.op_negate_i(),
                 Op::RShift => self.op_rshift(),
                 Op::Subtract => self.op_subtract(),
@@ -535,7 +620,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::NextValue => self.op_next_value(),
                 Op::IsType { index } => self.op_is_type(method, index),
                 Op::IsTypeLate => self.op_is_type_late(),
+                Op::AsType { type_name } => self.op_as_type(method, type_name),
+                Op::AsTypeLate => self.op_as_type_late(),
                 Op::InstanceOf => self.op_instance_of(),
+                Op::In => self.op_in(),
+                Op::GetDescendants { index } => self.op_get_descendants(method, index),
+                Op::CheckFilter => self.op_check_filter(),
+                Op::Dxns { index } => self.op_dxns(method, index),
+                Op::DxnsLate => self.op_dxns_late(),
+                Op::EscXElem => self.op_esc_xelem(),
+                Op::EscXAttr => self.op_esc_xattr(),
                 Op::Label => Ok(FrameControl::Continue),
                 Op::Debug {
                     is_local_register,
@@ -735,15 +829,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let name = name?;
-        let base_proto = receiver.get_base_proto(&name)?;
-        let function = receiver
-            .get_property(receiver, &name, self)?
-            .coerce_to_object(self)?;
-        let value = function.call(Some(receiver), &args, self, base_proto)?;
+        let name = receiver.resolve_multiname(&multiname)?;
+        let value = if let Some(name) = name {
+            let base_proto = receiver.get_base_proto(&name)?;
+            let function = receiver
+                .get_property(receiver, &name, self)?
+                .coerce_to_object(self)?;
+            function.call(Some(receiver), &args, self, base_proto)?
+        } else if proxy::is_proxy(receiver) {
+            let mut proxy_args = vec![self.proxy_name(&multiname)?];
+            proxy_args.extend(args);
+            proxy::call_proxy_method(self, receiver, "callProperty", &proxy_args)?
+        } else {
+            return Err(format!("Could not find method {:?}", multiname.local_name()).into());
+        };
 
         self.context.avm2.push(value);
 
@@ -781,16 +880,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
-        let name: Result<QName, Error> = receiver
-            .resolve_multiname(&multiname)?
-            .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
-        let name = name?;
-        let base_proto = receiver.get_base_proto(&name)?;
-        let function = receiver
-            .get_property(receiver, &name, self)?
-            .coerce_to_object(self)?;
-
-        function.call(Some(receiver), &args, self, base_proto)?;
+        let name = receiver.resolve_multiname(&multiname)?;
+        if let Some(name) = name {
+            let base_proto = receiver.get_base_proto(&name)?;
+            let function = receiver
+                .get_property(receiver, &name, self)?
+                .coerce_to_object(self)?;
+
+            function.call(Some(receiver), &args, self, base_proto)?;
+        } else if proxy::is_proxy(receiver) {
+            let mut proxy_args = vec![self.proxy_name(&multiname)?];
+            proxy_args.extend(args);
+            proxy::call_proxy_method(self, receiver, "callProperty", &proxy_args)?;
+        } else {
+            return Err(format!("Could not find method {:?}", multiname.local_name()).into());
+        }
 
         Ok(FrameControl::Continue)
     }
@@ -896,14 +1000,31 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            let value = dictionary.as_dictionary().and_then(|d| d.get(key));
+            self.context.avm2.push(value.unwrap_or(Value::Undefined));
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        if let Some(value) = e4x::get_property(self, object, &multiname)? {
+            self.context.avm2.push(value);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = if let Some(name) = object.resolve_multiname(&multiname)? {
+            object.get_property(object, &name, self)?
+        } else if proxy::is_proxy(object) {
+            let proxy_name = self.proxy_name(&multiname)?;
+            proxy::call_proxy_method(self, object, "getProperty", &[proxy_name])?
+        } else {
+            return Err(format!("Could not resolve property {:?}", multiname.local_name()).into());
+        };
 
-        let value = object.get_property(object, &name?, self)?;
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
@@ -915,11 +1036,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop();
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            if let Some(mut dictionary) = dictionary.as_dictionary_mut(self.context.gc_context) {
+                dictionary.set(key, value);
+            }
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if e4x::set_property(self, object, &multiname, value.clone())? {
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
+        } else if proxy::is_proxy(object) {
+            let proxy_name = self.proxy_name(&multiname)?;
+            proxy::call_proxy_method(self, object, "setProperty", &[proxy_name, value])?;
         } else {
             //TODO: Non-dynamic objects should fail
             //TODO: This should only work if the public namespace is present
@@ -962,13 +1098,33 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error> {
+        if let Some((dictionary, key)) = self.pop_dictionary_key(method, &index) {
+            let deleted = dictionary
+                .as_dictionary_mut(self.context.gc_context)
+                .map_or(false, |mut d| d.delete(key));
+            self.context.avm2.push(deleted);
+
+            return Ok(FrameControl::Continue);
+        }
+
         let multiname = self.pool_multiname(method, index)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
+        if let Some(deleted) = e4x::delete_property(self, object, &multiname)? {
+            self.context.avm2.push(deleted);
+
+            return Ok(FrameControl::Continue);
+        }
+
         if let Some(name) = object.resolve_multiname(&multiname)? {
             self.context
                 .avm2
                 .push(object.delete_property(self.context.gc_context, &name))
+        } else if proxy::is_proxy(object) {
+            let proxy_name = self.proxy_name(&multiname)?;
+            let deleted = proxy::call_proxy_method(self, object, "deleteProperty", &[proxy_name])?
+                .coerce_to_boolean();
+            self.context.avm2.push(deleted)
         } else {
             self.context.avm2.push(false)
         }
@@ -1307,6 +1463,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_new_array(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
+        let args = self.context.avm2.pop_args(num_args);
+        let array = ArrayStorage::from_args(&args[..]);
+        let array_obj = ArrayObject::from_array(
+            array,
+            self.context.avm2.prototypes().array,
+            self.context.gc_context,
+        );
+
+        self.context.avm2.push(array_obj);
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_new_function(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -1433,7 +1603,19 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop();
         let value1 = self.context.avm2.pop();
 
-        // TODO: Special handling required for `Date` and ECMA-357/E4X `XML`
+        if let Some(list) = e4x::concat(self, &value1, &value2)? {
+            self.context.avm2.push(list);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        if let (Some(i1), Some(i2)) = (value1.as_integer(), value2.as_integer()) {
+            self.context.avm2.push(i1 + i2);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        // TODO: Special handling required for `Date`
         let sum_value = match (value1, value2) {
             (Value::Number(n1), Value::Number(n2)) => Value::Number(n1 + n2),
             (Value::String(s), value2) => {
@@ -1481,7 +1663,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 + value2);
+        self.context.avm2.push(value1.wrapping_add(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -1532,13 +1714,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_declocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value - 1, self.context.gc_context)?;
+        self.set_local_register(index, value.wrapping_sub(1), self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
     fn op_decrement(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_number(self)?;
+        let value = self.context.avm2.pop();
+        if let Some(i) = value.as_integer() {
+            self.context.avm2.push(i - 1);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.context.avm2.push(value - 1.0);
 
@@ -1548,7 +1737,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_decrement_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value - 1);
+        self.context.avm2.push(value.wrapping_sub(1));
 
         Ok(FrameControl::Continue)
     }
@@ -1573,13 +1762,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_inclocal_i(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.local_register(index)?.coerce_to_i32(self)?;
 
-        self.set_local_register(index, value + 1, self.context.gc_context)?;
+        self.set_local_register(index, value.wrapping_add(1), self.context.gc_context)?;
 
         Ok(FrameControl::Continue)
     }
 
     fn op_increment(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_number(self)?;
+        let value = self.context.avm2.pop();
+        if let Some(i) = value.as_integer() {
+            self.context.avm2.push(i + 1);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value = value.coerce_to_number(self)?;
 
         self.context.avm2.push(value + 1.0);
 
@@ -1589,7 +1785,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_increment_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value + 1);
+        self.context.avm2.push(value.wrapping_add(1));
 
         Ok(FrameControl::Continue)
     }
@@ -1604,8 +1800,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_modulo(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+        if let (Some(i1), Some(i2)) = (value1.as_integer(), value2.as_integer()) {
+            self.context.avm2.push(integer_modulo(i1, i2));
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 % value2);
 
@@ -1613,8 +1817,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_multiply(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+        if let (Some(i1), Some(i2)) = (value1.as_integer(), value2.as_integer()) {
+            self.context.avm2.push(integer_multiply(i1, i2));
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 * value2);
 
@@ -1625,13 +1837,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 * value2);
+        self.context.avm2.push(value1.wrapping_mul(value2));
 
         Ok(FrameControl::Continue)
     }
 
     fn op_negate(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value1 = self.context.avm2.pop();
+        if let Some(i) = value1.as_integer() {
+            self.context.avm2.push(integer_negate(i));
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(-value1);
 
@@ -1641,7 +1860,157 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_negate_i(&mut self) -> Result<FrameControl<'gc>, Error> {
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(-value1);
+        self.context.avm2.push(value1.wrapping_neg());
+
+        Ok(FrameControl::Continue)
+    }
+
+    /// Look up the domain memory used by the fast memory opcodes.
+    fn domain_memory(&mut self) -> Result<Object<'gc>, Error> {
+        let generation = self.context.avm2.domain_memory_generation();
+        let memory = match self.domain_memory {
+            Some((cached, memory)) if cached == generation => memory,
+            _ => {
+                let swf = self.context.swf.clone();
+                let memory = Avm2::movie_domain(&mut self.context, swf).domain_memory();
+                self.domain_memory = Some((generation, memory));
+                memory
+            }
+        };
+
+        memory.ok_or_else(|| "RangeError: Error #1506: The specified range is invalid.".into())
+    }
+
+    /// Fill `bytes` from domain memory, starting at the address on top of the
+    /// stack.
+    fn load_domain_memory(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let memory = self.domain_memory()?;
+        let storage = memory.as_bytearray().unwrap();
+
+        bytes.copy_from_slice(storage.read_at(address, bytes.len())?);
+
+        Ok(())
+    }
+
+    /// Write bytes to domain memory at the given address.
+    fn store_domain_memory(&mut self, address: i32, data: &[u8]) -> Result<(), Error> {
+        let memory = self.domain_memory()?;
+        let mut storage = memory.as_bytearray_mut(self.context.gc_context).unwrap();
+
+        storage.write_at(address, data)
+    }
+
+    fn op_li8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let mut bytes = [0; 1];
+        self.load_domain_memory(&mut bytes)?;
+
+        self.context.avm2.push(bytes[0]);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li16(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let mut bytes = [0; 2];
+        self.load_domain_memory(&mut bytes)?;
+
+        self.context.avm2.push(u16::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_li32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let mut bytes = [0; 4];
+        self.load_domain_memory(&mut bytes)?;
+
+        self.context.avm2.push(i32::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let mut bytes = [0; 4];
+        self.load_domain_memory(&mut bytes)?;
+
+        self.context.avm2.push(f32::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_lf64(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let mut bytes = [0; 8];
+        self.load_domain_memory(&mut bytes)?;
+
+        self.context.avm2.push(f64::from_le_bytes(bytes));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &[value as u8])?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si16(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &(value as u16).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_si32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.store_domain_memory(address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf32(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(address, &(value as f32).to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sf64(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let address = self.context.avm2.pop().coerce_to_i32(self)?;
+        let value = self.context.avm2.pop().coerce_to_number(self)?;
+
+        self.store_domain_memory(address, &value.to_le_bytes())?;
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi1(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(-(value & 1));
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi8(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(value as i8 as i32);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_sxi16(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop().coerce_to_i32(self)?;
+
+        self.context.avm2.push(value as i16 as i32);
 
         Ok(FrameControl::Continue)
     }
@@ -1656,8 +2025,16 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_subtract(&mut self) -> Result<FrameControl<'gc>, Error> {
-        let value2 = self.context.avm2.pop().coerce_to_number(self)?;
-        let value1 = self.context.avm2.pop().coerce_to_number(self)?;
+        let value2 = self.context.avm2.pop();
+        let value1 = self.context.avm2.pop();
+        if let (Some(i1), Some(i2)) = (value1.as_integer(), value2.as_integer()) {
+            self.context.avm2.push(i1 - i2);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let value2 = value2.coerce_to_number(self)?;
+        let value1 = value1.coerce_to_number(self)?;
 
         self.context.avm2.push(value1 - value2);
 
@@ -1668,7 +2045,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value2 = self.context.avm2.pop().coerce_to_i32(self)?;
         let value1 = self.context.avm2.pop().coerce_to_i32(self)?;
 
-        self.context.avm2.push(value1 - value2);
+        self.context.avm2.push(value1.wrapping_sub(value2));
 
         Ok(FrameControl::Continue)
     }
@@ -1978,7 +2355,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
         let next_index = cur_index + 1;
 
-        if object.get_enumerant_name(next_index).is_some() {
+        if has_enumerant(object, next_index) {
             self.context.avm2.push(next_index);
         } else {
             self.context.avm2.push(0.0);
@@ -1998,10 +2375,21 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 .coerce_to_object(self)?,
         );
 
+        if let Some(proxy_object) = object.filter(|o| proxy::is_proxy(*o)) {
+            let next_index =
+                proxy::call_proxy_method(self, proxy_object, "nextNameIndex", &[cur_index.into()])?
+                    .coerce_to_u32(self)?;
+
+            self.context.avm2.push(next_index != 0);
+            self.set_local_register(index_register, next_index, self.context.gc_context)?;
+
+            return Ok(FrameControl::Continue);
+        }
+
         let mut next_index = cur_index + 1;
 
         while let Some(cur_object) = object {
-            if cur_object.get_enumerant_name(next_index).is_none() {
+            if !has_enumerant(cur_object, next_index) {
                 next_index = 1;
                 object = cur_object.proto();
             } else {
@@ -2028,9 +2416,27 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object
-            .get_enumerant_name(cur_index as u32)
-            .map(|n| n.local_name().into());
+        if proxy::is_proxy(object) {
+            let name = proxy::call_proxy_method(self, object, "nextName", &[cur_index.into()])?;
+            self.context.avm2.push(name);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let cur_index = cur_index as u32;
+        let key_count = native_key_count(object);
+        let name = if cur_index <= key_count && e4x::object_values(object).is_some() {
+            Some(AvmString::new(self.context.gc_context, (cur_index - 1).to_string()).into())
+        } else if cur_index <= key_count {
+            object
+                .as_dictionary()
+                .and_then(|d| d.entry_at(cur_index))
+                .map(|(key, _)| key.into())
+        } else {
+            object
+                .get_enumerant_name(cur_index - key_count)
+                .map(|n| n.local_name().into())
+        };
 
         self.context.avm2.push(name.unwrap_or(Value::Undefined));
 
@@ -2041,7 +2447,36 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let cur_index = self.context.avm2.pop().coerce_to_number(self)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name = object.get_enumerant_name(cur_index as u32);
+        if proxy::is_proxy(object) {
+            let value = proxy::call_proxy_method(self, object, "nextValue", &[cur_index.into()])?;
+            self.context.avm2.push(value);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let cur_index = cur_index as u32;
+        let key_count = native_key_count(object);
+        if let Some(values) = e4x::object_values(object).filter(|_| cur_index <= key_count) {
+            let value = match values.get(cur_index as usize - 1) {
+                Some(value) => XMLObject::from_value(self, value.clone())?.into(),
+                None => Value::Undefined,
+            };
+            self.context.avm2.push(value);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        if cur_index <= key_count {
+            let value = object
+                .as_dictionary()
+                .and_then(|d| d.entry_at(cur_index))
+                .map(|(_, value)| value);
+            self.context.avm2.push(value.unwrap_or(Value::Undefined));
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = object.get_enumerant_name(cur_index - key_count);
         let value = if let Some(name) = name {
             object.get_property(object, &name, self)?
         } else {
@@ -2053,13 +2488,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_is_type(
+    /// Resolve the type named by a multiname in the constant pool.
+    fn resolve_type(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         type_name_index: Index<AbcMultiname>,
-    ) -> Result<FrameControl<'gc>, Error> {
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
-
+    ) -> Result<Object<'gc>, Error> {
         let type_name =
             self.pool_multiname_static(method, type_name_index, self.context.gc_context)?;
         let type_object = if let Some(scope) = self.scope() {
@@ -2068,31 +2502,87 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             None
         };
 
-        if let Some(type_object) = type_object {
-            let is_instance_of = value.is_instance_of(self, type_object, true)?;
-            self.context.avm2.push(is_instance_of);
-        } else {
-            return Err(format!(
+        type_object.ok_or_else(|| {
+            format!(
                 "Attempted to check against nonexistent type {:?}",
                 type_name
             )
-            .into());
+            .into()
+        })
+    }
+
+    /// Determine if a value is of the given type, as checked by `is` and
+    /// `as`.
+    ///
+    /// `null` and `undefined` are not instances of any type.
+    fn value_is_type(
+        &mut self,
+        value: Value<'gc>,
+        type_object: Object<'gc>,
+    ) -> Result<bool, Error> {
+        match value {
+            Value::Undefined | Value::Null => Ok(false),
+            value => value
+                .coerce_to_object(self)?
+                .is_instance_of(self, type_object, true),
         }
+    }
+
+    fn op_is_type(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        type_name_index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        let type_object = self.resolve_type(method, type_name_index)?;
+
+        let is_instance_of = self.value_is_type(value, type_object)?;
+        self.context.avm2.push(is_instance_of);
 
         Ok(FrameControl::Continue)
     }
 
     fn op_is_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
-        let value = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
 
-        let is_instance_of = value.is_instance_of(self, type_object, true)?;
+        let is_instance_of = self.value_is_type(value, type_object)?;
 
         self.context.avm2.push(is_instance_of);
 
         Ok(FrameControl::Continue)
     }
 
+    fn op_as_type(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        type_name_index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        let type_object = self.resolve_type(method, type_name_index)?;
+
+        if self.value_is_type(value.clone(), type_object)? {
+            self.context.avm2.push(value);
+        } else {
+            self.context.avm2.push(Value::Null);
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_as_type_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let type_object = self.context.avm2.pop().coerce_to_object(self)?;
+        let value = self.context.avm2.pop();
+
+        if self.value_is_type(value.clone(), type_object)? {
+            self.context.avm2.push(value);
+        } else {
+            self.context.avm2.push(Value::Null);
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_instance_of(&mut self) -> Result<FrameControl<'gc>, Error> {
         let type_object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop().coerce_to_object(self)?;
@@ -2104,6 +2594,112 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_in(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let name_value = self.context.avm2.pop();
+
+        if let (Some(dictionary), Value::Object(key)) = (object.as_dictionary(), &name_value) {
+            let has_property = dictionary.has(*key);
+            drop(dictionary);
+            self.context.avm2.push(has_property);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let name = name_value.coerce_to_string(self)?;
+
+        if e4x::object_values(object).is_some() {
+            let multiname = QName::dynamic_name(name).into();
+            let has_property = e4x::has_property(self, object, &multiname);
+            self.context.avm2.push(has_property);
+
+            return Ok(FrameControl::Continue);
+        }
+
+        let has_property = if let Some(ns) = object.resolve_any(name)? {
+            object.has_property(&QName::new(ns, name))?
+        } else if proxy::is_proxy(object) {
+            proxy::call_proxy_method(self, object, "hasProperty", &[name_value])?
+                .coerce_to_boolean()
+        } else {
+            false
+        };
+
+        self.context.avm2.push(has_property);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_get_descendants(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcMultiname>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let multiname = self.pool_multiname(method, index)?;
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
+        let descendants = e4x::get_descendants(self, object, &multiname)?;
+
+        self.context.avm2.push(descendants);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_check_filter(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.peek(0);
+
+        if e4x::values_of(&value).is_none() {
+            return Err("TypeError: Error #1123: Filter operator not supported on type".into());
+        }
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<String>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let uri = self.pool_string(&method, index, self.context.gc_context)?;
+
+        self.default_xml_namespace = Some(uri);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_dxns_late(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let uri = self.context.avm2.pop().coerce_to_string(self)?;
+
+        self.default_xml_namespace = Some(uri);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xelem(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+        let escaped = match e4x::xml_string_of(self, &value) {
+            Some(markup) => markup,
+            None => {
+                let string = value.coerce_to_string(self)?;
+                AvmString::new(self.context.gc_context, e4x::escape_element_value(&string))
+            }
+        };
+
+        self.context.avm2.push(escaped);
+
+        Ok(FrameControl::Continue)
+    }
+
+    fn op_esc_xattr(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let string = self.context.avm2.pop().coerce_to_string(self)?;
+        let escaped = e4x::escape_attribute_value(&string);
+
+        self.context
+            .avm2
+            .push(AvmString::new(self.context.gc_context, escaped));
+
+        Ok(FrameControl::Continue)
+    }
+
     #[allow(unused_variables)]
     #[cfg(avm_debug)]
     fn op_debug(
@@ -2168,3 +2764,25 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 }
+
+/// The number of entries an object enumerates before its ordinary
+/// properties: the object keys of a `Dictionary`, or the values of an
+/// `XMLList` (an `XML` object being a list of one). This is zero for any other
+/// object.
+fn native_key_count(object: Object<'_>) -> u32 {
+    if let Some(values) = e4x::object_values(object) {
+        return values.len() as u32;
+    }
+
+    object.as_dictionary().map_or(0, |d| d.len() as u32)
+}
+
+/// Whether an object has an enumerable entry at a given index.
+///
+/// The native entries of a `Dictionary` or `XMLList` occupy the first
+/// indices, followed by the object's ordinary enumerable properties.
+fn has_enumerant(object: Object<'_>, index: u32) -> bool {
+    let key_count = native_key_count(object);
+
+    index <= key_count || object.get_enumerant_name(index - key_count).is_some()
+}
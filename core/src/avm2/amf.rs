@@ -0,0 +1,890 @@
+//! AMF serialization of AVM2 values, as used by `ByteArray.writeObject` and
+//! `ByteArray.readObject`.
+//!
+//! Values are read from and written to a `ByteArray` object directly, rather
+//! than to a separate buffer, so that `IExternalizable` classes can read and
+//! write their own data in the middle of a serialized object.
+//!
+//! Only the types that exist in our AVM2 can be serialized: primitives,
+//! strings, plain and class-aliased objects, and `ByteArray`s. Arrays, dates,
+//! XML, vectors and dictionaries are rejected when read.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ObjectEncoding;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::TraitKind;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+const AMF0_NUMBER: u8 = 0x00;
+const AMF0_BOOLEAN: u8 = 0x01;
+const AMF0_STRING: u8 = 0x02;
+const AMF0_OBJECT: u8 = 0x03;
+const AMF0_NULL: u8 = 0x05;
+const AMF0_UNDEFINED: u8 = 0x06;
+const AMF0_REFERENCE: u8 = 0x07;
+const AMF0_OBJECT_END: u8 = 0x09;
+const AMF0_LONG_STRING: u8 = 0x0C;
+const AMF0_TYPED_OBJECT: u8 = 0x10;
+const AMF0_AVMPLUS: u8 = 0x11;
+
+const AMF3_UNDEFINED: u8 = 0x00;
+const AMF3_NULL: u8 = 0x01;
+const AMF3_FALSE: u8 = 0x02;
+const AMF3_TRUE: u8 = 0x03;
+const AMF3_INTEGER: u8 = 0x04;
+const AMF3_DOUBLE: u8 = 0x05;
+const AMF3_STRING: u8 = 0x06;
+const AMF3_OBJECT: u8 = 0x0A;
+const AMF3_BYTE_ARRAY: u8 = 0x0C;
+
+/// The range of integers that fit in an AMF3 integer.
+const AMF3_INT_MIN: i32 = -(1 << 28);
+const AMF3_INT_MAX: i32 = (1 << 28) - 1;
+
+/// Write a value to a `ByteArray` at its current position, in the array's
+/// object encoding.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    let mut writer = Writer::new(target);
+    match object_encoding(target)? {
+        ObjectEncoding::Amf0 => writer.write_amf0_value(activation, value),
+        ObjectEncoding::Amf3 => writer.write_amf3_value(activation, value),
+    }
+}
+
+/// Read a value from a `ByteArray` at its current position, in the array's
+/// object encoding.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let mut reader = Reader::new(target);
+    match object_encoding(target)? {
+        ObjectEncoding::Amf0 => reader.read_amf0_value(activation),
+        ObjectEncoding::Amf3 => reader.read_amf3_value(activation),
+    }
+}
+
+fn object_encoding(target: Object<'_>) -> Result<ObjectEncoding, Error> {
+    Ok(target
+        .as_bytearray()
+        .ok_or("AMF data can only be read from or written to a ByteArray")?
+        .object_encoding())
+}
+
+/// Encode an unsigned 29-bit integer in AMF3's variable length format.
+fn encode_u29(value: u32) -> Vec<u8> {
+    let value = value & 0x1FFF_FFFF;
+    if value < 0x80 {
+        vec![value as u8]
+    } else if value < 0x4000 {
+        vec![(value >> 7) as u8 | 0x80, value as u8 & 0x7F]
+    } else if value < 0x20_0000 {
+        vec![
+            (value >> 14) as u8 | 0x80,
+            (value >> 7) as u8 | 0x80,
+            value as u8 & 0x7F,
+        ]
+    } else {
+        vec![
+            (value >> 22) as u8 | 0x80,
+            (value >> 15) as u8 | 0x80,
+            (value >> 8) as u8 | 0x80,
+            value as u8,
+        ]
+    }
+}
+
+/// The description of an object's members in AMF3.
+#[derive(Clone, Debug, PartialEq)]
+struct Traits {
+    /// The alias of the object's class, or empty for anonymous objects.
+    class_name: String,
+
+    /// The names of the members that are always present.
+    sealed: Vec<String>,
+
+    /// Whether the object can have further, dynamic members.
+    dynamic: bool,
+
+    /// Whether the object serializes itself with `writeExternal` and
+    /// `readExternal`.
+    externalizable: bool,
+}
+
+fn public_name<'gc>(activation: &mut Activation<'_, 'gc, '_>, name: &str) -> QName<'gc> {
+    QName::new(
+        Namespace::public_namespace(),
+        AvmString::new(activation.context.gc_context, name.to_string()),
+    )
+}
+
+/// Determine whether an object has a method with the given public name.
+fn has_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &str,
+) -> bool {
+    let name = public_name(activation, name);
+    object.has_property(&name).unwrap_or(false)
+}
+
+/// Call a public method of an object.
+fn call_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &str,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = public_name(activation, name);
+    let base_proto = object.get_base_proto(&name)?;
+    let method = object
+        .get_property(object, &name, activation)?
+        .coerce_to_object(activation)?;
+
+    method.call(Some(object), args, activation, base_proto)
+}
+
+/// Construct an object from its class, without arguments.
+fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut constr: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let object = proto.construct(activation, &[])?;
+    constr.call(Some(object), &[], activation, object.proto())?;
+
+    Ok(object)
+}
+
+/// Construct an empty `ByteArray`.
+fn construct_bytearray<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+    let constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.utils"), "ByteArray"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    construct(activation, constr)
+}
+
+/// Determine the traits that an object is serialized with.
+fn traits_of<'gc>(activation: &mut Activation<'_, 'gc, '_>, object: Object<'gc>) -> Traits {
+    let class = object.proto().and_then(|proto| proto.as_class());
+    let class_name = match class {
+        Some(class) => activation
+            .avm2()
+            .alias_for_class(class)
+            .unwrap_or("")
+            .to_string(),
+        None => String::new(),
+    };
+
+    // Without an `IExternalizable` interface to check for, any aliased class
+    // with a `writeExternal` method is treated as externalizable.
+    if !class_name.is_empty() && has_method(activation, object, "writeExternal") {
+        return Traits {
+            class_name,
+            sealed: Vec::new(),
+            dynamic: false,
+            externalizable: true,
+        };
+    }
+
+    // The sealed members are the public variables and read-write accessors
+    // declared by the object's class and its superclasses, superclass first.
+    let mut classes = Vec::new();
+    let mut proto = object.proto();
+    while let Some(p) = proto {
+        if let Some(class) = p.as_class() {
+            classes.push(class);
+        }
+        proto = p.proto();
+    }
+
+    let mut sealed: Vec<String> = Vec::new();
+    for class in classes.iter().rev() {
+        let read = class.read();
+        for instance_trait in read.instance_traits() {
+            if instance_trait.name().namespace() != &Namespace::public_namespace() {
+                continue;
+            }
+
+            let is_property = match instance_trait.kind() {
+                TraitKind::Slot { .. } => true,
+                TraitKind::Getter { .. } => read.instance_traits().iter().any(|other| {
+                    other.name() == instance_trait.name()
+                        && matches!(other.kind(), TraitKind::Setter { .. })
+                }),
+                _ => false,
+            };
+
+            let name = instance_trait.name().local_name().to_string();
+            if is_property && !sealed.contains(&name) {
+                sealed.push(name);
+            }
+        }
+    }
+
+    Traits {
+        class_name,
+        sealed,
+        dynamic: class.map_or(true, |class| !class.read().is_sealed()),
+        externalizable: false,
+    }
+}
+
+/// List the enumerable dynamic properties of an object.
+fn dynamic_names<'gc>(object: Object<'gc>) -> Vec<QName<'gc>> {
+    let mut names = Vec::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        if object.property_is_enumerable(&name) {
+            names.push(name);
+        }
+        index += 1;
+    }
+
+    names
+}
+
+/// Create the object that a serialized object of the given class is read
+/// into.
+///
+/// Objects of unknown classes are read as plain objects.
+fn instantiate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    class_name: &str,
+) -> Result<Object<'gc>, Error> {
+    if !class_name.is_empty() {
+        if let Some(constr) = activation.avm2().class_by_alias(class_name) {
+            return construct(activation, constr);
+        }
+    }
+
+    let object_proto = activation.avm2().prototypes().object;
+    Ok(ScriptObject::object(
+        activation.context.gc_context,
+        object_proto,
+    ))
+}
+
+/// Serializes values into a `ByteArray`.
+struct Writer<'gc> {
+    target: Object<'gc>,
+
+    /// The AMF3 string reference table.
+    strings: Vec<String>,
+
+    /// The AMF3 object reference table.
+    objects: Vec<Object<'gc>>,
+
+    /// The AMF3 traits reference table.
+    traits: Vec<Traits>,
+
+    /// The AMF0 object reference table.
+    amf0_objects: Vec<Object<'gc>>,
+}
+
+impl<'gc> Writer<'gc> {
+    fn new(target: Object<'gc>) -> Self {
+        Self {
+            target,
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+            amf0_objects: Vec::new(),
+        }
+    }
+
+    fn write(&self, activation: &mut Activation<'_, 'gc, '_>, data: &[u8]) -> Result<(), Error> {
+        self.target
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("AMF data can only be written to a ByteArray")?
+            .write_bytes(data);
+
+        Ok(())
+    }
+
+    fn write_u29(&self, activation: &mut Activation<'_, 'gc, '_>, value: u32) -> Result<(), Error> {
+        self.write(activation, &encode_u29(value))
+    }
+
+    fn write_amf3_string(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        string: &str,
+    ) -> Result<(), Error> {
+        // The empty string is never sent by reference.
+        if string.is_empty() {
+            return self.write_u29(activation, 1);
+        }
+
+        if let Some(index) = self.strings.iter().position(|s| s == string) {
+            return self.write_u29(activation, (index as u32) << 1);
+        }
+
+        self.strings.push(string.to_string());
+        self.write_u29(activation, ((string.len() as u32) << 1) | 1)?;
+        self.write(activation, string.as_bytes())
+    }
+
+    fn write_amf3_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.write(activation, &[AMF3_UNDEFINED]),
+            Value::Null => self.write(activation, &[AMF3_NULL]),
+            Value::Bool(false) => self.write(activation, &[AMF3_FALSE]),
+            Value::Bool(true) => self.write(activation, &[AMF3_TRUE]),
+            Value::Integer(i) if (AMF3_INT_MIN..=AMF3_INT_MAX).contains(&i) => {
+                self.write(activation, &[AMF3_INTEGER])?;
+                self.write_u29(activation, i as u32)
+            }
+            Value::Unsigned(u) if u <= AMF3_INT_MAX as u32 => {
+                self.write(activation, &[AMF3_INTEGER])?;
+                self.write_u29(activation, u)
+            }
+            Value::Integer(i) => self.write_amf3_double(activation, i.into()),
+            Value::Unsigned(u) => self.write_amf3_double(activation, u.into()),
+            Value::Number(n) => self.write_amf3_double(activation, n),
+            Value::String(s) => {
+                self.write(activation, &[AMF3_STRING])?;
+                self.write_amf3_string(activation, &s)
+            }
+            Value::Object(o) => self.write_amf3_object(activation, o),
+        }
+    }
+
+    fn write_amf3_double(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: f64,
+    ) -> Result<(), Error> {
+        self.write(activation, &[AMF3_DOUBLE])?;
+        self.write(activation, &value.to_be_bytes())
+    }
+
+    fn write_amf3_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        object: Object<'gc>,
+    ) -> Result<(), Error> {
+        // Functions can't be serialized.
+        if object.as_executable().is_some() {
+            return self.write(activation, &[AMF3_UNDEFINED]);
+        }
+
+        let bytes = object
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec());
+        let marker = if bytes.is_some() {
+            AMF3_BYTE_ARRAY
+        } else {
+            AMF3_OBJECT
+        };
+        self.write(activation, &[marker])?;
+
+        if let Some(index) = self.objects.iter().position(|o| Object::ptr_eq(*o, object)) {
+            return self.write_u29(activation, (index as u32) << 1);
+        }
+        self.objects.push(object);
+
+        if let Some(bytes) = bytes {
+            self.write_u29(activation, ((bytes.len() as u32) << 1) | 1)?;
+            return self.write(activation, &bytes);
+        }
+
+        let traits = traits_of(activation, object);
+        if let Some(index) = self.traits.iter().position(|t| *t == traits) {
+            self.write_u29(activation, ((index as u32) << 2) | 0b01)?;
+        } else if traits.externalizable {
+            self.write_u29(activation, 0b0111)?;
+            self.write_amf3_string(activation, &traits.class_name)?;
+            self.traits.push(traits.clone());
+        } else {
+            let dynamic_flag = if traits.dynamic { 0b1000 } else { 0 };
+            self.write_u29(
+                activation,
+                ((traits.sealed.len() as u32) << 4) | dynamic_flag | 0b0011,
+            )?;
+            self.write_amf3_string(activation, &traits.class_name)?;
+            for name in &traits.sealed {
+                self.write_amf3_string(activation, name)?;
+            }
+            self.traits.push(traits.clone());
+        }
+
+        if traits.externalizable {
+            call_method(activation, object, "writeExternal", &[self.target.into()])?;
+            return Ok(());
+        }
+
+        let mut object = object;
+        for name in &traits.sealed {
+            let name = public_name(activation, name);
+            let value = object.get_property(object, &name, activation)?;
+            self.write_amf3_value(activation, value)?;
+        }
+
+        if traits.dynamic {
+            for name in dynamic_names(object) {
+                let value = object.get_property(object, &name, activation)?;
+                if name.local_name().is_empty() {
+                    continue;
+                }
+                self.write_amf3_string(activation, &name.local_name())?;
+                self.write_amf3_value(activation, value)?;
+            }
+            self.write_amf3_string(activation, "")?;
+        }
+
+        Ok(())
+    }
+
+    fn write_amf0_string(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        string: &str,
+    ) -> Result<(), Error> {
+        self.write(activation, &(string.len() as u16).to_be_bytes())?;
+        self.write(activation, string.as_bytes())
+    }
+
+    fn write_amf0_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.write(activation, &[AMF0_UNDEFINED]),
+            Value::Null => self.write(activation, &[AMF0_NULL]),
+            Value::Bool(b) => self.write(activation, &[AMF0_BOOLEAN, b as u8]),
+            Value::Integer(i) => self.write_amf0_number(activation, i.into()),
+            Value::Unsigned(u) => self.write_amf0_number(activation, u.into()),
+            Value::Number(n) => self.write_amf0_number(activation, n),
+            Value::String(s) if s.len() > 0xFFFF => {
+                self.write(activation, &[AMF0_LONG_STRING])?;
+                self.write(activation, &(s.len() as u32).to_be_bytes())?;
+                self.write(activation, s.as_bytes())
+            }
+            Value::String(s) => {
+                self.write(activation, &[AMF0_STRING])?;
+                self.write_amf0_string(activation, &s)
+            }
+            Value::Object(o) => self.write_amf0_object(activation, o),
+        }
+    }
+
+    fn write_amf0_number(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: f64,
+    ) -> Result<(), Error> {
+        self.write(activation, &[AMF0_NUMBER])?;
+        self.write(activation, &value.to_be_bytes())
+    }
+
+    fn write_amf0_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<(), Error> {
+        if object.as_executable().is_some() {
+            return self.write(activation, &[AMF0_UNDEFINED]);
+        }
+
+        // AMF0 has no way to represent these, so they switch to AMF3.
+        if object.as_bytearray().is_some() {
+            self.write(activation, &[AMF0_AVMPLUS])?;
+            return self.write_amf3_object(activation, object);
+        }
+
+        if let Some(index) = self
+            .amf0_objects
+            .iter()
+            .position(|o| Object::ptr_eq(*o, object))
+        {
+            self.write(activation, &[AMF0_REFERENCE])?;
+            return self.write(activation, &(index as u16).to_be_bytes());
+        }
+
+        let traits = traits_of(activation, object);
+        if traits.externalizable {
+            self.write(activation, &[AMF0_AVMPLUS])?;
+            return self.write_amf3_object(activation, object);
+        }
+
+        self.amf0_objects.push(object);
+        if traits.class_name.is_empty() {
+            self.write(activation, &[AMF0_OBJECT])?;
+        } else {
+            self.write(activation, &[AMF0_TYPED_OBJECT])?;
+            self.write_amf0_string(activation, &traits.class_name)?;
+        }
+
+        for name in &traits.sealed {
+            let qname = public_name(activation, name);
+            let value = object.get_property(object, &qname, activation)?;
+            self.write_amf0_string(activation, name)?;
+            self.write_amf0_value(activation, value)?;
+        }
+
+        for name in dynamic_names(object) {
+            let value = object.get_property(object, &name, activation)?;
+            if name.local_name().is_empty() {
+                continue;
+            }
+            self.write_amf0_string(activation, &name.local_name())?;
+            self.write_amf0_value(activation, value)?;
+        }
+
+        self.write_amf0_string(activation, "")?;
+        self.write(activation, &[AMF0_OBJECT_END])
+    }
+}
+
+/// Deserializes values from a `ByteArray`.
+struct Reader<'gc> {
+    target: Object<'gc>,
+
+    /// The AMF3 string reference table.
+    strings: Vec<String>,
+
+    /// The AMF3 object reference table.
+    objects: Vec<Object<'gc>>,
+
+    /// The AMF3 traits reference table.
+    traits: Vec<Traits>,
+
+    /// The AMF0 object reference table.
+    amf0_objects: Vec<Object<'gc>>,
+}
+
+impl<'gc> Reader<'gc> {
+    fn new(target: Object<'gc>) -> Self {
+        Self {
+            target,
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+            amf0_objects: Vec::new(),
+        }
+    }
+
+    fn read(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        amount: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let mut bytearray = self
+            .target
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("AMF data can only be read from a ByteArray")?;
+
+        Ok(bytearray.read_bytes(amount)?.to_vec())
+    }
+
+    fn read_u8(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u8, Error> {
+        Ok(self.read(activation, 1)?[0])
+    }
+
+    fn read_u16(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u16, Error> {
+        let bytes = self.read(activation, 2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u32, Error> {
+        let bytes = self.read(activation, 4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_f64(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<f64, Error> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.read(activation, 8)?);
+        Ok(f64::from_be_bytes(bytes))
+    }
+
+    fn read_utf8(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        len: usize,
+    ) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(&self.read(activation, len)?).into_owned())
+    }
+
+    fn read_u29(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u32, Error> {
+        let mut value = 0;
+        for _ in 0..3 {
+            let byte = self.read_u8(activation)?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Ok((value << 8) | u32::from(self.read_u8(activation)?))
+    }
+
+    fn read_amf3_string(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<String, Error> {
+        let header = self.read_u29(activation)?;
+        if header & 1 == 0 {
+            let index = (header >> 1) as usize;
+            return self
+                .strings
+                .get(index)
+                .cloned()
+                .ok_or_else(|| format!("Invalid AMF3 string reference {}", index).into());
+        }
+
+        let string = self.read_utf8(activation, (header >> 1) as usize)?;
+        if !string.is_empty() {
+            self.strings.push(string.clone());
+        }
+
+        Ok(string)
+    }
+
+    fn read_amf3_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let marker = self.read_u8(activation)?;
+        Ok(match marker {
+            AMF3_UNDEFINED => Value::Undefined,
+            AMF3_NULL => Value::Null,
+            AMF3_FALSE => false.into(),
+            AMF3_TRUE => true.into(),
+            AMF3_INTEGER => {
+                // Sign-extend the 29-bit integer.
+                let value = self.read_u29(activation)?;
+                (((value << 3) as i32) >> 3).into()
+            }
+            AMF3_DOUBLE => self.read_f64(activation)?.into(),
+            AMF3_STRING => {
+                let string = self.read_amf3_string(activation)?;
+                AvmString::new(activation.context.gc_context, string).into()
+            }
+            AMF3_OBJECT => self.read_amf3_object(activation)?.into(),
+            AMF3_BYTE_ARRAY => self.read_amf3_bytearray(activation)?.into(),
+            _ => return Err(format!("Unsupported AMF3 type marker {:#x}", marker).into()),
+        })
+    }
+
+    fn read_amf3_reference(&self, index: u32) -> Result<Object<'gc>, Error> {
+        self.objects
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| format!("Invalid AMF3 object reference {}", index).into())
+    }
+
+    fn read_amf3_bytearray(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let header = self.read_u29(activation)?;
+        if header & 1 == 0 {
+            return self.read_amf3_reference(header >> 1);
+        }
+
+        let bytes = self.read(activation, (header >> 1) as usize)?;
+        let object = construct_bytearray(activation)?;
+        if let Some(mut bytearray) = object.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&bytes);
+            bytearray.set_position(0);
+        }
+        self.objects.push(object);
+
+        Ok(object)
+    }
+
+    fn read_amf3_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Object<'gc>, Error> {
+        let header = self.read_u29(activation)?;
+        if header & 1 == 0 {
+            return self.read_amf3_reference(header >> 1);
+        }
+
+        let traits = if header & 0b10 == 0 {
+            let index = (header >> 2) as usize;
+            self.traits
+                .get(index)
+                .cloned()
+                .ok_or_else(|| format!("Invalid AMF3 traits reference {}", index))?
+        } else if header & 0b100 != 0 {
+            let class_name = self.read_amf3_string(activation)?;
+            let traits = Traits {
+                class_name,
+                sealed: Vec::new(),
+                dynamic: false,
+                externalizable: true,
+            };
+            self.traits.push(traits.clone());
+            traits
+        } else {
+            let dynamic = header & 0b1000 != 0;
+            let class_name = self.read_amf3_string(activation)?;
+            let mut sealed = Vec::new();
+            for _ in 0..(header >> 4) {
+                sealed.push(self.read_amf3_string(activation)?);
+            }
+            let traits = Traits {
+                class_name,
+                sealed,
+                dynamic,
+                externalizable: false,
+            };
+            self.traits.push(traits.clone());
+            traits
+        };
+
+        let mut object = instantiate(activation, &traits.class_name)?;
+        self.objects.push(object);
+
+        if traits.externalizable {
+            if !has_method(activation, object, "readExternal") {
+                return Err(format!(
+                    "ArgumentError: Error #2173: Unable to read object in stream. The class {} does not implement flash.utils.IExternalizable but is aliased to an externalizable class.",
+                    traits.class_name
+                )
+                .into());
+            }
+
+            call_method(activation, object, "readExternal", &[self.target.into()])?;
+            return Ok(object);
+        }
+
+        for name in &traits.sealed {
+            let value = self.read_amf3_value(activation)?;
+            let name = public_name(activation, name);
+            object.set_property(object, &name, value, activation)?;
+        }
+
+        if traits.dynamic {
+            loop {
+                let name = self.read_amf3_string(activation)?;
+                if name.is_empty() {
+                    break;
+                }
+
+                let value = self.read_amf3_value(activation)?;
+                let name = public_name(activation, &name);
+                object.set_property(object, &name, value, activation)?;
+            }
+        }
+
+        Ok(object)
+    }
+
+    fn read_amf0_string(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<String, Error> {
+        let len = self.read_u16(activation)?;
+        self.read_utf8(activation, len.into())
+    }
+
+    fn read_amf0_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let marker = self.read_u8(activation)?;
+        Ok(match marker {
+            AMF0_NUMBER => self.read_f64(activation)?.into(),
+            AMF0_BOOLEAN => (self.read_u8(activation)? != 0).into(),
+            AMF0_STRING => {
+                let string = self.read_amf0_string(activation)?;
+                AvmString::new(activation.context.gc_context, string).into()
+            }
+            AMF0_LONG_STRING => {
+                let len = self.read_u32(activation)?;
+                let string = self.read_utf8(activation, len as usize)?;
+                AvmString::new(activation.context.gc_context, string).into()
+            }
+            AMF0_OBJECT => self.read_amf0_object(activation, "")?.into(),
+            AMF0_TYPED_OBJECT => {
+                let class_name = self.read_amf0_string(activation)?;
+                self.read_amf0_object(activation, &class_name)?.into()
+            }
+            AMF0_NULL => Value::Null,
+            AMF0_UNDEFINED => Value::Undefined,
+            AMF0_REFERENCE => {
+                let index = self.read_u16(activation)?;
+                self.amf0_objects
+                    .get(usize::from(index))
+                    .copied()
+                    .ok_or_else(|| format!("Invalid AMF0 object reference {}", index))?
+                    .into()
+            }
+            AMF0_AVMPLUS => self.read_amf3_value(activation)?,
+            _ => return Err(format!("Unsupported AMF0 type marker {:#x}", marker).into()),
+        })
+    }
+
+    fn read_amf0_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class_name: &str,
+    ) -> Result<Object<'gc>, Error> {
+        let mut object = instantiate(activation, class_name)?;
+        self.amf0_objects.push(object);
+
+        loop {
+            let name = self.read_amf0_string(activation)?;
+            if name.is_empty() {
+                let marker = self.read_u8(activation)?;
+                if marker != AMF0_OBJECT_END {
+                    return Err(format!("Unsupported AMF0 type marker {:#x}", marker).into());
+                }
+                break;
+            }
+
+            let value = self.read_amf0_value(activation)?;
+            let name = public_name(activation, &name);
+            object.set_property(object, &name, value, activation)?;
+        }
+
+        Ok(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_u29;
+
+    #[test]
+    fn u29_encoding() {
+        assert_eq!(encode_u29(0), [0x00]);
+        assert_eq!(encode_u29(0x7F), [0x7F]);
+        assert_eq!(encode_u29(0x80), [0x81, 0x00]);
+        assert_eq!(encode_u29(0x3FFF), [0xFF, 0x7F]);
+        assert_eq!(encode_u29(0x4000), [0x81, 0x80, 0x00]);
+        assert_eq!(encode_u29(0x1F_FFFF), [0xFF, 0xFF, 0x7F]);
+        assert_eq!(encode_u29(0x20_0000), [0x80, 0xC0, 0x80, 0x00]);
+        assert_eq!(encode_u29(0x1FFF_FFFF), [0xFF, 0xFF, 0xFF, 0xFF]);
+
+        // Negative integers are sent as their low 29 bits.
+        assert_eq!(encode_u29(-1i32 as u32), [0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Backing storage for `Array`
+
+use crate::avm2::value::Value;
+use gc_arena::Collect;
+
+/// The elements of an `Array`.
+///
+/// Arrays may be sparse: an index below the array's length which has never
+/// been assigned, or which has been deleted, is a hole rather than an
+/// `undefined` value.
+#[derive(Collect, Debug, Clone, Default)]
+#[collect(no_drop)]
+pub struct ArrayStorage<'gc> {
+    storage: Vec<Option<Value<'gc>>>,
+}
+
+impl<'gc> ArrayStorage<'gc> {
+    /// Construct an array of the given length, consisting entirely of holes.
+    pub fn new(length: usize) -> Self {
+        Self {
+            storage: vec![None; length],
+        }
+    }
+
+    /// Construct an array holding the given values.
+    pub fn from_args(values: &[Value<'gc>]) -> Self {
+        Self {
+            storage: values.iter().cloned().map(Some).collect(),
+        }
+    }
+
+    /// Retrieve a value, if the index is in bounds and not a hole.
+    pub fn get(&self, index: usize) -> Option<Value<'gc>> {
+        self.storage.get(index).cloned().flatten()
+    }
+
+    /// Store a value, growing the array if necessary.
+    pub fn set(&mut self, index: usize, value: Value<'gc>) {
+        if index >= self.storage.len() {
+            self.storage.resize(index + 1, None);
+        }
+
+        self.storage[index] = Some(value);
+    }
+
+    /// Replace a value with a hole, without changing the array's length.
+    pub fn delete(&mut self, index: usize) {
+        if let Some(slot) = self.storage.get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    /// Append a value to the end of the array.
+    pub fn push(&mut self, value: Value<'gc>) {
+        self.storage.push(Some(value));
+    }
+
+    pub fn length(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Truncate the array, or pad it with holes, to the given length.
+    pub fn set_length(&mut self, length: usize) {
+        self.storage.resize(length, None);
+    }
+
+    /// Iterate over the array's elements, yielding `None` for holes.
+    pub fn iter(&self) -> impl Iterator<Item = Option<Value<'gc>>> + '_ {
+        self.storage.iter().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holes() {
+        let mut array = ArrayStorage::new(2);
+        assert_eq!(array.length(), 2);
+        assert_eq!(array.get(0), None);
+
+        array.set(4, 5.0.into());
+        assert_eq!(array.length(), 5);
+        assert_eq!(array.get(3), None);
+        assert_eq!(array.get(4), Some(5.0.into()));
+        assert_eq!(array.get(5), None);
+
+        array.delete(4);
+        assert_eq!(array.length(), 5);
+        assert_eq!(array.get(4), None);
+    }
+
+    #[test]
+    fn length() {
+        let mut array = ArrayStorage::from_args(&[1.0.into(), 2.0.into(), 3.0.into()]);
+        array.push(4.0.into());
+        assert_eq!(array.length(), 4);
+
+        array.set_length(2);
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(1.0.into()), Some(2.0.into())]
+        );
+
+        array.set_length(3);
+        assert_eq!(array.get(2), None);
+        assert_eq!(array.length(), 3);
+    }
+}
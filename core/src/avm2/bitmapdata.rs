@@ -0,0 +1,542 @@
+//! Backing storage for `flash.display.BitmapData`
+
+use crate::color_transform::ColorTransform;
+use gc_arena::Collect;
+use swf::BlendMode;
+
+/// An affine transform from the pixels of a source to the pixels of a
+/// `BitmapData`, with the same fields as `flash.geom.Matrix`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrawMatrix {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub tx: f64,
+    pub ty: f64,
+}
+
+impl Default for DrawMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl DrawMatrix {
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        )
+    }
+
+    /// The transform undoing this one, or `None` if it squashes everything
+    /// into a line or a point.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+}
+
+/// A rectangle of whole pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl PixelRect {
+    /// The rectangle covering every pixel that the given area touches.
+    pub fn covering(x: f64, y: f64, width: f64, height: f64) -> Self {
+        let clamp = |v: f64| v.max(f64::from(i32::MIN)).min(f64::from(i32::MAX)) as i32;
+        let (x_min, y_min) = (clamp(x.floor()), clamp(y.floor()));
+        let (x_max, y_max) = (clamp((x + width).ceil()), clamp((y + height).ceil()));
+        Self {
+            x: x_min,
+            y: y_min,
+            width: x_max.saturating_sub(x_min).max(0),
+            height: y_max.saturating_sub(y_min).max(0),
+        }
+    }
+
+    /// The part of this rectangle that is also in `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let x_min = self.x.max(other.x);
+        let y_min = self.y.max(other.y);
+        let x_max = (self.x + self.width).min(other.x + other.width);
+        let y_max = (self.y + self.height).min(other.y + other.height);
+        Self {
+            x: x_min,
+            y: y_min,
+            width: (x_max - x_min).max(0),
+            height: (y_max - y_min).max(0),
+        }
+    }
+}
+
+/// The pixels of a `BitmapData`.
+///
+/// Like Flash Player, pixels are kept with their color premultiplied by their
+/// alpha, so colors written to transparent pixels lose precision.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(require_static)]
+pub struct BitmapDataStorage {
+    width: u32,
+    height: u32,
+    transparent: bool,
+
+    /// Premultiplied 0xAARRGGBB pixels, row by row.
+    pixels: Vec<u32>,
+}
+
+/// The largest width or height of a `BitmapData`.
+pub const MAX_DIMENSION: u32 = 8191;
+
+/// The largest number of pixels in a `BitmapData`.
+pub const MAX_PIXELS: u32 = 16_777_215;
+
+impl BitmapDataStorage {
+    /// Creates a bitmap filled with an unmultiplied 0xAARRGGBB color.
+    ///
+    /// Bitmaps that are not transparent ignore the alpha of the color.
+    pub fn new(width: u32, height: u32, transparent: bool, fill_color: u32) -> Self {
+        let mut storage = Self {
+            width,
+            height,
+            transparent,
+            pixels: vec![0; (width * height) as usize],
+        };
+        let fill_color = storage.premultiply(fill_color);
+        for pixel in storage.pixels.iter_mut() {
+            *pixel = fill_color;
+        }
+        storage
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// The rectangle covering the whole bitmap.
+    pub fn rect(&self) -> PixelRect {
+        PixelRect {
+            x: 0,
+            y: 0,
+            width: self.width as i32,
+            height: self.height as i32,
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Converts an unmultiplied color into the form it is stored in.
+    fn premultiply(&self, color: u32) -> u32 {
+        let alpha = if self.transparent { color >> 24 } else { 0xFF };
+        let channel = |shift: u32| ((color >> shift & 0xFF) * alpha + 127) / 255;
+        alpha << 24 | channel(16) << 16 | channel(8) << 8 | channel(0)
+    }
+
+    /// The unmultiplied 0xAARRGGBB color of a pixel, or 0 outside the bitmap.
+    pub fn get_pixel32(&self, x: i32, y: i32) -> u32 {
+        let pixel = match self.index(x, y) {
+            Some(index) => self.pixels[index],
+            None => return 0,
+        };
+        let alpha = pixel >> 24;
+        if alpha == 0 {
+            return 0;
+        }
+        let channel = |shift: u32| ((pixel >> shift & 0xFF) * 255 + alpha / 2) / alpha;
+        alpha << 24 | channel(16).min(255) << 16 | channel(8).min(255) << 8 | channel(0).min(255)
+    }
+
+    /// The unmultiplied 0xRRGGBB color of a pixel, or 0 outside the bitmap.
+    pub fn get_pixel(&self, x: i32, y: i32) -> u32 {
+        self.get_pixel32(x, y) & 0xFF_FFFF
+    }
+
+    /// Sets a pixel to an unmultiplied 0xAARRGGBB color.
+    pub fn set_pixel32(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = self.premultiply(color);
+        }
+    }
+
+    /// Sets the color of a pixel to an 0xRRGGBB color, keeping its alpha.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(index) = self.index(x, y) {
+            let alpha = self.pixels[index] & 0xFF00_0000;
+            self.pixels[index] = self.premultiply(alpha | (color & 0xFF_FFFF));
+        }
+    }
+
+    /// Sets every pixel in a rectangle to an unmultiplied 0xAARRGGBB color.
+    pub fn fill_rect(&mut self, rect: PixelRect, color: u32) {
+        let rect = rect.intersection(&self.rect());
+        let color = self.premultiply(color);
+        for y in rect.y..rect.y + rect.height {
+            let start = y as usize * self.width as usize + rect.x as usize;
+            for pixel in &mut self.pixels[start..start + rect.width as usize] {
+                *pixel = color;
+            }
+        }
+    }
+
+    /// Reads the premultiplied color of the source at a point, in pixels.
+    ///
+    /// Returns `None` if the point is not on the bitmap.
+    fn sample(&self, x: f64, y: f64, smoothing: bool) -> Option<[f64; 4]> {
+        if x < 0.0 || y < 0.0 || x >= f64::from(self.width) || y >= f64::from(self.height) {
+            return None;
+        }
+
+        let pixel = |x: i32, y: i32| {
+            let x = x.max(0).min(self.width as i32 - 1);
+            let y = y.max(0).min(self.height as i32 - 1);
+            unpack(self.pixels[y as usize * self.width as usize + x as usize])
+        };
+
+        if !smoothing {
+            return Some(pixel(x as i32, y as i32));
+        }
+
+        // Blend the four pixels whose centers surround the point.
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let [p00, p10, p01, p11] = [
+            pixel(x0, y0),
+            pixel(x0 + 1, y0),
+            pixel(x0, y0 + 1),
+            pixel(x0 + 1, y0 + 1),
+        ];
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            let top = p00[i] + (p10[i] - p00[i]) * fx;
+            let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+            *channel = top + (bottom - top) * fy;
+        }
+        Some(color)
+    }
+
+    /// Draws another bitmap onto this one, as `BitmapData.draw` does.
+    ///
+    /// The source is placed by `matrix`, has its colors changed by
+    /// `color_transform` and is composited onto the existing pixels with
+    /// `blend_mode`. Only the normal, add and multiply blend modes are
+    /// supported; any other mode is drawn as normal. Nothing is drawn outside
+    /// of `clip_rect`, if given. When `smoothing` is set, the source is
+    /// sampled bilinearly rather than from the nearest pixel.
+    pub fn draw(
+        &mut self,
+        source: &BitmapDataStorage,
+        matrix: &DrawMatrix,
+        color_transform: &ColorTransform,
+        blend_mode: BlendMode,
+        clip_rect: Option<PixelRect>,
+        smoothing: bool,
+    ) {
+        let inverse = match matrix.inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+
+        // Only visit the pixels that the source can land on.
+        let (width, height) = (f64::from(source.width), f64::from(source.height));
+        let corners = [
+            matrix.transform_point(0.0, 0.0),
+            matrix.transform_point(width, 0.0),
+            matrix.transform_point(0.0, height),
+            matrix.transform_point(width, height),
+        ];
+        let x_min = corners.iter().map(|c| c.0).fold(f64::INFINITY, f64::min);
+        let y_min = corners.iter().map(|c| c.1).fold(f64::INFINITY, f64::min);
+        let x_max = corners
+            .iter()
+            .map(|c| c.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let y_max = corners
+            .iter()
+            .map(|c| c.1)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let mut area = PixelRect::covering(x_min, y_min, x_max - x_min, y_max - y_min)
+            .intersection(&self.rect());
+        if let Some(clip_rect) = clip_rect {
+            area = area.intersection(&clip_rect);
+        }
+
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                let (u, v) = inverse.transform_point(f64::from(x) + 0.5, f64::from(y) + 0.5);
+                let color = match source.sample(u, v, smoothing) {
+                    Some(color) => apply_color_transform(color, color_transform),
+                    None => continue,
+                };
+
+                let index = y as usize * self.width as usize + x as usize;
+                let mut result = blend(color, unpack(self.pixels[index]), blend_mode);
+                if !self.transparent {
+                    result[3] = 1.0;
+                }
+                self.pixels[index] = pack(result);
+            }
+        }
+    }
+}
+
+/// Splits a premultiplied 0xAARRGGBB pixel into [r, g, b, a], from 0 to 1.
+fn unpack(pixel: u32) -> [f64; 4] {
+    let channel = |shift: u32| f64::from(pixel >> shift & 0xFF) / 255.0;
+    [channel(16), channel(8), channel(0), channel(24)]
+}
+
+/// Joins premultiplied [r, g, b, a] channels into a pixel.
+fn pack(color: [f64; 4]) -> u32 {
+    let channel = |value: f64| (value.max(0.0).min(1.0) * 255.0).round() as u32;
+    let alpha = channel(color[3]);
+    let color_channel = |value: f64| channel(value).min(alpha);
+    alpha << 24
+        | color_channel(color[0]) << 16
+        | color_channel(color[1]) << 8
+        | color_channel(color[2])
+}
+
+/// Applies a color transform to a premultiplied color.
+///
+/// The transform applies to the unmultiplied color, as it does in Flash.
+fn apply_color_transform(color: [f64; 4], transform: &ColorTransform) -> [f64; 4] {
+    if transform.is_identity() {
+        return color;
+    }
+
+    let [r, g, b, a] = color;
+    let unmultiply = |value: f64| if a > 0.0 { value / a } else { 0.0 };
+    let apply = |value: f64, mult: f32, add: f32| {
+        (value * f64::from(mult) + f64::from(add)).max(0.0).min(1.0)
+    };
+    let alpha = apply(a, transform.a_mult, transform.a_add);
+    [
+        apply(unmultiply(r), transform.r_mult, transform.r_add) * alpha,
+        apply(unmultiply(g), transform.g_mult, transform.g_add) * alpha,
+        apply(unmultiply(b), transform.b_mult, transform.b_add) * alpha,
+        alpha,
+    ]
+}
+
+/// Composites a premultiplied source color onto a premultiplied destination.
+fn blend(src: [f64; 4], dst: [f64; 4], blend_mode: BlendMode) -> [f64; 4] {
+    let (src_alpha, dst_alpha) = (src[3], dst[3]);
+    let mut result = [0.0; 4];
+    for (i, channel) in result.iter_mut().enumerate().take(3) {
+        let (s, d) = (src[i], dst[i]);
+        *channel = match blend_mode {
+            BlendMode::Add => (s + d).min(1.0),
+            BlendMode::Multiply => s * d + s * (1.0 - dst_alpha) + d * (1.0 - src_alpha),
+            _ => s + d * (1.0 - src_alpha),
+        };
+    }
+    result[3] = src_alpha + dst_alpha * (1.0 - src_alpha);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity_transform() -> ColorTransform {
+        ColorTransform {
+            r_mult: 1.0,
+            g_mult: 1.0,
+            b_mult: 1.0,
+            a_mult: 1.0,
+            r_add: 0.0,
+            g_add: 0.0,
+            b_add: 0.0,
+            a_add: 0.0,
+        }
+    }
+
+    /// A 2x2 source with a different color in each pixel.
+    fn quadrants() -> BitmapDataStorage {
+        let mut source = BitmapDataStorage::new(2, 2, true, 0);
+        source.set_pixel32(0, 0, 0xFFFF_0000);
+        source.set_pixel32(1, 0, 0xFF00_FF00);
+        source.set_pixel32(0, 1, 0xFF00_00FF);
+        source.set_pixel32(1, 1, 0xFFFF_FFFF);
+        source
+    }
+
+    #[test]
+    fn pixels_are_premultiplied() {
+        let mut bitmap = BitmapDataStorage::new(2, 2, true, 0x8000_FF00);
+        assert_eq!(bitmap.get_pixel32(1, 1), 0x8000_FF00);
+        assert_eq!(bitmap.get_pixel32(2, 0), 0);
+
+        // Colors of fully transparent pixels are lost.
+        bitmap.set_pixel32(0, 0, 0x00FF_FFFF);
+        assert_eq!(bitmap.get_pixel32(0, 0), 0);
+
+        // setPixel keeps the alpha of the pixel.
+        bitmap.set_pixel(1, 0, 0x0000_00FF);
+        assert_eq!(bitmap.get_pixel32(1, 0), 0x8000_00FF);
+
+        let opaque = BitmapDataStorage::new(1, 1, false, 0x0012_3456);
+        assert_eq!(opaque.get_pixel32(0, 0), 0xFF12_3456);
+    }
+
+    #[test]
+    fn draw_with_matrix() {
+        let mut target = BitmapDataStorage::new(6, 6, true, 0);
+        let matrix = DrawMatrix {
+            a: 2.0,
+            d: 2.0,
+            tx: 1.0,
+            ty: 2.0,
+            ..DrawMatrix::IDENTITY
+        };
+        target.draw(
+            &quadrants(),
+            &matrix,
+            &identity_transform(),
+            BlendMode::Normal,
+            None,
+            false,
+        );
+
+        assert_eq!(target.get_pixel32(0, 2), 0);
+        assert_eq!(target.get_pixel32(1, 2), 0xFFFF_0000);
+        assert_eq!(target.get_pixel32(2, 3), 0xFFFF_0000);
+        assert_eq!(target.get_pixel32(3, 2), 0xFF00_FF00);
+        assert_eq!(target.get_pixel32(1, 4), 0xFF00_00FF);
+        assert_eq!(target.get_pixel32(4, 5), 0xFFFF_FFFF);
+        assert_eq!(target.get_pixel32(5, 5), 0);
+        assert_eq!(target.get_pixel32(1, 1), 0);
+    }
+
+    #[test]
+    fn draw_with_clip_rect() {
+        let mut target = BitmapDataStorage::new(2, 2, true, 0);
+        target.draw(
+            &quadrants(),
+            &DrawMatrix::IDENTITY,
+            &identity_transform(),
+            BlendMode::Normal,
+            Some(PixelRect {
+                x: 1,
+                y: 0,
+                width: 5,
+                height: 1,
+            }),
+            false,
+        );
+
+        assert_eq!(target.get_pixel32(0, 0), 0);
+        assert_eq!(target.get_pixel32(1, 0), 0xFF00_FF00);
+        assert_eq!(target.get_pixel32(1, 1), 0);
+    }
+
+    #[test]
+    fn draw_with_color_transform() {
+        let mut target = BitmapDataStorage::new(1, 1, true, 0);
+        let transform = ColorTransform {
+            r_mult: 0.5,
+            a_mult: 0.5,
+            b_add: 1.0,
+            ..identity_transform()
+        };
+        let source = BitmapDataStorage::new(1, 1, true, 0xFFFF_0000);
+        target.draw(
+            &source,
+            &DrawMatrix::IDENTITY,
+            &transform,
+            BlendMode::Normal,
+            None,
+            false,
+        );
+
+        assert_eq!(target.get_pixel32(0, 0), 0x8080_00FF);
+    }
+
+    #[test]
+    fn draw_with_blend_modes() {
+        let source = BitmapDataStorage::new(1, 1, true, 0xFF80_8080);
+        let draw = |blend_mode| {
+            let mut target = BitmapDataStorage::new(1, 1, false, 0x00FF_4000);
+            target.draw(
+                &source,
+                &DrawMatrix::IDENTITY,
+                &identity_transform(),
+                blend_mode,
+                None,
+                false,
+            );
+            target.get_pixel32(0, 0)
+        };
+
+        assert_eq!(draw(BlendMode::Normal), 0xFF80_8080);
+        assert_eq!(draw(BlendMode::Add), 0xFFFF_C080);
+        assert_eq!(draw(BlendMode::Multiply), 0xFF80_2000);
+    }
+
+    #[test]
+    fn draw_with_smoothing() {
+        let mut source = BitmapDataStorage::new(2, 1, false, 0);
+        source.set_pixel32(1, 0, 0xFFFF_FFFF);
+        let matrix = DrawMatrix {
+            a: 2.0,
+            ..DrawMatrix::IDENTITY
+        };
+        let draw = |smoothing| {
+            let mut target = BitmapDataStorage::new(4, 1, false, 0);
+            target.draw(
+                &source,
+                &matrix,
+                &identity_transform(),
+                BlendMode::Normal,
+                None,
+                smoothing,
+            );
+            (0..4).map(|x| target.get_pixel(x, 0)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draw(false), [0, 0, 0xFF_FFFF, 0xFF_FFFF]);
+        assert_eq!(draw(true), [0, 0x40_4040, 0xBF_BFBF, 0xFF_FFFF]);
+    }
+}
@@ -0,0 +1,415 @@
+//! Backing storage for `flash.utils.ByteArray`
+
+use crate::avm2::Error;
+use gc_arena::Collect;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// The compression algorithms accepted by `ByteArray.compress` and
+/// `ByteArray.uncompress`, as named by `flash.utils.CompressionAlgorithm`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    /// A zlib stream, with header and Adler-32 checksum.
+    Zlib,
+
+    /// A raw DEFLATE stream with no header.
+    Deflate,
+
+    /// An LZMA stream in the format Flash writes it: 5 bytes of encoder
+    /// properties, followed by the uncompressed length as a 64-bit little
+    /// endian integer, followed by the compressed data.
+    Lzma,
+}
+
+impl Default for CompressionAlgorithm {
+    fn default() -> Self {
+        CompressionAlgorithm::Zlib
+    }
+}
+
+impl FromStr for CompressionAlgorithm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zlib" => Ok(CompressionAlgorithm::Zlib),
+            "deflate" => Ok(CompressionAlgorithm::Deflate),
+            "lzma" => Ok(CompressionAlgorithm::Lzma),
+            _ => Err(
+                "ArgumentError: Error #2008: Parameter algorithm must be one of the accepted values."
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// The AMF version used by `ByteArray.readObject` and `writeObject`, as
+/// named by `flash.net.ObjectEncoding`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ObjectEncoding {
+    /// AMF0, as used by ActionScript 1 and 2.
+    Amf0,
+
+    /// AMF3, as used by ActionScript 3.
+    Amf3,
+}
+
+impl ObjectEncoding {
+    /// Look up an encoding by its `ObjectEncoding` constant.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(ObjectEncoding::Amf0),
+            3 => Some(ObjectEncoding::Amf3),
+            _ => None,
+        }
+    }
+
+    /// The `ObjectEncoding` constant for this encoding.
+    pub fn to_u32(self) -> u32 {
+        match self {
+            ObjectEncoding::Amf0 => 0,
+            ObjectEncoding::Amf3 => 3,
+        }
+    }
+}
+
+impl Default for ObjectEncoding {
+    fn default() -> Self {
+        ObjectEncoding::Amf3
+    }
+}
+
+/// The bytes held by a `ByteArray`, along with its read/write position.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(require_static)]
+pub struct ByteArrayStorage {
+    /// The underlying data.
+    bytes: Vec<u8>,
+
+    /// The current position used by reads and writes.
+    position: usize,
+
+    /// The encoding used when reading and writing objects.
+    object_encoding: ObjectEncoding,
+}
+
+impl ByteArrayStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    pub fn object_encoding(&self) -> ObjectEncoding {
+        self.object_encoding
+    }
+
+    pub fn set_object_encoding(&mut self, object_encoding: ObjectEncoding) {
+        self.object_encoding = object_encoding;
+    }
+
+    /// The number of bytes between the current position and the end of the
+    /// array.
+    pub fn bytes_available(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    /// Resize the array, padding with zeroes if it grows.
+    ///
+    /// The position is clamped to the new length.
+    pub fn set_length(&mut self, length: usize) {
+        self.bytes.resize(length, 0);
+        self.position = self.position.min(length);
+    }
+
+    /// Empty the array and reset the position.
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.position = 0;
+    }
+
+    /// Write bytes at the current position, growing the array as needed.
+    pub fn write_bytes(&mut self, data: &[u8]) {
+        let end = self.position + data.len();
+        if end > self.bytes.len() {
+            self.bytes.resize(end, 0);
+        }
+
+        self.bytes[self.position..end].copy_from_slice(data);
+        self.position = end;
+    }
+
+    /// Read bytes from the current position.
+    ///
+    /// Reading past the end of the array raises an `EOFError` without moving
+    /// the position.
+    pub fn read_bytes(&mut self, amount: usize) -> Result<&[u8], Error> {
+        if amount > self.bytes_available() {
+            return Err("EOFError: Error #2030: End of file was encountered.".into());
+        }
+
+        let start = self.position;
+        self.position += amount;
+
+        Ok(&self.bytes[start..self.position])
+    }
+
+    /// Read bytes at an absolute address, as the fast memory opcodes do when
+    /// this array is the domain memory. The position is not used or moved.
+    pub fn read_at(&self, address: i32, amount: usize) -> Result<&[u8], Error> {
+        let start = self.check_range(address, amount)?;
+
+        Ok(&self.bytes[start..start + amount])
+    }
+
+    /// Write bytes at an absolute address, as the fast memory opcodes do when
+    /// this array is the domain memory. Unlike `write_bytes`, this never
+    /// grows the array.
+    pub fn write_at(&mut self, address: i32, data: &[u8]) -> Result<(), Error> {
+        let start = self.check_range(address, data.len())?;
+        self.bytes[start..start + data.len()].copy_from_slice(data);
+
+        Ok(())
+    }
+
+    /// Check that `amount` bytes starting at `address` lie within the array.
+    fn check_range(&self, address: i32, amount: usize) -> Result<usize, Error> {
+        if address < 0 || address as usize + amount > self.bytes.len() {
+            return Err("RangeError: Error #1506: The specified range is invalid.".into());
+        }
+
+        Ok(address as usize)
+    }
+
+    /// Compress the entire contents of the array in place.
+    ///
+    /// As in Flash, the position is moved to the end of the new data.
+    pub fn compress(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error> {
+        let compressed = match algorithm {
+            CompressionAlgorithm::Zlib => {
+                let mut encoder = libflate::zlib::Encoder::new(Vec::new())?;
+                encoder.write_all(&self.bytes)?;
+                encoder.finish().into_result()?
+            }
+            CompressionAlgorithm::Deflate => {
+                let mut encoder = libflate::deflate::Encoder::new(Vec::new());
+                encoder.write_all(&self.bytes)?;
+                encoder.finish().into_result()?
+            }
+            CompressionAlgorithm::Lzma => lzma_compress(&self.bytes)?,
+        };
+
+        self.position = compressed.len();
+        self.bytes = compressed;
+
+        Ok(())
+    }
+
+    /// Decompress the entire contents of the array in place.
+    ///
+    /// On success the position is reset to zero. Malformed data raises an
+    /// `IOError` and leaves the array untouched.
+    pub fn uncompress(&mut self, algorithm: CompressionAlgorithm) -> Result<(), Error> {
+        let mut decompressed = Vec::new();
+        let result = match algorithm {
+            CompressionAlgorithm::Zlib => libflate::zlib::Decoder::new(&self.bytes[..])
+                .and_then(|mut decoder| decoder.read_to_end(&mut decompressed)),
+            CompressionAlgorithm::Deflate => {
+                libflate::deflate::Decoder::new(&self.bytes[..]).read_to_end(&mut decompressed)
+            }
+            CompressionAlgorithm::Lzma => lzma_uncompress(&self.bytes, &mut decompressed),
+        };
+
+        if result.is_err() {
+            return Err("IOError: Error #2058: There was an error decompressing the data.".into());
+        }
+
+        self.bytes = decompressed;
+        self.position = 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "lzma")]
+fn lzma_compress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    use xz2::stream::{LzmaOptions, Stream};
+    use xz2::write::XzEncoder;
+
+    let options = LzmaOptions::new_preset(6)?;
+    let stream = Stream::new_lzma_encoder(&options)?;
+    let mut encoder = XzEncoder::new_stream(Vec::new(), stream);
+    encoder.write_all(data)?;
+    let mut compressed = encoder.finish()?;
+
+    // The LZMA "alone" encoder writes an unknown length into the header, but
+    // Flash always writes the real uncompressed length.
+    if compressed.len() < 13 {
+        return Err("LZMA encoder produced a truncated header".into());
+    }
+    compressed[5..13].copy_from_slice(&(data.len() as u64).to_le_bytes());
+
+    Ok(compressed)
+}
+
+#[cfg(feature = "lzma")]
+fn lzma_uncompress(data: &[u8], output: &mut Vec<u8>) -> std::io::Result<usize> {
+    use xz2::read::XzDecoder;
+    use xz2::stream::Stream;
+
+    let stream = Stream::new_lzma_decoder(u64::max_value())?;
+    XzDecoder::new_stream(data, stream).read_to_end(output)
+}
+
+#[cfg(not(feature = "lzma"))]
+fn lzma_compress(_data: &[u8]) -> Result<Vec<u8>, Error> {
+    Err("Support for LZMA compression is not enabled.".into())
+}
+
+#[cfg(not(feature = "lzma"))]
+fn lzma_uncompress(_data: &[u8], _output: &mut Vec<u8>) -> std::io::Result<usize> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "Support for LZMA compression is not enabled.",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"Hello, world! Hello, world! Hello, world! Hello, world!";
+
+    fn from_vec(bytes: Vec<u8>) -> ByteArrayStorage {
+        ByteArrayStorage {
+            bytes,
+            ..Default::default()
+        }
+    }
+
+    fn round_trip(algorithm: CompressionAlgorithm) {
+        let mut storage = from_vec(DATA.to_vec());
+        storage.compress(algorithm).unwrap();
+        assert_ne!(&storage.bytes[..], DATA);
+        assert_eq!(storage.position(), storage.len());
+
+        storage.uncompress(algorithm).unwrap();
+        assert_eq!(&storage.bytes[..], DATA);
+        assert_eq!(storage.position(), 0);
+        assert_eq!(storage.len(), DATA.len());
+    }
+
+    #[test]
+    fn zlib_round_trip() {
+        round_trip(CompressionAlgorithm::Zlib);
+    }
+
+    #[test]
+    fn deflate_round_trip() {
+        round_trip(CompressionAlgorithm::Deflate);
+    }
+
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn lzma_round_trip() {
+        round_trip(CompressionAlgorithm::Lzma);
+    }
+
+    #[test]
+    #[cfg(feature = "lzma")]
+    fn lzma_header_has_length() {
+        let mut storage = from_vec(DATA.to_vec());
+        storage.compress(CompressionAlgorithm::Lzma).unwrap();
+        let mut length = [0u8; 8];
+        length.copy_from_slice(&storage.bytes[5..13]);
+        assert_eq!(u64::from_le_bytes(length), DATA.len() as u64);
+    }
+
+    #[test]
+    fn deflate_has_no_zlib_header() {
+        let mut zlib = from_vec(DATA.to_vec());
+        zlib.compress(CompressionAlgorithm::Zlib).unwrap();
+        let mut deflate = from_vec(DATA.to_vec());
+        deflate.compress(CompressionAlgorithm::Deflate).unwrap();
+        assert_eq!(zlib.len(), deflate.len() + 6);
+    }
+
+    #[test]
+    fn malformed_data_is_an_error() {
+        for &algorithm in &[
+            CompressionAlgorithm::Zlib,
+            CompressionAlgorithm::Deflate,
+            CompressionAlgorithm::Lzma,
+        ] {
+            let mut storage = from_vec(vec![0xFF; 32]);
+            storage.set_position(7);
+            assert!(storage.uncompress(algorithm).is_err());
+            assert_eq!(&storage.bytes[..], &[0xFF; 32][..]);
+            assert_eq!(storage.position(), 7);
+        }
+    }
+
+    #[test]
+    fn read_write_bookkeeping() {
+        let mut storage = ByteArrayStorage::new();
+        storage.write_bytes(b"abcdef");
+        assert_eq!(storage.position(), 6);
+        assert_eq!(storage.bytes_available(), 0);
+
+        storage.set_position(2);
+        storage.write_bytes(b"XY");
+        assert_eq!(&storage.bytes[..], b"abXYef");
+        assert_eq!(storage.read_bytes(2).unwrap(), b"ef");
+        assert!(storage.read_bytes(1).is_err());
+        assert_eq!(storage.position(), 6);
+
+        storage.set_length(3);
+        assert_eq!(storage.position(), 3);
+        assert_eq!(&storage.bytes[..], b"abX");
+    }
+
+    #[test]
+    fn absolute_access_is_bounds_checked() {
+        let mut storage = from_vec(vec![0; 8]);
+        storage.set_position(3);
+        storage.write_at(4, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(storage.read_at(4, 4).unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(storage.position(), 3);
+
+        assert!(storage.read_at(5, 4).is_err());
+        assert!(storage.read_at(-1, 1).is_err());
+        assert!(storage.write_at(8, &[0]).is_err());
+        assert_eq!(storage.len(), 8);
+    }
+
+    #[test]
+    fn algorithm_names() {
+        assert_eq!(
+            "zlib".parse::<CompressionAlgorithm>().unwrap(),
+            CompressionAlgorithm::Zlib
+        );
+        assert_eq!(
+            "deflate".parse::<CompressionAlgorithm>().unwrap(),
+            CompressionAlgorithm::Deflate
+        );
+        assert_eq!(
+            "lzma".parse::<CompressionAlgorithm>().unwrap(),
+            CompressionAlgorithm::Lzma
+        );
+        assert!("gzip".parse::<CompressionAlgorithm>().is_err());
+    }
+}
@@ -140,6 +140,16 @@ impl<'gc> Class<'gc> {
         )
     }
 
+    /// Determine if instances of this class can hold dynamic properties.
+    pub fn is_sealed(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::Sealed)
+    }
+
+    /// Determine if this class is an interface.
+    pub fn is_interface(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::Interface)
+    }
+
     /// Set the attributes of the class (sealed/final/interface status).
     pub fn set_attributes(&mut self, attributes: EnumSet<ClassAttributes>) {
         self.attributes = CollectWrapper(attributes);
@@ -343,7 +353,7 @@ impl<'gc> Class<'gc> {
     /// class. They will not be accessible on the class prototype, and any
     /// properties defined on the prototype will be shadowed by these traits.
     pub fn define_instance_trait(&mut self, my_trait: Trait<'gc>) {
-        self.class_traits.push(my_trait);
+        self.instance_traits.push(my_trait);
     }
 
     /// Given a name, append instance traits matching the name to a list of
@@ -364,6 +374,12 @@ impl<'gc> Class<'gc> {
         do_trait_lookup(name, known_traits, &self.instance_traits)
     }
 
+    /// The traits defined on instances of this class, not including those of
+    /// its superclasses.
+    pub fn instance_traits(&self) -> &[Trait<'gc>] {
+        &self.instance_traits
+    }
+
     /// Determines if this class provides a given trait on it's instances.
     pub fn has_instance_trait(&self, name: &QName<'gc>) -> bool {
         for trait_entry in self.instance_traits.iter() {
@@ -0,0 +1,79 @@
+//! Backing storage for `flash.utils.Dictionary`
+
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use gc_arena::Collect;
+
+/// The entries of a `Dictionary` that are keyed by object identity.
+///
+/// Primitive keys are stored as ordinary dynamic properties on the dictionary
+/// itself; only object keys, which must not be coerced to strings, live here.
+///
+/// Keys are always held strongly, even for dictionaries constructed with
+/// `weakKeys` set: our garbage collector has no weak references to sweep
+/// entries with.
+#[derive(Collect, Debug, Clone, Default)]
+#[collect(no_drop)]
+pub struct DictionaryStorage<'gc> {
+    /// Each object key and its value, in insertion order.
+    entries: Vec<(Object<'gc>, Value<'gc>)>,
+}
+
+impl<'gc> DictionaryStorage<'gc> {
+    /// Construct empty dictionary storage.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of object keys in the dictionary.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn position(&self, key: Object<'gc>) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|(other, _)| Object::ptr_eq(*other, key))
+    }
+
+    /// Retrieve the value stored under an object key.
+    pub fn get(&self, key: Object<'gc>) -> Option<Value<'gc>> {
+        self.position(key).map(|i| self.entries[i].1.clone())
+    }
+
+    /// Store a value under an object key, replacing any existing value.
+    pub fn set(&mut self, key: Object<'gc>, value: Value<'gc>) {
+        match self.position(key) {
+            Some(i) => self.entries[i].1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    /// Remove an object key, returning whether it was present.
+    pub fn delete(&mut self, key: Object<'gc>) -> bool {
+        match self.position(key) {
+            Some(i) => {
+                self.entries.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether an object key is present.
+    pub fn has(&self, key: Object<'gc>) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Retrieve the key and value at a given enumeration index.
+    ///
+    /// Enumeration indices start at 1, as with the `hasnext` family of
+    /// opcodes.
+    pub fn entry_at(&self, index: u32) -> Option<(Object<'gc>, Value<'gc>)> {
+        let index = (index as usize).checked_sub(1)?;
+
+        self.entries.get(index).cloned()
+    }
+}
@@ -0,0 +1,478 @@
+//! Binding display objects to the AVM2 objects that represent them
+
+use crate::avm2::activation::Activation;
+use crate::avm2::events::{broadcast_event, dispatch_event};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::display_object::{Avm2MousePick, DisplayObject, MovieClip, TDisplayObject};
+use swf::Twips;
+
+/// Look up the constructor of a class in the global scope.
+fn class_constr<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: QName<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+
+    globals
+        .get_property(globals, &name, activation)?
+        .coerce_to_object(activation)
+}
+
+/// The name of the builtin class that represents a display object which the
+/// movie did not give a class of its own.
+fn default_class_name(display_object: DisplayObject<'_>) -> &'static str {
+    if display_object.as_movie_clip().is_some() {
+        "MovieClip"
+    } else if display_object.as_graphic().is_some() || display_object.as_morph_shape().is_some() {
+        "Shape"
+    } else if display_object.as_button().is_some() || display_object.as_edit_text().is_some() {
+        // TODO: These should be `SimpleButton` and `TextField`.
+        "InteractiveObject"
+    } else {
+        "DisplayObject"
+    }
+}
+
+/// Construct an instance of a class to represent a display object, and bind
+/// the two together.
+///
+/// The object is bound before its constructor runs, so that the constructor
+/// can already see the display object's properties.
+pub fn construct_display_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+    mut constr: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let object = proto.construct(activation, &[])?;
+
+    object.init_display_object(activation.context.gc_context, display_object);
+    display_object.set_object2(activation.context.gc_context, object);
+
+    constr.call(Some(object), &[], activation, Some(proto))?;
+
+    Ok(object)
+}
+
+/// Get the AVM2 object that represents a display object, creating one if it
+/// does not have one yet.
+pub fn display_object_to_avm2<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> Result<Object<'gc>, Error> {
+    if let Some(object) = display_object.object2() {
+        return Ok(object);
+    }
+
+    let class_name = QName::new(
+        Namespace::package("flash.display"),
+        default_class_name(display_object),
+    );
+    let constr = class_constr(activation, class_name)?;
+
+    construct_display_object(activation, display_object, constr)
+}
+
+/// Get the `Stage`, creating it the first time it is asked for.
+///
+/// The stage is the parent of the root movie's main timeline. It has no
+/// display object of its own.
+pub fn stage_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+    if let Some(stage) = activation.context.avm2.stage {
+        return Ok(stage);
+    }
+
+    let mut constr = class_constr(
+        activation,
+        QName::new(Namespace::package("flash.display"), "Stage"),
+    )?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let stage = proto.construct(activation, &[])?;
+
+    activation.context.avm2.stage = Some(stage);
+
+    Ok(stage)
+}
+
+/// Get the AVM2 object that represents a display object's parent.
+///
+/// The root movie's main timeline has the stage as its parent. Any other
+/// object without a parent has none.
+pub fn display_parent_to_avm2<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> Result<Value<'gc>, Error> {
+    match display_object.parent() {
+        Some(parent) => Ok(display_object_to_avm2(activation, parent)?.into()),
+        None if is_root_movie(activation, display_object) => Ok(stage_object(activation)?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Determine if a display object is the main timeline of the root movie.
+pub fn is_root_movie<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> bool {
+    activation
+        .context
+        .levels
+        .get(&0)
+        .map(|root| DisplayObject::ptr_eq(*root, display_object))
+        .unwrap_or(false)
+}
+
+/// Construct the document class of a movie, as given by its `SymbolClass`
+/// tag, on the movie's main timeline.
+pub fn construct_document_class<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    root: DisplayObject<'gc>,
+    class_name: &str,
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let name = QName::from_qualified_name(class_name, activation.context.gc_context);
+
+    let result = class_constr(&mut activation, name)
+        .and_then(|constr| construct_display_object(&mut activation, root, constr));
+
+    if let Err(e) = result {
+        log::error!("Could not construct document class {}: {}", class_name, e);
+    }
+}
+
+/// Find the object that receives AVM2 mouse events at the given stage
+/// position, which is the stage if nothing else under the mouse can.
+fn mouse_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    root: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) -> Result<(Object<'gc>, (Twips, Twips)), Error> {
+    match root.mouse_pick_avm2(&mut activation.context, point) {
+        Avm2MousePick::Hit(target) => Ok((
+            display_object_to_avm2(activation, target)?,
+            target.global_to_local(point),
+        )),
+        Avm2MousePick::PropagateToParent | Avm2MousePick::Miss => {
+            Ok((stage_object(activation)?, point))
+        }
+    }
+}
+
+/// Dispatch a bubbling `MouseEvent` of the given type.
+fn dispatch_mouse_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    local: (Twips, Twips),
+) -> Result<(), Error> {
+    let mut constr = class_constr(
+        activation,
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+    )?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let args = [
+        event_type.into(),
+        true.into(),
+        false.into(),
+        local.0.to_pixels().into(),
+        local.1.to_pixels().into(),
+    ];
+    let event = proto.construct(activation, &args)?;
+    constr.call(Some(event), &args, activation, Some(proto))?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Dispatch `mouseDown` to the object under the mouse in a movie whose main
+/// timeline is `root`.
+pub fn mouse_down<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    root: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let result = mouse_target(&mut activation, root, point).and_then(|(target, local)| {
+        activation.context.avm2.mouse_down_target = Some(target);
+        dispatch_mouse_event(&mut activation, target, "mouseDown", local)
+    });
+
+    if let Err(e) = result {
+        log::error!("Error dispatching mouseDown: {}", e);
+    }
+}
+
+/// Dispatch `mouseUp` to the object under the mouse in a movie whose main
+/// timeline is `root`, followed by `click` if the mouse went down on the same
+/// object.
+pub fn mouse_up<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    root: DisplayObject<'gc>,
+    point: (Twips, Twips),
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let down_target = activation.context.avm2.mouse_down_target.take();
+    let result = mouse_target(&mut activation, root, point).and_then(|(target, local)| {
+        dispatch_mouse_event(&mut activation, target, "mouseUp", local)?;
+
+        if down_target.map(|down| Object::ptr_eq(down, target)) == Some(true) {
+            dispatch_mouse_event(&mut activation, target, "click", local)?;
+        }
+
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        log::error!("Error dispatching mouseUp: {}", e);
+    }
+}
+
+/// How many more times frame scripts are run in one frame, because scripts
+/// moved playheads to frames that have scripts of their own.
+const MAX_CHAINED_GOTOS: usize = 5;
+
+/// Collect the movie clips in a display object's subtree, parents first.
+fn collect_movie_clips<'gc>(display_object: DisplayObject<'gc>, clips: &mut Vec<MovieClip<'gc>>) {
+    if let Some(clip) = display_object.as_movie_clip() {
+        clips.push(clip);
+    }
+
+    for child in display_object.children() {
+        collect_movie_clips(child, clips);
+    }
+}
+
+/// Run the frame scripts of the movie clips under `root` whose playheads
+/// entered a frame with a script since they last ran.
+///
+/// A goto in a frame script moves the playhead straight away, but the script
+/// of the frame it lands on waits for another pass over the clips. Flash
+/// gives up on scripts queued by chains of more than a few gotos.
+pub fn run_frame_scripts<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, root: DisplayObject<'gc>) {
+    if context.avm2.running_frame_scripts {
+        return;
+    }
+    context.avm2.running_frame_scripts = true;
+
+    let mut clips = Vec::new();
+    for _ in 0..=MAX_CHAINED_GOTOS {
+        collect_movie_clips(root, &mut clips);
+
+        let mut ran_script = false;
+        for clip in clips.drain(..) {
+            if let Some(script) = clip.take_queued_frame_script(context.gc_context) {
+                ran_script = true;
+
+                let mut activation = Activation::from_nothing(context.reborrow());
+                let result = display_object_to_avm2(&mut activation, clip.into())
+                    .and_then(|this| script.call(Some(this), &[], &mut activation, None));
+                if let Err(e) = result {
+                    log::error!("Error running frame script: {}", e);
+                }
+            }
+        }
+
+        if !ran_script {
+            break;
+        }
+    }
+
+    // Anything still queued was reached by too many chained gotos.
+    collect_movie_clips(root, &mut clips);
+    for clip in clips {
+        clip.take_queued_frame_script(context.gc_context);
+    }
+
+    context.avm2.running_frame_scripts = false;
+}
+
+/// Remember that an object listens for broadcast events, if it is one that
+/// hears them. Only display objects and the stage do.
+pub fn register_broadcast_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) {
+    let is_stage = activation
+        .context
+        .avm2
+        .stage
+        .map_or(false, |stage| Object::ptr_eq(stage, object));
+    if object.as_display_object().is_none() && !is_stage {
+        return;
+    }
+
+    let broadcast_list = &mut activation.context.avm2.broadcast_list;
+    if !broadcast_list
+        .iter()
+        .any(|listener| Object::ptr_eq(*listener, object))
+    {
+        broadcast_list.push(object);
+    }
+}
+
+/// Broadcast a frame event, such as `enterFrame`, to every display object
+/// that listens for it, in the order they started listening.
+///
+/// Objects that start listening while the event is broadcast don't hear it
+/// until the next frame.
+pub fn broadcast_frame_event<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    event_type: &'static str,
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let targets = activation.context.avm2.broadcast_list.clone();
+
+    for target in targets {
+        let listening = target.as_dispatch().map_or(false, |dispatch_list| {
+            dispatch_list.has_event_listener(event_type)
+        });
+        if !listening {
+            continue;
+        }
+
+        let result = class_constr(
+            &mut activation,
+            QName::new(Namespace::package("flash.events"), "Event"),
+        )
+        .and_then(|mut constr| {
+            let proto = constr
+                .get_property(
+                    constr,
+                    &QName::new(Namespace::public_namespace(), "prototype"),
+                    &mut activation,
+                )?
+                .coerce_to_object(&mut activation)?;
+            let args = [event_type.into(), false.into(), false.into()];
+            let event = proto.construct(&mut activation, &args)?;
+            constr.call(Some(event), &args, &mut activation, Some(proto))?;
+
+            broadcast_event(&mut activation, target, event)
+        });
+
+        if let Err(e) = result {
+            log::error!("Error dispatching {}: {}", event_type, e);
+        }
+    }
+}
+
+/// Construct a bubbling `TextEvent` and dispatch it to a text field.
+///
+/// Returns `false` if the event was cancelled.
+fn dispatch_text_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    text_field: DisplayObject<'gc>,
+    event_type: &'static str,
+    cancelable: bool,
+    text: &str,
+) -> Result<bool, Error> {
+    let target = display_object_to_avm2(activation, text_field)?;
+    let mut constr = class_constr(
+        activation,
+        QName::new(Namespace::package("flash.events"), "TextEvent"),
+    )?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let text = AvmString::new(activation.context.gc_context, text);
+    let args = [
+        event_type.into(),
+        true.into(),
+        cancelable.into(),
+        text.into(),
+    ];
+    let event = proto.construct(activation, &args)?;
+    constr.call(Some(event), &args, activation, Some(proto))?;
+
+    dispatch_event(activation, target, event)
+}
+
+/// Dispatch a bubbling `TextEvent.LINK` to a text field whose `event:` link
+/// was clicked, carrying the rest of the link's URL.
+pub fn text_link<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    text_field: DisplayObject<'gc>,
+    text: &str,
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    if let Err(e) = dispatch_text_event(&mut activation, text_field, "link", false, text) {
+        log::error!("Error dispatching link: {}", e);
+    }
+}
+
+/// Dispatch a bubbling, cancelable `TextEvent.TEXT_INPUT` to a text field
+/// that the user is typing into.
+///
+/// Returns `false` if a listener called `preventDefault`, in which case the
+/// text should not be inserted.
+pub fn text_input<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    text_field: DisplayObject<'gc>,
+    text: &str,
+) -> bool {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    match dispatch_text_event(&mut activation, text_field, "textInput", true, text) {
+        Ok(handled) => handled,
+        Err(e) => {
+            log::error!("Error dispatching textInput: {}", e);
+            true
+        }
+    }
+}
+
+/// Dispatch a bubbling `Event.CHANGE` to a text field whose text the user
+/// changed.
+pub fn text_change<'gc>(context: &mut UpdateContext<'_, 'gc, '_>, text_field: DisplayObject<'gc>) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let result = display_object_to_avm2(&mut activation, text_field).and_then(|target| {
+        let mut constr = class_constr(
+            &mut activation,
+            QName::new(Namespace::package("flash.events"), "Event"),
+        )?;
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                &mut activation,
+            )?
+            .coerce_to_object(&mut activation)?;
+        let args = ["change".into(), true.into(), false.into()];
+        let event = proto.construct(&mut activation, &args)?;
+        constr.call(Some(event), &args, &mut activation, Some(proto))?;
+
+        dispatch_event(&mut activation, target, event)?;
+
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        log::error!("Error dispatching change: {}", e);
+    }
+}
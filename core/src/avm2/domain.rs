@@ -0,0 +1,187 @@
+//! Application Domains
+
+use crate::avm2::names::QName;
+use crate::avm2::object::Object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::HashSet;
+
+/// Represents a set of scripts and movies that share traits across different
+/// script-global scopes.
+///
+/// Domains form a chain: names defined in a parent domain are visible to, and
+/// take precedence over, names defined in its children. Sibling domains do not
+/// see each other's definitions.
+#[derive(Copy, Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct Domain<'gc>(GcCell<'gc, DomainData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct DomainData<'gc> {
+    /// The object that the definitions of this domain are installed on.
+    ///
+    /// TODO: All domains currently share the player's global scope, so
+    /// definitions in sibling domains can still clash with each other when
+    /// scripts look them up by name.
+    globals: Object<'gc>,
+
+    /// The names defined in this domain.
+    defs: HashSet<QName<'gc>>,
+
+    /// The parent domain.
+    parent: Option<Domain<'gc>>,
+
+    /// The `ByteArray` accessed by the fast memory opcodes, as set by
+    /// `ApplicationDomain.domainMemory`.
+    domain_memory: Option<Object<'gc>>,
+}
+
+impl<'gc> Domain<'gc> {
+    /// Create a new domain with no parent.
+    ///
+    /// This is intended exclusively for creating the player globals domain,
+    /// which holds all of the builtin classes.
+    pub fn global_domain(mc: MutationContext<'gc, '_>, globals: Object<'gc>) -> Domain<'gc> {
+        Self(GcCell::allocate(
+            mc,
+            DomainData {
+                globals,
+                defs: HashSet::new(),
+                parent: None,
+                domain_memory: None,
+            },
+        ))
+    }
+
+    /// Create a new domain as a child of another domain.
+    ///
+    /// This is used for movies and for `ApplicationDomain`s constructed by
+    /// scripts.
+    pub fn movie_domain(mc: MutationContext<'gc, '_>, parent: Domain<'gc>) -> Domain<'gc> {
+        Self(GcCell::allocate(
+            mc,
+            DomainData {
+                globals: parent.globals(),
+                defs: HashSet::new(),
+                parent: Some(parent),
+                domain_memory: None,
+            },
+        ))
+    }
+
+    /// Get the parent of this domain.
+    pub fn parent_domain(self) -> Option<Domain<'gc>> {
+        self.0.read().parent
+    }
+
+    /// Get the object that this domain's definitions are installed on.
+    pub fn globals(self) -> Object<'gc> {
+        self.0.read().globals
+    }
+
+    /// Get the `ByteArray` used as this domain's memory, if any.
+    pub fn domain_memory(self) -> Option<Object<'gc>> {
+        self.0.read().domain_memory
+    }
+
+    /// Set the `ByteArray` used as this domain's memory.
+    ///
+    /// Activations cache the domain memory, so callers must also call
+    /// `Avm2::invalidate_domain_memory`.
+    pub fn set_domain_memory(self, mc: MutationContext<'gc, '_>, memory: Option<Object<'gc>>) {
+        self.0.write(mc).domain_memory = memory;
+    }
+
+    /// Determine if two domains are the same domain.
+    pub fn ptr_eq(a: Domain<'gc>, b: Domain<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
+    /// Determine if something has been defined in this domain or any of its
+    /// parents.
+    pub fn has_definition(self, name: &QName<'gc>) -> bool {
+        self.get_defining_domain(name).is_some()
+    }
+
+    /// Find the domain that defines a given name.
+    ///
+    /// Parent domains are searched first, since their definitions cannot be
+    /// overridden by children.
+    pub fn get_defining_domain(self, name: &QName<'gc>) -> Option<Domain<'gc>> {
+        let read = self.0.read();
+
+        if let Some(defining_domain) = read.parent.and_then(|p| p.get_defining_domain(name)) {
+            return Some(defining_domain);
+        }
+
+        if read.defs.contains(name) {
+            return Some(self);
+        }
+
+        None
+    }
+
+    /// Record that a name is defined in this domain.
+    pub fn export_definition(self, name: QName<'gc>, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).defs.insert(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::names::Namespace;
+    use crate::avm2::object::ScriptObject;
+    use gc_arena::rootless_arena;
+
+    #[test]
+    fn definitions_resolve_through_parents() {
+        rootless_arena(|mc| {
+            let global = Domain::global_domain(mc, ScriptObject::bare_object(mc));
+            let parent = Domain::movie_domain(mc, global);
+            let child = Domain::movie_domain(mc, parent);
+            let sibling = Domain::movie_domain(mc, global);
+
+            let builtin = QName::new(Namespace::package("flash.display"), "MovieClip");
+            let asset = QName::new(Namespace::package(""), "AssetClass");
+            global.export_definition(builtin.clone(), mc);
+            parent.export_definition(asset.clone(), mc);
+
+            assert!(Domain::ptr_eq(child.parent_domain().unwrap(), parent));
+            assert!(global.parent_domain().is_none());
+
+            assert!(Domain::ptr_eq(
+                child.get_defining_domain(&builtin).unwrap(),
+                global
+            ));
+            assert!(Domain::ptr_eq(
+                child.get_defining_domain(&asset).unwrap(),
+                parent
+            ));
+            assert!(sibling.has_definition(&builtin));
+            assert!(!sibling.has_definition(&asset));
+            assert!(!global.has_definition(&asset));
+        })
+    }
+
+    #[test]
+    fn parent_definitions_take_precedence() {
+        rootless_arena(|mc| {
+            let global = Domain::global_domain(mc, ScriptObject::bare_object(mc));
+            let child = Domain::movie_domain(mc, global);
+            let name = QName::new(Namespace::package(""), "Shared");
+
+            child.export_definition(name.clone(), mc);
+            assert!(Domain::ptr_eq(
+                child.get_defining_domain(&name).unwrap(),
+                child
+            ));
+
+            global.export_definition(name.clone(), mc);
+            assert!(Domain::ptr_eq(
+                child.get_defining_domain(&name).unwrap(),
+                global
+            ));
+        })
+    }
+}
@@ -0,0 +1,1183 @@
+//! E4X (ECMAScript for XML) support for the `XML` and `XMLList` classes
+//!
+//! E4X values are views onto the same XML trees used by AVM1's `XML` class.
+//! An `XML` object holds one node (or one attribute of an element), and an
+//! `XMLList` object holds an ordered list of them.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Multiname, Namespace};
+use crate::avm2::object::{Object, TObject, XMLListObject, XMLObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::xml::{XMLDocument, XMLName, XMLNode};
+use gc_arena::{Collect, MutationContext};
+
+/// The static settings of the `XML` class.
+#[derive(Clone, Copy, Collect, Debug)]
+#[collect(require_static)]
+pub struct XmlSettings {
+    /// Whether `toXMLString` puts nested nodes on their own, indented lines.
+    pub pretty_printing: bool,
+
+    /// How many spaces each level of nesting is indented by.
+    pub pretty_indent: u32,
+}
+
+impl Default for XmlSettings {
+    fn default() -> Self {
+        Self {
+            pretty_printing: true,
+            pretty_indent: 2,
+        }
+    }
+}
+
+/// A single E4X value: a node of an XML tree, or an attribute of an element.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum E4XNode<'gc> {
+    Node(XMLNode<'gc>),
+    Attribute(XMLNode<'gc>, XMLName),
+}
+
+impl<'gc> E4XNode<'gc> {
+    /// The element this value is, if it is one.
+    pub fn element(&self) -> Option<XMLNode<'gc>> {
+        match self {
+            E4XNode::Node(node) if node.is_element() => Some(*node),
+            _ => None,
+        }
+    }
+
+    /// The element this value belongs to, if any.
+    ///
+    /// The document root that a parsed tree hangs from is not an E4X value,
+    /// so top-level nodes have no parent.
+    pub fn parent(&self) -> Option<XMLNode<'gc>> {
+        match self {
+            E4XNode::Node(node) => node
+                .parent()
+                .ok()
+                .flatten()
+                .filter(|parent| !parent.is_document_root()),
+            E4XNode::Attribute(element, _) => Some(*element),
+        }
+    }
+
+    /// Check if two values are the same node or attribute.
+    pub fn ptr_eq(&self, other: &E4XNode<'gc>) -> bool {
+        match (self, other) {
+            (E4XNode::Node(a), E4XNode::Node(b)) => XMLNode::ptr_eq(*a, *b),
+            (E4XNode::Attribute(a, a_name), E4XNode::Attribute(b, b_name)) => {
+                XMLNode::ptr_eq(*a, *b) && a_name == b_name
+            }
+            _ => false,
+        }
+    }
+
+    /// The kind of this value, as reported by `nodeKind()`.
+    pub fn node_kind(&self) -> &'static str {
+        match self {
+            E4XNode::Attribute(_, _) => "attribute",
+            E4XNode::Node(node) if node.is_element() => "element",
+            E4XNode::Node(node) if node.is_comment() => "comment",
+            E4XNode::Node(_) => "text",
+        }
+    }
+
+    /// The local part of this value's name, if it has one.
+    pub fn local_name(&self) -> Option<String> {
+        match self {
+            E4XNode::Node(node) => node.tag_name().map(|n| n.local_name().to_string()),
+            E4XNode::Attribute(_, name) => Some(name.local_name().to_string()),
+        }
+    }
+
+    /// The namespace URI of this value's name.
+    ///
+    /// Prefixes are resolved against the declarations in scope; unprefixed
+    /// elements are in the default namespace, and unprefixed attributes are
+    /// in no namespace.
+    pub fn uri(&self) -> String {
+        match self {
+            E4XNode::Node(node) => match node.tag_name() {
+                Some(name) => node
+                    .lookup_uri_for_namespace(name.prefix().unwrap_or(""))
+                    .unwrap_or_default(),
+                None => String::new(),
+            },
+            E4XNode::Attribute(element, name) => match name.prefix() {
+                Some(prefix) => element.lookup_uri_for_namespace(prefix).unwrap_or_default(),
+                None => String::new(),
+            },
+        }
+    }
+
+    /// Whether this value has simple content: it is not an element, or it is
+    /// an element without element children.
+    pub fn has_simple_content(&self) -> bool {
+        match self.element() {
+            Some(element) => element
+                .children()
+                .map_or(true, |mut children| !children.any(|c| c.is_element())),
+            None => !matches!(self, E4XNode::Node(node) if node.is_comment()),
+        }
+    }
+
+    /// Convert this value to a string, as `toString()` does.
+    ///
+    /// Values with simple content yield their text; elements with complex
+    /// content yield their markup.
+    pub fn string_value(&self, settings: XmlSettings) -> String {
+        match self {
+            E4XNode::Attribute(element, name) => element.attribute_value(name).unwrap_or_default(),
+            E4XNode::Node(node) if node.is_element() => {
+                if self.has_simple_content() {
+                    node.children()
+                        .map(|children| {
+                            children
+                                .filter(|c| c.is_text())
+                                .filter_map(|c| c.node_value())
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    self.to_xml_string(settings)
+                }
+            }
+            E4XNode::Node(node) => node.node_value().unwrap_or_default(),
+        }
+    }
+
+    /// Convert this value to markup, as `toXMLString()` does.
+    pub fn to_xml_string(&self, settings: XmlSettings) -> String {
+        match self {
+            E4XNode::Attribute(element, name) => {
+                escape_attribute_value(&element.attribute_value(name).unwrap_or_default())
+            }
+            E4XNode::Node(node) => {
+                let mut out = String::new();
+                write_node(
+                    *node,
+                    &in_scope_declarations(*node),
+                    &[],
+                    settings,
+                    0,
+                    &mut out,
+                );
+                out
+            }
+        }
+    }
+
+    /// Copy this value, along with everything below it.
+    ///
+    /// The copy has no parent. Copied attributes become text nodes.
+    pub fn deep_copy(&self, mc: MutationContext<'gc, '_>) -> XMLNode<'gc> {
+        match self {
+            E4XNode::Node(node) => {
+                let copy = node.duplicate(mc, true);
+                if let Some(element) = self.element() {
+                    for (name, value) in in_scope_declarations(element) {
+                        copy.set_attribute_value(mc, &name, &value);
+                    }
+                }
+
+                copy
+            }
+            E4XNode::Attribute(element, name) => XMLNode::new_text(
+                mc,
+                &element.attribute_value(name).unwrap_or_default(),
+                XMLDocument::new(mc),
+            ),
+        }
+    }
+
+    /// Remove this value from its parent element.
+    pub fn remove(&self, mc: MutationContext<'gc, '_>) -> Result<(), Error> {
+        match self {
+            E4XNode::Node(node) => {
+                if let Some(mut parent) = self.parent() {
+                    parent.remove_child(mc, *node)?;
+                }
+            }
+            E4XNode::Attribute(element, name) => element.delete_attribute(mc, name),
+        }
+
+        Ok(())
+    }
+}
+
+/// A name that E4X properties (child elements and attributes) are looked up
+/// by.
+#[derive(Clone, Debug)]
+pub struct E4XName {
+    /// Whether this names attributes rather than child nodes.
+    is_attribute: bool,
+
+    /// The namespace URIs that match, or `None` if any namespace does.
+    uris: Option<Vec<String>>,
+
+    /// The local name that matches, or `None` if any name does.
+    local_name: Option<String>,
+}
+
+impl E4XName {
+    /// Convert a multiname into an E4X name.
+    ///
+    /// The public namespace stands for the default XML namespace, if one was
+    /// set with `default xml namespace`, unless this names attributes.
+    /// Namespaces that are not public or user-defined, such as `private`
+    /// ones, never name XML.
+    pub fn from_multiname(multiname: &Multiname<'_>, default_namespace: Option<&str>) -> Self {
+        let default_namespace = default_namespace.filter(|_| !multiname.is_attribute());
+        let mut uris = Some(Vec::new());
+        for ns in multiname.namespace_set() {
+            match ns {
+                Namespace::Any => uris = None,
+                Namespace::Package(uri) if uri.is_empty() => {
+                    if let Some(uris) = &mut uris {
+                        uris.push(default_namespace.unwrap_or("").to_string())
+                    }
+                }
+                Namespace::Namespace(uri) | Namespace::Package(uri) => {
+                    if let Some(uris) = &mut uris {
+                        uris.push(uri.to_string())
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            is_attribute: multiname.is_attribute(),
+            uris,
+            local_name: multiname
+                .local_name()
+                .filter(|name| &**name != "*")
+                .map(|name| name.to_string()),
+        }
+    }
+
+    /// Convert a name passed to a method such as `child()` into an E4X name.
+    ///
+    /// A leading `@` names attributes, and `*` is any name.
+    pub fn from_string(name: &str, default_namespace: Option<&str>) -> Self {
+        let (is_attribute, name) = match name.strip_prefix('@') {
+            Some(name) => (true, name),
+            None => (false, name),
+        };
+        let uri = if is_attribute {
+            ""
+        } else {
+            default_namespace.unwrap_or("")
+        };
+
+        if name == "*" {
+            Self::any(is_attribute)
+        } else {
+            Self {
+                is_attribute,
+                uris: Some(vec![uri.to_string()]),
+                local_name: Some(name.to_string()),
+            }
+        }
+    }
+
+    /// A name matching every child node, or every attribute.
+    pub fn any(is_attribute: bool) -> Self {
+        Self {
+            is_attribute,
+            uris: None,
+            local_name: None,
+        }
+    }
+
+    /// Whether this names attributes rather than child nodes.
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+
+    /// The list index this name refers to, if it is one.
+    pub fn index(&self) -> Option<usize> {
+        if self.is_attribute {
+            return None;
+        }
+
+        self.local_name.as_ref().and_then(|name| name.parse().ok())
+    }
+
+    /// Whether this name matches a given node or attribute.
+    ///
+    /// Text and comment nodes are only matched by the any name.
+    pub fn matches(&self, value: &E4XNode<'_>) -> bool {
+        if self.is_attribute != matches!(value, E4XNode::Attribute(_, _)) {
+            return false;
+        }
+
+        if self.uris.is_none() && self.local_name.is_none() {
+            return true;
+        }
+
+        let local_name = match value.local_name() {
+            Some(local_name) => local_name,
+            None => return false,
+        };
+
+        if let Some(name) = &self.local_name {
+            if *name != local_name {
+                return false;
+            }
+        }
+
+        match &self.uris {
+            Some(uris) => uris.contains(&value.uri()),
+            None => true,
+        }
+    }
+
+    /// Build the markup name of a new element with this name, along with the
+    /// namespace declaration it needs, if any.
+    fn new_element_name(&self, parent: XMLNode<'_>) -> Result<(String, Option<String>), Error> {
+        let local_name = self
+            .local_name
+            .as_ref()
+            .ok_or("TypeError: Error #1089: Cannot create an element from the any name")?;
+        let uri = match &self.uris {
+            Some(uris) if !uris.iter().any(|uri| uri.is_empty()) => uris.first(),
+            _ => None,
+        };
+
+        Ok(match uri {
+            Some(uri) => match parent.lookup_namespace_for_uri(uri) {
+                Some(prefix) => (format!("{}:{}", prefix, local_name), None),
+                None => (local_name.clone(), Some(uri.clone())),
+            },
+            None => (local_name.clone(), None),
+        })
+    }
+}
+
+/// Parse a string of markup into a list of top-level nodes.
+///
+/// Whitespace-only text, comments, processing instructions and doctypes are
+/// discarded, as E4X does by default.
+pub fn parse<'gc>(mc: MutationContext<'gc, '_>, data: &str) -> Result<Vec<XMLNode<'gc>>, Error> {
+    let document = XMLDocument::new(mc);
+    let mut root = document.as_node();
+
+    if root.replace_with_str(mc, data, true, true).is_err() {
+        return Err("TypeError: Error #1090: XML parser failure: element is malformed.".into());
+    }
+
+    let mut pending = vec![root];
+    while let Some(mut node) = pending.pop() {
+        let children: Vec<_> = node.children().map(|c| c.collect()).unwrap_or_default();
+        for child in children {
+            if child.is_comment() || child.is_doctype() {
+                node.remove_child(mc, child)?;
+            } else if child.is_element() {
+                pending.push(child);
+            }
+        }
+    }
+
+    Ok(root.children().map(|c| c.collect()).unwrap_or_default())
+}
+
+/// The namespace declarations made above a node that it is serialized or
+/// copied with, so that its prefixes still resolve on their own.
+fn in_scope_declarations(node: XMLNode<'_>) -> Vec<(XMLName, String)> {
+    let mut declarations: Vec<(XMLName, String)> = Vec::new();
+    let mut ancestor = E4XNode::Node(node).parent();
+
+    while let Some(element) = ancestor {
+        if let Some(attributes) = attributes_of(element) {
+            for (name, value) in attributes {
+                let is_shadowed = declarations.iter().any(|(other, _)| *other == name)
+                    || node.attribute_value(&name).is_some();
+                if is_declaration(&name) && !is_shadowed {
+                    declarations.push((name, value));
+                }
+            }
+        }
+
+        ancestor = E4XNode::Node(element).parent();
+    }
+
+    declarations
+}
+
+/// Whether an attribute name is a namespace declaration.
+fn is_declaration(name: &XMLName) -> bool {
+    name.prefix() == Some("xmlns") || node_name_is(name, "xmlns")
+}
+
+/// Whether a name is exactly the given unprefixed name.
+fn node_name_is(name: &XMLName, expected: &str) -> bool {
+    name.prefix().is_none() && name.local_name() == expected
+}
+
+/// The attributes of an element, in order.
+fn attributes_of(element: XMLNode<'_>) -> Option<Vec<(XMLName, String)>> {
+    if !element.is_element() {
+        return None;
+    }
+
+    Some(
+        element
+            .attribute_keys()
+            .into_iter()
+            .map(|key| {
+                let name = XMLName::from_str(&key);
+                let value = element.attribute_value(&name).unwrap_or_default();
+                (name, value)
+            })
+            .collect(),
+    )
+}
+
+/// The attributes of an element that are E4X values: everything except
+/// namespace declarations.
+fn value_attributes(element: XMLNode<'_>) -> Vec<XMLName> {
+    attributes_of(element)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, _)| name)
+        .filter(|name| !is_declaration(name))
+        .collect()
+}
+
+/// Escape text for use as element content.
+pub fn escape_element_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Escape text for use as an attribute value.
+pub fn escape_attribute_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#xA;"),
+            '\r' => out.push_str("&#xD;"),
+            '\t' => out.push_str("&#x9;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Write the markup for a node.
+///
+/// `declarations` are extra namespace declarations to add to the node's start
+/// tag, and `in_scope` are those already written by its ancestors, which are
+/// not repeated. With pretty printing, children go on their own lines, indented one
+/// level deeper than `level`, unless the only child is text.
+fn write_node(
+    node: XMLNode<'_>,
+    declarations: &[(XMLName, String)],
+    in_scope: &[(XMLName, String)],
+    settings: XmlSettings,
+    level: usize,
+    out: &mut String,
+) {
+    if node.is_comment() {
+        out.push_str("<!--");
+        out.push_str(&node.node_value().unwrap_or_default());
+        out.push_str("-->");
+        return;
+    }
+
+    let name = match node.tag_name() {
+        Some(name) => name,
+        None => {
+            let text = node.node_value().unwrap_or_default();
+            let text = if settings.pretty_printing {
+                text.trim()
+            } else {
+                &text
+            };
+            out.push_str(&escape_element_value(text));
+            return;
+        }
+    };
+
+    let attributes: Vec<_> = attributes_of(node)
+        .unwrap_or_default()
+        .into_iter()
+        .chain(declarations.iter().cloned())
+        .filter(|attribute| !in_scope.contains(attribute))
+        .collect();
+    let mut child_scope = in_scope.to_vec();
+    child_scope.extend(
+        attributes
+            .iter()
+            .filter(|(name, _)| is_declaration(name))
+            .cloned(),
+    );
+
+    out.push('<');
+    out.push_str(&name.node_name());
+    for (name, value) in attributes.iter() {
+        out.push(' ');
+        out.push_str(&name.node_name());
+        out.push_str("=\"");
+        out.push_str(&escape_attribute_value(value));
+        out.push('"');
+    }
+
+    let children: Vec<_> = node.children().map(|c| c.collect()).unwrap_or_default();
+    let children: Vec<_> = children
+        .into_iter()
+        .filter(|child| {
+            !settings.pretty_printing
+                || !child.is_text()
+                || !child.node_value().unwrap_or_default().trim().is_empty()
+        })
+        .collect();
+
+    if children.is_empty() {
+        out.push_str("/>");
+        return;
+    }
+
+    out.push('>');
+
+    let indent_children =
+        settings.pretty_printing && !(children.len() == 1 && children[0].is_text());
+    let indent = settings.pretty_indent as usize;
+    for child in children {
+        if indent_children {
+            out.push('\n');
+            out.push_str(&" ".repeat((level + 1) * indent));
+        }
+
+        write_node(child, &[], &child_scope, settings, level + 1, out);
+    }
+
+    if indent_children {
+        out.push('\n');
+        out.push_str(&" ".repeat(level * indent));
+    }
+
+    out.push_str("</");
+    out.push_str(&name.node_name());
+    out.push('>');
+}
+
+/// The child nodes or attributes of each value that match a name, in order.
+pub fn children<'gc>(values: &[E4XNode<'gc>], name: &E4XName) -> Vec<E4XNode<'gc>> {
+    let mut out = Vec::new();
+    for element in values.iter().filter_map(|v| v.element()) {
+        if name.is_attribute() {
+            out.extend(
+                value_attributes(element)
+                    .into_iter()
+                    .map(|attribute| E4XNode::Attribute(element, attribute))
+                    .filter(|attribute| name.matches(attribute)),
+            );
+        } else if let Some(children) = element.children() {
+            out.extend(
+                children
+                    .map(E4XNode::Node)
+                    .filter(|child| name.matches(child)),
+            );
+        }
+    }
+
+    out
+}
+
+/// The descendants of each value that match a name, in document order.
+///
+/// Attribute names match the attributes of the values themselves as well as
+/// those of their descendants.
+pub fn descendants<'gc>(values: &[E4XNode<'gc>], name: &E4XName) -> Vec<E4XNode<'gc>> {
+    let mut out = Vec::new();
+    for value in values {
+        let element = match value.element() {
+            Some(element) => element,
+            None => continue,
+        };
+
+        if name.is_attribute() {
+            out.extend(children(&[value.clone()], name));
+        }
+
+        // Walk the tree in document order, using a stack of the children
+        // still to be visited, nearest last.
+        let mut pending: Vec<XMLNode<'gc>> = element
+            .children()
+            .map(|c| c.rev().collect())
+            .unwrap_or_default();
+        while let Some(node) = pending.pop() {
+            let node_value = E4XNode::Node(node);
+            if name.is_attribute() {
+                out.extend(children(&[node_value], name));
+            } else if name.matches(&node_value) {
+                out.push(node_value);
+            }
+
+            if let Some(children) = node.children() {
+                pending.extend(children.rev());
+            }
+        }
+    }
+
+    out
+}
+
+/// Remove every child node or attribute of each value that matches a name.
+pub fn delete_children<'gc>(
+    mc: MutationContext<'gc, '_>,
+    values: &[E4XNode<'gc>],
+    name: &E4XName,
+) -> Result<(), Error> {
+    for child in children(values, name) {
+        child.remove(mc)?;
+    }
+
+    Ok(())
+}
+
+/// What a value assigned to an E4X property is replaced with.
+enum Replacement<'gc> {
+    /// Primitives become the text content of the element they are assigned
+    /// to.
+    Text(String),
+
+    /// XML values are copied into place.
+    Nodes(Vec<XMLNode<'gc>>),
+}
+
+impl<'gc> Replacement<'gc> {
+    fn from_value(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: Value<'gc>,
+    ) -> Result<Self, Error> {
+        Ok(match values_of(&value) {
+            Some(values) => Replacement::Nodes(
+                values
+                    .iter()
+                    .map(|v| v.deep_copy(activation.context.gc_context))
+                    .collect(),
+            ),
+            None => Replacement::Text(value.coerce_to_string(activation)?.to_string()),
+        })
+    }
+}
+
+/// Replace the content of an element with text.
+fn set_text_content<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut element: XMLNode<'gc>,
+    text: &str,
+) -> Result<(), Error> {
+    let children: Vec<_> = element.children().map(|c| c.collect()).unwrap_or_default();
+    for child in children {
+        element.remove_child(mc, child)?;
+    }
+
+    if !text.is_empty() {
+        let text = XMLNode::new_text(mc, text, element.document());
+        element.append_child(mc, text)?;
+    }
+
+    Ok(())
+}
+
+/// Assign to the child elements or attributes of a value that match a name.
+///
+/// Assigning to an attribute sets its value. Assigning to elements replaces
+/// the first match, removes the rest, and appends a new element if there was
+/// no match.
+pub fn set_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: &E4XNode<'gc>,
+    name: &E4XName,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    let element = match target.element() {
+        Some(element) => element,
+        None => return Ok(()),
+    };
+
+    if name.index().is_some() {
+        return Err("TypeError: Error #1087: Assignment to indexed XML is not allowed.".into());
+    }
+
+    let mc = activation.context.gc_context;
+    let settings = activation.avm2().xml_settings();
+
+    if name.is_attribute() {
+        let value = match values_of(&value) {
+            Some(values) => values
+                .iter()
+                .map(|v| v.string_value(settings))
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => value.coerce_to_string(activation)?.to_string(),
+        };
+        let matches = children(&[target.clone()], name);
+        let attribute_name = match matches.first() {
+            Some(E4XNode::Attribute(_, attribute_name)) => attribute_name.clone(),
+            _ => XMLName::from_str(&name.new_element_name(element)?.0),
+        };
+
+        for other in matches.iter().skip(1) {
+            other.remove(mc)?;
+        }
+
+        element.set_attribute_value(mc, &attribute_name, &value);
+
+        return Ok(());
+    }
+
+    let replacement = Replacement::from_value(activation, value)?;
+    let matches = children(&[target.clone()], name);
+    for other in matches.iter().skip(1) {
+        other.remove(mc)?;
+    }
+
+    let existing = matches.first().and_then(|m| match m {
+        E4XNode::Node(node) => Some(*node),
+        _ => None,
+    });
+
+    match (replacement, existing) {
+        (Replacement::Text(text), Some(existing)) if existing.is_element() => {
+            set_text_content(mc, existing, &text)?;
+        }
+        (Replacement::Text(text), existing) => {
+            let (element_name, declaration) = name.new_element_name(element)?;
+            let new_element = XMLNode::new_element(mc, &element_name, element.document());
+            if let Some(uri) = declaration {
+                new_element.set_attribute_value(mc, &XMLName::from_str("xmlns"), &uri);
+            }
+
+            set_text_content(mc, new_element, &text)?;
+            replace_or_append(mc, element, existing, vec![new_element])?;
+        }
+        (Replacement::Nodes(nodes), existing) => {
+            replace_or_append(mc, element, existing, nodes)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace a child of an element with some nodes, or append them if there is
+/// no child to replace.
+fn replace_or_append<'gc>(
+    mc: MutationContext<'gc, '_>,
+    mut element: XMLNode<'gc>,
+    existing: Option<XMLNode<'gc>>,
+    nodes: Vec<XMLNode<'gc>>,
+) -> Result<(), Error> {
+    let position = match existing.and_then(|e| element.child_position(e)) {
+        Some(position) => {
+            element.remove_child(mc, existing.unwrap())?;
+            position
+        }
+        None => element.children_len(),
+    };
+
+    for (offset, node) in nodes.into_iter().enumerate() {
+        element.insert_child(mc, position + offset, node)?;
+    }
+
+    Ok(())
+}
+
+/// Replace one value of an `XMLList`, in its parent as well as the list.
+///
+/// Primitives become the text content of elements and the value of
+/// attributes; XML values take the replaced node's place in its parent.
+pub fn replace_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: &E4XNode<'gc>,
+    value: Value<'gc>,
+) -> Result<Vec<E4XNode<'gc>>, Error> {
+    let settings = activation.avm2().xml_settings();
+    let replacement = Replacement::from_value(activation, value)?;
+    let mc = activation.context.gc_context;
+
+    match (target, replacement) {
+        (E4XNode::Attribute(element, name), replacement) => {
+            let text = match replacement {
+                Replacement::Text(text) => text,
+                Replacement::Nodes(nodes) => nodes
+                    .into_iter()
+                    .map(|node| E4XNode::Node(node).string_value(settings))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            };
+            element.set_attribute_value(mc, name, &text);
+
+            Ok(vec![target.clone()])
+        }
+        (E4XNode::Node(node), Replacement::Text(text)) if node.is_element() => {
+            set_text_content(mc, *node, &text)?;
+
+            Ok(vec![target.clone()])
+        }
+        (E4XNode::Node(node), replacement) => {
+            let nodes = match replacement {
+                Replacement::Text(text) => vec![XMLNode::new_text(mc, &text, node.document())],
+                Replacement::Nodes(nodes) => nodes,
+            };
+
+            if let Some(parent) = target.parent() {
+                replace_or_append(mc, parent, Some(*node), nodes.clone())?;
+            }
+
+            Ok(nodes.into_iter().map(E4XNode::Node).collect())
+        }
+    }
+}
+
+/// Concatenate XML values into an `XMLList`, as `+` does.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    left: &Value<'gc>,
+    right: &Value<'gc>,
+) -> Result<Option<Value<'gc>>, Error> {
+    match (values_of(left), values_of(right)) {
+        (Some(mut left), Some(right)) => {
+            left.extend(right);
+            Ok(Some(XMLListObject::from_values(activation, left)?.into()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The values held by an `XML` or `XMLList` object.
+///
+/// An `XML` object is treated as a list of itself. Every other value yields
+/// `None`.
+pub fn values_of<'gc>(value: &Value<'gc>) -> Option<Vec<E4XNode<'gc>>> {
+    match value {
+        Value::Object(object) => object_values(*object),
+        _ => None,
+    }
+}
+
+/// The values held by an `XML` or `XMLList` object.
+pub fn object_values<'gc>(object: Object<'gc>) -> Option<Vec<E4XNode<'gc>>> {
+    if let Some(value) = object.as_xml() {
+        return Some(vec![value.clone()]);
+    }
+
+    object.as_xml_list().map(|list| list.clone())
+}
+
+/// The default XML namespace of an activation, if one was set.
+fn default_namespace(activation: &Activation<'_, '_, '_>) -> Option<String> {
+    activation.default_xml_namespace().map(|ns| ns.to_string())
+}
+
+/// Look up a multiname on an `XML` or `XMLList` object.
+///
+/// This yields `None` for any other kind of object. Numeric names index into
+/// lists (an `XML` object is a list of one); other names yield an `XMLList`
+/// of matching children or attributes.
+pub fn get_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<Value<'gc>>, Error> {
+    let values = match object_values(object) {
+        Some(values) => values,
+        None => return Ok(None),
+    };
+    let name = E4XName::from_multiname(multiname, default_namespace(activation).as_deref());
+
+    if let Some(index) = name.index() {
+        return Ok(Some(match values.get(index) {
+            Some(value) => XMLObject::from_value(activation, value.clone())?.into(),
+            None => Value::Undefined,
+        }));
+    }
+
+    let children = children(&values, &name);
+    Ok(Some(
+        XMLListObject::from_values(activation, children)?.into(),
+    ))
+}
+
+/// Whether an `XML` or `XMLList` object has children or attributes matching
+/// a multiname.
+///
+/// This is what makes child names visible, unqualified, inside the scope of
+/// a filtering predicate.
+pub fn has_property<'gc>(
+    activation: &Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> bool {
+    let values = match object_values(object) {
+        Some(values) => values,
+        None => return false,
+    };
+    let name = E4XName::from_multiname(multiname, default_namespace(activation).as_deref());
+
+    match name.index() {
+        Some(index) => index < values.len(),
+        None => !children(&values, &name).is_empty(),
+    }
+}
+
+/// Assign a multiname on an `XML` or `XMLList` object.
+///
+/// This yields `false`, and does nothing, for any other kind of object.
+/// Assigning past the end of a list appends to it.
+pub fn set_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+    value: Value<'gc>,
+) -> Result<bool, Error> {
+    let name = E4XName::from_multiname(multiname, default_namespace(activation).as_deref());
+
+    if let Some(target) = object.as_xml().map(|v| v.clone()) {
+        set_child(activation, &target, &name, value)?;
+
+        return Ok(true);
+    }
+
+    let values = match object.as_xml_list().map(|list| list.clone()) {
+        Some(values) => values,
+        None => return Ok(false),
+    };
+
+    match name.index() {
+        Some(index) if index < values.len() => {
+            let replaced = replace_value(activation, &values[index], value)?;
+            if let Some(mut list) = object.as_xml_list_mut(activation.context.gc_context) {
+                list.splice(index..=index, replaced);
+            }
+        }
+        Some(_) => {
+            let appended = match values_of(&value) {
+                Some(appended) => appended,
+                None => {
+                    let text = value.coerce_to_string(activation)?;
+                    let mc = activation.context.gc_context;
+                    vec![E4XNode::Node(XMLNode::new_text(
+                        mc,
+                        &text,
+                        XMLDocument::new(mc),
+                    ))]
+                }
+            };
+            if let Some(mut list) = object.as_xml_list_mut(activation.context.gc_context) {
+                list.extend(appended);
+            }
+        }
+        None => match values.as_slice() {
+            [] => {}
+            [target] => set_child(activation, target, &name, value)?,
+            _ => {
+                return Err(
+                    "TypeError: Error #1089: Assignment to lists with more than one item is not supported."
+                        .into(),
+                )
+            }
+        },
+    }
+
+    Ok(true)
+}
+
+/// Delete a multiname from an `XML` or `XMLList` object.
+///
+/// This yields `None` for any other kind of object. Deleting an index of a
+/// list removes that value from its parent and from the list, so later
+/// values move down to fill the gap.
+pub fn delete_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Option<bool>, Error> {
+    let values = match object_values(object) {
+        Some(values) => values,
+        None => return Ok(None),
+    };
+    let name = E4XName::from_multiname(multiname, default_namespace(activation).as_deref());
+    let mc = activation.context.gc_context;
+
+    match name.index() {
+        Some(index) if object.as_xml_list().is_some() => {
+            if let Some(value) = values.get(index) {
+                value.remove(mc)?;
+                if let Some(mut list) = object.as_xml_list_mut(mc) {
+                    list.remove(index);
+                }
+            }
+        }
+        Some(_) => return Ok(Some(false)),
+        None => delete_children(mc, &values, &name)?,
+    }
+
+    Ok(Some(true))
+}
+
+/// Collect the descendants of an `XML` or `XMLList` object matching a
+/// multiname into an `XMLList`, as the `..` operator does.
+pub fn get_descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    multiname: &Multiname<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let values = object_values(object)
+        .ok_or("TypeError: Error #1016: Descendants operator (..) not supported on type")?;
+    let name = E4XName::from_multiname(multiname, default_namespace(activation).as_deref());
+    let descendants = descendants(&values, &name);
+
+    Ok(XMLListObject::from_values(activation, descendants)?.into())
+}
+
+/// Convert a list of values to a string, as `toString()` does.
+///
+/// Lists whose values all have simple content yield their text run together;
+/// other lists yield their markup.
+pub fn list_string_value(values: &[E4XNode<'_>], settings: XmlSettings) -> String {
+    if values.iter().all(|v| v.has_simple_content()) {
+        values.iter().map(|v| v.string_value(settings)).collect()
+    } else {
+        list_xml_string(values, settings)
+    }
+}
+
+/// Convert a list of values to markup, as `toXMLString()` does.
+pub fn list_xml_string(values: &[E4XNode<'_>], settings: XmlSettings) -> String {
+    let strings: Vec<_> = values.iter().map(|v| v.to_xml_string(settings)).collect();
+
+    strings.join(if settings.pretty_printing { "\n" } else { "" })
+}
+
+/// Convert a value to nodes that can be inserted into a tree.
+///
+/// XML values are used as they are, unless they already belong to a tree, in
+/// which case they are copied. Anything else is parsed as markup.
+pub fn to_nodes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+) -> Result<Vec<XMLNode<'gc>>, Error> {
+    let mc = activation.context.gc_context;
+
+    match values_of(value) {
+        Some(values) => Ok(values
+            .iter()
+            .map(|v| match v {
+                E4XNode::Node(node) if v.parent().is_none() => *node,
+                v => v.deep_copy(mc),
+            })
+            .collect()),
+        None => {
+            let markup = value.coerce_to_string(activation)?;
+            parse(activation.context.gc_context, &markup)
+        }
+    }
+}
+
+/// Convert a value to a string with `toXMLString` if it is XML, as the
+/// escaping opcodes of XML literals do.
+pub fn xml_string_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+) -> Option<AvmString<'gc>> {
+    let settings = activation.avm2().xml_settings();
+    let values = values_of(value)?;
+
+    Some(AvmString::new(
+        activation.context.gc_context,
+        list_xml_string(&values, settings),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gc_arena::rootless_arena;
+
+    fn xml_string(node: XMLNode<'_>, settings: XmlSettings) -> String {
+        E4XNode::Node(node).to_xml_string(settings)
+    }
+
+    #[test]
+    fn round_trip_preserves_declarations_and_attribute_order() {
+        rootless_arena(|mc| {
+            let markup = r#"<a xmlns:p="urn:p" z="1" y="2" p:x="3"><p:b/></a>"#;
+            let nodes = parse(mc, markup).unwrap();
+            let settings = XmlSettings {
+                pretty_printing: false,
+                pretty_indent: 2,
+            };
+
+            assert_eq!(nodes.len(), 1);
+            assert_eq!(xml_string(nodes[0], settings), markup);
+        })
+    }
+
+    #[test]
+    fn pretty_printing() {
+        rootless_arena(|mc| {
+            let nodes = parse(mc, "<a> <b>text</b><c><d/></c></a>").unwrap();
+            let settings = XmlSettings {
+                pretty_printing: true,
+                pretty_indent: 3,
+            };
+
+            assert_eq!(
+                xml_string(nodes[0], settings),
+                "<a>\n   <b>text</b>\n   <c>\n      <d/>\n   </c>\n</a>"
+            );
+        })
+    }
+
+    #[test]
+    fn descendants_in_document_order() {
+        rootless_arena(|mc| {
+            let nodes = parse(mc, "<a><b n='1'><b n='2'/></b><c><b n='3'/></c></a>").unwrap();
+            let found = descendants(&[E4XNode::Node(nodes[0])], &E4XName::from_string("b", None));
+            let numbers: Vec<_> = found
+                .iter()
+                .filter_map(|b| b.element())
+                .filter_map(|b| b.attribute_value(&XMLName::from_str("n")))
+                .collect();
+
+            assert_eq!(numbers, vec!["1", "2", "3"]);
+        })
+    }
+
+    #[test]
+    fn copies_keep_their_prefixes_resolvable() {
+        rootless_arena(|mc| {
+            let nodes = parse(mc, r#"<a xmlns:p="urn:p"><p:b/></a>"#).unwrap();
+            let child = children(&[E4XNode::Node(nodes[0])], &E4XName::any(false));
+            let copy = child[0].deep_copy(mc);
+
+            assert_eq!(E4XNode::Node(copy).uri(), "urn:p");
+            assert_eq!(
+                xml_string(copy, XmlSettings::default()),
+                r#"<p:b xmlns:p="urn:p"/>"#
+            );
+        })
+    }
+}
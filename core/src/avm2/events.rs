@@ -0,0 +1,598 @@
+//! Core event structure
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::Collect;
+use std::collections::{BTreeMap, HashMap};
+
+/// Which phase of event dispatch is currently occurring.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum EventPhase {
+    /// The event has yet to be fired on any target and is not currently
+    /// being dispatched.
+    None,
+
+    /// The event is being dispatched to the ancestors of the target, from the
+    /// root of the display list downwards.
+    Capturing,
+
+    /// The event is being dispatched to the target itself.
+    AtTarget,
+
+    /// The event is being dispatched to the ancestors of the target, from the
+    /// target's parent upwards.
+    Bubbling,
+}
+
+impl From<EventPhase> for u32 {
+    fn from(phase: EventPhase) -> u32 {
+        match phase {
+            EventPhase::None => 0,
+            EventPhase::Capturing => 1,
+            EventPhase::AtTarget => 2,
+            EventPhase::Bubbling => 3,
+        }
+    }
+}
+
+/// How far an event is allowed to propagate.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum PropagationMode {
+    /// Propagate events normally.
+    Propagate,
+
+    /// Stop propagating events once the current target has run all of its
+    /// handlers.
+    Stop,
+
+    /// Stop propagating events immediately, without running any further
+    /// handlers.
+    StopImmediate,
+}
+
+/// Represents data fields of an event that can be fired on an object that
+/// implements `IEventDispatcher`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct Event<'gc> {
+    /// Whether or not the event "bubbles" - fires on its' ancestors after it
+    /// fires on the target.
+    bubbles: bool,
+
+    /// Whether or not the event's default response can be cancelled.
+    cancelable: bool,
+
+    /// Whether or not the event's default response has been cancelled.
+    cancelled: bool,
+
+    /// Whether or not event propagation has stopped.
+    propagation: PropagationMode,
+
+    /// The object currently having its event handlers invoked.
+    current_target: Option<Object<'gc>>,
+
+    /// The current event phase.
+    event_phase: EventPhase,
+
+    /// The object this event was dispatched on.
+    target: Option<Object<'gc>>,
+
+    /// The name of the event being triggered.
+    event_type: AvmString<'gc>,
+}
+
+impl<'gc> Event<'gc> {
+    /// Construct a new event of a given type.
+    pub fn new<S>(event_type: S) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        Event {
+            bubbles: false,
+            cancelable: false,
+            cancelled: false,
+            propagation: PropagationMode::Propagate,
+            current_target: None,
+            event_phase: EventPhase::None,
+            target: None,
+            event_type: event_type.into(),
+        }
+    }
+
+    pub fn event_type(&self) -> AvmString<'gc> {
+        self.event_type
+    }
+
+    pub fn set_event_type<S>(&mut self, event_type: S)
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        self.event_type = event_type.into();
+    }
+
+    pub fn is_bubbling(&self) -> bool {
+        self.bubbles
+    }
+
+    pub fn set_bubbles(&mut self, bubbling: bool) {
+        self.bubbles = bubbling;
+    }
+
+    pub fn is_cancelable(&self) -> bool {
+        self.cancelable
+    }
+
+    pub fn set_cancelable(&mut self, cancelable: bool) {
+        self.cancelable = cancelable;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// Cancel the event's default response, if it is cancelable.
+    pub fn cancel(&mut self) {
+        if self.cancelable {
+            self.cancelled = true;
+        }
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation != PropagationMode::Propagate
+    }
+
+    pub fn stop_propagation(&mut self) {
+        if self.propagation != PropagationMode::StopImmediate {
+            self.propagation = PropagationMode::Stop;
+        }
+    }
+
+    pub fn is_propagation_stopped_immediately(&self) -> bool {
+        self.propagation == PropagationMode::StopImmediate
+    }
+
+    pub fn stop_immediate_propagation(&mut self) {
+        self.propagation = PropagationMode::StopImmediate;
+    }
+
+    pub fn phase(&self) -> EventPhase {
+        self.event_phase
+    }
+
+    pub fn set_phase(&mut self, phase: EventPhase) {
+        self.event_phase = phase;
+    }
+
+    pub fn target(&self) -> Option<Object<'gc>> {
+        self.target
+    }
+
+    pub fn set_target(&mut self, target: Object<'gc>) {
+        self.target = Some(target)
+    }
+
+    pub fn current_target(&self) -> Option<Object<'gc>> {
+        self.current_target
+    }
+
+    pub fn set_current_target(&mut self, current_target: Object<'gc>) {
+        self.current_target = Some(current_target)
+    }
+}
+
+/// A set of handlers organized by event type, priority, and order added.
+///
+/// Priorities are stored in ascending order, so handlers must be iterated
+/// from the highest priority level downwards.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(no_drop)]
+pub struct DispatchList<'gc>(HashMap<AvmString<'gc>, BTreeMap<i32, Vec<EventHandler<'gc>>>>);
+
+impl<'gc> DispatchList<'gc> {
+    /// Construct a new dispatch list.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get all of the event handlers for a given event type, if such a type
+    /// exists.
+    fn get_event(
+        &self,
+        event: impl Into<AvmString<'gc>>,
+    ) -> Option<&BTreeMap<i32, Vec<EventHandler<'gc>>>> {
+        self.0.get(&event.into())
+    }
+
+    /// Get all of the event handlers for a given event type, for mutation.
+    ///
+    /// If the event type does not exist, it will be added to the dispatch
+    /// list.
+    fn get_event_mut(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+    ) -> &mut BTreeMap<i32, Vec<EventHandler<'gc>>> {
+        self.0.entry(event.into()).or_insert_with(BTreeMap::new)
+    }
+
+    /// Get a single priority level of event handlers for a given event type,
+    /// for mutation.
+    fn get_event_priority_mut(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+        priority: i32,
+    ) -> &mut Vec<EventHandler<'gc>> {
+        self.get_event_mut(event)
+            .entry(priority)
+            .or_insert_with(Vec::new)
+    }
+
+    /// Add an event handler to this dispatch list.
+    ///
+    /// This is equivalent to `addEventListener`, and follows the same rules:
+    /// adding a handler that is already present for the same event and phase
+    /// does nothing, handlers are invoked in descending order of priority,
+    /// and handlers of equal priority are invoked in the order they were
+    /// added.
+    pub fn add_event_listener(
+        &mut self,
+        event: impl Into<AvmString<'gc>> + Clone,
+        priority: i32,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let new_handler = EventHandler::new(handler, use_capture);
+
+        if let Some(event_sheaf) = self.get_event(event.clone()) {
+            for level in event_sheaf.values() {
+                if level.contains(&new_handler) {
+                    return;
+                }
+            }
+        }
+
+        self.get_event_priority_mut(event, priority)
+            .push(new_handler);
+    }
+
+    /// Remove an event handler from this dispatch list.
+    ///
+    /// Both the handler and the phase it was registered for must match.
+    pub fn remove_event_listener(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+        handler: Object<'gc>,
+        use_capture: bool,
+    ) {
+        let old_handler = EventHandler::new(handler, use_capture);
+
+        for (_, level) in self.get_event_mut(event).iter_mut() {
+            if let Some(position) = level.iter().position(|h| h == &old_handler) {
+                level.remove(position);
+                return;
+            }
+        }
+    }
+
+    /// Determine if there are any event handlers in this dispatch list.
+    pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
+        if let Some(event_sheaf) = self.get_event(event) {
+            for level in event_sheaf.values() {
+                if !level.is_empty() {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Yield the event handlers on this dispatch list for a given event and
+    /// phase, in the order they should be invoked.
+    ///
+    /// Capture handlers are only yielded when `use_capture` is true; all
+    /// other handlers are yielded when it is false.
+    pub fn iter_event_handlers<'a>(
+        &'a self,
+        event: impl Into<AvmString<'gc>>,
+        use_capture: bool,
+    ) -> impl 'a + Iterator<Item = Object<'gc>> {
+        self.get_event(event)
+            .into_iter()
+            .flat_map(|event_sheaf| event_sheaf.values().rev())
+            .flatten()
+            .filter(move |eh| eh.use_capture == use_capture)
+            .map(|eh| eh.handler)
+    }
+}
+
+/// A single instance of an event handler.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+struct EventHandler<'gc> {
+    /// The event handler to call.
+    handler: Object<'gc>,
+
+    /// Indicates if this handler should only be called for capturing events
+    /// (when `true`), or if it should only be called for bubbling and
+    /// at-target events (when `false`).
+    use_capture: bool,
+}
+
+impl<'gc> EventHandler<'gc> {
+    fn new(handler: Object<'gc>, use_capture: bool) -> Self {
+        Self {
+            handler,
+            use_capture,
+        }
+    }
+}
+
+impl<'gc> PartialEq for EventHandler<'gc> {
+    fn eq(&self, rhs: &Self) -> bool {
+        self.use_capture == rhs.use_capture && Object::ptr_eq(self.handler, rhs.handler)
+    }
+}
+
+impl<'gc> Eq for EventHandler<'gc> {}
+
+/// Retrieve the display list parent of an object, if it is a display object.
+///
+/// Ancestors are located through the object's public `parent` property, so
+/// that the propagation path matches what the movie itself can observe.
+fn display_parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Option<Object<'gc>>, Error> {
+    let mut globals = activation.avm2().globals();
+    let display_object_name = QName::new(Namespace::package("flash.display"), "DisplayObject");
+    let display_object_constr =
+        match globals.get_property(globals, &display_object_name, activation)? {
+            Value::Object(constr) => constr,
+            _ => return Ok(None),
+        };
+
+    if !object.is_instance_of(activation, display_object_constr, false)? {
+        return Ok(None);
+    }
+
+    let parent_name = QName::new(Namespace::public_namespace(), "parent");
+    if !object.has_property(&parent_name)? {
+        return Ok(None);
+    }
+
+    match object.get_property(object, &parent_name, activation)? {
+        Value::Object(parent) => Ok(Some(parent)),
+        _ => Ok(None),
+    }
+}
+
+/// List the display list ancestors of an object, starting with its parent.
+///
+/// Objects that are not display objects have no ancestors.
+pub fn display_ancestors<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+) -> Result<Vec<Object<'gc>>, Error> {
+    let mut ancestor_list = Vec::new();
+    let mut parent = display_parent(activation, target)?;
+    while let Some(ancestor) = parent {
+        ancestor_list.push(ancestor);
+        parent = display_parent(activation, ancestor)?;
+    }
+
+    Ok(ancestor_list)
+}
+
+/// Call all of the event handlers on a given target for the event's current
+/// phase.
+///
+/// The `target` is the current target of the `event`; it is set as such
+/// before any handlers are run. Handlers registered for the capture phase
+/// only run during `EventPhase::Capturing`, all others run in the remaining
+/// phases.
+fn dispatch_event_to_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error> {
+    let (event_type, use_capture) = {
+        let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+
+        evtmut.set_current_target(target);
+
+        (evtmut.event_type(), evtmut.phase() == EventPhase::Capturing)
+    };
+
+    // Handlers may add or remove other handlers while we run them, which
+    // must not affect the current dispatch.
+    let handlers: Vec<Object<'gc>> = match target.as_dispatch() {
+        Some(dispatch_list) => dispatch_list
+            .iter_event_handlers(event_type, use_capture)
+            .collect(),
+        None => return Ok(()),
+    };
+
+    for handler in handlers {
+        if event
+            .as_event()
+            .unwrap()
+            .is_propagation_stopped_immediately()
+        {
+            break;
+        }
+
+        handler.call(None, &[event.into()], activation, None)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatch an event on an object, and it's display list ancestors if it has
+/// any.
+///
+/// Events dispatched on display objects go through all three phases of
+/// propagation: capturing from the root of the display list down to the
+/// target's parent, at-target, and (if the event bubbles) bubbling back up
+/// from the target's parent. Events dispatched on any other object only run
+/// the at-target phase.
+///
+/// The return value is `true` if the event's default action should run
+/// (i.e. it was not cancelled).
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<bool, Error> {
+    let ancestor_list = display_ancestors(activation, target)?;
+
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+    evtmut.set_phase(EventPhase::Capturing);
+    evtmut.set_target(target);
+    drop(evtmut);
+
+    for ancestor in ancestor_list.iter().rev() {
+        if event.as_event().unwrap().is_propagation_stopped() {
+            break;
+        }
+
+        dispatch_event_to_target(activation, *ancestor, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::AtTarget);
+
+    if !event.as_event().unwrap().is_propagation_stopped() {
+        dispatch_event_to_target(activation, target, event)?;
+    }
+
+    event
+        .as_event_mut(activation.context.gc_context)
+        .unwrap()
+        .set_phase(EventPhase::Bubbling);
+
+    if event.as_event().unwrap().is_bubbling() {
+        for ancestor in ancestor_list.iter() {
+            if event.as_event().unwrap().is_propagation_stopped() {
+                break;
+            }
+
+            dispatch_event_to_target(activation, *ancestor, event)?;
+        }
+    }
+
+    let handled = !event.as_event().unwrap().is_cancelled();
+
+    Ok(handled)
+}
+
+/// The events that are broadcast to every display object that listens for
+/// them, rather than dispatched through the display list.
+pub const BROADCAST_EVENTS: &[&str] = &["enterFrame", "frameConstructed", "exitFrame"];
+
+/// Dispatch a broadcast event, such as `enterFrame`, on an object.
+///
+/// Broadcast events only run the at-target phase, even on display objects:
+/// every object that listens for them is a target of its own.
+pub fn broadcast_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event: Object<'gc>,
+) -> Result<(), Error> {
+    let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+    evtmut.set_phase(EventPhase::AtTarget);
+    evtmut.set_target(target);
+    drop(evtmut);
+
+    dispatch_event_to_target(activation, target, event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::object::ScriptObject;
+    use gc_arena::rootless_arena;
+
+    #[test]
+    fn handlers_run_in_priority_order() {
+        rootless_arena(|mc| {
+            let first = ScriptObject::bare_object(mc);
+            let second = ScriptObject::bare_object(mc);
+            let third = ScriptObject::bare_object(mc);
+            let mut list = DispatchList::new();
+
+            list.add_event_listener("click", 0, second, false);
+            list.add_event_listener("click", 0, third, false);
+            list.add_event_listener("click", 10, first, false);
+
+            let handlers: Vec<_> = list.iter_event_handlers("click", false).collect();
+            assert_eq!(handlers.len(), 3);
+            assert!(Object::ptr_eq(handlers[0], first));
+            assert!(Object::ptr_eq(handlers[1], second));
+            assert!(Object::ptr_eq(handlers[2], third));
+        })
+    }
+
+    #[test]
+    fn duplicate_handlers_are_ignored() {
+        rootless_arena(|mc| {
+            let handler = ScriptObject::bare_object(mc);
+            let mut list = DispatchList::new();
+
+            list.add_event_listener("click", 0, handler, false);
+            list.add_event_listener("click", 5, handler, false);
+            list.add_event_listener("click", 0, handler, true);
+
+            assert_eq!(list.iter_event_handlers("click", false).count(), 1);
+            assert_eq!(list.iter_event_handlers("click", true).count(), 1);
+        })
+    }
+
+    #[test]
+    fn removal_matches_phase() {
+        rootless_arena(|mc| {
+            let handler = ScriptObject::bare_object(mc);
+            let mut list = DispatchList::new();
+
+            list.add_event_listener("click", 0, handler, true);
+            list.remove_event_listener("click", handler, false);
+            assert!(list.has_event_listener("click"));
+
+            list.remove_event_listener("click", handler, true);
+            assert!(!list.has_event_listener("click"));
+            assert!(!list.has_event_listener("keyDown"));
+        })
+    }
+
+    #[test]
+    fn stopping_propagation() {
+        let mut event = Event::new("click");
+        assert!(!event.is_propagation_stopped());
+
+        event.stop_propagation();
+        assert!(event.is_propagation_stopped());
+        assert!(!event.is_propagation_stopped_immediately());
+
+        event.stop_immediate_propagation();
+        event.stop_propagation();
+        assert!(event.is_propagation_stopped_immediately());
+    }
+
+    #[test]
+    fn only_cancelable_events_cancel() {
+        let mut event = Event::new("click");
+        event.cancel();
+        assert!(!event.is_cancelled());
+
+        event.set_cancelable(true);
+        event.cancel();
+        assert!(event.is_cancelled());
+    }
+}
@@ -90,16 +90,20 @@ impl<'gc> Executable<'gc> {
             }
             Executable::Action(bm) => {
                 let reciever = bm.reciever.or(unbound_reciever);
-                let mut activation = Activation::from_method(
+
+                activation.context.avm2.push_call(bm.method);
+                let result = Activation::from_method(
                     activation.context.reborrow(),
                     bm.method,
                     bm.scope,
                     reciever,
                     arguments,
                     base_proto,
-                )?;
+                )
+                .and_then(|mut activation| activation.run_actions(bm.method));
+                activation.context.avm2.pop_call();
 
-                activation.run_actions(bm.method)
+                result
             }
         }
     }
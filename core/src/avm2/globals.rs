@@ -2,9 +2,14 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
 use crate::avm2::method::NativeMethod;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{FunctionObject, Object, ScriptObject, TObject};
+use crate::avm2::object::{
+    ArrayObject, BitmapDataObject, ByteArrayObject, DictionaryObject, DispatchObject, DomainObject,
+    EventObject, FunctionObject, Matrix3DObject, NamespaceObject, Object, ScriptObject,
+    StageObject, TObject, XMLListObject, XMLObject,
+};
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
@@ -13,16 +18,21 @@ use crate::avm2::Error;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::f64::NAN;
 
+mod array;
 mod boolean;
 mod class;
-mod flash;
+mod error;
+pub mod flash;
 mod function;
 mod int;
+mod json;
 mod namespace;
 mod number;
 mod object;
 mod string;
 mod r#uint;
+mod xml;
+mod xml_list;
 
 fn trace<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -49,6 +59,9 @@ pub struct SystemPrototypes<'gc> {
     pub int: Object<'gc>,
     pub uint: Object<'gc>,
     pub namespace: Object<'gc>,
+    pub array: Object<'gc>,
+    pub xml: Object<'gc>,
+    pub xml_list: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -75,6 +88,9 @@ impl<'gc> SystemPrototypes<'gc> {
             int: empty,
             uint: empty,
             namespace: empty,
+            array: empty,
+            xml: empty,
+            xml_list: empty,
         }
     }
 }
@@ -82,16 +98,19 @@ impl<'gc> SystemPrototypes<'gc> {
 /// Add a free-function builtin to the global scope.
 fn function<'gc>(
     mc: MutationContext<'gc, '_>,
-    mut global_scope: Object<'gc>,
+    domain: Domain<'gc>,
     package: impl Into<AvmString<'gc>>,
     name: impl Into<AvmString<'gc>>,
     nf: NativeMethod<'gc>,
     fn_proto: Object<'gc>,
 ) {
-    global_scope
+    let name = QName::new(Namespace::package(package), name);
+    domain.export_definition(name.clone(), mc);
+    domain
+        .globals()
         .install_dynamic_property(
             mc,
-            QName::new(Namespace::package(package), name),
+            name,
             FunctionObject::from_builtin(mc, nf, fn_proto).into(),
         )
         .unwrap()
@@ -102,11 +121,7 @@ fn function<'gc>(
 /// Since the function has to return a normal prototype object in this case, we
 /// have to construct a constructor to go along with it, as if we had called
 /// `install_foreign_trait` with such a class.
-fn dynamic_class<'gc>(
-    mc: MutationContext<'gc, '_>,
-    mut global_scope: Object<'gc>,
-    constr: Object<'gc>,
-) {
+fn dynamic_class<'gc>(mc: MutationContext<'gc, '_>, domain: Domain<'gc>, constr: Object<'gc>) {
     let name = constr
         .as_class()
         .expect("constrs have classes in them")
@@ -114,7 +129,8 @@ fn dynamic_class<'gc>(
         .name()
         .clone();
 
-    global_scope.install_const(mc, name, 0, constr.into());
+    domain.export_definition(name.clone(), mc);
+    domain.globals().install_const(mc, name, 0, constr.into());
 }
 
 /// Add a class builtin to the global scope.
@@ -122,10 +138,12 @@ fn dynamic_class<'gc>(
 /// This function returns a prototype which may be stored in `SystemPrototypes`.
 fn class<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
-    mut global: Object<'gc>,
+    domain: Domain<'gc>,
     class_def: GcCell<'gc, Class<'gc>>,
 ) -> Result<Object<'gc>, Error> {
+    let mut global = domain.globals();
     let class_trait = Trait::from_class(class_def);
+    domain.export_definition(class_trait.name().clone(), activation.context.gc_context);
     let global_scope = Scope::push_scope(global.get_scope(), global, activation.context.gc_context);
     let mut constr = global
         .install_foreign_trait(activation, class_trait, Some(global_scope), global)?
@@ -140,15 +158,70 @@ fn class<'gc>(
         .coerce_to_object(activation)
 }
 
+/// A function that constructs the prototype for a class builtin whose
+/// instances are a native object type, given the superclass prototype.
+type Deriver<'gc> = fn(
+    Object<'gc>,
+    MutationContext<'gc, '_>,
+    GcCell<'gc, Class<'gc>>,
+    Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error>;
+
+/// Add a class builtin whose instances are a native object type.
+///
+/// This works like `class`, except that the class prototype is replaced with
+/// one constructed by `deriver`. Since prototypes construct their own
+/// instances, this ensures that the class and all of its subclasses produce
+/// the correct kind of object.
+fn native_class<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    domain: Domain<'gc>,
+    class_def: GcCell<'gc, Class<'gc>>,
+    deriver: Deriver<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let mut global = domain.globals();
+    let class_trait = Trait::from_class(class_def);
+    domain.export_definition(class_trait.name().clone(), activation.context.gc_context);
+    let global_scope = Scope::push_scope(global.get_scope(), global, activation.context.gc_context);
+    let mut constr = global
+        .install_foreign_trait(activation, class_trait, Some(global_scope), global)?
+        .coerce_to_object(activation)?;
+
+    let prototype_name = QName::new(Namespace::public_namespace(), "prototype");
+    let script_proto = constr
+        .get_property(constr, &prototype_name, activation)?
+        .coerce_to_object(activation)?;
+    let base_proto: Result<Object<'gc>, Error> = script_proto
+        .proto()
+        .ok_or_else(|| "Native classes must have a superclass".into());
+
+    let mut proto = deriver(
+        base_proto?,
+        activation.context.gc_context,
+        class_def,
+        Some(global_scope),
+    )?;
+    constr.install_dynamic_property(activation.context.gc_context, prototype_name, proto.into())?;
+    proto.install_dynamic_property(
+        activation.context.gc_context,
+        QName::new(Namespace::public_namespace(), "constructor"),
+        constr.into(),
+    )?;
+
+    Ok(proto)
+}
+
 /// Add a builtin constant to the global scope.
 fn constant<'gc>(
     mc: MutationContext<'gc, '_>,
-    mut global_scope: Object<'gc>,
+    domain: Domain<'gc>,
     package: impl Into<AvmString<'gc>>,
     name: impl Into<AvmString<'gc>>,
     value: Value<'gc>,
 ) {
-    global_scope.install_const(mc, QName::new(Namespace::package(package), name), 0, value)
+    let name = QName::new(Namespace::package(package), name);
+    domain.export_definition(name.clone(), mc);
+    domain.globals().install_const(mc, name, 0, value)
 }
 
 /// Initialize all remaining builtin classes.
@@ -157,7 +230,7 @@ fn constant<'gc>(
 /// player. It will return a list of prototypes it has created, which should be
 /// stored on the AVM.
 pub fn load_player_globals<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error> {
-    let gs = activation.avm2().globals();
+    let domain = activation.avm2().global_domain();
 
     // public / root package
     let object_proto = object::create_proto(activation);
@@ -166,9 +239,9 @@ pub fn load_player_globals<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Res
 
     let object_constr = object::fill_proto(activation.context.gc_context, object_proto, fn_proto);
 
-    dynamic_class(activation.context.gc_context, gs, object_constr);
-    dynamic_class(activation.context.gc_context, gs, function_constr);
-    dynamic_class(activation.context.gc_context, gs, class_constr);
+    dynamic_class(activation.context.gc_context, domain, object_constr);
+    dynamic_class(activation.context.gc_context, domain, function_constr);
+    dynamic_class(activation.context.gc_context, domain, class_constr);
 
     // At this point, we need at least a partial set of system prototypes in
     // order to continue initializing the player. The rest of the prototypes
@@ -188,40 +261,70 @@ pub fn load_player_globals<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Res
 
     sp.string = class(
         activation,
-        gs,
+        domain,
         string::create_class(activation.context.gc_context),
     )?;
     sp.boolean = class(
         activation,
-        gs,
+        domain,
         boolean::create_class(activation.context.gc_context),
     )?;
     sp.number = class(
         activation,
-        gs,
+        domain,
         number::create_class(activation.context.gc_context),
     )?;
     sp.int = class(
         activation,
-        gs,
+        domain,
         int::create_class(activation.context.gc_context),
     )?;
     sp.uint = class(
         activation,
-        gs,
+        domain,
         uint::create_class(activation.context.gc_context),
     )?;
-    sp.namespace = class(
+    sp.namespace = native_class(
         activation,
-        gs,
+        domain,
         namespace::create_class(activation.context.gc_context),
+        NamespaceObject::derive,
+    )?;
+    sp.array = native_class(
+        activation,
+        domain,
+        array::create_class(activation.context.gc_context),
+        ArrayObject::derive,
+    )?;
+    sp.xml = native_class(
+        activation,
+        domain,
+        xml::create_class(activation.context.gc_context),
+        XMLObject::derive,
+    )?;
+    sp.xml_list = native_class(
+        activation,
+        domain,
+        xml_list::create_class(activation.context.gc_context),
+        XMLListObject::derive,
     )?;
 
     activation.context.avm2.system_prototypes = Some(sp);
 
+    class(
+        activation,
+        domain,
+        error::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        json::create_class(activation.context.gc_context),
+    )?;
+
     function(
         activation.context.gc_context,
-        gs,
+        domain,
         "",
         "trace",
         trace,
@@ -229,54 +332,413 @@ pub fn load_player_globals<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Res
     );
     constant(
         activation.context.gc_context,
-        gs,
+        domain,
         "",
         "undefined",
         Value::Undefined,
     );
-    constant(activation.context.gc_context, gs, "", "null", Value::Null);
-    constant(activation.context.gc_context, gs, "", "NaN", NAN.into());
     constant(
         activation.context.gc_context,
-        gs,
+        domain,
+        "",
+        "null",
+        Value::Null,
+    );
+    constant(activation.context.gc_context, domain, "", "NaN", NAN.into());
+    constant(
+        activation.context.gc_context,
+        domain,
         "",
         "Infinity",
         f64::INFINITY.into(),
     );
 
     // package `flash.events`
-    class(
+    native_class(
+        activation,
+        domain,
+        flash::events::event::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
         activation,
-        gs,
+        domain,
         flash::events::eventdispatcher::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::focusevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::gestureevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::events::gesturephase::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::keyboardevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::mouseevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::netstatusevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::progressevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::textevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::timerevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::touchevent::create_class(activation.context.gc_context),
+        EventObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::events::transformgestureevent::create_class(activation.context.gc_context),
+        EventObject::derive,
     )?;
 
-    // package `flash.display`
+    // package `flash.desktop`
     class(
         activation,
-        gs,
+        domain,
+        flash::desktop::clipboard::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::desktop::clipboardformats::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::desktop::clipboardtransfermode::create_class(activation.context.gc_context),
+    )?;
+
+    // package `flash.display`
+    native_class(
+        activation,
+        domain,
         flash::display::displayobject::create_class(activation.context.gc_context),
+        StageObject::derive,
     )?;
     class(
         activation,
-        gs,
+        domain,
+        flash::display::shape::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
         flash::display::interactiveobject::create_class(activation.context.gc_context),
     )?;
     class(
         activation,
-        gs,
+        domain,
         flash::display::displayobjectcontainer::create_class(activation.context.gc_context),
     )?;
     class(
         activation,
-        gs,
+        domain,
         flash::display::sprite::create_class(activation.context.gc_context),
     )?;
     class(
         activation,
-        gs,
+        domain,
         flash::display::movieclip::create_class(activation.context.gc_context),
     )?;
+    class(
+        activation,
+        domain,
+        flash::display::stage::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::display::loader::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::display::loaderinfo::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::display::scene::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::display::framelabel::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::display::bitmapdata::create_class(activation.context.gc_context),
+        BitmapDataObject::derive,
+    )?;
+
+    // package `flash.geom`
+    class(
+        activation,
+        domain,
+        flash::geom::point::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::rectangle::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::matrix::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::vector3d::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::geom::matrix3d::create_class(activation.context.gc_context),
+        Matrix3DObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::perspectiveprojection::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::colortransform::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::geom::transform::create_class(activation.context.gc_context),
+    )?;
+
+    // package `flash.net`
+    native_class(
+        activation,
+        domain,
+        flash::net::netconnection::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::net::netstream::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::net::sharedobject::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::net::urlrequest::create_class(activation.context.gc_context),
+    )?;
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.net",
+        "registerClassAlias",
+        flash::net::register_class_alias,
+        fn_proto,
+    );
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.net",
+        "getClassByAlias",
+        flash::net::get_class_by_alias,
+        fn_proto,
+    );
+
+    // package `flash.system`
+    class(
+        activation,
+        domain,
+        flash::system::capabilities::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::system::applicationdomain::create_class(activation.context.gc_context),
+        DomainObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::system::loadercontext::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::system::messagechannel::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::system::worker::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::system::workerdomain::create_class(activation.context.gc_context),
+    )?;
+
+    // package `flash.ui`
+    class(
+        activation,
+        domain,
+        flash::ui::keyboard::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::keylocation::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::mouse::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::mousecursor::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::mousecursordata::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::multitouch::create_class(activation.context.gc_context),
+    )?;
+    class(
+        activation,
+        domain,
+        flash::ui::multitouchinputmode::create_class(activation.context.gc_context),
+    )?;
+
+    // package `flash.utils`
+    native_class(
+        activation,
+        domain,
+        flash::utils::bytearray::create_class(activation.context.gc_context),
+        ByteArrayObject::derive,
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::utils::dictionary::create_class(activation.context.gc_context),
+        DictionaryObject::derive,
+    )?;
+    class(
+        activation,
+        domain,
+        flash::utils::proxy::create_class(activation.context.gc_context),
+    )?;
+    native_class(
+        activation,
+        domain,
+        flash::utils::timer::create_class(activation.context.gc_context),
+        DispatchObject::derive,
+    )?;
+    constant(
+        activation.context.gc_context,
+        domain,
+        "flash.utils",
+        "flash_proxy",
+        NamespaceObject::from_namespace(
+            flash::utils::proxy::flash_proxy_namespace(),
+            activation.avm2().prototypes().namespace,
+            activation.context.gc_context,
+        )?
+        .into(),
+    );
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.utils",
+        "getQualifiedClassName",
+        flash::utils::get_qualified_class_name,
+        fn_proto,
+    );
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.utils",
+        "getQualifiedSuperclassName",
+        flash::utils::get_qualified_superclass_name,
+        fn_proto,
+    );
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.utils",
+        "getDefinitionByName",
+        flash::utils::get_definition_by_name,
+        fn_proto,
+    );
+    function(
+        activation.context.gc_context,
+        domain,
+        "flash.utils",
+        "getTimer",
+        flash::utils::get_timer,
+        fn_proto,
+    );
 
     Ok(())
 }
@@ -0,0 +1,198 @@
+//! `Array` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `Array`'s instance initializer.
+///
+/// A single numeric argument is the length of an array of holes; any other
+/// arguments are the array's elements.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let storage = match args {
+            [Value::Number(_)] | [Value::Unsigned(_)] | [Value::Integer(_)] => {
+                let expected_length = args[0].coerce_to_number(activation)?;
+                if expected_length < 0.0
+                    || expected_length > u32::MAX as f64
+                    || expected_length.fract() != 0.0
+                {
+                    return Err(format!(
+                        "RangeError: Error #1005: Array index is not a positive integer ({})",
+                        expected_length
+                    )
+                    .into());
+                }
+
+                ArrayStorage::new(expected_length as usize)
+            }
+            _ => ArrayStorage::from_args(args),
+        };
+
+        if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+            *array = storage;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(array) = this.as_ref().and_then(|this| this.as_array_storage()) {
+        return Ok((array.length() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+            array.set_length(length as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.push`.
+pub fn push<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut array) = this.as_array_storage_mut(activation.context.gc_context) {
+            for arg in args {
+                array.push(arg.clone());
+            }
+
+            return Ok((array.length() as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.join`.
+///
+/// Holes, `undefined` and `null` are all joined as the empty string.
+pub fn join<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let separator = match args.get(0) {
+        None | Some(Value::Undefined) => ",".into(),
+        Some(separator) => separator.coerce_to_string(activation)?,
+    };
+
+    if let Some(this) = this {
+        // Elements may be objects whose `toString` touches this array, so
+        // the storage must not stay borrowed while we coerce them.
+        let elements: Vec<_> = match this.as_array_storage() {
+            Some(array) => array.iter().collect(),
+            None => return Ok(Value::Undefined),
+        };
+
+        let mut accum = Vec::with_capacity(elements.len());
+        for element in elements {
+            accum.push(match element {
+                None | Some(Value::Undefined) | Some(Value::Null) => "".into(),
+                Some(value) => value.coerce_to_string(activation)?,
+            });
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            accum
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(&separator),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Array.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    join(activation, this, &[])
+}
+
+/// Construct `Array`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "Array"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(set_length),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] =
+        &[("push", push), ("join", join), ("toString", to_string)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,171 @@
+//! `Error` impl
+
+use crate::avm1::globals::system::SystemCapabilities;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+fn stack_trace_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("Error".into()), "stackTrace")
+}
+
+/// Implements `Error`'s instance initializer.
+///
+/// Debugger players remember the call stack that the error was created in.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let message = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| "".into())
+            .coerce_to_string(activation)?;
+        let error_id = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "message"),
+            message.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public_namespace(), "errorID"),
+            error_id.into(),
+            activation,
+        )?;
+
+        if activation
+            .context
+            .system
+            .has_capability(SystemCapabilities::Debugger)
+        {
+            let description =
+                to_string(activation, Some(this), &[])?.coerce_to_string(activation)?;
+            let stack_trace = format!("{}{}", description, activation.avm2().stack_trace());
+            let stack_trace = AvmString::new(activation.context.gc_context, stack_trace);
+            this.set_property(this, &stack_trace_name(), stack_trace.into(), activation)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.getStackTrace`.
+///
+/// Release players don't keep stack traces, and return `null`.
+pub fn get_stack_trace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &stack_trace_name(), activation);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Error.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "name"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+        let message = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "message"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}: {}", name, message),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Error`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(""), "Error"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("message", "String", "".into()),
+        ("name", "String", "Error".into()),
+        ("errorID", "int", 0.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_slot(
+        stack_trace_name(),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some(Value::Null),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] =
+        &[("getStackTrace", get_stack_trace), ("toString", to_string)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
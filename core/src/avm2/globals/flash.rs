@@ -1,4 +1,10 @@
 //! `flash` namespace
 
+pub mod desktop;
 pub mod display;
 pub mod events;
+pub mod geom;
+pub mod net;
+pub mod system;
+pub mod ui;
+pub mod utils;
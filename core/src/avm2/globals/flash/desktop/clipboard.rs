@@ -0,0 +1,217 @@
+//! `flash.desktop.Clipboard` builtin/prototype
+//!
+//! Only the system clipboard, `Clipboard.generalClipboard`, is supported, and
+//! only plain text can be stored on it. Like the browser clipboard APIs,
+//! writes are only allowed while a user-initiated event is being handled.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The value of `ClipboardFormats.TEXT_FORMAT`.
+const TEXT_FORMAT: &str = "air:text";
+
+/// Read the clipboard format named by an argument.
+fn format_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<AvmString<'gc>, Error> {
+    args.get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)
+}
+
+/// Fail unless the clipboard may currently be written to.
+fn check_user_event(activation: &Activation<'_, '_, '_>) -> Result<(), Error> {
+    if activation.context.is_user_event {
+        Ok(())
+    } else {
+        Err("SecurityError: Error #2176: Certain actions, such as those that display a pop-up window, may only be invoked upon user interaction, for example by a mouse click or button press.".into())
+    }
+}
+
+/// Implements `flash.desktop.Clipboard`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: Error #2014: Feature is not available at this time.".into())
+}
+
+/// Implements `flash.desktop.Clipboard`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.generalClipboard`.
+pub fn general_clipboard<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let instance_name = QName::new(
+            Namespace::Private("flash.desktop:Clipboard".into()),
+            "generalClipboard",
+        );
+        if let Value::Object(instance) = this.get_property(this, &instance_name, activation)? {
+            return Ok(instance.into());
+        }
+
+        let proto = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let instance = proto.construct(activation, &[])?;
+        this.set_property(this, &instance_name, instance.into(), activation)?;
+
+        return Ok(instance.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.getData`.
+///
+/// Returns `null` for formats other than text.
+pub fn get_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let format = format_arg(activation, args)?;
+    if &*format != TEXT_FORMAT {
+        return Ok(Value::Null);
+    }
+
+    let content = activation.context.input.clipboard_content();
+    Ok(AvmString::new(activation.context.gc_context, content).into())
+}
+
+/// Implements `Clipboard.setData`.
+///
+/// Returns whether the format is supported.
+pub fn set_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_user_event(activation)?;
+
+    let format = format_arg(activation, args)?;
+    if &*format != TEXT_FORMAT {
+        return Ok(false.into());
+    }
+
+    let content = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    activation
+        .context
+        .input
+        .set_clipboard_content(content.to_string());
+
+    Ok(true.into())
+}
+
+/// Implements `Clipboard.hasFormat`.
+pub fn has_format<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let format = format_arg(activation, args)?;
+    if &*format != TEXT_FORMAT {
+        return Ok(false.into());
+    }
+
+    Ok((!activation.context.input.clipboard_content().is_empty()).into())
+}
+
+/// Implements `Clipboard.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_user_event(activation)?;
+    activation
+        .context
+        .input
+        .set_clipboard_content(String::new());
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.clearData`.
+pub fn clear_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    check_user_event(activation)?;
+
+    let format = format_arg(activation, args)?;
+    if &*format == TEXT_FORMAT {
+        activation
+            .context
+            .input
+            .set_clipboard_content(String::new());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Clipboard`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.desktop"), "Clipboard"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "generalClipboard"),
+        Method::from_builtin(general_clipboard),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getData", get_data),
+        ("setData", set_data),
+        ("hasFormat", has_format),
+        ("clear", clear),
+        ("clearData", clear_data),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,63 @@
+//! `flash.desktop.ClipboardFormats` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.desktop.ClipboardFormats`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ClipboardFormats cannot be constructed".into())
+}
+
+/// Implements `flash.desktop.ClipboardFormats`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `ClipboardFormats`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.desktop"), "ClipboardFormats"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    let constants = &[
+        ("BITMAP_FORMAT", "air:bitmap"),
+        ("FILE_LIST_FORMAT", "air:file list"),
+        ("FILE_PROMISE_LIST_FORMAT", "air:file promise list"),
+        ("HTML_FORMAT", "air:html"),
+        ("RICH_TEXT_FORMAT", "air:rtf"),
+        ("TEXT_FORMAT", "air:text"),
+        ("URL_FORMAT", "air:url"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    class
+}
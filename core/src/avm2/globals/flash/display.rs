@@ -1,7 +1,14 @@
 //! `flash.display` namespace
 
+pub mod bitmapdata;
 pub mod displayobject;
 pub mod displayobjectcontainer;
+pub mod framelabel;
 pub mod interactiveobject;
+pub mod loader;
+pub mod loaderinfo;
 pub mod movieclip;
+pub mod scene;
+pub mod shape;
 pub mod sprite;
+pub mod stage;
@@ -0,0 +1,441 @@
+//! `flash.display.BitmapData` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bitmapdata::{
+    BitmapDataStorage, DrawMatrix, PixelRect, MAX_DIMENSION, MAX_PIXELS,
+};
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::color_transform::ColorTransform;
+use gc_arena::{GcCell, MutationContext};
+use swf::BlendMode;
+
+/// Read a public property of an object as a number.
+fn number_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    object
+        .get_property(
+            object,
+            &QName::new(Namespace::public_namespace(), name),
+            activation,
+        )?
+        .coerce_to_number(activation)
+}
+
+/// Read an optional argument, treating `null` the same as a missing one.
+fn optional_object_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Option<Object<'gc>>, Error> {
+    match args.get(index) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(None),
+        Some(value) => Ok(Some(value.coerce_to_object(activation)?)),
+    }
+}
+
+/// Read a `Rectangle` argument as the pixels it covers.
+fn rect_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: Object<'gc>,
+) -> Result<PixelRect, Error> {
+    let x = number_property(activation, rect, "x")?;
+    let y = number_property(activation, rect, "y")?;
+    let width = number_property(activation, rect, "width")?;
+    let height = number_property(activation, rect, "height")?;
+    Ok(PixelRect::covering(x, y, width, height))
+}
+
+/// Read a `Matrix` argument.
+fn matrix_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: Object<'gc>,
+) -> Result<DrawMatrix, Error> {
+    Ok(DrawMatrix {
+        a: number_property(activation, matrix, "a")?,
+        b: number_property(activation, matrix, "b")?,
+        c: number_property(activation, matrix, "c")?,
+        d: number_property(activation, matrix, "d")?,
+        tx: number_property(activation, matrix, "tx")?,
+        ty: number_property(activation, matrix, "ty")?,
+    })
+}
+
+/// Read a `ColorTransform` argument.
+fn color_transform_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: Object<'gc>,
+) -> Result<ColorTransform, Error> {
+    let ct = color_transform;
+    Ok(ColorTransform {
+        r_mult: number_property(activation, ct, "redMultiplier")? as f32,
+        g_mult: number_property(activation, ct, "greenMultiplier")? as f32,
+        b_mult: number_property(activation, ct, "blueMultiplier")? as f32,
+        a_mult: number_property(activation, ct, "alphaMultiplier")? as f32,
+        r_add: number_property(activation, ct, "redOffset")? as f32 / 255.0,
+        g_add: number_property(activation, ct, "greenOffset")? as f32 / 255.0,
+        b_add: number_property(activation, ct, "blueOffset")? as f32 / 255.0,
+        a_add: number_property(activation, ct, "alphaOffset")? as f32 / 255.0,
+    })
+}
+
+/// Parse a blend mode name, as listed in `flash.display.BlendMode`.
+fn parse_blend_mode(name: &str) -> Option<BlendMode> {
+    Some(match name {
+        "normal" => BlendMode::Normal,
+        "layer" => BlendMode::Layer,
+        "multiply" => BlendMode::Multiply,
+        "screen" => BlendMode::Screen,
+        "lighten" => BlendMode::Lighten,
+        "darken" => BlendMode::Darken,
+        "difference" => BlendMode::Difference,
+        "add" => BlendMode::Add,
+        "subtract" => BlendMode::Subtract,
+        "invert" => BlendMode::Invert,
+        "alpha" => BlendMode::Alpha,
+        "erase" => BlendMode::Erase,
+        "overlay" => BlendMode::Overlay,
+        "hardlight" => BlendMode::HardLight,
+        _ => return None,
+    })
+}
+
+/// Read the `x` and `y` arguments shared by the pixel methods.
+fn xy_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(i32, i32), Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let y = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    Ok((x, y))
+}
+
+/// Implements `flash.display.BitmapData`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let width = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let height = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let transparent = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Bool(true))
+        .coerce_to_boolean();
+    let fill_color = args
+        .get(3)
+        .cloned()
+        .unwrap_or_else(|| 0xFFFF_FFFFu32.into())
+        .coerce_to_u32(activation)?;
+
+    let is_valid_dimension = |value: i32| value > 0 && value as u32 <= MAX_DIMENSION;
+    if !is_valid_dimension(width)
+        || !is_valid_dimension(height)
+        || width as u32 * height as u32 > MAX_PIXELS
+    {
+        return Err("ArgumentError: Error #2015: Invalid BitmapData.".into());
+    }
+
+    if let Some(mut storage) = this
+        .as_ref()
+        .and_then(|this| this.as_bitmap_data_mut(activation.context.gc_context))
+    {
+        *storage = BitmapDataStorage::new(width as u32, height as u32, transparent, fill_color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.BitmapData`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.transparent`'s getter.
+pub fn transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.transparent().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel`.
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (x, y) = xy_args(activation, args)?;
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.get_pixel(x, y).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel32`.
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (x, y) = xy_args(activation, args)?;
+    if let Some(storage) = this.as_ref().and_then(|this| this.as_bitmap_data()) {
+        return Ok(storage.get_pixel32(x, y).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel`.
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (x, y) = xy_args(activation, args)?;
+    let color = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    if let Some(mut storage) = this
+        .as_ref()
+        .and_then(|this| this.as_bitmap_data_mut(activation.context.gc_context))
+    {
+        storage.set_pixel(x, y, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel32`.
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (x, y) = xy_args(activation, args)?;
+    let color = args
+        .get(2)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    if let Some(mut storage) = this
+        .as_ref()
+        .and_then(|this| this.as_bitmap_data_mut(activation.context.gc_context))
+    {
+        storage.set_pixel32(x, y, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.fillRect`.
+pub fn fill_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let rect = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let rect = rect_arg(activation, rect)?;
+    let color = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    if let Some(mut storage) = this
+        .as_ref()
+        .and_then(|this| this.as_bitmap_data_mut(activation.context.gc_context))
+    {
+        storage.fill_rect(rect, color);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.draw`.
+///
+/// Only other `BitmapData` objects can be drawn so far; display objects are
+/// ignored.
+pub fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let this = match this {
+        Some(this) => this,
+        None => return Ok(Value::Undefined),
+    };
+    let source = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    if Object::ptr_eq(source, this) {
+        return Err("ArgumentError: Error #2015: Invalid BitmapData.".into());
+    }
+
+    let matrix = match optional_object_arg(activation, args, 1)? {
+        Some(matrix) => matrix_arg(activation, matrix)?,
+        None => DrawMatrix::IDENTITY,
+    };
+    let color_transform = match optional_object_arg(activation, args, 2)? {
+        Some(color_transform) => color_transform_arg(activation, color_transform)?,
+        None => ColorTransform::default(),
+    };
+    let blend_mode = match args.get(3) {
+        None | Some(Value::Undefined) | Some(Value::Null) => BlendMode::Normal,
+        Some(value) => {
+            let name = value.coerce_to_string(activation)?;
+            parse_blend_mode(&name).unwrap_or(BlendMode::Normal)
+        }
+    };
+    let clip_rect = match optional_object_arg(activation, args, 4)? {
+        Some(clip_rect) => Some(rect_arg(activation, clip_rect)?),
+        None => None,
+    };
+    let smoothing = args
+        .get(5)
+        .cloned()
+        .unwrap_or(Value::Bool(false))
+        .coerce_to_boolean();
+
+    if !matches!(
+        blend_mode,
+        BlendMode::Normal | BlendMode::Layer | BlendMode::Add | BlendMode::Multiply
+    ) {
+        log::warn!(
+            "BitmapData.draw: blend mode {:?} is not yet supported, drawing as normal",
+            blend_mode
+        );
+    }
+
+    let source = match source.as_bitmap_data() {
+        Some(source) => source.clone(),
+        None => {
+            log::warn!("BitmapData.draw: drawing display objects is not yet supported");
+            return Ok(Value::Undefined);
+        }
+    };
+    if let Some(mut storage) = this.as_bitmap_data_mut(activation.context.gc_context) {
+        storage.draw(
+            &source,
+            &matrix,
+            &color_transform,
+            blend_mode,
+            clip_rect,
+            smoothing,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `BitmapData`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "BitmapData"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("width", width),
+        ("height", height),
+        ("transparent", transparent),
+    ];
+
+    for &(name, getter) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getPixel", get_pixel),
+        ("getPixel32", get_pixel32),
+        ("setPixel", set_pixel),
+        ("setPixel32", set_pixel32),
+        ("fillRect", fill_rect),
+        ("draw", draw),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -2,11 +2,16 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::display::{display_object_to_avm2, display_parent_to_avm2, stage_object};
+use crate::avm2::globals::flash::geom::transform::{apply_to_display_object, create_transform};
+use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.DisplayObject`'s instance constructor.
@@ -27,13 +32,291 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Find the topmost ancestor of a display object, if it is the main timeline
+/// of a movie on the stage.
+fn root_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: DisplayObject<'gc>,
+) -> Option<DisplayObject<'gc>> {
+    let root = display_object.root();
+
+    if activation
+        .context
+        .levels
+        .values()
+        .any(|level| DisplayObject::ptr_eq(*level, root))
+    {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Implements `DisplayObject.name`'s getter.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(AvmString::new(activation.context.gc_context, dobj.name().to_string()).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.parent`'s getter.
+pub fn parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return display_parent_to_avm2(activation, dobj);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.root`'s getter.
+pub fn root<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(root) = root_of(activation, dobj) {
+            return Ok(display_object_to_avm2(activation, root)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.stage`'s getter.
+pub fn stage<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if root_of(activation, dobj).is_some() {
+            return Ok(stage_object(activation)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.x`'s getter.
+pub fn x<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.x().into());
+    }
+
+    Ok(0.0.into())
+}
+
+/// Implements `DisplayObject.x`'s setter.
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let x = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_x(activation.context.gc_context, x);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.y`'s getter.
+pub fn y<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.y().into());
+    }
+
+    Ok(0.0.into())
+}
+
+/// Implements `DisplayObject.y`'s setter.
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let y = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_y(activation.context.gc_context, y);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.width().into());
+    }
+
+    Ok(0.0.into())
+}
+
+/// Implements `DisplayObject.width`'s setter.
+pub fn set_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let width = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_width(activation.context.gc_context, width);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.height().into());
+    }
+
+    Ok(0.0.into())
+}
+
+/// Implements `DisplayObject.height`'s setter.
+pub fn set_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let height = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_height(activation.context.gc_context, height);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObject.transform`'s getter.
+///
+/// Each read constructs a new `Transform`.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(create_transform(activation, this)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `DisplayObject.transform`'s setter.
+///
+/// This copies the matrix and color transform of the given `Transform`.
+pub fn set_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let transform = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        apply_to_display_object(activation, transform, dobj)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "DisplayObject"),
         Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("name", name),
+        ("parent", parent),
+        ("root", root),
+        ("stage", stage),
+        ("x", x),
+        ("y", y),
+        ("width", width),
+        ("height", height),
+        ("transform", transform),
+    ];
+
+    for &(name, method) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let setters: &[(&str, NativeMethod<'gc>)] = &[
+        ("x", set_x),
+        ("y", set_y),
+        ("width", set_width),
+        ("height", set_height),
+        ("transform", set_transform),
+    ];
+
+    for &(name, method) in setters {
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
 }
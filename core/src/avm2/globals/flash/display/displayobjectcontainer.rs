@@ -1,13 +1,19 @@
 //! `flash.display.DisplayObjectContainer` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::display::display_object_to_avm2;
+use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::prelude::Depth;
 use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
 
 /// Implements `flash.display.DisplayObjectContainer`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -27,9 +33,191 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// The children of a container, in the order of their child indices.
+fn child_list<'gc>(this: Option<Object<'gc>>) -> Vec<DisplayObject<'gc>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+        .map(|mc| mc.render_list())
+        .unwrap_or_default()
+}
+
+/// Implements `DisplayObjectContainer.numChildren`'s getter.
+pub fn num_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((child_list(this).len() as i32).into())
+}
+
+/// Implements `DisplayObjectContainer.getChildAt`.
+pub fn get_child_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let children = child_list(this);
+
+    match children.get(index as usize) {
+        Some(child) if index >= 0 => Ok(display_object_to_avm2(activation, *child)?.into()),
+        _ => Err(format!(
+            "RangeError: Error #2006: The supplied index is out of bounds ({})",
+            index
+        )
+        .into()),
+    }
+}
+
+/// Implements `DisplayObjectContainer.getChildByName`.
+pub fn get_child_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    for child in child_list(this) {
+        if *child.name() == *name.as_str() {
+            return Ok(display_object_to_avm2(activation, child)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Collect the descendants of a container under a stage position, back to
+/// front.
+///
+/// Invisible objects are skipped along with their children, as are masks and
+/// anything that a mask hides at that position.
+fn objects_under_point<'gc>(
+    children: Vec<DisplayObject<'gc>>,
+    point: (Twips, Twips),
+    out: &mut Vec<DisplayObject<'gc>>,
+) {
+    // The masks in effect, as the depth they stop at and whether they
+    // contain the point.
+    let mut masks: Vec<(Depth, bool)> = Vec::new();
+    for child in children {
+        while masks
+            .last()
+            .map_or(false, |&(clip_depth, _)| child.depth() >= clip_depth)
+        {
+            masks.pop();
+        }
+
+        if child.clip_depth() > 0 && child.allow_as_mask() {
+            masks.push((child.clip_depth(), child.hit_test_shape(point)));
+            continue;
+        }
+
+        if !child.visible() || masks.iter().any(|&(_, contains_point)| !contains_point) {
+            continue;
+        }
+
+        if let Some(mc) = child.as_movie_clip() {
+            if mc.hit_test_drawing(point) {
+                out.push(child);
+            }
+            objects_under_point(mc.render_list(), point, out);
+        } else if child.hit_test_shape(point) {
+            out.push(child);
+        }
+    }
+}
+
+/// Implements `DisplayObjectContainer.getObjectsUnderPoint`.
+///
+/// Unlike mouse events, this ignores `mouseEnabled` and `mouseChildren`.
+pub fn get_objects_under_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut point = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let x = point
+        .get_property(
+            point,
+            &QName::new(Namespace::public_namespace(), "x"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+    let y = point
+        .get_property(
+            point,
+            &QName::new(Namespace::public_namespace(), "y"),
+            activation,
+        )?
+        .coerce_to_number(activation)?;
+
+    let mut objects = Vec::new();
+    objects_under_point(
+        child_list(this),
+        (Twips::from_pixels(x), Twips::from_pixels(y)),
+        &mut objects,
+    );
+
+    let mut array = ArrayStorage::new(0);
+    for object in objects {
+        array.push(display_object_to_avm2(activation, object)?.into());
+    }
+
+    Ok(ArrayObject::from_array(
+        array,
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s getter.
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_children().into());
+    }
+
+    Ok(true.into())
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s setter.
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_mouse_children(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObjectContainer`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(
             Namespace::package("flash.display"),
             "DisplayObjectContainer",
@@ -38,5 +226,42 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "numChildren"),
+        Method::from_builtin(num_children),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "mouseChildren"),
+        Method::from_builtin(mouse_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "mouseChildren"),
+        Method::from_builtin(set_mouse_children),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getChildAt", get_child_at),
+        ("getChildByName", get_child_by_name),
+        ("getObjectsUnderPoint", get_objects_under_point),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    // TODO: This is not consulted by Tab navigation yet.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public_namespace(), "tabChildren"),
+        QName::new(Namespace::public_namespace(), "Boolean").into(),
+        Some(true.into()),
+    ));
+
+    class
 }
@@ -0,0 +1,142 @@
+//! `flash.display.FrameLabel` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The property that stores the name of a `FrameLabel`.
+fn name_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.display:FrameLabel".into()),
+        "name",
+    )
+}
+
+/// The property that stores the frame number of a `FrameLabel`.
+fn frame_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.display:FrameLabel".into()),
+        "frame",
+    )
+}
+
+/// Construct a new `FrameLabel`.
+pub fn create_frame_label<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &str,
+    frame: u16,
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+    let mut constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.display"), "FrameLabel"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    let name = AvmString::new(activation.context.gc_context, name.to_string());
+    let args = [name.into(), frame.into()];
+    let frame_label = proto.construct(activation, &args)?;
+    constr.call(Some(frame_label), &args, activation, frame_label.proto())?;
+
+    Ok(frame_label)
+}
+
+/// Implements `flash.display.FrameLabel`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let frame = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(this, &name_name(), name.into(), activation)?;
+        this.set_property(this, &frame_name(), frame.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.FrameLabel`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `FrameLabel.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &name_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FrameLabel.frame`.
+pub fn frame<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &frame_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `FrameLabel`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "FrameLabel"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[("name", name), ("frame", frame)];
+
+    for &(name, method) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -4,9 +4,11 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.InteractiveObject`'s instance constructor.
@@ -27,13 +29,73 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `InteractiveObject.mouseEnabled`'s getter.
+pub fn mouse_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_enabled().into());
+    }
+
+    Ok(true.into())
+}
+
+/// Implements `InteractiveObject.mouseEnabled`'s setter.
+pub fn set_mouse_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        dobj.set_mouse_enabled(activation.context.gc_context, value);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `InteractiveObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "InteractiveObject"),
         Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "mouseEnabled"),
+        Method::from_builtin(mouse_enabled),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "mouseEnabled"),
+        Method::from_builtin(set_mouse_enabled),
+    ));
+
+    // TODO: These are not consulted by Tab navigation yet.
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("tabEnabled", "Boolean", false.into()),
+        ("tabIndex", "int", (-1).into()),
+        ("focusRect", "Object", Value::Null),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    class
 }
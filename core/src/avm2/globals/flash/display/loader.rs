@@ -0,0 +1,250 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::loaderinfo::create_loader_info;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Domain, Error};
+use crate::backend::navigator::{OwnedFuture, RequestOptions};
+use crate::loader::Error as LoaderError;
+use gc_arena::{GcCell, MutationContext};
+
+/// The property that stores the `LoaderInfo` of a `Loader`.
+fn content_loader_info_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.display:Loader".into()),
+        "contentLoaderInfo",
+    )
+}
+
+/// Implements `flash.display.Loader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let loader_info = create_loader_info(activation, this)?;
+        this.set_property(
+            this,
+            &content_loader_info_name(),
+            loader_info.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The settings of a `LoaderContext` that affect how a movie is loaded.
+struct LoadSettings<'gc> {
+    /// The domain that the movie's scripts are loaded into.
+    domain: Domain<'gc>,
+
+    /// Whether `loadBytes` may load movies, which contain code.
+    allow_code_import: bool,
+}
+
+/// Read the settings of the `LoaderContext` passed to `load` or `loadBytes`.
+///
+/// Movies are loaded into the `applicationDomain` of the context. Without
+/// one, they get their own child of the loading movie's domain.
+fn load_settings<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_context: Value<'gc>,
+) -> Result<LoadSettings<'gc>, Error> {
+    let mut application_domain = None;
+    let mut allow_code_import = true;
+
+    if let Value::Object(mut loader_context) = loader_context {
+        let property = |name: &'static str| QName::new(Namespace::public_namespace(), name);
+
+        if let Value::Object(domain) = loader_context.get_property(
+            loader_context,
+            &property("applicationDomain"),
+            activation,
+        )? {
+            application_domain = domain.as_application_domain();
+        }
+
+        // TODO: Policy files and security domains are not supported; all
+        // movies are loaded as if they were trusted.
+        if let Value::Object(_) =
+            loader_context.get_property(loader_context, &property("securityDomain"), activation)?
+        {
+            log::warn!("LoaderContext.securityDomain is not supported");
+        }
+
+        if let Value::Bool(false) =
+            loader_context.get_property(loader_context, &property("allowCodeImport"), activation)?
+        {
+            allow_code_import = false;
+        }
+    }
+
+    let domain = match application_domain {
+        Some(domain) => domain,
+        None => {
+            let movie = activation.context.swf.clone();
+            let current_domain = Avm2::movie_domain(&mut activation.context, movie);
+            Domain::movie_domain(activation.context.gc_context, current_domain)
+        }
+    };
+
+    Ok(LoadSettings {
+        domain,
+        allow_code_import,
+    })
+}
+
+/// Get the `LoaderInfo` of a `Loader`.
+fn loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(this, &content_loader_info_name(), activation)?
+        .coerce_to_object(activation)
+}
+
+/// Implements `Loader.contentLoaderInfo`.
+pub fn content_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(loader_info(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.load`.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut request = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let url = request
+            .get_property(
+                request,
+                &QName::new(Namespace::public_namespace(), "url"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+        let settings = load_settings(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+        let loader_info = loader_info(activation, this)?;
+
+        let fetch = activation
+            .context
+            .navigator
+            .fetch(&url, RequestOptions::get());
+        let process = activation.context.load_manager.load_movie_into_loader_info(
+            activation.context.player.clone().unwrap(),
+            loader_info,
+            settings.domain,
+            fetch,
+            url.to_string(),
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.loadBytes`.
+pub fn load_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bytes = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let data = bytes
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec())
+            .ok_or("TypeError: Error #1034: Parameter bytes must be a ByteArray.")?;
+        let settings = load_settings(activation, args.get(1).cloned().unwrap_or(Value::Null))?;
+
+        // Every movie contains code, as far as Flash Player is concerned.
+        let is_movie =
+            data.starts_with(b"FWS") || data.starts_with(b"CWS") || data.starts_with(b"ZWS");
+        if is_movie && !settings.allow_code_import {
+            return Err("SecurityError: Error #3015: Loader.loadBytes() is not permitted to load content with executable code.".into());
+        }
+
+        let loader_info = loader_info(activation, this)?;
+        let url = activation.context.swf.url().unwrap_or_default().to_string();
+        let fetch: OwnedFuture<Vec<u8>, LoaderError> = Box::pin(async move { Ok(data) });
+        let process = activation.context.load_manager.load_movie_into_loader_info(
+            activation.context.player.clone().unwrap(),
+            loader_info,
+            settings.domain,
+            fetch,
+            url,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "contentLoaderInfo"),
+        Method::from_builtin(content_loader_info),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[("load", load), ("loadBytes", load_bytes)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,213 @@
+//! `flash.display.LoaderInfo` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{DomainObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.display.LoaderInfo`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.LoaderInfo`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Set a public property of a `LoaderInfo`.
+fn set_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut loader_info: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    loader_info.set_property(
+        loader_info,
+        &QName::new(Namespace::public_namespace(), name),
+        value,
+        activation,
+    )
+}
+
+/// Construct an instance of a builtin class.
+fn construct_builtin<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+    let mut constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package(package), name),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let object = proto.construct(activation, args)?;
+    constr.call(Some(object), args, activation, object.proto())?;
+
+    Ok(object)
+}
+
+/// Dispatch a plain `Event` of the given type to a `LoaderInfo`.
+fn dispatch_loader_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_info: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let args = [Value::from(event_type), false.into(), false.into()];
+    let event = construct_builtin(activation, "flash.events", "Event", &args)?;
+
+    dispatch_event(activation, loader_info, event)?;
+
+    Ok(())
+}
+
+/// Construct the `LoaderInfo` of a `Loader`.
+pub fn create_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let loader_info = construct_builtin(activation, "flash.display", "LoaderInfo", &[])?;
+
+    set_info(activation, loader_info, "loader", loader.into())?;
+    set_info(activation, loader_info, "content", Value::Null)?;
+    set_info(activation, loader_info, "applicationDomain", Value::Null)?;
+    set_info(activation, loader_info, "url", Value::Null)?;
+    set_info(activation, loader_info, "bytesLoaded", 0.into())?;
+    set_info(activation, loader_info, "bytesTotal", 0.into())?;
+    set_info(activation, loader_info, "bytes", Value::Null)?;
+    set_info(activation, loader_info, "contentType", Value::Null)?;
+    set_info(activation, loader_info, "width", 0.into())?;
+    set_info(activation, loader_info, "height", 0.into())?;
+
+    Ok(loader_info)
+}
+
+/// Update a `LoaderInfo` whose content has been fetched, then fire its
+/// `open` and `progress` events.
+pub fn loader_info_open<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_info: Object<'gc>,
+    url: &str,
+    length: usize,
+) -> Result<(), Error> {
+    let url = AvmString::new(activation.context.gc_context, url.to_string());
+
+    set_info(activation, loader_info, "url", url.into())?;
+    set_info(activation, loader_info, "bytesLoaded", 0.into())?;
+    set_info(activation, loader_info, "bytesTotal", length.into())?;
+    dispatch_loader_event(activation, loader_info, "open")?;
+
+    // The whole file is fetched at once, so there is only ever one progress event.
+    set_info(activation, loader_info, "bytesLoaded", length.into())?;
+    let args = [
+        "progress".into(),
+        false.into(),
+        false.into(),
+        length.into(),
+        length.into(),
+    ];
+    let event = construct_builtin(activation, "flash.events", "ProgressEvent", &args)?;
+    dispatch_event(activation, loader_info, event)?;
+
+    Ok(())
+}
+
+/// The content that a `Loader` has loaded.
+pub struct LoadedContentInfo<'a> {
+    /// The loaded file.
+    pub data: &'a [u8],
+
+    /// The MIME type of the file.
+    pub content_type: &'static str,
+
+    /// The width of the movie or image, in pixels.
+    pub width: u32,
+
+    /// The height of the movie or image, in pixels.
+    pub height: u32,
+}
+
+/// Update a `LoaderInfo` for content whose scripts, if any, have been loaded
+/// into `domain`, then fire its `init` and `complete` events.
+pub fn loader_info_complete<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_info: Object<'gc>,
+    content: LoadedContentInfo<'_>,
+    domain: Domain<'gc>,
+) -> Result<(), Error> {
+    let domain_object = DomainObject::from_domain(activation, domain)?;
+    let bytes = construct_builtin(activation, "flash.utils", "ByteArray", &[])?;
+    if let Some(mut storage) = bytes.as_bytearray_mut(activation.context.gc_context) {
+        storage.write_bytes(content.data);
+        storage.set_position(0);
+    }
+
+    set_info(activation, loader_info, "bytes", bytes.into())?;
+    set_info(
+        activation,
+        loader_info,
+        "contentType",
+        content.content_type.into(),
+    )?;
+    set_info(activation, loader_info, "width", content.width.into())?;
+    set_info(activation, loader_info, "height", content.height.into())?;
+    set_info(
+        activation,
+        loader_info,
+        "applicationDomain",
+        domain_object.into(),
+    )?;
+
+    dispatch_loader_event(activation, loader_info, "init")?;
+    dispatch_loader_event(activation, loader_info, "complete")
+}
+
+/// Fire the `ioError` event of a `LoaderInfo` whose movie failed to load.
+pub fn loader_info_error<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    loader_info: Object<'gc>,
+    url: &str,
+) -> Result<(), Error> {
+    let url = AvmString::new(activation.context.gc_context, url.to_string());
+    set_info(activation, loader_info, "url", url.into())?;
+
+    // TODO: This should be an `IOErrorEvent`.
+    dispatch_loader_event(activation, loader_info, "ioError")
+}
+
+/// Construct `LoaderInfo`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    Class::new(
+        QName::new(Namespace::package("flash.display"), "LoaderInfo"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    )
+}
@@ -1,12 +1,19 @@
 //! `flash.display.MovieClip` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::display::run_frame_scripts;
+use crate::avm2::globals::flash::display::framelabel::create_frame_label;
+use crate::avm2::globals::flash::display::scene::create_scene;
+use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{MovieClip, Scene, TDisplayObject};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.display.MovieClip`'s instance constructor.
@@ -27,13 +34,361 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Get the movie clip an object represents.
+fn movie_clip<'gc>(this: Option<Object<'gc>>) -> Option<MovieClip<'gc>> {
+    this.and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+}
+
+/// Implements `MovieClip.addFrameScript`.
+///
+/// The arguments are pairs of a zero-based frame index and the function to
+/// run when the playhead enters that frame. A function that is `null`
+/// removes the frame's script, and frames past the end of the timeline are
+/// ignored.
+pub fn add_frame_script<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        for pair in args.chunks_exact(2) {
+            let index = pair[0].coerce_to_u32(activation)?;
+            if index >= u32::from(clip.total_frames()) {
+                continue;
+            }
+
+            let script = match &pair[1] {
+                Value::Null | Value::Undefined => None,
+                script => Some(script.coerce_to_object(activation)?),
+            };
+            clip.set_frame_script(activation.context.gc_context, index as u16 + 1, script);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.currentFrame`'s getter.
+pub fn current_frame<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        return Ok(clip.current_frame().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.totalFrames`'s getter.
+pub fn total_frames<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        return Ok(clip.total_frames().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.framesLoaded`'s getter.
+pub fn frames_loaded<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        return Ok(clip.frames_loaded().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct an `Array` of `FrameLabel`s for the labels within a scene.
+///
+/// The frame numbers count from the start of the scene.
+fn frame_labels<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    clip: MovieClip<'gc>,
+    scene: &Scene,
+) -> Result<Object<'gc>, Error> {
+    let mut array = ArrayStorage::new(0);
+    for (name, frame) in clip.labels_in_scene(scene) {
+        let frame_label = create_frame_label(activation, &name, frame - scene.start + 1)?;
+        array.push(frame_label.into());
+    }
+
+    let array_proto = activation.avm2().prototypes().array;
+    Ok(ArrayObject::from_array(
+        array,
+        array_proto,
+        activation.context.gc_context,
+    ))
+}
+
+/// Construct a `Scene` object for a scene of a clip's timeline.
+fn scene_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    clip: MovieClip<'gc>,
+    scene: &Scene,
+) -> Result<Object<'gc>, Error> {
+    let labels = frame_labels(activation, clip, scene)?;
+    create_scene(activation, &scene.name, labels, scene.length)
+}
+
+/// Implements `MovieClip.currentScene`'s getter.
+pub fn current_scene<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        if let Some(scene) = clip.current_scene() {
+            return Ok(scene_object(activation, clip, &scene)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `MovieClip.scenes`'s getter.
+pub fn scenes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        let mut array = ArrayStorage::new(0);
+        for scene in clip.scenes() {
+            array.push(scene_object(activation, clip, &scene)?.into());
+        }
+
+        let array_proto = activation.avm2().prototypes().array;
+        return Ok(
+            ArrayObject::from_array(array, array_proto, activation.context.gc_context).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.currentLabels`'s getter.
+///
+/// Only the labels within the current scene are included.
+pub fn current_labels<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        if let Some(scene) = clip.current_scene() {
+            return Ok(frame_labels(activation, clip, &scene)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.currentLabel`'s getter.
+pub fn current_label<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(label) = movie_clip(this).and_then(|clip| clip.current_label()) {
+        return Ok(AvmString::new(activation.context.gc_context, label).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `MovieClip.currentFrameLabel`'s getter.
+pub fn current_frame_label<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(label) = movie_clip(this).and_then(|clip| clip.current_frame_label()) {
+        return Ok(AvmString::new(activation.context.gc_context, label).into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `MovieClip.play`.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        clip.play(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.stop`.
+pub fn stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        clip.stop(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Move a clip's playhead to the frame given by the `frame` and `scene`
+/// arguments of a goto.
+///
+/// A frame number counts from the start of the scene, and a string is a
+/// frame label within it. Without a scene, numbers count from the start of
+/// the timeline and labels are looked up in the current scene first.
+fn goto_frame<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    stop: bool,
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        let scene = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            scene => Some(scene.coerce_to_string(activation)?),
+        };
+
+        let frame = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::String(label) => {
+                let frame = match &scene {
+                    Some(scene) => clip.scene_label_to_number(scene, &label),
+                    None => clip.frame_label_to_number(&label),
+                };
+                frame.ok_or_else(|| {
+                    format!(
+                        "ArgumentError: Error #2109: Frame label {} not found.",
+                        label
+                    )
+                })?
+            }
+            frame => {
+                let frame = frame.coerce_to_i32(activation)?.max(1) as u16;
+                match scene {
+                    Some(scene) => clip.scene_frame_to_number(&scene, frame).ok_or_else(|| {
+                        format!("ArgumentError: Error #2108: Scene {} was not found.", scene)
+                    })?,
+                    None => frame,
+                }
+            }
+        };
+
+        clip.goto_frame(&mut activation.context, frame, stop);
+
+        // The script of the frame the playhead landed on runs straight away,
+        // unless this is itself a frame script.
+        run_frame_scripts(&mut activation.context, clip.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.gotoAndPlay`.
+pub fn goto_and_play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    goto_frame(activation, this, args, false)
+}
+
+/// Implements `MovieClip.gotoAndStop`.
+pub fn goto_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    goto_frame(activation, this, args, true)
+}
+
+/// Implements `MovieClip.nextFrame`.
+pub fn next_frame<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        clip.next_frame(&mut activation.context);
+        run_frame_scripts(&mut activation.context, clip.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MovieClip.prevFrame`.
+pub fn prev_frame<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(clip) = movie_clip(this) {
+        clip.prev_frame(&mut activation.context);
+        run_frame_scripts(&mut activation.context, clip.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `MovieClip`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.display"), "MovieClip"),
         Some(QName::new(Namespace::package("flash.display"), "Sprite").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("currentFrame", current_frame),
+        ("totalFrames", total_frames),
+        ("framesLoaded", frames_loaded),
+        ("currentScene", current_scene),
+        ("scenes", scenes),
+        ("currentLabels", current_labels),
+        ("currentLabel", current_label),
+        ("currentFrameLabel", current_frame_label),
+    ];
+
+    for &(name, method) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("addFrameScript", add_frame_script),
+        ("play", play),
+        ("stop", stop),
+        ("gotoAndPlay", goto_and_play),
+        ("gotoAndStop", goto_and_stop),
+        ("nextFrame", next_frame),
+        ("prevFrame", prev_frame),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
 }
@@ -0,0 +1,164 @@
+//! `flash.display.Scene` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The property that stores the name of a `Scene`.
+fn name_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("flash.display:Scene".into()), "name")
+}
+
+/// The property that stores the `FrameLabel`s of a `Scene`.
+fn labels_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("flash.display:Scene".into()), "labels")
+}
+
+/// The property that stores the number of frames in a `Scene`.
+fn num_frames_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.display:Scene".into()),
+        "numFrames",
+    )
+}
+
+/// Construct a new `Scene` with the given array of `FrameLabel`s.
+pub fn create_scene<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &str,
+    labels: Object<'gc>,
+    num_frames: u16,
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+    let mut constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.display"), "Scene"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    let name = AvmString::new(activation.context.gc_context, name.to_string());
+    let args = [name.into(), labels.into(), num_frames.into()];
+    let scene = proto.construct(activation, &args)?;
+    constr.call(Some(scene), &args, activation, scene.proto())?;
+
+    Ok(scene)
+}
+
+/// Implements `flash.display.Scene`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let labels = args.get(1).cloned().unwrap_or(Value::Null);
+        let num_frames = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(this, &name_name(), name.into(), activation)?;
+        this.set_property(this, &labels_name(), labels, activation)?;
+        this.set_property(this, &num_frames_name(), num_frames.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Scene`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Scene.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &name_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Scene.labels`.
+pub fn labels<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &labels_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Scene.numFrames`.
+pub fn num_frames<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &num_frames_name(), activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Scene`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Scene"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("name", name),
+        ("labels", labels),
+        ("numFrames", num_frames),
+    ];
+
+    for &(name, method) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -1,3 +1,15 @@
 //! `flash.events` namespace
 
+pub mod event;
 pub mod eventdispatcher;
+pub mod focusevent;
+pub mod gestureevent;
+pub mod gesturephase;
+pub mod keyboardevent;
+pub mod mouseevent;
+pub mod netstatusevent;
+pub mod progressevent;
+pub mod textevent;
+pub mod timerevent;
+pub mod touchevent;
+pub mod transformgestureevent;
@@ -0,0 +1,311 @@
+//! `flash.events.Event` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.Event`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let bubbles = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+        let cancelable = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.set_event_type(event_type);
+            evt.set_bubbles(bubbles);
+            evt.set_cancelable(cancelable);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.Event`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `bubbles` property's getter
+pub fn bubbles<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_bubbling().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cancelable` property's getter
+pub fn cancelable<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelable().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `currentTarget` property's getter
+pub fn current_target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt
+            .current_target()
+            .map(|o| o.into())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `eventPhase` property's getter
+pub fn event_phase<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        let event_phase: u32 = evt.phase().into();
+        return Ok(event_phase.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `target` property's getter
+pub fn target<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.target().map(|o| o.into()).unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `type` property's getter
+pub fn event_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.event_type().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `clone`
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let proto: Result<Object<'gc>, Error> = this
+            .proto()
+            .ok_or_else(|| "Cannot clone an event without a class".into());
+        let new_event = proto?.construct(activation, &[])?;
+
+        if let (Some(evt), Some(mut new_evt)) = (
+            this.as_event(),
+            new_event.as_event_mut(activation.context.gc_context),
+        ) {
+            new_evt.set_event_type(evt.event_type());
+            new_evt.set_bubbles(evt.is_bubbling());
+            new_evt.set_cancelable(evt.is_cancelable());
+        }
+
+        return Ok(new_event.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `isDefaultPrevented`
+pub fn is_default_prevented<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(evt) = this.as_ref().and_then(|this| this.as_event()) {
+        return Ok(evt.is_cancelled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `preventDefault`
+pub fn prevent_default<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.cancel();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopPropagation`
+pub fn stop_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.stop_propagation();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopImmediatePropagation`
+pub fn stop_immediate_propagation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut evt) = this.as_event_mut(activation.context.gc_context) {
+            evt.stop_immediate_propagation();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return this.to_string(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Event`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "Event"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("bubbles", bubbles),
+        ("cancelable", cancelable),
+        ("currentTarget", current_target),
+        ("eventPhase", event_phase),
+        ("target", target),
+        ("type", event_type),
+    ];
+
+    for &(name, getter) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("clone", clone),
+        ("isDefaultPrevented", is_default_prevented),
+        ("preventDefault", prevent_default),
+        ("stopPropagation", stop_propagation),
+        ("stopImmediatePropagation", stop_immediate_propagation),
+        ("toString", to_string),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let constants = &[
+        ("ACTIVATE", "activate"),
+        ("ADDED", "added"),
+        ("ADDED_TO_STAGE", "addedToStage"),
+        ("CANCEL", "cancel"),
+        ("CHANGE", "change"),
+        ("CLOSE", "close"),
+        ("COMPLETE", "complete"),
+        ("DEACTIVATE", "deactivate"),
+        ("ENTER_FRAME", "enterFrame"),
+        ("EXIT_FRAME", "exitFrame"),
+        ("FRAME_CONSTRUCTED", "frameConstructed"),
+        ("INIT", "init"),
+        ("OPEN", "open"),
+        ("REMOVED", "removed"),
+        ("REMOVED_FROM_STAGE", "removedFromStage"),
+        ("RENDER", "render"),
+        ("RESIZE", "resize"),
+        ("SCROLL", "scroll"),
+        ("SELECT", "select"),
+        ("UNLOAD", "unload"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    class
+}
@@ -2,9 +2,14 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
-use crate::avm2::method::Method;
+use crate::avm2::display::register_broadcast_listener;
+use crate::avm2::events::{
+    dispatch_event as dispatch_event_internal, display_ancestors, BROADCAST_EVENTS,
+};
+use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
@@ -27,13 +32,196 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `EventDispatcher.addEventListener`.
+pub fn add_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+        let priority = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        if BROADCAST_EVENTS.contains(&event_type.as_str()) {
+            register_broadcast_listener(activation, this);
+        }
+
+        //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
+        if let Some(mut dispatch_list) = this.as_dispatch_mut(activation.context.gc_context) {
+            dispatch_list.add_event_listener(event_type, priority, listener, use_capture);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.removeEventListener`.
+pub fn remove_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let listener = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let use_capture = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        if let Some(mut dispatch_list) = this.as_dispatch_mut(activation.context.gc_context) {
+            dispatch_list.remove_event_listener(event_type, listener, use_capture);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `EventDispatcher.hasEventListener`.
+pub fn has_event_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(dispatch_list) = this.as_dispatch() {
+            return Ok(dispatch_list.has_event_listener(event_type).into());
+        }
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `EventDispatcher.willTrigger`.
+pub fn will_trigger<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let event_type = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let mut targets = vec![this];
+        targets.extend(display_ancestors(activation, this)?);
+
+        for target in targets {
+            if let Some(dispatch_list) = target.as_dispatch() {
+                if dispatch_list.has_event_listener(event_type) {
+                    return Ok(true.into());
+                }
+            }
+        }
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `EventDispatcher.dispatchEvent`.
+pub fn dispatch_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+
+        if event.as_event().is_none() {
+            return Err("Dispatched Events must be subclasses of Event.".into());
+        }
+
+        // Events that have already been dispatched are cloned, using whatever
+        // `clone` method the event's class provides, so that the original
+        // event's target and phase are left intact.
+        let has_target = event.as_event().unwrap().target().is_some();
+        if has_target {
+            let clone_name = QName::new(Namespace::public_namespace(), "clone");
+            let base_proto = event.get_base_proto(&clone_name)?;
+            let clone_method = event
+                .get_property(event, &clone_name, activation)?
+                .coerce_to_object(activation)?;
+
+            event = clone_method
+                .call(Some(event), &[], activation, base_proto)?
+                .coerce_to_object(activation)?;
+
+            if event.as_event().is_none() {
+                return Err("Cloned Events must be subclasses of Event.".into());
+            }
+        }
+
+        return Ok(dispatch_event_internal(activation, this, event)?.into());
+    }
+
+    Ok(false.into())
+}
+
 /// Construct `EventDispatcher`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
-    Class::new(
+    let class = Class::new(
         QName::new(Namespace::package("flash.events"), "EventDispatcher"),
         Some(QName::new(Namespace::public_namespace(), "Object").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
-    )
+    );
+
+    let mut write = class.write(mc);
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("addEventListener", add_event_listener),
+        ("removeEventListener", remove_event_listener),
+        ("hasEventListener", has_event_listener),
+        ("willTrigger", will_trigger),
+        ("dispatchEvent", dispatch_event),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
 }
@@ -0,0 +1,110 @@
+//! `flash.events.FocusEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.FocusEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Unlike most events, focus events bubble by default.
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or_else(|| true.into()),
+        args.get(2).cloned().unwrap_or_else(|| false.into()),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+
+    if let Some(mut this) = this {
+        let related_object = args.get(3).cloned().unwrap_or(Value::Null);
+        let shift_key = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+        let key_code = args
+            .get(5)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_u32(activation)?;
+
+        let properties = [
+            ("relatedObject", related_object),
+            ("shiftKey", shift_key.into()),
+            ("keyCode", key_code.into()),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.FocusEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `FocusEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "FocusEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let constants: &[(&str, &'static str)] = &[
+        ("FOCUS_IN", "focusIn"),
+        ("FOCUS_OUT", "focusOut"),
+        ("KEY_FOCUS_CHANGE", "keyFocusChange"),
+        ("MOUSE_FOCUS_CHANGE", "mouseFocusChange"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("relatedObject", "Object", Value::Null),
+        ("shiftKey", "Boolean", false.into()),
+        ("keyCode", "uint", 0.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    class
+}
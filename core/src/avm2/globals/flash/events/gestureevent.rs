@@ -0,0 +1,104 @@
+//! `flash.events.GestureEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.GestureEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Gesture events bubble by default.
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or_else(|| true.into()),
+        args.get(2).cloned().unwrap_or_else(|| false.into()),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+
+    if let Some(mut this) = this {
+        let arg = |index: usize, default: Value<'gc>| args.get(index).cloned().unwrap_or(default);
+
+        let phase = arg(3, Value::Null);
+        let local_x = arg(4, 0.into()).coerce_to_number(activation)?;
+        let local_y = arg(5, 0.into()).coerce_to_number(activation)?;
+        let ctrl_key = arg(6, false.into()).coerce_to_boolean();
+        let alt_key = arg(7, false.into()).coerce_to_boolean();
+        let shift_key = arg(8, false.into()).coerce_to_boolean();
+
+        let properties = [
+            ("phase", phase),
+            ("localX", local_x.into()),
+            ("localY", local_y.into()),
+            ("ctrlKey", ctrl_key.into()),
+            ("altKey", alt_key.into()),
+            ("shiftKey", shift_key.into()),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.GestureEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GestureEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "GestureEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "GESTURE_TWO_FINGER_TAP"),
+        QName::new(Namespace::public_namespace(), "String").into(),
+        Some("gestureTwoFingerTap".into()),
+    ));
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("phase", "String", Value::Null),
+        ("localX", "Number", 0.into()),
+        ("localY", "Number", 0.into()),
+        ("ctrlKey", "Boolean", false.into()),
+        ("altKey", "Boolean", false.into()),
+        ("shiftKey", "Boolean", false.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,132 @@
+//! `flash.events.KeyboardEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.KeyboardEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Keyboard events bubble by default.
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or_else(|| true.into()),
+        args.get(2).cloned().unwrap_or_else(|| false.into()),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+
+    if let Some(mut this) = this {
+        let mut codes = [0; 3];
+        for (i, code) in codes.iter_mut().enumerate() {
+            *code = args
+                .get(3 + i)
+                .cloned()
+                .unwrap_or_else(|| 0.into())
+                .coerce_to_u32(activation)?;
+        }
+        let mut modifiers = [false; 3];
+        for (i, modifier) in modifiers.iter_mut().enumerate() {
+            *modifier = args
+                .get(6 + i)
+                .cloned()
+                .unwrap_or_else(|| false.into())
+                .coerce_to_boolean();
+        }
+
+        let properties: [(&str, Value<'gc>); 6] = [
+            ("charCode", codes[0].into()),
+            ("keyCode", codes[1].into()),
+            ("keyLocation", codes[2].into()),
+            ("ctrlKey", modifiers[0].into()),
+            ("altKey", modifiers[1].into()),
+            ("shiftKey", modifiers[2].into()),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.KeyboardEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `KeyboardEvent.updateAfterEvent`.
+///
+/// The stage is always rendered on the next frame, so there is nothing to do.
+pub fn update_after_event<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `KeyboardEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "KeyboardEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let constants: &[(&str, &'static str)] = &[("KEY_DOWN", "keyDown"), ("KEY_UP", "keyUp")];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("charCode", "uint", 0.into()),
+        ("keyCode", "uint", 0.into()),
+        ("keyLocation", "uint", 0.into()),
+        ("ctrlKey", "Boolean", false.into()),
+        ("altKey", "Boolean", false.into()),
+        ("shiftKey", "Boolean", false.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    class
+}
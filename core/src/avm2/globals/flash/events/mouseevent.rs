@@ -0,0 +1,139 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Unlike most events, mouse events bubble by default.
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or_else(|| true.into()),
+        args.get(2).cloned().unwrap_or_else(|| false.into()),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+
+    if let Some(mut this) = this {
+        let local_x = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| f64::NAN.into())
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| f64::NAN.into())
+            .coerce_to_number(activation)?;
+        let related_object = args.get(5).cloned().unwrap_or(Value::Null);
+        let mut flags = [false; 4];
+        for (i, flag) in flags.iter_mut().enumerate() {
+            *flag = args
+                .get(6 + i)
+                .cloned()
+                .unwrap_or_else(|| false.into())
+                .coerce_to_boolean();
+        }
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        let properties = [
+            ("localX", local_x.into()),
+            ("localY", local_y.into()),
+            ("relatedObject", related_object),
+            ("ctrlKey", flags[0].into()),
+            ("altKey", flags[1].into()),
+            ("shiftKey", flags[2].into()),
+            ("buttonDown", flags[3].into()),
+            ("delta", delta.into()),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let constants: &[(&str, &'static str)] = &[
+        ("CLICK", "click"),
+        ("DOUBLE_CLICK", "doubleClick"),
+        ("MOUSE_DOWN", "mouseDown"),
+        ("MOUSE_MOVE", "mouseMove"),
+        ("MOUSE_OUT", "mouseOut"),
+        ("MOUSE_OVER", "mouseOver"),
+        ("MOUSE_UP", "mouseUp"),
+        ("MOUSE_WHEEL", "mouseWheel"),
+        ("ROLL_OUT", "rollOut"),
+        ("ROLL_OVER", "rollOver"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("localX", "Number", f64::NAN.into()),
+        ("localY", "Number", f64::NAN.into()),
+        ("relatedObject", "Object", Value::Null),
+        ("ctrlKey", "Boolean", false.into()),
+        ("altKey", "Boolean", false.into()),
+        ("shiftKey", "Boolean", false.into()),
+        ("buttonDown", "Boolean", false.into()),
+        ("delta", "int", 0.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,132 @@
+//! `flash.events.TouchEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.TouchEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // Like mouse events, touch events bubble by default.
+    let event_args = [
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        args.get(1).cloned().unwrap_or_else(|| true.into()),
+        args.get(2).cloned().unwrap_or_else(|| false.into()),
+    ];
+    event::instance_init(activation, this, &event_args)?;
+
+    if let Some(mut this) = this {
+        let arg = |index: usize, default: Value<'gc>| args.get(index).cloned().unwrap_or(default);
+
+        let touch_point_id = arg(3, 0.into()).coerce_to_i32(activation)?;
+        let is_primary_touch_point = arg(4, false.into()).coerce_to_boolean();
+        let local_x = arg(5, f64::NAN.into()).coerce_to_number(activation)?;
+        let local_y = arg(6, f64::NAN.into()).coerce_to_number(activation)?;
+        let size_x = arg(7, f64::NAN.into()).coerce_to_number(activation)?;
+        let size_y = arg(8, f64::NAN.into()).coerce_to_number(activation)?;
+        let pressure = arg(9, f64::NAN.into()).coerce_to_number(activation)?;
+        let related_object = arg(10, Value::Null);
+        let ctrl_key = arg(11, false.into()).coerce_to_boolean();
+        let alt_key = arg(12, false.into()).coerce_to_boolean();
+        let shift_key = arg(13, false.into()).coerce_to_boolean();
+
+        let properties = [
+            ("touchPointID", touch_point_id.into()),
+            ("isPrimaryTouchPoint", is_primary_touch_point.into()),
+            ("localX", local_x.into()),
+            ("localY", local_y.into()),
+            ("sizeX", size_x.into()),
+            ("sizeY", size_y.into()),
+            ("pressure", pressure.into()),
+            ("relatedObject", related_object),
+            ("ctrlKey", ctrl_key.into()),
+            ("altKey", alt_key.into()),
+            ("shiftKey", shift_key.into()),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                value.clone(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.TouchEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `TouchEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "TouchEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let constants: &[(&str, &'static str)] = &[
+        ("TOUCH_BEGIN", "touchBegin"),
+        ("TOUCH_END", "touchEnd"),
+        ("TOUCH_MOVE", "touchMove"),
+        ("TOUCH_OUT", "touchOut"),
+        ("TOUCH_OVER", "touchOver"),
+        ("TOUCH_ROLL_OUT", "touchRollOut"),
+        ("TOUCH_ROLL_OVER", "touchRollOver"),
+        ("TOUCH_TAP", "touchTap"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    let slots: &[(&str, &str, Value<'gc>)] = &[
+        ("touchPointID", "int", 0.into()),
+        ("isPrimaryTouchPoint", "Boolean", false.into()),
+        ("localX", "Number", f64::NAN.into()),
+        ("localY", "Number", f64::NAN.into()),
+        ("sizeX", "Number", f64::NAN.into()),
+        ("sizeY", "Number", f64::NAN.into()),
+        ("pressure", "Number", f64::NAN.into()),
+        ("relatedObject", "Object", Value::Null),
+        ("ctrlKey", "Boolean", false.into()),
+        ("altKey", "Boolean", false.into()),
+        ("shiftKey", "Boolean", false.into()),
+    ];
+
+    for (name, type_name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), *name),
+            QName::new(Namespace::public_namespace(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,115 @@
+//! `flash.events.TransformGestureEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::events::gestureevent;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.TransformGestureEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    // The transform is given between the position and the modifier keys.
+    let arg = |index: usize, default: Value<'gc>| args.get(index).cloned().unwrap_or(default);
+    let gesture_args = [
+        arg(0, Value::Undefined),
+        arg(1, true.into()),
+        arg(2, false.into()),
+        arg(3, Value::Null),
+        arg(4, 0.into()),
+        arg(5, 0.into()),
+        arg(11, false.into()),
+        arg(12, false.into()),
+        arg(13, false.into()),
+    ];
+    gestureevent::instance_init(activation, this, &gesture_args)?;
+
+    if let Some(mut this) = this {
+        let scale_x = arg(6, 1.into()).coerce_to_number(activation)?;
+        let scale_y = arg(7, 1.into()).coerce_to_number(activation)?;
+        let rotation = arg(8, 0.into()).coerce_to_number(activation)?;
+        let offset_x = arg(9, 0.into()).coerce_to_number(activation)?;
+        let offset_y = arg(10, 0.into()).coerce_to_number(activation)?;
+
+        let properties = [
+            ("scaleX", scale_x),
+            ("scaleY", scale_y),
+            ("rotation", rotation),
+            ("offsetX", offset_x),
+            ("offsetY", offset_y),
+        ];
+        for (name, value) in properties.iter() {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public_namespace(), *name),
+                (*value).into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.TransformGestureEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `TransformGestureEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "TransformGestureEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "GestureEvent").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let constants: &[(&str, &'static str)] = &[
+        ("GESTURE_PAN", "gesturePan"),
+        ("GESTURE_ROTATE", "gestureRotate"),
+        ("GESTURE_SWIPE", "gestureSwipe"),
+        ("GESTURE_ZOOM", "gestureZoom"),
+    ];
+
+    for &(name, value) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "String").into(),
+            Some(value.into()),
+        ));
+    }
+
+    let slots: &[(&str, f64)] = &[
+        ("scaleX", 1.0),
+        ("scaleY", 1.0),
+        ("rotation", 0.0),
+        ("offsetX", 0.0),
+        ("offsetY", 0.0),
+    ];
+
+    for &(name, default) in slots {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(default.into()),
+        ));
+    }
+
+    class
+}
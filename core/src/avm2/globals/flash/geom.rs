@@ -0,0 +1,128 @@
+//! `flash.geom` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+pub mod colortransform;
+pub mod matrix;
+pub mod matrix3d;
+pub mod perspectiveprojection;
+pub mod point;
+pub mod rectangle;
+pub mod transform;
+pub mod vector3d;
+
+/// Construct an instance of a `flash.geom` class, as `new` would.
+fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    class_name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    let mut globals = activation.avm2().globals();
+    let constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.geom"), class_name),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    construct_with(activation, constr, args)
+}
+
+/// Construct an instance of the same class as `object`.
+///
+/// This is used by `clone` so that subclasses of the geometry classes get
+/// copies of their own class back.
+fn construct_like<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    let constr = object
+        .get_property(
+            object,
+            &QName::new(Namespace::public_namespace(), "constructor"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    construct_with(activation, constr, args)
+}
+
+fn construct_with<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut constr: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Object<'gc>, Error> {
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+
+    let object = proto.construct(activation, args)?;
+    constr.call(Some(object), args, activation, object.proto())?;
+
+    Ok(object)
+}
+
+/// Read a numeric property of a geometry object.
+fn get_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    object
+        .get_property(
+            object,
+            &QName::new(Namespace::public_namespace(), name),
+            activation,
+        )?
+        .coerce_to_number(activation)
+}
+
+/// Write a numeric property of a geometry object.
+fn set_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+    value: f64,
+) -> Result<(), Error> {
+    object.set_property(
+        object,
+        &QName::new(Namespace::public_namespace(), name),
+        value.into(),
+        activation,
+    )
+}
+
+/// Read a numeric argument, using `default` if it was not passed.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        Some(value) => value.coerce_to_number(activation),
+        None => Ok(default),
+    }
+}
+
+/// Read an object argument.
+fn object_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Object<'gc>, Error> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)
+}
@@ -0,0 +1,259 @@
+//! `flash.geom.ColorTransform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::{
+    construct, get_number, number_arg, object_arg, set_number,
+};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::color_transform::ColorTransform;
+use gc_arena::{GcCell, MutationContext};
+
+const COMPONENTS: [&str; 8] = [
+    "redMultiplier",
+    "greenMultiplier",
+    "blueMultiplier",
+    "alphaMultiplier",
+    "redOffset",
+    "greenOffset",
+    "blueOffset",
+    "alphaOffset",
+];
+
+const IDENTITY: [f64; 8] = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+
+/// Read the multipliers and offsets of a `ColorTransform`.
+fn components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: Object<'gc>,
+) -> Result<[f64; 8], Error> {
+    let mut result = [0.0; 8];
+    for (value, name) in result.iter_mut().zip(COMPONENTS.iter()) {
+        *value = get_number(activation, color_transform, name)?;
+    }
+    Ok(result)
+}
+
+/// Overwrite the multipliers and offsets of a `ColorTransform`.
+fn set_components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: Object<'gc>,
+    values: [f64; 8],
+) -> Result<(), Error> {
+    for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+        set_number(activation, color_transform, name, *value)?;
+    }
+    Ok(())
+}
+
+/// Construct a new `ColorTransform` with the values of a display object's
+/// color transform.
+///
+/// Display objects store their offsets as fractions of 255.
+pub fn color_transform_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: ColorTransform,
+) -> Result<Object<'gc>, Error> {
+    let args = [
+        f64::from(color_transform.r_mult).into(),
+        f64::from(color_transform.g_mult).into(),
+        f64::from(color_transform.b_mult).into(),
+        f64::from(color_transform.a_mult).into(),
+        f64::from(color_transform.r_add * 255.0).into(),
+        f64::from(color_transform.g_add * 255.0).into(),
+        f64::from(color_transform.b_add * 255.0).into(),
+        f64::from(color_transform.a_add * 255.0).into(),
+    ];
+    construct(activation, "ColorTransform", &args)
+}
+
+/// Read a `ColorTransform` as a display object's color transform.
+pub fn object_to_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    color_transform: Object<'gc>,
+) -> Result<ColorTransform, Error> {
+    let [r_mult, g_mult, b_mult, a_mult, r_add, g_add, b_add, a_add] =
+        components(activation, color_transform)?;
+    Ok(ColorTransform {
+        r_mult: r_mult as f32,
+        g_mult: g_mult as f32,
+        b_mult: b_mult as f32,
+        a_mult: a_mult as f32,
+        r_add: r_add as f32 / 255.0,
+        g_add: g_add as f32 / 255.0,
+        b_add: b_add as f32 / 255.0,
+        a_add: a_add as f32 / 255.0,
+    })
+}
+
+/// Implements `flash.geom.ColorTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = IDENTITY;
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = number_arg(activation, args, i, *value)?;
+        }
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.ColorTransform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.color`'s getter.
+///
+/// This is the RGB offsets packed into a single color; the multipliers are
+/// ignored.
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, _, _, _, r, g, b, _] = components(activation, this)?;
+        let r = Value::from(r).coerce_to_u32(activation)?;
+        let g = Value::from(g).coerce_to_u32(activation)?;
+        let b = Value::from(b).coerce_to_u32(activation)?;
+        return Ok(((r << 16) | (g << 8) | b).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.color`'s setter.
+///
+/// This tints the color completely: the RGB multipliers become zero, and the
+/// offsets become the components of the color. Alpha is left alone.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let color = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let [_, _, _, a_mult, _, _, _, a_add] = components(activation, this)?;
+        let r = f64::from((color >> 16) & 0xFF);
+        let g = f64::from((color >> 8) & 0xFF);
+        let b = f64::from(color & 0xFF);
+        set_components(activation, this, [0.0, 0.0, 0.0, a_mult, r, g, b, a_add])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.concat`.
+///
+/// The result is the same as applying `second`, and then this transform.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let second = object_arg(activation, args, 0)?;
+        let [r2, g2, b2, a2, r_add2, g_add2, b_add2, a_add2] = components(activation, second)?;
+        let [r, g, b, a, r_add, g_add, b_add, a_add] = components(activation, this)?;
+        set_components(
+            activation,
+            this,
+            [
+                r * r2,
+                g * g2,
+                b * b2,
+                a * a2,
+                r * r_add2 + r_add,
+                g * g_add2 + g_add,
+                b * b_add2 + b_add,
+                a * a_add2 + a_add,
+            ],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let values = components(activation, this)?;
+        let mut parts = Vec::with_capacity(values.len());
+        for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+            let value = Value::from(*value).coerce_to_string(activation)?;
+            parts.push(format!("{}={}", name, value));
+        }
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "ColorTransform"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (&name, &default) in COMPONENTS.iter().zip(IDENTITY.iter()) {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(default.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "color"),
+        Method::from_builtin(color),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "color"),
+        Method::from_builtin(set_color),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[("concat", concat), ("toString", to_string)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,536 @@
+//! `flash.geom.Matrix` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::point::{coords_arg, create_point};
+use crate::avm2::globals::flash::geom::vector3d::components_arg;
+use crate::avm2::globals::flash::geom::{
+    construct, construct_like, get_number, number_arg, object_arg, set_number,
+};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+const COMPONENTS: [&str; 6] = ["a", "b", "c", "d", "tx", "ty"];
+
+const IDENTITY: [f64; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// The size of the square that gradients are defined in, in pixels.
+///
+/// Gradient fills span -16384 to 16384 twips, so a gradient box matrix
+/// scales that square down to the requested size.
+const GRADIENT_SIZE: f64 = 1638.4;
+
+/// Read the `a`, `b`, `c`, `d`, `tx` and `ty` components of a `Matrix`.
+pub fn components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: Object<'gc>,
+) -> Result<[f64; 6], Error> {
+    let mut result = [0.0; 6];
+    for (value, name) in result.iter_mut().zip(COMPONENTS.iter()) {
+        *value = get_number(activation, matrix, name)?;
+    }
+    Ok(result)
+}
+
+/// Overwrite the components of a `Matrix`.
+fn set_components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: Object<'gc>,
+    values: [f64; 6],
+) -> Result<(), Error> {
+    for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+        set_number(activation, matrix, name, *value)?;
+    }
+    Ok(())
+}
+
+/// Construct a new `Matrix` with the components of a display object's matrix.
+pub fn matrix_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: Matrix,
+) -> Result<Object<'gc>, Error> {
+    let args = [
+        f64::from(matrix.a).into(),
+        f64::from(matrix.b).into(),
+        f64::from(matrix.c).into(),
+        f64::from(matrix.d).into(),
+        matrix.tx.to_pixels().into(),
+        matrix.ty.to_pixels().into(),
+    ];
+    construct(activation, "Matrix", &args)
+}
+
+/// Read a `Matrix` as a display object's matrix.
+pub fn object_to_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    matrix: Object<'gc>,
+) -> Result<Matrix, Error> {
+    let [a, b, c, d, tx, ty] = components(activation, matrix)?;
+    Ok(Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    })
+}
+
+/// Multiply two matrices, such that the result applies `first` and then
+/// `second`.
+fn multiply(first: [f64; 6], second: [f64; 6]) -> [f64; 6] {
+    let [a, b, c, d, tx, ty] = first;
+    let [a2, b2, c2, d2, tx2, ty2] = second;
+    [
+        a * a2 + b * c2,
+        a * b2 + b * d2,
+        c * a2 + d * c2,
+        c * b2 + d * d2,
+        tx * a2 + ty * c2 + tx2,
+        tx * b2 + ty * d2 + ty2,
+    ]
+}
+
+/// Build the matrix for `createBox`: a scale, then a rotation, then a
+/// translation.
+fn box_matrix(scale_x: f64, scale_y: f64, rotation: f64, tx: f64, ty: f64) -> [f64; 6] {
+    let (sin, cos) = rotation.sin_cos();
+    [
+        cos * scale_x,
+        sin * scale_y,
+        -sin * scale_x,
+        cos * scale_y,
+        tx,
+        ty,
+    ]
+}
+
+/// Implements `flash.geom.Matrix`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = IDENTITY;
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = number_arg(activation, args, i, *value)?;
+        }
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_components(activation, this, IDENTITY)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.invert`.
+///
+/// A matrix that cannot be inverted is reset to the identity.
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [a, b, c, d, tx, ty] = components(activation, this)?;
+        let inverted = if b == 0.0 && c == 0.0 {
+            let (a, d) = (1.0 / a, 1.0 / d);
+            [a, 0.0, 0.0, d, -a * tx, -d * ty]
+        } else {
+            let det = a * d - b * c;
+            if det == 0.0 {
+                IDENTITY
+            } else {
+                let (a, b, c, d) = (d / det, -b / det, -c / det, a / det);
+                [a, b, c, d, -(a * tx + c * ty), -(b * tx + d * ty)]
+            }
+        };
+        set_components(activation, this, inverted)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.concat`.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = object_arg(activation, args, 0)?;
+        let other = components(activation, other)?;
+        let values = components(activation, this)?;
+        set_components(activation, this, multiply(values, other))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.translate`.
+pub fn translate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+        let [a, b, c, d, tx, ty] = components(activation, this)?;
+        set_components(activation, this, [a, b, c, d, tx + dx, ty + dy])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.scale`.
+pub fn scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let sx = number_arg(activation, args, 0, f64::NAN)?;
+        let sy = number_arg(activation, args, 1, f64::NAN)?;
+        let values = components(activation, this)?;
+        let scale = [sx, 0.0, 0.0, sy, 0.0, 0.0];
+        set_components(activation, this, multiply(values, scale))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.rotate`.
+pub fn rotate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let angle = number_arg(activation, args, 0, f64::NAN)?;
+        let values = components(activation, this)?;
+        let rotation = box_matrix(1.0, 1.0, angle, 0.0, 0.0);
+        set_components(activation, this, multiply(values, rotation))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.createBox`.
+pub fn create_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0, f64::NAN)?;
+        let scale_y = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+        let values = box_matrix(scale_x, scale_y, rotation, tx, ty);
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.createGradientBox`.
+///
+/// The gradient square is scaled to `width` by `height`, and then moved so
+/// that its top-left corner sits at `tx`, `ty`.
+pub fn create_gradient_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let width = number_arg(activation, args, 0, f64::NAN)?;
+        let height = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+        let values = box_matrix(
+            width / GRADIENT_SIZE,
+            height / GRADIENT_SIZE,
+            rotation,
+            tx + width / 2.0,
+            ty + height / 2.0,
+        );
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.transformPoint`.
+pub fn transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [a, b, c, d, tx, ty] = components(activation, this)?;
+        let (x, y) = coords_arg(activation, args, 0)?;
+        return Ok(create_point(activation, (a * x + c * y + tx, b * x + d * y + ty))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.deltaTransformPoint`, which ignores the translation.
+pub fn delta_transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [a, b, c, d, _, _] = components(activation, this)?;
+        let (x, y) = coords_arg(activation, args, 0)?;
+        return Ok(create_point(activation, (a * x + c * y, b * x + d * y))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The indices of the components in each row of the matrix, when it is
+/// viewed as a 3x3 matrix with an implied `0, 0, 1` bottom row.
+const ROWS: [[usize; 3]; 2] = [[0, 2, 4], [1, 3, 5]];
+
+/// The indices of the components in each column of the matrix.
+const COLUMNS: [[usize; 2]; 3] = [[0, 1], [2, 3], [4, 5]];
+
+/// Implements `Matrix.copyRowTo`.
+pub fn copy_row_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let row = number_arg(activation, args, 0, 0.0)? as usize;
+        let vector = object_arg(activation, args, 1)?;
+        let values = components(activation, this)?;
+        let row = match ROWS.get(row) {
+            Some(&[x, y, z]) => [values[x], values[y], values[z]],
+            None => [0.0, 0.0, 1.0],
+        };
+        for (value, name) in row.iter().zip(["x", "y", "z"].iter()) {
+            set_number(activation, vector, name, *value)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.copyRowFrom`.
+pub fn copy_row_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let row = number_arg(activation, args, 0, 0.0)? as usize;
+        let [x, y, z, _] = components_arg(activation, args, 1)?;
+        if let Some(indices) = ROWS.get(row) {
+            let mut values = components(activation, this)?;
+            for (index, value) in indices.iter().zip([x, y, z].iter()) {
+                values[*index] = *value;
+            }
+            set_components(activation, this, values)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.copyColumnTo`.
+pub fn copy_column_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let column = number_arg(activation, args, 0, 0.0)? as usize;
+        let vector = object_arg(activation, args, 1)?;
+        let values = components(activation, this)?;
+        if let Some(&[x, y]) = COLUMNS.get(column) {
+            let z = if column == 2 { 1.0 } else { 0.0 };
+            for (value, name) in [values[x], values[y], z].iter().zip(["x", "y", "z"].iter()) {
+                set_number(activation, vector, name, *value)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.copyColumnFrom`.
+pub fn copy_column_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let column = number_arg(activation, args, 0, 0.0)? as usize;
+        let [x, y, _, _] = components_arg(activation, args, 1)?;
+        if let Some(&[xi, yi]) = COLUMNS.get(column) {
+            let mut values = components(activation, this)?;
+            values[xi] = x;
+            values[yi] = y;
+            set_components(activation, this, values)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = [0.0; 6];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = number_arg(activation, args, i, f64::NAN)?;
+        }
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = object_arg(activation, args, 0)?;
+        let values = components(activation, source)?;
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let args: Vec<Value<'gc>> = components(activation, this)?
+            .iter()
+            .map(|v| (*v).into())
+            .collect();
+        return Ok(construct_like(activation, this, &args)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let values = components(activation, this)?;
+        let mut parts = Vec::with_capacity(values.len());
+        for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+            let value = Value::from(*value).coerce_to_string(activation)?;
+            parts.push(format!("{}={}", name, value));
+        }
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Matrix`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (&name, &default) in COMPONENTS.iter().zip(IDENTITY.iter()) {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(default.into()),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("identity", identity),
+        ("invert", invert),
+        ("concat", concat),
+        ("translate", translate),
+        ("scale", scale),
+        ("rotate", rotate),
+        ("createBox", create_box),
+        ("createGradientBox", create_gradient_box),
+        ("transformPoint", transform_point),
+        ("deltaTransformPoint", delta_transform_point),
+        ("copyRowTo", copy_row_to),
+        ("copyRowFrom", copy_row_from),
+        ("copyColumnTo", copy_column_to),
+        ("copyColumnFrom", copy_column_from),
+        ("setTo", set_to),
+        ("copyFrom", copy_from),
+        ("clone", clone),
+        ("toString", to_string),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
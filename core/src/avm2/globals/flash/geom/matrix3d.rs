@@ -0,0 +1,470 @@
+//! `flash.geom.Matrix3D` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::construct;
+use crate::avm2::globals::flash::geom::vector3d::{components_arg, create_vector3d};
+use crate::avm2::matrix3d::Matrix3D;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Read 16 numbers from an indexable object, such as a `Vector.<Number>`.
+fn read_raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<[f64; 16], Error> {
+    let mut source = value.coerce_to_object(activation)?;
+    let mut raw = [0.0; 16];
+    for (i, value) in raw.iter_mut().enumerate() {
+        let name =
+            QName::dynamic_name(AvmString::new(activation.context.gc_context, i.to_string()));
+        *value = source
+            .get_property(source, &name, activation)?
+            .coerce_to_number(activation)?;
+    }
+    Ok(raw)
+}
+
+/// Read a `Matrix3D` argument.
+fn matrix_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Matrix3D, Error> {
+    let object = args
+        .get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let matrix = object.as_matrix3d().map(|matrix| *matrix);
+    matrix.ok_or_else(|| "TypeError: Error #1034: Type Coercion failed: expected Matrix3D".into())
+}
+
+/// Read the `x`, `y` and `z` arguments shared by the translation and scale
+/// methods.
+fn xyz_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<[f64; 3], Error> {
+    let mut values = [0.0; 3];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = args
+            .get(i)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+    }
+    Ok(values)
+}
+
+/// Read the arguments of `appendRotation` and `prependRotation`.
+fn rotation_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Matrix3D, Error> {
+    let degrees = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+    let [x, y, z, _] = components_arg(activation, args, 1)?;
+    let pivot = match args.get(2) {
+        None | Some(Value::Undefined) | Some(Value::Null) => [0.0; 3],
+        Some(_) => {
+            let [x, y, z, _] = components_arg(activation, args, 2)?;
+            [x, y, z]
+        }
+    };
+
+    Ok(Matrix3D::rotation(degrees, [x, y, z], pivot))
+}
+
+/// Apply `f` to the matrix held by `this`.
+fn with_matrix<'gc, F>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    f: F,
+) -> Result<Value<'gc>, Error>
+where
+    F: FnOnce(&mut Matrix3D) -> Value<'gc>,
+{
+    if let Some(mut matrix) = this
+        .as_ref()
+        .and_then(|this| this.as_matrix3d_mut(activation.context.gc_context))
+    {
+        return Ok(f(&mut matrix));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(Value::Undefined),
+        Some(value) => {
+            let raw = read_raw_data(activation, value.clone())?;
+            with_matrix(activation, this, |matrix| {
+                *matrix = Matrix3D::from_raw(raw);
+                Value::Undefined
+            })
+        }
+    }
+}
+
+/// Implements `flash.geom.Matrix3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix3D.rawData`'s getter.
+///
+/// TODO: This should return a `Vector.<Number>`, but vectors are not yet
+/// supported, so an array-like object is returned instead.
+pub fn raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let raw = match this.as_ref().and_then(|this| this.as_matrix3d()) {
+        Some(matrix) => *matrix.raw(),
+        None => return Ok(Value::Undefined),
+    };
+
+    let mc = activation.context.gc_context;
+    let mut result = ScriptObject::object(mc, activation.avm2().prototypes().object);
+    for (i, value) in raw.iter().enumerate() {
+        let name = QName::dynamic_name(AvmString::new(mc, i.to_string()));
+        result.install_dynamic_property(mc, name, (*value).into())?;
+    }
+    result.install_dynamic_property(mc, QName::dynamic_name("length"), 16u32.into())?;
+
+    Ok(result.into())
+}
+
+/// Implements `Matrix3D.rawData`'s setter.
+pub fn set_raw_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let raw = read_raw_data(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+    with_matrix(activation, this, |matrix| {
+        *matrix = Matrix3D::from_raw(raw);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.determinant`.
+pub fn determinant<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    with_matrix(activation, this, |matrix| matrix.determinant().into())
+}
+
+/// Implements `Matrix3D.position`'s getter.
+pub fn position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let raw = match this.as_ref().and_then(|this| this.as_matrix3d()) {
+        Some(matrix) => *matrix.raw(),
+        None => return Ok(Value::Undefined),
+    };
+
+    Ok(create_vector3d(activation, [raw[12], raw[13], raw[14], raw[15]])?.into())
+}
+
+/// Implements `Matrix3D.position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [x, y, z, _] = components_arg(activation, args, 0)?;
+    with_matrix(activation, this, |matrix| {
+        let mut raw = *matrix.raw();
+        raw[12] = x;
+        raw[13] = y;
+        raw[14] = z;
+        *matrix = Matrix3D::from_raw(raw);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.append`.
+pub fn append<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let lhs = matrix_arg(activation, args, 0)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.append(&lhs);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.prepend`.
+pub fn prepend<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let rhs = matrix_arg(activation, args, 0)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.prepend(&rhs);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.appendTranslation`.
+pub fn append_translation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [x, y, z] = xyz_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.append(&Matrix3D::translation(x, y, z));
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.prependTranslation`.
+pub fn prepend_translation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [x, y, z] = xyz_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.prepend(&Matrix3D::translation(x, y, z));
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.appendScale`.
+pub fn append_scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [x, y, z] = xyz_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.append(&Matrix3D::scale(x, y, z));
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.prependScale`.
+pub fn prepend_scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [x, y, z] = xyz_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.prepend(&Matrix3D::scale(x, y, z));
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.appendRotation`.
+pub fn append_rotation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let rotation = rotation_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.append(&rotation);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.prependRotation`.
+pub fn prepend_rotation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let rotation = rotation_args(activation, args)?;
+    with_matrix(activation, this, |matrix| {
+        matrix.prepend(&rotation);
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    with_matrix(activation, this, |matrix| {
+        *matrix = Matrix3D::IDENTITY;
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.invert`.
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    with_matrix(activation, this, |matrix| matrix.invert().into())
+}
+
+/// Implements `Matrix3D.transpose`.
+pub fn transpose<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    with_matrix(activation, this, |matrix| {
+        matrix.transpose();
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let source = matrix_arg(activation, args, 0)?;
+    with_matrix(activation, this, |matrix| {
+        *matrix = source;
+        Value::Undefined
+    })
+}
+
+/// Implements `Matrix3D.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let source = match this.as_ref().and_then(|this| this.as_matrix3d()) {
+        Some(matrix) => *matrix,
+        None => return Ok(Value::Undefined),
+    };
+
+    let result = construct(activation, "Matrix3D", &[])?;
+    if let Some(mut matrix) = result.as_matrix3d_mut(activation.context.gc_context) {
+        *matrix = source;
+    }
+
+    Ok(result.into())
+}
+
+/// Implements `Matrix3D.transformVector`.
+pub fn transform_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let matrix = match this.as_ref().and_then(|this| this.as_matrix3d()) {
+        Some(matrix) => *matrix,
+        None => return Ok(Value::Undefined),
+    };
+    let [x, y, z, _] = components_arg(activation, args, 0)?;
+
+    Ok(create_vector3d(activation, matrix.transform_point([x, y, z]))?.into())
+}
+
+/// Implements `Matrix3D.deltaTransformVector`.
+pub fn delta_transform_vector<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let matrix = match this.as_ref().and_then(|this| this.as_matrix3d()) {
+        Some(matrix) => *matrix,
+        None => return Ok(Value::Undefined),
+    };
+    let [x, y, z, _] = components_arg(activation, args, 0)?;
+    let [x, y, z] = matrix.transform_direction([x, y, z]);
+
+    Ok(create_vector3d(activation, [x, y, z, 0.0])?.into())
+}
+
+/// Construct `Matrix3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix3D"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, Option<NativeMethod<'gc>>)] = &[
+        ("rawData", raw_data, Some(set_raw_data)),
+        ("position", position, Some(set_position)),
+        ("determinant", determinant, None),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+
+        if let Some(setter) = setter {
+            write.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public_namespace(), name),
+                Method::from_builtin(setter),
+            ));
+        }
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("append", append),
+        ("prepend", prepend),
+        ("appendTranslation", append_translation),
+        ("prependTranslation", prepend_translation),
+        ("appendScale", append_scale),
+        ("prependScale", prepend_scale),
+        ("appendRotation", append_rotation),
+        ("prependRotation", prepend_rotation),
+        ("identity", identity),
+        ("invert", invert),
+        ("transpose", transpose),
+        ("copyFrom", copy_from),
+        ("clone", clone),
+        ("transformVector", transform_vector),
+        ("deltaTransformVector", delta_transform_vector),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
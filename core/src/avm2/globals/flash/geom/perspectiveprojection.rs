@@ -0,0 +1,181 @@
+//! `flash.geom.PerspectiveProjection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::construct;
+use crate::avm2::matrix3d::Matrix3D;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The field of view of a new projection, in degrees.
+const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+fn field_of_view_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::public_namespace(), "fieldOfView")
+}
+
+/// Read the field of view of `this`, in degrees.
+fn field_of_view<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<f64, Error> {
+    this.get_property(this, &field_of_view_name(), activation)?
+        .coerce_to_number(activation)
+}
+
+/// The distance from the viewer to the stage that produces the given field
+/// of view across the width of the stage.
+fn focal_length_for(activation: &mut Activation<'_, '_, '_>, field_of_view: f64) -> f64 {
+    let stage_width = f64::from(activation.context.swf.width());
+    stage_width / 2.0 / (field_of_view.to_radians() / 2.0).tan()
+}
+
+/// Implements `flash.geom.PerspectiveProjection`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.PerspectiveProjection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `PerspectiveProjection.focalLength`'s getter.
+pub fn focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let field_of_view = field_of_view(activation, this)?;
+        return Ok(focal_length_for(activation, field_of_view).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PerspectiveProjection.focalLength`'s setter.
+pub fn set_focal_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let focal_length = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let stage_width = f64::from(activation.context.swf.width());
+        let field_of_view = (stage_width / 2.0 / focal_length).atan().to_degrees() * 2.0;
+        this.set_property(
+            this,
+            &field_of_view_name(),
+            field_of_view.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PerspectiveProjection.toMatrix3D`.
+pub fn to_matrix3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let field_of_view = field_of_view(activation, this)?;
+        let focal_length = focal_length_for(activation, field_of_view);
+
+        let result = construct(activation, "Matrix3D", &[])?;
+        if let Some(mut matrix) = result.as_matrix3d_mut(activation.context.gc_context) {
+            *matrix = Matrix3D::from_raw([
+                focal_length,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                focal_length,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+                1.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            ]);
+        }
+        return Ok(result.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `PerspectiveProjection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "PerspectiveProjection"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_slot(
+        field_of_view_name(),
+        QName::new(Namespace::public_namespace(), "Number").into(),
+        Some(DEFAULT_FIELD_OF_VIEW.into()),
+    ));
+
+    // TODO: This should default to the center of the stage as a `Point`,
+    // which doesn't exist yet.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public_namespace(), "projectionCenter"),
+        QName::new(Namespace::public_namespace(), "Object").into(),
+        Some(Value::Null),
+    ));
+
+    let accessors: &[(&str, NativeMethod<'gc>, Option<NativeMethod<'gc>>)] =
+        &[("focalLength", focal_length, Some(set_focal_length))];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+
+        if let Some(setter) = setter {
+            write.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public_namespace(), name),
+                Method::from_builtin(setter),
+            ));
+        }
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "toMatrix3D"),
+        Method::from_builtin(to_matrix3d),
+    ));
+
+    class
+}
@@ -0,0 +1,334 @@
+//! `flash.geom.Point` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::{
+    construct, construct_like, get_number, number_arg, object_arg, set_number,
+};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Read the `x` and `y` coordinates of a `Point`.
+pub fn coords<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    point: Object<'gc>,
+) -> Result<(f64, f64), Error> {
+    Ok((
+        get_number(activation, point, "x")?,
+        get_number(activation, point, "y")?,
+    ))
+}
+
+/// Overwrite the `x` and `y` coordinates of a `Point`.
+pub fn set_coords<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    point: Object<'gc>,
+    (x, y): (f64, f64),
+) -> Result<(), Error> {
+    set_number(activation, point, "x", x)?;
+    set_number(activation, point, "y", y)
+}
+
+/// Read the coordinates of a `Point` argument.
+pub fn coords_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<(f64, f64), Error> {
+    let point = object_arg(activation, args, index)?;
+    coords(activation, point)
+}
+
+/// Construct a new `Point`.
+pub fn create_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    (x, y): (f64, f64),
+) -> Result<Object<'gc>, Error> {
+    construct(activation, "Point", &[x.into(), y.into()])
+}
+
+/// Implements `flash.geom.Point`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let x = number_arg(activation, args, 0, 0.0)?;
+        let y = number_arg(activation, args, 1, 0.0)?;
+        set_coords(activation, this, (x, y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Point`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.length`.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        return Ok((x * x + y * y).sqrt().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.add`.
+pub fn add<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        let (ox, oy) = coords_arg(activation, args, 0)?;
+        return Ok(create_point(activation, (x + ox, y + oy))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.subtract`.
+pub fn subtract<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        let (ox, oy) = coords_arg(activation, args, 0)?;
+        return Ok(create_point(activation, (x - ox, y - oy))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.offset`.
+pub fn offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+        set_coords(activation, this, (x + dx, y + dy))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.normalize`.
+pub fn normalize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let thickness = number_arg(activation, args, 0, f64::NAN)?;
+        let (x, y) = coords(activation, this)?;
+        let length = (x * x + y * y).sqrt();
+        if length > 0.0 {
+            let scale = thickness / length;
+            set_coords(activation, this, (x * scale, y * scale))?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.equals`.
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = coords(activation, this)?;
+        let b = coords_arg(activation, args, 0)?;
+        return Ok((a == b).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let x = number_arg(activation, args, 0, f64::NAN)?;
+        let y = number_arg(activation, args, 1, f64::NAN)?;
+        set_coords(activation, this, (x, y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let source = coords_arg(activation, args, 0)?;
+        set_coords(activation, this, source)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        return Ok(construct_like(activation, this, &[x.into(), y.into()])?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y) = coords(activation, this)?;
+        let x = Value::from(x).coerce_to_string(activation)?;
+        let y = Value::from(y).coerce_to_string(activation)?;
+        return Ok(
+            AvmString::new(activation.context.gc_context, format!("(x={}, y={})", x, y)).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Point.distance`.
+pub fn distance<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (ax, ay) = coords_arg(activation, args, 0)?;
+    let (bx, by) = coords_arg(activation, args, 1)?;
+    let (dx, dy) = (ax - bx, ay - by);
+
+    Ok((dx * dx + dy * dy).sqrt().into())
+}
+
+/// Implements `Point.interpolate`.
+///
+/// A factor of 1 returns the first point and a factor of 0 returns the
+/// second.
+pub fn interpolate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let (ax, ay) = coords_arg(activation, args, 0)?;
+    let (bx, by) = coords_arg(activation, args, 1)?;
+    let f = number_arg(activation, args, 2, f64::NAN)?;
+
+    Ok(create_point(activation, (bx + f * (ax - bx), by + f * (ay - by)))?.into())
+}
+
+/// Implements `Point.polar`.
+pub fn polar<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let length = number_arg(activation, args, 0, f64::NAN)?;
+    let angle = number_arg(activation, args, 1, f64::NAN)?;
+
+    Ok(create_point(activation, (length * angle.cos(), length * angle.sin()))?.into())
+}
+
+/// Construct `Point`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Point"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for &name in ["x", "y"].iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(0.0.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "length"),
+        Method::from_builtin(length),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("add", add),
+        ("subtract", subtract),
+        ("offset", offset),
+        ("normalize", normalize),
+        ("equals", equals),
+        ("setTo", set_to),
+        ("copyFrom", copy_from),
+        ("clone", clone),
+        ("toString", to_string),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let class_methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("distance", distance),
+        ("interpolate", interpolate),
+        ("polar", polar),
+    ];
+
+    for &(name, method) in class_methods {
+        write.define_class_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,686 @@
+//! `flash.geom.Rectangle` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::point::{coords_arg, create_point};
+use crate::avm2::globals::flash::geom::{
+    construct, construct_like, get_number, number_arg, object_arg, set_number,
+};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+const COMPONENTS: [&str; 4] = ["x", "y", "width", "height"];
+
+/// Read the `x`, `y`, `width` and `height` of a `Rectangle`.
+pub fn bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: Object<'gc>,
+) -> Result<[f64; 4], Error> {
+    let mut result = [0.0; 4];
+    for (value, name) in result.iter_mut().zip(COMPONENTS.iter()) {
+        *value = get_number(activation, rect, name)?;
+    }
+    Ok(result)
+}
+
+/// Overwrite the `x`, `y`, `width` and `height` of a `Rectangle`.
+fn set_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: Object<'gc>,
+    values: [f64; 4],
+) -> Result<(), Error> {
+    for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+        set_number(activation, rect, name, *value)?;
+    }
+    Ok(())
+}
+
+/// Read the bounds of a `Rectangle` argument.
+fn bounds_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<[f64; 4], Error> {
+    let rect = object_arg(activation, args, index)?;
+    bounds(activation, rect)
+}
+
+/// Construct a new `Rectangle`.
+pub fn create_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: [f64; 4],
+) -> Result<Object<'gc>, Error> {
+    let args: Vec<Value<'gc>> = values.iter().map(|v| (*v).into()).collect();
+    construct(activation, "Rectangle", &args)
+}
+
+/// Whether the given bounds enclose no area.
+///
+/// NaN sizes count as empty, since the comparisons fail.
+fn is_empty_bounds([_, _, width, height]: [f64; 4]) -> bool {
+    !(width > 0.0 && height > 0.0)
+}
+
+/// Implements `flash.geom.Rectangle`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = [0.0; 4];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = number_arg(activation, args, i, 0.0)?;
+        }
+        set_bounds(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Rectangle`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.left`.
+pub fn left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(get_number(activation, this, "x")?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.left`'s setter, which keeps the right edge fixed.
+pub fn set_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let left = number_arg(activation, args, 0, f64::NAN)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        set_bounds(activation, this, [left, y, width + x - left, height])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.top`.
+pub fn top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(get_number(activation, this, "y")?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.top`'s setter, which keeps the bottom edge fixed.
+pub fn set_top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let top = number_arg(activation, args, 0, f64::NAN)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        set_bounds(activation, this, [x, top, width, height + y - top])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.right`.
+pub fn right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, _, width, _] = bounds(activation, this)?;
+        return Ok((x + width).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.right`'s setter.
+pub fn set_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let right = number_arg(activation, args, 0, f64::NAN)?;
+        let x = get_number(activation, this, "x")?;
+        set_number(activation, this, "width", right - x)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottom`.
+pub fn bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, y, _, height] = bounds(activation, this)?;
+        return Ok((y + height).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottom`'s setter.
+pub fn set_bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bottom = number_arg(activation, args, 0, f64::NAN)?;
+        let y = get_number(activation, this, "y")?;
+        set_number(activation, this, "height", bottom - y)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.topLeft`.
+pub fn top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, _, _] = bounds(activation, this)?;
+        return Ok(create_point(activation, (x, y))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.topLeft`'s setter, which keeps the bottom-right
+/// corner fixed.
+pub fn set_top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (left, top) = coords_arg(activation, args, 0)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        set_bounds(
+            activation,
+            this,
+            [left, top, width + x - left, height + y - top],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottomRight`.
+pub fn bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, width, height] = bounds(activation, this)?;
+        return Ok(create_point(activation, (x + width, y + height))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottomRight`'s setter.
+pub fn set_bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (right, bottom) = coords_arg(activation, args, 0)?;
+        let [x, y, _, _] = bounds(activation, this)?;
+        set_bounds(activation, this, [x, y, right - x, bottom - y])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.size`.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, _, width, height] = bounds(activation, this)?;
+        return Ok(create_point(activation, (width, height))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.size`'s setter.
+pub fn set_size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (width, height) = coords_arg(activation, args, 0)?;
+        set_number(activation, this, "width", width)?;
+        set_number(activation, this, "height", height)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.isEmpty`.
+pub fn is_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(is_empty_bounds(bounds(activation, this)?).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.setEmpty`.
+pub fn set_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_bounds(activation, this, [0.0; 4])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.contains`.
+pub fn contains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let px = number_arg(activation, args, 0, f64::NAN)?;
+        let py = number_arg(activation, args, 1, f64::NAN)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        return Ok((px >= x && px < x + width && py >= y && py < y + height).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.containsPoint`.
+pub fn contains_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (px, py) = coords_arg(activation, args, 0)?;
+        return contains(activation, Some(this), &[px.into(), py.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.containsRect`.
+///
+/// An empty rectangle must lie strictly inside this one to be contained.
+pub fn contains_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, width, height] = bounds(activation, this)?;
+        let other = bounds_arg(activation, args, 0)?;
+        let [ox, oy, owidth, oheight] = other;
+        let (right, bottom) = (x + width, y + height);
+        let (oright, obottom) = (ox + owidth, oy + oheight);
+        let contained = if is_empty_bounds(other) {
+            ox > x && oy > y && oright < right && obottom < bottom
+        } else {
+            ox >= x && oy >= y && oright <= right && obottom <= bottom
+        };
+        return Ok(contained.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.intersects`.
+pub fn intersects<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = bounds(activation, this)?;
+        let b = bounds_arg(activation, args, 0)?;
+        return Ok((!is_empty_bounds(intersect_bounds(a, b))).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// The overlap of two rectangles, or all zeroes if they do not overlap.
+fn intersect_bounds(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+    if is_empty_bounds(a) || is_empty_bounds(b) {
+        return [0.0; 4];
+    }
+
+    let x = a[0].max(b[0]);
+    let y = a[1].max(b[1]);
+    let result = [
+        x,
+        y,
+        (a[0] + a[2]).min(b[0] + b[2]) - x,
+        (a[1] + a[3]).min(b[1] + b[3]) - y,
+    ];
+
+    if is_empty_bounds(result) {
+        [0.0; 4]
+    } else {
+        result
+    }
+}
+
+/// Implements `Rectangle.intersection`.
+pub fn intersection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = bounds(activation, this)?;
+        let b = bounds_arg(activation, args, 0)?;
+        return Ok(create_rectangle(activation, intersect_bounds(a, b))?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.union`.
+///
+/// If either rectangle is empty, a copy of the other one is returned.
+pub fn union<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = object_arg(activation, args, 0)?;
+        let a = bounds(activation, this)?;
+        let b = bounds(activation, other)?;
+        if is_empty_bounds(a) {
+            return clone(activation, Some(other), &[]);
+        }
+        if is_empty_bounds(b) {
+            return clone(activation, Some(this), &[]);
+        }
+
+        let x = a[0].min(b[0]);
+        let y = a[1].min(b[1]);
+        let result = [
+            x,
+            y,
+            (a[0] + a[2]).max(b[0] + b[2]) - x,
+            (a[1] + a[3]).max(b[1] + b[3]) - y,
+        ];
+        return Ok(create_rectangle(activation, result)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        set_bounds(
+            activation,
+            this,
+            [x - dx, y - dy, width + 2.0 * dx, height + 2.0 * dy],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.inflatePoint`.
+pub fn inflate_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (dx, dy) = coords_arg(activation, args, 0)?;
+        return inflate(activation, Some(this), &[dx.into(), dy.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.offset`.
+pub fn offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+        let [x, y, width, height] = bounds(activation, this)?;
+        set_bounds(activation, this, [x + dx, y + dy, width, height])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.offsetPoint`.
+pub fn offset_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (dx, dy) = coords_arg(activation, args, 0)?;
+        return offset(activation, Some(this), &[dx.into(), dy.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.equals`.
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = bounds(activation, this)?;
+        let b = bounds_arg(activation, args, 0)?;
+        return Ok((a == b).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = [0.0; 4];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = number_arg(activation, args, i, f64::NAN)?;
+        }
+        set_bounds(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let values = bounds_arg(activation, args, 0)?;
+        set_bounds(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let args: Vec<Value<'gc>> = bounds(activation, this)?
+            .iter()
+            .map(|v| (*v).into())
+            .collect();
+        return Ok(construct_like(activation, this, &args)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, width, height] = bounds(activation, this)?;
+        let x = Value::from(x).coerce_to_string(activation)?;
+        let y = Value::from(y).coerce_to_string(activation)?;
+        let width = Value::from(width).coerce_to_string(activation)?;
+        let height = Value::from(height).coerce_to_string(activation)?;
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("(x={}, y={}, w={}, h={})", x, y, width, height),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Rectangle`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Rectangle"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for &name in COMPONENTS.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(0.0.into()),
+        ));
+    }
+
+    let accessors: &[(&str, NativeMethod<'gc>, NativeMethod<'gc>)] = &[
+        ("left", left, set_left),
+        ("top", top, set_top),
+        ("right", right, set_right),
+        ("bottom", bottom, set_bottom),
+        ("topLeft", top_left, set_top_left),
+        ("bottomRight", bottom_right, set_bottom_right),
+        ("size", size, set_size),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(setter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("isEmpty", is_empty),
+        ("setEmpty", set_empty),
+        ("contains", contains),
+        ("containsPoint", contains_point),
+        ("containsRect", contains_rect),
+        ("intersects", intersects),
+        ("intersection", intersection),
+        ("union", union),
+        ("inflate", inflate),
+        ("inflatePoint", inflate_point),
+        ("offset", offset),
+        ("offsetPoint", offset_point),
+        ("equals", equals),
+        ("setTo", set_to),
+        ("copyFrom", copy_from),
+        ("clone", clone),
+        ("toString", to_string),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
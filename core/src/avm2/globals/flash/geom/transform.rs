@@ -0,0 +1,268 @@
+//! `flash.geom.Transform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::colortransform::{
+    color_transform_to_object, object_to_color_transform,
+};
+use crate::avm2::globals::flash::geom::matrix::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::flash::geom::rectangle::create_rectangle;
+use crate::avm2::globals::flash::geom::{construct, object_arg};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// The property that stores the display object a `Transform` belongs to.
+fn display_object_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.geom:Transform".into()),
+        "displayObject",
+    )
+}
+
+/// Get the display object a `Transform` belongs to.
+///
+/// Every read of a `Transform` goes back to the display object, so the
+/// matrices and color transforms it hands out are fresh copies which must be
+/// assigned back to take effect.
+fn display_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+) -> Result<Option<DisplayObject<'gc>>, Error> {
+    if let Some(mut this) = this {
+        if let Value::Object(object) =
+            this.get_property(this, &display_object_name(), activation)?
+        {
+            return Ok(object.as_display_object());
+        }
+    }
+
+    Ok(None)
+}
+
+/// Construct a new `Transform` for a display object.
+pub fn create_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    construct(activation, "Transform", &[display_object.into()])
+}
+
+/// Copy the matrix and color transform of the display object a `Transform`
+/// belongs to onto another display object.
+pub fn apply_to_display_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    transform: Object<'gc>,
+    display_object: DisplayObject<'gc>,
+) -> Result<(), Error> {
+    if let Some(source) = self::display_object(activation, Some(transform))? {
+        let matrix = *source.matrix();
+        let color_transform = *source.color_transform();
+        let mc = activation.context.gc_context;
+        display_object.set_matrix(mc, &matrix);
+        display_object.set_color_transform(mc, &color_transform);
+        display_object.set_transformed_by_script(mc, true);
+    }
+
+    Ok(())
+}
+
+/// Implements `flash.geom.Transform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let display_object = object_arg(activation, args, 0)?;
+        this.set_property(
+            this,
+            &display_object_name(),
+            display_object.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Transform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.matrix`'s getter.
+pub fn matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = display_object(activation, this)? {
+        let matrix = *dobj.matrix();
+        return Ok(matrix_to_object(activation, matrix)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Transform.matrix`'s setter.
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let matrix = object_arg(activation, args, 0)?;
+    let matrix = object_to_matrix(activation, matrix)?;
+
+    if let Some(dobj) = display_object(activation, this)? {
+        dobj.set_matrix(activation.context.gc_context, &matrix);
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.colorTransform`'s getter.
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = display_object(activation, this)? {
+        let color_transform = *dobj.color_transform();
+        return Ok(color_transform_to_object(activation, color_transform)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Transform.colorTransform`'s setter.
+pub fn set_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let color_transform = object_arg(activation, args, 0)?;
+    let color_transform = object_to_color_transform(activation, color_transform)?;
+
+    if let Some(dobj) = display_object(activation, this)? {
+        dobj.set_color_transform(activation.context.gc_context, &color_transform);
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.concatenatedMatrix`'s getter.
+///
+/// Unlike AVM1, this includes the scaling of the stage into the viewport.
+pub fn concatenated_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = display_object(activation, this)? {
+        let matrix = activation.context.view_matrix * dobj.local_to_global_matrix();
+        return Ok(matrix_to_object(activation, matrix)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Transform.concatenatedColorTransform`'s getter.
+pub fn concatenated_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = display_object(activation, this)? {
+        // Walk through parents to get combined color transform.
+        let mut color_transform = *dobj.color_transform();
+        let mut node = dobj.parent();
+        while let Some(display_object) = node {
+            color_transform = *display_object.color_transform() * color_transform;
+            node = display_object.parent();
+        }
+        return Ok(color_transform_to_object(activation, color_transform)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Transform.pixelBounds`'s getter.
+///
+/// These are the bounds of the object in the viewport, grown outwards to
+/// whole pixels.
+pub fn pixel_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = display_object(activation, this)? {
+        let matrix = activation.context.view_matrix * dobj.local_to_global_matrix();
+        let bounds = dobj.bounds_with_transform(&matrix);
+        let values = if bounds.valid {
+            let x_min = bounds.x_min.to_pixels().floor();
+            let y_min = bounds.y_min.to_pixels().floor();
+            let x_max = bounds.x_max.to_pixels().ceil();
+            let y_max = bounds.y_max.to_pixels().ceil();
+            [x_min, y_min, x_max - x_min, y_max - y_min]
+        } else {
+            [0.0; 4]
+        };
+        return Ok(create_rectangle(activation, values)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Construct `Transform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Transform"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("matrix", matrix),
+        ("colorTransform", color_transform),
+        ("concatenatedMatrix", concatenated_matrix),
+        ("concatenatedColorTransform", concatenated_color_transform),
+        ("pixelBounds", pixel_bounds),
+    ];
+
+    for &(name, method) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let setters: &[(&str, NativeMethod<'gc>)] = &[
+        ("matrix", set_matrix),
+        ("colorTransform", set_color_transform),
+    ];
+
+    for &(name, method) in setters {
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
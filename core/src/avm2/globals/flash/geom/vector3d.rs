@@ -0,0 +1,542 @@
+//! `flash.geom.Vector3D` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::geom::construct;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+const COMPONENTS: [&str; 4] = ["x", "y", "z", "w"];
+
+/// Read the `x`, `y`, `z` and `w` components of a `Vector3D`.
+pub fn components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut vector: Object<'gc>,
+) -> Result<[f64; 4], Error> {
+    let mut result = [0.0; 4];
+    for (value, name) in result.iter_mut().zip(COMPONENTS.iter()) {
+        *value = vector
+            .get_property(
+                vector,
+                &QName::new(Namespace::public_namespace(), *name),
+                activation,
+            )?
+            .coerce_to_number(activation)?;
+    }
+    Ok(result)
+}
+
+/// Overwrite the `x`, `y`, `z` and `w` components of a `Vector3D`.
+fn set_components<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut vector: Object<'gc>,
+    values: [f64; 4],
+) -> Result<(), Error> {
+    for (value, name) in values.iter().zip(COMPONENTS.iter()) {
+        vector.set_property(
+            vector,
+            &QName::new(Namespace::public_namespace(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+    Ok(())
+}
+
+/// Read the components of a `Vector3D` argument.
+pub fn components_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<[f64; 4], Error> {
+    let vector = args
+        .get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    components(activation, vector)
+}
+
+/// Construct a new `Vector3D`.
+pub fn create_vector3d<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: [f64; 4],
+) -> Result<Object<'gc>, Error> {
+    let args: Vec<Value<'gc>> = values.iter().map(|v| (*v).into()).collect();
+    construct(activation, "Vector3D", &args)
+}
+
+/// Implements `flash.geom.Vector3D`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut values = [0.0; 4];
+        for (i, value) in values.iter_mut().enumerate() {
+            if let Some(arg) = args.get(i) {
+                *value = arg.coerce_to_number(activation)?;
+            }
+        }
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Vector3D`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.length`.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        return Ok((x * x + y * y + z * z).sqrt().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.lengthSquared`.
+pub fn length_squared<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        return Ok((x * x + y * y + z * z).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.add`.
+pub fn add<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        return Ok(create_vector3d(activation, [x + ax, y + ay, z + az, 0.0])?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.subtract`.
+pub fn subtract<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        return Ok(create_vector3d(activation, [x - ax, y - ay, z - az, 0.0])?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.crossProduct`.
+pub fn cross_product<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        return Ok(create_vector3d(
+            activation,
+            [y * az - z * ay, z * ax - x * az, x * ay - y * ax, 1.0],
+        )?
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.dotProduct`.
+pub fn dot_product<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        return Ok((x * ax + y * ay + z * az).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.incrementBy`.
+pub fn increment_by<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, w] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        set_components(activation, this, [x + ax, y + ay, z + az, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.decrementBy`.
+pub fn decrement_by<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, w] = components(activation, this)?;
+        let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+        set_components(activation, this, [x - ax, y - ay, z - az, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.scaleBy`.
+pub fn scale_by<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let [x, y, z, w] = components(activation, this)?;
+        set_components(activation, this, [x * scale, y * scale, z * scale, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.negate`.
+pub fn negate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, w] = components(activation, this)?;
+        set_components(activation, this, [-x, -y, -z, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.normalize`.
+pub fn normalize<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, w] = components(activation, this)?;
+        let length = (x * x + y * y + z * z).sqrt();
+        if length != 0.0 {
+            set_components(activation, this, [x / length, y / length, z / length, w])?;
+        }
+        return Ok(length.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.project`.
+pub fn project<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, w] = components(activation, this)?;
+        set_components(activation, this, [x / w, y / w, z / w, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, _, _, w] = components(activation, this)?;
+        let mut values = [0.0, 0.0, 0.0, w];
+        for (i, value) in values.iter_mut().take(3).enumerate() {
+            *value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+        }
+        set_components(activation, this, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components_arg(activation, args, 0)?;
+        let [_, _, _, w] = components(activation, this)?;
+        set_components(activation, this, [x, y, z, w])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let values = components(activation, this)?;
+        return Ok(create_vector3d(activation, values)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.equals`.
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = components(activation, this)?;
+        let b = components_arg(activation, args, 0)?;
+        let all_four = args.get(1).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let count = if all_four { 4 } else { 3 };
+        return Ok(a
+            .iter()
+            .zip(b.iter())
+            .take(count)
+            .all(|(a, b)| a == b)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.nearEquals`.
+pub fn near_equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let a = components(activation, this)?;
+        let b = components_arg(activation, args, 0)?;
+        let tolerance = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let all_four = args.get(2).map(|v| v.coerce_to_boolean()).unwrap_or(false);
+        let count = if all_four { 4 } else { 3 };
+        return Ok(a
+            .iter()
+            .zip(b.iter())
+            .take(count)
+            .all(|(a, b)| (a - b).abs() < tolerance)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [x, y, z, _] = components(activation, this)?;
+        let x = Value::from(x).coerce_to_string(activation)?;
+        let y = Value::from(y).coerce_to_string(activation)?;
+        let z = Value::from(z).coerce_to_string(activation)?;
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("Vector3D({}, {}, {})", x, y, z),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Vector3D.angleBetween`.
+pub fn angle_between<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+    let [bx, by, bz, _] = components_arg(activation, args, 1)?;
+    let dot = ax * bx + ay * by + az * bz;
+    let lengths = (ax * ax + ay * ay + az * az).sqrt() * (bx * bx + by * by + bz * bz).sqrt();
+
+    Ok((dot / lengths).max(-1.0).min(1.0).acos().into())
+}
+
+/// Implements `Vector3D.distance`.
+pub fn distance<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let [ax, ay, az, _] = components_arg(activation, args, 0)?;
+    let [bx, by, bz, _] = components_arg(activation, args, 1)?;
+    let (dx, dy, dz) = (ax - bx, ay - by, az - bz);
+
+    Ok((dx * dx + dy * dy + dz * dz).sqrt().into())
+}
+
+/// Implements `Vector3D.X_AXIS`.
+pub fn x_axis<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(create_vector3d(activation, [1.0, 0.0, 0.0, 0.0])?.into())
+}
+
+/// Implements `Vector3D.Y_AXIS`.
+pub fn y_axis<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(create_vector3d(activation, [0.0, 1.0, 0.0, 0.0])?.into())
+}
+
+/// Implements `Vector3D.Z_AXIS`.
+pub fn z_axis<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(create_vector3d(activation, [0.0, 0.0, 1.0, 0.0])?.into())
+}
+
+/// Construct `Vector3D`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Vector3D"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for &name in COMPONENTS.iter() {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public_namespace(), name),
+            QName::new(Namespace::public_namespace(), "Number").into(),
+            Some(0.0.into()),
+        ));
+    }
+
+    let getters: &[(&str, NativeMethod<'gc>)] =
+        &[("length", length), ("lengthSquared", length_squared)];
+
+    for &(name, getter) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("add", add),
+        ("subtract", subtract),
+        ("crossProduct", cross_product),
+        ("dotProduct", dot_product),
+        ("incrementBy", increment_by),
+        ("decrementBy", decrement_by),
+        ("scaleBy", scale_by),
+        ("negate", negate),
+        ("normalize", normalize),
+        ("project", project),
+        ("setTo", set_to),
+        ("copyFrom", copy_from),
+        ("clone", clone),
+        ("equals", equals),
+        ("nearEquals", near_equals),
+        ("toString", to_string),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let class_methods: &[(&str, NativeMethod<'gc>)] =
+        &[("angleBetween", angle_between), ("distance", distance)];
+
+    for &(name, method) in class_methods {
+        write.define_class_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    let axes: &[(&str, NativeMethod<'gc>)] =
+        &[("X_AXIS", x_axis), ("Y_AXIS", y_axis), ("Z_AXIS", z_axis)];
+
+    for &(name, getter) in axes {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,57 @@
+//! `flash.net` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+pub mod netconnection;
+pub mod netstream;
+pub mod sharedobject;
+pub mod urlrequest;
+
+/// Implements `flash.net.registerClassAlias`
+pub fn register_class_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let class = args.get(1).cloned().unwrap_or(Value::Undefined);
+
+    if matches!(alias, Value::Undefined | Value::Null) {
+        return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into());
+    }
+    if matches!(class, Value::Undefined | Value::Null) {
+        return Err("TypeError: Error #2007: Parameter classObject must be non-null.".into());
+    }
+
+    let alias = alias.coerce_to_string(activation)?.to_string();
+    let class = class.coerce_to_object(activation)?;
+    activation.avm2().register_class_alias(alias, class);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.getClassByAlias`
+pub fn get_class_by_alias<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let alias = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if matches!(alias, Value::Undefined | Value::Null) {
+        return Err("TypeError: Error #2007: Parameter aliasName must be non-null.".into());
+    }
+
+    let alias = alias.coerce_to_string(activation)?;
+    match activation.avm2().class_by_alias(&alias) {
+        Some(class) => Ok(class.into()),
+        None => Err(format!(
+            "ReferenceError: Error #1014: Class {} could not be found.",
+            alias
+        )
+        .into()),
+    }
+}
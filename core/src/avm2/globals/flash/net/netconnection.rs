@@ -0,0 +1,128 @@
+//! `flash.net.NetConnection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::net_stream::dispatch_net_status;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The property that stores whether a `NetConnection` is connected.
+fn connected_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.net:NetConnection".into()),
+        "connected",
+    )
+}
+
+/// Implements `flash.net.NetConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Only `null` connections, which play files downloaded over HTTP, are supported.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let command = args.get(0).cloned().unwrap_or(Value::Null);
+        if let Value::Null | Value::Undefined = command {
+            this.set_property(this, &connected_name(), true.into(), activation)?;
+            dispatch_net_status(activation, this, "NetConnection.Connect.Success", "status")?;
+        } else {
+            log::warn!(
+                "NetConnection.connect: Connecting to {} is not supported",
+                command.coerce_to_string(activation)?
+            );
+            this.set_property(this, &connected_name(), false.into(), activation)?;
+            dispatch_net_status(activation, this, "NetConnection.Connect.Failed", "error")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let connected = this
+            .get_property(this, &connected_name(), activation)?
+            .coerce_to_boolean();
+        if connected {
+            this.set_property(this, &connected_name(), false.into(), activation)?;
+            dispatch_net_status(activation, this, "NetConnection.Connect.Closed", "status")?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connected`.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let connected = this
+            .get_property(this, &connected_name(), activation)?
+            .coerce_to_boolean();
+        return Ok(connected.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Construct `NetConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "connected"),
+        Method::from_builtin(connected),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[("connect", connect), ("close", close)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
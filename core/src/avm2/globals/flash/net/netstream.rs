@@ -0,0 +1,301 @@
+//! `flash.net.NetStream` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::net_stream::NetStreams;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.NetStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.context.net_streams.add_stream(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.play`.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Null | Value::Undefined => {}
+            url => {
+                let url = url.coerce_to_string(activation)?;
+                NetStreams::play(&mut activation.context, this.into(), &url);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.pause`.
+pub fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.context.net_streams.pause(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.resume`.
+pub fn resume<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.context.net_streams.resume(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.togglePause`.
+pub fn toggle_pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.context.net_streams.toggle_pause(this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.seek`.
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        activation
+            .context
+            .net_streams
+            .seek(this.into(), offset.max(0.0));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation
+            .context
+            .net_streams
+            .close(activation.context.video, this.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.time`.
+pub fn time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation.context.net_streams.time(this.into()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bufferLength`.
+pub fn buffer_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .net_streams
+            .buffer_length(this.into())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bufferTime`'s getter.
+pub fn buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .net_streams
+            .buffer_time(this.into())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bufferTime`'s setter.
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let buffer_time = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        activation
+            .context
+            .net_streams
+            .set_buffer_time(this.into(), buffer_time);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.bytesLoaded` and `NetStream.bytesTotal`.
+///
+/// Files are only played once they are fully downloaded, so both are the same.
+pub fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .net_streams
+            .bytes_loaded(this.into())
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.client`'s getter.
+pub fn client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(client) = this.and_then(|this| activation.context.net_streams.client(this)) {
+        return Ok(client.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.client`'s setter.
+pub fn set_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let client = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        activation.context.net_streams.set_client(this, client);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, Option<NativeMethod<'gc>>)] = &[
+        ("time", time, None),
+        ("bufferLength", buffer_length, None),
+        ("bufferTime", buffer_time, Some(set_buffer_time)),
+        ("bytesLoaded", bytes_loaded, None),
+        ("bytesTotal", bytes_loaded, None),
+        ("client", client, Some(set_client)),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+
+        if let Some(setter) = setter {
+            write.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public_namespace(), name),
+                Method::from_builtin(setter),
+            ));
+        }
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("play", play),
+        ("pause", pause),
+        ("resume", resume),
+        ("togglePause", toggle_pause),
+        ("seek", seek),
+        ("close", close),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,212 @@
+//! `flash.net.SharedObject` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ObjectEncoding;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::shared_object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.SharedObject`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.SharedObject`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.getLocal`.
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let local_path = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            local_path => Some(local_path.coerce_to_string(activation)?),
+        };
+
+        let object = shared_object::get_local(activation, this, &name, local_path.as_deref())?;
+        return Ok(object.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.data`.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(shared_object::data(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.objectEncoding`'s getter.
+pub fn object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(shared_object::object_encoding(activation, this)?
+            .to_u32()
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.objectEncoding`'s setter.
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let encoding = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let encoding = ObjectEncoding::from_u32(encoding).ok_or(
+        "ArgumentError: Error #2008: Parameter objectEncoding must be one of the accepted values.",
+    )?;
+
+    if let Some(this) = this {
+        shared_object::set_object_encoding(activation, this, encoding)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.size`.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(shared_object::size(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.flush`.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let min_disk_space = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?
+            .max(0) as usize;
+        return Ok(shared_object::flush(activation, this, min_disk_space)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        shared_object::clear(activation, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.close`.
+///
+/// Local shared objects have no connection to close.
+pub fn close<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `SharedObject`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "SharedObject"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public_namespace(), "getLocal"),
+        Method::from_builtin(get_local),
+    ));
+    write.define_class_trait(Trait::from_slot(
+        QName::new(Namespace::public_namespace(), "defaultObjectEncoding"),
+        QName::new(Namespace::public_namespace(), "uint").into(),
+        Some(ObjectEncoding::default().to_u32().into()),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "objectEncoding"),
+        Method::from_builtin(object_encoding),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "objectEncoding"),
+        Method::from_builtin(set_object_encoding),
+    ));
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[("data", data), ("size", size)];
+    for &(name, getter) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] =
+        &[("flush", flush), ("clear", clear), ("close", close)];
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
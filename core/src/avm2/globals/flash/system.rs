@@ -0,0 +1,8 @@
+//! `flash.system` namespace
+
+pub mod applicationdomain;
+pub mod capabilities;
+pub mod loadercontext;
+pub mod messagechannel;
+pub mod worker;
+pub mod workerdomain;
@@ -0,0 +1,249 @@
+//! `flash.system.ApplicationDomain` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{DomainObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Domain, Error};
+use gc_arena::{GcCell, MutationContext};
+
+/// The smallest `ByteArray` that may be used as domain memory, as given by
+/// `ApplicationDomain.MIN_DOMAIN_MEMORY_LENGTH`.
+pub const MIN_DOMAIN_MEMORY_LENGTH: usize = 1024;
+
+/// Implements `flash.system.ApplicationDomain`'s instance constructor.
+///
+/// The new domain is a child of `parentDomain`, or of the player globals
+/// domain if no parent is given.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let parent_domain = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => activation.avm2().global_domain(),
+            parent => parent
+                .coerce_to_object(activation)?
+                .as_application_domain()
+                .ok_or("TypeError: parentDomain is not an ApplicationDomain")?,
+        };
+
+        let domain = Domain::movie_domain(activation.context.gc_context, parent_domain);
+        this.init_application_domain(activation.context.gc_context, domain);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.ApplicationDomain`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Split a definition name such as `flash.display.MovieClip` or
+/// `flash.display::MovieClip` into its package and local name.
+fn definition_name<'gc>(activation: &mut Activation<'_, 'gc, '_>, name: &str) -> QName<'gc> {
+    let mc = activation.context.gc_context;
+    let (package, local_name) = if let Some(index) = name.rfind("::") {
+        (&name[..index], &name[index + 2..])
+    } else if let Some(index) = name.rfind('.') {
+        (&name[..index], &name[index + 1..])
+    } else {
+        ("", name)
+    };
+
+    QName::new(
+        Namespace::package(AvmString::new(mc, package)),
+        AvmString::new(mc, local_name),
+    )
+}
+
+/// Implements `ApplicationDomain.currentDomain`.
+///
+/// TODO: This is the domain of the root movie, rather than the domain of the
+/// movie that the calling code was loaded from.
+pub fn current_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let movie = activation.context.swf.clone();
+    let domain = Avm2::movie_domain(&mut activation.context, movie);
+
+    Ok(DomainObject::from_domain(activation, domain)?.into())
+}
+
+/// Implements `ApplicationDomain.parentDomain`.
+pub fn parent_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(parent_domain) = this
+        .and_then(|this| this.as_application_domain())
+        .and_then(|domain| domain.parent_domain())
+    {
+        return Ok(DomainObject::from_domain(activation, parent_domain)?.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `ApplicationDomain.domainMemory`'s getter.
+pub fn domain_memory<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(memory) = this
+        .and_then(|this| this.as_application_domain())
+        .and_then(|domain| domain.domain_memory())
+    {
+        return Ok(memory.into());
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `ApplicationDomain.domainMemory`'s setter.
+///
+/// The memory must be a `ByteArray` of at least `MIN_DOMAIN_MEMORY_LENGTH`
+/// bytes, or `null` to remove it.
+pub fn set_domain_memory<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(domain) = this.and_then(|this| this.as_application_domain()) {
+        let memory = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            memory => {
+                let memory = memory.coerce_to_object(activation)?;
+                let length = memory.as_bytearray().map(|bytes| bytes.len()).ok_or(
+                    "TypeError: Error #1034: Type Coercion failed: domainMemory must be a ByteArray.",
+                )?;
+                if length < MIN_DOMAIN_MEMORY_LENGTH {
+                    return Err(
+                        "RangeError: Error #1503: Domain memory must be at least 1024 bytes long."
+                            .into(),
+                    );
+                }
+
+                Some(memory)
+            }
+        };
+
+        domain.set_domain_memory(activation.context.gc_context, memory);
+        activation.context.avm2.invalidate_domain_memory();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ApplicationDomain.getDefinition`.
+pub fn get_definition<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(domain) = this.and_then(|this| this.as_application_domain()) {
+        let local_name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = definition_name(activation, &local_name);
+        let defining_domain = domain.get_defining_domain(&name).ok_or_else(|| {
+            format!(
+                "ReferenceError: Error #1065: Variable {} is not defined.",
+                local_name
+            )
+        })?;
+        let mut globals = defining_domain.globals();
+
+        return globals.get_property(globals, &name, activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ApplicationDomain.hasDefinition`.
+pub fn has_definition<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(domain) = this.and_then(|this| this.as_application_domain()) {
+        let local_name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = definition_name(activation, &local_name);
+
+        return Ok(domain.has_definition(&name).into());
+    }
+
+    Ok(false.into())
+}
+
+/// Construct `ApplicationDomain`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "ApplicationDomain"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "currentDomain"),
+        Method::from_builtin(current_domain),
+    ));
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public_namespace(), "MIN_DOMAIN_MEMORY_LENGTH"),
+        QName::new(Namespace::public_namespace(), "uint").into(),
+        Some((MIN_DOMAIN_MEMORY_LENGTH as u32).into()),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "parentDomain"),
+        Method::from_builtin(parent_domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "domainMemory"),
+        Method::from_builtin(domain_memory),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "domainMemory"),
+        Method::from_builtin(set_domain_memory),
+    ));
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getDefinition", get_definition),
+        ("hasDefinition", has_definition),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,247 @@
+//! `flash.system.Capabilities` builtin/prototype
+
+use crate::avm1::globals::system::SystemCapabilities;
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.Capabilities`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Capabilities cannot be constructed".into())
+}
+
+/// Implements `flash.system.Capabilities`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Capabilities.version`.
+pub fn version<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let version = activation
+        .context
+        .system
+        .get_version_string(activation.context.avm1);
+
+    Ok(AvmString::new(activation.context.gc_context, version).into())
+}
+
+/// Implements `Capabilities.playerType`.
+pub fn player_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(AvmString::new(
+        activation.context.gc_context,
+        activation.context.system.player_type.to_string(),
+    )
+    .into())
+}
+
+/// Implements `Capabilities.os`.
+pub fn os<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(AvmString::new(
+        activation.context.gc_context,
+        activation.context.system.os.to_string(),
+    )
+    .into())
+}
+
+/// Implements `Capabilities.manufacturer`.
+pub fn manufacturer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let manufacturer = activation
+        .context
+        .system
+        .manufacturer
+        .get_manufacturer_string(activation.context.player_version);
+
+    Ok(AvmString::new(activation.context.gc_context, manufacturer).into())
+}
+
+/// Implements `Capabilities.language`.
+pub fn language<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let language = activation
+        .context
+        .system
+        .language
+        .get_language_code(activation.context.player_version)
+        .to_string();
+
+    Ok(AvmString::new(activation.context.gc_context, language).into())
+}
+
+/// Implements `Capabilities.screenResolutionX`.
+pub fn screen_resolution_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.system.screen_resolution.0.into())
+}
+
+/// Implements `Capabilities.screenResolutionY`.
+pub fn screen_resolution_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.system.screen_resolution.1.into())
+}
+
+/// Implements `Capabilities.screenDPI`.
+pub fn screen_dpi<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.system.dpi.into())
+}
+
+/// Implements `Capabilities.pixelAspectRatio`.
+pub fn pixel_aspect_ratio<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.system.aspect_ratio.into())
+}
+
+/// Implements `Capabilities.screenColor`.
+pub fn screen_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(AvmString::new(
+        activation.context.gc_context,
+        activation.context.system.screen_color.to_string(),
+    )
+    .into())
+}
+
+/// Implements `Capabilities.serverString`.
+pub fn server_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let server_string = activation
+        .context
+        .system
+        .get_server_string(activation.context.avm1);
+
+    Ok(AvmString::new(activation.context.gc_context, server_string).into())
+}
+
+macro_rules! capabilities_getter {
+    ($name:ident, $capability:expr) => {
+        pub fn $name<'gc>(
+            activation: &mut Activation<'_, 'gc, '_>,
+            _this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            Ok(activation.context.system.has_capability($capability).into())
+        }
+    };
+}
+
+capabilities_getter!(has_audio, SystemCapabilities::Audio);
+capabilities_getter!(has_mp3, SystemCapabilities::MP3);
+capabilities_getter!(has_accessibility, SystemCapabilities::Accessibility);
+capabilities_getter!(has_audio_encoder, SystemCapabilities::AudioEncoder);
+capabilities_getter!(has_embedded_video, SystemCapabilities::EmbeddedVideo);
+capabilities_getter!(has_ime, SystemCapabilities::IME);
+capabilities_getter!(has_printing, SystemCapabilities::Printing);
+capabilities_getter!(has_screen_broadcast, SystemCapabilities::ScreenBroadcast);
+capabilities_getter!(has_screen_playback, SystemCapabilities::ScreenPlayback);
+capabilities_getter!(has_streaming_audio, SystemCapabilities::StreamingAudio);
+capabilities_getter!(has_streaming_video, SystemCapabilities::StreamingVideo);
+capabilities_getter!(has_tls, SystemCapabilities::TLS);
+capabilities_getter!(has_video_encoder, SystemCapabilities::VideoEncoder);
+capabilities_getter!(is_debugger, SystemCapabilities::Debugger);
+capabilities_getter!(supports_32_bit_processes, SystemCapabilities::Process32Bit);
+capabilities_getter!(supports_64_bit_processes, SystemCapabilities::Process64Bit);
+
+/// Construct `Capabilities`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Capabilities"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("version", version),
+        ("playerType", player_type),
+        ("os", os),
+        ("manufacturer", manufacturer),
+        ("language", language),
+        ("screenResolutionX", screen_resolution_x),
+        ("screenResolutionY", screen_resolution_y),
+        ("screenDPI", screen_dpi),
+        ("pixelAspectRatio", pixel_aspect_ratio),
+        ("screenColor", screen_color),
+        ("serverString", server_string),
+        ("hasAudio", has_audio),
+        ("hasMP3", has_mp3),
+        ("hasAccessibility", has_accessibility),
+        ("hasAudioEncoder", has_audio_encoder),
+        ("hasEmbeddedVideo", has_embedded_video),
+        ("hasIME", has_ime),
+        ("hasPrinting", has_printing),
+        ("hasScreenBroadcast", has_screen_broadcast),
+        ("hasScreenPlayback", has_screen_playback),
+        ("hasStreamingAudio", has_streaming_audio),
+        ("hasStreamingVideo", has_streaming_video),
+        ("hasTLS", has_tls),
+        ("hasVideoEncoder", has_video_encoder),
+        ("isDebugger", is_debugger),
+        ("supports32BitProcesses", supports_32_bit_processes),
+        ("supports64BitProcesses", supports_64_bit_processes),
+    ];
+
+    for &(name, getter) in getters {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    class
+}
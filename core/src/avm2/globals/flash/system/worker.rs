@@ -0,0 +1,226 @@
+//! `flash.system.Worker` builtin/prototype
+//!
+//! Ruffle runs every movie on a single thread, so the only worker is the
+//! primordial one. Content that checks `Worker.isSupported` can still link
+//! against this class and take its fallback path.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+fn private_name<'gc>(name: &'static str) -> QName<'gc> {
+    QName::new(Namespace::Private("flash.system:Worker".into()), name)
+}
+
+/// Get the single instance of `class`, creating it the first time it's
+/// requested.
+///
+/// This is used for the objects describing the primordial worker, which
+/// can't be constructed by scripts.
+pub fn primordial_instance<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut class: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let instance_name = private_name("primordial");
+    if let Value::Object(instance) = class.get_property(class, &instance_name, activation)? {
+        return Ok(instance);
+    }
+
+    let proto = class
+        .get_property(
+            class,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let instance = proto.construct(activation, &[])?;
+    class.set_property(class, &instance_name, instance.into(), activation)?;
+
+    Ok(instance)
+}
+
+/// Implements `flash.system.Worker`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("ArgumentError: Error #2012: Worker class cannot be instantiated.".into())
+}
+
+/// Implements `flash.system.Worker`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Worker.current`.
+pub fn current<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(primordial_instance(activation, this)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.isPrimordial`.
+pub fn is_primordial<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `Worker.state`.
+pub fn state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok("running".into())
+}
+
+/// Implements `Worker.getSharedProperty`.
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let name = QName::new(Namespace::Private("flash.system:Worker".into()), key);
+
+        return this.get_property(this, &name, activation);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.setSharedProperty`.
+///
+/// With only one worker, the shared properties are just stored on it.
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let key = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let value = args.get(1).cloned().unwrap_or(Value::Undefined);
+        let name = QName::new(Namespace::Private("flash.system:Worker".into()), key);
+
+        this.set_property(this, &name, value, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.start`.
+///
+/// The primordial worker is always running already.
+pub fn start<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: The worker has already been started.".into())
+}
+
+/// Implements `Worker.terminate`.
+///
+/// The primordial worker can't be terminated.
+pub fn terminate<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Worker.createMessageChannel`.
+pub fn create_message_channel<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("IllegalOperationError: Message channels are not supported.".into())
+}
+
+/// Construct `Worker`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Worker"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    let class_getters: &[(&str, NativeMethod<'gc>)] =
+        &[("isSupported", is_supported), ("current", current)];
+    for &(name, getter) in class_getters {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let getters: &[(&str, NativeMethod<'gc>)] =
+        &[("isPrimordial", is_primordial), ("state", state)];
+    for &(name, getter) in getters {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getSharedProperty", get_shared_property),
+        ("setSharedProperty", set_shared_property),
+        ("start", start),
+        ("terminate", terminate),
+        ("createMessageChannel", create_message_channel),
+    ];
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
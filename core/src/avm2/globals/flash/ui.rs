@@ -0,0 +1,9 @@
+//! `flash.ui` namespace
+
+pub mod keyboard;
+pub mod keylocation;
+pub mod mouse;
+pub mod mousecursor;
+pub mod mousecursordata;
+pub mod multitouch;
+pub mod multitouchinputmode;
@@ -0,0 +1,106 @@
+//! `flash.ui.Keyboard` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.Keyboard`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Keyboard cannot be constructed".into())
+}
+
+/// Implements `flash.ui.Keyboard`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The key codes of keys whose name isn't derived from the character or
+/// number they type.
+const KEY_CODES: &[(&str, u32)] = &[
+    ("BACKSPACE", 8),
+    ("TAB", 9),
+    ("ENTER", 13),
+    ("COMMAND", 15),
+    ("SHIFT", 16),
+    ("CONTROL", 17),
+    ("ALTERNATE", 18),
+    ("CAPS_LOCK", 20),
+    ("NUMPAD", 21),
+    ("ESCAPE", 27),
+    ("SPACE", 32),
+    ("PAGE_UP", 33),
+    ("PAGE_DOWN", 34),
+    ("END", 35),
+    ("HOME", 36),
+    ("LEFT", 37),
+    ("UP", 38),
+    ("RIGHT", 39),
+    ("DOWN", 40),
+    ("INSERT", 45),
+    ("DELETE", 46),
+    ("NUMPAD_MULTIPLY", 106),
+    ("NUMPAD_ADD", 107),
+    ("NUMPAD_ENTER", 108),
+    ("NUMPAD_SUBTRACT", 109),
+    ("NUMPAD_DECIMAL", 110),
+    ("NUMPAD_DIVIDE", 111),
+    ("SEMICOLON", 186),
+    ("EQUAL", 187),
+    ("COMMA", 188),
+    ("MINUS", 189),
+    ("PERIOD", 190),
+    ("SLASH", 191),
+    ("BACKQUOTE", 192),
+    ("LEFTBRACKET", 219),
+    ("BACKSLASH", 220),
+    ("RIGHTBRACKET", 221),
+    ("QUOTE", 222),
+];
+
+/// Construct `Keyboard`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Keyboard"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    let mut constants: Vec<(String, u32)> = KEY_CODES
+        .iter()
+        .map(|&(name, code)| (name.to_string(), code))
+        .collect();
+    constants.extend((b'A'..=b'Z').map(|c| ((c as char).to_string(), u32::from(c))));
+    constants.extend((0..10).map(|n| (format!("NUMBER_{}", n), 48 + n)));
+    constants.extend((0..10).map(|n| (format!("NUMPAD_{}", n), 96 + n)));
+    constants.extend((1..=15).map(|n| (format!("F{}", n), 111 + n)));
+
+    for (name, code) in constants {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public_namespace(), AvmString::new(mc, name)),
+            QName::new(Namespace::public_namespace(), "uint").into(),
+            Some(code.into()),
+        ));
+    }
+
+    class
+}
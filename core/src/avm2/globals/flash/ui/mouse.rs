@@ -0,0 +1,262 @@
+//! `flash.ui.Mouse` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::input::{CustomCursor, MouseCursor};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.ui.Mouse`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Mouse cannot be constructed".into())
+}
+
+/// Implements `flash.ui.Mouse`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.hide`.
+pub fn hide<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.input.hide_mouse();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.show`.
+pub fn show<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.input.show_mouse();
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.cursor`'s getter.
+pub fn cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = activation
+        .context
+        .mouse_cursor
+        .script_cursor_name
+        .clone()
+        .unwrap_or_else(|| "auto".to_string());
+
+    Ok(AvmString::new(activation.context.gc_context, name).into())
+}
+
+/// Implements `Mouse.cursor`'s setter.
+///
+/// A cursor set by script overrides the cursor chosen automatically when
+/// hovering over buttons, until it is set back to `MouseCursor.AUTO`.
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let mouse_cursor = &mut activation.context.mouse_cursor;
+
+    if &*name == "auto" {
+        mouse_cursor.script_cursor_name = None;
+    } else {
+        let old_name = mouse_cursor.script_cursor_name.replace(name.to_string());
+        if mouse_cursor.script_cursor().is_none() {
+            mouse_cursor.script_cursor_name = old_name;
+            return Err(format!("ArgumentError: Unknown cursor name {}", name).into());
+        }
+    }
+
+    let cursor = mouse_cursor.cursor();
+    activation.context.input.set_mouse_cursor(cursor);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.supportsCursor` and `Mouse.supportsNativeCursor`.
+pub fn supports_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `Mouse.registerCursor`.
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let mut cursor_data = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+
+    let mut hotspot = (0, 0);
+    if let Value::Object(mut hotspot_object) = cursor_data.get_property(
+        cursor_data,
+        &QName::new(Namespace::public_namespace(), "hotSpot"),
+        activation,
+    )? {
+        let x = hotspot_object
+            .get_property(
+                hotspot_object,
+                &QName::new(Namespace::public_namespace(), "x"),
+                activation,
+            )?
+            .coerce_to_u32(activation)?;
+        let y = hotspot_object
+            .get_property(
+                hotspot_object,
+                &QName::new(Namespace::public_namespace(), "y"),
+                activation,
+            )?
+            .coerce_to_u32(activation)?;
+        hotspot = (x, y);
+    }
+
+    let frame_rate = cursor_data
+        .get_property(
+            cursor_data,
+            &QName::new(Namespace::public_namespace(), "frameRate"),
+            activation,
+        )?
+        .coerce_to_u32(activation)?;
+
+    // TODO: The frames of the cursor are `BitmapData`s, which we can't read
+    // yet, so custom cursors are displayed as the default arrow.
+    log::warn!(
+        "Mouse.registerCursor: Custom cursor images are not yet supported ({})",
+        name
+    );
+
+    let cursor = MouseCursor::Custom(Arc::new(CustomCursor {
+        width: 0,
+        height: 0,
+        frames: vec![],
+        hotspot,
+        frame_rate,
+    }));
+
+    let mouse_cursor = &mut activation.context.mouse_cursor;
+    mouse_cursor.registered.insert(name.to_string(), cursor);
+
+    // Re-registering the current cursor replaces it immediately.
+    if mouse_cursor.script_cursor_name.as_deref() == Some(&*name) {
+        let cursor = mouse_cursor.cursor();
+        activation.context.input.set_mouse_cursor(cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.unregisterCursor`.
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let mouse_cursor = &mut activation.context.mouse_cursor;
+    mouse_cursor.registered.remove(&*name);
+
+    // Unregistering the current cursor reverts to the automatic cursor.
+    if mouse_cursor.script_cursor_name.as_deref() == Some(&*name) {
+        mouse_cursor.script_cursor_name = None;
+        let cursor = mouse_cursor.cursor();
+        activation.context.input.set_mouse_cursor(cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Mouse`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Mouse"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "cursor"),
+        Method::from_builtin(cursor),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "cursor"),
+        Method::from_builtin(set_cursor),
+    ));
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("supportsCursor", supports_cursor),
+        ("supportsNativeCursor", supports_cursor),
+    ];
+
+    for &(name, getter) in getters {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("hide", hide),
+        ("show", show),
+        ("registerCursor", register_cursor),
+        ("unregisterCursor", unregister_cursor),
+    ];
+
+    for &(name, method) in methods {
+        write.define_class_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
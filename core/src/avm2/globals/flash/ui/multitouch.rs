@@ -0,0 +1,132 @@
+//! `flash.ui.Multitouch` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::touch::MultitouchInputMode;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.Multitouch`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Multitouch cannot be constructed".into())
+}
+
+/// Implements `flash.ui.Multitouch`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Multitouch.inputMode`'s getter.
+pub fn input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mode = activation.avm2().multitouch_input_mode();
+
+    Ok(AvmString::new(activation.context.gc_context, mode.to_string()).into())
+}
+
+/// Implements `Multitouch.inputMode`'s setter.
+pub fn set_input_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mode = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let mode: MultitouchInputMode = mode.parse().map_err(|_| {
+        "ArgumentError: Error #2008: Parameter inputMode must be one of the accepted values."
+    })?;
+
+    activation.avm2().set_multitouch_input_mode(mode);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Multitouch.supportsTouchEvents` and
+/// `Multitouch.supportsGestureEvents`.
+///
+/// Touches are tracked, but display objects have no script objects to send
+/// touch events to yet, so content is told to rely on mouse events.
+pub fn supports_touch_events<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Multitouch.maxTouchPoints`.
+pub fn max_touch_points<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(0.into())
+}
+
+/// Implements `Multitouch.supportedGestures`.
+pub fn supported_gestures<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Construct `Multitouch`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Multitouch"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public_namespace(), "inputMode"),
+        Method::from_builtin(input_mode),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public_namespace(), "inputMode"),
+        Method::from_builtin(set_input_mode),
+    ));
+
+    let getters: &[(&str, NativeMethod<'gc>)] = &[
+        ("maxTouchPoints", max_touch_points),
+        ("supportedGestures", supported_gestures),
+        ("supportsGestureEvents", supports_touch_events),
+        ("supportsTouchEvents", supports_touch_events),
+    ];
+
+    for &(name, getter) in getters {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+    }
+
+    class
+}
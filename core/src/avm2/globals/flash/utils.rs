@@ -0,0 +1,218 @@
+//! `flash.utils` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::QName;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::GcCell;
+
+pub mod bytearray;
+pub mod dictionary;
+pub mod proxy;
+pub mod timer;
+
+/// Determine the class of a given value.
+///
+/// Class constructors resolve to themselves, while every other value
+/// resolves to the class its prototype was derived from.
+fn class_of<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<Option<GcCell<'gc, Class<'gc>>>, Error> {
+    let object = value.coerce_to_object(activation)?;
+
+    Ok(object
+        .as_class()
+        .or_else(|| object.proto().and_then(|proto| proto.as_class())))
+}
+
+/// The class name of a primitive value, or `None` if it is an object.
+///
+/// Numbers are reported by what they hold, not how they're stored: any whole
+/// number that fits in an `int` is an `int`, and a `uint` too large to fit in
+/// one is a `uint`.
+fn primitive_class_name(value: &Value<'_>) -> Option<&'static str> {
+    match *value {
+        Value::Undefined => Some("void"),
+        Value::Null => Some("null"),
+        Value::Integer(_) => Some("int"),
+        Value::Unsigned(u) if u > i32::MAX as u32 => Some("uint"),
+        Value::Unsigned(_) => Some("int"),
+        Value::Number(n) if n.fract() == 0.0 && n >= i32::MIN.into() && n <= i32::MAX.into() => {
+            Some("int")
+        }
+        _ => None,
+    }
+}
+
+/// Implements `flash.utils.getQualifiedClassName`
+pub fn get_qualified_class_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    if let Some(name) = primitive_class_name(&value) {
+        return Ok(name.into());
+    }
+
+    match class_of(activation, value)? {
+        Some(class) => Ok(class
+            .read()
+            .name()
+            .to_qualified_name(activation.context.gc_context)
+            .into()),
+        None => Ok("Object".into()),
+    }
+}
+
+/// Implements `flash.utils.getQualifiedSuperclassName`
+pub fn get_qualified_superclass_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    if matches!(value, Value::Undefined | Value::Null) {
+        return Ok(Value::Null);
+    }
+
+    let class = match class_of(activation, value)? {
+        Some(class) => class,
+        None => return Ok(Value::Null),
+    };
+
+    let super_name = class.read().super_class_name().clone();
+    match super_name {
+        Some(super_name) => {
+            let globals = activation.avm2().globals();
+            let super_class = globals
+                .resolve_multiname(&super_name)?
+                .map(|name| name.to_qualified_name(activation.context.gc_context));
+
+            match (super_class, super_name.local_name()) {
+                (Some(name), _) => Ok(name.into()),
+                (None, Some(local_name)) => Ok(local_name.into()),
+                (None, None) => Ok(Value::Null),
+            }
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Split an applied type name, such as `__AS3__.vec::Vector.<int>`, into its
+/// base name and type parameter.
+///
+/// Returns `None` if the name is not an applied type.
+fn split_applied_type(name: &str) -> Option<(&str, &str)> {
+    let start = name.find(".<")?;
+    if !name.ends_with('>') {
+        return None;
+    }
+
+    Some((&name[..start], &name[start + 2..name.len() - 1]))
+}
+
+/// Look up a definition by name.
+///
+/// Applied types such as `__AS3__.vec::Vector.<int>` are never found, as
+/// parametrized classes aren't supported yet.
+fn resolve_definition<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &str,
+) -> Result<Value<'gc>, Error> {
+    let not_defined = || {
+        format!(
+            "ReferenceError: Error #1065: Variable {} is not defined.",
+            name
+        )
+    };
+
+    if split_applied_type(name).is_some() {
+        log::warn!(
+            "getDefinitionByName: Applied type {} is not supported",
+            name
+        );
+        return Err(not_defined().into());
+    }
+
+    let qname = QName::from_qualified_name(name, activation.context.gc_context);
+    let mut globals = activation.avm2().globals();
+    if !globals.has_property(&qname)? {
+        return Err(not_defined().into());
+    }
+
+    globals.get_property(globals, &qname, activation)
+}
+
+/// Implements `flash.utils.getTimer`
+pub fn get_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.timers.time().into())
+}
+
+/// Implements `flash.utils.getDefinitionByName`
+pub fn get_definition_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    resolve_definition(activation, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{primitive_class_name, split_applied_type};
+    use crate::avm2::value::Value;
+
+    #[test]
+    fn primitive_class_names() {
+        assert_eq!(primitive_class_name(&Value::Undefined), Some("void"));
+        assert_eq!(primitive_class_name(&Value::Null), Some("null"));
+        assert_eq!(primitive_class_name(&Value::Integer(-1)), Some("int"));
+        assert_eq!(primitive_class_name(&Value::Unsigned(5)), Some("int"));
+        assert_eq!(
+            primitive_class_name(&Value::Unsigned(i32::MAX as u32)),
+            Some("int")
+        );
+        assert_eq!(
+            primitive_class_name(&Value::Unsigned(i32::MAX as u32 + 1)),
+            Some("uint")
+        );
+        assert_eq!(
+            primitive_class_name(&Value::Unsigned(u32::MAX)),
+            Some("uint")
+        );
+        assert_eq!(primitive_class_name(&Value::Number(3.0)), Some("int"));
+        assert_eq!(primitive_class_name(&Value::Number(0.5)), None);
+        assert_eq!(primitive_class_name(&Value::Bool(true)), None);
+    }
+
+    #[test]
+    fn applied_type_names() {
+        assert_eq!(
+            split_applied_type("__AS3__.vec::Vector.<int>"),
+            Some(("__AS3__.vec::Vector", "int"))
+        );
+        assert_eq!(
+            split_applied_type("__AS3__.vec::Vector.<__AS3__.vec::Vector.<uint>>"),
+            Some(("__AS3__.vec::Vector", "__AS3__.vec::Vector.<uint>"))
+        );
+        assert_eq!(
+            split_applied_type("__AS3__.vec::Vector.<*>"),
+            Some(("__AS3__.vec::Vector", "*"))
+        );
+        assert_eq!(split_applied_type("flash.display::Sprite"), None);
+    }
+}
@@ -0,0 +1,410 @@
+//! `flash.utils.ByteArray` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::amf;
+use crate::avm2::bytearray::{CompressionAlgorithm, ObjectEncoding};
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.utils.ByteArray`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.ByteArray`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Parse the optional `algorithm` argument shared by `compress` and
+/// `uncompress`.
+fn algorithm_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<CompressionAlgorithm, Error> {
+    match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(CompressionAlgorithm::Zlib),
+        Some(value) => value.coerce_to_string(activation)?.parse(),
+    }
+}
+
+/// Implements `ByteArray.length`'s getter.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.as_ref().and_then(|this| this.as_bytearray()) {
+        return Ok((bytearray.len() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.length`'s setter.
+pub fn set_length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_length(length as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s getter.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.as_ref().and_then(|this| this.as_bytearray()) {
+        return Ok((bytearray.position() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.position`'s setter.
+pub fn set_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let position = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_position(position as usize);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.bytesAvailable`.
+pub fn bytes_available<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.as_ref().and_then(|this| this.as_bytearray()) {
+        return Ok((bytearray.bytes_available() as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeByte`.
+pub fn write_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let byte = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(&[byte as u8]);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readByte`.
+pub fn read_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok((bytearray.read_bytes(1)?[0] as i8 as i32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUnsignedByte`.
+pub fn read_unsigned_byte<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            return Ok((bytearray.read_bytes(1)?[0] as u32).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeUTFBytes`.
+pub fn write_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let string = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.write_bytes(string.as_bytes());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readUTFBytes`.
+pub fn read_utf_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let length = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            let string =
+                String::from_utf8_lossy(bytearray.read_bytes(length as usize)?).into_owned();
+            return Ok(AvmString::new(activation.context.gc_context, string).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.objectEncoding`'s getter.
+pub fn object_encoding<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bytearray) = this.as_ref().and_then(|this| this.as_bytearray()) {
+        return Ok(bytearray.object_encoding().to_u32().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.objectEncoding`'s setter.
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let encoding = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    let encoding = ObjectEncoding::from_u32(encoding).ok_or(
+        "ArgumentError: Error #2008: Parameter objectEncoding must be one of the accepted values.",
+    )?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.set_object_encoding(encoding);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.writeObject`.
+pub fn write_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    if let Some(this) = this {
+        amf::write_object(activation, this, value)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.readObject`.
+pub fn read_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return amf::read_object(activation, this);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.compress`.
+pub fn compress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let algorithm = algorithm_arg(activation, args)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.compress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.uncompress`.
+pub fn uncompress<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let algorithm = algorithm_arg(activation, args)?;
+
+    if let Some(this) = this {
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.uncompress(algorithm)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ByteArray.deflate`.
+pub fn deflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    compress(activation, this, &["deflate".into()])
+}
+
+/// Implements `ByteArray.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    uncompress(activation, this, &["deflate".into()])
+}
+
+/// Construct `ByteArray`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "ByteArray"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, Option<NativeMethod<'gc>>)] = &[
+        ("length", length, Some(set_length)),
+        ("position", position, Some(set_position)),
+        ("bytesAvailable", bytes_available, None),
+        ("objectEncoding", object_encoding, Some(set_object_encoding)),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+
+        if let Some(setter) = setter {
+            write.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public_namespace(), name),
+                Method::from_builtin(setter),
+            ));
+        }
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("clear", clear),
+        ("writeByte", write_byte),
+        ("readByte", read_byte),
+        ("readUnsignedByte", read_unsigned_byte),
+        ("writeUTFBytes", write_utf_bytes),
+        ("readUTFBytes", read_utf_bytes),
+        ("writeObject", write_object),
+        ("readObject", read_object),
+        ("compress", compress),
+        ("uncompress", uncompress),
+        ("deflate", deflate),
+        ("inflate", inflate),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
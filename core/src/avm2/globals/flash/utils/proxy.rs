@@ -0,0 +1,145 @@
+//! `flash.utils.Proxy` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The URI of the `flash_proxy` namespace, which all overridable `Proxy`
+/// methods live in.
+pub const NS_FLASH_PROXY: &str = "http://www.adobe.com/2006/actionscript/flash/proxy";
+
+/// Construct the `flash_proxy` namespace.
+pub fn flash_proxy_namespace<'gc>() -> Namespace<'gc> {
+    Namespace::Namespace(NS_FLASH_PROXY.into())
+}
+
+/// Implements `flash.utils.Proxy`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.Proxy`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+macro_rules! not_implemented {
+    ($name:ident, $method:literal) => {
+        /// Base implementation of a `flash_proxy` method, which subclasses
+        /// are expected to override.
+        pub fn $name<'gc>(
+            _activation: &mut Activation<'_, 'gc, '_>,
+            _this: Option<Object<'gc>>,
+            _args: &[Value<'gc>],
+        ) -> Result<Value<'gc>, Error> {
+            Err(concat!(
+                "IllegalOperationError: Error #2088: The Proxy class does not implement ",
+                $method,
+                ". It must be overridden by a subclass."
+            )
+            .into())
+        }
+    };
+}
+
+not_implemented!(get_property, "getProperty");
+not_implemented!(set_property, "setProperty");
+not_implemented!(call_property, "callProperty");
+not_implemented!(has_property, "hasProperty");
+not_implemented!(delete_property, "deleteProperty");
+not_implemented!(get_descendants, "getDescendants");
+not_implemented!(next_name_index, "nextNameIndex");
+not_implemented!(next_name, "nextName");
+not_implemented!(next_value, "nextValue");
+
+/// Implements `flash_proxy::isAttribute`.
+pub fn is_attribute<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Determine if an object is an instance of a `Proxy` subclass.
+pub fn is_proxy<'gc>(object: Object<'gc>) -> bool {
+    let proxy_name = QName::new(Namespace::package("flash.utils"), "Proxy");
+    let mut proto = object.proto();
+
+    while let Some(p) = proto {
+        if let Some(class) = p.as_class() {
+            if class.read().name() == &proxy_name {
+                return true;
+            }
+        }
+
+        proto = p.proto();
+    }
+
+    false
+}
+
+/// Call one of a proxy's `flash_proxy` methods.
+pub fn call_proxy_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut proxy: Object<'gc>,
+    method_name: &'static str,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = QName::new(flash_proxy_namespace(), method_name);
+    let base_proto = proxy.get_base_proto(&name)?;
+    let method = proxy
+        .get_property(proxy, &name, activation)?
+        .coerce_to_object(activation)?;
+
+    method.call(Some(proxy), args, activation, base_proto)
+}
+
+/// Construct `Proxy`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "Proxy"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("getProperty", get_property),
+        ("setProperty", set_property),
+        ("callProperty", call_property),
+        ("hasProperty", has_property),
+        ("deleteProperty", delete_property),
+        ("getDescendants", get_descendants),
+        ("nextNameIndex", next_name_index),
+        ("nextName", next_name),
+        ("nextValue", next_value),
+        ("isAttribute", is_attribute),
+    ];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(flash_proxy_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,338 @@
+//! `flash.utils.Timer` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Coerce a delay argument, rejecting delays that Flash Player would throw on.
+fn coerce_delay<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<f64, Error> {
+    let delay = value.coerce_to_number(activation)?;
+    if delay < 0.0 || !delay.is_finite() {
+        return Err("RangeError: Error #2066: The Timer delay specified is out of range.".into());
+    }
+
+    Ok(delay)
+}
+
+/// The property that stores the delay of a `Timer`, in milliseconds.
+fn delay_name<'gc>() -> QName<'gc> {
+    QName::new(Namespace::Private("flash.utils:Timer".into()), "delay")
+}
+
+/// The property that stores how many times a `Timer` ticks before stopping.
+fn repeat_count_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.utils:Timer".into()),
+        "repeatCount",
+    )
+}
+
+/// The property that stores how many times a `Timer` has ticked since it was
+/// last reset.
+fn current_count_name<'gc>() -> QName<'gc> {
+    QName::new(
+        Namespace::Private("flash.utils:Timer".into()),
+        "currentCount",
+    )
+}
+
+/// Implements `flash.utils.Timer`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let delay = coerce_delay(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        let repeat_count = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(this, &delay_name(), delay.into(), activation)?;
+        this.set_property(this, &repeat_count_name(), repeat_count.into(), activation)?;
+        this.set_property(this, &current_count_name(), 0.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.Timer`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Timer.currentCount`.
+pub fn current_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &current_count_name(), activation);
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Timer.delay`'s getter.
+pub fn delay<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &delay_name(), activation);
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Timer.delay`'s setter.
+///
+/// A running timer is restarted, so that its next tick is one new delay from
+/// now.
+pub fn set_delay<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let delay = coerce_delay(activation, args.get(0).cloned().unwrap_or(Value::Undefined))?;
+        this.set_property(this, &delay_name(), delay.into(), activation)?;
+
+        activation.context.timers.restart_avm2_timer(this, delay);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Timer.repeatCount`'s getter.
+pub fn repeat_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(this, &repeat_count_name(), activation);
+    }
+
+    Ok(0.into())
+}
+
+/// Implements `Timer.repeatCount`'s setter.
+///
+/// A running timer that has already fired this many times is stopped.
+pub fn set_repeat_count<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let repeat_count = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        this.set_property(this, &repeat_count_name(), repeat_count.into(), activation)?;
+
+        let current_count = this
+            .get_property(this, &current_count_name(), activation)?
+            .coerce_to_i32(activation)?;
+        if repeat_count > 0 && current_count >= repeat_count {
+            activation.context.timers.stop_avm2_timer(this);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Timer.running`.
+pub fn running<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation.context.timers.avm2_timer_running(this).into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `Timer.start`.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let delay = this
+            .get_property(this, &delay_name(), activation)?
+            .coerce_to_number(activation)?;
+        let movie = activation
+            .avm2()
+            .current_movie()
+            .unwrap_or_else(|| activation.context.swf.clone());
+        activation
+            .context
+            .timers
+            .start_avm2_timer(this, delay, movie);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Timer.stop`.
+pub fn stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.context.timers.stop_avm2_timer(this);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Timer.reset`.
+pub fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.context.timers.stop_avm2_timer(this);
+        this.set_property(this, &current_count_name(), 0.into(), activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `TimerEvent` of the given type to a `Timer`.
+fn dispatch_timer_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let mut globals = activation.avm2().globals();
+    let mut constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.events"), "TimerEvent"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let args = [Value::from(event_type), false.into(), false.into()];
+    let event = proto.construct(activation, &args)?;
+    constr.call(Some(event), &args, activation, event.proto())?;
+
+    dispatch_event(activation, target, event)?;
+    Ok(())
+}
+
+/// Count a tick of a running `Timer` and fire its `timer` event, followed by
+/// its `timerComplete` event if that was its last tick.
+///
+/// A `Timer` that has reached its repeat count is stopped before either event
+/// is dispatched.
+pub fn tick<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut target: Object<'gc>,
+) -> Result<(), Error> {
+    let current_count = target
+        .get_property(target, &current_count_name(), activation)?
+        .coerce_to_i32(activation)?
+        .saturating_add(1);
+    target.set_property(
+        target,
+        &current_count_name(),
+        current_count.into(),
+        activation,
+    )?;
+
+    let repeat_count = target
+        .get_property(target, &repeat_count_name(), activation)?
+        .coerce_to_i32(activation)?;
+    let is_complete = repeat_count > 0 && current_count >= repeat_count;
+    if is_complete {
+        activation.context.timers.stop_avm2_timer(target);
+    }
+
+    dispatch_timer_event(activation, target, "timer")?;
+
+    if is_complete {
+        dispatch_timer_event(activation, target, "timerComplete")?;
+    }
+
+    Ok(())
+}
+
+/// Construct `Timer`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.utils"), "Timer"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, Option<NativeMethod<'gc>>)] = &[
+        ("currentCount", current_count, None),
+        ("delay", delay, Some(set_delay)),
+        ("repeatCount", repeat_count, Some(set_repeat_count)),
+        ("running", running, None),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+
+        if let Some(setter) = setter {
+            write.define_instance_trait(Trait::from_setter(
+                QName::new(Namespace::public_namespace(), name),
+                Method::from_builtin(setter),
+            ));
+        }
+    }
+
+    let methods: &[(&str, NativeMethod<'gc>)] =
+        &[("start", start), ("stop", stop), ("reset", reset)];
+
+    for &(name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
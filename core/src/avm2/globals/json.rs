@@ -0,0 +1,554 @@
+//! `JSON` impl
+//!
+//! Values are serialized the same way as Flash Player 11's native `JSON`:
+//! the public variables, constants and getters of sealed classes are written
+//! before any dynamic properties, `toJSON` methods and replacer functions can
+//! substitute values, and cyclic structures are rejected.
+//!
+//! JSON arrays are parsed into `Array`s, and `Array`s are written as JSON
+//! arrays. Replacer lists may also be any other object with a `length` and
+//! indexed properties.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::{Trait, TraitKind};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use json::JsonValue;
+
+const CYCLIC_ERROR: &str =
+    "TypeError: Error #1129: Cyclic structure cannot be converted to JSON string.";
+
+const REPLACER_ERROR: &str = "TypeError: Error #1131: Replacer argument to JSON stringifier must be an array or a two parameter function.";
+
+const PARSE_ERROR: &str = "SyntaxError: Error #1132: Invalid JSON parse input.";
+
+/// The most characters that `space` can indent each level by.
+const MAX_GAP: usize = 10;
+
+/// Implements `JSON`'s instance constructor.
+pub fn instance_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("JSON cannot be constructed".into())
+}
+
+/// Implements `JSON`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `JSON.stringify`.
+pub fn stringify<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let replacer = match args.get(1).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => Replacer::None,
+        Value::Object(function) if function.as_executable().is_some() => {
+            Replacer::Function(function)
+        }
+        Value::Object(list) => Replacer::Keys(property_list(activation, list)?),
+        _ => return Err(REPLACER_ERROR.into()),
+    };
+    let gap = gap(&args.get(2).cloned().unwrap_or(Value::Null));
+
+    let mut serializer = Serializer {
+        replacer,
+        gap,
+        indent: String::new(),
+        stack: Vec::new(),
+    };
+    let object_proto = activation.avm2().prototypes().object;
+    let mut holder = ScriptObject::object(activation.context.gc_context, object_proto);
+    holder.set_property(holder, &QName::dynamic_name(""), value.clone(), activation)?;
+
+    Ok(
+        match serializer.serialize_property(activation, holder, "", value)? {
+            Some(json) => AvmString::new(activation.context.gc_context, json).into(),
+            None => Value::Undefined,
+        },
+    )
+}
+
+/// Implements `JSON.parse`.
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let text = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let json = json::parse(&text).map_err(|_| PARSE_ERROR)?;
+    let value = json_to_value(activation, &json)?;
+
+    match args.get(1) {
+        Some(Value::Object(reviver)) if reviver.as_executable().is_some() => {
+            let object_proto = activation.avm2().prototypes().object;
+            let mut holder = ScriptObject::object(activation.context.gc_context, object_proto);
+            let name = QName::dynamic_name("");
+            holder.set_property(holder, &name, value, activation)?;
+            revive(activation, *reviver, holder, name)
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Convert a parsed JSON value to an AVM2 value.
+fn json_to_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    json: &JsonValue,
+) -> Result<Value<'gc>, Error> {
+    Ok(match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Short(s) => AvmString::new(activation.context.gc_context, s.to_string()).into(),
+        JsonValue::String(s) => AvmString::new(activation.context.gc_context, s.clone()).into(),
+        JsonValue::Number(n) => Value::Number((*n).into()),
+        JsonValue::Boolean(b) => Value::Bool(*b),
+        JsonValue::Object(_) => {
+            let object_proto = activation.avm2().prototypes().object;
+            let mut object = ScriptObject::object(activation.context.gc_context, object_proto);
+            for (key, value) in json.entries() {
+                let value = json_to_value(activation, value)?;
+                let name = QName::dynamic_name(AvmString::new(
+                    activation.context.gc_context,
+                    key.to_string(),
+                ));
+                object.set_property(object, &name, value, activation)?;
+            }
+            object.into()
+        }
+        JsonValue::Array(values) => {
+            let mut array = ArrayStorage::new(0);
+            for value in values {
+                array.push(json_to_value(activation, value)?);
+            }
+
+            let array_proto = activation.avm2().prototypes().array;
+            ArrayObject::from_array(array, array_proto, activation.context.gc_context).into()
+        }
+    })
+}
+
+/// Pass a parsed value through a reviver function, innermost properties first.
+///
+/// Properties that the reviver returns `undefined` for are deleted.
+fn revive<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    reviver: Object<'gc>,
+    mut holder: Object<'gc>,
+    name: QName<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let value = holder.get_property(holder, &name, activation)?;
+    if let Value::Object(mut object) = value {
+        let keys = match array_length(object) {
+            Some(length) => (0..length)
+                .map(|index| index_name(activation.context.gc_context, index))
+                .collect(),
+            None => dynamic_names(object),
+        };
+        for key in keys {
+            match revive(activation, reviver, object, key.clone())? {
+                Value::Undefined => {
+                    object.delete_property(activation.context.gc_context, &key);
+                }
+                revived => object.set_property(object, &key, revived, activation)?,
+            }
+        }
+    }
+
+    reviver.call(
+        Some(holder),
+        &[name.local_name().into(), value],
+        activation,
+        None,
+    )
+}
+
+/// How the second argument of `JSON.stringify` changes its output.
+enum Replacer<'gc> {
+    None,
+
+    /// Called with each key and value, and returns the value to serialize.
+    Function(Object<'gc>),
+
+    /// The only property names that are serialized, in order.
+    Keys(Vec<String>),
+}
+
+/// Read the property names of a replacer array.
+///
+/// Strings and numbers are used as names, anything else is ignored.
+fn property_list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut list: Object<'gc>,
+) -> Result<Vec<String>, Error> {
+    let length = match array_length(list) {
+        Some(length) => length,
+        None => {
+            let length_name = QName::dynamic_name("length");
+            if !list.has_property(&length_name)? {
+                return Err(REPLACER_ERROR.into());
+            }
+
+            list.get_property(list, &length_name, activation)?
+                .coerce_to_u32(activation)? as usize
+        }
+    };
+    let mut keys: Vec<String> = Vec::new();
+    for index in 0..length {
+        let name = index_name(activation.context.gc_context, index);
+        let key = match list.get_property(list, &name, activation)? {
+            Value::String(s) => s.to_string(),
+            value @ Value::Number(_) | value @ Value::Integer(_) | value @ Value::Unsigned(_) => {
+                value.coerce_to_string(activation)?.to_string()
+            }
+            _ => continue,
+        };
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    Ok(keys)
+}
+
+/// The length of an object, if it is an `Array`.
+fn array_length(object: Object<'_>) -> Option<usize> {
+    object.as_array_storage().map(|array| array.length())
+}
+
+/// The property name of an array index.
+fn index_name<'gc>(mc: MutationContext<'gc, '_>, index: usize) -> QName<'gc> {
+    QName::dynamic_name(AvmString::new(mc, index.to_string()))
+}
+
+/// The indentation of each level, from the third argument of `JSON.stringify`.
+///
+/// Numbers are clamped to at most ten spaces, and strings are cut to their
+/// first ten characters. Anything else doesn't indent.
+fn gap(space: &Value<'_>) -> String {
+    let spaces = match space {
+        Value::Number(n) if *n >= 1.0 => n.min(MAX_GAP as f64) as usize,
+        Value::Integer(i) => (*i).max(0).min(MAX_GAP as i32) as usize,
+        Value::Unsigned(u) => (*u).min(MAX_GAP as u32) as usize,
+        Value::String(s) => return s.chars().take(MAX_GAP).collect(),
+        _ => 0,
+    };
+
+    " ".repeat(spaces)
+}
+
+/// Quote a string as a JSON string literal.
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Join the serialized members of an object or array between `brackets`,
+/// indenting them if there is a gap.
+fn join_members(
+    members: &[String],
+    brackets: (char, char),
+    gap: &str,
+    indent: &str,
+    stepback: &str,
+) -> String {
+    let (open, close) = brackets;
+    if members.is_empty() {
+        format!("{}{}", open, close)
+    } else if gap.is_empty() {
+        format!("{}{}{}", open, members.join(","), close)
+    } else {
+        let separator = format!(",\n{}", indent);
+        format!(
+            "{}\n{}{}\n{}{}",
+            open,
+            indent,
+            members.join(&separator),
+            stepback,
+            close
+        )
+    }
+}
+
+/// List the enumerable dynamic properties of an object.
+fn dynamic_names<'gc>(object: Object<'gc>) -> Vec<QName<'gc>> {
+    let mut names = Vec::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        if object.property_is_enumerable(&name) {
+            names.push(name);
+        }
+        index += 1;
+    }
+
+    names
+}
+
+/// List the names of the properties that an object is serialized with.
+///
+/// These are the public variables, constants and getters declared by the
+/// object's class and its superclasses, superclass first, followed by its
+/// dynamic properties.
+fn property_names<'gc>(object: Object<'gc>) -> Vec<String> {
+    let mut classes = Vec::new();
+    let mut proto = object.proto();
+    while let Some(p) = proto {
+        if let Some(class) = p.as_class() {
+            classes.push(class);
+        }
+        proto = p.proto();
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    for class in classes.iter().rev() {
+        for instance_trait in class.read().instance_traits() {
+            if instance_trait.name().namespace() != &Namespace::public_namespace() {
+                continue;
+            }
+
+            let is_property = matches!(
+                instance_trait.kind(),
+                TraitKind::Slot { .. } | TraitKind::Const { .. } | TraitKind::Getter { .. }
+            );
+            let name = instance_trait.name().local_name().to_string();
+            if is_property && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    for name in dynamic_names(object) {
+        let name = name.local_name().to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Serializes values for `JSON.stringify`.
+struct Serializer<'gc> {
+    replacer: Replacer<'gc>,
+
+    /// The indentation added by each level of nesting.
+    gap: String,
+
+    /// The indentation of the object currently being serialized.
+    indent: String,
+
+    /// The objects currently being serialized, to detect cycles.
+    stack: Vec<Object<'gc>>,
+}
+
+impl<'gc> Serializer<'gc> {
+    /// Serialize the property `key` of `holder`, which has the given value.
+    ///
+    /// Returns `None` if the property should be left out.
+    fn serialize_property(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        holder: Object<'gc>,
+        key: &str,
+        mut value: Value<'gc>,
+    ) -> Result<Option<String>, Error> {
+        let key_value: Value<'gc> = AvmString::new(activation.context.gc_context, key).into();
+
+        if let Value::Object(mut object) = value {
+            let to_json_name = QName::dynamic_name("toJSON");
+            if let Value::Object(to_json) =
+                object.get_property(object, &to_json_name, activation)?
+            {
+                if to_json.as_executable().is_some() {
+                    let base_proto = object.get_base_proto(&to_json_name)?;
+                    value =
+                        to_json.call(Some(object), &[key_value.clone()], activation, base_proto)?;
+                }
+            }
+        }
+
+        if let Replacer::Function(replacer) = self.replacer {
+            value = replacer.call(Some(holder), &[key_value, value], activation, None)?;
+        }
+
+        Ok(match value {
+            Value::Undefined => None,
+            Value::Null => Some("null".to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Integer(i) => Some(i.to_string()),
+            Value::Unsigned(u) => Some(u.to_string()),
+            Value::Number(n) if !n.is_finite() => Some("null".to_string()),
+            Value::Number(_) => Some(value.coerce_to_string(activation)?.to_string()),
+            Value::String(s) => Some(quote(&s)),
+            Value::Object(object) if object.as_executable().is_some() => None,
+            Value::Object(object) => Some(self.serialize_object(activation, object)?),
+        })
+    }
+
+    fn serialize_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut object: Object<'gc>,
+    ) -> Result<String, Error> {
+        if self.stack.iter().any(|o| Object::ptr_eq(*o, object)) {
+            return Err(CYCLIC_ERROR.into());
+        }
+        self.stack.push(object);
+
+        let stepback = self.indent.clone();
+        self.indent.push_str(&self.gap);
+
+        if let Some(length) = array_length(object) {
+            // Elements that can't be written, such as `undefined` and
+            // functions, are written as `null` to keep the others in place.
+            let mut elements = Vec::new();
+            for index in 0..length {
+                let name = index_name(activation.context.gc_context, index);
+                let value = object.get_property(object, &name, activation)?;
+                let key = index.to_string();
+                elements.push(
+                    self.serialize_property(activation, object, &key, value)?
+                        .unwrap_or_else(|| "null".to_string()),
+                );
+            }
+
+            let json = join_members(&elements, ('[', ']'), &self.gap, &self.indent, &stepback);
+            self.indent = stepback;
+            self.stack.pop();
+
+            return Ok(json);
+        }
+
+        let keys = match &self.replacer {
+            Replacer::Keys(keys) => keys.clone(),
+            _ => property_names(object),
+        };
+        let separator = if self.gap.is_empty() { ":" } else { ": " };
+        let mut members = Vec::new();
+        for key in keys {
+            let name =
+                QName::dynamic_name(AvmString::new(activation.context.gc_context, key.clone()));
+            let value = object.get_property(object, &name, activation)?;
+            if let Some(json) = self.serialize_property(activation, object, &key, value)? {
+                members.push(format!("{}{}{}", quote(&key), separator, json));
+            }
+        }
+
+        let json = join_members(&members, ('{', '}'), &self.gap, &self.indent, &stepback);
+        self.indent = stepback;
+        self.stack.pop();
+
+        Ok(json)
+    }
+}
+
+/// Construct `JSON`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package(""), "JSON"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::Sealed | ClassAttributes::Final);
+
+    let methods: &[(&str, NativeMethod<'gc>)] = &[("stringify", stringify), ("parse", parse)];
+
+    for &(name, method) in methods {
+        write.define_class_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gap, join_members, quote};
+    use crate::avm2::value::Value;
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote("plain"), r#""plain""#);
+        assert_eq!(quote(r#"say "hi"\"#), r#""say \"hi\"\\""#);
+        assert_eq!(quote("a\nb\tc\r\u{8}\u{c}"), r#""a\nb\tc\r\b\f""#);
+        assert_eq!(quote("\u{1}\u{1f}"), r#""\u0001\u001f""#);
+        assert_eq!(quote("é/✓"), "\"é/✓\"");
+    }
+
+    #[test]
+    fn gaps() {
+        assert_eq!(gap(&Value::Null), "");
+        assert_eq!(gap(&Value::Number(2.9)), "  ");
+        assert_eq!(gap(&Value::Number(-3.0)), "");
+        assert_eq!(gap(&Value::Number(std::f64::NAN)), "");
+        assert_eq!(gap(&Value::Integer(25)), " ".repeat(10));
+        assert_eq!(gap(&Value::String("\t".into())), "\t");
+        assert_eq!(gap(&Value::String("0123456789abc".into())), "0123456789");
+    }
+
+    #[test]
+    fn member_layout() {
+        let members = ["\"a\":1".to_string(), "\"b\":{}".to_string()];
+        assert_eq!(join_members(&[], ('{', '}'), "  ", "  ", ""), "{}");
+        assert_eq!(
+            join_members(&members, ('{', '}'), "", "", ""),
+            r#"{"a":1,"b":{}}"#
+        );
+        assert_eq!(
+            join_members(&members, ('{', '}'), "  ", "    ", "  "),
+            "{\n    \"a\":1,\n    \"b\":{}\n  }"
+        );
+        assert_eq!(join_members(&[], ('[', ']'), "", "", ""), "[]");
+        assert_eq!(
+            join_members(
+                &["1".to_string(), "[]".to_string()],
+                ('[', ']'),
+                "\t",
+                "\t",
+                ""
+            ),
+            "[\n\t1,\n\t[]\n]"
+        );
+    }
+}
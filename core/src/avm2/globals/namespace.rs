@@ -4,18 +4,39 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `Namespace`'s instance initializer.
+///
+/// With one argument, that argument is either another namespace or a URI.
+/// With two, they are the prefix and URI. Prefixes are not tracked, so only
+/// the URI is kept.
 pub fn instance_init<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("Namespace constructor is a stub.".into())
+    if let Some(this) = this {
+        let uri_value = match args {
+            [] => return Ok(Value::Undefined),
+            [uri] => uri.clone(),
+            [_prefix, uri, ..] => uri.clone(),
+        };
+
+        let uri = match uri_value {
+            Value::Object(o) if o.as_namespace().is_some() => o.as_namespace().unwrap().as_uri(),
+            value => value.coerce_to_string(activation)?,
+        };
+
+        if let Some(mut namespace) = this.as_namespace_mut(activation.context.gc_context) {
+            *namespace = Namespace::Namespace(uri);
+        }
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `Namespace`'s class initializer.
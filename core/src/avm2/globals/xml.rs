@@ -0,0 +1,567 @@
+//! `XML` impl
+//!
+//! Most methods here also serve `XMLList`, which treats a list of one value
+//! like that value.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::{self, E4XName, E4XNode};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{NamespaceObject, Object, TObject, XMLListObject, XMLObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::xml::{XMLDocument, XMLNode};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `XML`'s instance initializer.
+///
+/// The argument is parsed as markup, unless it is already XML, in which case
+/// it is copied.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mc = activation.context.gc_context;
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let nodes = match (&value, e4x::values_of(&value)) {
+            (Value::Undefined, _) | (Value::Null, _) => vec![],
+            (_, Some(values)) => values.iter().map(|v| v.deep_copy(mc)).collect(),
+            (_, None) => e4x::to_nodes(activation, &value)?,
+        };
+
+        let node = match nodes.as_slice() {
+            [] => XMLNode::new_text(mc, "", XMLDocument::new(mc)),
+            [node] => *node,
+            _ => {
+                return Err("TypeError: Error #1088: The markup in the document following the root element must be well-formed.".into())
+            }
+        };
+
+        if let Some(mut value) = this.as_xml_mut(mc) {
+            *value = E4XNode::Node(node);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The values of an `XML` or `XMLList` method's receiver.
+pub fn this_values<'gc>(this: Option<Object<'gc>>) -> Result<Vec<E4XNode<'gc>>, Error> {
+    this.and_then(e4x::object_values)
+        .ok_or_else(|| "TypeError: Error #1034: Type Coercion failed: not XML".into())
+}
+
+/// The single value of an `XML` method's receiver.
+///
+/// `XMLList` methods that only make sense for one value accept lists of
+/// exactly one.
+fn this_value<'gc>(this: Option<Object<'gc>>, method: &str) -> Result<E4XNode<'gc>, Error> {
+    match this_values(this)?.as_slice() {
+        [value] => Ok(value.clone()),
+        _ => Err(format!(
+            "TypeError: Error #1086: The {} method only works on lists containing one item.",
+            method
+        )
+        .into()),
+    }
+}
+
+/// Convert the optional name argument of a method to an E4X name.
+///
+/// A missing name matches everything.
+fn name_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    is_attribute: bool,
+) -> Result<E4XName, Error> {
+    let default_namespace = activation.default_xml_namespace().map(|ns| ns.to_string());
+
+    match args.get(0) {
+        None | Some(Value::Undefined) => Ok(E4XName::any(is_attribute)),
+        Some(name) => {
+            let name = name.coerce_to_string(activation)?;
+            let name = if is_attribute {
+                format!("@{}", name)
+            } else {
+                name.to_string()
+            };
+
+            Ok(E4XName::from_string(&name, default_namespace.as_deref()))
+        }
+    }
+}
+
+/// Collect values into a new `XMLList`.
+fn list<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: Vec<E4XNode<'gc>>,
+) -> Result<Value<'gc>, Error> {
+    Ok(XMLListObject::from_values(activation, values)?.into())
+}
+
+/// Implements `XML.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = this_values(this)?;
+    let string = e4x::list_string_value(&values, activation.avm2().xml_settings());
+
+    Ok(AvmString::new(activation.context.gc_context, string).into())
+}
+
+/// Implements `XML.toXMLString`.
+pub fn to_xml_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = this_values(this)?;
+    let string = e4x::list_xml_string(&values, activation.avm2().xml_settings());
+
+    Ok(AvmString::new(activation.context.gc_context, string).into())
+}
+
+/// Implements `XML.length`.
+///
+/// An `XML` object is a list of one.
+pub fn length<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((this_values(this)?.len() as u32).into())
+}
+
+/// Implements `XML.name`.
+///
+/// There is no `QName` class yet, so the name is returned in the string form
+/// a `QName` would convert to: `uri::localName`, or just the local name if it
+/// is in no namespace.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = this_value(this, "name")?;
+
+    Ok(match value.local_name() {
+        Some(local_name) => {
+            let uri = value.uri();
+            let name = if uri.is_empty() {
+                local_name
+            } else {
+                format!("{}::{}", uri, local_name)
+            };
+
+            AvmString::new(activation.context.gc_context, name).into()
+        }
+        None => Value::Null,
+    })
+}
+
+/// Implements `XML.localName`.
+pub fn local_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = this_value(this, "localName")?;
+
+    Ok(match value.local_name() {
+        Some(local_name) => AvmString::new(activation.context.gc_context, local_name).into(),
+        None => Value::Null,
+    })
+}
+
+/// Implements `XML.namespace`.
+///
+/// Only the namespace of the value itself is supported, not looking up a
+/// namespace by prefix.
+pub fn namespace<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = this_value(this, "namespace")?;
+    if value.local_name().is_none() {
+        return Ok(Value::Null);
+    }
+
+    let uri = AvmString::new(activation.context.gc_context, value.uri());
+    let proto = activation.avm2().prototypes().namespace;
+
+    Ok(NamespaceObject::from_namespace(
+        Namespace::Namespace(uri),
+        proto,
+        activation.context.gc_context,
+    )?
+    .into())
+}
+
+/// Implements `XML.nodeKind`.
+pub fn node_kind<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(this_value(this, "nodeKind")?.node_kind().into())
+}
+
+/// Implements `XML.parent`.
+pub fn parent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = this_values(this)?;
+    let first_parent = values.first().and_then(|v| v.parent());
+    let shared_parent = first_parent.filter(|first| {
+        values
+            .iter()
+            .all(|v| v.parent().map_or(false, |p| XMLNode::ptr_eq(p, *first)))
+    });
+
+    match shared_parent {
+        Some(parent) => Ok(XMLObject::from_value(activation, E4XNode::Node(parent))?.into()),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Implements `XML.hasSimpleContent`.
+pub fn has_simple_content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let values = this_values(this)?;
+    let is_simple = match values.as_slice() {
+        [value] => value.has_simple_content(),
+        values => values.iter().all(|v| v.element().is_none()),
+    };
+
+    Ok(is_simple.into())
+}
+
+/// Implements `XML.hasComplexContent`.
+pub fn has_complex_content<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let is_simple = has_simple_content(activation, this, args)?.coerce_to_boolean();
+    let is_element = this_values(this)?.iter().any(|v| v.element().is_some());
+
+    Ok((!is_simple && is_element).into())
+}
+
+/// Implements `XML.attribute`.
+pub fn attribute<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = name_arg(activation, args, true)?;
+    let attributes = e4x::children(&this_values(this)?, &name);
+
+    list(activation, attributes)
+}
+
+/// Implements `XML.attributes`.
+pub fn attributes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let attributes = e4x::children(&this_values(this)?, &E4XName::any(true));
+
+    list(activation, attributes)
+}
+
+/// Implements `XML.child`.
+///
+/// A numeric name picks a child by its position.
+pub fn child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = name_arg(activation, args, false)?;
+    let values = this_values(this)?;
+    let children = match name.index() {
+        Some(index) => values
+            .iter()
+            .filter_map(|v| {
+                e4x::children(&[v.clone()], &E4XName::any(false))
+                    .into_iter()
+                    .nth(index)
+            })
+            .collect(),
+        None => e4x::children(&values, &name),
+    };
+
+    list(activation, children)
+}
+
+/// Implements `XML.children`.
+pub fn children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let children = e4x::children(&this_values(this)?, &E4XName::any(false));
+
+    list(activation, children)
+}
+
+/// Implements `XML.elements`.
+pub fn elements<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = name_arg(activation, args, false)?;
+    let elements = e4x::children(&this_values(this)?, &name)
+        .into_iter()
+        .filter(|v| v.element().is_some())
+        .collect();
+
+    list(activation, elements)
+}
+
+/// Implements `XML.descendants`.
+pub fn descendants<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = name_arg(activation, args, false)?;
+    let descendants = e4x::descendants(&this_values(this)?, &name);
+
+    list(activation, descendants)
+}
+
+/// Implements `XML.text`.
+pub fn text<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let text = e4x::children(&this_values(this)?, &E4XName::any(false))
+        .into_iter()
+        .filter(|v| v.node_kind() == "text")
+        .collect();
+
+    list(activation, text)
+}
+
+/// Implements `XML.comments`.
+///
+/// Comments are discarded when markup is parsed, so this only finds comments
+/// that were inserted afterwards.
+pub fn comments<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let comments = e4x::children(&this_values(this)?, &E4XName::any(false))
+        .into_iter()
+        .filter(|v| v.node_kind() == "comment")
+        .collect();
+
+    list(activation, comments)
+}
+
+/// Insert a value into the children of an `XML` method's receiver.
+fn insert_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    method: &str,
+    at_start: bool,
+) -> Result<Value<'gc>, Error> {
+    let mut element = match this_value(this, method)?.element() {
+        Some(element) => element,
+        None => return Ok(this.map(|t| t.into()).unwrap_or(Value::Undefined)),
+    };
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let nodes = e4x::to_nodes(activation, &value)?;
+    let mc = activation.context.gc_context;
+
+    let position = if at_start { 0 } else { element.children_len() };
+    for (offset, node) in nodes.into_iter().enumerate() {
+        element.insert_child(mc, position + offset, node)?;
+    }
+
+    Ok(this.map(|t| t.into()).unwrap_or(Value::Undefined))
+}
+
+/// Implements `XML.appendChild`.
+pub fn append_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    insert_children(activation, this, args, "appendChild", false)
+}
+
+/// Implements `XML.prependChild`.
+pub fn prepend_child<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    insert_children(activation, this, args, "prependChild", true)
+}
+
+/// Implements `XML.copy`.
+pub fn copy<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mc = activation.context.gc_context;
+    let copies: Vec<_> = this_values(this)?
+        .iter()
+        .map(|v| E4XNode::Node(v.deep_copy(mc)))
+        .collect();
+
+    match (this.and_then(|t| t.as_xml().map(|_| ())), copies.first()) {
+        (Some(()), Some(copy)) => Ok(XMLObject::from_value(activation, copy.clone())?.into()),
+        _ => list(activation, copies),
+    }
+}
+
+/// Implements `XML.prettyPrinting`'s getter.
+pub fn pretty_printing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.avm2().xml_settings().pretty_printing.into())
+}
+
+/// Implements `XML.prettyPrinting`'s setter.
+pub fn set_pretty_printing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let pretty_printing = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_boolean();
+    activation.avm2().xml_settings_mut().pretty_printing = pretty_printing;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XML.prettyIndent`'s getter.
+pub fn pretty_indent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.avm2().xml_settings().pretty_indent.into())
+}
+
+/// Implements `XML.prettyIndent`'s setter.
+///
+/// Negative indents are treated as no indent.
+pub fn set_pretty_indent<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let pretty_indent = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    activation.avm2().xml_settings_mut().pretty_indent = pretty_indent.max(0) as u32;
+
+    Ok(Value::Undefined)
+}
+
+/// The methods shared by `XML` and `XMLList`.
+pub fn shared_methods<'gc>() -> Vec<(&'static str, NativeMethod<'gc>)> {
+    vec![
+        ("toString", to_string),
+        ("toXMLString", to_xml_string),
+        ("length", length),
+        ("name", name),
+        ("localName", local_name),
+        ("namespace", namespace),
+        ("nodeKind", node_kind),
+        ("parent", parent),
+        ("hasSimpleContent", has_simple_content),
+        ("hasComplexContent", has_complex_content),
+        ("attribute", attribute),
+        ("attributes", attributes),
+        ("child", child),
+        ("children", children),
+        ("elements", elements),
+        ("descendants", descendants),
+        ("text", text),
+        ("comments", comments),
+        ("copy", copy),
+    ]
+}
+
+/// Construct `XML`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "XML"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, NativeMethod<'gc>)] = &[
+        ("prettyPrinting", pretty_printing, set_pretty_printing),
+        ("prettyIndent", pretty_indent, set_pretty_indent),
+    ];
+
+    for &(name, getter, setter) in accessors {
+        write.define_class_trait(Trait::from_getter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(getter),
+        ));
+        write.define_class_trait(Trait::from_setter(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(setter),
+        ));
+    }
+
+    let mut methods = shared_methods();
+    methods.push(("appendChild", append_child));
+    methods.push(("prependChild", prepend_child));
+
+    for (name, method) in methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,71 @@
+//! `XMLList` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::{self, E4XNode};
+use crate::avm2::globals::xml;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `XMLList`'s instance initializer.
+///
+/// Unlike `XML`, any number of top-level nodes is accepted.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let values = match (&value, e4x::values_of(&value)) {
+            (Value::Undefined, _) | (Value::Null, _) => vec![],
+            (_, Some(values)) => values,
+            (_, None) => e4x::to_nodes(activation, &value)?
+                .into_iter()
+                .map(E4XNode::Node)
+                .collect(),
+        };
+
+        if let Some(mut list) = this.as_xml_list_mut(activation.context.gc_context) {
+            *list = values;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLList`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `XMLList`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public_namespace(), "XMLList"),
+        Some(QName::new(Namespace::public_namespace(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    for (name, method) in xml::shared_methods() {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public_namespace(), name),
+            Method::from_builtin(method),
+        ));
+    }
+
+    class
+}
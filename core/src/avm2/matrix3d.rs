@@ -0,0 +1,318 @@
+//! Backing storage for `flash.geom.Matrix3D`
+
+use gc_arena::Collect;
+
+/// A 4x4 transformation matrix, stored in the same column-major order as
+/// `Matrix3D.rawData`.
+///
+/// The element at row `r` and column `c` is stored at index `c * 4 + r`, and
+/// the matrix transforms column vectors, so the translation lives in
+/// elements 12, 13 and 14.
+#[derive(Clone, Copy, Collect, Debug, PartialEq)]
+#[collect(require_static)]
+pub struct Matrix3D {
+    raw: [f64; 16],
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ],
+    };
+
+    pub fn from_raw(raw: [f64; 16]) -> Self {
+        Self { raw }
+    }
+
+    pub fn raw(&self) -> &[f64; 16] {
+        &self.raw
+    }
+
+    fn get(&self, row: usize, col: usize) -> f64 {
+        self.raw[col * 4 + row]
+    }
+
+    /// A translation by the given offsets.
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw[12] = x;
+        matrix.raw[13] = y;
+        matrix.raw[14] = z;
+        matrix
+    }
+
+    /// A scale by the given factors.
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.raw[0] = x;
+        matrix.raw[5] = y;
+        matrix.raw[10] = z;
+        matrix
+    }
+
+    /// A rotation of `degrees` around `axis`, which passes through `pivot`.
+    ///
+    /// Returns the identity matrix if `axis` has no length.
+    pub fn rotation(degrees: f64, axis: [f64; 3], pivot: [f64; 3]) -> Self {
+        let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if length == 0.0 || !length.is_finite() {
+            return Self::IDENTITY;
+        }
+        let (x, y, z) = (axis[0] / length, axis[1] / length, axis[2] / length);
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let t = 1.0 - cos;
+
+        let rotation = Self::from_raw([
+            t * x * x + cos,
+            t * x * y + sin * z,
+            t * x * z - sin * y,
+            0.0,
+            t * x * y - sin * z,
+            t * y * y + cos,
+            t * y * z + sin * x,
+            0.0,
+            t * x * z + sin * y,
+            t * y * z - sin * x,
+            t * z * z + cos,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ]);
+
+        Self::translation(pivot[0], pivot[1], pivot[2])
+            .multiply(&rotation)
+            .multiply(&Self::translation(-pivot[0], -pivot[1], -pivot[2]))
+    }
+
+    /// Returns `self * rhs`: the transform that applies `rhs`, then `self`.
+    pub fn multiply(&self, rhs: &Self) -> Self {
+        let mut raw = [0.0; 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                raw[col * 4 + row] = (0..4).map(|i| self.get(row, i) * rhs.get(i, col)).sum();
+            }
+        }
+        Self { raw }
+    }
+
+    /// Apply `lhs` after this transform, as `Matrix3D.append` does.
+    pub fn append(&mut self, lhs: &Self) {
+        *self = lhs.multiply(self);
+    }
+
+    /// Apply `rhs` before this transform, as `Matrix3D.prepend` does.
+    pub fn prepend(&mut self, rhs: &Self) {
+        *self = self.multiply(rhs);
+    }
+
+    /// Transform a point, including translation.
+    pub fn transform_point(&self, point: [f64; 3]) -> [f64; 4] {
+        let mut result = [0.0; 4];
+        for (row, value) in result.iter_mut().enumerate() {
+            *value = self.get(row, 0) * point[0]
+                + self.get(row, 1) * point[1]
+                + self.get(row, 2) * point[2]
+                + self.get(row, 3);
+        }
+        result
+    }
+
+    /// Transform a direction, ignoring translation.
+    pub fn transform_direction(&self, direction: [f64; 3]) -> [f64; 3] {
+        let mut result = [0.0; 3];
+        for (row, value) in result.iter_mut().enumerate() {
+            *value = self.get(row, 0) * direction[0]
+                + self.get(row, 1) * direction[1]
+                + self.get(row, 2) * direction[2];
+        }
+        result
+    }
+
+    pub fn transpose(&mut self) {
+        let mut raw = [0.0; 16];
+        for col in 0..4 {
+            for row in 0..4 {
+                raw[row * 4 + col] = self.get(row, col);
+            }
+        }
+        self.raw = raw;
+    }
+
+    /// The cofactor expansion of each element, used by both `determinant` and
+    /// `invert`.
+    fn adjugate(&self) -> [f64; 16] {
+        let m = &self.raw;
+        let mut inv = [0.0; 16];
+
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+            + m[9] * m[7] * m[14]
+            + m[13] * m[6] * m[11]
+            - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+            - m[8] * m[7] * m[14]
+            - m[12] * m[6] * m[11]
+            + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+            + m[8] * m[7] * m[13]
+            + m[12] * m[5] * m[11]
+            - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+            - m[8] * m[6] * m[13]
+            - m[12] * m[5] * m[10]
+            + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+            - m[9] * m[3] * m[14]
+            - m[13] * m[2] * m[11]
+            + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+            + m[8] * m[3] * m[14]
+            + m[12] * m[2] * m[11]
+            - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+            - m[8] * m[3] * m[13]
+            - m[12] * m[1] * m[11]
+            + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+            + m[8] * m[2] * m[13]
+            + m[12] * m[1] * m[10]
+            - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+            + m[5] * m[3] * m[14]
+            + m[13] * m[2] * m[7]
+            - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+            - m[4] * m[3] * m[14]
+            - m[12] * m[2] * m[7]
+            + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+            + m[4] * m[3] * m[13]
+            + m[12] * m[1] * m[7]
+            - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+            - m[4] * m[2] * m[13]
+            - m[12] * m[1] * m[6]
+            + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+            - m[5] * m[3] * m[10]
+            - m[9] * m[2] * m[7]
+            + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+            + m[4] * m[3] * m[10]
+            + m[8] * m[2] * m[7]
+            - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+            - m[4] * m[3] * m[9]
+            - m[8] * m[1] * m[7]
+            + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+            + m[4] * m[2] * m[9]
+            + m[8] * m[1] * m[6]
+            - m[8] * m[2] * m[5];
+
+        inv
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let inv = self.adjugate();
+        self.raw[0] * inv[0] + self.raw[1] * inv[4] + self.raw[2] * inv[8] + self.raw[3] * inv[12]
+    }
+
+    /// Invert this matrix in place.
+    ///
+    /// Returns `false` and leaves the matrix unchanged if it is not invertible.
+    pub fn invert(&mut self) -> bool {
+        let inv = self.adjugate();
+        let det = self.raw[0] * inv[0]
+            + self.raw[1] * inv[4]
+            + self.raw[2] * inv[8]
+            + self.raw[3] * inv[12];
+        if det == 0.0 || !det.is_finite() {
+            return false;
+        }
+
+        for (value, cofactor) in self.raw.iter_mut().zip(inv.iter()) {
+            *value = cofactor / det;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f64; 4], b: [f64; 4]) {
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() < 1e-9, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn append_applies_after() {
+        let mut matrix = Matrix3D::scale(2.0, 2.0, 2.0);
+        matrix.append(&Matrix3D::translation(10.0, 0.0, 0.0));
+        assert_close(
+            matrix.transform_point([1.0, 1.0, 1.0]),
+            [12.0, 2.0, 2.0, 1.0],
+        );
+
+        let mut matrix = Matrix3D::scale(2.0, 2.0, 2.0);
+        matrix.prepend(&Matrix3D::translation(10.0, 0.0, 0.0));
+        assert_close(
+            matrix.transform_point([1.0, 1.0, 1.0]),
+            [22.0, 2.0, 2.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn rotation_about_axes() {
+        let matrix = Matrix3D::rotation(90.0, [0.0, 0.0, 1.0], [0.0, 0.0, 0.0]);
+        assert_close(
+            matrix.transform_point([1.0, 0.0, 0.0]),
+            [0.0, 1.0, 0.0, 1.0],
+        );
+
+        let matrix = Matrix3D::rotation(90.0, [0.0, 1.0, 0.0], [0.0, 0.0, 0.0]);
+        assert_close(
+            matrix.transform_point([1.0, 0.0, 0.0]),
+            [0.0, 0.0, -1.0, 1.0],
+        );
+
+        let matrix = Matrix3D::rotation(180.0, [0.0, 0.0, 1.0], [5.0, 0.0, 0.0]);
+        assert_close(
+            matrix.transform_point([0.0, 0.0, 0.0]),
+            [10.0, 0.0, 0.0, 1.0],
+        );
+    }
+
+    #[test]
+    fn invert_round_trip() {
+        let mut matrix = Matrix3D::rotation(30.0, [1.0, 1.0, 0.0], [0.0, 0.0, 0.0]);
+        matrix.append(&Matrix3D::translation(5.0, -3.0, 8.0));
+        matrix.prepend(&Matrix3D::scale(2.0, 3.0, 4.0));
+
+        let mut inverse = matrix;
+        assert!(inverse.invert());
+        let product = matrix.multiply(&inverse);
+        for (a, b) in product.raw().iter().zip(Matrix3D::IDENTITY.raw().iter()) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        assert!((matrix.determinant() - 24.0).abs() < 1e-9);
+
+        let mut singular = Matrix3D::scale(0.0, 1.0, 1.0);
+        assert!(!singular.invert());
+        assert_eq!(singular, Matrix3D::scale(0.0, 1.0, 1.0));
+    }
+}
@@ -102,6 +102,20 @@ impl<'gc> BytecodeMethod<'gc> {
         &self.abc.0.methods.get(self.abc_method as usize).unwrap()
     }
 
+    /// The name of this method as shown in stack traces, such as
+    /// `pkg::Class/method`.
+    pub fn display_name(&self) -> String {
+        let name = self.method().name.0;
+        let name = name
+            .checked_sub(1)
+            .and_then(|index| self.abc.0.constant_pool.strings.get(index as usize));
+
+        match name {
+            Some(name) if !name.is_empty() => display_name(name),
+            _ => "Function/<anonymous>".to_string(),
+        }
+    }
+
     /// Get a reference to the ABC method body entry this refers to.
     ///
     /// Some methods do not have bodies; this returns `None` in that case.
@@ -114,6 +128,18 @@ impl<'gc> BytecodeMethod<'gc> {
     }
 }
 
+/// Compilers name methods like `pkg:Class/method`, but stack traces separate
+/// the package from the class with `::`.
+fn display_name(name: &str) -> String {
+    let class_end = name.find('/').unwrap_or(name.len());
+    match name[..class_end].rfind(':') {
+        Some(colon) if !name[..colon].ends_with(':') => {
+            format!("{}::{}", &name[..colon], &name[colon + 1..])
+        }
+        _ => name.to_string(),
+    }
+}
+
 /// An uninstantiated method that can either be natively implemented or sourced
 /// from an ABC file.
 #[derive(Clone)]
@@ -177,3 +203,26 @@ impl<'gc> Method<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn method_display_names() {
+        assert_eq!(display_name("Main/init"), "Main/init");
+        assert_eq!(
+            display_name("com.example:Main/init"),
+            "com.example::Main/init"
+        );
+        assert_eq!(
+            display_name("com.example::Main/init"),
+            "com.example::Main/init"
+        );
+        assert_eq!(
+            display_name("com.example:Main/private:helper"),
+            "com.example::Main/private:helper"
+        );
+        assert_eq!(display_name("com.example:Main"), "com.example::Main");
+    }
+}
@@ -167,6 +167,44 @@ impl<'gc> QName<'gc> {
     pub fn namespace(&self) -> &Namespace<'gc> {
         &self.ns
     }
+
+    /// Converts this `QName` to a fully qualified name, as returned by
+    /// `getQualifiedClassName`.
+    ///
+    /// Names in the public namespace are returned as-is; everything else is
+    /// written as `package::Name`.
+    pub fn to_qualified_name(&self, mc: MutationContext<'gc, '_>) -> AvmString<'gc> {
+        let uri = self.ns.as_uri();
+        if uri.is_empty() {
+            self.name
+        } else {
+            AvmString::new(mc, format!("{}::{}", uri, self.name))
+        }
+    }
+
+    /// Parse a fully qualified name, such as one produced by
+    /// `to_qualified_name`, back into a `QName`.
+    ///
+    /// Both `package::Name` and `package.Name` forms are accepted, matching
+    /// `getDefinitionByName`.
+    pub fn from_qualified_name(name: &str, mc: MutationContext<'gc, '_>) -> Self {
+        if let Some(index) = name.rfind("::") {
+            QName::new(
+                Namespace::package(AvmString::new(mc, name[..index].to_string())),
+                AvmString::new(mc, name[index + 2..].to_string()),
+            )
+        } else if let Some(index) = name.rfind('.') {
+            QName::new(
+                Namespace::package(AvmString::new(mc, name[..index].to_string())),
+                AvmString::new(mc, name[index + 1..].to_string()),
+            )
+        } else {
+            QName::new(
+                Namespace::public_namespace(),
+                AvmString::new(mc, name.to_string()),
+            )
+        }
+    }
 }
 
 /// A `Multiname` consists of a name which could be resolved in one or more
@@ -176,11 +214,15 @@ impl<'gc> QName<'gc> {
 /// process consists of searching each name space for a given name.
 ///
 /// The existence of a `name` of `None` indicates the `Any` name.
+///
+/// Attribute names, such as `@id`, are only meaningful to E4X `XML` objects;
+/// every other object ignores the distinction.
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub struct Multiname<'gc> {
     ns: Vec<Namespace<'gc>>,
     name: Option<AvmString<'gc>>,
+    is_attribute: bool,
 }
 
 impl<'gc> Multiname<'gc> {
@@ -235,8 +277,10 @@ impl<'gc> Multiname<'gc> {
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
+        let abc_multiname = abc_multiname?;
+        let is_attribute = is_attribute_multiname(abc_multiname);
 
-        Ok(match abc_multiname? {
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -246,6 +290,7 @@ impl<'gc> Multiname<'gc> {
                     )?],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQName { name } | AbcMultiname::RTQNameA { name } => {
@@ -254,6 +299,7 @@ impl<'gc> Multiname<'gc> {
                     ns: vec![ns],
                     name: translation_unit
                         .pool_string_option(name.0, activation.context.gc_context)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::RTQNameL | AbcMultiname::RTQNameLA => {
@@ -262,6 +308,7 @@ impl<'gc> Multiname<'gc> {
                 Self {
                     ns: vec![ns],
                     name: Some(name),
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -278,6 +325,7 @@ impl<'gc> Multiname<'gc> {
                     activation.context.gc_context,
                 )?,
                 name: translation_unit.pool_string_option(name.0, activation.context.gc_context)?,
+                is_attribute,
             },
             AbcMultiname::MultinameL { namespace_set }
             | AbcMultiname::MultinameLA { namespace_set } => {
@@ -289,6 +337,7 @@ impl<'gc> Multiname<'gc> {
                         activation.context.gc_context,
                     )?,
                     name: Some(name),
+                    is_attribute,
                 }
             }
         })
@@ -314,8 +363,10 @@ impl<'gc> Multiname<'gc> {
             .multinames
             .get(actual_index)
             .ok_or_else(|| format!("Unknown multiname constant {}", multiname_index.0).into());
+        let abc_multiname = abc_multiname?;
+        let is_attribute = is_attribute_multiname(abc_multiname);
 
-        Ok(match abc_multiname? {
+        Ok(match abc_multiname {
             AbcMultiname::QName { namespace, name } | AbcMultiname::QNameA { namespace, name } => {
                 Self {
                     ns: vec![Namespace::from_abc_namespace(
@@ -324,6 +375,7 @@ impl<'gc> Multiname<'gc> {
                         mc,
                     )?],
                     name: translation_unit.pool_string_option(name.0, mc)?,
+                    is_attribute,
                 }
             }
             AbcMultiname::Multiname {
@@ -336,6 +388,7 @@ impl<'gc> Multiname<'gc> {
             } => Self {
                 ns: Self::abc_namespace_set(translation_unit, namespace_set.clone(), mc)?,
                 name: translation_unit.pool_string_option(name.0, mc)?,
+                is_attribute,
             },
             _ => return Err(format!("Multiname {} is not static", multiname_index.0).into()),
         })
@@ -346,6 +399,7 @@ impl<'gc> Multiname<'gc> {
         Self {
             ns: vec![Namespace::Any],
             name: None,
+            is_attribute: false,
         }
     }
 
@@ -356,6 +410,23 @@ impl<'gc> Multiname<'gc> {
     pub fn local_name(&self) -> Option<AvmString<'gc>> {
         self.name
     }
+
+    /// Whether this names an E4X attribute rather than a property.
+    pub fn is_attribute(&self) -> bool {
+        self.is_attribute
+    }
+}
+
+/// Whether an ABC multiname is one of the attribute (`...A`) kinds.
+fn is_attribute_multiname(multiname: &AbcMultiname) -> bool {
+    matches!(
+        multiname,
+        AbcMultiname::QNameA { .. }
+            | AbcMultiname::RTQNameA { .. }
+            | AbcMultiname::RTQNameLA
+            | AbcMultiname::MultinameA { .. }
+            | AbcMultiname::MultinameLA { .. }
+    )
 }
 
 impl<'gc> From<QName<'gc>> for Multiname<'gc> {
@@ -363,6 +434,7 @@ impl<'gc> From<QName<'gc>> for Multiname<'gc> {
         Self {
             ns: vec![q.ns],
             name: Some(q.name),
+            is_attribute: false,
         }
     }
 }
@@ -0,0 +1,149 @@
+//! Sending the events of a `flash.net.NetStream` to the movie.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::events::dispatch_event;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::flv::ScriptDataValue;
+use crate::net_stream::StreamEvent;
+
+/// Dispatch the events of a stream as `NetStatusEvent`s, and call the methods of its client
+/// named by script data tags.
+pub fn send_net_stream_events<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    target: Object<'gc>,
+    client: Object<'gc>,
+    events: Vec<StreamEvent>,
+) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    for event in events {
+        let result = match event {
+            StreamEvent::Status(code, level) => {
+                dispatch_net_status(&mut activation, target, code, level)
+            }
+            StreamEvent::Call(name, value) => call_client(&mut activation, client, &name, value),
+        };
+        if let Err(e) = result {
+            log::error!("Unhandled error in NetStream event: {}", e);
+        }
+    }
+}
+
+/// Dispatch a `NetStatusEvent` whose `info` object holds the given code and level.
+pub fn dispatch_net_status<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    code: &'static str,
+    level: &'static str,
+) -> Result<(), Error> {
+    let mut info = ScriptObject::object(
+        activation.context.gc_context,
+        activation.avm2().prototypes().object,
+    );
+    info.set_property(
+        info,
+        &QName::new(Namespace::public_namespace(), "code"),
+        code.into(),
+        activation,
+    )?;
+    info.set_property(
+        info,
+        &QName::new(Namespace::public_namespace(), "level"),
+        level.into(),
+        activation,
+    )?;
+
+    let mut globals = activation.avm2().globals();
+    let mut constr = globals
+        .get_property(
+            globals,
+            &QName::new(Namespace::package("flash.events"), "NetStatusEvent"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let proto = constr
+        .get_property(
+            constr,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let args = [
+        Value::from("netStatus"),
+        false.into(),
+        false.into(),
+        info.into(),
+    ];
+    let event = proto.construct(activation, &args)?;
+    constr.call(Some(event), &args, activation, event.proto())?;
+
+    dispatch_event(activation, target, event)?;
+    Ok(())
+}
+
+/// Call the method of a `NetStream` client named by a script data tag.
+fn call_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut client: Object<'gc>,
+    name: &str,
+    value: ScriptDataValue,
+) -> Result<(), Error> {
+    let name = AvmString::new(activation.context.gc_context, name.to_string());
+    let method = client.get_property(
+        client,
+        &QName::new(Namespace::public_namespace(), name),
+        activation,
+    )?;
+    if let Value::Object(method) = method {
+        let value = script_data_to_value(activation, value)?;
+        method.call(Some(client), &[value], activation, None)?;
+    }
+    Ok(())
+}
+
+fn script_data_to_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: ScriptDataValue,
+) -> Result<Value<'gc>, Error> {
+    let properties = match value {
+        ScriptDataValue::Number(n) | ScriptDataValue::Date(n) => return Ok(n.into()),
+        ScriptDataValue::Boolean(b) => return Ok(b.into()),
+        ScriptDataValue::String(s) => {
+            return Ok(AvmString::new(activation.context.gc_context, s).into())
+        }
+        ScriptDataValue::Null => return Ok(Value::Null),
+        ScriptDataValue::Undefined => return Ok(Value::Undefined),
+        ScriptDataValue::Object(properties) => properties,
+        // TODO: Build an `Array` once AVM2 has one.
+        ScriptDataValue::StrictArray(values) => {
+            let length = values.len();
+            let mut properties: Vec<_> = values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| (i.to_string(), value))
+                .collect();
+            properties.push(("length".to_string(), ScriptDataValue::Number(length as f64)));
+            properties
+        }
+    };
+
+    let mut object = ScriptObject::object(
+        activation.context.gc_context,
+        activation.avm2().prototypes().object,
+    );
+    for (name, value) in properties {
+        let value = script_data_to_value(activation, value)?;
+        let name = AvmString::new(activation.context.gc_context, name);
+        object.set_property(
+            object,
+            &QName::new(Namespace::public_namespace(), name),
+            value,
+            activation,
+        )?;
+    }
+    Ok(object.into())
+}
@@ -1,29 +1,60 @@
 //! AVM2 objects.
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bitmapdata::BitmapDataStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::dictionary::DictionaryStorage;
+use crate::avm2::domain::Domain;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::events::{DispatchList, Event};
 use crate::avm2::function::Executable;
+use crate::avm2::matrix3d::Matrix3D;
 use crate::avm2::names::{Multiname, Namespace, QName};
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::{Hint, Value};
 use crate::avm2::Error;
+use crate::display_object::DisplayObject;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
-use std::cell::Ref;
+use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
+mod array_object;
+mod bitmapdata_object;
+mod bytearray_object;
 mod custom_object;
+mod dictionary_object;
+mod dispatch_object;
+mod domain_object;
+mod event_object;
 mod function_object;
+mod matrix3d_object;
 mod namespace_object;
 mod primitive_object;
 mod script_object;
-
+mod stage_object;
+mod xml_list_object;
+mod xml_object;
+
+pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bitmapdata_object::BitmapDataObject;
+pub use crate::avm2::object::bytearray_object::ByteArrayObject;
+pub use crate::avm2::object::dictionary_object::DictionaryObject;
+pub use crate::avm2::object::dispatch_object::DispatchObject;
+pub use crate::avm2::object::domain_object::DomainObject;
+pub use crate::avm2::object::event_object::EventObject;
 pub use crate::avm2::object::function_object::FunctionObject;
+pub use crate::avm2::object::matrix3d_object::Matrix3DObject;
 pub use crate::avm2::object::namespace_object::NamespaceObject;
 pub use crate::avm2::object::primitive_object::PrimitiveObject;
 pub use crate::avm2::object::script_object::ScriptObject;
+pub use crate::avm2::object::stage_object::StageObject;
+pub use crate::avm2::object::xml_list_object::XMLListObject;
+pub use crate::avm2::object::xml_object::XMLObject;
 
 /// Represents an object that can be directly interacted with by the AVM2
 /// runtime.
@@ -35,6 +66,17 @@ pub use crate::avm2::object::script_object::ScriptObject;
         FunctionObject(FunctionObject<'gc>),
         PrimitiveObject(PrimitiveObject<'gc>),
         NamespaceObject(NamespaceObject<'gc>),
+        ByteArrayObject(ByteArrayObject<'gc>),
+        ArrayObject(ArrayObject<'gc>),
+        EventObject(EventObject<'gc>),
+        DispatchObject(DispatchObject<'gc>),
+        Matrix3DObject(Matrix3DObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
+        DomainObject(DomainObject<'gc>),
+        StageObject(StageObject<'gc>),
+        DictionaryObject(DictionaryObject<'gc>),
+        XMLObject(XMLObject<'gc>),
+        XMLListObject(XMLListObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -708,7 +750,7 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
 
             if check_interfaces {
                 for interface in proto.interfaces() {
-                    if Object::ptr_eq(interface, type_proto) {
+                    if interface_implements(interface, type_proto) {
                         return Ok(true);
                     }
                 }
@@ -735,6 +777,143 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_namespace(&self) -> Option<Ref<Namespace<'gc>>> {
         None
     }
+
+    /// Unwrap this object as a mutable namespace.
+    fn as_namespace_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Namespace<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's array storage, if the object is an `Array`.
+    fn as_array_storage(&self) -> Option<Ref<ArrayStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's array storage mutably, if the object is an
+    /// `Array`.
+    fn as_array_storage_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<ArrayStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's byte array storage, if the object is a
+    /// `ByteArray`.
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object's byte array storage mutably, if the object is a
+    /// `ByteArray`.
+    fn as_bytearray_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        None
+    }
+
+    /// Unwrap this object's matrix, if the object is a `Matrix3D`.
+    fn as_matrix3d(&self) -> Option<Ref<Matrix3D>> {
+        None
+    }
+
+    /// Unwrap this object's matrix mutably, if the object is a `Matrix3D`.
+    fn as_matrix3d_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Matrix3D>> {
+        None
+    }
+
+    /// Unwrap this object's pixels, if the object is a `BitmapData`.
+    fn as_bitmap_data(&self) -> Option<Ref<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object's pixels mutably, if the object is a `BitmapData`.
+    fn as_bitmap_data_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<BitmapDataStorage>> {
+        None
+    }
+
+    /// Unwrap this object's object-keyed entries, if the object is a
+    /// `Dictionary`.
+    fn as_dictionary(&self) -> Option<Ref<DictionaryStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's object-keyed entries mutably, if the object is a
+    /// `Dictionary`.
+    fn as_dictionary_mut(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<DictionaryStorage<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's node or attribute, if the object is `XML`.
+    fn as_xml(&self) -> Option<Ref<E4XNode<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's node or attribute mutably, if the object is
+    /// `XML`.
+    fn as_xml_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<E4XNode<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's values, if the object is an `XMLList`.
+    fn as_xml_list(&self) -> Option<Ref<Vec<E4XNode<'gc>>>> {
+        None
+    }
+
+    /// Unwrap this object's values mutably, if the object is an `XMLList`.
+    fn as_xml_list_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<E4XNode<'gc>>>> {
+        None
+    }
+
+    /// Unwrap this object's domain, if the object is an `ApplicationDomain`.
+    fn as_application_domain(&self) -> Option<Domain<'gc>> {
+        None
+    }
+
+    /// Set the domain of an `ApplicationDomain`.
+    ///
+    /// This does nothing if the object is not an `ApplicationDomain`.
+    fn init_application_domain(&self, _mc: MutationContext<'gc, '_>, _domain: Domain<'gc>) {}
+
+    /// Unwrap this object's display object, if it represents one.
+    fn as_display_object(&self) -> Option<DisplayObject<'gc>> {
+        None
+    }
+
+    /// Bind this object to the display object it represents.
+    ///
+    /// This does nothing if the object cannot represent display objects.
+    fn init_display_object(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+        _display_object: DisplayObject<'gc>,
+    ) {
+    }
+
+    /// Unwrap this object as an event.
+    fn as_event(&self) -> Option<Ref<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable event.
+    fn as_event_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<Event<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's list of event handlers, if it is an event
+    /// dispatcher.
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        None
+    }
+
+    /// Unwrap this object's list of event handlers mutably, if it is an event
+    /// dispatcher.
+    fn as_dispatch_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -744,3 +923,32 @@ impl<'gc> Object<'gc> {
         a.as_ptr() == b.as_ptr()
     }
 }
+
+/// Determine if the interface prototype `interface` is, or extends, the type
+/// whose prototype is `type_proto`.
+///
+/// Interfaces are matched by name as well as by identity, since a movie
+/// loaded into a child domain may implement an interface whose class object
+/// is defined again in another domain.
+fn interface_implements<'gc>(interface: Object<'gc>, type_proto: Object<'gc>) -> bool {
+    if Object::ptr_eq(interface, type_proto) {
+        return true;
+    }
+
+    if let (Some(interface_class), Some(type_class)) = (interface.as_class(), type_proto.as_class())
+    {
+        let interface_class = interface_class.read();
+        let type_class = type_class.read();
+        if interface_class.is_interface()
+            && type_class.is_interface()
+            && interface_class.name() == type_class.name()
+        {
+            return true;
+        }
+    }
+
+    interface
+        .interfaces()
+        .into_iter()
+        .any(|parent| interface_implements(parent, type_proto))
+}
@@ -0,0 +1,246 @@
+//! Array-structured objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object_base;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which stores numerical properties in an array.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct ArrayObject<'gc>(GcCell<'gc, ArrayObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct ArrayObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// Array-structured properties
+    array: ArrayStorage<'gc>,
+}
+
+/// Determine if a property name refers to an array element, and if so, which
+/// one.
+///
+/// Only public names that are the canonical string form of an unsigned
+/// integer are array elements; `"01"` is an ordinary dynamic property.
+fn array_index(name: &QName<'_>) -> Option<usize> {
+    if name.namespace() != &Namespace::public_namespace() {
+        return None;
+    }
+
+    let index = name.local_name().parse::<u32>().ok()?;
+    if index.to_string() != name.local_name().as_str() {
+        return None;
+    }
+
+    Some(index as usize)
+}
+
+impl<'gc> ArrayObject<'gc> {
+    /// Construct the prototype for a class whose instances are arrays.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(ArrayObject(GcCell::allocate(
+            mc,
+            ArrayObjectData {
+                base,
+                array: ArrayStorage::new(0),
+            },
+        ))
+        .into())
+    }
+
+    /// Construct an array holding the given values, using the system `Array`
+    /// prototype.
+    pub fn from_array(
+        array: ArrayStorage<'gc>,
+        proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass);
+
+        ArrayObject(GcCell::allocate(mc, ArrayObjectData { base, array })).into()
+    }
+}
+
+impl<'gc> TObject<'gc> for ArrayObject<'gc> {
+    impl_avm2_custom_object_base!(base);
+
+    fn get_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<Value<'gc>, Error> {
+        let read = self.0.read();
+
+        if let Some(index) = array_index(name) {
+            return Ok(read.array.get(index).unwrap_or(Value::Undefined));
+        }
+
+        let rv = read.base.get_property_local(reciever, name, activation)?;
+
+        drop(read);
+
+        rv.resolve(activation)
+    }
+
+    fn set_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(activation.context.gc_context);
+
+        if let Some(index) = array_index(name) {
+            write.array.set(index, value);
+
+            return Ok(());
+        }
+
+        let rv = write
+            .base
+            .set_property_local(reciever, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn init_property_local(
+        self,
+        reciever: Object<'gc>,
+        name: &QName<'gc>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let mut write = self.0.write(activation.context.gc_context);
+
+        if let Some(index) = array_index(name) {
+            write.array.set(index, value);
+
+            return Ok(());
+        }
+
+        let rv = write
+            .base
+            .init_property_local(reciever, name, value, activation)?;
+
+        drop(write);
+
+        rv.resolve(activation)?;
+
+        Ok(())
+    }
+
+    fn delete_property(&self, gc_context: MutationContext<'gc, '_>, name: &QName<'gc>) -> bool {
+        if let Some(index) = array_index(name) {
+            self.0.write(gc_context).array.delete(index);
+
+            return true;
+        }
+
+        self.0.write(gc_context).base.delete_property(name)
+    }
+
+    fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+        if let Some(index) = array_index(name) {
+            return Ok(self.0.read().array.get(index).is_some());
+        }
+
+        self.0.read().base.has_own_property(name)
+    }
+
+    fn has_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+        // Holes, and indices past the end of the array, still resolve so that
+        // reading them yields `undefined`. The `in` operator goes through
+        // `resolve_any` first, which only finds elements that exist.
+        if array_index(name).is_some() || self.has_own_property(name)? {
+            Ok(true)
+        } else if let Some(proto) = self.proto() {
+            Ok(proto.has_own_property(name)?)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+        let name = QName::dynamic_name(local_name);
+        if let Some(index) = array_index(&name) {
+            if self.0.read().array.get(index).is_some() {
+                return Ok(Some(Namespace::public_namespace()));
+            }
+        }
+
+        self.0.read().base.resolve_any(local_name)
+    }
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object Array]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_array_storage(&self) -> Option<Ref<ArrayStorage<'gc>>> {
+        Some(Ref::map(self.0.read(), |s| &s.array))
+    }
+
+    fn as_array_storage_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<ArrayStorage<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.array))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::ArrayObject(*self);
+
+        Ok(ArrayObject::from_array(
+            ArrayStorage::new(0),
+            this,
+            activation.context.gc_context,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::ArrayObject(*self);
+
+        ArrayObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
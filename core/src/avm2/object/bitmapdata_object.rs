@@ -0,0 +1,107 @@
+//! Bitmap data objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bitmapdata::BitmapDataStorage;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a `BitmapData`'s pixels.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct BitmapDataObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The pixels held by this object.
+    storage: BitmapDataStorage,
+}
+
+impl<'gc> BitmapDataObject<'gc> {
+    /// Construct the prototype for a class whose instances hold bitmaps.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(BitmapDataObject(GcCell::allocate(
+            mc,
+            BitmapDataObjectData {
+                base,
+                storage: BitmapDataStorage::default(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for BitmapDataObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object BitmapData]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_bitmap_data(&self) -> Option<Ref<BitmapDataStorage>> {
+        Some(Ref::map(self.0.read(), |s| &s.storage))
+    }
+
+    fn as_bitmap_data_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<BitmapDataStorage>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.storage))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(BitmapDataObject(GcCell::allocate(
+            activation.context.gc_context,
+            BitmapDataObjectData {
+                base,
+                storage: BitmapDataStorage::default(),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+
+        BitmapDataObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
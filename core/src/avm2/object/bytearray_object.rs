@@ -0,0 +1,104 @@
+//! Byte array objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a `ByteArray`'s data.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct ByteArrayObject<'gc>(GcCell<'gc, ByteArrayObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct ByteArrayObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The bytes held by this array.
+    storage: ByteArrayStorage,
+}
+
+impl<'gc> ByteArrayObject<'gc> {
+    /// Construct the prototype for a class whose instances hold byte arrays.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(ByteArrayObject(GcCell::allocate(
+            mc,
+            ByteArrayObjectData {
+                base,
+                storage: ByteArrayStorage::new(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object ByteArray]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_bytearray(&self) -> Option<Ref<ByteArrayStorage>> {
+        Some(Ref::map(self.0.read(), |s| &s.storage))
+    }
+
+    fn as_bytearray_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<ByteArrayStorage>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.storage))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::ByteArrayObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(ByteArrayObject(GcCell::allocate(
+            activation.context.gc_context,
+            ByteArrayObjectData {
+                base,
+                storage: ByteArrayStorage::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::ByteArrayObject(*self);
+
+        ByteArrayObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
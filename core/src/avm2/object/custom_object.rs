@@ -1,7 +1,23 @@
 //! Custom object macro
 
+/// Implement all of `TObject` that a native object forwards to its
+/// `ScriptObjectData`.
 #[macro_export]
 macro_rules! impl_avm2_custom_object {
+    ($field:ident) => {
+        $crate::impl_avm2_custom_object_properties!($field);
+        $crate::impl_avm2_custom_object_base!($field);
+    };
+}
+
+/// Implement the property accessors of `TObject` by forwarding them to a
+/// native object's `ScriptObjectData`.
+///
+/// Native objects that need to intercept property access, such as arrays,
+/// implement these themselves and use `impl_avm2_custom_object_base` for
+/// everything else.
+#[macro_export]
+macro_rules! impl_avm2_custom_object_properties {
     ($field:ident) => {
         fn get_property_local(
             self,
@@ -55,6 +71,29 @@ macro_rules! impl_avm2_custom_object {
             Ok(())
         }
 
+        fn delete_property(
+            &self,
+            gc_context: MutationContext<'gc, '_>,
+            multiname: &QName<'gc>,
+        ) -> bool {
+            self.0.write(gc_context).$field.delete_property(multiname)
+        }
+
+        fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
+            self.0.read().$field.resolve_any(local_name)
+        }
+
+        fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
+            self.0.read().$field.has_own_property(name)
+        }
+    };
+}
+
+/// Implement everything in `TObject` other than property access by forwarding
+/// it to a native object's `ScriptObjectData`.
+#[macro_export]
+macro_rules! impl_avm2_custom_object_base {
+    ($field:ident) => {
         fn is_property_overwritable(
             self,
             gc_context: MutationContext<'gc, '_>,
@@ -66,14 +105,6 @@ macro_rules! impl_avm2_custom_object {
                 .is_property_overwritable(name)
         }
 
-        fn delete_property(
-            &self,
-            gc_context: MutationContext<'gc, '_>,
-            multiname: &QName<'gc>,
-        ) -> bool {
-            self.0.write(gc_context).$field.delete_property(multiname)
-        }
-
         fn get_slot(self, id: u32) -> Result<Value<'gc>, Error> {
             self.0.read().$field.get_slot(id)
         }
@@ -116,10 +147,6 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.get_scope()
         }
 
-        fn resolve_any(self, local_name: AvmString<'gc>) -> Result<Option<Namespace<'gc>>, Error> {
-            self.0.read().$field.resolve_any(local_name)
-        }
-
         fn resolve_any_trait(
             self,
             local_name: AvmString<'gc>,
@@ -127,10 +154,6 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.resolve_any_trait(local_name)
         }
 
-        fn has_own_property(self, name: &QName<'gc>) -> Result<bool, Error> {
-            self.0.read().$field.has_own_property(name)
-        }
-
         fn has_trait(self, name: &QName<'gc>) -> Result<bool, Error> {
             self.0.read().$field.has_trait(name)
         }
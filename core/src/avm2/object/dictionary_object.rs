@@ -0,0 +1,107 @@
+//! Dictionary objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::dictionary::DictionaryStorage;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds the object-keyed entries of a `Dictionary`.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DictionaryObject<'gc>(GcCell<'gc, DictionaryObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DictionaryObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The entries keyed by object identity.
+    storage: DictionaryStorage<'gc>,
+}
+
+impl<'gc> DictionaryObject<'gc> {
+    /// Construct the prototype for a class whose instances are dictionaries.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(DictionaryObject(GcCell::allocate(
+            mc,
+            DictionaryObjectData {
+                base,
+                storage: DictionaryStorage::new(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for DictionaryObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object Dictionary]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_dictionary(&self) -> Option<Ref<DictionaryStorage<'gc>>> {
+        Some(Ref::map(self.0.read(), |s| &s.storage))
+    }
+
+    fn as_dictionary_mut(
+        &self,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<DictionaryStorage<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.storage))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(DictionaryObject(GcCell::allocate(
+            activation.context.gc_context,
+            DictionaryObjectData {
+                base,
+                storage: DictionaryStorage::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::DictionaryObject(*self);
+
+        DictionaryObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,135 @@
+//! Application Domains
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::domain::Domain;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An Object which represents an `ApplicationDomain`.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct DomainObject<'gc>(GcCell<'gc, DomainObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct DomainObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The domain this object holds.
+    ///
+    /// This is only `None` for the `ApplicationDomain` prototype.
+    domain: Option<Domain<'gc>>,
+}
+
+impl<'gc> DomainObject<'gc> {
+    /// Construct the prototype for a class whose instances are domains.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(DomainObject(GcCell::allocate(
+            mc,
+            DomainObjectData { base, domain: None },
+        ))
+        .into())
+    }
+
+    /// Create an `ApplicationDomain` object for a given domain.
+    pub fn from_domain(
+        activation: &mut Activation<'_, 'gc, '_>,
+        domain: Domain<'gc>,
+    ) -> Result<Object<'gc>, Error> {
+        let mut globals = activation.avm2().globals();
+        let mut constr = globals
+            .get_property(
+                globals,
+                &QName::new(Namespace::package("flash.system"), "ApplicationDomain"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let proto = constr
+            .get_property(
+                constr,
+                &QName::new(Namespace::public_namespace(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let base = ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass);
+
+        Ok(DomainObject(GcCell::allocate(
+            activation.context.gc_context,
+            DomainObjectData {
+                base,
+                domain: Some(domain),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for DomainObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object ApplicationDomain]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_application_domain(&self) -> Option<Domain<'gc>> {
+        self.0.read().domain
+    }
+
+    fn init_application_domain(&self, mc: MutationContext<'gc, '_>, domain: Domain<'gc>) {
+        self.0.write(mc).domain = Some(domain);
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::DomainObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+        let domain = activation.avm2().global_domain();
+
+        Ok(DomainObject(GcCell::allocate(
+            activation.context.gc_context,
+            DomainObjectData {
+                base,
+                domain: Some(domain),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::DomainObject(*self);
+
+        DomainObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -0,0 +1,104 @@
+//! Matrix3D objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::matrix3d::Matrix3D;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a `Matrix3D`'s data.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct Matrix3DObject<'gc>(GcCell<'gc, Matrix3DObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct Matrix3DObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The matrix held by this object.
+    matrix: Matrix3D,
+}
+
+impl<'gc> Matrix3DObject<'gc> {
+    /// Construct the prototype for a class whose instances hold 3D matrices.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(Matrix3DObject(GcCell::allocate(
+            mc,
+            Matrix3DObjectData {
+                base,
+                matrix: Matrix3D::IDENTITY,
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for Matrix3DObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object Matrix3D]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_matrix3d(&self) -> Option<Ref<Matrix3D>> {
+        Some(Ref::map(self.0.read(), |s| &s.matrix))
+    }
+
+    fn as_matrix3d_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Matrix3D>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.matrix))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::Matrix3DObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(Matrix3DObject(GcCell::allocate(
+            activation.context.gc_context,
+            Matrix3DObjectData {
+                base,
+                matrix: Matrix3D::IDENTITY,
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::Matrix3DObject(*self);
+
+        Matrix3DObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -12,7 +12,7 @@ use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::impl_avm2_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
-use std::cell::Ref;
+use std::cell::{Ref, RefMut};
 
 /// An Object which represents a boxed namespace name.
 #[derive(Collect, Debug, Clone, Copy)]
@@ -44,6 +44,28 @@ impl<'gc> NamespaceObject<'gc> {
         ))
         .into())
     }
+
+    /// Instantiate a namespace subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(NamespaceObject(GcCell::allocate(
+            mc,
+            NamespaceObjectData {
+                base,
+                namespace: Namespace::public_namespace(),
+            },
+        ))
+        .into())
+    }
 }
 
 impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
@@ -61,6 +83,10 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
         Some(Ref::map(self.0.read(), |s| &s.namespace))
     }
 
+    fn as_namespace_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Namespace<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.namespace))
+    }
+
     fn construct(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
@@ -86,18 +112,7 @@ impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
         scope: Option<GcCell<'gc, Scope<'gc>>>,
     ) -> Result<Object<'gc>, Error> {
         let this: Object<'gc> = Object::NamespaceObject(*self);
-        let base = ScriptObjectData::base_new(
-            Some(this),
-            ScriptObjectClass::InstancePrototype(class, scope),
-        );
 
-        Ok(NamespaceObject(GcCell::allocate(
-            activation.context.gc_context,
-            NamespaceObjectData {
-                base,
-                namespace: Namespace::public_namespace(),
-            },
-        ))
-        .into())
+        NamespaceObject::derive(this, activation.context.gc_context, class, scope)
     }
 }
@@ -0,0 +1,127 @@
+//! AVM2 object impl for display objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::DispatchList;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::DisplayObject;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which represents a display object to scripts.
+///
+/// Instances are bound to their display object when they are created for one
+/// that is already on the display list. Objects constructed by scripts, and
+/// the stage, have no display object.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct StageObject<'gc>(GcCell<'gc, StageObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct StageObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The event handlers registered on this object.
+    dispatch: DispatchList<'gc>,
+
+    /// The display object this object represents, if any.
+    display_object: Option<DisplayObject<'gc>>,
+}
+
+impl<'gc> StageObject<'gc> {
+    /// Construct the prototype for a class whose instances represent display
+    /// objects.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(StageObject(GcCell::allocate(
+            mc,
+            StageObjectData {
+                base,
+                dispatch: DispatchList::new(),
+                display_object: None,
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for StageObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok("[object DisplayObject]".into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_dispatch(&self) -> Option<Ref<DispatchList<'gc>>> {
+        Some(Ref::map(self.0.read(), |d| &d.dispatch))
+    }
+
+    fn as_dispatch_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<DispatchList<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |d| &mut d.dispatch))
+    }
+
+    fn as_display_object(&self) -> Option<DisplayObject<'gc>> {
+        self.0.read().display_object
+    }
+
+    fn init_display_object(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        display_object: DisplayObject<'gc>,
+    ) {
+        self.0.write(mc).display_object = Some(display_object);
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StageObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(StageObject(GcCell::allocate(
+            activation.context.gc_context,
+            StageObjectData {
+                base,
+                dispatch: DispatchList::new(),
+                display_object: None,
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StageObject(*self);
+
+        StageObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
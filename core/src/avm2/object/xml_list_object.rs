@@ -0,0 +1,127 @@
+//! XMLList objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::{list_string_value, E4XNode, XmlSettings};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds an ordered list of E4X nodes and attributes.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XMLListObject<'gc>(GcCell<'gc, XMLListObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XMLListObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The nodes and attributes in this list.
+    values: Vec<E4XNode<'gc>>,
+}
+
+impl<'gc> XMLListObject<'gc> {
+    /// Collect E4X values into an `XMLList` object.
+    pub fn from_values(
+        activation: &mut Activation<'_, 'gc, '_>,
+        values: Vec<E4XNode<'gc>>,
+    ) -> Result<Object<'gc>, Error> {
+        let proto = activation.avm2().prototypes().xml_list;
+        let base = ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass);
+
+        Ok(XMLListObject(GcCell::allocate(
+            activation.context.gc_context,
+            XMLListObjectData { base, values },
+        ))
+        .into())
+    }
+
+    /// Construct the prototype for a class whose instances are XML lists.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(XMLListObject(GcCell::allocate(
+            mc,
+            XMLListObjectData {
+                base,
+                values: Vec::new(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for XMLListObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        // As with `XML`, the `toString` method is what honors the settings.
+        let values = &self.0.read().values;
+        let string = list_string_value(values, XmlSettings::default());
+
+        Ok(AvmString::new(mc, string).into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn as_xml_list(&self) -> Option<Ref<Vec<E4XNode<'gc>>>> {
+        Some(Ref::map(self.0.read(), |s| &s.values))
+    }
+
+    fn as_xml_list_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<Vec<E4XNode<'gc>>>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.values))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XMLListObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(XMLListObject(GcCell::allocate(
+            activation.context.gc_context,
+            XMLListObjectData {
+                base,
+                values: Vec::new(),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XMLListObject(*self);
+
+        XMLListObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
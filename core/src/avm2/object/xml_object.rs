@@ -0,0 +1,133 @@
+//! XML objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::e4x::E4XNode;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::{Hint, Value};
+use crate::avm2::Error;
+use crate::impl_avm2_custom_object;
+use crate::xml::{XMLDocument, XMLNode};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An Object which holds a single E4X node or attribute.
+#[derive(Collect, Debug, Clone, Copy)]
+#[collect(no_drop)]
+pub struct XMLObject<'gc>(GcCell<'gc, XMLObjectData<'gc>>);
+
+#[derive(Collect, Debug, Clone)]
+#[collect(no_drop)]
+pub struct XMLObjectData<'gc> {
+    /// All normal script data.
+    base: ScriptObjectData<'gc>,
+
+    /// The node or attribute this object holds.
+    value: E4XNode<'gc>,
+}
+
+impl<'gc> XMLObject<'gc> {
+    /// Wrap an E4X value in an `XML` object.
+    pub fn from_value(
+        activation: &mut Activation<'_, 'gc, '_>,
+        value: E4XNode<'gc>,
+    ) -> Result<Object<'gc>, Error> {
+        let proto = activation.avm2().prototypes().xml;
+        let base = ScriptObjectData::base_new(Some(proto), ScriptObjectClass::NoClass);
+
+        Ok(XMLObject(GcCell::allocate(
+            activation.context.gc_context,
+            XMLObjectData { base, value },
+        ))
+        .into())
+    }
+
+    /// Construct the prototype for a class whose instances are XML.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(XMLObject(GcCell::allocate(
+            mc,
+            XMLObjectData {
+                base,
+                value: empty_text(mc),
+            },
+        ))
+        .into())
+    }
+}
+
+/// The value of an `XML` object that has not been initialized yet.
+fn empty_text<'gc>(mc: MutationContext<'gc, '_>) -> E4XNode<'gc> {
+    E4XNode::Node(XMLNode::new_text(mc, "", XMLDocument::new(mc)))
+}
+
+impl<'gc> TObject<'gc> for XMLObject<'gc> {
+    impl_avm2_custom_object!(base);
+
+    fn to_string(&self, mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        // The `toString` method honors `XML.prettyPrinting`; without an
+        // activation to read it from, this uses the default settings.
+        let value = &self.0.read().value;
+
+        Ok(AvmString::new(mc, value.string_value(Default::default())).into())
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn default_hint(&self) -> Hint {
+        Hint::String
+    }
+
+    fn as_xml(&self) -> Option<Ref<E4XNode<'gc>>> {
+        Some(Ref::map(self.0.read(), |s| &s.value))
+    }
+
+    fn as_xml_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<E4XNode<'gc>>> {
+        Some(RefMut::map(self.0.write(mc), |s| &mut s.value))
+    }
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XMLObject(*self);
+        let base = ScriptObjectData::base_new(Some(this), ScriptObjectClass::NoClass);
+
+        Ok(XMLObject(GcCell::allocate(
+            activation.context.gc_context,
+            XMLObjectData {
+                base,
+                value: empty_text(activation.context.gc_context),
+            },
+        ))
+        .into())
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::XMLObject(*self);
+
+        XMLObject::derive(this, activation.context.gc_context, class, scope)
+    }
+}
@@ -1,6 +1,7 @@
 //! Represents AVM2 scope chain resolution.
 
 use crate::avm2::activation::Activation;
+use crate::avm2::e4x;
 use crate::avm2::names::Multiname;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
@@ -95,12 +96,17 @@ impl<'gc> Scope<'gc> {
 
     /// Find an object that contains a given property in the scope stack.
     ///
-    /// This function yields `None` if no such scope exists.
+    /// This function yields `None` if no such scope exists. `XML` objects, as
+    /// pushed by filtering predicates, contain their children and attributes.
     pub fn find(
         &self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<Object<'gc>>, Error> {
+        if e4x::has_property(activation, *self.locals(), name) {
+            return Ok(Some(*self.locals()));
+        }
+
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(*self.locals()));
@@ -123,6 +129,10 @@ impl<'gc> Scope<'gc> {
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<Value<'gc>>, Error> {
+        if e4x::has_property(activation, self.values, name) {
+            return e4x::get_property(activation, self.values, name);
+        }
+
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
                 return Ok(Some(self.values.get_property(
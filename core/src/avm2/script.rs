@@ -6,10 +6,12 @@ use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
+use crate::tag_utils::SwfMovie;
 use fnv::FnvHashMap;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::mem::drop;
 use std::rc::Rc;
+use std::sync::Arc;
 use swf::avm2::types::{AbcFile, Index, Script as AbcScript};
 
 #[derive(Copy, Clone, Debug, Collect)]
@@ -34,6 +36,9 @@ pub struct TranslationUnitData<'gc> {
     /// The ABC file that all of the following loaded data comes from.
     abc: CollectWrapper<Rc<AbcFile>>,
 
+    /// The movie that the ABC file was loaded from.
+    movie: Arc<SwfMovie>,
+
     /// All classes loaded from the ABC's class list.
     classes: FnvHashMap<u32, GcCell<'gc, Class<'gc>>>,
 
@@ -48,11 +53,12 @@ pub struct TranslationUnitData<'gc> {
 }
 
 impl<'gc> TranslationUnit<'gc> {
-    pub fn from_abc(abc: Rc<AbcFile>, mc: MutationContext<'gc, '_>) -> Self {
+    pub fn from_abc(abc: Rc<AbcFile>, movie: Arc<SwfMovie>, mc: MutationContext<'gc, '_>) -> Self {
         Self(GcCell::allocate(
             mc,
             TranslationUnitData {
                 abc: CollectWrapper(abc),
+                movie,
                 classes: FnvHashMap::default(),
                 methods: FnvHashMap::default(),
                 scripts: FnvHashMap::default(),
@@ -66,6 +72,11 @@ impl<'gc> TranslationUnit<'gc> {
         self.0.read().abc.0.clone()
     }
 
+    /// Retrieve the movie that this translation unit was loaded from.
+    pub fn movie(self) -> Arc<SwfMovie> {
+        self.0.read().movie.clone()
+    }
+
     /// Load a method from the ABC file and return it's method definition.
     pub fn load_method(
         self,
@@ -0,0 +1,413 @@
+//! Local shared objects, as opened with `flash.net.SharedObject.getLocal`.
+//!
+//! Like in AVM1, the `data` of a shared object is kept by the storage backend
+//! as JSON. Each shared object is stored under a key made of its local path
+//! and its name, so that movies on the same domain can share data by asking
+//! for a common path.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ObjectEncoding;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::net_stream::dispatch_net_status;
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use crate::sol::Sol;
+use json::JsonValue;
+
+/// The most data a shared object can hold before Flash Player asks the user
+/// for more space, in bytes.
+const SIZE_LIMIT: usize = 100 * 1024;
+
+/// Characters that can't appear in the name of a shared object.
+const INVALID_NAME_CHARS: &[char] = &[
+    '~', '%', '&', '\\', ';', ':', '"', '\'', ',', '<', '>', '?', '#', ' ',
+];
+
+/// The error thrown when a shared object can't be opened.
+const CREATE_ERROR: &str = "Error: Error #2134: Cannot create SharedObject.";
+
+/// The error thrown when a shared object can't be written to storage.
+const FLUSH_ERROR: &str = "Error: Error #2130: Unable to flush SharedObject.";
+
+fn private_name<'gc>(name: &'static str) -> QName<'gc> {
+    QName::new(Namespace::Private("flash.net:SharedObject".into()), name)
+}
+
+fn public_name<'gc>(activation: &mut Activation<'_, 'gc, '_>, name: &str) -> QName<'gc> {
+    QName::new(
+        Namespace::public_namespace(),
+        AvmString::new(activation.context.gc_context, name.to_string()),
+    )
+}
+
+/// Split a path into its non-empty segments, so that leading, trailing and
+/// repeated slashes don't matter.
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// The key that a shared object is stored under.
+///
+/// `local_path` defaults to the full path of the movie's URL, and must be a
+/// prefix of it; `"/"` shares the object with every movie on the domain.
+/// Returns `None` if the name or path is not allowed.
+pub fn storage_key(
+    movie_url: Option<&str>,
+    name: &str,
+    local_path: Option<&str>,
+) -> Option<String> {
+    if name.is_empty() || name.contains(INVALID_NAME_CHARS) || name.contains('/') {
+        return None;
+    }
+
+    let movie_url = movie_url.and_then(|url| url::Url::parse(url).ok());
+    let movie_path = movie_url.as_ref().map(|url| url.path()).unwrap_or_default();
+    let movie_segments = path_segments(movie_path);
+    let segments = match local_path {
+        Some(local_path) => {
+            let segments = path_segments(local_path);
+            if !movie_segments.starts_with(&segments) {
+                return None;
+            }
+            segments
+        }
+        None => movie_segments,
+    };
+
+    let mut key: String = segments
+        .iter()
+        .map(|segment| format!("{}/", segment))
+        .collect();
+    key.push_str(name);
+    Some(key)
+}
+
+/// Open the shared object stored under `key`, reusing it if it is already
+/// open.
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut class: Object<'gc>,
+    name: &str,
+    local_path: Option<&str>,
+) -> Result<Object<'gc>, Error> {
+    let key = storage_key(activation.context.swf.url(), name, local_path).ok_or(CREATE_ERROR)?;
+    if let Some(object) = activation.avm2().shared_object(&key) {
+        return Ok(object);
+    }
+
+    let proto = class
+        .get_property(
+            class,
+            &QName::new(Namespace::public_namespace(), "prototype"),
+            activation,
+        )?
+        .coerce_to_object(activation)?;
+    let mut object = proto.construct(activation, &[])?;
+    class.call(Some(object), &[], activation, object.proto())?;
+
+    let default_encoding = class
+        .get_property(
+            class,
+            &QName::new(Namespace::public_namespace(), "defaultObjectEncoding"),
+            activation,
+        )?
+        .coerce_to_u32(activation)?;
+    let encoding = ObjectEncoding::from_u32(default_encoding).unwrap_or_default();
+
+    let object_proto = activation.avm2().prototypes().object;
+    let data = ScriptObject::object(activation.context.gc_context, object_proto);
+    if let Some(saved) = activation.context.storage.get_string(&key) {
+        if let Ok(json) = json::parse(&saved) {
+            json_to_data(activation, &json, data)?;
+        }
+    }
+
+    let key_value = AvmString::new(activation.context.gc_context, key.clone());
+    let name_value = AvmString::new(activation.context.gc_context, name.to_string());
+    object.set_property(object, &private_name("key"), key_value.into(), activation)?;
+    object.set_property(object, &private_name("name"), name_value.into(), activation)?;
+    object.set_property(object, &private_name("data"), data.into(), activation)?;
+    set_object_encoding(activation, object, encoding)?;
+
+    activation.avm2().register_shared_object(key, object);
+    Ok(object)
+}
+
+/// The `data` object of a shared object.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    object
+        .get_property(object, &private_name("data"), activation)?
+        .coerce_to_object(activation)
+}
+
+fn key<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<String, Error> {
+    Ok(object
+        .get_property(object, &private_name("key"), activation)?
+        .coerce_to_string(activation)?
+        .to_string())
+}
+
+/// The name of a shared object, as written to `.sol` files.
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<String, Error> {
+    Ok(object
+        .get_property(object, &private_name("name"), activation)?
+        .coerce_to_string(activation)?
+        .to_string())
+}
+
+/// The encoding that a shared object is written to `.sol` files with.
+pub fn object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<ObjectEncoding, Error> {
+    let encoding = object
+        .get_property(object, &private_name("objectEncoding"), activation)?
+        .coerce_to_u32(activation)?;
+    Ok(ObjectEncoding::from_u32(encoding).unwrap_or_default())
+}
+
+pub fn set_object_encoding<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    encoding: ObjectEncoding,
+) -> Result<(), Error> {
+    object.set_property(
+        object,
+        &private_name("objectEncoding"),
+        encoding.to_u32().into(),
+        activation,
+    )
+}
+
+/// The size of a shared object when written to a `.sol` file, in bytes.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<usize, Error> {
+    let name = name(activation, object)?;
+    let data = data(activation, object)?;
+    let json = data_to_json(activation, data)?;
+    let encoding = object_encoding(activation, object)?;
+    Ok(serialized_size(&name, &json, encoding))
+}
+
+fn serialized_size(name: &str, json: &JsonValue, encoding: ObjectEncoding) -> usize {
+    Sol::from_json(name, json, encoding).write().len()
+}
+
+/// Write a shared object to storage.
+///
+/// Returns `"pending"` if Flash Player would have asked the user for more
+/// space first, because the data or `min_disk_space` exceeds its default
+/// limit. There is no such prompt here, so the data is written regardless,
+/// and the object is told that the user agreed.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    min_disk_space: usize,
+) -> Result<&'static str, Error> {
+    let key = key(activation, object)?;
+    let name = name(activation, object)?;
+    let data = data(activation, object)?;
+    let json = data_to_json(activation, data)?;
+    let encoding = object_encoding(activation, object)?;
+    let size = serialized_size(&name, &json, encoding);
+
+    if !activation.context.storage.put_string(&key, json.dump()) {
+        return Err(FLUSH_ERROR.into());
+    }
+
+    if size.max(min_disk_space) > SIZE_LIMIT {
+        dispatch_net_status(activation, object, "SharedObject.Flush.Success", "status")?;
+        Ok("pending")
+    } else {
+        Ok("flushed")
+    }
+}
+
+/// Delete all the data of a shared object, both in memory and in storage.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<(), Error> {
+    let key = key(activation, object)?;
+    let data = data(activation, object)?;
+    for name in dynamic_names(data) {
+        data.delete_property(activation.context.gc_context, &name);
+    }
+
+    activation.context.storage.remove_key(&key);
+    Ok(())
+}
+
+/// Write every open shared object to storage.
+pub fn flush_all(context: &mut UpdateContext<'_, '_, '_>) {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    for object in activation.avm2().shared_objects() {
+        if let Err(e) = flush(&mut activation, object, 0) {
+            log::warn!("Couldn't flush shared object: {}", e);
+        }
+    }
+}
+
+/// The name and encoding of the open shared object stored under `key`, if
+/// any.
+pub fn header_of(
+    context: &mut UpdateContext<'_, '_, '_>,
+    key: &str,
+) -> Option<(String, ObjectEncoding)> {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let object = activation.avm2().shared_object(key)?;
+    let name = name(&mut activation, object).ok()?;
+    let encoding = object_encoding(&mut activation, object).ok()?;
+    Some((name, encoding))
+}
+
+/// Merge imported data into the open shared object stored under `key`, and
+/// write it back to storage.
+///
+/// Returns `false` if no such shared object is open.
+pub fn import(context: &mut UpdateContext<'_, '_, '_>, key: &str, json: &JsonValue) -> bool {
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let object = match activation.avm2().shared_object(key) {
+        Some(object) => object,
+        None => return false,
+    };
+
+    let result = data(&mut activation, object)
+        .and_then(|data| json_to_data(&mut activation, json, data))
+        .and_then(|_| flush(&mut activation, object, 0));
+    if let Err(e) = result {
+        log::warn!("Couldn't import shared object: {}", e);
+    }
+    true
+}
+
+/// List the enumerable dynamic properties of an object.
+fn dynamic_names<'gc>(object: Object<'gc>) -> Vec<QName<'gc>> {
+    let mut names = Vec::new();
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        if object.property_is_enumerable(&name) {
+            names.push(name);
+        }
+        index += 1;
+    }
+
+    names
+}
+
+/// Convert the properties of an object to JSON, recursing into objects.
+///
+/// Undefined values and functions aren't stored.
+fn data_to_json<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<JsonValue, Error> {
+    let mut json = JsonValue::new_object();
+    for name in dynamic_names(object) {
+        let value = match object.get_property(object, &name, activation)? {
+            Value::Undefined => continue,
+            Value::Null => JsonValue::Null,
+            Value::Bool(b) => b.into(),
+            Value::Number(n) => n.into(),
+            Value::Integer(i) => i.into(),
+            Value::Unsigned(u) => u.into(),
+            Value::String(s) => s.to_string().into(),
+            Value::Object(o) if o.as_executable().is_some() || o.as_class().is_some() => continue,
+            Value::Object(o) => data_to_json(activation, o)?,
+        };
+        json[name.local_name().to_string().as_str()] = value;
+    }
+
+    Ok(json)
+}
+
+/// Define the properties of a JSON object on an object, recursing into
+/// objects.
+fn json_to_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    json: &JsonValue,
+    mut object: Object<'gc>,
+) -> Result<(), Error> {
+    for (key, value) in json.entries() {
+        let value = match value {
+            JsonValue::Null => Value::Null,
+            JsonValue::Short(s) => {
+                AvmString::new(activation.context.gc_context, s.to_string()).into()
+            }
+            JsonValue::String(s) => AvmString::new(activation.context.gc_context, s.clone()).into(),
+            JsonValue::Number(n) => Value::Number((*n).into()),
+            JsonValue::Boolean(b) => Value::Bool(*b),
+            JsonValue::Object(_) => {
+                let object_proto = activation.avm2().prototypes().object;
+                let child = ScriptObject::object(activation.context.gc_context, object_proto);
+                json_to_data(activation, value, child)?;
+                child.into()
+            }
+            JsonValue::Array(_) => continue,
+        };
+        let name = public_name(activation, key);
+        object.set_property(object, &name, value, activation)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: Option<&str> = Some("http://example.com/games/pong.swf?v=2");
+
+    #[test]
+    fn default_path() {
+        assert_eq!(
+            storage_key(URL, "save", None).as_deref(),
+            Some("games/pong.swf/save")
+        );
+        assert_eq!(storage_key(None, "save", None).as_deref(), Some("save"));
+    }
+
+    #[test]
+    fn local_paths() {
+        assert_eq!(storage_key(URL, "save", Some("/")).as_deref(), Some("save"));
+        assert_eq!(storage_key(URL, "save", Some("")).as_deref(), Some("save"));
+        assert_eq!(
+            storage_key(URL, "save", Some("/games")),
+            storage_key(URL, "save", Some("games//")),
+        );
+        assert_eq!(
+            storage_key(URL, "save", Some("/games/pong.swf")),
+            storage_key(URL, "save", None),
+        );
+        assert_eq!(storage_key(URL, "save", Some("/gam")), None);
+        assert_eq!(storage_key(URL, "save", Some("/other")), None);
+    }
+
+    #[test]
+    fn invalid_names() {
+        assert_eq!(storage_key(URL, "", None), None);
+        for name in &["a~b", "100%", "a&b", "a b", "a:b", "a#b", "a/b"] {
+            assert_eq!(storage_key(URL, name, Some("/")), None, "{}", name);
+        }
+        assert_eq!(
+            storage_key(URL, "save_1.v2", Some("/")).as_deref(),
+            Some("save_1.v2")
+        );
+    }
+}
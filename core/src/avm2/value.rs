@@ -10,6 +10,7 @@ use crate::avm2::{Avm2, Error};
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use gc_arena::{Collect, MutationContext};
 use std::cell::Ref;
+use std::convert::TryFrom;
 use std::f64::NAN;
 use swf::avm2::types::{DefaultValue as AbcDefaultValue, Index};
 
@@ -117,6 +118,20 @@ impl<'gc> From<usize> for Value<'gc> {
     }
 }
 
+impl<'gc> From<i64> for Value<'gc> {
+    /// Integers within the range of `int` or `uint` stay integers; anything
+    /// larger is rounded to the nearest `Number`.
+    fn from(value: i64) -> Self {
+        if let Ok(i) = i32::try_from(value) {
+            Value::Integer(i)
+        } else if let Ok(u) = u32::try_from(value) {
+            Value::Unsigned(u)
+        } else {
+            Value::Number(value as f64)
+        }
+    }
+}
+
 impl PartialEq for Value<'_> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -406,7 +421,11 @@ impl<'gc> Value<'gc> {
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToUint32 algorithm which appears to match AVM2.
     pub fn coerce_to_u32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<u32, Error> {
-        Ok(f64_to_wrapping_u32(self.coerce_to_number(activation)?))
+        Ok(match self {
+            Value::Unsigned(u) => *u,
+            Value::Integer(i) => *i as u32,
+            _ => f64_to_wrapping_u32(self.coerce_to_number(activation)?),
+        })
     }
 
     /// Coerce the value to a 32-bit signed integer.
@@ -417,7 +436,24 @@ impl<'gc> Value<'gc> {
     /// Numerical conversions occur according to ECMA-262 3rd Edition's
     /// ToInt32 algorithm which appears to match AVM2.
     pub fn coerce_to_i32(&self, activation: &mut Activation<'_, 'gc, '_>) -> Result<i32, Error> {
-        Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
+        Ok(match self {
+            Value::Integer(i) => *i,
+            Value::Unsigned(u) => *u as i32,
+            _ => f64_to_wrapping_i32(self.coerce_to_number(activation)?),
+        })
+    }
+
+    /// Get the exact value of an `int` or `uint`.
+    ///
+    /// Arithmetic on two integers can use this to skip the round trip through
+    /// `Number`. The `integer_*` functions in this module produce the same
+    /// results that the `Number` operation would have.
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(i) => Some(i64::from(*i)),
+            Value::Unsigned(u) => Some(i64::from(*u)),
+            _ => None,
+        }
     }
 
     /// Mininum number of digits after which numbers are formatted as
@@ -649,3 +685,118 @@ impl<'gc> Value<'gc> {
         Ok(Some(num_self < num_other))
     }
 }
+
+/// Multiply two integers with the same result as multiplying them as `Number`s.
+///
+/// Products beyond 2^53 are rounded exactly once, like the floating-point
+/// multiplication would, and a zero product with a negative factor is `-0`.
+pub fn integer_multiply<'gc>(a: i64, b: i64) -> Value<'gc> {
+    match a.checked_mul(b) {
+        Some(0) if a < 0 || b < 0 => Value::Number(-0.0),
+        Some(product) => product.into(),
+        None => Value::Number(a as f64 * b as f64),
+    }
+}
+
+/// Take the remainder of two integers with the same result as `Number` modulo.
+///
+/// The result has the sign of the dividend, including `-0`, and a zero
+/// divisor produces `NaN`.
+pub fn integer_modulo<'gc>(a: i64, b: i64) -> Value<'gc> {
+    match a.checked_rem(b) {
+        None => Value::Number(NAN),
+        Some(0) if a < 0 => Value::Number(-0.0),
+        Some(remainder) => remainder.into(),
+    }
+}
+
+/// Negate an integer with the same result as `Number` negation.
+pub fn integer_negate<'gc>(a: i64) -> Value<'gc> {
+    if a == 0 {
+        Value::Number(-0.0)
+    } else {
+        (-a).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `Number` that a value represents, for comparing against
+    /// floating-point arithmetic.
+    fn number(value: Value<'_>) -> f64 {
+        match value {
+            Value::Number(n) => n,
+            Value::Integer(i) => i.into(),
+            Value::Unsigned(u) => u.into(),
+            value => panic!("{:?} is not a number", value),
+        }
+    }
+
+    fn assert_same_number(integer: Value<'_>, float: f64) {
+        let integer = number(integer);
+        assert!(
+            integer.to_bits() == float.to_bits() || (integer.is_nan() && float.is_nan()),
+            "{} != {}",
+            integer,
+            float
+        );
+    }
+
+    const OPERANDS: &[i64] = &[
+        0,
+        1,
+        -1,
+        2,
+        -4,
+        31,
+        65536,
+        0x7FFF_FFFF,
+        -0x8000_0000,
+        0xFFFF_FFFF,
+    ];
+
+    #[test]
+    fn integers_stay_integers() {
+        assert!(matches!(Value::from(5i64), Value::Integer(5)));
+        assert!(matches!(
+            Value::from(-0x8000_0000i64),
+            Value::Integer(-0x8000_0000)
+        ));
+        assert!(matches!(
+            Value::from(0x8000_0000i64),
+            Value::Unsigned(0x8000_0000)
+        ));
+        assert!(matches!(Value::from(0x1_0000_0000i64), Value::Number(n) if n == 4294967296.0));
+    }
+
+    #[test]
+    fn multiply_matches_number() {
+        for &a in OPERANDS {
+            for &b in OPERANDS {
+                assert_same_number(integer_multiply(a, b), a as f64 * b as f64);
+            }
+        }
+
+        // Products past 2^53 are only rounded once.
+        let product = integer_multiply(0x7FFF_FFFF, 0x7FFF_FFFF);
+        assert_same_number(product, 2147483647.0 * 2147483647.0);
+    }
+
+    #[test]
+    fn modulo_matches_number() {
+        for &a in OPERANDS {
+            for &b in OPERANDS {
+                assert_same_number(integer_modulo(a, b), a as f64 % b as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn negate_matches_number() {
+        for &a in OPERANDS {
+            assert_same_number(integer_negate(a), -(a as f64));
+        }
+    }
+}
@@ -57,6 +57,14 @@ pub trait AudioBackend {
     /// Should be called whenever a MovieClip timeline stops playing or seeks to a new frame.
     fn stop_stream(&mut self, stream: AudioStreamHandle);
 
+    /// Returns whether a sound instance is still playing.
+    /// Sounds that have finished playing or were stopped are no longer playing.
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool;
+
+    /// Sets the volume and panning of a playing sound instance or stream.
+    /// No-op if the sound is not playing.
+    fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
+
     /// Good ol' stopAllSounds() :-)
     fn stop_all_sounds(&mut self);
 
@@ -73,6 +81,19 @@ pub trait AudioBackend {
     /// Returns `None` if sound is not registered.
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32>;
 
+    /// Get the playback position of a sound instance in milliseconds, from the start of the sound.
+    /// Returns `None` if the sound instance is not playing, or if this backend can't tell.
+    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<u32> {
+        None
+    }
+
+    /// Get the peak amplitudes of the left and right channels of a sound instance over the last
+    /// few milliseconds, from 0.0 to 1.0.
+    /// Returns `None` if the sound instance is not playing, or if this backend can't tell.
+    fn get_sound_peak(&self, _instance: SoundInstanceHandle) -> Option<[f32; 2]> {
+        None
+    }
+
     // TODO: Eventually remove this/move it to library.
     fn is_loading_complete(&self) -> bool {
         true
@@ -130,6 +151,9 @@ impl AudioBackend for NullAudioBackend {
     fn stop_stream(&mut self, stream: AudioStreamHandle) {
         self.streams.remove(stream);
     }
+    fn is_sound_playing(&self, _instance: SoundInstanceHandle) -> bool {
+        false
+    }
     fn stop_all_sounds(&mut self) {}
     fn stop_sounds_with_handle(&mut self, _handle: SoundHandle) {}
     fn is_sound_playing_with_handle(&mut self, _handle: SoundHandle) -> bool {
@@ -146,3 +170,73 @@ impl Default for NullAudioBackend {
         NullAudioBackend::new()
     }
 }
+
+/// The volume and panning of a sound, as used by `SoundTransform` in AS3 and `Sound.setTransform`
+/// in AVM1.
+///
+/// The pan is a matrix that mixes the left and right input channels into the left and right
+/// output channels. Transforms of nested display objects are multiplied together.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SoundTransform {
+    pub volume: f32,
+    pub left_to_left: f32,
+    pub left_to_right: f32,
+    pub right_to_left: f32,
+    pub right_to_right: f32,
+}
+
+impl SoundTransform {
+    /// Returns the pan of this transform, from -1.0 (fully left) to 1.0 (fully right).
+    pub fn pan(&self) -> f32 {
+        self.right_to_right - self.left_to_left
+    }
+
+    /// Sets the pan of this transform, from -1.0 (fully left) to 1.0 (fully right).
+    /// Panning to one side lowers the volume of the other side; channels are not mixed.
+    pub fn set_pan(&mut self, pan: f32) {
+        let pan = pan.max(-1.0).min(1.0);
+        self.left_to_left = (1.0 - pan).min(1.0);
+        self.left_to_right = 0.0;
+        self.right_to_left = 0.0;
+        self.right_to_right = (1.0 + pan).min(1.0);
+    }
+
+    /// Applies this transform to a stereo sample frame.
+    pub fn apply(&self, [left, right]: [f32; 2]) -> [f32; 2] {
+        [
+            (left * self.left_to_left + right * self.right_to_left) * self.volume,
+            (left * self.left_to_right + right * self.right_to_right) * self.volume,
+        ]
+    }
+}
+
+impl Default for SoundTransform {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            left_to_left: 1.0,
+            left_to_right: 0.0,
+            right_to_left: 0.0,
+            right_to_right: 1.0,
+        }
+    }
+}
+
+impl std::ops::Mul for SoundTransform {
+    type Output = Self;
+
+    /// Concatenates two transforms; `child` is applied first, then `self`.
+    fn mul(self, child: Self) -> Self {
+        Self {
+            volume: self.volume * child.volume,
+            left_to_left: self.left_to_left * child.left_to_left
+                + self.right_to_left * child.left_to_right,
+            left_to_right: self.left_to_right * child.left_to_left
+                + self.right_to_right * child.left_to_right,
+            right_to_left: self.left_to_left * child.right_to_left
+                + self.right_to_left * child.right_to_right,
+            right_to_right: self.left_to_right * child.right_to_left
+                + self.right_to_right * child.right_to_right,
+        }
+    }
+}
@@ -5,7 +5,7 @@ mod mp3;
 mod pcm;
 
 pub use adpcm::AdpcmDecoder;
-pub use mp3::Mp3Decoder;
+pub use mp3::{count_mp3_sample_frames, Mp3Decoder};
 pub use pcm::PcmDecoder;
 
 use crate::tag_utils::SwfSlice;
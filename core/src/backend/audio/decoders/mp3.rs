@@ -170,3 +170,66 @@ impl<R: AsRef<[u8]> + Default> SeekableDecoder for Mp3Decoder<Cursor<R>> {
         *self = Mp3Decoder::new(self.num_channels, self.sample_rate, cursor);
     }
 }
+
+/// Counts the sample frames of MP3 data by walking its frame headers, without decoding it.
+/// Used for the duration of MP3 sounds whose `DefineSound` tag has no sample count.
+pub fn count_mp3_sample_frames(data: &[u8]) -> u32 {
+    // Layer III bitrates in kbps, by bitrate index.
+    const MPEG1_BITRATES: [u32; 15] = [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ];
+    const MPEG2_BITRATES: [u32; 15] =
+        [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+    let mut num_sample_frames = 0;
+    let mut pos = 0;
+    while pos + 4 <= data.len() {
+        let header = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+        let is_layer_3 = (header >> 17) & 0b11 == 0b01;
+        if header & 0xffe0_0000 != 0xffe0_0000 || !is_layer_3 {
+            // Not a frame header; resynchronize.
+            pos += 1;
+            continue;
+        }
+
+        let version = (header >> 19) & 0b11;
+        let bitrate_index = ((header >> 12) & 0b1111) as usize;
+        let sample_rate_index = ((header >> 10) & 0b11) as usize;
+        let padding = (header >> 9) & 1;
+        if version == 0b01 || bitrate_index == 0 || bitrate_index == 15 || sample_rate_index == 3 {
+            pos += 1;
+            continue;
+        }
+
+        let (bitrates, sample_rates, samples_per_frame, slot_factor) = match version {
+            0b11 => (&MPEG1_BITRATES, [44100, 48000, 32000], 1152, 144),
+            0b10 => (&MPEG2_BITRATES, [22050, 24000, 16000], 576, 72),
+            _ => (&MPEG2_BITRATES, [11025, 12000, 8000], 576, 72),
+        };
+        let bitrate = bitrates[bitrate_index] * 1000;
+        let sample_rate = sample_rates[sample_rate_index];
+        let frame_len = (slot_factor * bitrate / sample_rate + padding) as usize;
+
+        num_sample_frames += samples_per_frame;
+        pos += frame_len;
+    }
+    num_sample_frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_sample_frames() {
+        // MPEG-1 Layer III, 128kbps, 44.1kHz, no padding: 417 bytes per frame.
+        let mut data = vec![];
+        for _ in 0..3 {
+            let mut frame = vec![0; 417];
+            frame[..4].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+            data.extend(frame);
+        }
+        assert_eq!(count_mp3_sample_frames(&data), 3 * 1152);
+        assert_eq!(count_mp3_sample_frames(&[]), 0);
+    }
+}
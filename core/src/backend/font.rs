@@ -0,0 +1,39 @@
+/// Loads fonts installed on the system, for text fields that use device fonts.
+pub trait FontProvider {
+    /// Loads the system font with the given family name, as a `DefineFont3` tag with glyph
+    /// outlines and layout metrics.
+    ///
+    /// The `_sans`, `_serif` and `_typewriter` names refer to the default font of that kind.
+    /// If the family is not installed, a default font is used instead.
+    /// Returns `None` if no font could be loaded at all.
+    fn load_device_font(&mut self, name: &str, is_bold: bool, is_italic: bool)
+        -> Option<swf::Font>;
+}
+
+/// Font provider that has no system fonts.
+///
+/// Text fields using device fonts will fall back to the built-in device font.
+pub struct NullFontProvider {}
+
+impl NullFontProvider {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl FontProvider for NullFontProvider {
+    fn load_device_font(
+        &mut self,
+        _name: &str,
+        _is_bold: bool,
+        _is_italic: bool,
+    ) -> Option<swf::Font> {
+        None
+    }
+}
+
+impl Default for NullFontProvider {
+    fn default() -> Self {
+        NullFontProvider::new()
+    }
+}
@@ -1,11 +1,7 @@
-use crate::events::KeyCode;
 use downcast_rs::Downcast;
+use std::sync::Arc;
 
 pub trait InputBackend: Downcast {
-    fn is_key_down(&self, key: KeyCode) -> bool;
-
-    fn get_last_key_code(&self) -> KeyCode;
-
     fn mouse_visible(&self) -> bool;
 
     fn hide_mouse(&mut self);
@@ -15,29 +11,41 @@ pub trait InputBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Get the text content of the clipboard, or an empty string if it
+    /// holds no text.
+    fn clipboard_content(&mut self) -> String;
+
     /// Set the clipboard to the given content
     fn set_clipboard_content(&mut self, content: String);
+
+    /// Begin a print job, showing a print dialog if the platform has one.
+    ///
+    /// Returns the format of the paper that will be printed on, or `None` if
+    /// printing is unavailable or the user cancelled the dialog.
+    fn start_print_job(&mut self) -> Option<PrintPageFormat>;
+
+    /// Print the pages of the job started by `start_print_job`.
+    fn print_pages(&mut self, pages: Vec<PrintedPage>);
 }
 impl_downcast!(InputBackend);
 
 /// Input backend that does nothing
-pub struct NullInputBackend {}
+///
+/// The clipboard is kept in memory, so that movies can read back what they
+/// copied.
+pub struct NullInputBackend {
+    clipboard: String,
+}
 
 impl NullInputBackend {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            clipboard: String::new(),
+        }
     }
 }
 
 impl InputBackend for NullInputBackend {
-    fn is_key_down(&self, _key: KeyCode) -> bool {
-        false
-    }
-
-    fn get_last_key_code(&self) -> KeyCode {
-        KeyCode::Unknown
-    }
-
     fn mouse_visible(&self) -> bool {
         true
     }
@@ -48,7 +56,19 @@ impl InputBackend for NullInputBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
-    fn set_clipboard_content(&mut self, _content: String) {}
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content;
+    }
+
+    fn start_print_job(&mut self) -> Option<PrintPageFormat> {
+        None
+    }
+
+    fn print_pages(&mut self, _pages: Vec<PrintedPage>) {}
 }
 
 impl Default for NullInputBackend {
@@ -59,7 +79,7 @@ impl Default for NullInputBackend {
 
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the input backend via `InputBackend::set_mouse_cursor`.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MouseCursor {
     /// The default arrow icon.
     /// Equivalent to AS3 `MouseCursor.ARROW`.
@@ -76,4 +96,82 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A custom cursor registered by the movie.
+    /// Registered with AS3 `Mouse.registerCursor`.
+    Custom(Arc<CustomCursor>),
+}
+
+/// A custom mouse cursor image, possibly animated.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CustomCursor {
+    /// The width of each frame, in pixels.
+    pub width: u32,
+
+    /// The height of each frame, in pixels.
+    pub height: u32,
+
+    /// The frames of the cursor, as unmultiplied RGBA pixels.
+    ///
+    /// A cursor with no frames should be displayed as the default arrow.
+    pub frames: Vec<Vec<u8>>,
+
+    /// The position of the cursor's hotspot within each frame, in pixels.
+    pub hotspot: (u32, u32),
+
+    /// The rate at which the frames are animated, in frames per second.
+    pub frame_rate: u32,
+}
+
+/// The orientation of the paper of a print job.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PrintOrientation {
+    Portrait,
+    Landscape,
+}
+
+/// The paper of a print job, as reported by the input backend.
+///
+/// All sizes are in points (1/72 of an inch).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PrintPageFormat {
+    /// The size of the paper.
+    pub paper_width: u32,
+    pub paper_height: u32,
+
+    /// The size of the printable area of the paper.
+    pub page_width: u32,
+    pub page_height: u32,
+
+    pub orientation: PrintOrientation,
+}
+
+impl Default for PrintPageFormat {
+    /// US Letter paper in portrait orientation, with quarter-inch margins.
+    fn default() -> Self {
+        Self {
+            paper_width: 612,
+            paper_height: 792,
+            page_width: 576,
+            page_height: 756,
+            orientation: PrintOrientation::Portrait,
+        }
+    }
+}
+
+/// A rendered page of a print job.
+#[derive(Debug)]
+pub struct PrintedPage {
+    /// The width of the image, in pixels.
+    pub width: u32,
+
+    /// The height of the image, in pixels.
+    pub height: u32,
+
+    /// The image of the page, as unmultiplied RGBA pixels.
+    pub pixels: Vec<u8>,
+
+    /// The number of pixels per point, i.e. the resolution of the image
+    /// divided by 72 DPI.
+    pub pixels_per_point: f64,
 }
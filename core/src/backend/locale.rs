@@ -3,7 +3,14 @@ use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 pub trait LocaleBackend {
     fn get_current_date_time(&self) -> DateTime<Utc>;
 
+    /// The current offset of the local timezone from UTC.
     fn get_timezone(&self) -> FixedOffset;
+
+    /// The offset of the local timezone from UTC at the given time, which differs from the
+    /// current one across daylight saving time changes.
+    fn get_timezone_at(&self, _date: DateTime<Utc>) -> FixedOffset {
+        self.get_timezone()
+    }
 }
 
 /// Locale backend that mostly does nothing.
@@ -1,6 +1,7 @@
 //! Browser-related platform functions
 
 use crate::loader::Error;
+use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use indexmap::IndexMap;
 use std::borrow::Cow;
 use std::collections::VecDeque;
@@ -69,7 +70,7 @@ pub fn url_from_relative_url(base: &str, relative: &str) -> Result<Url, ParseErr
 }
 
 /// Enumerates all possible navigation methods.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum NavigationMethod {
     /// Indicates that navigation should generate a GET request.
     GET,
@@ -107,6 +108,9 @@ pub struct RequestOptions {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// Additional HTTP headers to be sent with the request.
+    headers: Vec<(String, String)>,
 }
 
 impl RequestOptions {
@@ -115,6 +119,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::GET,
             body: None,
+            headers: Vec::new(),
         }
     }
 
@@ -123,7 +128,22 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::POST,
             body,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Replace the mime type of this request's body, if it has one.
+    pub fn with_content_type(mut self, mime: String) -> Self {
+        if let Some((_, body_mime)) = &mut self.body {
+            *body_mime = mime;
         }
+        self
+    }
+
+    /// Add additional HTTP headers to this request.
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers.extend(headers);
+        self
     }
 
     /// Retrieve the navigation method for this request.
@@ -135,6 +155,11 @@ impl RequestOptions {
     pub fn body(&self) -> &Option<(Vec<u8>, String)> {
         &self.body
     }
+
+    /// Retrieve the additional HTTP headers of this request.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
 }
 
 /// Type alias for pinned, boxed, and owned futures that output a falliable
@@ -198,6 +223,26 @@ pub trait NavigatorBackend {
     /// current document's base URL, while the most obvious base for a desktop
     /// client would be the file-URL form of the current path.
     fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str>;
+
+    /// Open a TCP connection to the given host and port.
+    ///
+    /// The outcome of the connection attempt, any data received, and the
+    /// connection closing must be reported through `sender`, tagged with
+    /// `handle`. Data to be written to the socket arrives on `receiver`; the
+    /// connection should be closed once its sender has been dropped.
+    ///
+    /// The default implementation does not support sockets, and fails every
+    /// connection attempt.
+    fn connect_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        handle: SocketHandle,
+        _receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+    }
 }
 
 /// A null implementation of an event loop that only supports blocking.
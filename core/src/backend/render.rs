@@ -1,4 +1,5 @@
 use crate::shape_utils::DistilledShape;
+use crate::stage::StageQuality;
 pub use crate::{transform::Transform, Color};
 use downcast_rs::Downcast;
 use std::io::Read;
@@ -31,6 +32,11 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
+    /// Sets the stage quality and the SWF version of the movie for the following frames,
+    /// which together decide whether scaled bitmaps are smoothed.
+    /// See `StageQuality::smooth_bitmaps`.
+    fn set_quality(&mut self, quality: StageQuality, swf_version: u8);
+
     fn begin_frame(&mut self, clear: Color);
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform);
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
@@ -39,6 +45,35 @@ pub trait RenderBackend: Downcast {
     fn push_mask(&mut self);
     fn activate_mask(&mut self);
     fn pop_mask(&mut self);
+
+    /// Creates a transparent bitmap that can be rendered into, used for `cacheAsBitmap`.
+    /// Returns `None` if this backend does not support offscreen rendering.
+    fn create_offscreen_bitmap(&mut self, width: u32, height: u32) -> Option<BitmapHandle>;
+    /// Clears the given offscreen bitmap and renders everything until the matching
+    /// `pop_offscreen_target` into it. Offscreen targets can be nested.
+    fn push_offscreen_target(&mut self, bitmap: BitmapHandle);
+    fn pop_offscreen_target(&mut self);
+    /// Releases an offscreen bitmap created by `create_offscreen_bitmap`.
+    fn remove_offscreen_bitmap(&mut self, bitmap: BitmapHandle);
+    /// Reads back the pixels of an offscreen bitmap, as rendered by the last
+    /// `end_frame`. Returns `None` if this backend can't read back bitmaps.
+    fn offscreen_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap>;
+
+    /// Blends everything rendered until the matching `pop_blend_mode` onto the content below
+    /// it with the given blend mode. Blend modes can be nested; the innermost one applies.
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode);
+    fn pop_blend_mode(&mut self);
+
+    /// Returns a summary of the backend's resource usage, for debugging.
+    fn debug_info(&self) -> String {
+        String::new()
+    }
+
+    /// Returns the number of draw calls submitted for the last frame that was ended, if the
+    /// backend keeps count. This is shown in the diagnostics overlay.
+    fn last_frame_draw_count(&self) -> Option<usize> {
+        None
+    }
 }
 impl_downcast!(RenderBackend);
 
@@ -133,6 +168,7 @@ impl RenderBackend for NullRenderer {
             height: 0,
         })
     }
+    fn set_quality(&mut self, _quality: StageQuality, _swf_version: u8) {}
     fn begin_frame(&mut self, _clear: Color) {}
     fn end_frame(&mut self) {}
     fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform) {}
@@ -141,6 +177,17 @@ impl RenderBackend for NullRenderer {
     fn push_mask(&mut self) {}
     fn activate_mask(&mut self) {}
     fn pop_mask(&mut self) {}
+    fn create_offscreen_bitmap(&mut self, _width: u32, _height: u32) -> Option<BitmapHandle> {
+        None
+    }
+    fn push_offscreen_target(&mut self, _bitmap: BitmapHandle) {}
+    fn pop_offscreen_target(&mut self) {}
+    fn remove_offscreen_bitmap(&mut self, _bitmap: BitmapHandle) {}
+    fn offscreen_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
+        None
+    }
+    fn push_blend_mode(&mut self, _blend_mode: swf::BlendMode) {}
+    fn pop_blend_mode(&mut self) {}
 }
 
 /// The format of image data in a DefineBitsJpeg2/3 tag.
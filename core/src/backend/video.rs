@@ -0,0 +1,184 @@
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use generational_arena::Index;
+use swf::{VideoCodec, VideoDeblocking};
+
+pub type VideoStreamHandle = Index;
+
+type Error = Box<dyn std::error::Error>;
+
+/// An encoded video frame, as stored in a `VideoFrame` tag.
+#[derive(Debug, Copy, Clone)]
+pub struct EncodedFrame<'a> {
+    /// The codec used to encode the frame.
+    pub codec: VideoCodec,
+
+    /// The encoded frame data.
+    pub data: &'a [u8],
+
+    /// The frame number of this frame in its video stream.
+    pub frame_id: u16,
+}
+
+/// Whether a video frame can be decoded on its own.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrameDependency {
+    /// The frame is a keyframe, and decoding it resets the decoder.
+    None,
+
+    /// The frame only encodes differences to the frames decoded before it.
+    Past,
+}
+
+impl FrameDependency {
+    pub fn is_keyframe(self) -> bool {
+        self == FrameDependency::None
+    }
+}
+
+/// Decodes the video streams embedded in SWF files.
+pub trait VideoBackend {
+    /// Creates a decoder for a video stream.
+    fn register_video_stream(
+        &mut self,
+        num_frames: u32,
+        size: (u16, u16),
+        codec: VideoCodec,
+        filter: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error>;
+
+    /// Decodes a frame of a video stream, and uploads it as a bitmap.
+    ///
+    /// Frames must be decoded in order, starting at a keyframe. To seek, decode the nearest
+    /// keyframe before the target frame, followed by every frame up to the target frame.
+    /// The returned bitmap may be reused for later frames of the same stream.
+    fn decode_video_stream_frame(
+        &mut self,
+        stream: VideoStreamHandle,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error>;
+
+    /// Releases the decoder of a video stream.
+    fn remove_video_stream(&mut self, stream: VideoStreamHandle);
+}
+
+/// Video backend that can't decode any video.
+pub struct NullVideoBackend {}
+
+impl NullVideoBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl VideoBackend for NullVideoBackend {
+    fn register_video_stream(
+        &mut self,
+        _num_frames: u32,
+        _size: (u16, u16),
+        _codec: VideoCodec,
+        _filter: VideoDeblocking,
+    ) -> Result<VideoStreamHandle, Error> {
+        Err("Video decoding is not supported by this backend".into())
+    }
+
+    fn decode_video_stream_frame(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _encoded_frame: EncodedFrame<'_>,
+        _renderer: &mut dyn RenderBackend,
+    ) -> Result<BitmapInfo, Error> {
+        Err("Video decoding is not supported by this backend".into())
+    }
+
+    fn remove_video_stream(&mut self, _stream: VideoStreamHandle) {}
+}
+
+impl Default for NullVideoBackend {
+    fn default() -> Self {
+        NullVideoBackend::new()
+    }
+}
+
+/// Determines whether an encoded frame is a keyframe by reading its picture header.
+///
+/// Screen video frames don't say whether they are keyframes, so they are always treated as
+/// depending on the frames before them.
+pub fn frame_dependency(encoded_frame: EncodedFrame<'_>) -> FrameDependency {
+    let data = encoded_frame.data;
+    let is_keyframe = match encoded_frame.codec {
+        VideoCodec::H263 => h263_picture_type(data) == Some(0),
+        // The top bit of the first byte is the frame mode; 0 is an intra frame.
+        VideoCodec::VP6 => data.first().map_or(false, |b| b & 0x80 == 0),
+        // Alpha frames begin with a 24-bit offset to the alpha channel data.
+        VideoCodec::VP6WithAlpha => data.get(3).map_or(false, |b| b & 0x80 == 0),
+        VideoCodec::ScreenVideo => false,
+    };
+    if is_keyframe {
+        FrameDependency::None
+    } else {
+        FrameDependency::Past
+    }
+}
+
+/// Reads the picture type from a Sorenson H.263 picture header.
+/// 0 is an intra frame, 1 is an inter frame, and 2 is a disposable inter frame.
+fn h263_picture_type(data: &[u8]) -> Option<u8> {
+    let bit = |i: usize| -> Option<u32> { Some(u32::from((*data.get(i / 8)? >> (7 - i % 8)) & 1)) };
+    let bits = |start: usize, len: usize| -> Option<u32> {
+        (start..start + len).try_fold(0, |n, i| Some(n << 1 | bit(i)?))
+    };
+
+    // Picture start code (17 bits), version (5 bits) and temporal reference (8 bits).
+    if bits(0, 17)? != 1 {
+        return None;
+    }
+    let size_pos = 17 + 5 + 8;
+    let type_pos = match bits(size_pos, 3)? {
+        // Custom picture dimensions, as either two 8-bit or two 16-bit values.
+        0 => size_pos + 3 + 16,
+        1 => size_pos + 3 + 32,
+        _ => size_pos + 3,
+    };
+    Some(bits(type_pos, 2)? as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h263_frame(picture_type: u8) -> Vec<u8> {
+        // Start code, version 0, temporal reference 0, picture size 2 (QCIF), then the type.
+        let header: u64 = (1 << 23) | (2 << 7) | (u64::from(picture_type) << 5);
+        header.to_be_bytes()[3..8].to_vec()
+    }
+
+    fn frame(codec: VideoCodec, data: &[u8]) -> EncodedFrame<'_> {
+        EncodedFrame {
+            codec,
+            data,
+            frame_id: 0,
+        }
+    }
+
+    #[test]
+    fn h263_keyframes() {
+        let h263 =
+            |picture_type| frame_dependency(frame(VideoCodec::H263, &h263_frame(picture_type)));
+        assert_eq!(h263(0), FrameDependency::None);
+        assert_eq!(h263(1), FrameDependency::Past);
+        assert_eq!(h263(2), FrameDependency::Past);
+        assert_eq!(
+            frame_dependency(frame(VideoCodec::H263, &[0xff, 0xff])),
+            FrameDependency::Past
+        );
+    }
+
+    #[test]
+    fn vp6_keyframes() {
+        assert!(frame_dependency(frame(VideoCodec::VP6, &[0x00, 0x00])).is_keyframe());
+        assert!(!frame_dependency(frame(VideoCodec::VP6, &[0x80, 0x00])).is_keyframe());
+        assert!(frame_dependency(frame(VideoCodec::VP6WithAlpha, &[0, 0, 2, 0x00])).is_keyframe());
+        assert!(!frame_dependency(frame(VideoCodec::VP6WithAlpha, &[0, 0, 2, 0x80])).is_keyframe());
+    }
+}
@@ -0,0 +1,333 @@
+//! Bitmap caching of display objects (`cacheAsBitmap`).
+//!
+//! A cached display object renders its content and children once into an offscreen bitmap,
+//! and on later frames only draws that bitmap. The cache is redrawn when the content of the
+//! object or anything below it changes, or when the scale, rotation or skew it is drawn with
+//! changes. Moving the object does not redraw the cache; like Flash Player, the cached bitmap is
+//! snapped to whole pixels instead.
+
+use crate::backend::render::{BitmapHandle, RenderBackend};
+use crate::context::RenderContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::prelude::*;
+use crate::transform::{Transform, TransformStack};
+use std::collections::HashMap;
+
+/// The largest width or height of a cached bitmap, in pixels.
+/// Flash Player renders larger objects normally instead of caching them.
+const MAX_DIMENSION: u32 = 8191;
+
+/// The largest number of pixels of a cached bitmap.
+const MAX_PIXELS: u32 = 16_777_215;
+
+/// Caches that have not been drawn for this many frames are evicted.
+const MAX_UNUSED_FRAMES: u64 = 60;
+
+/// The total size of all cached bitmaps, in bytes. The least recently drawn caches are evicted
+/// to stay below this.
+const MAX_TOTAL_BYTES: usize = 128 * 1024 * 1024;
+
+/// The bitmap cache of a single display object.
+#[derive(Copy, Clone, Debug)]
+pub struct BitmapCache {
+    /// The ID of the bitmap in `BitmapCaches`.
+    id: u64,
+
+    /// The scale, rotation and skew components of the matrix the bitmap was drawn with.
+    scale: [f32; 4],
+
+    /// The position of the top-left corner of the bitmap relative to the object's origin, after
+    /// scaling.
+    offset: (Twips, Twips),
+
+    width: u32,
+    height: u32,
+}
+
+struct CachedBitmap {
+    handle: BitmapHandle,
+    width: u32,
+    height: u32,
+    last_drawn: u64,
+}
+
+impl CachedBitmap {
+    fn size_in_bytes(&self) -> usize {
+        self.width as usize * self.height as usize * 4
+    }
+}
+
+/// Owns the offscreen bitmaps of all cached display objects.
+///
+/// Display objects only hold the ID of their bitmap, so that bitmaps of objects that are no longer
+/// rendered can be evicted without visiting them.
+#[derive(Default)]
+pub struct BitmapCaches {
+    bitmaps: HashMap<u64, CachedBitmap>,
+    next_id: u64,
+    frame: u64,
+}
+
+impl BitmapCaches {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the bitmap with the given ID, marking it as drawn this frame.
+    fn get(&mut self, id: u64) -> Option<BitmapHandle> {
+        let frame = self.frame;
+        self.bitmaps.get_mut(&id).map(|bitmap| {
+            bitmap.last_drawn = frame;
+            bitmap.handle
+        })
+    }
+
+    fn insert(&mut self, handle: BitmapHandle, width: u32, height: u32) -> u64 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.bitmaps.insert(
+            id,
+            CachedBitmap {
+                handle,
+                width,
+                height,
+                last_drawn: self.frame,
+            },
+        );
+        id
+    }
+
+    fn remove(&mut self, id: u64, renderer: &mut dyn RenderBackend) {
+        if let Some(bitmap) = self.bitmaps.remove(&id) {
+            renderer.remove_offscreen_bitmap(bitmap.handle);
+        }
+    }
+
+    /// Evicts bitmaps that have not been drawn recently, or that exceed the memory budget.
+    /// Called by the player after every rendered frame.
+    pub fn end_frame(&mut self, renderer: &mut dyn RenderBackend) {
+        let frame = self.frame;
+        let mut total_bytes = 0;
+        let mut by_age: Vec<(u64, u64)> = Vec::with_capacity(self.bitmaps.len());
+        for (&id, bitmap) in &self.bitmaps {
+            if frame - bitmap.last_drawn < MAX_UNUSED_FRAMES {
+                total_bytes += bitmap.size_in_bytes();
+            }
+            by_age.push((bitmap.last_drawn, id));
+        }
+        by_age.sort_unstable();
+
+        for (last_drawn, id) in by_age {
+            let is_stale = frame - last_drawn >= MAX_UNUSED_FRAMES;
+            if !is_stale && total_bytes <= MAX_TOTAL_BYTES {
+                break;
+            }
+            if !is_stale {
+                total_bytes -= self.bitmaps[&id].size_in_bytes();
+            }
+            self.remove(id, renderer);
+        }
+
+        self.frame += 1;
+    }
+}
+
+/// Renders a display object through its bitmap cache, redrawing the cache first if it is invalid.
+///
+/// Returns `false` if the object can't be cached, because it is too large or the render backend
+/// has no offscreen rendering. The caller should then render the object normally.
+pub fn render_cached<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+) -> bool {
+    let parent_transform = context.transform_stack.transform().clone();
+    let matrix = parent_transform.matrix * *display_object.matrix();
+    let scale = [matrix.a, matrix.b, matrix.c, matrix.d];
+    let scale_matrix = Matrix {
+        a: matrix.a,
+        b: matrix.b,
+        c: matrix.c,
+        d: matrix.d,
+        tx: Twips::new(0),
+        ty: Twips::new(0),
+    };
+
+    let bounds = display_object.bounds_with_transform(&scale_matrix);
+    if !bounds.valid {
+        // Nothing to draw.
+        return true;
+    }
+    let x_min = bounds.x_min.to_pixels().floor();
+    let y_min = bounds.y_min.to_pixels().floor();
+    let width = (bounds.x_max.to_pixels().ceil() - x_min) as u32;
+    let height = (bounds.y_max.to_pixels().ceil() - y_min) as u32;
+    if width == 0 || height == 0 {
+        return true;
+    }
+    if width > MAX_DIMENSION || height > MAX_DIMENSION || width * height > MAX_PIXELS {
+        return false;
+    }
+    let offset = (Twips::from_pixels(x_min), Twips::from_pixels(y_min));
+
+    // The own transform of the object is applied when drawing the bitmap, so only its content
+    // and the subtree below it can invalidate the cache.
+    let (content_changed, _) = display_object.take_changes(context.gc_context);
+    let subtree_changed = take_subtree_changes(context, display_object);
+
+    let cache = display_object.bitmap_cache();
+    let existing = cache.and_then(|cache| {
+        context
+            .bitmap_caches
+            .get(cache.id)
+            .map(|handle| (cache, handle))
+    });
+    let handle = match existing {
+        Some((cache, handle))
+            if cache.scale == scale
+                && cache.offset == offset
+                && cache.width == width
+                && cache.height == height
+                && !content_changed
+                && !subtree_changed =>
+        {
+            handle
+        }
+        _ => {
+            let (id, handle) = match existing {
+                Some((cache, handle)) if cache.width == width && cache.height == height => {
+                    (cache.id, handle)
+                }
+                _ => {
+                    if let Some(cache) = cache {
+                        context
+                            .bitmap_caches
+                            .remove(cache.id, &mut *context.renderer);
+                    }
+                    let handle = match context.renderer.create_offscreen_bitmap(width, height) {
+                        Some(handle) => handle,
+                        None => {
+                            display_object.set_bitmap_cache(context.gc_context, None);
+                            return false;
+                        }
+                    };
+                    (context.bitmap_caches.insert(handle, width, height), handle)
+                }
+            };
+
+            draw_cache(
+                context,
+                display_object,
+                handle,
+                Matrix {
+                    tx: Twips::new(0) - offset.0,
+                    ty: Twips::new(0) - offset.1,
+                    ..scale_matrix
+                },
+            );
+            display_object.set_bitmap_cache(
+                context.gc_context,
+                Some(BitmapCache {
+                    id,
+                    scale,
+                    offset,
+                    width,
+                    height,
+                }),
+            );
+            handle
+        }
+    };
+
+    let transform = Transform {
+        matrix: Matrix {
+            tx: Twips::from_pixels((matrix.tx + offset.0).to_pixels().round()),
+            ty: Twips::from_pixels((matrix.ty + offset.1).to_pixels().round()),
+            ..Default::default()
+        },
+        color_transform: parent_transform.color_transform * *display_object.color_transform(),
+    };
+    context.renderer.render_bitmap(handle, &transform);
+    true
+}
+
+/// Renders the content of a display object into its cached bitmap.
+fn draw_cache<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+    handle: BitmapHandle,
+    matrix: Matrix,
+) {
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix,
+        ..Default::default()
+    });
+
+    context.renderer.push_offscreen_target(handle);
+    let mut cache_context = RenderContext {
+        renderer: &mut *context.renderer,
+        video: &mut *context.video,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        // The whole object is cached, even the parts outside of the stage.
+        view_bounds: display_object.world_bounds(),
+        clip_depth_stack: vec![],
+        gc_context: context.gc_context,
+        bitmap_caches: &mut *context.bitmap_caches,
+    };
+    display_object.render_self(&mut cache_context);
+    cache_context.renderer.pop_offscreen_target();
+}
+
+/// Clears the change flags of every object below the given display object, returning whether any
+/// of them changed.
+fn take_subtree_changes<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+) -> bool {
+    let mut changed = false;
+    for child in display_object.children() {
+        let (content_changed, transform_changed) = child.take_changes(context.gc_context);
+        // Every child must be visited to clear its flags.
+        let subtree_changed = take_subtree_changes(context, child);
+        changed |= content_changed || transform_changed || subtree_changed;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::render::NullRenderer;
+
+    #[test]
+    fn evicts_unused_bitmaps() {
+        let mut renderer = NullRenderer::new();
+        let mut caches = BitmapCaches::new();
+        let used = caches.insert(BitmapHandle(0), 10, 10);
+        let unused = caches.insert(BitmapHandle(1), 10, 10);
+
+        for _ in 0..=MAX_UNUSED_FRAMES {
+            assert!(caches.get(used).is_some());
+            caches.end_frame(&mut renderer);
+        }
+
+        assert!(caches.get(used).is_some());
+        assert!(caches.get(unused).is_none());
+    }
+
+    #[test]
+    fn evicts_least_recently_drawn_over_budget() {
+        let mut renderer = NullRenderer::new();
+        let mut caches = BitmapCaches::new();
+        let old = caches.insert(BitmapHandle(0), 4096, 4096);
+        caches.end_frame(&mut renderer);
+        let new = caches.insert(BitmapHandle(1), 4096, 4096);
+        let newest = caches.insert(BitmapHandle(2), 4096, 4096);
+        caches.end_frame(&mut renderer);
+
+        assert!(caches.get(old).is_none());
+        assert!(caches.get(new).is_some());
+        assert!(caches.get(newest).is_some());
+    }
+}
@@ -1,22 +1,36 @@
 //! Contexts and helper types passed between functions.
 use crate::avm1;
 
+use crate::audio_manager::AudioManager;
 use crate::avm1::globals::system::SystemProperties;
-use crate::avm1::{Avm1, Object, Timers, Value};
+use crate::avm1::{Avm1, Object, Value};
 use crate::avm2::Avm2;
+use crate::backend::font::FontProvider;
 use crate::backend::input::InputBackend;
 use crate::backend::locale::LocaleBackend;
 use crate::backend::storage::StorageBackend;
+use crate::backend::video::VideoBackend;
 use crate::backend::{audio::AudioBackend, navigator::NavigatorBackend, render::RenderBackend};
+use crate::bitmap_cache::BitmapCaches;
+use crate::context_menu::ContextMenuState;
 use crate::display_object::EditText;
 use crate::external::ExternalInterface;
+use crate::focus_tracker::FocusTracker;
+use crate::keyboard::Keyboard;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
+use crate::net_stream::NetStreams;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::print_job::PrintJobs;
+use crate::socket::Sockets;
+use crate::stage::{StageAlign, StageQuality, StageScaleMode};
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::timer::Timers;
 use crate::transform::TransformStack;
 use core::fmt;
+use enumset::EnumSet;
 use gc_arena::{Collect, CollectionContext, MutationContext};
 use rand::rngs::SmallRng;
 use std::collections::{BTreeMap, HashMap, VecDeque};
@@ -57,6 +71,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The audio backend, used by display objects and AVM to play audio.
     pub audio: &'a mut (dyn AudioBackend + 'a),
 
+    /// The sounds that are currently playing, and the display objects that own them.
+    pub audio_manager: &'a mut AudioManager<'gc>,
+
     /// The navigator backend, used by the AVM to make HTTP requests and visit webpages.
     pub navigator: &'a mut (dyn NavigatorBackend + 'a),
 
@@ -66,12 +83,21 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The input backend, used to detect user interactions.
     pub input: &'a mut dyn InputBackend,
 
+    /// The keys held down and the last key event.
+    pub keyboard: &'a mut Keyboard,
+
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
     /// The locale backend, used for localisation and personalisation
     pub locale: &'a mut dyn LocaleBackend,
 
+    /// The font provider, used to load system fonts for text fields that use device fonts.
+    pub font_provider: &'a mut dyn FontProvider,
+
+    /// The video backend, used to decode the frames of embedded video streams.
+    pub video: &'a mut dyn VideoBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -85,15 +111,38 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The display object that the mouse is currently hovering over.
     pub mouse_hovered_object: Option<DisplayObject<'gc>>,
 
+    /// The display object that has keyboard focus.
+    pub focus_tracker: FocusTracker<'gc>,
+
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
     /// The object being dragged via a `startDrag` action.
     pub drag_object: &'a mut Option<crate::player::DragObject<'gc>>,
 
+    /// The automatic and script-chosen mouse cursors.
+    pub mouse_cursor: &'a mut crate::player::MouseCursorState,
+
     /// The dimensions of the stage.
     pub stage_size: (Twips, Twips),
 
+    /// The matrix transforming the stage into the viewport.
+    pub view_matrix: Matrix,
+
+    /// How the movie is scaled to fit the viewport.
+    pub scale_mode: &'a mut StageScaleMode,
+
+    /// The edges of the viewport that the stage is anchored to.
+    pub stage_align: &'a mut EnumSet<StageAlign>,
+
+    /// How finely the movie is rendered.
+    pub quality: &'a mut StageQuality,
+
+    /// The frame rate of the movie, in FPS.
+    ///
+    /// Use `Player::clamp_frame_rate` when changing this.
+    pub frame_rate: &'a mut f64,
+
     /// Weak reference to the player.
     ///
     /// Recipients of an update context may upgrade the reference to ensure
@@ -121,6 +170,18 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     pub timers: &'a mut Timers<'gc>,
 
+    /// Sockets opened by `XMLSocket`.
+    pub sockets: &'a mut Sockets<'gc>,
+
+    /// Connections opened by `LocalConnection`.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
+    /// Print jobs started by `PrintJob`.
+    pub print_jobs: &'a mut PrintJobs<'gc>,
+
+    /// Videos played by `NetStream`.
+    pub net_streams: &'a mut NetStreams<'gc>,
+
     /// The AVM1 global state.
     pub avm1: &'a mut Avm1<'gc>,
 
@@ -129,6 +190,16 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// External interface for (for example) Javascript <-> Actionscript interaction
     pub external_interface: &'a mut ExternalInterface<'gc>,
+
+    /// The context menu that is currently open, if any.
+    pub current_context_menu: &'a mut Option<ContextMenuState<'gc>>,
+
+    /// Whether a user-initiated event, such as a mouse click or key press, is
+    /// currently being handled.
+    ///
+    /// Some actions, like writing to the clipboard, are only allowed while
+    /// this is set.
+    pub is_user_event: bool,
 }
 
 unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context> {
@@ -140,6 +211,7 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.needs_render.trace(cc);
         self.swf.trace(cc);
         self.audio.trace(cc);
+        self.audio_manager.trace(cc);
         self.navigator.trace(cc);
         self.renderer.trace(cc);
         self.input.trace(cc);
@@ -148,16 +220,23 @@ unsafe impl<'a, 'gc, 'gc_context> Collect for UpdateContext<'a, 'gc, 'gc_context
         self.levels.trace(cc);
         self.system_prototypes.trace(cc);
         self.mouse_hovered_object.trace(cc);
+        self.focus_tracker.trace(cc);
         self.mouse_position.trace(cc);
         self.drag_object.trace(cc);
+        self.mouse_cursor.trace(cc);
         self.load_manager.trace(cc);
         self.system.trace(cc);
         self.instance_counter.trace(cc);
         self.shared_objects.trace(cc);
         self.unbound_text_fields.trace(cc);
         self.timers.trace(cc);
+        self.sockets.trace(cc);
+        self.local_connections.trace(cc);
+        self.print_jobs.trace(cc);
+        self.net_streams.trace(cc);
         self.avm1.trace(cc);
         self.avm2.trace(cc);
+        self.current_context_menu.trace(cc);
     }
 }
 
@@ -182,18 +261,29 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             needs_render: self.needs_render,
             swf: self.swf,
             audio: self.audio,
+            audio_manager: self.audio_manager,
             navigator: self.navigator,
             renderer: self.renderer,
             locale: self.locale,
+            font_provider: self.font_provider,
+            video: self.video,
             input: self.input,
+            keyboard: self.keyboard,
             storage: self.storage,
             rng: self.rng,
             levels: self.levels,
             system_prototypes: self.system_prototypes.clone(),
             mouse_hovered_object: self.mouse_hovered_object,
+            focus_tracker: self.focus_tracker,
             mouse_position: self.mouse_position,
             drag_object: self.drag_object,
+            mouse_cursor: self.mouse_cursor,
             stage_size: self.stage_size,
+            view_matrix: self.view_matrix,
+            scale_mode: self.scale_mode,
+            stage_align: self.stage_align,
+            quality: self.quality,
+            frame_rate: self.frame_rate,
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
@@ -201,9 +291,15 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             shared_objects: self.shared_objects,
             unbound_text_fields: self.unbound_text_fields,
             timers: self.timers,
+            sockets: self.sockets,
+            local_connections: self.local_connections,
+            print_jobs: self.print_jobs,
+            net_streams: self.net_streams,
             avm1: self.avm1,
             avm2: self.avm2,
             external_interface: self.external_interface,
+            current_context_menu: self.current_context_menu,
+            is_user_event: self.is_user_event,
         }
     }
 }
@@ -300,6 +396,9 @@ pub struct RenderContext<'a, 'gc> {
     /// The renderer, used by the display objects to draw themselves.
     pub renderer: &'a mut dyn RenderBackend,
 
+    /// The video backend, used to decode the frames of video display objects.
+    pub video: &'a mut dyn VideoBackend,
+
     /// The library, which provides access to fonts and other definitions when rendering.
     pub library: &'a Library<'gc>,
 
@@ -310,6 +409,12 @@ pub struct RenderContext<'a, 'gc> {
 
     /// The stack of clip depths, used in masking.
     pub clip_depth_stack: Vec<Depth>,
+
+    /// The mutation context, used by display objects to update their bitmap caches.
+    pub gc_context: MutationContext<'gc, 'a>,
+
+    /// The offscreen bitmaps of display objects with `cacheAsBitmap` set.
+    pub bitmap_caches: &'a mut BitmapCaches,
 }
 
 /// The type of action being run.
@@ -318,7 +423,8 @@ pub enum ActionType<'gc> {
     /// Normal frame or event actions.
     Normal { bytecode: SwfSlice },
 
-    /// Construct a movie with a custom class or on(construct) events
+    /// Construct a movie with a custom class, running its `onClipEvent(initialize)`
+    /// and `onClipEvent(construct)` events first
     Construct {
         constructor: Option<Object<'gc>>,
         events: Vec<SwfSlice>,
@@ -344,6 +450,9 @@ pub enum ActionType<'gc> {
         is_lazy_initialize: bool,
         abc: SwfSlice,
     },
+
+    /// Construct the AVM2 document class of a movie on its main timeline.
+    ConstructDocumentClass { class_name: String },
 }
 
 impl fmt::Debug for ActionType<'_> {
@@ -387,6 +496,10 @@ impl fmt::Debug for ActionType<'_> {
                 .field("is_lazy_initialize", is_lazy_initialize)
                 .field("bytecode", abc)
                 .finish(),
+            ActionType::ConstructDocumentClass { class_name } => f
+                .debug_struct("ActionType::ConstructDocumentClass")
+                .field("class_name", class_name)
+                .finish(),
         }
     }
 }
@@ -0,0 +1,185 @@
+//! Context menu items
+//!
+//! When the context menu is opened, the player builds a list of items from
+//! the built-in player items and any custom items added by the movie. The
+//! frontend displays those items, and reports the index of the chosen item
+//! back to the player, which then runs the matching callback.
+
+use crate::avm1;
+use crate::display_object::DisplayObject;
+use gc_arena::Collect;
+
+/// The maximum number of custom items that may be added to a menu.
+/// Any further items are ignored.
+pub const MAX_CUSTOM_ITEMS: usize = 15;
+
+/// The maximum length of a custom item's caption, in characters.
+const MAX_CAPTION_LENGTH: usize = 100;
+
+/// Words that may not appear in the caption of a custom item.
+const RESERVED_CAPTION_WORDS: &[&str] = &["adobe", "macromedia", "flash player", "settings"];
+
+/// Captions of the built-in items, which custom items may not reuse.
+const BUILT_IN_CAPTIONS: &[&str] = &[
+    "zoom in",
+    "zoom out",
+    "100%",
+    "show all",
+    "quality",
+    "play",
+    "loop",
+    "rewind",
+    "forward",
+    "back",
+    "movie not loaded",
+    "about",
+    "print",
+    "show redraw regions",
+    "debugger",
+    "undo",
+    "cut",
+    "copy",
+    "paste",
+    "delete",
+    "select all",
+    "open",
+    "open in new window",
+    "copy link",
+];
+
+/// The items of the context menu that is currently open.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct ContextMenuState<'gc> {
+    info: Vec<ContextMenuItem>,
+    callbacks: Vec<ContextMenuCallback<'gc>>,
+}
+
+impl<'gc> ContextMenuState<'gc> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add an item to the end of the menu.
+    pub fn push(&mut self, item: ContextMenuItem, callback: ContextMenuCallback<'gc>) {
+        self.info.push(item);
+        self.callbacks.push(callback);
+    }
+
+    /// The items to be displayed by the frontend.
+    pub fn info(&self) -> &[ContextMenuItem] {
+        &self.info
+    }
+
+    /// The callback to run when the item at the given index is chosen.
+    pub fn callback(&self, index: usize) -> Option<&ContextMenuCallback<'gc>> {
+        self.callbacks.get(index)
+    }
+}
+
+/// A single item of the context menu, as displayed by the frontend.
+#[derive(Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub struct ContextMenuItem {
+    /// The text of the item.
+    pub caption: String,
+
+    /// Whether the item can be chosen. Disabled items are still displayed,
+    /// but greyed out.
+    pub enabled: bool,
+
+    /// Whether a separator should be displayed above the item.
+    pub separator_before: bool,
+
+    /// Whether the item should be displayed with a check mark.
+    pub checked: bool,
+}
+
+/// The action to take when a context menu item is chosen.
+pub enum ContextMenuCallback<'gc> {
+    /// Toggle playback of the root movie.
+    Play,
+
+    /// Go to the first frame of the root movie.
+    Rewind,
+
+    /// Go to the next frame of the root movie.
+    Forward,
+
+    /// Go to the previous frame of the root movie.
+    Back,
+
+    /// Show or hide the diagnostics overlay.
+    ToggleDebugOverlay,
+
+    /// Call the `onSelect` handler of an AVM1 `ContextMenuItem`.
+    Avm1 {
+        /// The object whose menu was opened.
+        target: DisplayObject<'gc>,
+
+        /// The `ContextMenuItem` that was chosen.
+        item: avm1::Object<'gc>,
+
+        /// The item's `onSelect` handler.
+        callback: avm1::Object<'gc>,
+    },
+}
+
+unsafe impl<'gc> Collect for ContextMenuCallback<'gc> {
+    #[inline]
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        if let ContextMenuCallback::Avm1 {
+            target,
+            item,
+            callback,
+        } = self
+        {
+            target.trace(cc);
+            item.trace(cc);
+            callback.trace(cc);
+        }
+    }
+}
+
+/// Determine if a movie may add a custom item with the given caption.
+///
+/// Flash Player silently ignores items with captions that are empty, too
+/// long, contain line breaks or reserved words, or could be mistaken for one
+/// of the built-in items.
+pub fn is_valid_custom_caption(caption: &str) -> bool {
+    let trimmed = caption.trim();
+    if trimmed.is_empty()
+        || caption.chars().count() > MAX_CAPTION_LENGTH
+        || caption.contains(|c| c == '\n' || c == '\r')
+    {
+        return false;
+    }
+
+    let lower = trimmed.to_lowercase();
+    !RESERVED_CAPTION_WORDS
+        .iter()
+        .any(|word| lower.contains(word))
+        && !BUILT_IN_CAPTIONS.contains(&lower.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_caption_restrictions() {
+        assert!(is_valid_custom_caption("Restart level"));
+        assert!(is_valid_custom_caption(&"a".repeat(MAX_CAPTION_LENGTH)));
+
+        assert!(!is_valid_custom_caption(""));
+        assert!(!is_valid_custom_caption("   "));
+        assert!(!is_valid_custom_caption(
+            &"a".repeat(MAX_CAPTION_LENGTH + 1)
+        ));
+        assert!(!is_valid_custom_caption("Two\nlines"));
+        assert!(!is_valid_custom_caption("About Adobe Flash"));
+        assert!(!is_valid_custom_caption("Game Settings"));
+        assert!(!is_valid_custom_caption("Play"));
+        assert!(!is_valid_custom_caption(" zoom in "));
+    }
+}
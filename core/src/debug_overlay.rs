@@ -0,0 +1,310 @@
+//! Diagnostics overlay
+//!
+//! When enabled, the player measures how long each part of a frame takes and
+//! draws a small panel with the results in the top-left corner of the
+//! viewport. The panel is drawn with the device font and a `Drawing`, so it
+//! works on every render backend.
+
+use crate::context::RenderContext;
+use crate::drawing::Drawing;
+use crate::font::{EvalParameters, Font};
+use crate::prelude::*;
+use crate::shape_utils::DrawCommand;
+use crate::transform::Transform;
+use std::time::Duration;
+use swf::FillStyle;
+
+/// The number of measurements that each figure is averaged over.
+const SAMPLE_COUNT: usize = 30;
+
+/// The number of rendered frames between updates of the panel's text.
+///
+/// Counting display objects walks the whole display list, so this is not
+/// done on every frame.
+const REFRESH_INTERVAL: u32 = 10;
+
+/// The size of the panel's text, in pixels.
+const TEXT_SIZE: f64 = 12.0;
+
+/// The space between the edge of the panel and its text, in pixels.
+const PADDING: f64 = 4.0;
+
+/// The width of the panel, in pixels.
+const PANEL_WIDTH: f64 = 280.0;
+
+/// The number of lines of text in the panel.
+const LINE_COUNT: usize = 5;
+
+/// A moving average over the last `SAMPLE_COUNT` measurements.
+#[derive(Default)]
+struct Samples {
+    values: [f64; SAMPLE_COUNT],
+    next: usize,
+    len: usize,
+    sum: f64,
+}
+
+impl Samples {
+    fn push(&mut self, value: f64) {
+        self.sum += value - self.values[self.next];
+        self.values[self.next] = value;
+        self.next = (self.next + 1) % SAMPLE_COUNT;
+        self.len = (self.len + 1).min(SAMPLE_COUNT);
+    }
+
+    fn average(&self) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            self.sum / self.len as f64
+        }
+    }
+}
+
+/// Counts of live objects, taken when the panel's text is refreshed.
+#[derive(Clone, Debug, Default)]
+pub struct DebugCounts {
+    /// The size of the garbage-collected heap, in bytes.
+    pub gc_heap_size: usize,
+
+    /// The number of `setInterval`/`setTimeout` and AVM2 timers.
+    pub timers: usize,
+
+    /// The number of sounds that are currently playing.
+    pub sounds: usize,
+
+    /// The number of display objects on the stage, including levels.
+    pub display_objects: usize,
+}
+
+impl DebugCounts {
+    /// Counts the given display object and all of its descendants.
+    pub fn count_display_objects(&mut self, object: DisplayObject<'_>) {
+        self.display_objects += 1;
+        for child in object.children() {
+            self.count_display_objects(child);
+        }
+    }
+}
+
+/// Frame timings and object counts displayed by the diagnostics overlay.
+pub struct DebugOverlay {
+    /// The time between the starts of consecutive frames, in milliseconds.
+    frame_intervals: Samples,
+
+    /// The time spent running frames, timers and other scripts per tick, in milliseconds.
+    script_times: Samples,
+
+    /// The time spent rendering each frame, in milliseconds.
+    render_times: Samples,
+
+    /// The time spent updating audio per tick, in milliseconds.
+    audio_times: Samples,
+
+    /// When the last frame was started, measured from the navigator's clock.
+    last_frame_start: Option<Duration>,
+
+    /// The number of draw calls in the last rendered frame, if the render
+    /// backend reports it.
+    draw_calls: Option<usize>,
+
+    counts: DebugCounts,
+
+    /// The number of rendered frames until the text is next refreshed.
+    frames_til_refresh: u32,
+
+    /// The lines of text currently displayed.
+    lines: Vec<String>,
+
+    /// The translucent backdrop behind the text.
+    background: Drawing,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        let mut background = Drawing::new();
+        background.set_fill_style(Some(FillStyle::Color(Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 192,
+        })));
+        let width = Twips::from_pixels(PANEL_WIDTH);
+        let height = Twips::from_pixels(TEXT_SIZE * LINE_COUNT as f64 + PADDING * 2.0);
+        background.draw_command(DrawCommand::MoveTo {
+            x: Twips::zero(),
+            y: Twips::zero(),
+        });
+        background.draw_command(DrawCommand::LineTo {
+            x: width,
+            y: Twips::zero(),
+        });
+        background.draw_command(DrawCommand::LineTo {
+            x: width,
+            y: height,
+        });
+        background.draw_command(DrawCommand::LineTo {
+            x: Twips::zero(),
+            y: height,
+        });
+        background.draw_command(DrawCommand::LineTo {
+            x: Twips::zero(),
+            y: Twips::zero(),
+        });
+
+        Self {
+            frame_intervals: Samples::default(),
+            script_times: Samples::default(),
+            render_times: Samples::default(),
+            audio_times: Samples::default(),
+            last_frame_start: None,
+            draw_calls: None,
+            counts: DebugCounts::default(),
+            frames_til_refresh: 0,
+            lines: Vec::new(),
+            background,
+        }
+    }
+
+    /// Records that a frame has started running at the given time.
+    pub fn record_frame_start(&mut self, now: Duration) {
+        if let Some(last) = self.last_frame_start.replace(now) {
+            self.frame_intervals.push(duration_ms(now - last));
+        }
+    }
+
+    pub fn record_script_time(&mut self, time: Duration) {
+        self.script_times.push(duration_ms(time));
+    }
+
+    pub fn record_audio_time(&mut self, time: Duration) {
+        self.audio_times.push(duration_ms(time));
+    }
+
+    /// Records the time taken and the draw calls submitted by the frame that
+    /// was just rendered.
+    pub fn record_render(&mut self, time: Duration, draw_calls: Option<usize>) {
+        self.render_times.push(duration_ms(time));
+        self.draw_calls = draw_calls;
+    }
+
+    /// Returns whether the text will be refreshed on the next render, in
+    /// which case the caller should provide new counts with `set_counts`.
+    pub fn needs_counts(&self) -> bool {
+        self.frames_til_refresh == 0
+    }
+
+    pub fn set_counts(&mut self, counts: DebugCounts) {
+        self.counts = counts;
+    }
+
+    /// Draws the panel in the top-left corner of the viewport.
+    ///
+    /// `inverse_view_matrix` must undo the view matrix on top of the transform
+    /// stack, so that the panel is unaffected by the stage's scale.
+    pub fn render(
+        &mut self,
+        context: &mut RenderContext<'_, '_>,
+        font: Option<Font<'_>>,
+        frame_rate: f64,
+        inverse_view_matrix: Matrix,
+    ) {
+        if self.frames_til_refresh == 0 {
+            self.refresh_lines(frame_rate);
+            self.frames_til_refresh = REFRESH_INTERVAL;
+        }
+        self.frames_til_refresh -= 1;
+
+        context.transform_stack.push(&Transform {
+            matrix: inverse_view_matrix,
+            ..Default::default()
+        });
+        self.background.render(context);
+
+        if let Some(font) = font {
+            let params =
+                EvalParameters::from_parts(Twips::from_pixels(TEXT_SIZE), Twips::zero(), false);
+            let mut transform = Transform::default();
+            transform.matrix.tx = Twips::from_pixels(PADDING);
+            transform.matrix.ty = Twips::from_pixels(PADDING);
+            for line in &self.lines {
+                font.evaluate(
+                    line,
+                    transform.clone(),
+                    params,
+                    |transform, glyph, _advance| {
+                        context.transform_stack.push(transform);
+                        context
+                            .renderer
+                            .render_shape(glyph.shape, context.transform_stack.transform());
+                        context.transform_stack.pop();
+                    },
+                );
+                transform.matrix.ty += Twips::from_pixels(TEXT_SIZE);
+            }
+        }
+
+        context.transform_stack.pop();
+    }
+
+    fn refresh_lines(&mut self, frame_rate: f64) {
+        let frame_interval = self.frame_intervals.average();
+        let fps = if frame_interval > 0.0 {
+            1000.0 / frame_interval
+        } else {
+            0.0
+        };
+
+        self.lines.clear();
+        self.lines
+            .push(format!("FPS: {:.1} / {:.1}", fps, frame_rate));
+        self.lines.push(format!(
+            "Script: {:.2} ms  Render: {:.2} ms  Audio: {:.2} ms",
+            self.script_times.average(),
+            self.render_times.average(),
+            self.audio_times.average()
+        ));
+
+        self.lines.push(match self.draw_calls {
+            Some(count) => format!("Draw calls: {}", count),
+            None => "Draw calls: n/a".to_string(),
+        });
+
+        self.lines
+            .push(format!("GC heap: {} KiB", self.counts.gc_heap_size / 1024));
+        self.lines.push(format!(
+            "Timers: {}  Sounds: {}  Objects: {}",
+            self.counts.timers, self.counts.sounds, self.counts.display_objects
+        ));
+    }
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_average_over_window() {
+        let mut samples = Samples::default();
+        assert_eq!(samples.average(), 0.0);
+
+        samples.push(2.0);
+        samples.push(4.0);
+        assert_eq!(samples.average(), 3.0);
+
+        for _ in 0..SAMPLE_COUNT {
+            samples.push(10.0);
+        }
+        assert_eq!(samples.average(), 10.0);
+    }
+}
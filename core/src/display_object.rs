@@ -1,7 +1,11 @@
 use crate::avm1::{Object, TObject, Value};
+use crate::avm2::Object as Avm2Object;
+use crate::backend::audio::SoundTransform;
+use crate::bitmap_cache::BitmapCache;
 use crate::context::{RenderContext, UpdateContext};
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
+use crate::scaling_grid::{GridSlices, ScalingGrid};
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use enumset::{EnumSet, EnumSetType};
@@ -11,6 +15,7 @@ use std::cell::{Ref, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use swf::{BlendMode, Rectangle};
 
 mod bitmap;
 mod button;
@@ -19,16 +24,18 @@ mod graphic;
 mod morph_shape;
 mod movie_clip;
 mod text;
+mod video;
 
 use crate::avm1::activation::Activation;
 use crate::events::{ClipEvent, ClipEventResult};
 pub use bitmap::Bitmap;
 pub use button::Button;
-pub use edit_text::{AutoSizeMode, EditText};
+pub use edit_text::{AutoSizeMode, EditText, TextLineMetrics, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
-pub use movie_clip::MovieClip;
+pub use movie_clip::{MovieClip, Scene};
 pub use text::Text;
+pub use video::Video;
 
 #[derive(Clone, Debug)]
 pub struct DisplayObjectBase<'gc> {
@@ -38,6 +45,8 @@ pub struct DisplayObjectBase<'gc> {
     transform: Transform,
     name: String,
     clip_depth: Depth,
+    blend_mode: BlendMode,
+    sound_transform: SoundTransform,
 
     // Cached transform properties `_xscale`, `_yscale`, `_rotation`.
     // These are expensive to calculate, so they will be calculated and cached when AS requests
@@ -59,6 +68,26 @@ pub struct DisplayObjectBase<'gc> {
 
     /// Bit flags for various display object properites.
     flags: EnumSet<DisplayObjectFlags>,
+
+    /// The cached bitmap of this object, if `cacheAsBitmap` is set and it has been drawn.
+    bitmap_cache: Option<BitmapCache>,
+
+    /// Whether this object can be focused with the Tab key (`tabEnabled` property).
+    /// `None` if it was never set, in which case buttons and button-mode clips are tabbable.
+    tab_enabled: Option<bool>,
+
+    /// The position of this object in the tab order (`tabIndex` property).
+    tab_index: Option<i32>,
+
+    /// Whether a yellow rectangle is drawn around this object when it has keyboard focus
+    /// (`_focusrect` property). `None` defers to the setting of the root movie.
+    focus_rect: Option<bool>,
+
+    /// The 9-slice grid this object's artwork is scaled with (`scale9Grid` property).
+    scaling_grid: Option<ScalingGrid>,
+
+    /// The AVM2 object that represents this display object to scripts, if one has been created.
+    avm2_object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -70,6 +99,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             transform: Default::default(),
             name: Default::default(),
             clip_depth: Default::default(),
+            blend_mode: BlendMode::Normal,
+            sound_transform: SoundTransform::default(),
             rotation: 0.0,
             scale_x: 1.0,
             scale_y: 1.0,
@@ -77,7 +108,16 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             first_child: None,
             prev_sibling: None,
             next_sibling: None,
-            flags: DisplayObjectFlags::Visible.into(),
+            flags: DisplayObjectFlags::Visible
+                | DisplayObjectFlags::TabChildren
+                | DisplayObjectFlags::MouseEnabled
+                | DisplayObjectFlags::MouseChildren,
+            bitmap_cache: None,
+            tab_enabled: None,
+            tab_index: None,
+            focus_rect: None,
+            scaling_grid: None,
+            avm2_object: None,
         }
     }
 }
@@ -89,6 +129,7 @@ unsafe impl<'gc> Collect for DisplayObjectBase<'gc> {
         self.first_child.trace(cc);
         self.prev_sibling.trace(cc);
         self.next_sibling.trace(cc);
+        self.avm2_object.trace(cc);
     }
 }
 
@@ -97,7 +138,10 @@ impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
         self.first_child = None;
-        self.flags = DisplayObjectFlags::Visible.into();
+        self.flags = DisplayObjectFlags::Visible
+            | DisplayObjectFlags::TabChildren
+            | DisplayObjectFlags::MouseEnabled
+            | DisplayObjectFlags::MouseChildren;
     }
 
     fn id(&self) -> CharacterId {
@@ -108,6 +152,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_depth(&mut self, depth: Depth) {
         self.depth = depth;
+        self.set_transform_changed();
     }
     fn place_frame(&self) -> u16 {
         self.place_frame
@@ -123,16 +168,19 @@ impl<'gc> DisplayObjectBase<'gc> {
         &self.transform.matrix
     }
     fn matrix_mut(&mut self, _context: MutationContext<'gc, '_>) -> &mut Matrix {
+        self.set_transform_changed();
         &mut self.transform.matrix
     }
     fn set_matrix(&mut self, _context: MutationContext<'gc, '_>, matrix: &Matrix) {
         self.transform.matrix = *matrix;
         self.flags.remove(DisplayObjectFlags::ScaleRotationCached);
+        self.set_transform_changed();
     }
     fn color_transform(&self) -> &ColorTransform {
         &self.transform.color_transform
     }
     fn color_transform_mut(&mut self) -> &mut ColorTransform {
+        self.set_transform_changed();
         &mut self.transform.color_transform
     }
     fn set_color_transform(
@@ -141,12 +189,14 @@ impl<'gc> DisplayObjectBase<'gc> {
         color_transform: &ColorTransform,
     ) {
         self.transform.color_transform = *color_transform;
+        self.set_transform_changed();
     }
     fn x(&self) -> f64 {
         self.transform.matrix.tx.to_pixels()
     }
     fn set_x(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_transform_changed();
         self.transform.matrix.tx = Twips::from_pixels(value)
     }
     fn y(&self) -> f64 {
@@ -154,6 +204,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_y(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_transform_changed();
         self.transform.matrix.ty = Twips::from_pixels(value)
     }
 
@@ -196,6 +247,7 @@ impl<'gc> DisplayObjectBase<'gc> {
 
     fn set_scale(&mut self, scale_x: f32, scale_y: f32, rotation: f32) {
         self.cache_scale_rotation();
+        self.set_transform_changed();
         let mut matrix = &mut self.transform.matrix;
         let rotation = rotation.to_radians();
         let cos_x = f32::cos(rotation);
@@ -215,6 +267,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_rotation(&mut self, radians: f64) {
         self.set_transformed_by_script(true);
+        self.set_transform_changed();
         self.cache_scale_rotation();
         self.rotation = radians;
         let cos_x = f64::cos(radians);
@@ -233,6 +286,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_scale_x(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_transform_changed();
         self.cache_scale_rotation();
         self.scale_x = value;
         let cos = f64::cos(self.rotation);
@@ -247,6 +301,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_scale_y(&mut self, value: f64) {
         self.set_transformed_by_script(true);
+        self.set_transform_changed();
         self.cache_scale_rotation();
         self.scale_y = value;
         let cos = f64::cos(self.rotation + self.skew);
@@ -274,6 +329,20 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
     fn set_clip_depth(&mut self, _context: MutationContext<'gc, '_>, depth: Depth) {
         self.clip_depth = depth;
+        self.set_transform_changed();
+    }
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+    fn set_blend_mode(&mut self, value: BlendMode) {
+        self.blend_mode = value;
+        self.set_transform_changed();
+    }
+    fn sound_transform(&self) -> SoundTransform {
+        self.sound_transform
+    }
+    fn set_sound_transform(&mut self, value: SoundTransform) {
+        self.sound_transform = value;
     }
     fn parent(&self) -> Option<DisplayObject<'gc>> {
         self.parent
@@ -294,6 +363,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         node: Option<DisplayObject<'gc>>,
     ) {
         self.first_child = node;
+        self.set_content_changed();
     }
     fn prev_sibling(&self) -> Option<DisplayObject<'gc>> {
         self.prev_sibling
@@ -304,6 +374,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         node: Option<DisplayObject<'gc>>,
     ) {
         self.prev_sibling = node;
+        self.set_transform_changed();
     }
     fn next_sibling(&self) -> Option<DisplayObject<'gc>> {
         self.next_sibling
@@ -314,6 +385,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         node: Option<DisplayObject<'gc>>,
     ) {
         self.next_sibling = node;
+        self.set_transform_changed();
     }
     fn removed(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::Removed)
@@ -336,6 +408,127 @@ impl<'gc> DisplayObjectBase<'gc> {
         } else {
             self.flags.remove(DisplayObjectFlags::Visible);
         }
+        self.set_transform_changed();
+    }
+
+    fn is_bitmap_cached(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CacheAsBitmap)
+    }
+
+    fn set_is_bitmap_cached(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::CacheAsBitmap);
+        } else {
+            self.flags.remove(DisplayObjectFlags::CacheAsBitmap);
+            self.bitmap_cache = None;
+        }
+    }
+
+    fn bitmap_cache(&self) -> Option<BitmapCache> {
+        self.bitmap_cache
+    }
+
+    fn tab_enabled(&self) -> Option<bool> {
+        self.tab_enabled
+    }
+
+    fn set_tab_enabled(&mut self, value: Option<bool>) {
+        self.tab_enabled = value;
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_tab_index(&mut self, value: Option<i32>) {
+        self.tab_index = value;
+    }
+
+    fn scaling_grid(&self) -> Option<&ScalingGrid> {
+        self.scaling_grid.as_ref()
+    }
+
+    fn set_scaling_grid(&mut self, rect: Option<Rectangle>) {
+        self.scaling_grid = rect.map(ScalingGrid::new);
+    }
+
+    fn tab_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::TabChildren)
+    }
+
+    fn set_tab_children(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::TabChildren);
+        } else {
+            self.flags.remove(DisplayObjectFlags::TabChildren);
+        }
+    }
+
+    fn mouse_enabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MouseEnabled)
+    }
+
+    fn set_mouse_enabled(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::MouseEnabled);
+        } else {
+            self.flags.remove(DisplayObjectFlags::MouseEnabled);
+        }
+    }
+
+    fn mouse_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MouseChildren)
+    }
+
+    fn set_mouse_children(&mut self, value: bool) {
+        if value {
+            self.flags.insert(DisplayObjectFlags::MouseChildren);
+        } else {
+            self.flags.remove(DisplayObjectFlags::MouseChildren);
+        }
+    }
+
+    fn object2(&self) -> Option<Avm2Object<'gc>> {
+        self.avm2_object
+    }
+
+    fn set_object2(&mut self, object: Avm2Object<'gc>) {
+        self.avm2_object = Some(object);
+    }
+
+    fn focus_rect(&self) -> Option<bool> {
+        self.focus_rect
+    }
+
+    fn set_focus_rect(&mut self, value: Option<bool>) {
+        self.focus_rect = value;
+    }
+
+    fn set_bitmap_cache(&mut self, cache: Option<BitmapCache>) {
+        self.bitmap_cache = cache;
+    }
+
+    /// Marks that the transform or visibility of this object changed.
+    /// Bitmap caches of ancestors containing this object must be redrawn.
+    fn set_transform_changed(&mut self) {
+        self.flags.insert(DisplayObjectFlags::TransformChanged);
+    }
+
+    /// Marks that the content drawn by this object changed.
+    /// Bitmap caches containing this object, including its own, must be redrawn.
+    fn set_content_changed(&mut self) {
+        self.flags.insert(DisplayObjectFlags::ContentChanged);
+    }
+
+    /// Clears the change flags, returning whether the content and the transform changed.
+    fn take_changes(&mut self) -> (bool, bool) {
+        let changes = (
+            self.flags.contains(DisplayObjectFlags::ContentChanged),
+            self.flags.contains(DisplayObjectFlags::TransformChanged),
+        );
+        self.flags.remove(DisplayObjectFlags::ContentChanged);
+        self.flags.remove(DisplayObjectFlags::TransformChanged);
+        changes
     }
 
     fn transformed_by_script(&self) -> bool {
@@ -372,6 +565,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         MorphShape(MorphShape<'gc>),
         MovieClip(MovieClip<'gc>),
         Text(Text<'gc>),
+        Video(Video<'gc>),
     }
 )]
 pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>> {
@@ -530,13 +724,25 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         let object_bounds = self.bounds();
         let object_width = (object_bounds.x_max - object_bounds.x_min).to_pixels();
         let object_height = (object_bounds.y_max - object_bounds.y_min).to_pixels();
-        let aspect_ratio = object_height / object_width;
+        if object_width == 0.0 {
+            // There is no width to scale.
+            return;
+        }
 
-        let (target_scale_x, target_scale_y) = if object_width != 0.0 {
-            (value / object_width, value / object_height)
-        } else {
-            (0.0, 0.0)
-        };
+        let rotation = self.rotation(gc_context);
+        let cos = f64::abs(f64::cos(rotation));
+        let sin = f64::abs(f64::sin(rotation));
+
+        if object_height == 0.0 {
+            // A flat object's width only depends on its X scale.
+            if cos != 0.0 {
+                self.set_scale_x(gc_context, value / (object_width * cos));
+            }
+            return;
+        }
+
+        let aspect_ratio = object_height / object_width;
+        let (target_scale_x, target_scale_y) = (value / object_width, value / object_height);
 
         // No idea about the derivation of this -- figured it out via lots of trial and error.
         // It has to do with the length of the sides A, B of an AABB enclosing the object's OBB with sides a, b:
@@ -544,9 +750,6 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         // B = cos(t) * a + sin(t) * b
         let prev_scale_x = self.scale_x(gc_context);
         let prev_scale_y = self.scale_y(gc_context);
-        let rotation = self.rotation(gc_context);
-        let cos = f64::abs(f64::cos(rotation));
-        let sin = f64::abs(f64::sin(rotation));
         let new_scale_x = aspect_ratio * (cos * target_scale_x + sin * target_scale_y)
             / ((cos + aspect_ratio * sin) * (aspect_ratio * cos + sin));
         let new_scale_y =
@@ -567,13 +770,25 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         let object_bounds = self.bounds();
         let object_width = (object_bounds.x_max - object_bounds.x_min).to_pixels();
         let object_height = (object_bounds.y_max - object_bounds.y_min).to_pixels();
-        let aspect_ratio = object_width / object_height;
+        if object_height == 0.0 {
+            // There is no height to scale.
+            return;
+        }
 
-        let (target_scale_x, target_scale_y) = if object_height != 0.0 {
-            (value / object_width, value / object_height)
-        } else {
-            (0.0, 0.0)
-        };
+        let rotation = self.rotation(gc_context);
+        let cos = f64::abs(f64::cos(rotation));
+        let sin = f64::abs(f64::sin(rotation));
+
+        if object_width == 0.0 {
+            // A flat object's height only depends on its Y scale.
+            if cos != 0.0 {
+                self.set_scale_y(gc_context, value / (object_height * cos));
+            }
+            return;
+        }
+
+        let aspect_ratio = object_width / object_height;
+        let (target_scale_x, target_scale_y) = (value / object_width, value / object_height);
 
         // No idea about the derivation of this -- figured it out via lots of trial and error.
         // It has to do with the length of the sides A, B of an AABB enclosing the object's OBB with sides a, b:
@@ -581,9 +796,6 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         // B = cos(t) * a + sin(t) * b
         let prev_scale_x = self.scale_x(gc_context);
         let prev_scale_y = self.scale_y(gc_context);
-        let rotation = self.rotation(gc_context);
-        let cos = f64::abs(f64::cos(rotation));
-        let sin = f64::abs(f64::sin(rotation));
         let new_scale_x =
             (aspect_ratio * cos * prev_scale_x + sin * prev_scale_y) / (aspect_ratio * cos + sin);
         let new_scale_y = aspect_ratio * (sin * target_scale_x + cos * target_scale_y)
@@ -631,6 +843,23 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
 
     fn clip_depth(&self) -> Depth;
     fn set_clip_depth(&self, context: MutationContext<'gc, '_>, depth: Depth);
+
+    /// The blend mode used when compositing this object onto the content below it.
+    /// Returned by the `blendMode` ActionScript property.
+    fn blend_mode(&self) -> BlendMode;
+
+    /// Sets the blend mode used when compositing this object onto the content below it.
+    /// Set by the `blendMode` ActionScript property, or by a `PlaceObject3` tag.
+    fn set_blend_mode(&self, context: MutationContext<'gc, '_>, value: BlendMode);
+
+    /// The volume and panning of sounds played by this object and its children.
+    /// This is multiplied with the sound transforms of this object's ancestors.
+    fn sound_transform(&self) -> SoundTransform;
+
+    /// Sets the volume and panning of sounds played by this object and its children.
+    /// Set by the `soundTransform` AS3 property, or by an AVM1 `Sound` object targeting this clip.
+    fn set_sound_transform(&self, context: &mut UpdateContext<'_, 'gc, '_>, value: SoundTransform);
+
     fn parent(&self) -> Option<DisplayObject<'gc>>;
     fn set_parent(&self, context: MutationContext<'gc, '_>, parent: Option<DisplayObject<'gc>>);
     fn first_child(&self) -> Option<DisplayObject<'gc>>;
@@ -711,8 +940,75 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         ClipEventResult::NotHandled
     }
 
+    /// Whether this object is rendered through a bitmap cache.
+    /// Returned by the `cacheAsBitmap` ActionScript property.
+    fn is_bitmap_cached(&self) -> bool;
+
+    /// Sets whether this object is rendered through a bitmap cache.
+    /// Set by the `cacheAsBitmap` ActionScript property, or by a `PlaceObject3` tag.
+    fn set_is_bitmap_cached(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// The bitmap cache of this object, if it has been drawn.
+    fn bitmap_cache(&self) -> Option<BitmapCache>;
+    fn set_bitmap_cache(&self, context: MutationContext<'gc, '_>, cache: Option<BitmapCache>);
+
+    /// Whether this object was explicitly included in or excluded from the tab order.
+    /// Returned by the `tabEnabled` ActionScript property.
+    fn tab_enabled(&self) -> Option<bool>;
+    fn set_tab_enabled(&self, context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// The explicit position of this object in the tab order, if any.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<i32>;
+    fn set_tab_index(&self, context: MutationContext<'gc, '_>, value: Option<i32>);
+
+    /// The centre rectangle of the 9-slice grid this object is scaled with, if any.
+    /// Set by the `scale9Grid` ActionScript property, or by a `DefineScalingGrid` tag.
+    fn scaling_grid(&self) -> Option<Rectangle>;
+    fn set_scaling_grid(&self, context: MutationContext<'gc, '_>, rect: Option<Rectangle>);
+
+    /// Whether the children of this object are part of the tab order.
+    /// Returned by the `tabChildren` ActionScript property.
+    fn tab_children(&self) -> bool;
+    fn set_tab_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether this object can be the target of AVM2 mouse events.
+    /// Returned by the `mouseEnabled` ActionScript property.
+    fn mouse_enabled(&self) -> bool;
+    fn set_mouse_enabled(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether the children of this object can be the target of AVM2 mouse events.
+    /// Returned by the `mouseChildren` ActionScript property.
+    fn mouse_children(&self) -> bool;
+    fn set_mouse_children(&self, context: MutationContext<'gc, '_>, value: bool);
+
+    /// The AVM2 object that represents this display object, if one has been created.
+    ///
+    /// Use `avm2::display_object_to_avm2` to create one on demand.
+    fn object2(&self) -> Option<Avm2Object<'gc>>;
+    fn set_object2(&self, context: MutationContext<'gc, '_>, object: Avm2Object<'gc>);
+
+    /// Whether the focus rectangle is drawn around this object, if it was set.
+    /// Returned by the `_focusrect` ActionScript property.
+    fn focus_rect(&self) -> Option<bool>;
+    fn set_focus_rect(&self, context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// Clears the change flags of this object, returning whether its content and its transform
+    /// changed since the last call. Used to validate bitmap caches.
+    fn take_changes(&self, context: MutationContext<'gc, '_>) -> (bool, bool);
+
     fn run_frame(&self, _context: &mut UpdateContext<'_, 'gc, '_>) {}
-    fn render(&self, _context: &mut RenderContext<'_, 'gc>) {}
+
+    /// Renders this object, applying its transform.
+    fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        context.transform_stack.push(&*self.transform());
+        self.render_self(context);
+        context.transform_stack.pop();
+    }
+
+    /// Renders the content and children of this object in its own coordinate space.
+    /// The transform of this object has already been pushed by `render`.
+    fn render_self(&self, _context: &mut RenderContext<'_, 'gc>) {}
 
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // Unload children.
@@ -742,6 +1038,12 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
     fn as_morph_shape(&self) -> Option<MorphShape<'gc>> {
         None
     }
+    fn as_video(&self) -> Option<Video<'gc>> {
+        None
+    }
+    fn as_graphic(&self) -> Option<Graphic<'gc>> {
+        None
+    }
     fn apply_place_object(
         &self,
         gc_context: MutationContext<'gc, '_>,
@@ -761,9 +1063,23 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
             if let Some(clip_depth) = place_object.clip_depth {
                 self.set_clip_depth(gc_context, clip_depth.into());
             }
+            if let Some(blend_mode) = place_object.blend_mode {
+                self.set_blend_mode(gc_context, blend_mode);
+            }
+            if let Some(is_bitmap_cached) = place_object.is_bitmap_cached {
+                self.set_is_bitmap_cached(gc_context, is_bitmap_cached);
+            }
+            // Objects with filters are always drawn through a bitmap cache.
+            if let Some(filters) = &place_object.filters {
+                if !filters.is_empty() {
+                    self.set_is_bitmap_cached(gc_context, true);
+                }
+            }
             if let Some(ratio) = place_object.ratio {
                 if let Some(mut morph_shape) = self.as_morph_shape() {
                     morph_shape.set_ratio(gc_context, ratio);
+                } else if let Some(video) = self.as_video() {
+                    video.seek(gc_context, ratio);
                 }
             }
             // Clip events only apply to movie clips.
@@ -795,9 +1111,14 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
         self.set_name(gc_context, &*other.name());
+        self.set_blend_mode(gc_context, other.blend_mode());
+        self.set_is_bitmap_cached(gc_context, other.is_bitmap_cached());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
         }
+        if let (Some(me), Some(other)) = (self.as_video(), other.as_video()) {
+            me.seek(gc_context, other.frame());
+        }
         // onEnterFrame actions only apply to movie clips.
         if let (Some(me), Some(other)) = (self.as_movie_clip(), other.as_movie_clip()) {
             me.set_clip_actions(gc_context, other.clip_actions().iter().cloned().collect());
@@ -828,6 +1149,23 @@ pub trait TDisplayObject<'gc>: 'gc + Collect + Debug + Into<DisplayObject<'gc>>
         None
     }
 
+    /// Find the target of an AVM2 mouse event at the given stage position,
+    /// among this object and its children.
+    ///
+    /// Objects which cannot receive mouse events, such as shapes, leave the
+    /// event to their closest parent that can.
+    fn mouse_pick_avm2(
+        &self,
+        _context: &mut UpdateContext<'_, 'gc, '_>,
+        pos: (Twips, Twips),
+    ) -> Avm2MousePick<'gc> {
+        if self.visible() && self.hit_test_shape(pos) {
+            Avm2MousePick::PropagateToParent
+        } else {
+            Avm2MousePick::Miss
+        }
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1008,6 +1346,30 @@ macro_rules! impl_display_object_sansbounds {
         ) {
             self.0.write(context).$field.set_clip_depth(context, depth)
         }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: swf::BlendMode,
+        ) {
+            self.0.write(context).$field.set_blend_mode(value)
+        }
+        fn sound_transform(&self) -> crate::backend::audio::SoundTransform {
+            self.0.read().$field.sound_transform()
+        }
+        fn set_sound_transform(
+            &self,
+            context: &mut crate::context::UpdateContext<'_, 'gc, '_>,
+            value: crate::backend::audio::SoundTransform,
+        ) {
+            self.0
+                .write(context.gc_context)
+                .$field
+                .set_sound_transform(value);
+            context.audio_manager.invalidate_sound_transforms();
+        }
         fn parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
             self.0.read().$field.parent()
         }
@@ -1060,6 +1422,89 @@ macro_rules! impl_display_object_sansbounds {
         fn set_visible(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_visible(value);
         }
+        fn is_bitmap_cached(&self) -> bool {
+            self.0.read().$field.is_bitmap_cached()
+        }
+        fn set_is_bitmap_cached(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_is_bitmap_cached(value);
+        }
+        fn bitmap_cache(&self) -> Option<crate::bitmap_cache::BitmapCache> {
+            self.0.read().$field.bitmap_cache()
+        }
+        fn set_bitmap_cache(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            cache: Option<crate::bitmap_cache::BitmapCache>,
+        ) {
+            self.0.write(context).$field.set_bitmap_cache(cache);
+        }
+        fn tab_enabled(&self) -> Option<bool> {
+            self.0.read().$field.tab_enabled()
+        }
+        fn set_tab_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: Option<bool>,
+        ) {
+            self.0.write(context).$field.set_tab_enabled(value);
+        }
+        fn tab_index(&self) -> Option<i32> {
+            self.0.read().$field.tab_index()
+        }
+        fn scaling_grid(&self) -> Option<swf::Rectangle> {
+            self.0
+                .read()
+                .$field
+                .scaling_grid()
+                .map(|grid| grid.rect().clone())
+        }
+        fn set_scaling_grid(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            rect: Option<swf::Rectangle>,
+        ) {
+            self.0.write(context).$field.set_scaling_grid(rect);
+        }
+        fn set_tab_index(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<i32>) {
+            self.0.write(context).$field.set_tab_index(value);
+        }
+        fn tab_children(&self) -> bool {
+            self.0.read().$field.tab_children()
+        }
+        fn set_tab_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_tab_children(value);
+        }
+        fn mouse_enabled(&self) -> bool {
+            self.0.read().$field.mouse_enabled()
+        }
+        fn set_mouse_enabled(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_enabled(value);
+        }
+        fn mouse_children(&self) -> bool {
+            self.0.read().$field.mouse_children()
+        }
+        fn set_mouse_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_children(value);
+        }
+        fn object2(&self) -> Option<crate::avm2::Object<'gc>> {
+            self.0.read().$field.object2()
+        }
+        fn set_object2(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            object: crate::avm2::Object<'gc>,
+        ) {
+            self.0.write(context).$field.set_object2(object);
+        }
+        fn focus_rect(&self) -> Option<bool> {
+            self.0.read().$field.focus_rect()
+        }
+        fn set_focus_rect(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<bool>) {
+            self.0.write(context).$field.set_focus_rect(value);
+        }
+        fn take_changes(&self, context: gc_arena::MutationContext<'gc, '_>) -> (bool, bool) {
+            self.0.write(context).$field.take_changes()
+        }
         fn transformed_by_script(&self) -> bool {
             self.0.read().$field.transformed_by_script()
         }
@@ -1114,11 +1559,15 @@ macro_rules! impl_display_object {
 }
 
 /// Renders the children of a display object, taking masking into account.
+///
+/// If the object is scaled through a scaling grid, its vector art is rendered through
+/// `scaling_grid`. Nested clips, bitmaps and text are scaled normally.
 // TODO(Herschel): Move this into an IDisplayObject/IDisplayObjectContainer trait when
 // we figure out inheritance
 pub fn render_children<'gc>(
     context: &mut RenderContext<'_, 'gc>,
     children: &std::collections::BTreeMap<Depth, DisplayObject<'gc>>,
+    scaling_grid: Option<&GridSlices<'_>>,
 ) {
     let mut clip_depth = 0;
     let mut clip_depth_stack = vec![];
@@ -1135,11 +1584,18 @@ pub fn render_children<'gc>(
             clip_depth_stack.push(clip_depth);
             clip_depth = child.clip_depth();
             context.renderer.push_mask();
-            child.render(context);
+            render_display_object(context, child);
             context.renderer.activate_mask();
         } else if child.visible() {
             // Normal child.
-            child.render(context);
+            match scaling_grid {
+                Some(slices)
+                    if child.as_graphic().is_some() || child.as_morph_shape().is_some() =>
+                {
+                    slices.render(context, |context| render_display_object(context, child))
+                }
+                _ => render_display_object(context, child),
+            }
         }
     }
 
@@ -1149,6 +1605,27 @@ pub fn render_children<'gc>(
     }
 }
 
+/// Renders a display object, going through its bitmap cache if `cacheAsBitmap` is set.
+pub fn render_display_object<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    display_object: DisplayObject<'gc>,
+) {
+    let blend_mode = display_object.blend_mode();
+    if blend_mode != BlendMode::Normal {
+        // The object is blended as a whole, so it is drawn into a bitmap first, like
+        // `cacheAsBitmap`. If that fails, each part of it is blended separately.
+        context.renderer.push_blend_mode(blend_mode);
+        if !crate::bitmap_cache::render_cached(context, display_object) {
+            display_object.render(context);
+        }
+        context.renderer.pop_blend_mode();
+    } else if !display_object.is_bitmap_cached()
+        || !crate::bitmap_cache::render_cached(context, display_object)
+    {
+        display_object.render(context);
+    }
+}
+
 pub fn get_child_by_name<'gc>(
     children: &std::collections::BTreeMap<Depth, DisplayObject<'gc>>,
     name: &str,
@@ -1170,6 +1647,39 @@ pub fn get_child_by_name<'gc>(
     }
 }
 
+/// The result of picking the target of an AVM2 mouse event in part of the
+/// display list.
+#[derive(Clone, Copy, Debug)]
+pub enum Avm2MousePick<'gc> {
+    /// The event goes to this object.
+    Hit(DisplayObject<'gc>),
+
+    /// The mouse is over an object which cannot receive the event itself,
+    /// so it goes to the closest parent that can.
+    PropagateToParent,
+
+    /// The mouse is not over anything.
+    Miss,
+}
+
+impl<'gc> Avm2MousePick<'gc> {
+    /// Adjust the result of picking among the children of `parent`, taking
+    /// the parent's `mouseEnabled` and `mouseChildren` into account.
+    pub fn combine_with_parent(self, parent: DisplayObject<'gc>) -> Self {
+        match self {
+            Avm2MousePick::Hit(_) if parent.mouse_children() => self,
+            Avm2MousePick::Hit(_) | Avm2MousePick::PropagateToParent => {
+                if parent.mouse_enabled() {
+                    Avm2MousePick::Hit(parent)
+                } else {
+                    Avm2MousePick::PropagateToParent
+                }
+            }
+            Avm2MousePick::Miss => Avm2MousePick::Miss,
+        }
+    }
+}
+
 impl<'gc> DisplayObject<'gc> {
     pub fn ptr_eq(a: DisplayObject<'gc>, b: DisplayObject<'gc>) -> bool {
         a.as_ptr() == b.as_ptr()
@@ -1193,6 +1703,27 @@ enum DisplayObjectFlags {
     /// Whether this object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     TransformedByScript,
+
+    /// Whether this object is rendered through a bitmap cache (`cacheAsBitmap` property).
+    CacheAsBitmap,
+
+    /// Whether the children of this object are part of the tab order (`tabChildren` property).
+    TabChildren,
+
+    /// Whether this object can be the target of AVM2 mouse events (`mouseEnabled` property).
+    MouseEnabled,
+
+    /// Whether the children of this object can be the target of AVM2 mouse events
+    /// (`mouseChildren` property).
+    MouseChildren,
+
+    /// Whether the transform or visibility of this object changed since a bitmap cache containing
+    /// it was last validated.
+    TransformChanged,
+
+    /// Whether the content of this object changed since a bitmap cache containing it was last
+    /// validated.
+    ContentChanged,
 }
 
 pub struct ChildIter<'gc> {
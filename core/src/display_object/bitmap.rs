@@ -73,30 +73,32 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         BoundingBox {
             x_min: Twips::new(0),
             y_min: Twips::new(0),
-            x_max: Twips::new(Bitmap::width(*self)),
-            y_max: Twips::new(Bitmap::height(*self)),
+            x_max: Twips::from_pixels(Bitmap::width(*self).into()),
+            y_max: Twips::from_pixels(Bitmap::height(*self).into()),
             valid: true,
         }
     }
 
+    fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
+        // Bitmaps are hit as a solid rectangle, regardless of transparency.
+        self.world_bounds().contains(point)
+            && self.self_bounds().contains(self.global_to_local(point))
+    }
+
     fn run_frame(&self, _context: &mut UpdateContext) {
         // Noop
     }
 
-    fn render(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext) {
         if !self.world_bounds().intersects(&context.view_bounds) {
             // Off-screen; culled
             return;
         }
 
-        context.transform_stack.push(&*self.transform());
-
         context.renderer.render_bitmap(
             self.0.read().static_data.bitmap_handle,
             context.transform_stack.transform(),
         );
-
-        context.transform_stack.pop();
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::avm1::{Object, StageObject, Value};
 use crate::context::{ActionType, RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{Avm2MousePick, DisplayObjectBase, TDisplayObject};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
@@ -174,6 +174,29 @@ impl<'gc> Button<'gc> {
             prev_child = Some(child);
         }
     }
+
+    fn play_sound(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        sound: Option<&swf::ButtonSound>,
+    ) {
+        if let Some((id, sound_info)) = sound {
+            let movie = self.0.read().movie();
+            if let Some(sound_handle) = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .get_sound(*id)
+            {
+                let _ = context.audio_manager.start_sound(
+                    context.audio,
+                    sound_handle,
+                    sound_info,
+                    Some(self.into()),
+                    Some(movie),
+                );
+            }
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Button<'gc> {
@@ -266,12 +289,15 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         }
     }
 
-    fn render(&self, context: &mut RenderContext<'_, 'gc>) {
-        context.transform_stack.push(&*self.transform());
-
-        crate::display_object::render_children(context, &self.0.read().children);
-
-        context.transform_stack.pop();
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
+        let bounds = self.bounds();
+        let matrix = *self.matrix();
+        let read = self.0.read();
+        let slices = read
+            .base
+            .scaling_grid()
+            .and_then(|grid| grid.slices(&matrix, &bounds));
+        crate::display_object::render_children(context, &read.children, slices.as_ref());
     }
 
     fn self_bounds(&self) -> BoundingBox {
@@ -306,6 +332,20 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         None
     }
 
+    fn mouse_pick_avm2(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Avm2MousePick<'gc> {
+        if self.mouse_pick(context, (*self).into(), point).is_none() {
+            Avm2MousePick::Miss
+        } else if self.mouse_enabled() {
+            Avm2MousePick::Hit((*self).into())
+        } else {
+            Avm2MousePick::PropagateToParent
+        }
+    }
+
     fn object(&self) -> Value<'gc> {
         self.0
             .read()
@@ -360,35 +400,29 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
             _ => return ClipEventResult::NotHandled,
         };
 
-        match (cur_state, new_state) {
+        let sound = match (cur_state, new_state) {
             (ButtonState::Up, ButtonState::Over) => {
                 write.run_actions(context, swf::ButtonActionCondition::IdleToOverUp, None);
-                write.play_sound(context, write.static_data.read().up_to_over_sound.as_ref());
+                write.static_data.read().up_to_over_sound.clone()
             }
             (ButtonState::Over, ButtonState::Up) => {
                 write.run_actions(context, swf::ButtonActionCondition::OverUpToIdle, None);
-                write.play_sound(context, write.static_data.read().over_to_up_sound.as_ref());
+                write.static_data.read().over_to_up_sound.clone()
             }
             (ButtonState::Over, ButtonState::Down) => {
                 write.run_actions(context, swf::ButtonActionCondition::OverUpToOverDown, None);
-                write.play_sound(
-                    context,
-                    write.static_data.read().over_to_down_sound.as_ref(),
-                );
+                write.static_data.read().over_to_down_sound.clone()
             }
             (ButtonState::Down, ButtonState::Over) => {
                 write.run_actions(context, swf::ButtonActionCondition::OverDownToOverUp, None);
-                write.play_sound(
-                    context,
-                    write.static_data.read().down_to_over_sound.as_ref(),
-                );
+                write.static_data.read().down_to_over_sound.clone()
             }
-            _ => (),
+            _ => None,
         };
 
         // Queue ActionScript-defined event handlers after the SWF defined ones.
         // (e.g., clip.onRelease = foo).
-        if context.swf.version() >= 6 {
+        if write.movie().version() >= 6 {
             if let Some(name) = event.method_name() {
                 context.action_queue.queue_actions(
                     self_display_object,
@@ -402,8 +436,12 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
             }
         }
 
-        if write.state != new_state {
-            drop(write);
+        let state_changed = write.state != new_state;
+        drop(write);
+
+        self.play_sound(context, sound.as_ref());
+
+        if state_changed {
             self.set_state(self_display_object, context, new_state);
         }
 
@@ -416,21 +454,6 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
 }
 
 impl<'gc> ButtonData<'gc> {
-    fn play_sound(
-        &self,
-        context: &mut UpdateContext<'_, 'gc, '_>,
-        sound: Option<&swf::ButtonSound>,
-    ) {
-        if let Some((id, sound_info)) = sound {
-            if let Some(sound_handle) = context
-                .library
-                .library_for_movie_mut(self.movie())
-                .get_sound(*id)
-            {
-                let _ = context.audio.start_sound(sound_handle, sound_info);
-            }
-        }
-    }
     fn run_actions(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
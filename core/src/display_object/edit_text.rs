@@ -1,18 +1,19 @@
 //! `EditText` display object and support code.
 use crate::avm1::activation::Activation;
-use crate::avm1::globals::text_field::attach_virtual_properties;
-use crate::avm1::{Avm1, AvmString, Object, StageObject, TObject, Value};
-use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::avm1::globals::text_field::{attach_virtual_properties, initialize_broadcaster};
+use crate::avm1::{root_error_handler, Avm1, AvmString, Object, StageObject, TObject, Value};
+use crate::context::{ActionType, RenderContext, UpdateContext};
+use crate::display_object::{Avm2MousePick, DisplayObjectBase, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::font::{round_down_to_pixel, Glyph};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, TextFormat};
+use crate::html::{BoxBounds, FormatSpans, LayoutBox, LineMetrics, Position, Size, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use crate::xml::XMLDocument;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cmp::{max, min};
 use std::{cell::Ref, sync::Arc};
 use swf::Twips;
 
@@ -29,6 +30,196 @@ pub enum AutoSizeMode {
     Right,
 }
 
+/// The selected span of an `EditText`, as character indices into its text.
+///
+/// The selection is anchored where it began, and extends to the caret, which
+/// may be before or after the anchor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub struct TextSelection {
+    anchor: usize,
+    caret: usize,
+}
+
+impl TextSelection {
+    /// A selection from `anchor` to the caret at `caret`.
+    pub fn for_range(anchor: usize, caret: usize) -> Self {
+        Self { anchor, caret }
+    }
+
+    /// An empty selection, with the caret at `position`.
+    pub fn for_position(position: usize) -> Self {
+        Self::for_range(position, position)
+    }
+
+    /// The index of the first selected character.
+    pub fn start(self) -> usize {
+        min(self.anchor, self.caret)
+    }
+
+    /// The index after the last selected character.
+    pub fn end(self) -> usize {
+        max(self.anchor, self.caret)
+    }
+
+    /// The position of the caret.
+    pub fn caret(self) -> usize {
+        self.caret
+    }
+
+    /// Limits the selection to text of the given length.
+    pub fn clamp(self, length: usize) -> Self {
+        Self::for_range(min(self.anchor, length), min(self.caret, length))
+    }
+}
+
+/// The characters that the user may type into an `EditText`, as set by its
+/// `restrict` property.
+///
+/// The pattern lists the accepted characters, and `-` between two characters
+/// accepts the whole range. Each `^` toggles between accepting and rejecting
+/// the characters that follow, and a pattern starting with `^` accepts
+/// everything not rejected. `\` escapes the next character.
+#[derive(Clone, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub struct TextRestrict {
+    /// The pattern that this was parsed from.
+    pattern: String,
+
+    /// Whether characters that are not listed in the pattern are accepted.
+    accept_unlisted: bool,
+
+    /// The listed character ranges, and whether they are accepted.
+    /// Later ranges take precedence over earlier ones.
+    ranges: Vec<(char, char, bool)>,
+}
+
+impl TextRestrict {
+    pub fn from_pattern(pattern: &str) -> Self {
+        let mut ranges = Vec::new();
+        let mut accept = true;
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            let start = match c {
+                '^' => {
+                    accept = !accept;
+                    continue;
+                }
+                '\\' => match chars.next() {
+                    Some(c) => c,
+                    None => break,
+                },
+                c => c,
+            };
+
+            // A dash followed by another character makes a range; a dash
+            // at the end of the pattern is just a dash.
+            let mut end = start;
+            if chars.peek() == Some(&'-') {
+                let mut lookahead = chars.clone();
+                lookahead.next();
+                let range_end = match lookahead.next() {
+                    Some('\\') => lookahead.next(),
+                    range_end => range_end,
+                };
+                if let Some(range_end) = range_end {
+                    end = range_end;
+                    chars = lookahead;
+                }
+            }
+
+            ranges.push((start, end, accept));
+        }
+
+        Self {
+            pattern: pattern.to_string(),
+            accept_unlisted: pattern.starts_with('^'),
+            ranges,
+        }
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the pattern accepts or rejects the character `c`, or `None` if it isn't listed.
+    fn lookup(&self, c: char) -> Option<bool> {
+        self.ranges
+            .iter()
+            .rev()
+            .find(|(start, end, _)| (*start..=*end).contains(&c))
+            .map(|(_, _, accept)| *accept)
+    }
+
+    /// Whether the character `c` may be typed.
+    pub fn accepts(&self, c: char) -> bool {
+        self.lookup(c).unwrap_or(self.accept_unlisted)
+    }
+
+    /// Removes the characters that may not be typed from `text`.
+    ///
+    /// An unlisted letter that is accepted in the other case is converted, so
+    /// that a field restricted to `A-Z` turns typed lowercase letters into
+    /// uppercase ones.
+    pub fn filter(&self, text: &str) -> String {
+        text.chars()
+            .filter_map(|c| match self.lookup(c) {
+                Some(true) => Some(c),
+                Some(false) => None,
+                None if self.accept_unlisted => Some(c),
+                None => {
+                    let other_case: Vec<char> = if c.is_lowercase() {
+                        c.to_uppercase().collect()
+                    } else {
+                        c.to_lowercase().collect()
+                    };
+                    match other_case[..] {
+                        [other] if self.accepts(other) => Some(other),
+                        _ => None,
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The measurements of a line of laid-out text, as given by AS3's
+/// `TextField.getLineMetrics`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextLineMetrics {
+    /// The left edge of the line's text, relative to the field.
+    pub x: Twips,
+
+    /// The width of the line's text.
+    pub width: Twips,
+
+    /// The distance from the top of this line to the top of the next.
+    pub height: Twips,
+
+    /// The ascent of the tallest text on the line.
+    pub ascent: Twips,
+
+    /// The descent of the tallest text on the line.
+    pub descent: Twips,
+
+    /// The leading below the line.
+    pub leading: Twips,
+}
+
+/// Strip a protocol, such as `asfunction:`, from the start of a link's URL.
+///
+/// Protocols are matched case-insensitively.
+fn strip_protocol<'a>(url: &'a str, protocol: &str) -> Option<&'a str> {
+    if url.len() >= protocol.len()
+        && url.is_char_boundary(protocol.len())
+        && url[..protocol.len()].eq_ignore_ascii_case(protocol)
+    {
+        Some(&url[protocol.len()..])
+    } else {
+        None
+    }
+}
+
 /// A dynamic text field.
 /// The text in this text field can be changed dynamically.
 /// It may be selectable or editable by the user, depending on the text field properties.
@@ -98,6 +289,21 @@ pub struct EditTextData<'gc> {
     /// The intrinsic bounds of the laid-out text.
     intrinsic_bounds: BoxBounds<Twips>,
 
+    /// The position of each line of the laid-out text.
+    line_metrics: LineMetrics,
+
+    /// The one-based index of the line scrolled to the top of the field.
+    scroll: usize,
+
+    /// The horizontal scroll position of the text, in twips.
+    hscroll: i32,
+
+    /// Whether or not the mouse wheel scrolls this text field.
+    mouse_wheel_enabled: bool,
+
+    /// Whether runs of whitespace in HTML text are collapsed when it is set.
+    condense_white: bool,
+
     /// The current intrinsic bounds of the text field.
     bounds: BoundingBox,
 
@@ -112,6 +318,18 @@ pub struct EditTextData<'gc> {
 
     /// Whether this text field is firing is variable binding (to prevent infinite loops).
     firing_variable_binding: bool,
+
+    /// The selected text and caret position, if this text field has been focused.
+    selection: Option<TextSelection>,
+
+    /// If the user can type into the text field.
+    is_editable: bool,
+
+    /// The characters that the user may type, or `None` to accept any.
+    restrict: Option<TextRestrict>,
+
+    /// The most characters that the user may type into the field, or 0 for no limit.
+    max_chars: i32,
 }
 
 impl<'gc> EditText<'gc> {
@@ -134,16 +352,16 @@ impl<'gc> EditText<'gc> {
         if is_html {
             document
                 .as_node()
-                .replace_with_str(context.gc_context, &text, false)
+                .replace_with_str(context.gc_context, &text, false, false)
                 .unwrap();
-            text_spans.lower_from_html(document);
+            text_spans.lower_from_html(document, false);
         } else {
             text_spans.replace_text(0, text_spans.text().len(), &text, Some(&default_format));
         }
 
         let bounds: BoundingBox = swf_tag.bounds.clone().into();
 
-        let (layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
+        let (layout, intrinsic_bounds, line_metrics) = LayoutBox::lower_from_text_spans(
             &text_spans,
             context,
             swf_movie.clone(),
@@ -154,6 +372,8 @@ impl<'gc> EditText<'gc> {
 
         let has_border = swf_tag.has_border;
         let is_device_font = swf_tag.is_device_font;
+        let is_editable = !swf_tag.is_read_only;
+        let max_chars = swf_tag.max_length.map_or(0, i32::from);
 
         let mut base = DisplayObjectBase::default();
 
@@ -188,11 +408,20 @@ impl<'gc> EditText<'gc> {
                 object: None,
                 layout,
                 intrinsic_bounds,
+                line_metrics,
+                scroll: 1,
+                hscroll: 0,
+                mouse_wheel_enabled: true,
+                condense_white: false,
                 bounds,
                 autosize: AutoSizeMode::None,
                 variable,
                 bound_stage_object: None,
                 firing_variable_binding: false,
+                selection: None,
+                is_editable,
+                restrict: None,
+                max_chars,
             },
         ));
 
@@ -227,7 +456,7 @@ impl<'gc> EditText<'gc> {
                 b: 0,
                 a: 0xFF,
             }),
-            max_length: Some(width as u16),
+            max_length: None,
             layout: Some(swf::TextLayout {
                 align: swf::TextAlign::Left,
                 left_margin: Twips::from_pixels(0.0),
@@ -313,7 +542,7 @@ impl<'gc> EditText<'gc> {
             if let Err(err) =
                 document
                     .as_node()
-                    .replace_with_str(context.gc_context, &html_string, false)
+                    .replace_with_str(context.gc_context, &html_string, false, false)
             {
                 log::warn!("Parsing error when setting TextField.htmlText: {}", err);
             }
@@ -343,7 +572,8 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context.gc_context);
 
         write.document = doc;
-        write.text_spans.lower_from_html(doc);
+        let condense_white = write.condense_white;
+        write.text_spans.lower_from_html(doc, condense_white);
 
         drop(write);
 
@@ -354,6 +584,128 @@ impl<'gc> EditText<'gc> {
         self.0.read().text_spans.text().len()
     }
 
+    /// The selected text and caret position, or `None` if this text field has
+    /// never had a selection.
+    pub fn selection(self) -> Option<TextSelection> {
+        self.0.read().selection
+    }
+
+    /// Changes the selection, limiting it to the length of the text.
+    pub fn set_selection(
+        self,
+        selection: Option<TextSelection>,
+        gc_context: MutationContext<'gc, '_>,
+    ) {
+        let mut edit_text = self.0.write(gc_context);
+        let length = edit_text.text_spans.text().len();
+        edit_text.selection = selection.map(|selection| selection.clamp(length));
+    }
+
+    pub fn is_editable(self) -> bool {
+        self.0.read().is_editable
+    }
+
+    pub fn set_editable(self, is_editable: bool, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).is_editable = is_editable;
+    }
+
+    pub fn restrict(self) -> Option<String> {
+        self.0
+            .read()
+            .restrict
+            .as_ref()
+            .map(|restrict| restrict.pattern().to_string())
+    }
+
+    /// Sets the characters that the user may type, or `None` to accept any.
+    pub fn set_restrict(self, pattern: Option<&str>, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).restrict = pattern.map(TextRestrict::from_pattern);
+    }
+
+    pub fn max_chars(self) -> i32 {
+        self.0.read().max_chars
+    }
+
+    pub fn set_max_chars(self, max_chars: i32, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).max_chars = max_chars;
+    }
+
+    /// Filters text that the user is about to type or paste over the current
+    /// selection, leaving only what `restrict` accepts and what fits within
+    /// `maxChars`. This does not apply to text set by scripts.
+    pub fn filter_input(self, text: &str) -> String {
+        let edit_text = self.0.read();
+        let mut text = match &edit_text.restrict {
+            Some(restrict) => restrict.filter(text),
+            None => text.to_string(),
+        };
+
+        if edit_text.max_chars > 0 {
+            let current = edit_text.text_spans.text();
+            let replaced = edit_text
+                .selection
+                .map(|selection| selection.clamp(current.len()))
+                .and_then(|selection| current.get(selection.start()..selection.end()))
+                .map_or(0, |replaced| replaced.chars().count());
+            let remaining =
+                (edit_text.max_chars as usize).saturating_sub(current.chars().count() - replaced);
+            if let Some((end, _)) = text.char_indices().nth(remaining) {
+                text.truncate(end);
+            }
+        }
+
+        text
+    }
+
+    /// Inserts text typed or pasted by the user over the current selection,
+    /// leaving the caret after it.
+    ///
+    /// In AVM2 movies, a `textInput` event is dispatched first, and nothing is
+    /// inserted if a listener cancels it. The text is then filtered through
+    /// `filter_input`. If anything is inserted, bound variables are updated
+    /// and the field's listeners are told that it changed.
+    pub fn text_input(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.is_editable() {
+            return;
+        }
+
+        // Control characters, such as those of Backspace or Enter, aren't text.
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        if text.is_empty() {
+            return;
+        }
+
+        if self.root().object2().is_some() && !crate::avm2::text_input(context, self.into(), &text)
+        {
+            return;
+        }
+
+        let text = self.filter_input(&text);
+        if text.is_empty() {
+            return;
+        }
+
+        let length = self.text_length();
+        let selection = self
+            .selection()
+            .map_or(TextSelection::for_position(length), |selection| {
+                selection.clamp(length)
+            });
+        self.replace_text(selection.start(), selection.end(), &text, context);
+        self.set_selection(
+            Some(TextSelection::for_position(selection.start() + text.len())),
+            context.gc_context,
+        );
+
+        Avm1::run_with_stack_frame_for_display_object(
+            self.into(),
+            self.swf_version(),
+            context,
+            |activation| self.propagate_text_binding(activation),
+        );
+        self.on_changed(context);
+    }
+
     pub fn new_text_format(self) -> TextFormat {
         self.0.read().text_spans.default_format().clone()
     }
@@ -410,6 +762,436 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    /// The height of the area that text is displayed within.
+    fn visible_height(edit_text: &EditTextData<'gc>) -> Twips {
+        edit_text.bounds.height() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0)
+    }
+
+    /// The width of the area that text is displayed within.
+    fn visible_width(edit_text: &EditTextData<'gc>) -> Twips {
+        edit_text.bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0)
+    }
+
+    /// The one-based index of the topmost visible line.
+    pub fn scroll(self) -> usize {
+        min(self.0.read().scroll, self.maxscroll())
+    }
+
+    /// Scroll the field so that the given one-based line is at the top.
+    ///
+    /// The value is clamped to the range `[1, maxscroll]`, and `onScroller`
+    /// is fired if the scroll position changes as a result.
+    pub fn set_scroll(self, scroll: f64, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if scroll.is_nan() {
+            return;
+        }
+
+        let old_scroll = self.scroll();
+        let new_scroll = scroll.max(1.0).min(self.maxscroll() as f64) as usize;
+
+        self.0.write(context.gc_context).scroll = new_scroll;
+
+        if old_scroll != new_scroll {
+            self.0.write(context.gc_context).base.set_content_changed();
+            self.on_scroller(context);
+        }
+    }
+
+    /// The largest value `scroll` can take on.
+    pub fn maxscroll(self) -> usize {
+        let edit_text = self.0.read();
+
+        edit_text
+            .line_metrics
+            .max_scroll_line(Self::visible_height(&edit_text))
+            + 1
+    }
+
+    /// The one-based index of the bottommost visible line.
+    pub fn bottom_scroll(self) -> usize {
+        let scroll = self.scroll();
+        let edit_text = self.0.read();
+
+        edit_text
+            .line_metrics
+            .bottom_line(scroll - 1, Self::visible_height(&edit_text))
+            + 1
+    }
+
+    /// The horizontal scroll position of the text, in pixels.
+    pub fn hscroll(self) -> f64 {
+        self.hscroll_twips().to_pixels()
+    }
+
+    fn hscroll_twips(self) -> Twips {
+        min(Twips::new(self.0.read().hscroll), self.maxhscroll_twips())
+    }
+
+    /// Scroll the field horizontally, clamping the value to the range
+    /// `[0, maxhscroll]`.
+    pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if hscroll.is_nan() {
+            return;
+        }
+
+        let old_hscroll = self.hscroll_twips();
+        let new_hscroll = max(
+            min(Twips::from_pixels(hscroll), self.maxhscroll_twips()),
+            Twips::new(0),
+        );
+
+        self.0.write(context.gc_context).hscroll = new_hscroll.get();
+
+        if old_hscroll != new_hscroll {
+            self.0.write(context.gc_context).base.set_content_changed();
+            self.on_scroller(context);
+        }
+    }
+
+    /// The largest value `hscroll` can take on, in pixels.
+    pub fn maxhscroll(self) -> f64 {
+        self.maxhscroll_twips().to_pixels()
+    }
+
+    fn maxhscroll_twips(self) -> Twips {
+        let edit_text = self.0.read();
+
+        max(
+            edit_text.intrinsic_bounds.width() - Self::visible_width(&edit_text),
+            Twips::new(0),
+        )
+    }
+
+    /// The offset from the layout's coordinate space to the field's, which
+    /// depends on how far the field is scrolled.
+    fn layout_offset(self) -> (Twips, Twips) {
+        let first_line = self.scroll() - 1;
+        let hscroll = self.hscroll_twips();
+        let edit_text = self.0.read();
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+
+        (
+            edit_text.bounds.x_min + padding - hscroll,
+            edit_text.bounds.y_min + padding - edit_text.line_metrics.line_top(first_line),
+        )
+    }
+
+    /// The index of the first character of each line.
+    fn line_offsets(edit_text: &EditTextData<'gc>) -> Vec<usize> {
+        let text = edit_text.text_spans.text();
+        let mut starts = vec![None; edit_text.line_metrics.line_count()];
+        starts[0] = Some(0);
+
+        for layout_box in edit_text.layout.iter() {
+            if let Some((start, _end)) = layout_box.text_range() {
+                let line = edit_text
+                    .line_metrics
+                    .line_at(layout_box.bounds().offset_y());
+                if let Some(line_start) = starts.get_mut(line) {
+                    *line_start = Some(line_start.map_or(start, |s| min(s, start)));
+                }
+            }
+        }
+
+        let mut offsets: Vec<usize> = Vec::with_capacity(starts.len());
+        for start in starts {
+            // Lines without any text boxes follow an explicit newline.
+            let offset = start.unwrap_or_else(|| {
+                let previous = offsets.last().copied().unwrap_or(0);
+                text[previous..]
+                    .find('\n')
+                    .map_or(text.len(), |i| previous + i + 1)
+            });
+            offsets.push(offset);
+        }
+
+        offsets
+    }
+
+    /// The number of lines of text.
+    pub fn num_lines(self) -> usize {
+        self.0.read().line_metrics.line_count()
+    }
+
+    /// The index of the first character of the given zero-based line.
+    ///
+    /// Returns `None` if there is no such line, which AS3 reports with a
+    /// `RangeError`.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        Self::line_offsets(&self.0.read()).get(line).copied()
+    }
+
+    /// The number of characters in the given zero-based line, including any
+    /// line break at its end.
+    pub fn line_length(self, line: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        let offsets = Self::line_offsets(&edit_text);
+        let start = *offsets.get(line)?;
+        let end = offsets
+            .get(line + 1)
+            .copied()
+            .unwrap_or_else(|| edit_text.text_spans.text().len());
+
+        Some(end.saturating_sub(start))
+    }
+
+    /// The text of the given zero-based line, including any line break at
+    /// its end.
+    pub fn line_text(self, line: usize) -> Option<String> {
+        let start = self.line_offset(line)?;
+        let length = self.line_length(line)?;
+        let edit_text = self.0.read();
+
+        edit_text
+            .text_spans
+            .text()
+            .get(start..start + length)
+            .map(str::to_string)
+    }
+
+    /// The zero-based line containing the character at `index`.
+    pub fn line_index_of_char(self, index: usize) -> Option<usize> {
+        let edit_text = self.0.read();
+        if index >= edit_text.text_spans.text().len() {
+            return None;
+        }
+
+        Self::line_offsets(&edit_text)
+            .iter()
+            .rposition(|offset| *offset <= index)
+    }
+
+    /// The zero-based line under the given point, in the field's coordinate
+    /// space.
+    pub fn line_index_at_point(self, point: (Twips, Twips)) -> Option<usize> {
+        let (_dx, dy) = self.layout_offset();
+        let edit_text = self.0.read();
+        let line_metrics = &edit_text.line_metrics;
+        let y = point.1 - dy;
+
+        if y < Twips::new(0) || y >= line_metrics.line_bottom(line_metrics.line_count() - 1) {
+            return None;
+        }
+
+        Some(line_metrics.line_at(y))
+    }
+
+    /// The index of the character under the given point, in the field's
+    /// coordinate space.
+    pub fn char_index_at_point(self, point: (Twips, Twips)) -> Option<usize> {
+        let line = self.line_index_at_point(point)?;
+        let (dx, _dy) = self.layout_offset();
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+        let x = point.0 - dx;
+
+        edit_text
+            .layout
+            .iter()
+            .filter(|layout_box| {
+                edit_text
+                    .line_metrics
+                    .line_at(layout_box.bounds().offset_y())
+                    == line
+            })
+            .find_map(|layout_box| {
+                let (start, end) = layout_box.text_range()?;
+                let left = layout_box.bounds().offset_x();
+                if x < left {
+                    return None;
+                }
+
+                text.get(start..end)?
+                    .char_indices()
+                    .map(|(i, _)| start + i)
+                    .find(|index| {
+                        layout_box
+                            .char_x_range(text, *index)
+                            .map_or(false, |(_, right)| x < left + right)
+                    })
+            })
+    }
+
+    /// The bounds of the character at `index`, in the field's coordinate
+    /// space.
+    ///
+    /// Returns `None` for characters that aren't laid out, such as line
+    /// breaks.
+    pub fn char_boundaries(self, index: usize) -> Option<BoxBounds<Twips>> {
+        let (dx, dy) = self.layout_offset();
+        let edit_text = self.0.read();
+        let text = edit_text.text_spans.text();
+
+        edit_text.layout.iter().find_map(|layout_box| {
+            let (left, right) = layout_box.char_x_range(text, index)?;
+            let bounds = layout_box.bounds();
+
+            Some(BoxBounds::from_position_and_size(
+                Position::from((bounds.offset_x() + left + dx, bounds.offset_y() + dy)),
+                Size::from((right - left, bounds.height())),
+            ))
+        })
+    }
+
+    /// The measurements of the given zero-based line.
+    pub fn line_metrics(self, line: usize) -> Option<TextLineMetrics> {
+        let edit_text = self.0.read();
+        let line_metrics = &edit_text.line_metrics;
+        if line >= line_metrics.line_count() {
+            return None;
+        }
+
+        let text = edit_text.text_spans.text();
+        let mut metrics = TextLineMetrics {
+            height: line_metrics.line_bottom(line) - line_metrics.line_top(line),
+            ..Default::default()
+        };
+        let mut line_bounds: Option<BoxBounds<Twips>> = None;
+        let mut tallest = Twips::new(0);
+
+        for layout_box in edit_text.layout.iter() {
+            if !layout_box.is_text_box()
+                || line_metrics.line_at(layout_box.bounds().offset_y()) != line
+            {
+                continue;
+            }
+
+            if let Some(line_bounds) = &mut line_bounds {
+                *line_bounds += layout_box.bounds();
+            } else {
+                line_bounds = Some(layout_box.bounds());
+            }
+
+            if let Some((_, text_format, font, params, _)) = layout_box.as_renderable_text(text) {
+                if params.height() > tallest {
+                    tallest = params.height();
+                    metrics.ascent = font.get_baseline_for_height(params.height());
+                    metrics.descent = font.get_descent_for_height(params.height());
+                    metrics.leading = font.get_leading_for_height(params.height())
+                        + Twips::from_pixels(text_format.leading.unwrap_or(0.0));
+                }
+            }
+        }
+
+        if let Some(line_bounds) = line_bounds {
+            metrics.x = Twips::from_pixels(Self::INTERNAL_PADDING) + line_bounds.offset_x();
+            metrics.width = line_bounds.width();
+        }
+
+        Some(metrics)
+    }
+
+    /// The URL and target window of the link under the given point, in the
+    /// field's coordinate space.
+    pub fn link_at_point(self, point: (Twips, Twips)) -> Option<(String, String)> {
+        let index = self.char_index_at_point(point)?;
+        let length = self.0.read().text_spans.text()[index..]
+            .chars()
+            .next()
+            .map_or(1, char::len_utf8);
+        let text_format = self.text_format(index, index + length);
+        let url = text_format.url.filter(|url| !url.is_empty())?;
+
+        Some((url, text_format.target.unwrap_or_default()))
+    }
+
+    /// Follow the link under the given point on the stage, if there is one.
+    ///
+    /// `asfunction:` links call a function on the clip containing the field,
+    /// and `event:` links dispatch `TextEvent.LINK` in ActionScript 3 movies.
+    /// Any other link is opened by the navigator.
+    pub fn click_link(self, context: &mut UpdateContext<'_, 'gc, '_>, point: (Twips, Twips)) {
+        let (url, target) = match self.link_at_point(self.global_to_local(point)) {
+            Some(link) => link,
+            None => return,
+        };
+
+        if let Some(call) = strip_protocol(&url, "asfunction:") {
+            let mut parts = call.splitn(2, ',');
+            let name = parts.next().unwrap_or_default();
+            let arg = parts.next().unwrap_or_default();
+            if let Some(parent) = self.parent() {
+                if let Value::Object(object) = parent.object() {
+                    Avm1::run_with_stack_frame_for_display_object(
+                        parent,
+                        self.swf_version(),
+                        context,
+                        |activation| {
+                            let arg = AvmString::new(activation.context.gc_context, arg);
+                            if let Err(e) = object.call_method(name, &[arg.into()], activation) {
+                                root_error_handler(activation, e);
+                            }
+                        },
+                    );
+                }
+            }
+        } else if let Some(text) = strip_protocol(&url, "event:") {
+            if self.root().object2().is_some() {
+                crate::avm2::text_link(context, self.into(), text);
+            }
+        } else {
+            let target = Some(target).filter(|target| !target.is_empty());
+            context.navigator.navigate_to_url(url, target, None);
+        }
+    }
+
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        self.0.read().mouse_wheel_enabled
+    }
+
+    pub fn set_mouse_wheel_enabled(self, context: MutationContext<'gc, '_>, enabled: bool) {
+        self.0.write(context).mouse_wheel_enabled = enabled;
+    }
+
+    pub fn condense_white(self) -> bool {
+        self.0.read().condense_white
+    }
+
+    /// Sets whether whitespace is collapsed in HTML text. This only affects
+    /// text set after the change.
+    pub fn set_condense_white(self, context: MutationContext<'gc, '_>, condense_white: bool) {
+        self.0.write(context).condense_white = condense_white;
+    }
+
+    /// Notify this text field's listeners that its scroll position or range
+    /// has changed.
+    ///
+    /// Text fields are broadcasters which listen to themselves, so this also
+    /// calls the field's own `onScroller` handler.
+    fn on_scroller(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if let Some(object) = self.0.read().object {
+            context.action_queue.queue_actions(
+                self.into(),
+                ActionType::Method {
+                    object,
+                    name: "broadcastMessage",
+                    args: vec!["onScroller".into(), object.into()],
+                },
+                false,
+            );
+        }
+    }
+
+    /// Notify this text field's listeners that the user changed its text.
+    ///
+    /// Like `onScroller`, this also calls the field's own `onChanged` handler.
+    /// In AVM2 movies, a bubbling `change` event is dispatched instead.
+    fn on_changed(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.root().object2().is_some() {
+            crate::avm2::text_change(context, self.into());
+        } else if let Some(object) = self.0.read().object {
+            context.action_queue.queue_actions(
+                self.into(),
+                ActionType::Method {
+                    object,
+                    name: "broadcastMessage",
+                    args: vec!["onChanged".into(), object.into()],
+                },
+                false,
+            );
+        }
+    }
+
     pub fn has_border(self) -> bool {
         self.0.read().has_border
     }
@@ -542,6 +1324,7 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context);
 
         write.drawing.clear();
+        write.base.set_content_changed();
 
         if write.has_border {
             let bounds = write.bounds.clone();
@@ -584,13 +1367,19 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let old_scroll = (self.scroll(), self.maxscroll(), self.hscroll_twips());
+
         let mut edit_text = self.0.write(context.gc_context);
+        // The text may have become shorter than the selection.
+        let length = edit_text.text_spans.text().len();
+        edit_text.selection = edit_text.selection.map(|selection| selection.clamp(length));
+
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
         let movie = edit_text.static_data.swf.clone();
         let width = edit_text.bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0);
 
-        let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
+        let (new_layout, intrinsic_bounds, line_metrics) = LayoutBox::lower_from_text_spans(
             &edit_text.text_spans,
             context,
             movie,
@@ -601,6 +1390,8 @@ impl<'gc> EditText<'gc> {
 
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
+        edit_text.line_metrics = line_metrics;
+        edit_text.base.set_content_changed();
 
         match autosize {
             AutoSizeMode::None => {}
@@ -640,6 +1431,15 @@ impl<'gc> EditText<'gc> {
                 edit_text.base.set_transformed_by_script(true);
             }
         }
+
+        drop(edit_text);
+
+        let new_scroll = (self.scroll(), self.maxscroll(), self.hscroll_twips());
+        self.0.write(context.gc_context).scroll = new_scroll.0;
+
+        if old_scroll != new_scroll {
+            self.on_scroller(context);
+        }
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -718,7 +1518,7 @@ impl<'gc> EditText<'gc> {
             activation.run_with_child_frame_for_display_object(
                 "[Text Field Binding]",
                 parent,
-                activation.context.swf.header().version,
+                parent.swf_version(),
                 |activation| {
                     if let Ok(Some((object, property))) =
                         activation.resolve_variable_path(parent, &variable)
@@ -805,7 +1605,7 @@ impl<'gc> EditText<'gc> {
                     activation.run_with_child_frame_for_display_object(
                         "[Propagate Text Binding]",
                         self.parent().unwrap(),
-                        activation.context.swf.header().version,
+                        self.swf_version(),
                         |activation| {
                             let _ = object.set(
                                 property,
@@ -867,6 +1667,13 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 context.system_prototypes.function,
             );
 
+            initialize_broadcaster(
+                context.gc_context,
+                object,
+                context.avm1.broadcaster_functions(),
+                context.system_prototypes.array,
+            );
+
             text.object = Some(object);
         }
 
@@ -885,7 +1692,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         // If this text field has a variable set, initialize text field binding.
         Avm1::run_with_stack_frame_for_display_object(
             (*self).into(),
-            context.swf.version(),
+            self.swf_version(),
             context,
             |activation| {
                 if !self.try_bind_text_field_variable(activation, true) {
@@ -913,6 +1720,26 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.0.read().bounds.clone()
     }
 
+    fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
+        // Text fields are hit anywhere within their bounds, not just on the glyphs.
+        self.world_bounds().contains(point)
+            && self.self_bounds().contains(self.global_to_local(point))
+    }
+
+    fn mouse_pick_avm2(
+        &self,
+        _context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Avm2MousePick<'gc> {
+        if !self.visible() || !self.hit_test_shape(point) {
+            Avm2MousePick::Miss
+        } else if self.mouse_enabled() {
+            Avm2MousePick::Hit((*self).into())
+        } else {
+            Avm2MousePick::PropagateToParent
+        }
+    }
+
     // The returned position x and y of a text field is offset by the text bounds.
     fn x(&self) -> f64 {
         let edit_text = self.0.read();
@@ -977,15 +1804,12 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         self.redraw_border(context);
     }
 
-    fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
         if !self.world_bounds().intersects(&context.view_bounds) {
             // Off-screen; culled
             return;
         }
 
-        let transform = self.transform().clone();
-        context.transform_stack.push(&transform);
-
         context.transform_stack.push(&Transform {
             matrix: Matrix {
                 tx: self.0.read().bounds.x_min,
@@ -997,24 +1821,41 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         self.0.read().drawing.render(context);
 
+        let first_line = self.scroll() - 1;
+        let last_line = self.bottom_scroll() - 1;
+        let hscroll = self.hscroll_twips();
+        let edit_text = self.0.read();
+        let scroll_offset = edit_text.line_metrics.line_top(first_line);
+
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
         // If this is actually right, offset the border in `redraw_border` instead of doing an extra push.
         context.transform_stack.push(&Transform {
             matrix: Matrix {
-                tx: Twips::from_pixels(Self::INTERNAL_PADDING),
-                ty: Twips::from_pixels(Self::INTERNAL_PADDING),
+                tx: Twips::from_pixels(Self::INTERNAL_PADDING) - hscroll,
+                ty: Twips::from_pixels(Self::INTERNAL_PADDING) - scroll_offset,
                 ..Default::default()
             },
             ..Default::default()
         });
 
-        for layout_box in self.0.read().layout.iter() {
-            self.render_layout_box(context, layout_box);
+        // Only lines that are scrolled into view are rendered. Underline
+        // drawings don't have meaningful bounds, but are always placed after
+        // the text of the line they belong to.
+        let mut line = 0;
+        for layout_box in edit_text.layout.iter() {
+            if layout_box.is_text_box() || layout_box.is_bullet() {
+                line = edit_text
+                    .line_metrics
+                    .line_at(layout_box.bounds().offset_y());
+            }
+
+            if line >= first_line && line <= last_line {
+                self.render_layout_box(context, layout_box);
+            }
         }
 
         context.transform_stack.pop();
         context.transform_stack.pop();
-        context.transform_stack.pop();
     }
 
     fn allow_as_mask(&self) -> bool {
@@ -1057,3 +1898,29 @@ unsafe impl<'gc> gc_arena::Collect for EditTextStatic {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextRestrict;
+
+    #[test]
+    fn restrict_ranges() {
+        let restrict = TextRestrict::from_pattern("0-9a-f");
+        assert_eq!(restrict.filter("1f9g-"), "1f9");
+        assert_eq!(restrict.filter("BAD"), "bad");
+    }
+
+    #[test]
+    fn restrict_negation() {
+        assert_eq!(TextRestrict::from_pattern("^0-9").filter("a1b2"), "ab");
+        assert_eq!(TextRestrict::from_pattern("A-Z^Q").filter("PQRq"), "PR");
+        assert_eq!(TextRestrict::from_pattern("^a^b").filter("abcA"), "bcA");
+    }
+
+    #[test]
+    fn restrict_escapes() {
+        assert_eq!(TextRestrict::from_pattern("\\-\\^").filter("a-^"), "-^");
+        assert_eq!(TextRestrict::from_pattern("a-").filter("ab-"), "a-");
+        assert_eq!(TextRestrict::from_pattern("").filter("abc"), "");
+    }
+}
@@ -39,6 +39,10 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         self.0.read().static_data.id
     }
 
+    fn as_graphic(&self) -> Option<Self> {
+        Some(*self)
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().static_data.bounds.clone()
     }
@@ -58,20 +62,16 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         // Noop
     }
 
-    fn render(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext) {
         if !self.world_bounds().intersects(&context.view_bounds) {
             // Off-screen; culled
             return;
         }
 
-        context.transform_stack.push(&*self.transform());
-
         context.renderer.render_shape(
             self.0.read().static_data.render_handle,
             context.transform_stack.transform(),
         );
-
-        context.transform_stack.pop();
     }
 
     fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
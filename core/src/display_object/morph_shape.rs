@@ -2,8 +2,9 @@ use crate::backend::render::{RenderBackend, ShapeHandle};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::shape_utils::{interpolate_morph_bounds, interpolate_morph_shape};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
-use swf::Twips;
+use std::cell::RefCell;
 
 #[derive(Clone, Debug, Collect, Copy)]
 #[collect(no_drop)]
@@ -36,7 +37,9 @@ impl<'gc> MorphShape<'gc> {
     }
 
     pub fn set_ratio(&mut self, gc_context: MutationContext<'gc, '_>, ratio: u16) {
-        self.0.write(gc_context).ratio = ratio;
+        let mut write = self.0.write(gc_context);
+        write.ratio = ratio;
+        write.base.set_content_changed();
     }
 }
 
@@ -55,26 +58,31 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         // Noop
     }
 
-    fn render(&self, context: &mut RenderContext) {
-        context.transform_stack.push(&*self.transform());
-
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            context
-                .renderer
-                .render_shape(frame.shape, context.transform_stack.transform());
-        } else {
-            log::warn!("Missing ratio for morph shape");
-        }
-
-        context.transform_stack.pop();
+    fn render_self(&self, context: &mut RenderContext) {
+        let read = self.0.read();
+        let shape = read
+            .static_data
+            .shape_for_ratio(context.renderer, read.ratio);
+        context
+            .renderer
+            .render_shape(shape, context.transform_stack.transform());
     }
 
     fn self_bounds(&self) -> BoundingBox {
-        // TODO: Use the bounds of the current ratio.
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            frame.bounds.clone()
+        let read = self.0.read();
+        let static_data = &read.static_data;
+        interpolate_morph_bounds(&static_data.start, &static_data.end, read.ratio).into()
+    }
+
+    fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
+        if self.world_bounds().contains(point) {
+            let local_matrix = self.global_to_local_matrix();
+            let point = local_matrix * point;
+            let read = self.0.read();
+            let shape = read.static_data.hit_test_shape_for_ratio(read.ratio);
+            crate::shape_utils::shape_hit_test(&shape, point, &local_matrix)
         } else {
-            BoundingBox::default()
+            false
         }
     }
 }
@@ -87,28 +95,34 @@ unsafe impl<'gc> gc_arena::Collect for MorphShapeData<'gc> {
     }
 }
 
-/// A precalculated intermediate frame for a morph shape.
-struct Frame {
-    shape: ShapeHandle,
-    bounds: BoundingBox,
+/// The number of distinct ratios a morph shape is tessellated at.
+/// Ratios are rounded to the nearest step, so that a long tween doesn't register a new shape on
+/// every frame.
+const RATIO_STEPS: u32 = 512;
+
+/// Rounds a ratio to the nearest of the `RATIO_STEPS` steps. The start and end states are exact.
+fn quantize_ratio(ratio: u16) -> u16 {
+    let step = (u32::from(ratio) * RATIO_STEPS + 32767) / 65535;
+    (step * 65535 / RATIO_STEPS) as u16
 }
 
 /// Static data shared between all instances of a morph shape.
-#[allow(dead_code)]
 pub struct MorphShapeStatic {
     id: CharacterId,
     start: swf::MorphShape,
     end: swf::MorphShape,
-    frames: fnv::FnvHashMap<u16, Frame>,
+
+    /// The shapes registered so far, by quantized ratio.
+    frames: RefCell<fnv::FnvHashMap<u16, ShapeHandle>>,
 }
 
 impl MorphShapeStatic {
     pub fn from_swf_tag(renderer: &mut dyn RenderBackend, swf_tag: &swf::DefineMorphShape) -> Self {
-        let mut morph_shape = Self {
+        let morph_shape = Self {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
             end: swf_tag.end.clone(),
-            frames: fnv::FnvHashMap::default(),
+            frames: RefCell::new(fnv::FnvHashMap::default()),
         };
         // Pre-register the start and end states.
         morph_shape.register_ratio(renderer, 0);
@@ -116,167 +130,27 @@ impl MorphShapeStatic {
         morph_shape
     }
 
-    pub fn register_ratio(&mut self, renderer: &mut dyn RenderBackend, ratio: u16) {
-        if self.frames.contains_key(&ratio) {
-            // Already registered.
-            return;
-        }
-
-        // Interpolate MorphShapes into a Shape.
-        use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
-        // Start shape is ratio 65535, end shape is ratio 0.
-        let b = f32::from(ratio) / 65535.0;
-        let a = 1.0 - b;
-        let fill_styles: Vec<FillStyle> = self
-            .start
-            .fill_styles
-            .iter()
-            .zip(self.end.fill_styles.iter())
-            .map(|(start, end)| lerp_fill(start, end, a, b))
-            .collect();
-        let line_styles: Vec<LineStyle> = self
-            .start
-            .line_styles
-            .iter()
-            .zip(self.end.line_styles.iter())
-            .map(|(start, end)| LineStyle {
-                width: lerp_twips(start.width, end.width, a, b),
-                color: lerp_color(&start.color, &end.color, a, b),
-                start_cap: start.start_cap,
-                end_cap: start.end_cap,
-                join_style: start.join_style,
-                fill_style: None,
-                allow_scale_x: start.allow_scale_x,
-                allow_scale_y: start.allow_scale_y,
-                is_pixel_hinted: start.is_pixel_hinted,
-                allow_close: start.allow_close,
-            })
-            .collect();
-
-        let mut shape = Vec::with_capacity(self.start.shape.len());
-        let mut start_iter = self.start.shape.iter();
-        let mut end_iter = self.end.shape.iter();
-        let mut start = start_iter.next();
-        let mut end = end_iter.next();
-        let mut start_x = Twips::new(0);
-        let mut start_y = Twips::new(0);
-        let mut end_x = Twips::new(0);
-        let mut end_y = Twips::new(0);
-        // TODO: Feels like this could be cleaned up a bit.
-        // We step through both the start records and end records, interpolating edges pairwise.
-        // Fill style/line style changes should only appear in the start records.
-        // However, StyleChangeRecord move_to can appear it both start and end records,
-        // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
-        // in case one side is missing a move_to; it will implicitly use the last pen position.
-        while let (Some(s), Some(e)) = (start, end) {
-            match (s, e) {
-                (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = start_change.clone();
-                    if let Some((s_x, s_y)) = start_change.move_to {
-                        if let Some((e_x, e_y)) = end_change.move_to {
-                            start_x = s_x;
-                            start_y = s_y;
-                            end_x = e_x;
-                            end_y = e_y;
-                            style_change.move_to = Some((
-                                lerp_twips(start_x, end_x, a, b),
-                                lerp_twips(start_y, end_y, a, b),
-                            ));
-                        } else {
-                            panic!("Expected move_to for morph shape")
-                        }
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    start = start_iter.next();
-                    end = end_iter.next();
-                }
-                (ShapeRecord::StyleChange(start_change), _) => {
-                    let mut style_change = start_change.clone();
-                    if let Some((s_x, s_y)) = start_change.move_to {
-                        start_x = s_x;
-                        start_y = s_y;
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    start = start_iter.next();
-                }
-                (_, ShapeRecord::StyleChange(end_change)) => {
-                    let mut style_change = end_change.clone();
-                    if let Some((e_x, e_y)) = end_change.move_to {
-                        end_x = e_x;
-                        end_y = e_y;
-                        style_change.move_to = Some((
-                            lerp_twips(start_x, end_x, a, b),
-                            lerp_twips(start_y, end_y, a, b),
-                        ));
-                    }
-                    shape.push(ShapeRecord::StyleChange(style_change));
-                    Self::update_pos(&mut end_x, &mut end_y, s);
-                    end = end_iter.next();
-                    continue;
-                }
-                _ => {
-                    shape.push(lerp_edges(s, e, a, b));
-                    Self::update_pos(&mut start_x, &mut start_y, s);
-                    Self::update_pos(&mut end_x, &mut end_y, e);
-                    start = start_iter.next();
-                    end = end_iter.next();
-                }
-            }
-        }
-
-        let styles = ShapeStyles {
-            fill_styles,
-            line_styles,
-        };
-
-        let bounds = crate::shape_utils::calculate_shape_bounds(&shape[..]);
-        let shape = swf::Shape {
-            version: 4,
-            id: 0,
-            shape_bounds: bounds.clone(),
-            edge_bounds: bounds.clone(),
-            has_fill_winding_rule: false,
-            has_non_scaling_strokes: false,
-            has_scaling_strokes: true,
-            styles,
-            shape,
-        };
+    /// Registers the shape for the given ratio with the renderer, if it isn't registered yet.
+    /// Ratios used by `PlaceObject` tags are registered while preloading; others are registered
+    /// the first time they are rendered.
+    pub fn register_ratio(&self, renderer: &mut dyn RenderBackend, ratio: u16) {
+        self.shape_for_ratio(renderer, ratio);
+    }
 
-        let frame = Frame {
-            shape: renderer.register_shape((&shape).into()),
-            bounds: bounds.into(),
-        };
-        self.frames.insert(ratio, frame);
+    /// Returns the shape for the given ratio, registering it first if needed.
+    fn shape_for_ratio(&self, renderer: &mut dyn RenderBackend, ratio: u16) -> ShapeHandle {
+        let ratio = quantize_ratio(ratio);
+        let mut frames = self.frames.borrow_mut();
+        *frames.entry(ratio).or_insert_with(|| {
+            let shape = interpolate_morph_shape(&self.start, &self.end, ratio);
+            renderer.register_shape((&shape).into())
+        })
     }
 
-    fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
-        use swf::ShapeRecord;
-        match record {
-            ShapeRecord::StraightEdge { delta_x, delta_y } => {
-                *x += *delta_x;
-                *y += *delta_y;
-            }
-            ShapeRecord::CurvedEdge {
-                control_delta_x,
-                control_delta_y,
-                anchor_delta_x,
-                anchor_delta_y,
-            } => {
-                *x += *control_delta_x + *anchor_delta_x;
-                *y += *control_delta_y + *anchor_delta_y;
-            }
-            ShapeRecord::StyleChange(ref style_change) => {
-                if let Some((move_x, move_y)) = style_change.move_to {
-                    *x = move_x;
-                    *y = move_y;
-                }
-            }
-        }
+    /// Returns the shape to hit test against for the given ratio.
+    /// This matches the shape that is rendered for that ratio.
+    fn hit_test_shape_for_ratio(&self, ratio: u16) -> swf::Shape {
+        interpolate_morph_shape(&self.start, &self.end, quantize_ratio(ratio))
     }
 }
 
@@ -286,214 +160,3 @@ unsafe impl<'gc> gc_arena::Collect for MorphShapeStatic {
         false
     }
 }
-
-// Interpolation functions
-// These interpolate between two SWF shape structures.
-// a + b should = 1.0
-
-fn lerp_color(start: &Color, end: &Color, a: f32, b: f32) -> Color {
-    // f32 -> u8 cast is defined to saturate for out of bounds values,
-    // so we don't have to worry about clamping.
-    Color {
-        r: (a * f32::from(start.r) + b * f32::from(end.r)) as u8,
-        g: (a * f32::from(start.g) + b * f32::from(end.g)) as u8,
-        b: (a * f32::from(start.b) + b * f32::from(end.b)) as u8,
-        a: (a * f32::from(start.a) + b * f32::from(end.a)) as u8,
-    }
-}
-
-fn lerp_twips(start: Twips, end: Twips, a: f32, b: f32) -> Twips {
-    Twips::new((start.get() as f32 * a + end.get() as f32 * b) as i32)
-}
-
-fn lerp_fill(start: &swf::FillStyle, end: &swf::FillStyle, a: f32, b: f32) -> swf::FillStyle {
-    use swf::FillStyle;
-    match (start, end) {
-        // Color-to-color
-        (FillStyle::Color(start), FillStyle::Color(end)) => {
-            FillStyle::Color(lerp_color(start, end, a, b))
-        }
-
-        // Bitmap-to-bitmap
-        // ID should be the same.
-        (
-            FillStyle::Bitmap {
-                id: start_id,
-                matrix: start,
-                is_smoothed,
-                is_repeating,
-            },
-            FillStyle::Bitmap { matrix: end, .. },
-        ) => FillStyle::Bitmap {
-            id: *start_id,
-            matrix: lerp_matrix(start, end, a, b),
-            is_smoothed: *is_smoothed,
-            is_repeating: *is_repeating,
-        },
-
-        // Linear-to-linear
-        (FillStyle::LinearGradient(start), FillStyle::LinearGradient(end)) => {
-            FillStyle::LinearGradient(lerp_gradient(start, end, a, b))
-        }
-
-        // Radial-to-radial
-        (FillStyle::RadialGradient(start), FillStyle::RadialGradient(end)) => {
-            FillStyle::RadialGradient(lerp_gradient(start, end, a, b))
-        }
-
-        // Focal gradients also interpolate focal point.
-        (
-            FillStyle::FocalGradient {
-                gradient: start,
-                focal_point: start_focal,
-            },
-            FillStyle::FocalGradient {
-                gradient: end,
-                focal_point: end_focal,
-            },
-        ) => FillStyle::FocalGradient {
-            gradient: lerp_gradient(start, end, a, b),
-            focal_point: a * start_focal + b * end_focal,
-        },
-
-        // All other combinations should not occur, because SWF stores the start/end fill as the same type, always.
-        // If you happened to make, say, a solid color-to-radial gradient tween in the IDE, this would get baked down into
-        // a radial-to-radial gradient on export.
-        _ => {
-            log::warn!(
-                "Unexpected morph shape fill style combination: {:#?}, {:#?}",
-                start,
-                end
-            );
-            start.clone()
-        }
-    }
-}
-
-fn lerp_edges(
-    start: &swf::ShapeRecord,
-    end: &swf::ShapeRecord,
-    a: f32,
-    b: f32,
-) -> swf::ShapeRecord {
-    use swf::ShapeRecord;
-    match (start, end) {
-        (
-            &ShapeRecord::StraightEdge {
-                delta_x: start_dx,
-                delta_y: start_dy,
-            },
-            &ShapeRecord::StraightEdge {
-                delta_x: end_dx,
-                delta_y: end_dy,
-            },
-        ) => ShapeRecord::StraightEdge {
-            delta_x: lerp_twips(start_dx, end_dx, a, b),
-            delta_y: lerp_twips(start_dy, end_dy, a, b),
-        },
-
-        (
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: start_cdx,
-                control_delta_y: start_cdy,
-                anchor_delta_x: start_adx,
-                anchor_delta_y: start_ady,
-            },
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: end_cdx,
-                control_delta_y: end_cdy,
-                anchor_delta_x: end_adx,
-                anchor_delta_y: end_ady,
-            },
-        ) => ShapeRecord::CurvedEdge {
-            control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-            control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-            anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-            anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
-        },
-
-        (
-            &ShapeRecord::StraightEdge {
-                delta_x: start_dx,
-                delta_y: start_dy,
-            },
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: end_cdx,
-                control_delta_y: end_cdy,
-                anchor_delta_x: end_adx,
-                anchor_delta_y: end_ady,
-            },
-        ) => {
-            let start_cdx = start_dx / 2;
-            let start_cdy = start_dy / 2;
-            let start_adx = start_cdx;
-            let start_ady = start_cdy;
-            ShapeRecord::CurvedEdge {
-                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
-            }
-        }
-
-        (
-            &ShapeRecord::CurvedEdge {
-                control_delta_x: start_cdx,
-                control_delta_y: start_cdy,
-                anchor_delta_x: start_adx,
-                anchor_delta_y: start_ady,
-            },
-            &ShapeRecord::StraightEdge {
-                delta_x: end_dx,
-                delta_y: end_dy,
-            },
-        ) => {
-            let end_cdx = end_dx / 2;
-            let end_cdy = end_dy / 2;
-            let end_adx = end_cdx;
-            let end_ady = end_cdy;
-            ShapeRecord::CurvedEdge {
-                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
-                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
-                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
-                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
-            }
-        }
-        _ => unreachable!("{:?} {:?}", start, end),
-    }
-}
-
-fn lerp_matrix(start: &swf::Matrix, end: &swf::Matrix, a: f32, b: f32) -> swf::Matrix {
-    // TODO: Lerping a matrix element-wise is geometrically wrong,
-    // but I doubt Flash is decomposing the matrix into scale-rotate-translate?
-    swf::Matrix {
-        a: start.a * a + end.a * b,
-        b: start.b * a + end.b * b,
-        c: start.c * a + end.c * b,
-        d: start.d * a + end.d * b,
-        tx: lerp_twips(start.tx, end.tx, a, b),
-        ty: lerp_twips(start.ty, end.ty, a, b),
-    }
-}
-
-fn lerp_gradient(start: &swf::Gradient, end: &swf::Gradient, a: f32, b: f32) -> swf::Gradient {
-    use swf::{Gradient, GradientRecord};
-    // Morph gradients are guaranteed to have the same number of records in the start/end gradient.
-    debug_assert!(start.records.len() == end.records.len());
-    let records: Vec<GradientRecord> = start
-        .records
-        .iter()
-        .zip(end.records.iter())
-        .map(|(start, end)| swf::GradientRecord {
-            ratio: (f32::from(start.ratio) * a + f32::from(end.ratio) * b) as u8,
-            color: lerp_color(&start.color, &end.color, a, b),
-        })
-        .collect();
-
-    Gradient {
-        matrix: lerp_matrix(&start.matrix, &end.matrix, a, b),
-        spread: start.spread,
-        interpolation: start.interpolation,
-        records,
-    }
-}
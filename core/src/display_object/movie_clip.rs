@@ -1,18 +1,20 @@
 //! `MovieClip` display object and support code.
 use crate::avm1::{Avm1, Object, StageObject, TObject, Value};
+use crate::avm2::Object as Avm2Object;
 use crate::backend::audio::AudioStreamHandle;
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::character::Character;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::{
-    Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
+    Avm2MousePick, Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic,
+    TDisplayObject, Text, Video,
 };
-use crate::drawing::Drawing;
+use crate::drawing::{Drawing, GraphicsData, PathCommand, TriangleCulling};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::font::Font;
 use crate::prelude::*;
-use crate::shape_utils::DrawCommand;
+use crate::shape_utils::{DrawCommand, FillRule};
 use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
 use enumset::{EnumSet, EnumSetType};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
@@ -49,6 +51,13 @@ pub struct MovieClipData<'gc> {
     flags: EnumSet<MovieClipFlags>,
     avm1_constructor: Option<Object<'gc>>,
     drawing: Drawing,
+
+    /// The AVM2 functions registered with `addFrameScript`, by frame.
+    frame_scripts: Vec<(FrameNumber, Avm2Object<'gc>)>,
+
+    /// The frame whose AVM2 frame script should run in the next frame script
+    /// phase, if the playhead has entered one since the last.
+    queued_script_frame: Option<FrameNumber>,
 }
 
 impl<'gc> MovieClip<'gc> {
@@ -69,6 +78,8 @@ impl<'gc> MovieClip<'gc> {
                 flags: EnumSet::empty(),
                 avm1_constructor: None,
                 drawing: Drawing::new(),
+                frame_scripts: Vec::new(),
+                queued_script_frame: None,
             },
         ))
     }
@@ -91,6 +102,8 @@ impl<'gc> MovieClip<'gc> {
                         total_frames: num_frames,
                         audio_stream_info: None,
                         frame_labels: HashMap::new(),
+                        ordered_frame_labels: Vec::new(),
+                        scene_labels: Vec::new(),
                     },
                 ),
                 tag_stream_pos: 0,
@@ -103,6 +116,8 @@ impl<'gc> MovieClip<'gc> {
                 flags: MovieClipFlags::Playing.into(),
                 avm1_constructor: None,
                 drawing: Drawing::new(),
+                frame_scripts: Vec::new(),
+                queued_script_frame: None,
             },
         ))
     }
@@ -132,6 +147,32 @@ impl<'gc> MovieClip<'gc> {
             .replace_with_movie(gc_context, movie)
     }
 
+    /// Unload the content of this clip, as with `unloadMovie`, leaving it empty.
+    ///
+    /// If a movie was loaded into this clip, everything that its code left running is stopped
+    /// as well: its sounds are silenced and its intervals and timeouts are cleared.
+    pub fn unload_movie(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // Clips that are part of their parent's timeline share its movie, which keeps running.
+        let loaded_movie = self.movie().filter(|movie| {
+            self.parent()
+                .and_then(|parent| parent.movie())
+                .map_or(true, |parent_movie| !Arc::ptr_eq(&parent_movie, movie))
+        });
+
+        self.unload(context);
+        context
+            .audio_manager
+            .stop_sounds_with_display_object(context.audio, (*self).into());
+        if let Some(movie) = loaded_movie {
+            context
+                .audio_manager
+                .stop_sounds_with_movie(context.audio, &movie);
+            context.timers.remove_movie_timers(&movie);
+        }
+
+        self.replace_with_movie(context.gc_context, None);
+    }
+
     pub fn preload(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -215,7 +256,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_font_3(context, reader),
-                TagCode::DefineFont4 => unimplemented!(),
+                TagCode::DefineFont4 => self
+                    .0
+                    .write(context.gc_context)
+                    .define_font_4(context, reader),
                 TagCode::DefineMorphShape => self.0.write(context.gc_context).define_morph_shape(
                     context,
                     reader,
@@ -244,6 +288,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_shape(context, reader, 4),
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::DefineSound => self
                     .0
                     .write(context.gc_context)
@@ -262,8 +310,17 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .define_text(context, reader, 2),
+                TagCode::DefineVideoStream => self
+                    .0
+                    .write(context.gc_context)
+                    .define_video_stream(context, reader),
+                TagCode::DefineSceneAndFrameLabelData => self
+                    .0
+                    .write(context.gc_context)
+                    .scene_and_frame_labels(reader, &mut static_data),
                 TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
                 TagCode::DoAbc => self.do_abc(context, reader, tag_len),
+                TagCode::SymbolClass => self.symbol_class(context, reader),
                 TagCode::ExportAssets => self
                     .0
                     .write(context.gc_context)
@@ -319,6 +376,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .preload_remove_object(context, reader, &mut ids, 2),
+                TagCode::VideoFrame => self
+                    .0
+                    .write(context.gc_context)
+                    .preload_video_frame(context, reader),
                 TagCode::ShowFrame => self.0.write(context.gc_context).preload_show_frame(
                     context,
                     reader,
@@ -385,12 +446,33 @@ impl<'gc> MovieClip<'gc> {
                 )
             })?;
 
-        Avm1::run_stack_frame_for_init_action(
-            self.into(),
-            context.swf.header().version,
-            slice,
-            context,
-        );
+        Avm1::run_stack_frame_for_init_action(self.into(), self.swf_version(), slice, context);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn symbol_class(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&[u8]>,
+    ) -> DecodeResult {
+        let num_symbols = reader.read_u16()?;
+        for _ in 0..num_symbols {
+            let id = reader.read_u16()?;
+            let class_name = reader.read_c_string()?;
+
+            // Character 0 is the main timeline, whose class is the movie's
+            // document class. It is constructed once the frame's ABC has run.
+            // TODO: Classes linked to library symbols are not used yet.
+            if id == 0 {
+                context.action_queue.queue_actions(
+                    self.into(),
+                    ActionType::ConstructDocumentClass { class_name },
+                    false,
+                );
+            }
+        }
 
         Ok(())
     }
@@ -411,12 +493,13 @@ impl<'gc> MovieClip<'gc> {
         let is_lazy_initialize = flags & 1 != 0;
 
         // The rest of the tag is an ABC file so we can take our SwfSlice now.
+        let abc_len = tag_len.saturating_sub(reader.get_ref().position() as usize);
         let slice = self
             .0
             .read()
             .static_data
             .swf
-            .resize_to_reader(reader, tag_len)
+            .resize_to_reader(reader, abc_len)
             .ok_or_else(|| {
                 std::io::Error::new(
                     std::io::ErrorKind::Other,
@@ -435,6 +518,24 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
+    /// Whether this movie clip acts as a button, which is the case if it has a mouse handler,
+    /// either via on(..) or via property mc.onRelease, etc.
+    pub fn is_button_mode(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
+        if self.0.read().has_button_clip_event {
+            true
+        } else {
+            let mut activation = Activation::from_stub(
+                context.reborrow(),
+                ActivationIdentifier::root("[Button Mode]"),
+            );
+            let object = self.object().coerce_to_object(&mut activation);
+
+            ClipEvent::BUTTON_EVENT_METHODS
+                .iter()
+                .any(|handler| object.has_property(&mut activation, handler))
+        }
+    }
+
     #[allow(dead_code)]
     pub fn playing(self) -> bool {
         self.0.read().playing()
@@ -498,6 +599,27 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().static_data.total_frames
     }
 
+    /// The size of this clip's data in bytes.
+    ///
+    /// For the root clip of a movie, this is the uncompressed size of the SWF
+    /// file. For clips defined in a `DefineSprite` tag, it is the size of the
+    /// sprite's tag data.
+    pub fn total_bytes(self) -> usize {
+        let mc = self.0.read();
+        let movie = mc.movie();
+        if mc.tag_stream_len() == movie.data().len() {
+            movie.uncompressed_len()
+        } else {
+            mc.tag_stream_len()
+        }
+    }
+
+    /// The number of bytes of this clip's data that have loaded.
+    pub fn loaded_bytes(self) -> usize {
+        // Movies are currently loaded in full before they start playing.
+        self.total_bytes()
+    }
+
     pub fn set_avm1_constructor(
         self,
         gc_context: MutationContext<'gc, '_>,
@@ -506,12 +628,170 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(gc_context).avm1_constructor = prototype;
     }
 
+    /// Register the AVM2 function to run when the playhead enters a frame,
+    /// replacing any that was there. `None` removes the frame's script.
+    /// `frame` should be 1-based.
+    pub fn set_frame_script(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        frame: FrameNumber,
+        script: Option<Avm2Object<'gc>>,
+    ) {
+        let mut mc = self.0.write(gc_context);
+        mc.frame_scripts
+            .retain(|(script_frame, _)| *script_frame != frame);
+        if let Some(script) = script {
+            mc.frame_scripts.push((frame, script));
+        }
+    }
+
+    /// Take the AVM2 frame script that is waiting to run, because the
+    /// playhead entered its frame since the last frame script phase.
+    pub fn take_queued_frame_script(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Option<Avm2Object<'gc>> {
+        let mut mc = self.0.write(gc_context);
+        let frame = mc.queued_script_frame.take()?;
+        mc.frame_scripts
+            .iter()
+            .find(|(script_frame, _)| *script_frame == frame)
+            .map(|(_, script)| *script)
+    }
+
     pub fn frame_label_to_number(self, frame_label: &str) -> Option<FrameNumber> {
+        // A label reused across scenes refers to the one in the current scene.
+        if let Some(scene) = self.current_scene() {
+            if let Some((_, frame)) = self
+                .labels_in_scene(&scene)
+                .into_iter()
+                .find(|(label, _)| label.eq_ignore_ascii_case(frame_label))
+            {
+                return Some(frame);
+            }
+        }
+
         // Frame labels are case insensitive.
         let label = frame_label.to_ascii_lowercase();
         self.0.read().static_data.frame_labels.get(&label).copied()
     }
 
+    /// Returns the scenes of this clip's timeline.
+    ///
+    /// A timeline without any scene data consists of a single scene named
+    /// `Scene 1`.
+    pub fn scenes(self) -> Vec<Scene> {
+        let read = self.0.read();
+        let total_frames = read.static_data.total_frames;
+        let scene_labels = &read.static_data.scene_labels;
+
+        if scene_labels.is_empty() {
+            return vec![Scene {
+                name: "Scene 1".to_string(),
+                start: 1,
+                length: total_frames,
+            }];
+        }
+
+        scene_labels
+            .iter()
+            .enumerate()
+            .map(|(i, (name, start))| {
+                let end = scene_labels
+                    .get(i + 1)
+                    .map(|(_, next)| *next)
+                    .unwrap_or_else(|| total_frames.saturating_add(1));
+                Scene {
+                    name: name.clone(),
+                    start: *start,
+                    length: end.saturating_sub(*start),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the scene containing the given frame.
+    pub fn scene_for_frame(self, frame: FrameNumber) -> Option<Scene> {
+        self.scenes()
+            .into_iter()
+            .rev()
+            .find(|scene| scene.start <= frame)
+    }
+
+    /// Returns the scene containing the playhead.
+    pub fn current_scene(self) -> Option<Scene> {
+        self.scene_for_frame(self.current_frame().max(1))
+    }
+
+    /// Returns the scene with the given name.
+    pub fn scene_by_name(self, name: &str) -> Option<Scene> {
+        self.scenes().into_iter().find(|scene| scene.name == name)
+    }
+
+    /// Returns the frame labels within the given scene, in frame order.
+    pub fn labels_in_scene(self, scene: &Scene) -> Vec<(String, FrameNumber)> {
+        self.0
+            .read()
+            .static_data
+            .ordered_frame_labels
+            .iter()
+            .filter(|(_, frame)| scene.contains(*frame))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the frame labels within the current scene, in frame order.
+    pub fn current_labels(self) -> Vec<(String, FrameNumber)> {
+        self.current_scene()
+            .map(|scene| self.labels_in_scene(&scene))
+            .unwrap_or_default()
+    }
+
+    /// Returns the label on the current frame, if it has one.
+    pub fn current_frame_label(self) -> Option<String> {
+        let current_frame = self.current_frame();
+        self.0
+            .read()
+            .static_data
+            .ordered_frame_labels
+            .iter()
+            .find(|(_, frame)| *frame == current_frame)
+            .map(|(label, _)| label.clone())
+    }
+
+    /// Returns the most recent label at or before the current frame, within
+    /// the current scene.
+    pub fn current_label(self) -> Option<String> {
+        let current_frame = self.current_frame();
+        self.current_labels()
+            .into_iter()
+            .rev()
+            .find(|(_, frame)| *frame <= current_frame)
+            .map(|(label, _)| label)
+    }
+
+    /// Resolve a frame label within the named scene.
+    ///
+    /// Unlike `frame_label_to_number`, this finds the right frame when the
+    /// same label is used in several scenes.
+    pub fn scene_label_to_number(self, scene: &str, frame_label: &str) -> Option<FrameNumber> {
+        let scene = self.scene_by_name(scene)?;
+        self.labels_in_scene(&scene)
+            .into_iter()
+            .find(|(label, _)| label.eq_ignore_ascii_case(frame_label))
+            .map(|(_, frame)| frame)
+    }
+
+    /// Resolve a frame number relative to the start of the named scene.
+    pub fn scene_frame_to_number(self, scene: &str, frame: FrameNumber) -> Option<FrameNumber> {
+        let scene = self.scene_by_name(scene)?;
+        if frame >= 1 && frame <= scene.length {
+            Some(scene.start + frame - 1)
+        } else {
+            None
+        }
+    }
+
     /// Returns the highest depth in use by this movie clip, or `None` if there are no children.
     pub fn highest_depth(self) -> Option<Depth> {
         self.0.read().children.keys().copied().rev().next()
@@ -531,6 +811,21 @@ impl<'gc> MovieClip<'gc> {
         mc.set_clip_actions(actions);
     }
 
+    /// The children of this clip in render order, back to front.
+    ///
+    /// This is the order that AVM2 child indices refer to.
+    pub fn render_list(self) -> Vec<DisplayObject<'gc>> {
+        self.0.read().children.values().copied().collect()
+    }
+
+    /// Tests if a given stage position point is on the clip's own vector
+    /// drawing, not counting its children.
+    pub fn hit_test_drawing(self, point: (Twips, Twips)) -> bool {
+        let local_matrix = self.global_to_local_matrix();
+        let point = local_matrix * point;
+        self.0.read().drawing.hit_test(point, &local_matrix)
+    }
+
     /// Adds a script-created display object as a child to this clip.
     pub fn add_child_from_avm(
         &mut self,
@@ -639,11 +934,13 @@ impl<'gc> MovieClip<'gc> {
     ) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.set_fill_style(style);
+        mc.base.set_content_changed();
     }
 
     pub fn clear(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.clear();
+        mc.base.set_content_changed();
     }
 
     pub fn set_line_style(
@@ -653,11 +950,82 @@ impl<'gc> MovieClip<'gc> {
     ) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.set_line_style(style);
+        mc.base.set_content_changed();
+    }
+
+    pub fn set_line_fill_style(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        fill_style: Option<FillStyle>,
+    ) {
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing.set_line_fill_style(fill_style);
+        mc.base.set_content_changed();
+    }
+
+    /// Replaces the Drawing API contents of this clip with a copy of another clip's.
+    pub fn copy_drawing(self, context: &mut UpdateContext<'_, 'gc, '_>, source: MovieClip<'gc>) {
+        let drawing = source.0.read().drawing.duplicate();
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing = drawing;
+        mc.base.set_content_changed();
     }
 
     pub fn draw_command(self, context: &mut UpdateContext<'_, 'gc, '_>, command: DrawCommand) {
         let mut mc = self.0.write(context.gc_context);
         mc.drawing.draw_command(command);
+        mc.base.set_content_changed();
+    }
+
+    pub fn draw_path(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        commands: &[PathCommand],
+        data: &[f64],
+        fill_rule: FillRule,
+    ) {
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing.draw_path(commands, data, fill_rule);
+        mc.base.set_content_changed();
+    }
+
+    pub fn draw_triangles(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        vertices: &[f64],
+        indices: Option<&[u32]>,
+        uvt_data: Option<&[f64]>,
+        culling: TriangleCulling,
+    ) {
+        let library = self
+            .movie()
+            .and_then(|movie| context.library.library_for_movie(movie));
+        let bitmap_dimensions = |id| match library?.get_character_by_id(id) {
+            Some(Character::Bitmap(bitmap)) => Some((
+                u32::from(Bitmap::width(*bitmap)),
+                u32::from(Bitmap::height(*bitmap)),
+            )),
+            _ => None,
+        };
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing
+            .draw_triangles(vertices, indices, uvt_data, culling, bitmap_dimensions);
+        mc.base.set_content_changed();
+    }
+
+    /// The Drawing API contents of this clip, as read by `readGraphicsData`.
+    pub fn graphics_data(self) -> Vec<GraphicsData> {
+        self.0.read().drawing.graphics_data()
+    }
+
+    pub fn draw_graphics_data(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        data: &[GraphicsData],
+    ) {
+        let mut mc = self.0.write(context.gc_context);
+        mc.drawing.draw_graphics_data(data);
+        mc.base.set_content_changed();
     }
 
     pub fn run_clip_event(
@@ -678,7 +1046,9 @@ impl<'gc> MovieClip<'gc> {
     ) {
         // Advance frame number.
         if self.current_frame() < self.total_frames() {
-            self.0.write(context.gc_context).current_frame += 1;
+            let mut mc = self.0.write(context.gc_context);
+            mc.current_frame += 1;
+            mc.queued_script_frame = Some(mc.current_frame);
         } else if self.total_frames() > 1 {
             // Looping acts exactly like a gotoAndPlay(1).
             // Specifically, object that existed on frame 1 should not be destroyed
@@ -821,28 +1191,6 @@ impl<'gc> MovieClip<'gc> {
             // when rewinding.
             self.0.write(context.gc_context).tag_stream_pos = 0;
             self.0.write(context.gc_context).current_frame = 0;
-
-            // Remove all display objects that were created after the desination frame.
-            // TODO: We want to do something like self.children.retain here,
-            // but BTreeMap::retain does not exist.
-            let children: SmallVec<[_; 16]> = self
-                .0
-                .read()
-                .children
-                .iter()
-                .filter_map(|(depth, clip)| {
-                    if clip.place_frame() > frame {
-                        Some((*depth, *clip))
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-            for (depth, child) in children {
-                let mut mc = self.0.write(context.gc_context);
-                mc.children.remove(&depth);
-                mc.remove_child_from_exec_list(context, child);
-            }
             true
         } else {
             false
@@ -856,13 +1204,8 @@ impl<'gc> MovieClip<'gc> {
         let mut index = 0;
 
         let len = mc.tag_stream_len() as u64;
-        // Sanity; let's make sure we don't seek way too far.
-        // TODO: This should be self.frames_loaded() when we implement that.
-        let clamped_frame = if frame <= mc.total_frames() {
-            frame
-        } else {
-            mc.total_frames()
-        };
+        // Sanity; let's make sure we don't seek past the frames that have loaded.
+        let clamped_frame = frame.min(self.frames_loaded());
         drop(mc);
 
         while self.current_frame() < clamped_frame && frame_pos < len {
@@ -935,6 +1278,41 @@ impl<'gc> MovieClip<'gc> {
         }
         let hit_target_frame = self.0.read().current_frame == frame;
 
+        if is_rewind {
+            // A rewind conceptually rebuilds the display list from an empty one,
+            // so diff the display list of the target frame against the current
+            // children. A child only persists if the same placement of the same
+            // character is still at its depth on the target frame; everything
+            // else placed by the timeline is removed. Objects created by
+            // ActionScript (place frame 0) are left alone.
+            // TODO: We want to do something like self.children.retain here,
+            // but BTreeMap::retain does not exist.
+            let children: SmallVec<[_; 16]> = self
+                .0
+                .read()
+                .children
+                .iter()
+                .filter_map(|(depth, clip)| {
+                    let persists = clip.place_frame() == 0
+                        || goto_commands.iter().any(|params| {
+                            params.depth() == *depth
+                                && params.id() == clip.id()
+                                && params.frame == clip.place_frame()
+                        });
+                    if persists {
+                        None
+                    } else {
+                        Some((*depth, *clip))
+                    }
+                })
+                .collect();
+            for (depth, child) in children {
+                let mut mc = self.0.write(context.gc_context);
+                mc.children.remove(&depth);
+                mc.remove_child_from_exec_list(context, child);
+            }
+        }
+
         // Run the list of goto commands to actually create and update the display objects.
         let run_goto_command = |clip: MovieClip<'gc>,
                                 context: &mut UpdateContext<'_, 'gc, '_>,
@@ -944,11 +1322,14 @@ impl<'gc> MovieClip<'gc> {
                 // Apply final delta to display pamareters.
                 // For rewinds, if an object was created before the final frame,
                 // it will exist on the final frame as well. Re-use this object
-                // instead of recreating.
+                // instead of recreating, so it keeps its own state (such as its
+                // current frame).
                 // If the ID is 0, we are modifying a previous child. Otherwise, we're replacing it.
-                // If it's a rewind, we removed any dead children above, so we always
-                // modify the previous child.
-                Some(prev_child) if params.id() == 0 || is_rewind => {
+                // If it's a rewind, we removed any children that don't match the
+                // target frame above, so any remaining timeline child is modified.
+                Some(prev_child)
+                    if params.id() == 0 || (is_rewind && prev_child.place_frame() != 0) =>
+                {
                     prev_child.apply_place_object(context.gc_context, &params.place_object);
                 }
                 _ => {
@@ -990,7 +1371,9 @@ impl<'gc> MovieClip<'gc> {
             self.0.write(context.gc_context).tag_stream_pos = frame_pos;
             self.run_frame_internal(self_display_object, context, false);
         } else {
-            self.0.write(context.gc_context).current_frame = clamped_frame;
+            let mut mc = self.0.write(context.gc_context);
+            mc.current_frame = clamped_frame;
+            mc.queued_script_frame = Some(clamped_frame);
         }
 
         // Finally, run frames for children that are placed on this frame.
@@ -1047,11 +1430,19 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         }
     }
 
-    fn render(&self, context: &mut RenderContext<'_, 'gc>) {
-        context.transform_stack.push(&*self.transform());
-        crate::display_object::render_children(context, &self.0.read().children);
-        self.0.read().drawing.render(context);
-        context.transform_stack.pop();
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
+        let bounds = self.bounds();
+        let matrix = *self.matrix();
+        let read = self.0.read();
+        let slices = read
+            .base
+            .scaling_grid()
+            .and_then(|grid| grid.slices(&matrix, &bounds));
+        crate::display_object::render_children(context, &read.children, slices.as_ref());
+        match slices {
+            Some(slices) => slices.render(context, |context| read.drawing.render(context)),
+            None => read.drawing.render(context),
+        }
     }
 
     fn self_bounds(&self) -> BoundingBox {
@@ -1070,9 +1461,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
-            let local_matrix = self.global_to_local_matrix();
-            let point = local_matrix * point;
-            if self.0.read().drawing.hit_test(point, &local_matrix) {
+            if self.hit_test_drawing(point) {
                 return true;
             }
         }
@@ -1087,25 +1476,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
         if self.visible() && self.world_bounds().contains(point) {
-            // This movieclip operates in "button mode" if it has a mouse handler,
-            // either via on(..) or via property mc.onRelease, etc.
-            let is_button_mode = {
-                if self.0.read().has_button_clip_event {
-                    true
-                } else {
-                    let mut activation = Activation::from_stub(
-                        context.reborrow(),
-                        ActivationIdentifier::root("[Mouse Pick]"),
-                    );
-                    let object = self.object().coerce_to_object(&mut activation);
-
-                    ClipEvent::BUTTON_EVENT_METHODS
-                        .iter()
-                        .any(|handler| object.has_property(&mut activation, handler))
-                }
-            };
-
-            if is_button_mode && self.hit_test_shape(point) {
+            if self.is_button_mode(context) && self.hit_test_shape(point) {
                 return Some(self_node);
             }
 
@@ -1122,6 +1493,34 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         None
     }
 
+    fn mouse_pick_avm2(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Avm2MousePick<'gc> {
+        if !self.visible() {
+            return Avm2MousePick::Miss;
+        }
+
+        // An interactive child under the mouse takes the event, even when a
+        // non-interactive one is drawn above it.
+        let mut propagated = false;
+        for child in self.render_list().into_iter().rev() {
+            let picked = child.mouse_pick_avm2(context, point);
+            match picked {
+                Avm2MousePick::Hit(_) => return picked.combine_with_parent((*self).into()),
+                Avm2MousePick::PropagateToParent => propagated = true,
+                Avm2MousePick::Miss => (),
+            }
+        }
+
+        if propagated || self.hit_test_drawing(point) {
+            Avm2MousePick::PropagateToParent.combine_with_parent((*self).into())
+        } else {
+            Avm2MousePick::Miss
+        }
+    }
+
     fn handle_clip_event(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1153,7 +1552,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.set_default_instance_name(context);
 
         if self.0.read().object.is_none() {
-            let version = context.swf.version();
+            let version = self.swf_version();
             let globals = context.avm1.global_object_cell();
 
             // If we are running within the AVM, this must be an immediate action.
@@ -1189,6 +1588,20 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                     if run_frame {
                         self.run_frame(&mut activation.context);
                     }
+
+                    // Initialize and construct events run before the
+                    // constructor, and can already see the init object's
+                    // properties.
+                    let events = self.0.read().pre_constructor_events();
+                    for event in events {
+                        let _ = activation.run_child_frame_for_action(
+                            "[Actions]",
+                            (*self).into(),
+                            self.swf_version(),
+                            event,
+                        );
+                    }
+
                     let _ = constructor.construct_on_existing(&mut activation, object, &[]);
                 }
 
@@ -1218,15 +1631,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             let mut mc = self.0.write(context.gc_context);
             mc.object = Some(object.into());
 
-            let mut events = Vec::new();
-
-            for clip_action in mc
-                .clip_actions()
-                .iter()
-                .filter(|action| action.event == ClipEvent::Construct)
-            {
-                events.push(clip_action.action_data.clone());
-            }
+            let events = mc.pre_constructor_events();
 
             context.action_queue.queue_actions(
                 display_object,
@@ -1245,7 +1650,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         // If this text field has a variable set, initialize text field binding.
         Avm1::run_with_stack_frame_for_display_object(
             (*self).into(),
-            context.swf.version(),
+            self.swf_version(),
             context,
             |activation| {
                 self.bind_text_field_variables(activation);
@@ -1300,6 +1705,9 @@ unsafe impl<'gc> Collect for MovieClipData<'gc> {
         self.static_data.trace(cc);
         self.object.trace(cc);
         self.avm1_constructor.trace(cc);
+        for (_, script) in self.frame_scripts.iter() {
+            script.trace(cc);
+        }
     }
 }
 
@@ -1329,6 +1737,8 @@ impl<'gc> MovieClipData<'gc> {
                 total_frames,
                 audio_stream_info: None,
                 frame_labels: HashMap::new(),
+                ordered_frame_labels: Vec::new(),
+                scene_labels: Vec::new(),
             },
         );
         self.tag_stream_pos = 0;
@@ -1336,6 +1746,8 @@ impl<'gc> MovieClipData<'gc> {
         self.current_frame = 0;
         self.audio_stream = None;
         self.children = BTreeMap::new();
+        self.frame_scripts = Vec::new();
+        self.queued_script_frame = None;
     }
 
     fn id(&self) -> CharacterId {
@@ -1499,8 +1911,7 @@ impl<'gc> MovieClipData<'gc> {
     ) -> ClipEventResult {
         let mut handled = ClipEventResult::NotHandled;
 
-        // TODO: What's the behavior for loaded SWF files?
-        if context.swf.version() >= 5 {
+        if self.movie().version() >= 5 {
             for clip_action in self
                 .clip_actions
                 .iter()
@@ -1521,7 +1932,7 @@ impl<'gc> MovieClipData<'gc> {
 
             // Queue ActionScript-defined event handlers after the SWF defined ones.
             // (e.g., clip.onEnterFrame = foo).
-            if context.swf.version() >= 6 {
+            if self.movie().version() >= 6 {
                 if let Some(name) = event.method_name() {
                     context.action_queue.queue_actions(
                         self_display_object,
@@ -1573,6 +1984,21 @@ impl<'gc> MovieClipData<'gc> {
         self.clip_actions = actions;
     }
 
+    /// The `onClipEvent(initialize)` and `onClipEvent(construct)` handlers,
+    /// in the order they run before the clip's AVM1 constructor.
+    fn pre_constructor_events(&self) -> Vec<SwfSlice> {
+        let events_of = |event| {
+            self.clip_actions
+                .iter()
+                .filter(move |action| action.event == event)
+                .map(|action| action.action_data.clone())
+        };
+
+        events_of(ClipEvent::Initialize)
+            .chain(events_of(ClipEvent::Construct))
+            .collect()
+    }
+
     fn initialized(&self) -> bool {
         self.flags.contains(MovieClipFlags::Initialized)
     }
@@ -1588,7 +2014,9 @@ impl<'gc> MovieClipData<'gc> {
     /// Stops the audio stream if one is playing.
     fn stop_audio_stream(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
         if let Some(audio_stream) = self.audio_stream.take() {
-            context.audio.stop_stream(audio_stream);
+            context
+                .audio_manager
+                .stop_stream(context.audio, audio_stream);
         }
     }
 
@@ -2067,6 +2495,52 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_font_4(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let font = reader.read_define_font_4()?;
+        match Font::from_font4_tag(context.gc_context, context.renderer, &font) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font.id, Character::Font(font_object)),
+            Err(e) => log::warn!("DefineFont4: Unable to load font {}: {}", font.name, e),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let id = reader.read_u16()?;
+        let rect = reader.read_rectangle()?;
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(id)
+        {
+            Some(Character::MovieClip(clip)) => {
+                clip.set_scaling_grid(context.gc_context, Some(rect))
+            }
+            Some(Character::Button(button)) => {
+                button.set_scaling_grid(context.gc_context, Some(rect))
+            }
+            Some(_) => log::warn!(
+                "DefineScalingGrid: Tried to apply on non-sprite/button ID {}",
+                id
+            ),
+            None => log::warn!("DefineScalingGrid: Character ID {} doesn't exist", id),
+        }
+        Ok(())
+    }
+
     #[inline]
     fn define_sound(
         &mut self,
@@ -2088,6 +2562,43 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_video_stream(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let streamdef = reader.read_define_video_stream()?;
+        let id = streamdef.id;
+        let video = Video::from_swf_tag(context.gc_context, streamdef);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::Video(video));
+        Ok(())
+    }
+
+    #[inline]
+    fn preload_video_frame(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<&'a [u8]>,
+    ) -> DecodeResult {
+        let tag = reader.read_video_frame()?;
+        match context
+            .library
+            .library_for_movie_mut(self.movie())
+            .get_character_by_id(tag.stream_id)
+        {
+            Some(Character::Video(video)) => video.preload_swf_frame(context.gc_context, tag),
+            _ => log::warn!(
+                "Attempted to preload video frames into non-video character {}",
+                tag.stream_id
+            ),
+        }
+        Ok(())
+    }
+
     fn define_sprite(
         &mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -2178,6 +2689,9 @@ impl<'gc, 'a> MovieClipData<'gc> {
         static_data: &mut MovieClipStatic,
     ) -> DecodeResult {
         let mut frame_label = reader.read_frame_label(tag_len)?;
+        static_data
+            .ordered_frame_labels
+            .push((frame_label.label.clone(), cur_frame));
         // Frame labels are case insensitive (ASCII).
         frame_label.label.make_ascii_lowercase();
         if let std::collections::hash_map::Entry::Vacant(v) =
@@ -2190,6 +2704,23 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn scene_and_frame_labels(
+        &mut self,
+        reader: &mut SwfStream<&'a [u8]>,
+        static_data: &mut MovieClipStatic,
+    ) -> DecodeResult {
+        let data = reader.read_define_scene_and_frame_label_data()?;
+        // Scene offsets are zero-based, but frame numbers are one-based.
+        static_data.scene_labels = data
+            .scenes
+            .into_iter()
+            .map(|scene| (scene.label, scene.frame_num as FrameNumber + 1))
+            .collect();
+        static_data.scene_labels.sort_by_key(|(_, start)| *start);
+        Ok(())
+    }
+
     #[inline]
     fn jpeg_tables(
         &mut self,
@@ -2352,7 +2883,7 @@ impl<'gc, 'a> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc, '_>,
         _reader: &mut SwfStream<&'a [u8]>,
     ) -> DecodeResult {
-        let mut mc = self.0.write(context.gc_context);
+        let mc = self.0.read();
         if let (Some(stream_info), None) = (&mc.static_data.audio_stream_info, mc.audio_stream) {
             let slice = mc
                 .static_data
@@ -2364,11 +2895,17 @@ impl<'gc, 'a> MovieClip<'gc> {
                         "Invalid slice generated when constructing sound stream block",
                     )
                 })?;
-            let audio_stream =
-                context
-                    .audio
-                    .start_stream(mc.id(), mc.current_frame() + 1, slice, &stream_info);
-            mc.audio_stream = audio_stream.ok();
+            let stream_info = stream_info.clone();
+            let clip_frame = mc.current_frame() + 1;
+            drop(mc);
+            let audio_stream = context.audio_manager.start_stream(
+                context.audio,
+                self.into(),
+                clip_frame,
+                slice,
+                &stream_info,
+            );
+            self.0.write(context.gc_context).audio_stream = audio_stream;
         }
 
         Ok(())
@@ -2391,24 +2928,58 @@ impl<'gc, 'a> MovieClip<'gc> {
             match start_sound.sound_info.event {
                 // "Event" sounds always play, independent of the timeline.
                 SoundEvent::Event => {
-                    let _ = context.audio.start_sound(handle, &start_sound.sound_info);
+                    let _ = context.audio_manager.start_sound(
+                        context.audio,
+                        handle,
+                        &start_sound.sound_info,
+                        Some(self.into()),
+                        self.movie(),
+                    );
                 }
 
                 // "Start" sounds only play if an instance of the same sound is not already playing.
                 SoundEvent::Start => {
                     if !context.audio.is_sound_playing_with_handle(handle) {
-                        let _ = context.audio.start_sound(handle, &start_sound.sound_info);
+                        let _ = context.audio_manager.start_sound(
+                            context.audio,
+                            handle,
+                            &start_sound.sound_info,
+                            Some(self.into()),
+                            self.movie(),
+                        );
                     }
                 }
 
                 // "Stop" stops any active instances of a given sound.
-                SoundEvent::Stop => context.audio.stop_sounds_with_handle(handle),
+                SoundEvent::Stop => context
+                    .audio_manager
+                    .stop_sounds_with_handle(context.audio, handle),
             }
         }
         Ok(())
     }
 }
 
+/// A scene of a movie clip's timeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scene {
+    /// The name of the scene.
+    pub name: String,
+
+    /// The first frame of the scene.
+    pub start: FrameNumber,
+
+    /// The number of frames in the scene.
+    pub length: FrameNumber,
+}
+
+impl Scene {
+    /// Whether the given frame is part of this scene.
+    pub fn contains(&self, frame: FrameNumber) -> bool {
+        frame >= self.start && frame - self.start < self.length
+    }
+}
+
 /// Static data shared between all instances of a movie clip.
 #[allow(dead_code)]
 #[derive(Clone)]
@@ -2416,6 +2987,13 @@ struct MovieClipStatic {
     id: CharacterId,
     swf: SwfSlice,
     frame_labels: HashMap<String, FrameNumber>,
+
+    /// Every frame label in the timeline, in frame order, with its original case.
+    /// Unlike `frame_labels`, this includes labels that are reused in several scenes.
+    ordered_frame_labels: Vec<(String, FrameNumber)>,
+
+    /// The name and first frame of each scene, from `DefineSceneAndFrameLabelData`.
+    scene_labels: Vec<(String, FrameNumber)>,
     audio_stream_info: Option<swf::SoundStreamHead>,
     total_frames: FrameNumber,
 }
@@ -2427,6 +3005,8 @@ impl MovieClipStatic {
             swf,
             total_frames: 1,
             frame_labels: HashMap::new(),
+            ordered_frame_labels: Vec::new(),
+            scene_labels: Vec::new(),
             audio_stream_info: None,
         }
     }
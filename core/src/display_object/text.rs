@@ -1,5 +1,6 @@
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::font::Font;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
@@ -14,6 +15,12 @@ pub struct Text<'gc>(GcCell<'gc, TextData<'gc>>);
 pub struct TextData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: gc_arena::Gc<'gc, TextStatic>,
+
+    /// The fonts used by this text, resolved when it was defined.
+    ///
+    /// Hit testing has no access to the library, so the glyph outlines are
+    /// looked up from here.
+    fonts: fnv::FnvHashMap<CharacterId, Font<'gc>>,
 }
 
 impl<'gc> Text<'gc> {
@@ -22,6 +29,15 @@ impl<'gc> Text<'gc> {
         swf: Arc<SwfMovie>,
         tag: &swf::Text,
     ) -> Self {
+        let mut fonts = fnv::FnvHashMap::default();
+        if let Some(library) = context.library.library_for_movie(swf.clone()) {
+            for font_id in tag.records.iter().filter_map(|block| block.font_id) {
+                if let Some(font) = library.get_font(font_id) {
+                    fonts.insert(font_id, font);
+                }
+            }
+        }
+
         Text(GcCell::allocate(
             context.gc_context,
             TextData {
@@ -36,6 +52,7 @@ impl<'gc> Text<'gc> {
                         text_blocks: tag.records.clone(),
                     },
                 ),
+                fonts,
             },
         ))
     }
@@ -56,9 +73,8 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         // Noop
     }
 
-    fn render(&self, context: &mut RenderContext) {
+    fn render_self(&self, context: &mut RenderContext) {
         let tf = self.0.read();
-        context.transform_stack.push(&*self.transform());
         context.transform_stack.push(&Transform {
             matrix: tf.static_data.text_transform,
             ..Default::default()
@@ -109,12 +125,60 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             }
         }
         context.transform_stack.pop();
-        context.transform_stack.pop();
     }
 
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().static_data.bounds.clone()
     }
+
+    fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
+        if !self.world_bounds().contains(point) {
+            return false;
+        }
+
+        // Transform the point into the space of the text records, then test
+        // it against each glyph using the same layout as `render_self`.
+        let tf = self.0.read();
+        let mut text_matrix = tf.static_data.text_transform;
+        text_matrix.invert();
+        let local_matrix = text_matrix * self.global_to_local_matrix();
+
+        let mut font_id = 0;
+        let mut height = Twips::new(0);
+        let mut glyph_matrix = Matrix::identity();
+        for block in &tf.static_data.text_blocks {
+            if let Some(x) = block.x_offset {
+                glyph_matrix.tx = x;
+            }
+            if let Some(y) = block.y_offset {
+                glyph_matrix.ty = y;
+            }
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(font) = tf.fonts.get(&font_id) {
+                let scale = (height.get() as f32) / font.scale();
+                glyph_matrix.a = scale;
+                glyph_matrix.d = scale;
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        let mut glyph_local_matrix = glyph_matrix;
+                        glyph_local_matrix.invert();
+                        let glyph_local_matrix = glyph_local_matrix * local_matrix;
+                        if crate::shape_utils::shape_hit_test(
+                            &glyph.outline,
+                            glyph_local_matrix * point,
+                            &glyph_local_matrix,
+                        ) {
+                            return true;
+                        }
+                        glyph_matrix.tx += Twips::new(c.advance);
+                    }
+                }
+            }
+        }
+
+        false
+    }
 }
 
 unsafe impl<'gc> gc_arena::Collect for TextData<'gc> {
@@ -122,6 +186,9 @@ unsafe impl<'gc> gc_arena::Collect for TextData<'gc> {
     fn trace(&self, cc: gc_arena::CollectionContext) {
         self.base.trace(cc);
         self.static_data.trace(cc);
+        for font in self.fonts.values() {
+            font.trace(cc);
+        }
     }
 }
 
@@ -0,0 +1,375 @@
+//! Video display object
+
+use crate::avm1::{Object as Avm1Object, StageObject, Value as Avm1Value};
+use crate::backend::render::BitmapInfo;
+use crate::backend::video::{frame_dependency, EncodedFrame, VideoStreamHandle};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, DisplayObjectBase, TDisplayObject};
+use crate::prelude::*;
+use crate::transform::Transform;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::{BTreeMap, BTreeSet};
+use swf::{DefineVideoStream, Matrix};
+
+/// A Video display object plays a video stream embedded in the SWF.
+///
+/// The frames of the stream are stored in `VideoFrame` tags, and the timeline selects which frame
+/// to show using the ratio of the `PlaceObject` tag. Frames are decoded when the video is drawn.
+///
+/// Alternatively, a `NetStream` can be attached to show the frames of an external video file
+/// instead. These are decoded by the stream and pushed to the video as playback advances.
+#[derive(Clone, Debug, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Video<'gc>(GcCell<'gc, VideoData<'gc>>);
+
+#[derive(Clone, Debug)]
+pub struct VideoData<'gc> {
+    base: DisplayObjectBase<'gc>,
+    object: Option<Avm1Object<'gc>>,
+
+    /// The video stream definition and its frames, shared between all instances.
+    /// Frames are added as the `VideoFrame` tags are preloaded.
+    source: GcCell<'gc, VideoSource>,
+
+    /// The decoder of this instance, created when the first frame is decoded.
+    /// `Err` if the video backend can't decode this stream.
+    stream: Option<Result<VideoStreamHandle, ()>>,
+
+    /// The frame that should be shown, set by the ratio of the `PlaceObject` tag.
+    target_frame: u16,
+
+    /// The last frame that was decoded.
+    decoded_frame: Option<u16>,
+
+    /// The bitmap of the last decoded frame.
+    bitmap: Option<BitmapInfo>,
+
+    /// Whether a `NetStream` is attached, replacing the frames of the embedded stream.
+    stream_attached: bool,
+
+    /// Whether the video should be smoothed when scaled.
+    /// Stored for `Video.smoothing`, but not used when rendering yet.
+    smoothing: bool,
+
+    /// The deblocking filter to apply, as set by `Video.deblocking`.
+    /// Stored, but not passed to the decoder yet.
+    deblocking: i32,
+}
+
+impl<'gc> Video<'gc> {
+    pub fn from_swf_tag(
+        gc_context: MutationContext<'gc, '_>,
+        streamdef: DefineVideoStream,
+    ) -> Self {
+        Video(GcCell::allocate(
+            gc_context,
+            VideoData {
+                base: Default::default(),
+                source: GcCell::allocate(
+                    gc_context,
+                    VideoSource {
+                        streamdef,
+                        frames: BTreeMap::new(),
+                        keyframes: BTreeSet::new(),
+                    },
+                ),
+                object: None,
+                stream: None,
+                target_frame: 0,
+                decoded_frame: None,
+                bitmap: None,
+                stream_attached: false,
+                smoothing: false,
+                deblocking: 0,
+            },
+        ))
+    }
+
+    /// Stores the data of a `VideoFrame` tag for this video stream.
+    pub fn preload_swf_frame(self, gc_context: MutationContext<'gc, '_>, tag: swf::VideoFrame) {
+        let read = self.0.read();
+        let mut source = read.source.write(gc_context);
+        let dependency = frame_dependency(EncodedFrame {
+            codec: source.streamdef.codec,
+            data: &tag.data,
+            frame_id: tag.frame_num,
+        });
+        if dependency.is_keyframe() {
+            source.keyframes.insert(tag.frame_num);
+        }
+        source.frames.insert(tag.frame_num, tag.data);
+    }
+
+    /// The frame of the video stream that is shown.
+    pub fn frame(self) -> u16 {
+        self.0.read().target_frame
+    }
+
+    /// Shows a different frame of the video stream.
+    /// The frame is decoded the next time the video is drawn.
+    pub fn seek(self, gc_context: MutationContext<'gc, '_>, frame: u16) {
+        let mut write = self.0.write(gc_context);
+        if write.target_frame != frame {
+            write.target_frame = frame;
+            write.base.set_content_changed();
+        }
+    }
+
+    /// Shows the frames of a `NetStream` instead of the embedded video stream, or goes back to
+    /// the embedded stream.
+    ///
+    /// This is called by `NetStreams::attach_video`, which keeps track of the attached videos.
+    pub fn attach_stream(self, gc_context: MutationContext<'gc, '_>, attached: bool) {
+        let mut write = self.0.write(gc_context);
+        write.stream_attached = attached;
+        write.bitmap = None;
+        write.decoded_frame = None;
+        write.base.set_content_changed();
+    }
+
+    /// Shows a frame decoded by the attached `NetStream`.
+    pub fn show_stream_frame(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        bitmap: Option<BitmapInfo>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if write.stream_attached {
+            write.bitmap = bitmap;
+            write.base.set_content_changed();
+        }
+    }
+
+    /// Clears the frame that is shown, until the next frame is decoded.
+    pub fn clear(self, gc_context: MutationContext<'gc, '_>) {
+        let mut write = self.0.write(gc_context);
+        write.bitmap = None;
+        write.base.set_content_changed();
+    }
+
+    /// The size of the frame that is shown, in pixels.
+    pub fn frame_size(self) -> (u32, u32) {
+        self.0
+            .read()
+            .bitmap
+            .map_or((0, 0), |bitmap| (bitmap.width.into(), bitmap.height.into()))
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, gc_context: MutationContext<'gc, '_>, smoothing: bool) {
+        self.0.write(gc_context).smoothing = smoothing;
+    }
+
+    pub fn deblocking(self) -> i32 {
+        self.0.read().deblocking
+    }
+
+    pub fn set_deblocking(self, gc_context: MutationContext<'gc, '_>, deblocking: i32) {
+        self.0.write(gc_context).deblocking = deblocking;
+    }
+
+    /// Decodes frames until the target frame is decoded.
+    ///
+    /// Playback continues from the last decoded frame if possible. Otherwise, the decoder is
+    /// re-synced by decoding from the nearest keyframe before the target frame, so that frames
+    /// are never decoded on top of unrelated ones.
+    fn decode_target_frame(self, context: &mut RenderContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        let target = write.target_frame;
+        let last_decoded = write.decoded_frame;
+        if last_decoded == Some(target) {
+            return;
+        }
+
+        let source = write.source;
+        let source = source.read();
+        let stream = match write.stream {
+            Some(Ok(stream)) => stream,
+            Some(Err(())) => return,
+            None => {
+                let streamdef = &source.streamdef;
+                let stream = context.video.register_video_stream(
+                    streamdef.num_frames.into(),
+                    (streamdef.width, streamdef.height),
+                    streamdef.codec,
+                    streamdef.deblocking,
+                );
+                match stream {
+                    Ok(stream) => {
+                        write.stream = Some(Ok(stream));
+                        stream
+                    }
+                    Err(e) => {
+                        log::warn!("Unable to play video stream {}: {}", streamdef.id, e);
+                        write.stream = Some(Err(()));
+                        return;
+                    }
+                }
+            }
+        };
+
+        let keyframe = source.keyframes.range(..=target).next_back().copied();
+        let start = match (last_decoded, keyframe) {
+            // Continue playing forward if there is no keyframe in between.
+            (Some(last), Some(keyframe)) if last < target && last >= keyframe => last + 1,
+            (Some(last), None) if last < target => last + 1,
+            (_, Some(keyframe)) => keyframe,
+            // Without a keyframe, the stream can only be decoded from its first frame.
+            (_, None) => 0,
+        };
+
+        // Even if decoding fails, don't try to decode this frame again.
+        write.decoded_frame = Some(target);
+        for (&frame_id, data) in source.frames.range(start..=target) {
+            let encoded_frame = EncodedFrame {
+                codec: source.streamdef.codec,
+                data,
+                frame_id,
+            };
+            match context
+                .video
+                .decode_video_stream_frame(stream, encoded_frame, context.renderer)
+            {
+                Ok(bitmap) => write.bitmap = Some(bitmap),
+                Err(e) => {
+                    log::warn!("Unable to decode video frame {}: {}", frame_id, e);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<'gc> TDisplayObject<'gc> for Video<'gc> {
+    impl_display_object!(base);
+
+    fn id(&self) -> CharacterId {
+        self.0.read().source.read().streamdef.id
+    }
+
+    fn as_video(&self) -> Option<Self> {
+        Some(*self)
+    }
+
+    fn self_bounds(&self) -> BoundingBox {
+        let read = self.0.read();
+        let source = read.source.read();
+        BoundingBox {
+            x_min: Twips::new(0),
+            y_min: Twips::new(0),
+            x_max: Twips::from_pixels(source.streamdef.width.into()),
+            y_max: Twips::from_pixels(source.streamdef.height.into()),
+            valid: true,
+        }
+    }
+
+    fn hit_test_shape(&self, point: (Twips, Twips)) -> bool {
+        self.world_bounds().contains(point)
+            && self.self_bounds().contains(self.global_to_local(point))
+    }
+
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_from_avm: bool,
+        _run_frame: bool,
+    ) {
+        self.set_default_instance_name(context);
+
+        let mut write = self.0.write(context.gc_context);
+        if write.object.is_none() {
+            let object = StageObject::for_display_object(
+                context.gc_context,
+                display_object,
+                Some(context.system_prototypes.video),
+            );
+            write.object = Some(object.into());
+        }
+    }
+
+    fn object(&self) -> Avm1Value<'gc> {
+        self.0
+            .read()
+            .object
+            .map(Avm1Value::from)
+            .unwrap_or(Avm1Value::Undefined)
+    }
+
+    fn run_frame(&self, _context: &mut UpdateContext) {
+        // Noop
+    }
+
+    fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        context.net_streams.detach_video(*self);
+
+        let mut write = self.0.write(context.gc_context);
+        if let Some(Ok(stream)) = write.stream.take() {
+            context.video.remove_video_stream(stream);
+        }
+        write.decoded_frame = None;
+        write.bitmap = None;
+        drop(write);
+
+        self.set_removed(context.gc_context, true);
+    }
+
+    fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
+        if !self.world_bounds().intersects(&context.view_bounds) {
+            // Off-screen; culled
+            return;
+        }
+
+        if !self.0.read().stream_attached {
+            self.decode_target_frame(context);
+        }
+
+        let read = self.0.read();
+        if let Some(bitmap) = read.bitmap {
+            // Decoded frames are stretched to the size of the video stream.
+            let source = read.source.read();
+            let scale_x = f32::from(source.streamdef.width) / f32::from(bitmap.width.max(1));
+            let scale_y = f32::from(source.streamdef.height) / f32::from(bitmap.height.max(1));
+            context.transform_stack.push(&Transform {
+                matrix: Matrix::scale(scale_x, scale_y),
+                ..Default::default()
+            });
+            context
+                .renderer
+                .render_bitmap(bitmap.handle, context.transform_stack.transform());
+            context.transform_stack.pop();
+        }
+    }
+}
+
+unsafe impl<'gc> gc_arena::Collect for VideoData<'gc> {
+    #[inline]
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        self.base.trace(cc);
+        self.object.trace(cc);
+        self.source.trace(cc);
+    }
+}
+
+/// The definition of a video stream, shared between all instances of it.
+#[derive(Clone, Debug)]
+pub struct VideoSource {
+    streamdef: DefineVideoStream,
+
+    /// The encoded data of each frame, by frame number.
+    frames: BTreeMap<u16, Vec<u8>>,
+
+    /// The frame numbers of the keyframes.
+    keyframes: BTreeSet<u16>,
+}
+
+unsafe impl gc_arena::Collect for VideoSource {
+    #[inline]
+    fn needs_trace() -> bool {
+        false
+    }
+}
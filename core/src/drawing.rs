@@ -1,10 +1,10 @@
 use crate::backend::render::ShapeHandle;
 use crate::bounding_box::BoundingBox;
 use crate::context::RenderContext;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use crate::shape_utils::{self, DistilledShape, DrawCommand, DrawPath, FillRule};
 use gc_arena::Collect;
 use std::cell::Cell;
-use swf::{FillStyle, LineStyle, Twips};
+use swf::{CharacterId, FillStyle, LineStyle, Matrix, Twips};
 
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
@@ -13,13 +13,108 @@ pub struct Drawing {
     shape_bounds: BoundingBox,
     edge_bounds: BoundingBox,
     dirty: Cell<bool>,
-    fills: Vec<(FillStyle, Vec<DrawCommand>)>,
+    fills: Vec<DrawingFill>,
     lines: Vec<(LineStyle, Vec<DrawCommand>)>,
-    current_fill: Option<(FillStyle, Vec<DrawCommand>)>,
+    current_fill: Option<DrawingFill>,
     current_line: Option<(LineStyle, Vec<DrawCommand>)>,
     cursor: (Twips, Twips),
 }
 
+/// A fill of a drawing, and the path that it paints.
+#[derive(Clone, Debug, PartialEq)]
+struct DrawingFill {
+    style: FillStyle,
+    rule: FillRule,
+    commands: Vec<DrawCommand>,
+}
+
+/// A command of a path drawn by `Graphics.drawPath`, as listed in
+/// `flash.display.GraphicsPathCommand`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathCommand {
+    NoOp,
+    MoveTo,
+    LineTo,
+    CurveTo,
+    WideMoveTo,
+    WideLineTo,
+    CubicCurveTo,
+}
+
+impl PathCommand {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        Some(match value {
+            0 => PathCommand::NoOp,
+            1 => PathCommand::MoveTo,
+            2 => PathCommand::LineTo,
+            3 => PathCommand::CurveTo,
+            4 => PathCommand::WideMoveTo,
+            5 => PathCommand::WideLineTo,
+            6 => PathCommand::CubicCurveTo,
+            _ => return None,
+        })
+    }
+
+    pub fn to_i32(self) -> i32 {
+        match self {
+            PathCommand::NoOp => 0,
+            PathCommand::MoveTo => 1,
+            PathCommand::LineTo => 2,
+            PathCommand::CurveTo => 3,
+            PathCommand::WideMoveTo => 4,
+            PathCommand::WideLineTo => 5,
+            PathCommand::CubicCurveTo => 6,
+        }
+    }
+
+    /// The number of coordinates this command reads from the path's data.
+    ///
+    /// The wide commands read an extra point, which is ignored, so that they
+    /// take up as much data as a curve.
+    fn data_len(self) -> usize {
+        match self {
+            PathCommand::NoOp => 0,
+            PathCommand::MoveTo | PathCommand::LineTo => 2,
+            PathCommand::CurveTo | PathCommand::WideMoveTo | PathCommand::WideLineTo => 4,
+            PathCommand::CubicCurveTo => 6,
+        }
+    }
+}
+
+/// Which triangles `Graphics.drawTriangles` leaves out, by the direction in
+/// which their vertices go around.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriangleCulling {
+    None,
+
+    /// Leave out the triangles that go around clockwise on the screen.
+    Positive,
+
+    /// Leave out the triangles that go around counter-clockwise on the screen.
+    Negative,
+}
+
+/// An entry of the drawing commands read back by `Graphics.readGraphicsData`,
+/// which can be drawn again by `Graphics.drawGraphicsData`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphicsData {
+    /// Begins a fill, as `beginFill` and the other fill methods do.
+    Fill(FillStyle),
+
+    /// Ends the current fill.
+    EndFill,
+
+    /// Sets or clears the line style.
+    Stroke(Option<LineStyle>),
+
+    /// A path, as taken by `drawPath`, with coordinates in pixels.
+    Path {
+        commands: Vec<PathCommand>,
+        data: Vec<f64>,
+        fill_rule: FillRule,
+    },
+}
+
 impl Drawing {
     pub fn new() -> Self {
         Self {
@@ -35,6 +130,18 @@ impl Drawing {
         }
     }
 
+    /// Copies the contents of this drawing into a new drawing.
+    ///
+    /// The copy gets its own shape in the renderer the next time it is drawn,
+    /// so that later changes to either drawing don't affect the other.
+    pub fn duplicate(&self) -> Self {
+        Self {
+            render_handle: Cell::new(None),
+            dirty: Cell::new(true),
+            ..self.clone()
+        }
+    }
+
     pub fn set_fill_style(&mut self, style: Option<FillStyle>) {
         // TODO: If current_fill is not closed, we should close it and also close current_line
 
@@ -42,13 +149,14 @@ impl Drawing {
             self.fills.push(existing);
         }
         if let Some(style) = style {
-            self.current_fill = Some((
+            self.current_fill = Some(DrawingFill {
                 style,
-                vec![DrawCommand::MoveTo {
+                rule: FillRule::EvenOdd,
+                commands: vec![DrawCommand::MoveTo {
                     x: self.cursor.0,
                     y: self.cursor.1,
                 }],
-            ));
+            });
         }
 
         self.dirty.set(true);
@@ -82,6 +190,19 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// Sets the fill used to paint the current line style, such as a gradient.
+    ///
+    /// Lines drawn before this call keep their old fill. Has no effect if no line style is set.
+    pub fn set_line_fill_style(&mut self, fill_style: Option<FillStyle>) {
+        if let Some((style, _)) = &self.current_line {
+            let style = LineStyle {
+                fill_style,
+                ..style.clone()
+            };
+            self.set_line_style(Some(style));
+        }
+    }
+
     pub fn draw_command(&mut self, command: DrawCommand) {
         let mut include_last = false;
         let stroke_width = if let Some((style, _)) = &self.current_line {
@@ -107,17 +228,17 @@ impl Drawing {
         self.cursor = command.end_point();
 
         if let Some((_, commands)) = &mut self.current_line {
-            commands.push(command.clone());
+            push_command(commands, command.clone());
         }
-        if let Some((_, commands)) = &mut self.current_fill {
-            commands.push(command);
+        if let Some(fill) = &mut self.current_fill {
+            push_command(&mut fill.commands, command);
         }
 
         if include_last {
             if let Some(command) = self
                 .current_fill
                 .as_ref()
-                .and_then(|(_, commands)| commands.last())
+                .and_then(|fill| fill.commands.last())
             {
                 stretch_bounding_box(&mut self.shape_bounds, command, stroke_width);
                 stretch_bounding_box(&mut self.edge_bounds, command, Twips::zero());
@@ -141,22 +262,16 @@ impl Drawing {
             self.dirty.set(false);
             let mut paths = Vec::new();
 
-            for (style, commands) in &self.fills {
+            for fill in self.fills.iter().chain(self.current_fill.iter()) {
                 paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
+                    style: &fill.style,
+                    commands: fill.commands.to_owned(),
+                    fill_rule: fill.rule,
                 })
             }
 
             // TODO: If the current_fill is not closed, we should automatically close current_line
 
-            if let Some((style, commands)) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
-
             for (style, commands) in &self.lines {
                 paths.push(DrawPath::Stroke {
                     style,
@@ -195,14 +310,269 @@ impl Drawing {
         }
     }
 
+    /// Draws a path given as `Graphics.drawPath` takes it: a list of commands,
+    /// and the coordinates that they read, in pixels.
+    ///
+    /// The fill rule applies to the whole of the current fill. Drawing stops
+    /// at the first command that runs out of coordinates.
+    pub fn draw_path(&mut self, commands: &[PathCommand], data: &[f64], fill_rule: FillRule) {
+        if let Some(fill) = &mut self.current_fill {
+            fill.rule = fill_rule;
+        }
+
+        let mut data = data;
+        for &command in commands {
+            if data.len() < command.data_len() {
+                break;
+            }
+            let (args, rest) = data.split_at(command.data_len());
+            data = rest;
+
+            let point = |i: usize| (Twips::from_pixels(args[i]), Twips::from_pixels(args[i + 1]));
+            match command {
+                PathCommand::NoOp => {}
+                PathCommand::MoveTo | PathCommand::WideMoveTo => {
+                    let (x, y) = point(command.data_len() - 2);
+                    self.draw_command(DrawCommand::MoveTo { x, y });
+                }
+                PathCommand::LineTo | PathCommand::WideLineTo => {
+                    let (x, y) = point(command.data_len() - 2);
+                    self.draw_command(DrawCommand::LineTo { x, y });
+                }
+                PathCommand::CurveTo => {
+                    let (x1, y1) = point(0);
+                    let (x2, y2) = point(2);
+                    self.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+                }
+                PathCommand::CubicCurveTo => {
+                    self.draw_cubic_curve(point(0), point(2), point(4));
+                }
+            }
+        }
+    }
+
+    /// Draws a cubic Bézier curve from the cursor, split into quadratic curves.
+    fn draw_cubic_curve(
+        &mut self,
+        control1: (Twips, Twips),
+        control2: (Twips, Twips),
+        anchor: (Twips, Twips),
+    ) {
+        const SEGMENTS: u32 = 4;
+
+        let coords = |(x, y): (Twips, Twips)| (f64::from(x.get()), f64::from(y.get()));
+        let [p0, p1, p2, p3] = [
+            coords(self.cursor),
+            coords(control1),
+            coords(control2),
+            coords(anchor),
+        ];
+        let point_at = |t: f64| {
+            let s = 1.0 - t;
+            let [a, b, c, d] = [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t];
+            (
+                a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+            )
+        };
+        let tangent_at = |t: f64| {
+            let s = 1.0 - t;
+            let [a, b, c] = [3.0 * s * s, 6.0 * s * t, 3.0 * t * t];
+            (
+                a * (p1.0 - p0.0) + b * (p2.0 - p1.0) + c * (p3.0 - p2.0),
+                a * (p1.1 - p0.1) + b * (p2.1 - p1.1) + c * (p3.1 - p2.1),
+            )
+        };
+        let twips = |v: f64| Twips::new(v.round() as i32);
+
+        // Each piece of the curve is itself a cubic curve, which is replaced
+        // by the quadratic curve whose control point best matches its two.
+        let step = 1.0 / f64::from(SEGMENTS);
+        for i in 0..SEGMENTS {
+            let (t0, t1) = (f64::from(i) * step, f64::from(i + 1) * step);
+            let (start, end) = (point_at(t0), point_at(t1));
+            let (tangent0, tangent1) = (tangent_at(t0), tangent_at(t1));
+            let control1 = (
+                start.0 + tangent0.0 * step / 3.0,
+                start.1 + tangent0.1 * step / 3.0,
+            );
+            let control2 = (
+                end.0 - tangent1.0 * step / 3.0,
+                end.1 - tangent1.1 * step / 3.0,
+            );
+            let control = (
+                (3.0 * (control1.0 + control2.0) - start.0 - end.0) / 4.0,
+                (3.0 * (control1.1 + control2.1) - start.1 - end.1) / 4.0,
+            );
+
+            // The last piece ends exactly on the anchor.
+            let (x2, y2) = if i + 1 == SEGMENTS {
+                anchor
+            } else {
+                (twips(end.0), twips(end.1))
+            };
+            self.draw_command(DrawCommand::CurveTo {
+                x1: twips(control.0),
+                y1: twips(control.1),
+                x2,
+                y2,
+            });
+        }
+    }
+
+    /// Draws triangles as `Graphics.drawTriangles` does, filling each one with
+    /// the current fill and outlining it with the current line style.
+    ///
+    /// `vertices` holds pairs of coordinates, in pixels. `indices` picks the
+    /// vertices of the triangles, three at a time; without it, the vertices are
+    /// used in order. When the current fill is a bitmap and `uvt_data` is
+    /// given, each triangle shows its own part of the bitmap. `uvt_data` holds
+    /// a (u, v) pair or a (u, v, t) triple for each vertex, with u and v as
+    /// fractions of the bitmap's size. The perspective value t is ignored.
+    pub fn draw_triangles<F>(
+        &mut self,
+        vertices: &[f64],
+        indices: Option<&[u32]>,
+        uvt_data: Option<&[f64]>,
+        culling: TriangleCulling,
+        get_bitmap_dimensions: F,
+    ) where
+        F: Fn(CharacterId) -> Option<(u32, u32)>,
+    {
+        let num_vertices = vertices.len() / 2;
+        let sequential_indices: Vec<u32>;
+        let indices = match indices {
+            Some(indices) => indices,
+            None => {
+                sequential_indices = (0..num_vertices as u32).collect();
+                &sequential_indices
+            }
+        };
+
+        let uvt_data = uvt_data.unwrap_or_default();
+        let uv_stride = if num_vertices > 0 && uvt_data.len() >= num_vertices * 3 {
+            3
+        } else if num_vertices > 0 && uvt_data.len() >= num_vertices * 2 {
+            2
+        } else {
+            0
+        };
+        let bitmap = match self.current_fill.as_ref().map(|fill| &fill.style) {
+            Some(FillStyle::Bitmap {
+                id,
+                is_smoothed,
+                is_repeating,
+                ..
+            }) if uv_stride > 0 => get_bitmap_dimensions(*id)
+                .map(|dimensions| (*id, *is_smoothed, *is_repeating, dimensions)),
+            _ => None,
+        };
+
+        for triangle in indices.chunks_exact(3) {
+            let index = |i: usize| triangle[i] as usize;
+            if triangle.iter().any(|&i| i as usize >= num_vertices) {
+                continue;
+            }
+            let point = |i: usize| (vertices[index(i) * 2], vertices[index(i) * 2 + 1]);
+            let points = [point(0), point(1), point(2)];
+
+            let (a, b, c) = (points[0], points[1], points[2]);
+            let cross = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+            let culled = match culling {
+                TriangleCulling::None => false,
+                TriangleCulling::Positive => cross > 0.0,
+                TriangleCulling::Negative => cross < 0.0,
+            };
+            if culled {
+                continue;
+            }
+
+            let commands = triangle_commands(points);
+            let mapped_style = bitmap.and_then(|(id, is_smoothed, is_repeating, dimensions)| {
+                let uv = |i: usize| {
+                    (
+                        uvt_data[index(i) * uv_stride],
+                        uvt_data[index(i) * uv_stride + 1],
+                    )
+                };
+                let matrix = triangle_bitmap_matrix(points, [uv(0), uv(1), uv(2)], dimensions)?;
+                Some(FillStyle::Bitmap {
+                    id,
+                    matrix,
+                    is_smoothed,
+                    is_repeating,
+                })
+            });
+
+            if let Some(style) = mapped_style {
+                // The triangle gets a fill of its own, and only its outline
+                // goes into the current fill's place.
+                let current_fill = self.current_fill.take();
+                for command in commands.iter().cloned() {
+                    self.draw_command(command);
+                }
+                self.current_fill = current_fill;
+                self.fills.push(DrawingFill {
+                    style,
+                    rule: FillRule::EvenOdd,
+                    commands,
+                });
+            } else {
+                for command in commands {
+                    self.draw_command(command);
+                }
+            }
+        }
+    }
+
+    /// Reads the drawing back as `Graphics.readGraphicsData` does.
+    ///
+    /// Every fill comes first, followed by its path, and then the lines. The
+    /// order in which fills and lines were drawn relative to each other is
+    /// not kept.
+    pub fn graphics_data(&self) -> Vec<GraphicsData> {
+        let mut data = Vec::new();
+
+        for fill in self.fills.iter().chain(self.current_fill.iter()) {
+            data.push(GraphicsData::Fill(fill.style.clone()));
+            data.push(path_data(&fill.commands, fill.rule));
+            data.push(GraphicsData::EndFill);
+        }
+
+        for (style, commands) in self.lines.iter().chain(self.current_line.iter()) {
+            data.push(GraphicsData::Stroke(Some(style.clone())));
+            data.push(path_data(commands, FillRule::EvenOdd));
+        }
+        if !self.lines.is_empty() || self.current_line.is_some() {
+            data.push(GraphicsData::Stroke(None));
+        }
+
+        data
+    }
+
+    /// Draws a list of drawing commands, as `Graphics.drawGraphicsData` does.
+    pub fn draw_graphics_data(&mut self, data: &[GraphicsData]) {
+        for entry in data {
+            match entry {
+                GraphicsData::Fill(style) => self.set_fill_style(Some(style.clone())),
+                GraphicsData::EndFill => self.set_fill_style(None),
+                GraphicsData::Stroke(style) => self.set_line_style(style.clone()),
+                GraphicsData::Path {
+                    commands,
+                    data,
+                    fill_rule,
+                } => self.draw_path(commands, data, *fill_rule),
+            }
+        }
+    }
+
     pub fn self_bounds(&self) -> BoundingBox {
         self.shape_bounds.clone()
     }
 
     pub fn hit_test(&self, point: (Twips, Twips), local_matrix: &swf::Matrix) -> bool {
-        use crate::shape_utils;
-        for path in &self.fills {
-            if shape_utils::draw_command_fill_hit_test(&path.1, point) {
+        for fill in self.fills.iter().chain(self.current_fill.iter()) {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.rule, point) {
                 return true;
             }
         }
@@ -214,8 +584,6 @@ impl Drawing {
             }
         }
 
-        // TODO: Handle cases where fill is not closed.
-        // Probably should have an explicit `flush` method that handles this.
         if let Some(path) = &self.current_line {
             if shape_utils::draw_command_stroke_hit_test(&path.1, path.0.width, point, local_matrix)
             {
@@ -250,3 +618,340 @@ fn stretch_bounding_box(
         }
     }
 }
+
+/// Adds a command to a path. A move right after another move replaces it, as
+/// the pen only ends up at the last one.
+fn push_command(commands: &mut Vec<DrawCommand>, command: DrawCommand) {
+    if let (DrawCommand::MoveTo { .. }, Some(DrawCommand::MoveTo { .. })) =
+        (&command, commands.last())
+    {
+        commands.pop();
+    }
+    commands.push(command);
+}
+
+/// The commands tracing a closed triangle, with corners in pixels.
+fn triangle_commands(points: [(f64, f64); 3]) -> Vec<DrawCommand> {
+    let twips = |(x, y): (f64, f64)| (Twips::from_pixels(x), Twips::from_pixels(y));
+    let (x0, y0) = twips(points[0]);
+    let (x1, y1) = twips(points[1]);
+    let (x2, y2) = twips(points[2]);
+    vec![
+        DrawCommand::MoveTo { x: x0, y: y0 },
+        DrawCommand::LineTo { x: x1, y: y1 },
+        DrawCommand::LineTo { x: x2, y: y2 },
+        DrawCommand::LineTo { x: x0, y: y0 },
+    ]
+}
+
+/// The bitmap fill matrix that places the given points of a bitmap, as
+/// fractions of its size, on the corners of a triangle, in pixels.
+///
+/// Returns `None` if the points of the bitmap are all in a line.
+fn triangle_bitmap_matrix(
+    points: [(f64, f64); 3],
+    uvs: [(f64, f64); 3],
+    (width, height): (u32, u32),
+) -> Option<Matrix> {
+    // Bitmap fills map the pixels of the bitmap into twips.
+    let texel = |(u, v): (f64, f64)| (u * f64::from(width), v * f64::from(height));
+    let twips = |(x, y): (f64, f64)| (x * 20.0, y * 20.0);
+    let [s0, s1, s2] = [texel(uvs[0]), texel(uvs[1]), texel(uvs[2])];
+    let [d0, d1, d2] = [twips(points[0]), twips(points[1]), twips(points[2])];
+
+    let (sx1, sy1) = (s1.0 - s0.0, s1.1 - s0.1);
+    let (sx2, sy2) = (s2.0 - s0.0, s2.1 - s0.1);
+    let (dx1, dy1) = (d1.0 - d0.0, d1.1 - d0.1);
+    let (dx2, dy2) = (d2.0 - d0.0, d2.1 - d0.1);
+    let det = sx1 * sy2 - sx2 * sy1;
+    if det.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let a = (dx1 * sy2 - dx2 * sy1) / det;
+    let b = (dy1 * sy2 - dy2 * sy1) / det;
+    let c = (dx2 * sx1 - dx1 * sx2) / det;
+    let d = (dy2 * sx1 - dy1 * sx2) / det;
+    Some(Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::new((d0.0 - a * s0.0 - c * s0.1).round() as i32),
+        ty: Twips::new((d0.1 - b * s0.0 - d * s0.1).round() as i32),
+    })
+}
+
+/// Converts a path into the commands and coordinates taken by `drawPath`.
+fn path_data(commands: &[DrawCommand], fill_rule: FillRule) -> GraphicsData {
+    let mut path_commands = Vec::with_capacity(commands.len());
+    let mut data = Vec::with_capacity(commands.len() * 2);
+    for command in commands {
+        match *command {
+            DrawCommand::MoveTo { x, y } => {
+                path_commands.push(PathCommand::MoveTo);
+                data.extend_from_slice(&[x.to_pixels(), y.to_pixels()]);
+            }
+            DrawCommand::LineTo { x, y } => {
+                path_commands.push(PathCommand::LineTo);
+                data.extend_from_slice(&[x.to_pixels(), y.to_pixels()]);
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                path_commands.push(PathCommand::CurveTo);
+                data.extend_from_slice(&[
+                    x1.to_pixels(),
+                    y1.to_pixels(),
+                    x2.to_pixels(),
+                    y2.to_pixels(),
+                ]);
+            }
+        }
+    }
+
+    GraphicsData::Path {
+        commands: path_commands,
+        data,
+        fill_rule,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_copies_commands() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(FillStyle::Color(swf::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        })));
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(10.0),
+            y: Twips::from_pixels(20.0),
+        });
+        drawing.dirty.set(false);
+        drawing.render_handle.set(Some(ShapeHandle(0)));
+
+        let copy = drawing.duplicate();
+        assert_eq!(copy.self_bounds(), drawing.self_bounds());
+        assert_eq!(copy.current_fill, drawing.current_fill);
+        assert!(copy.render_handle.get().is_none());
+        assert!(copy.dirty.get());
+    }
+
+    #[test]
+    fn hit_test_unclosed_fill() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(FillStyle::Color(swf::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        })));
+        // A right triangle whose last edge is left open.
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(100.0),
+            y: Twips::from_pixels(0.0),
+        });
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(100.0),
+            y: Twips::from_pixels(100.0),
+        });
+
+        let matrix = swf::Matrix::identity();
+        let inside = (Twips::from_pixels(90.0), Twips::from_pixels(10.0));
+        let outside = (Twips::from_pixels(10.0), Twips::from_pixels(90.0));
+        assert!(drawing.hit_test(inside, &matrix));
+        assert!(!drawing.hit_test(outside, &matrix));
+    }
+    fn red_fill() -> FillStyle {
+        FillStyle::Color(swf::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        })
+    }
+
+    fn fill_commands(drawing: &Drawing) -> &[DrawCommand] {
+        &drawing.current_fill.as_ref().unwrap().commands
+    }
+
+    #[test]
+    fn draw_path_commands() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(red_fill()));
+        drawing.draw_path(
+            &[
+                PathCommand::MoveTo,
+                PathCommand::LineTo,
+                PathCommand::NoOp,
+                PathCommand::WideLineTo,
+                PathCommand::CurveTo,
+                PathCommand::LineTo,
+            ],
+            &[
+                10.0, 10.0, 20.0, 10.0, 99.0, 99.0, 20.0, 20.0, 15.0, 25.0, 10.0, 20.0, 5.0,
+            ],
+            FillRule::NonZero,
+        );
+
+        let px = Twips::from_pixels;
+        assert_eq!(
+            fill_commands(&drawing),
+            &[
+                DrawCommand::MoveTo {
+                    x: px(10.0),
+                    y: px(10.0)
+                },
+                DrawCommand::LineTo {
+                    x: px(20.0),
+                    y: px(10.0)
+                },
+                DrawCommand::LineTo {
+                    x: px(20.0),
+                    y: px(20.0)
+                },
+                DrawCommand::CurveTo {
+                    x1: px(15.0),
+                    y1: px(25.0),
+                    x2: px(10.0),
+                    y2: px(20.0)
+                },
+            ][..]
+        );
+        assert_eq!(
+            drawing.current_fill.as_ref().unwrap().rule,
+            FillRule::NonZero
+        );
+    }
+
+    #[test]
+    fn draw_path_cubic_curve() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(red_fill()));
+        drawing.draw_path(
+            &[PathCommand::MoveTo, PathCommand::CubicCurveTo],
+            &[0.0, 0.0, 0.0, 100.0, 100.0, 100.0, 100.0, 0.0],
+            FillRule::EvenOdd,
+        );
+
+        let commands = fill_commands(&drawing);
+        assert_eq!(commands.len(), 5);
+        assert!(commands[1..]
+            .iter()
+            .all(|command| matches!(command, DrawCommand::CurveTo { .. })));
+        assert_eq!(
+            commands[4].end_point(),
+            (Twips::from_pixels(100.0), Twips::from_pixels(0.0))
+        );
+        // The middle of the curve is at (50, 75).
+        assert_eq!(
+            commands[2].end_point(),
+            (Twips::from_pixels(50.0), Twips::from_pixels(75.0))
+        );
+    }
+
+    #[test]
+    fn draw_triangles_culling() {
+        // The first triangle goes clockwise on the screen, the second one
+        // counter-clockwise.
+        let vertices = [0.0, 0.0, 10.0, 0.0, 0.0, 10.0];
+        let indices = [0, 1, 2, 0, 2, 1];
+        let count = |culling| {
+            let mut drawing = Drawing::new();
+            drawing.set_fill_style(Some(red_fill()));
+            drawing.draw_triangles(&vertices, Some(&indices), None, culling, |_| None);
+            fill_commands(&drawing)
+                .iter()
+                .filter(|command| matches!(command, DrawCommand::MoveTo { .. }))
+                .count()
+        };
+        assert_eq!(count(TriangleCulling::None), 2);
+        assert_eq!(count(TriangleCulling::Positive), 1);
+        assert_eq!(count(TriangleCulling::Negative), 1);
+    }
+
+    #[test]
+    fn draw_triangles_uv_mapping() {
+        let mut drawing = Drawing::new();
+        drawing.set_fill_style(Some(FillStyle::Bitmap {
+            id: 1,
+            matrix: Matrix::identity(),
+            is_smoothed: false,
+            is_repeating: false,
+        }));
+        // The whole of a 10x10 bitmap is stretched over a triangle at
+        // (50, 50) that is 100 pixels wide.
+        drawing.draw_triangles(
+            &[50.0, 50.0, 150.0, 50.0, 50.0, 150.0],
+            None,
+            Some(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+            TriangleCulling::None,
+            |id| if id == 1 { Some((10, 10)) } else { None },
+        );
+
+        assert_eq!(drawing.fills.len(), 1);
+        match &drawing.fills[0].style {
+            FillStyle::Bitmap { matrix, .. } => {
+                assert_eq!(matrix.a, 200.0);
+                assert_eq!(matrix.b, 0.0);
+                assert_eq!(matrix.c, 0.0);
+                assert_eq!(matrix.d, 200.0);
+                assert_eq!(matrix.tx, Twips::from_pixels(50.0));
+                assert_eq!(matrix.ty, Twips::from_pixels(50.0));
+            }
+            style => panic!("Expected a bitmap fill, got {:?}", style),
+        }
+        // The current fill is left as it was.
+        assert_eq!(fill_commands(&drawing).len(), 1);
+    }
+
+    #[test]
+    fn graphics_data_round_trip() {
+        let mut drawing = Drawing::new();
+        drawing.set_line_style(Some(LineStyle::new_v1(
+            Twips::from_pixels(1.0),
+            swf::Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            },
+        )));
+        drawing.set_fill_style(Some(red_fill()));
+        drawing.draw_path(
+            &[
+                PathCommand::LineTo,
+                PathCommand::LineTo,
+                PathCommand::LineTo,
+            ],
+            &[10.0, 0.0, 10.0, 10.0, 0.0, 0.0],
+            FillRule::NonZero,
+        );
+        drawing.set_fill_style(None);
+        drawing.set_line_style(None);
+
+        let data = drawing.graphics_data();
+        assert!(matches!(data[0], GraphicsData::Fill(_)));
+        assert!(matches!(
+            data[1],
+            GraphicsData::Path {
+                fill_rule: FillRule::NonZero,
+                ..
+            }
+        ));
+        assert_eq!(data[2], GraphicsData::EndFill);
+        assert!(matches!(data[3], GraphicsData::Stroke(Some(_))));
+        assert_eq!(data[5], GraphicsData::Stroke(None));
+
+        let mut copy = Drawing::new();
+        copy.draw_graphics_data(&data);
+        assert_eq!(copy.fills, drawing.fills);
+        assert_eq!(copy.lines, drawing.lines);
+        assert_eq!(copy.graphics_data(), data);
+    }
+}
@@ -1,16 +1,78 @@
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PlayerEvent {
-    KeyDown { key_code: KeyCode },
-    KeyUp { key_code: KeyCode },
-    MouseMove { x: f64, y: f64 },
-    MouseUp { x: f64, y: f64 },
-    MouseDown { x: f64, y: f64 },
+    /// A key being pressed.
+    ///
+    /// `key_char` is the character the key produces with the current keyboard
+    /// layout and modifiers, if it is known when the key is pressed.
+    KeyDown {
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    },
+    KeyUp {
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    },
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+    },
     MouseLeft,
-    MouseWheel { delta: MouseWheelDelta },
-    TextInput { codepoint: char },
+    MouseWheel {
+        delta: MouseWheelDelta,
+    },
+    TextInput {
+        codepoint: char,
+    },
+
+    /// A finger touching, moving on or leaving a touch screen.
+    ///
+    /// `id` identifies the touch point for as long as it stays in contact,
+    /// and `pressure` ranges from 0 to 1.
+    Touch {
+        phase: TouchPhase,
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: f64,
+    },
+}
+
+impl PlayerEvent {
+    /// Whether this event is a deliberate action by the user.
+    ///
+    /// Scripts run in response to these events may perform actions that are
+    /// otherwise forbidden, like writing to the clipboard.
+    pub fn is_user_gesture(&self) -> bool {
+        matches!(
+            self,
+            Self::KeyDown { .. }
+                | Self::KeyUp { .. }
+                | Self::MouseDown { .. }
+                | Self::MouseUp { .. }
+                | Self::TextInput { .. }
+        )
+    }
+}
+
+/// The stage of a touch point's contact with the screen.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TouchPhase {
+    Begin,
+    Move,
+    End,
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -82,13 +144,28 @@ impl ClipEvent {
     pub fn propagates(self) -> bool {
         matches!(
             self,
-            Self::MouseUp | Self::MouseDown | Self::MouseMove | Self::KeyPress { .. } | Self::KeyDown | Self::KeyUp
+            Self::MouseUp
+                | Self::MouseDown
+                | Self::MouseMove
+                | Self::KeyPress { .. }
+                | Self::KeyDown
+                | Self::KeyUp
         )
     }
 
     /// Indicates whether this is an event type used by Buttons (i.e., on that can be used in an `on` handler in Flash).
     pub fn is_button_event(self) -> bool {
-        matches!(self, Self::DragOut | Self::DragOver | Self::KeyPress { .. } | Self::Press | Self::RollOut | Self::RollOver | Self::Release | Self::ReleaseOutside)
+        matches!(
+            self,
+            Self::DragOut
+                | Self::DragOver
+                | Self::KeyPress { .. }
+                | Self::Press
+                | Self::RollOut
+                | Self::RollOver
+                | Self::Release
+                | Self::ReleaseOutside
+        )
     }
 
     /// Returns the method name of the event handler for this event.
@@ -117,12 +194,32 @@ impl ClipEvent {
     }
 }
 
-/// Flash virtual keycode.
+/// Where a key is on the keyboard, as reported by `KeyboardEvent.keyLocation`.
+///
+/// This tells apart keys with the same key code, such as the left and right
+/// Shift keys, or the digits above the letters and on the numeric keypad.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum KeyLocation {
+    Standard = 0,
+    Left = 1,
+    Right = 2,
+    NumPad = 3,
+}
+
+impl Default for KeyLocation {
+    fn default() -> Self {
+        KeyLocation::Standard
+    }
+}
+
+/// Flash virtual keycode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
 pub enum KeyCode {
     Unknown = 0,
     Backspace = 8,
+    Tab = 9,
     Return = 13,
     Shift = 16,
     Control = 17,
@@ -203,6 +300,7 @@ pub enum KeyCode {
     Insert = 45,
     Delete = 46,
     Pause = 19,
+    NumLock = 144,
     ScrollLock = 145,
     F1 = 112,
     F2 = 113,
@@ -347,6 +445,7 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
         KeyCode::Insert => ButtonKeyCode::Insert,
         KeyCode::Delete => ButtonKeyCode::Delete,
         KeyCode::Backspace => ButtonKeyCode::Backspace,
+        KeyCode::Tab => ButtonKeyCode::Tab,
         KeyCode::Return => ButtonKeyCode::Return,
         KeyCode::Up => ButtonKeyCode::Up,
         KeyCode::Down => ButtonKeyCode::Down,
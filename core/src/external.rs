@@ -1,18 +1,21 @@
 use crate::avm1::activation::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
 };
+use crate::avm1::object::date_object::DateObject;
 use crate::avm1::object::TObject;
 use crate::avm1::Value as Avm1Value;
 use crate::avm1::{
     AvmString as Avm1String, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
 };
 use crate::context::UpdateContext;
+use chrono::{TimeZone, Utc};
 use gc_arena::{Collect, CollectionContext};
 use std::collections::BTreeMap;
 
 /// An intermediate format of representing shared data between ActionScript and elsewhere.
 /// Regardless of the capabilities of both sides, all data will be translated to this potentially
-/// lossy format. Any recursion or additional metadata in ActionScript will not be translated.
+/// lossy format. Any additional metadata in ActionScript will not be translated, and any object
+/// that (directly or indirectly) contains itself will have the recursive reference become `Null`.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Null,
@@ -21,6 +24,12 @@ pub enum Value {
     String(String),
     Object(BTreeMap<String, Value>),
     List(Vec<Value>),
+
+    /// A date, as milliseconds since the Unix epoch. May be `NaN` for an invalid date.
+    Date(f64),
+
+    /// Raw binary data.
+    Bytes(Vec<u8>),
 }
 
 impl From<Avm1String<'_>> for Value {
@@ -117,6 +126,15 @@ impl Value {
     pub fn from_avm1<'gc>(
         activation: &mut Avm1Activation<'_, 'gc, '_>,
         value: Avm1Value<'gc>,
+    ) -> Result<Value, crate::avm1::error::Error<'gc>> {
+        Self::from_avm1_visited(activation, value, &mut Vec::new())
+    }
+
+    /// Convert an AVM1 value, replacing any object in `ancestors` with `Null` to break cycles.
+    fn from_avm1_visited<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        value: Avm1Value<'gc>,
+        ancestors: &mut Vec<Avm1Object<'gc>>,
     ) -> Result<Value, crate::avm1::error::Error<'gc>> {
         Ok(match value {
             Avm1Value::Undefined | Avm1Value::Null => Value::Null,
@@ -124,31 +142,51 @@ impl Value {
             Avm1Value::Number(value) => Value::Number(value),
             Avm1Value::String(value) => Value::String(value.to_string()),
             Avm1Value::Object(object) => {
-                if activation
-                    .context
-                    .avm1
-                    .prototypes()
-                    .array
-                    .is_prototype_of(object)
-                {
-                    let mut values = Vec::new();
-                    for value in object.array() {
-                        values.push(Value::from_avm1(activation, value)?);
-                    }
-                    Value::List(values)
-                } else {
-                    let keys = object.get_keys(activation);
-                    let mut values = BTreeMap::new();
-                    for key in keys {
-                        let value = object.get(&key, activation)?;
-                        values.insert(key, Value::from_avm1(activation, value)?);
-                    }
-                    Value::Object(values)
+                if ancestors.iter().any(|o| Avm1Object::ptr_eq(*o, object)) {
+                    return Ok(Value::Null);
                 }
+                ancestors.push(object);
+                let value = Self::from_avm1_object(activation, object, ancestors)?;
+                ancestors.pop();
+                value
             }
         })
     }
 
+    fn from_avm1_object<'gc>(
+        activation: &mut Avm1Activation<'_, 'gc, '_>,
+        object: Avm1Object<'gc>,
+        ancestors: &mut Vec<Avm1Object<'gc>>,
+    ) -> Result<Value, crate::avm1::error::Error<'gc>> {
+        Ok(if let Some(date) = object.as_date_object() {
+            Value::Date(
+                date.date_time()
+                    .map(|date_time| date_time.timestamp_millis() as f64)
+                    .unwrap_or(f64::NAN),
+            )
+        } else if activation
+            .context
+            .avm1
+            .prototypes()
+            .array
+            .is_prototype_of(object)
+        {
+            let mut values = Vec::new();
+            for value in object.array() {
+                values.push(Value::from_avm1_visited(activation, value, ancestors)?);
+            }
+            Value::List(values)
+        } else {
+            let keys = object.get_keys(activation);
+            let mut values = BTreeMap::new();
+            for key in keys {
+                let value = object.get(&key, activation)?;
+                values.insert(key, Value::from_avm1_visited(activation, value, ancestors)?);
+            }
+            Value::Object(values)
+        })
+    }
+
     pub fn into_avm1<'gc>(self, activation: &mut Avm1Activation<'_, 'gc, '_>) -> Avm1Value<'gc> {
         match self {
             Value::Null => Avm1Value::Null,
@@ -181,6 +219,27 @@ impl Value {
                 }
                 array.into()
             }
+            Value::Date(time) => {
+                let date_time = if time.is_finite() {
+                    Some(Utc.timestamp_millis(time as i64))
+                } else {
+                    None
+                };
+                DateObject::with_date_time(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().date),
+                    date_time,
+                )
+                .into()
+            }
+            // AVM1 has no binary data type, so bytes become an array of numbers.
+            Value::Bytes(bytes) => Value::List(
+                bytes
+                    .into_iter()
+                    .map(|byte| Value::Number(byte.into()))
+                    .collect(),
+            )
+            .into_avm1(activation),
         }
     }
 }
@@ -237,6 +296,13 @@ pub trait ExternalInterfaceProvider {
     fn on_callback_available(&self, name: &str);
 }
 
+/// Handles `fscommand` calls made by the movie, such as the standalone player's `fullscreen`
+/// or `quit` commands.
+pub trait FsCommandProvider {
+    /// Handle the given command. Returns `false` if the command is not recognised.
+    fn on_fs_command(&self, command: &str, args: &str) -> bool;
+}
+
 pub trait ExternalInterfaceMethod {
     fn call(&self, context: &mut UpdateContext<'_, '_, '_>, args: &[Value]) -> Value;
 }
@@ -253,6 +319,7 @@ where
 #[derive(Default)]
 pub struct ExternalInterface<'gc> {
     providers: Vec<Box<dyn ExternalInterfaceProvider>>,
+    fs_commands: Option<Box<dyn FsCommandProvider>>,
     callbacks: BTreeMap<String, Callback<'gc>>,
 }
 
@@ -272,6 +339,18 @@ impl<'gc> ExternalInterface<'gc> {
         self.providers.push(provider);
     }
 
+    pub fn set_fs_command_provider(&mut self, provider: Box<dyn FsCommandProvider>) {
+        self.fs_commands = Some(provider);
+    }
+
+    /// Pass an `fscommand` to the frontend. Returns `false` if it was not handled.
+    pub fn invoke_fs_command(&self, command: &str, args: &str) -> bool {
+        self.fs_commands
+            .as_ref()
+            .map(|provider| provider.on_fs_command(command, args))
+            .unwrap_or(false)
+    }
+
     pub fn add_callback(&mut self, name: String, callback: Callback<'gc>) {
         self.callbacks.insert(name.clone(), callback);
         for provider in &self.providers {
@@ -296,3 +375,67 @@ impl<'gc> ExternalInterface<'gc> {
         !self.providers.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::error::Error as Avm1Error;
+    use crate::avm1::test_utils::with_avm;
+
+    #[test]
+    fn recursive_object_becomes_null() {
+        with_avm(8, |activation, _root| -> Result<(), Avm1Error> {
+            let object = Avm1ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            object.set("self", object.into(), activation)?;
+            object.set("number", 1.0.into(), activation)?;
+
+            let mut expected = BTreeMap::new();
+            expected.insert("self".to_string(), Value::Null);
+            expected.insert("number".to_string(), Value::Number(1.0));
+            assert_eq!(
+                Value::from_avm1(activation, object.into())?,
+                Value::Object(expected)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn shared_object_is_not_recursion() {
+        with_avm(8, |activation, _root| -> Result<(), Avm1Error> {
+            let proto = Some(activation.context.avm1.prototypes().object);
+            let child = Avm1ScriptObject::object(activation.context.gc_context, proto);
+            let parent = Avm1ScriptObject::object(activation.context.gc_context, proto);
+            parent.set("a", child.into(), activation)?;
+            parent.set("b", child.into(), activation)?;
+
+            let mut expected = BTreeMap::new();
+            expected.insert("a".to_string(), Value::Object(BTreeMap::new()));
+            expected.insert("b".to_string(), Value::Object(BTreeMap::new()));
+            assert_eq!(
+                Value::from_avm1(activation, parent.into())?,
+                Value::Object(expected)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn date_round_trip() {
+        with_avm(8, |activation, _root| -> Result<(), Avm1Error> {
+            let value = Value::Date(1_234_567_890_123.0).into_avm1(activation);
+            assert!(value
+                .coerce_to_object(activation)
+                .as_date_object()
+                .is_some());
+            assert_eq!(
+                Value::from_avm1(activation, value)?,
+                Value::Date(1_234_567_890_123.0)
+            );
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,412 @@
+//! Parsing of FLV video files, as played by `NetStream`.
+
+use swf::VideoCodec;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("Not an FLV file")]
+    InvalidHeader,
+
+    #[error("Invalid script data")]
+    InvalidScriptData,
+}
+
+/// The header at the start of an FLV file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub has_audio: bool,
+    pub has_video: bool,
+
+    /// The offset of the first tag in the file.
+    pub data_offset: usize,
+}
+
+impl Header {
+    /// Parse the header at the start of an FLV file.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < 9 || !data.starts_with(b"FLV") {
+            return Err(Error::InvalidHeader);
+        }
+
+        let flags = data[4];
+        let data_offset = u32::from_be_bytes([data[5], data[6], data[7], data[8]]) as usize;
+        if data_offset < 9 {
+            return Err(Error::InvalidHeader);
+        }
+
+        Ok(Self {
+            has_audio: flags & 0b100 != 0,
+            has_video: flags & 0b1 != 0,
+            data_offset,
+        })
+    }
+}
+
+/// The kind of data held by an FLV tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TagKind {
+    Audio,
+    Video,
+    ScriptData,
+    Unknown(u8),
+}
+
+impl From<u8> for TagKind {
+    fn from(tag_type: u8) -> Self {
+        // The upper bits are reserved, or mark the tag as encrypted.
+        match tag_type & 0x1f {
+            8 => TagKind::Audio,
+            9 => TagKind::Video,
+            18 => TagKind::ScriptData,
+            other => TagKind::Unknown(other),
+        }
+    }
+}
+
+/// A single tag of an FLV file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tag<'a> {
+    pub kind: TagKind,
+
+    /// The time at which this tag should be played, in milliseconds.
+    pub timestamp: u32,
+
+    pub data: &'a [u8],
+}
+
+/// Reads the tags of an FLV file.
+///
+/// Iteration stops at the first incomplete tag, so that a partially downloaded file can be read
+/// up to the end of the data received so far.
+pub struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Start reading the tags that follow the header of an FLV file.
+    pub fn new(data: &'a [u8], header: &Header) -> Self {
+        // The header is followed by the size of the (nonexistent) previous tag.
+        Self {
+            data,
+            position: header.data_offset + 4,
+        }
+    }
+
+    /// The offset of the next tag to be read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Tag<'a>> {
+        let header = self.data.get(self.position..self.position + 11)?;
+        let data_size = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let timestamp = u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+
+        let data_start = self.position + 11;
+        let data = self.data.get(data_start..data_start + data_size)?;
+
+        // Each tag is followed by its size.
+        self.position = data_start + data_size + 4;
+        Some(Tag {
+            kind: header[0].into(),
+            timestamp,
+            data,
+        })
+    }
+}
+
+/// The contents of a video tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VideoTag<'a> {
+    /// 1 is a keyframe, 2 an inter frame, 3 a disposable inter frame, 4 a generated keyframe and
+    /// 5 a command frame.
+    pub frame_type: u8,
+
+    /// The codec of the frame, if it is one that can be embedded in a SWF file.
+    pub codec: Option<VideoCodec>,
+
+    /// The FLV codec ID of the frame.
+    pub codec_id: u8,
+
+    /// The encoded frame, in the same format as the data of a `VideoFrame` tag.
+    pub data: &'a [u8],
+}
+
+impl<'a> VideoTag<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Self> {
+        let first = *data.first()?;
+        let codec_id = first & 0xf;
+        let (codec, data) = match codec_id {
+            2 => (Some(VideoCodec::H263), &data[1..]),
+            3 => (Some(VideoCodec::ScreenVideo), &data[1..]),
+            // VP6 frames begin with a byte holding the amount of cropping to apply, which
+            // `VideoFrame` tags don't have.
+            4 => (Some(VideoCodec::VP6), data.get(2..)?),
+            5 => (Some(VideoCodec::VP6WithAlpha), data.get(2..)?),
+            _ => (None, &data[1..]),
+        };
+
+        Some(Self {
+            frame_type: first >> 4,
+            codec,
+            codec_id,
+            data,
+        })
+    }
+
+    pub fn is_keyframe(&self) -> bool {
+        self.frame_type == 1 || self.frame_type == 4
+    }
+}
+
+/// A value stored in a script data tag, such as the `onMetaData` information.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptDataValue {
+    Number(f64),
+    Boolean(bool),
+    String(String),
+    Object(Vec<(String, ScriptDataValue)>),
+    StrictArray(Vec<ScriptDataValue>),
+
+    /// A date, in milliseconds since the Unix epoch.
+    Date(f64),
+
+    Null,
+    Undefined,
+}
+
+impl ScriptDataValue {
+    /// Look up a property of an object.
+    pub fn get(&self, name: &str) -> Option<&ScriptDataValue> {
+        match self {
+            ScriptDataValue::Object(properties) => properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ScriptDataValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the contents of a script data tag, which hold the name of a method to call on the
+/// `NetStream` client, and its argument.
+pub fn parse_script_data(data: &[u8]) -> Result<(String, ScriptDataValue), Error> {
+    let mut reader = ScriptDataReader { data };
+    let name = match reader.read_value()? {
+        ScriptDataValue::String(name) => name,
+        _ => return Err(Error::InvalidScriptData),
+    };
+    // Some encoders don't write a value at all.
+    let value = if reader.data.is_empty() {
+        ScriptDataValue::Undefined
+    } else {
+        reader.read_value()?
+    };
+    Ok((name, value))
+}
+
+/// Reads AMF0-encoded values.
+struct ScriptDataReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ScriptDataReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(Error::InvalidScriptData);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(f64::from_be_bytes(bytes))
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(self.read_bytes(len)?).into_owned())
+    }
+
+    /// Read the properties of an object, up to the end marker.
+    fn read_properties(&mut self) -> Result<Vec<(String, ScriptDataValue)>, Error> {
+        let mut properties = Vec::new();
+        loop {
+            let len = self.read_u16()?.into();
+            let name = self.read_string(len)?;
+            if name.is_empty() && self.data.first() == Some(&9) {
+                self.data = &self.data[1..];
+                return Ok(properties);
+            }
+            properties.push((name, self.read_value()?));
+        }
+    }
+
+    fn read_value(&mut self) -> Result<ScriptDataValue, Error> {
+        Ok(match self.read_u8()? {
+            0 => ScriptDataValue::Number(self.read_f64()?),
+            1 => ScriptDataValue::Boolean(self.read_u8()? != 0),
+            2 => {
+                let len = self.read_u16()?.into();
+                ScriptDataValue::String(self.read_string(len)?)
+            }
+            3 => ScriptDataValue::Object(self.read_properties()?),
+            5 => ScriptDataValue::Null,
+            6 => ScriptDataValue::Undefined,
+            // References to earlier objects aren't supported.
+            7 => {
+                self.read_u16()?;
+                ScriptDataValue::Undefined
+            }
+            8 => {
+                // The length of an ECMA array is only a hint, it is terminated like an object.
+                self.read_u32()?;
+                ScriptDataValue::Object(self.read_properties()?)
+            }
+            10 => {
+                let len = self.read_u32()?;
+                let values = (0..len)
+                    .map(|_| self.read_value())
+                    .collect::<Result<_, _>>()?;
+                ScriptDataValue::StrictArray(values)
+            }
+            11 => {
+                let date = self.read_f64()?;
+                // The time zone is unused.
+                self.read_u16()?;
+                ScriptDataValue::Date(date)
+            }
+            12 => {
+                let len = self.read_u32()? as usize;
+                ScriptDataValue::String(self.read_string(len)?)
+            }
+            _ => return Err(Error::InvalidScriptData),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &[u8] = b"FLV\x01\x05\x00\x00\x00\x09\x00\x00\x00\x00";
+
+    fn tag(tag_type: u8, timestamp: u32, data: &[u8]) -> Vec<u8> {
+        let size = data.len() as u32;
+        let mut tag = vec![tag_type];
+        tag.extend(&size.to_be_bytes()[1..]);
+        tag.extend(&timestamp.to_be_bytes()[1..]);
+        tag.push((timestamp >> 24) as u8);
+        tag.extend(&[0, 0, 0]);
+        tag.extend(data);
+        tag.extend(&(size + 11).to_be_bytes());
+        tag
+    }
+
+    #[test]
+    fn read_tags() {
+        let mut data = HEADER.to_vec();
+        data.extend(tag(9, 0, &[0x12, 0xaa]));
+        data.extend(tag(8, 0x0100_0020, &[0x2f]));
+
+        let header = Header::parse(&data).unwrap();
+        assert!(header.has_audio);
+        assert!(header.has_video);
+
+        let tags: Vec<_> = Reader::new(&data, &header).collect();
+        assert_eq!(
+            tags,
+            vec![
+                Tag {
+                    kind: TagKind::Video,
+                    timestamp: 0,
+                    data: &[0x12, 0xaa],
+                },
+                Tag {
+                    kind: TagKind::Audio,
+                    timestamp: 0x0100_0020,
+                    data: &[0x2f],
+                },
+            ]
+        );
+
+        let video = VideoTag::parse(tags[0].data).unwrap();
+        assert!(video.is_keyframe());
+        assert_eq!(video.codec, Some(VideoCodec::H263));
+        assert_eq!(video.data, &[0xaa]);
+    }
+
+    #[test]
+    fn stop_at_incomplete_tag() {
+        let mut data = HEADER.to_vec();
+        data.extend(tag(9, 0, &[0x12, 0xaa]));
+        let complete_len = data.len();
+        data.extend(&tag(9, 40, &[0x22, 0xbb, 0xcc])[..8]);
+
+        let header = Header::parse(&data).unwrap();
+        let mut reader = Reader::new(&data, &header);
+        assert!(reader.next().is_some());
+        assert!(reader.next().is_none());
+        assert_eq!(reader.position(), complete_len);
+    }
+
+    #[test]
+    fn reject_invalid_header() {
+        assert_eq!(Header::parse(b"FWS\x01"), Err(Error::InvalidHeader));
+    }
+
+    #[test]
+    fn parse_metadata() {
+        let mut data = vec![2, 0, 10];
+        data.extend(b"onMetaData");
+        data.extend(&[8, 0, 0, 0, 2]);
+        data.extend(&[0, 8]);
+        data.extend(b"duration");
+        data.push(0);
+        data.extend(&12.5f64.to_be_bytes());
+        data.extend(&[0, 9]);
+        data.extend(b"canSeekOn");
+        data.extend(&[1, 1]);
+        data.extend(&[0, 0, 9]);
+
+        let (name, value) = parse_script_data(&data).unwrap();
+        assert_eq!(name, "onMetaData");
+        assert_eq!(
+            value.get("duration").and_then(|v| v.as_number()),
+            Some(12.5)
+        );
+        assert_eq!(
+            value.get("canSeekOn"),
+            Some(&ScriptDataValue::Boolean(true))
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Keyboard focus and Tab key navigation.
+//!
+//! Only one interactive object has focus at a time. Focus moves when the user
+//! clicks a focusable object, presses Tab or Shift+Tab, or when a script calls
+//! `Selection.setFocus`. The focus rectangle is only drawn while focus was
+//! last moved with the keyboard, as in Flash Player.
+
+use crate::avm1::Value;
+use crate::bounding_box::BoundingBox;
+use crate::context::{ActionType, RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, TDisplayObject, TextSelection};
+use crate::drawing::Drawing;
+use crate::shape_utils::DrawCommand;
+use gc_arena::{Collect, GcCell, MutationContext};
+use swf::{Color, LineStyle, Twips};
+
+/// Tracks which display object has keyboard focus.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    /// The object that currently has focus, if any.
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether focus was last moved with the keyboard, in which case the
+    /// focus rectangle is drawn around the focused object.
+    highlight: bool,
+
+    /// The rectangle drawn around the focused object.
+    highlight_drawing: Drawing,
+
+    /// The stage bounds that `highlight_drawing` was last drawn for.
+    highlight_bounds: BoundingBox,
+}
+
+impl<'gc> FocusTracker<'gc> {
+    /// The color of the focus rectangle.
+    const HIGHLIGHT_COLOR: Color = Color {
+        r: 0xFF,
+        g: 0xFF,
+        b: 0x00,
+        a: 0xFF,
+    };
+
+    /// The thickness of the focus rectangle, in twips.
+    const HIGHLIGHT_WIDTH: i32 = 40;
+
+    pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+                highlight_drawing: Drawing::new(),
+                highlight_bounds: BoundingBox::default(),
+            },
+        ))
+    }
+
+    /// The object that currently has focus, if any.
+    ///
+    /// Objects lose focus when they are removed from the display list.
+    pub fn get(self) -> Option<DisplayObject<'gc>> {
+        self.0.read().focus.filter(|focus| !focus.removed())
+    }
+
+    /// Whether focus was last moved with the keyboard.
+    pub fn is_highlighted(self) -> bool {
+        self.0.read().highlight
+    }
+
+    /// Moves focus to the given object, or removes focus if `None`.
+    ///
+    /// `highlight` should be set if focus was moved with the keyboard. The
+    /// `onKillFocus` handler of the previously focused object runs first,
+    /// followed by the `onSetFocus` handler of the new one and finally the
+    /// `Selection.onSetFocus` listeners.
+    pub fn set(
+        self,
+        focused_element: Option<DisplayObject<'gc>>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        highlight: bool,
+    ) {
+        let old = self.get();
+        {
+            let mut write = self.0.write(context.gc_context);
+            write.focus = focused_element;
+            write.highlight = highlight && focused_element.is_some();
+        }
+        *context.needs_render = true;
+
+        let unchanged = match (old, focused_element) {
+            (Some(old), Some(new)) => DisplayObject::ptr_eq(old, new),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return;
+        }
+
+        let old_value = old.map(|o| o.object()).unwrap_or(Value::Null);
+        let new_value = focused_element.map(|o| o.object()).unwrap_or(Value::Null);
+
+        if let Some(old) = old {
+            Self::queue_focus_method(context, old, "onKillFocus", new_value.clone());
+        }
+        if let Some(new) = focused_element {
+            // A text field selects all of its text when it gains focus.
+            if let Some(text_field) = new.as_edit_text() {
+                let selection = TextSelection::for_range(0, text_field.text_length());
+                text_field.set_selection(Some(selection), context.gc_context);
+            }
+            Self::queue_focus_method(context, new, "onSetFocus", old_value.clone());
+        }
+
+        if let Some(root) = context.levels.get(&0).copied() {
+            context.action_queue.queue_actions(
+                root,
+                ActionType::NotifyListeners {
+                    listener: "Selection",
+                    method: "onSetFocus",
+                    args: vec![old_value, new_value],
+                },
+                false,
+            );
+        }
+    }
+
+    /// Moves focus to the next object in the tab order, or to the previous
+    /// one if `reverse` is set (Shift+Tab). Focus wraps around at either end.
+    pub fn cycle(self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let tab_order = Self::tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let len = tab_order.len();
+        let current = self.get().and_then(|focus| {
+            tab_order
+                .iter()
+                .position(|o| DisplayObject::ptr_eq(*o, focus))
+        });
+        let next = match (current, reverse) {
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        self.set(Some(tab_order[next]), context, true);
+    }
+
+    /// Whether the given object can receive focus.
+    ///
+    /// Objects can opt in or out with `tabEnabled`; otherwise, buttons, input
+    /// text fields and movie clips with mouse handlers are focusable.
+    pub fn is_focusable(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+    ) -> bool {
+        if !object.visible() {
+            return false;
+        }
+
+        if let Some(tab_enabled) = object.tab_enabled() {
+            tab_enabled
+        } else if object.as_button().is_some() {
+            true
+        } else if let Some(text) = object.as_edit_text() {
+            text.is_editable()
+        } else if let Some(movie_clip) = object.as_movie_clip() {
+            movie_clip.is_button_mode(context)
+        } else {
+            false
+        }
+    }
+
+    /// Builds the tab order by walking the display list.
+    ///
+    /// If any focusable object has a `tabIndex`, only those objects take part,
+    /// ordered by index. Otherwise, objects are ordered by their position on
+    /// the stage, from top to bottom and then left to right.
+    fn tab_order(context: &mut UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+        let mut tab_order = Vec::new();
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().copied().collect();
+        for level in levels {
+            Self::fill_tab_order(context, level, &mut tab_order);
+        }
+
+        if tab_order.iter().any(|o| o.tab_index().is_some()) {
+            tab_order.retain(|o| o.tab_index().is_some());
+            tab_order.sort_by_key(|o| o.tab_index());
+        } else {
+            tab_order.sort_by_key(|o| {
+                let bounds = o.world_bounds();
+                (bounds.y_min, bounds.x_min)
+            });
+        }
+
+        tab_order
+    }
+
+    fn fill_tab_order(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: DisplayObject<'gc>,
+        tab_order: &mut Vec<DisplayObject<'gc>>,
+    ) {
+        if !object.visible() {
+            return;
+        }
+
+        if Self::is_focusable(context, object) {
+            tab_order.push(object);
+        }
+
+        // The children of a button are its states, which can't take focus.
+        if object.as_button().is_none() && object.tab_children() {
+            let children: Vec<DisplayObject<'gc>> = object.children().collect();
+            for child in children {
+                Self::fill_tab_order(context, child, tab_order);
+            }
+        }
+    }
+
+    /// Queues an AVM1 focus handler method on the given object.
+    fn queue_focus_method(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: DisplayObject<'gc>,
+        name: &'static str,
+        arg: Value<'gc>,
+    ) {
+        if let Value::Object(object) = target.object() {
+            context.action_queue.queue_actions(
+                target,
+                ActionType::Method {
+                    object,
+                    name,
+                    args: vec![arg],
+                },
+                false,
+            );
+        }
+    }
+
+    /// Whether the focus rectangle should be drawn around the given object.
+    ///
+    /// An object's own `_focusrect` takes priority over the one of its root.
+    fn shows_focus_rect(object: DisplayObject<'gc>) -> bool {
+        object
+            .focus_rect()
+            .or_else(|| object.root().focus_rect())
+            .unwrap_or(true)
+    }
+
+    /// Draws the focus rectangle around the focused object, in stage coordinates.
+    pub fn render_highlight(self, context: &mut RenderContext<'_, 'gc>) {
+        let focus = match self.get() {
+            Some(focus) if self.is_highlighted() && Self::shows_focus_rect(focus) => focus,
+            _ => return,
+        };
+
+        let bounds = focus.world_bounds();
+        if !bounds.valid {
+            return;
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        if write.highlight_bounds != bounds {
+            let drawing = &mut write.highlight_drawing;
+            drawing.clear();
+            drawing.set_line_style(Some(LineStyle::new_v1(
+                Twips::new(Self::HIGHLIGHT_WIDTH),
+                Self::HIGHLIGHT_COLOR,
+            )));
+            drawing.draw_command(DrawCommand::MoveTo {
+                x: bounds.x_min,
+                y: bounds.y_min,
+            });
+            drawing.draw_command(DrawCommand::LineTo {
+                x: bounds.x_max,
+                y: bounds.y_min,
+            });
+            drawing.draw_command(DrawCommand::LineTo {
+                x: bounds.x_max,
+                y: bounds.y_max,
+            });
+            drawing.draw_command(DrawCommand::LineTo {
+                x: bounds.x_min,
+                y: bounds.y_max,
+            });
+            drawing.draw_command(DrawCommand::LineTo {
+                x: bounds.x_min,
+                y: bounds.y_min,
+            });
+            write.highlight_bounds = bounds;
+        }
+
+        write.highlight_drawing.render(context);
+    }
+}
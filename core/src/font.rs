@@ -1,8 +1,10 @@
 use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::font_file::FontFile;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::transform::Transform;
 use gc_arena::{Collect, Gc, MutationContext};
+use std::sync::Arc;
 
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
 pub fn round_down_to_pixel(t: Twips) -> Twips {
@@ -32,8 +34,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -107,6 +108,7 @@ impl<'gc> Font<'gc> {
         for swf_glyph in &tag.glyphs {
             let glyph = Glyph {
                 shape: renderer.register_glyph_shape(swf_glyph),
+                outline: Arc::new(glyph_outline(swf_glyph)),
                 advance: swf_glyph.advance.unwrap_or(0),
             };
             let index = glyphs.len();
@@ -148,6 +150,38 @@ impl<'gc> Font<'gc> {
         )))
     }
 
+    /// Construct a font from a `DefineFont4` tag.
+    ///
+    /// The embedded OpenType font is converted into `DefineFont3` glyphs, with
+    /// its cubic curves approximated by quadratic ones. A tag without font
+    /// data only names a font, like a `DefineFont2` tag without glyphs.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+    ) -> Result<Font<'gc>, Error> {
+        let mut font = if let Some(data) = &tag.data {
+            FontFile::parse(data, &tag.name, tag.is_bold, tag.is_italic)?.font
+        } else {
+            swf::Font {
+                version: 3,
+                id: 0,
+                name: tag.name.clone(),
+                language: swf::Language::Unknown,
+                layout: None,
+                glyphs: vec![],
+                is_small_text: false,
+                is_shift_jis: false,
+                is_ansi: false,
+                is_bold: tag.is_bold,
+                is_italic: tag.is_italic,
+            }
+        };
+        font.id = tag.id;
+
+        Self::from_swf_tag(gc_context, renderer, &font)
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(self) -> bool {
@@ -200,6 +234,13 @@ impl<'gc> Font<'gc> {
         Twips::new((self.0.ascent as f32 * scale) as i32)
     }
 
+    /// Get the distance from the baseline to the bottom of the glyph at a given height.
+    pub fn get_descent_for_height(self, height: Twips) -> Twips {
+        let scale = height.get() as f32 / self.scale();
+
+        Twips::new((self.0.descent as f32 * scale) as i32)
+    }
+
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(self) -> bool {
         !self.0.kerning_pairs.is_empty()
@@ -359,9 +400,36 @@ impl<'gc> Font<'gc> {
 #[derive(Debug, Clone)]
 pub struct Glyph {
     pub shape: ShapeHandle,
+
+    /// The outline of the glyph, used for hit testing.
+    pub outline: Arc<swf::Shape>,
+
     pub advance: i16,
 }
 
+/// Build a shape with a single solid fill from the records of a glyph.
+fn glyph_outline(glyph: &swf::Glyph) -> swf::Shape {
+    swf::Shape {
+        version: 2,
+        id: 0,
+        shape_bounds: Default::default(),
+        edge_bounds: Default::default(),
+        has_fill_winding_rule: false,
+        has_non_scaling_strokes: false,
+        has_scaling_strokes: true,
+        styles: swf::ShapeStyles {
+            fill_styles: vec![swf::FillStyle::Color(Color {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: 255,
+            })],
+            line_styles: vec![],
+        },
+        shape: glyph.shape_records.clone(),
+    }
+}
+
 /// Structure which identifies a particular font by name and properties.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Collect)]
 #[collect(require_static)]
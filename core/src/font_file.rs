@@ -0,0 +1,243 @@
+//! Conversion of TrueType and OpenType font files into SWF fonts.
+//!
+//! Host applications can supply font files to be used as device fonts, so
+//! that text in movies which don't embed their fonts is laid out the same way
+//! as in Flash Player. The glyph outlines of the font are converted into a
+//! `DefineFont3` tag, which is then loaded like any other font.
+
+use swf::{ShapeRecord, StyleChangeData, Twips};
+use thiserror::Error;
+use ttf_parser::{name_id, Face, OutlineBuilder};
+
+/// The size of the EM square of a `DefineFont3` glyph, in twips.
+const EM_SQUARE: f32 = 20480.0;
+
+#[derive(Error, Debug)]
+pub enum FontFileError {
+    #[error("Invalid font data: {0}")]
+    InvalidData(ttf_parser::FaceParsingError),
+
+    #[error("Font has no units per EM")]
+    MissingUnitsPerEm,
+}
+
+/// A font file converted into a `DefineFont3` tag.
+pub struct FontFile {
+    /// The converted font.
+    pub font: swf::Font,
+
+    /// The family names stored in the font file itself, such as `Verdana`.
+    pub family_names: Vec<String>,
+}
+
+impl FontFile {
+    /// Parse a TrueType or OpenType font file.
+    ///
+    /// The font is named `name`, and is marked with the given style; the style
+    /// stored in the font file is ignored.
+    pub fn parse(
+        data: &[u8],
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Result<Self, FontFileError> {
+        let face = Face::from_slice(data, 0).map_err(FontFileError::InvalidData)?;
+        let units_per_em = face
+            .units_per_em()
+            .filter(|&units| units > 0)
+            .ok_or(FontFileError::MissingUnitsPerEm)?;
+        let scale = EM_SQUARE / f32::from(units_per_em);
+
+        let mut glyphs = vec![];
+        for code in 0..=u16::MAX {
+            let c = match std::char::from_u32(code.into()) {
+                Some(c) => c,
+                None => continue,
+            };
+            let glyph_id = match face.glyph_index(c) {
+                Some(glyph_id) => glyph_id,
+                None => continue,
+            };
+
+            // Glyphs without an outline, such as spaces, are still included
+            // so that their advance is known.
+            let mut outline = GlyphOutline::new(scale);
+            face.outline_glyph(glyph_id, &mut outline);
+            let advance = face
+                .glyph_hor_advance(glyph_id)
+                .map(|advance| (f32::from(advance) * scale).round().min(i16::MAX.into()) as i16)
+                .unwrap_or(0);
+            glyphs.push(swf::Glyph {
+                shape_records: outline.records,
+                code,
+                advance: Some(advance),
+                bounds: None,
+            });
+        }
+
+        let mut family_names = vec![];
+        for name_record in face.names() {
+            let id = name_record.name_id();
+            if id != name_id::FAMILY
+                && id != name_id::TYPOGRAPHIC_FAMILY
+                && id != name_id::FULL_NAME
+            {
+                continue;
+            }
+            if let Some(family_name) = name_record.to_string() {
+                if !family_name.is_empty() && !family_names.contains(&family_name) {
+                    family_names.push(family_name);
+                }
+            }
+        }
+
+        let font = swf::Font {
+            version: 3,
+            id: 0,
+            name: name.to_string(),
+            language: swf::Language::Unknown,
+            layout: Some(swf::FontLayout {
+                ascent: (f32::from(face.ascender()) * scale).max(0.0) as u16,
+                descent: (-f32::from(face.descender()) * scale).max(0.0) as u16,
+                leading: (f32::from(face.line_gap()) * scale) as i16,
+                kerning: vec![],
+            }),
+            glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold,
+            is_italic,
+        };
+
+        Ok(Self { font, family_names })
+    }
+}
+
+/// Converts a glyph outline in font units into SWF shape records.
+struct GlyphOutline {
+    records: Vec<ShapeRecord>,
+    scale: f32,
+
+    /// The pen position, in twips.
+    pen: (i32, i32),
+
+    /// The pen position, in font units.
+    pen_units: (f32, f32),
+
+    /// The start of the current contour, in twips.
+    contour_start: (i32, i32),
+}
+
+impl GlyphOutline {
+    fn new(scale: f32) -> Self {
+        Self {
+            records: vec![],
+            scale,
+            pen: (0, 0),
+            pen_units: (0.0, 0.0),
+            contour_start: (0, 0),
+        }
+    }
+
+    /// Converts a point to twips. Font units point up, while SWF coordinates point down.
+    fn to_twips(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x * self.scale).round() as i32,
+            (-y * self.scale).round() as i32,
+        )
+    }
+
+    fn straight_edge(&mut self, to: (i32, i32)) {
+        self.records.push(ShapeRecord::StraightEdge {
+            delta_x: Twips::new(to.0 - self.pen.0),
+            delta_y: Twips::new(to.1 - self.pen.1),
+        });
+        self.pen = to;
+    }
+
+    fn curved_edge(&mut self, control: (i32, i32), to: (i32, i32)) {
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(control.0 - self.pen.0),
+            control_delta_y: Twips::new(control.1 - self.pen.1),
+            anchor_delta_x: Twips::new(to.0 - control.0),
+            anchor_delta_y: Twips::new(to.1 - control.1),
+        });
+        self.pen = to;
+    }
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let to = self.to_twips(x, y);
+        // Glyphs are drawn with the font's only fill style, set by the first contour.
+        let fill_style_1 = if self.records.is_empty() {
+            Some(1)
+        } else {
+            None
+        };
+        self.records.push(ShapeRecord::StyleChange(StyleChangeData {
+            move_to: Some((Twips::new(to.0), Twips::new(to.1))),
+            fill_style_0: None,
+            fill_style_1,
+            line_style: None,
+            new_styles: None,
+        }));
+        self.pen = to;
+        self.pen_units = (x, y);
+        self.contour_start = to;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.to_twips(x, y);
+        self.straight_edge(to);
+        self.pen_units = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.to_twips(x1, y1);
+        let to = self.to_twips(x, y);
+        self.curved_edge(control, to);
+        self.pen_units = (x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // SWF shapes only have quadratic curves, so the cubic curve is split in half and each
+        // half is approximated by a quadratic curve.
+        let midpoint = |a: (f32, f32), b: (f32, f32)| ((a.0 + b.0) * 0.5, (a.1 + b.1) * 0.5);
+        let (from, c1, c2, to) = (self.pen_units, (x1, y1), (x2, y2), (x, y));
+        let mid_01 = midpoint(from, c1);
+        let mid_12 = midpoint(c1, c2);
+        let mid_23 = midpoint(c2, to);
+        let mid_012 = midpoint(mid_01, mid_12);
+        let mid_123 = midpoint(mid_12, mid_23);
+        let mid = midpoint(mid_012, mid_123);
+        for &(p0, p1, p2, p3) in &[(from, mid_01, mid_012, mid), (mid, mid_123, mid_23, to)] {
+            let control = self.to_twips(
+                ((p1.0 + p2.0) * 3.0 - p0.0 - p3.0) * 0.25,
+                ((p1.1 + p2.1) * 3.0 - p0.1 - p3.1) * 0.25,
+            );
+            let anchor = self.to_twips(p3.0, p3.1);
+            self.curved_edge(control, anchor);
+        }
+        self.pen_units = to;
+    }
+
+    fn close(&mut self) {
+        if self.pen != self.contour_start {
+            self.straight_edge(self.contour_start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_invalid_font_data() {
+        assert!(FontFile::parse(b"", "Test", false, false).is_err());
+        assert!(FontFile::parse(b"not a font file", "Test", false, false).is_err());
+        assert!(FontFile::parse(&[0, 1, 0, 0, 0, 0], "Test", false, false).is_err());
+    }
+}
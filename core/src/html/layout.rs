@@ -87,6 +87,9 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// The top of each line laid out so far.
+    line_tops: Vec<Twips>,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
@@ -104,6 +107,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            line_tops: Vec::new(),
         }
     }
 
@@ -226,6 +230,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             return;
         }
 
+        self.line_tops.push(self.cursor.y());
+
         let mut line_bounds = None;
         let mut box_count: i32 = 0;
         for linebox in self.boxes.get_mut(self.current_line..).unwrap() {
@@ -396,15 +402,32 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
-        let library = context.library.library_for_movie_mut(self.movie.clone());
+        let embedded_font = context
+            .library
+            .library_for_movie_mut(self.movie.clone())
+            .get_font_by_name(&span.font, span.bold, span.italic)
+            .filter(|f| !is_device_font && f.has_glyphs());
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, load the matching system font from the
+        // font provider, and fall back to using our embedded Noto Sans if there is none.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if let Some(font) = library
-            .get_font_by_name(&span.font, span.bold, span.italic)
-            .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| library.device_font())
+        if let Some(font) = embedded_font
+            .or_else(|| {
+                context.library.load_device_font(
+                    context.gc_context,
+                    context.renderer,
+                    context.font_provider,
+                    &span.font,
+                    span.bold,
+                    span.italic,
+                )
+            })
+            .or_else(|| {
+                context
+                    .library
+                    .library_for_movie_mut(self.movie.clone())
+                    .device_font()
+            })
         {
             self.font = Some(font);
             return self.font;
@@ -525,13 +548,13 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     fn end_layout(
         mut self,
         context: &mut UpdateContext<'_, 'gc, '_>,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>, LineMetrics) {
         self.fixup_line(context, !self.has_line_break, true);
 
-        (
-            self.boxes,
-            self.exterior_bounds.unwrap_or_else(Default::default),
-        )
+        let bounds = self.exterior_bounds.unwrap_or_else(Default::default);
+        let line_metrics = LineMetrics::new(self.line_tops, bounds.extent_y());
+
+        (self.boxes, bounds, line_metrics)
     }
 
     fn is_start_of_line(&self) -> bool {
@@ -539,6 +562,78 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     }
 }
 
+/// The vertical extent of each line within a fully laid-out `EditText`.
+///
+/// Lines are addressed by zero-based index here; ActionScript's one-based
+/// `scroll` values are converted by the caller.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub struct LineMetrics {
+    /// The top of each line, in layout order.
+    line_tops: Vec<Twips>,
+
+    /// The bottom of the last line.
+    bottom: Twips,
+}
+
+impl LineMetrics {
+    /// Construct line metrics from the top of each line and the bottom of
+    /// the last one.
+    pub fn new(line_tops: Vec<Twips>, bottom: Twips) -> Self {
+        Self { line_tops, bottom }
+    }
+
+    /// The number of lines in the layout.
+    ///
+    /// A layout always has at least one line, even if it is empty.
+    pub fn line_count(&self) -> usize {
+        max(self.line_tops.len(), 1)
+    }
+
+    /// The top of a given line.
+    pub fn line_top(&self, line: usize) -> Twips {
+        self.line_tops.get(line).copied().unwrap_or_default()
+    }
+
+    /// The bottom of a given line, which is also the top of the next line.
+    pub fn line_bottom(&self, line: usize) -> Twips {
+        self.line_tops.get(line + 1).copied().unwrap_or(self.bottom)
+    }
+
+    /// The line containing a given vertical position.
+    pub fn line_at(&self, y: Twips) -> usize {
+        self.line_tops
+            .iter()
+            .rposition(|top| *top <= y)
+            .unwrap_or(0)
+    }
+
+    /// The highest line that can be scrolled to the top of a viewport of the
+    /// given height while still filling it.
+    ///
+    /// This is the first line from which every following line fits within
+    /// the viewport.
+    pub fn max_scroll_line(&self, visible_height: Twips) -> usize {
+        (0..self.line_count())
+            .find(|line| self.bottom - self.line_top(*line) <= visible_height)
+            .unwrap_or_else(|| self.line_count() - 1)
+    }
+
+    /// The last line that is visible in a viewport of the given height,
+    /// when `first_line` is scrolled to its top.
+    ///
+    /// The first line is always considered visible, even if it is taller
+    /// than the viewport.
+    pub fn bottom_line(&self, first_line: usize, visible_height: Twips) -> usize {
+        let top = self.line_top(first_line);
+
+        (first_line..self.line_count())
+            .take_while(|line| self.line_bottom(*line) - top <= visible_height)
+            .last()
+            .unwrap_or(first_line)
+    }
+}
+
 /// A `LayoutBox` represents a single content box within a fully laid-out
 /// `EditText`.
 ///
@@ -655,7 +750,8 @@ impl<'gc> LayoutBox<'gc> {
     /// Construct a new layout hierarchy from text spans.
     ///
     /// The returned bounds will include both the text bounds itself, as well
-    /// as left and right margins on any of the lines.
+    /// as left and right margins on any of the lines. The returned line
+    /// metrics describe where each line of the layout begins.
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -663,7 +759,7 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
-    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
+    ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>, LineMetrics) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.text());
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
@@ -793,6 +889,40 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// Returns the range of text this box contains, if it is a text box.
+    pub fn text_range(&self) -> Option<(usize, usize)> {
+        match &self.content {
+            LayoutContent::Text { start, end, .. } => Some((*start, *end)),
+            LayoutContent::Bullet { .. } => None,
+            LayoutContent::Drawing(..) => None,
+        }
+    }
+
+    /// Returns the left and right edges of the character at `index`,
+    /// relative to the left edge of this box, if this box contains it.
+    ///
+    /// Characters without a glyph in the box's font have no width.
+    pub fn char_x_range(&self, text: &str, index: usize) -> Option<(Twips, Twips)> {
+        match &self.content {
+            LayoutContent::Text {
+                start,
+                end,
+                font,
+                params,
+                ..
+            } if *start <= index && index < *end => {
+                let text = text.get(*start..*end)?;
+                let offset = index - start;
+                let char_end = offset + text.get(offset..)?.chars().next()?.len_utf8();
+                let left = font.measure(text.get(..offset)?, *params, false).0;
+                let right = font.measure(&text[..char_end], *params, false).0;
+
+                Some((left, max(left, right)))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the drawing this box contains, if it has one.
     pub fn as_renderable_drawing(&self) -> Option<&Drawing> {
         match &self.content {
@@ -1,7 +1,8 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
-use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::html::layout::LineMetrics;
+use crate::html::text_format::{condense_white, FormatSpans, TextFormat, TextSpan};
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -793,3 +794,57 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn line_metrics_lookup() {
+    let metrics = LineMetrics::new(
+        vec![Twips::new(0), Twips::new(100), Twips::new(200)],
+        Twips::new(300),
+    );
+
+    assert_eq!(metrics.line_count(), 3);
+    assert_eq!(metrics.line_top(1), Twips::new(100));
+    assert_eq!(metrics.line_bottom(1), Twips::new(200));
+    assert_eq!(metrics.line_bottom(2), Twips::new(300));
+    assert_eq!(metrics.line_at(Twips::new(150)), 1);
+    assert_eq!(metrics.line_at(Twips::new(250)), 2);
+}
+
+#[test]
+fn line_metrics_scrolling() {
+    let metrics = LineMetrics::new(
+        vec![
+            Twips::new(0),
+            Twips::new(100),
+            Twips::new(200),
+            Twips::new(300),
+        ],
+        Twips::new(400),
+    );
+
+    assert_eq!(metrics.max_scroll_line(Twips::new(400)), 0);
+    assert_eq!(metrics.max_scroll_line(Twips::new(250)), 2);
+    assert_eq!(metrics.max_scroll_line(Twips::new(50)), 3);
+
+    assert_eq!(metrics.bottom_line(0, Twips::new(250)), 1);
+    assert_eq!(metrics.bottom_line(2, Twips::new(250)), 3);
+    assert_eq!(metrics.bottom_line(1, Twips::new(50)), 1);
+}
+
+#[test]
+fn line_metrics_empty() {
+    let metrics = LineMetrics::default();
+
+    assert_eq!(metrics.line_count(), 1);
+    assert_eq!(metrics.max_scroll_line(Twips::new(0)), 0);
+    assert_eq!(metrics.bottom_line(0, Twips::new(0)), 0);
+}
+
+#[test]
+fn condense_white_collapses_runs() {
+    assert_eq!(condense_white("a  b\t\n c", false), "a b c");
+    assert_eq!(condense_white("  a b ", false), " a b ");
+    assert_eq!(condense_white("  a b ", true), "a b ");
+    assert_eq!(condense_white("\r\n", true), "");
+    assert_eq!(condense_white("abc", false), "abc");
+}
@@ -83,6 +83,35 @@ fn process_html_entity(src: &str) -> Cow<str> {
     }
 }
 
+/// Collapse each run of whitespace into a single space, as done by
+/// `TextField.condenseWhite`.
+///
+/// Line breaks in the source are treated as whitespace too, so only markup
+/// such as `<br>` or `<p>` starts a new line. If `at_line_start` is set,
+/// leading whitespace is dropped entirely.
+pub fn condense_white(src: &str, at_line_start: bool) -> Cow<str> {
+    let mut result_str = String::with_capacity(src.len());
+    let mut last_was_white = at_line_start;
+
+    for ch in src.chars() {
+        if ch.is_ascii_whitespace() {
+            if !last_was_white {
+                result_str.push(' ');
+            }
+            last_was_white = true;
+        } else {
+            result_str.push(ch);
+            last_was_white = false;
+        }
+    }
+
+    if result_str == src {
+        Cow::Borrowed(src)
+    } else {
+        Cow::Owned(result_str)
+    }
+}
+
 /// A set of text formatting options to be applied to some part, or the whole
 /// of, a given text field.
 ///
@@ -1230,7 +1259,10 @@ impl FormatSpans {
     /// a handful of presentational attributes in the HTML tree to generate
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
-    pub fn lower_from_html<'gc>(&mut self, tree: XMLDocument<'gc>) {
+    ///
+    /// If `condense_white` is set, runs of whitespace in the markup's text are
+    /// collapsed into single spaces.
+    pub fn lower_from_html<'gc>(&mut self, tree: XMLDocument<'gc>, condense_white: bool) {
         let mut format_stack = vec![self.default_format.clone()];
         let mut last_successful_format = None;
 
@@ -1272,10 +1304,18 @@ impl FormatSpans {
                         .unwrap_or_else(Default::default),
                 )),
                 Step::Around(node) if node.is_text() => {
+                    let value = node.node_value().unwrap();
+                    let value = if condense_white {
+                        let at_line_start = self.text.is_empty()
+                            || self.text.ends_with(|c: char| c == '\n' || c == ' ');
+                        self::condense_white(&value, at_line_start)
+                    } else {
+                        Cow::Borrowed(value.as_str())
+                    };
                     self.replace_text(
                         self.text.len(),
                         self.text.len(),
-                        &process_html_entity(&node.node_value().unwrap()),
+                        &process_html_entity(&value),
                         format_stack.last(),
                     );
                     last_successful_format = format_stack.last().cloned();
@@ -0,0 +1,174 @@
+//! Keyboard state, as seen by scripts
+
+use crate::events::KeyCode;
+use std::collections::HashSet;
+
+/// Tracks the keys held down and the last key event, from the key events that
+/// reach the player.
+///
+/// Like Flash Player, a key counts as down for a whole frame if it was down at
+/// any point since the previous frame. Games that poll `Key.isDown` once per
+/// frame then don't miss keys that are tapped quickly.
+pub struct Keyboard {
+    /// The keys currently held down.
+    keys_held: HashSet<KeyCode>,
+
+    /// The keys that were down at any point since the last frame.
+    keys_down: HashSet<KeyCode>,
+
+    /// The key of the last key event.
+    last_key_code: KeyCode,
+
+    /// The character of the last key event, if it produced one.
+    last_key_char: Option<char>,
+
+    /// Whether Caps Lock is on.
+    caps_lock: bool,
+
+    /// Whether Num Lock is on.
+    num_lock: bool,
+}
+
+impl Keyboard {
+    pub fn new() -> Self {
+        Self {
+            keys_held: HashSet::new(),
+            keys_down: HashSet::new(),
+            last_key_code: KeyCode::Unknown,
+            last_key_char: None,
+            caps_lock: false,
+            num_lock: false,
+        }
+    }
+
+    /// Registers a key being pressed, or repeating while it is held.
+    pub fn key_down(&mut self, key_code: KeyCode, key_char: Option<char>) {
+        let is_repeat = !self.keys_held.insert(key_code);
+        self.keys_down.insert(key_code);
+        self.last_key_code = key_code;
+        self.last_key_char = key_char;
+
+        if !is_repeat {
+            match key_code {
+                KeyCode::CapsLock => self.caps_lock = !self.caps_lock,
+                KeyCode::NumLock => self.num_lock = !self.num_lock,
+                _ => (),
+            }
+        }
+    }
+
+    /// Registers a key being released.
+    pub fn key_up(&mut self, key_code: KeyCode, key_char: Option<char>) {
+        self.keys_held.remove(&key_code);
+        self.last_key_code = key_code;
+        self.last_key_char = key_char;
+    }
+
+    /// Registers a character typed with the last key, for platforms that only
+    /// report it after the key press.
+    pub fn text_input(&mut self, codepoint: char) {
+        if self.last_key_char.is_none() {
+            self.last_key_char = Some(codepoint);
+        }
+    }
+
+    /// Called after every frame, to forget keys that were released during it.
+    pub fn end_frame(&mut self) {
+        self.keys_down = self.keys_held.clone();
+    }
+
+    /// Whether the key was down at any point since the last frame, as returned
+    /// by `Key.isDown`.
+    pub fn is_key_down(&self, key_code: KeyCode) -> bool {
+        self.keys_down.contains(&key_code)
+    }
+
+    /// Whether the key is held down right now.
+    pub fn is_key_held(&self, key_code: KeyCode) -> bool {
+        self.keys_held.contains(&key_code)
+    }
+
+    /// Whether Caps Lock or Num Lock is on, as returned by `Key.isToggled`.
+    /// Other keys are never toggled.
+    pub fn is_toggled(&self, key_code: KeyCode) -> bool {
+        match key_code {
+            KeyCode::CapsLock => self.caps_lock,
+            KeyCode::NumLock => self.num_lock,
+            _ => false,
+        }
+    }
+
+    /// The key of the last key event, as returned by `Key.getCode`.
+    pub fn last_key_code(&self) -> KeyCode {
+        self.last_key_code
+    }
+
+    /// The character of the last key event, as returned by `Key.getAscii`.
+    pub fn last_key_char(&self) -> Option<char> {
+        self.last_key_char
+    }
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_tapped_between_frames_is_down_until_next_frame() {
+        let mut keyboard = Keyboard::new();
+        keyboard.key_down(KeyCode::Left, None);
+        keyboard.key_up(KeyCode::Left, None);
+        assert!(keyboard.is_key_down(KeyCode::Left));
+        assert!(!keyboard.is_key_held(KeyCode::Left));
+
+        keyboard.end_frame();
+        assert!(!keyboard.is_key_down(KeyCode::Left));
+    }
+
+    #[test]
+    fn held_key_stays_down() {
+        let mut keyboard = Keyboard::new();
+        keyboard.key_down(KeyCode::Right, None);
+        keyboard.end_frame();
+        keyboard.end_frame();
+        assert!(keyboard.is_key_down(KeyCode::Right));
+
+        keyboard.key_up(KeyCode::Right, None);
+        assert!(keyboard.is_key_down(KeyCode::Right));
+        keyboard.end_frame();
+        assert!(!keyboard.is_key_down(KeyCode::Right));
+    }
+
+    #[test]
+    fn lock_keys_toggle_once_per_press() {
+        let mut keyboard = Keyboard::new();
+        keyboard.key_down(KeyCode::CapsLock, None);
+        keyboard.key_down(KeyCode::CapsLock, None);
+        assert!(keyboard.is_toggled(KeyCode::CapsLock));
+        keyboard.key_up(KeyCode::CapsLock, None);
+        assert!(keyboard.is_toggled(KeyCode::CapsLock));
+
+        keyboard.key_down(KeyCode::CapsLock, None);
+        assert!(!keyboard.is_toggled(KeyCode::CapsLock));
+        assert!(!keyboard.is_toggled(KeyCode::NumLock));
+    }
+
+    #[test]
+    fn last_key_includes_text_input() {
+        let mut keyboard = Keyboard::new();
+        keyboard.key_down(KeyCode::A, None);
+        keyboard.text_input('a');
+        assert_eq!(keyboard.last_key_code(), KeyCode::A);
+        assert_eq!(keyboard.last_key_char(), Some('a'));
+
+        keyboard.key_up(KeyCode::A, Some('a'));
+        keyboard.text_input('b');
+        assert_eq!(keyboard.last_key_char(), Some('a'));
+    }
+}
@@ -15,34 +15,56 @@ extern crate downcast_rs;
 
 #[macro_use]
 mod avm1;
+mod audio_manager;
 mod avm2;
+mod bitmap_cache;
 mod bounding_box;
 mod character;
 mod collect;
 pub mod color_transform;
 pub mod context;
+pub mod context_menu;
+mod debug_overlay;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
+pub mod flv;
+mod focus_tracker;
 mod font;
+mod font_file;
 mod html;
+mod keyboard;
 mod library;
 pub mod loader;
+pub mod local_connection;
+pub mod net_stream;
 mod player;
 mod prelude;
+mod print_job;
 mod property_map;
+pub mod replay;
+mod scaling_grid;
 pub mod shape_utils;
+pub mod socket;
+mod sol;
+mod stage;
 pub mod string_utils;
 pub mod tag_utils;
+mod timer;
+pub mod touch;
 mod transform;
 mod xml;
 
 pub mod backend;
 pub mod external;
 
+pub use avm1::globals::system::{Language, PlayerType};
 pub use chrono;
 pub use events::PlayerEvent;
+pub use font_file::FontFileError;
 pub use indexmap;
-pub use player::Player;
+pub use player::{BackgroundThrottle, Player};
+pub use sol::SolError;
+pub use stage::{StageAlign, StageQuality, StageScaleMode};
 pub use swf;
 pub use swf::Color;
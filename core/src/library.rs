@@ -1,4 +1,7 @@
+use crate::avm2::Domain as Avm2Domain;
 use crate::backend::audio::SoundHandle;
+use crate::backend::font::FontProvider;
+use crate::backend::render::RenderBackend;
 use crate::character::Character;
 use crate::display_object::TDisplayObject;
 use crate::font::{Font, FontDescriptor};
@@ -19,6 +22,7 @@ pub struct MovieLibrary<'gc> {
     jpeg_tables: Option<Vec<u8>>,
     device_font: Option<Font<'gc>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
+    avm2_domain: Option<Avm2Domain<'gc>>,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -29,6 +33,7 @@ impl<'gc> MovieLibrary<'gc> {
             jpeg_tables: None,
             device_font: None,
             fonts: HashMap::new(),
+            avm2_domain: None,
         }
     }
 
@@ -132,6 +137,7 @@ impl<'gc> MovieLibrary<'gc> {
             Character::MovieClip(movie_clip) => Ok(movie_clip.instantiate(gc_context)),
             Character::Button(button) => Ok(button.instantiate(gc_context)),
             Character::Text(text) => Ok(text.instantiate(gc_context)),
+            Character::Video(video) => Ok(video.instantiate(gc_context)),
             _ => Err("Not a DisplayObject".into()),
         }
     }
@@ -145,6 +151,10 @@ impl<'gc> MovieLibrary<'gc> {
     }
 
     /// Find a font by it's name and parameters.
+    ///
+    /// Like Flash Player, if the requested bold or italic style of a font
+    /// isn't embedded, the regular style is used instead; the missing style
+    /// is not synthesized.
     pub fn get_font_by_name(
         &self,
         name: &str,
@@ -152,8 +162,27 @@ impl<'gc> MovieLibrary<'gc> {
         is_italic: bool,
     ) -> Option<Font<'gc>> {
         let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.fonts.get(&descriptor) {
+            return Some(*font);
+        }
+
+        if is_bold || is_italic {
+            let regular = FontDescriptor::from_parts(name, false, false);
+            if let Some(font) = self.fonts.get(&regular) {
+                log::warn!(
+                    "Font {} has no embedded {} style, using the regular style instead",
+                    name,
+                    match (is_bold, is_italic) {
+                        (true, true) => "bold italic",
+                        (true, false) => "bold",
+                        _ => "italic",
+                    }
+                );
+                return Some(*font);
+            }
+        }
 
-        self.fonts.get(&descriptor).copied()
+        None
     }
 
     pub fn get_sound(&self, id: CharacterId) -> Option<SoundHandle> {
@@ -193,6 +222,16 @@ impl<'gc> MovieLibrary<'gc> {
     pub fn set_device_font(&mut self, font: Option<Font<'gc>>) {
         self.device_font = font;
     }
+
+    /// Returns the AVM2 domain that this movie's scripts are loaded into.
+    pub fn avm2_domain(&self) -> Option<Avm2Domain<'gc>> {
+        self.avm2_domain
+    }
+
+    /// Sets the AVM2 domain that this movie's scripts are loaded into.
+    pub fn set_avm2_domain(&mut self, domain: Avm2Domain<'gc>) {
+        self.avm2_domain = Some(domain);
+    }
 }
 
 impl Default for MovieLibrary<'_> {
@@ -205,6 +244,13 @@ impl Default for MovieLibrary<'_> {
 pub struct Library<'gc> {
     /// All the movie libraries.
     movie_libraries: PtrWeakKeyHashMap<Weak<SwfMovie>, MovieLibrary<'gc>>,
+
+    /// System fonts loaded for device text, or `None` if loading the font failed.
+    device_fonts: HashMap<FontDescriptor, Option<Font<'gc>>>,
+
+    /// Fonts supplied by the host application for device text.
+    /// These take priority over the fonts of the font provider.
+    registered_device_fonts: HashMap<FontDescriptor, Font<'gc>>,
 }
 
 unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
@@ -213,6 +259,12 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
         for (_, val) in self.movie_libraries.iter() {
             val.trace(cc);
         }
+        for font in self.device_fonts.values() {
+            font.trace(cc);
+        }
+        for font in self.registered_device_fonts.values() {
+            font.trace(cc);
+        }
     }
 }
 
@@ -229,12 +281,77 @@ impl<'gc> Library<'gc> {
 
         self.movie_libraries.get_mut(&movie).unwrap()
     }
+
+    /// Returns the system font with the given name and style, for text that uses device fonts.
+    /// The font is loaded from the font provider the first time it is requested.
+    pub fn load_device_font(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        font_provider: &mut dyn FontProvider,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.registered_device_font(&descriptor) {
+            return Some(font);
+        }
+        if let Some(font) = self.device_fonts.get(&descriptor) {
+            return *font;
+        }
+
+        let font = font_provider
+            .load_device_font(name, is_bold, is_italic)
+            .and_then(|tag| match Font::from_swf_tag(gc_context, renderer, &tag) {
+                Ok(font) => Some(font),
+                Err(e) => {
+                    log::error!("Unable to load device font {}: {}", name, e);
+                    None
+                }
+            });
+        self.device_fonts.insert(descriptor, font);
+        font
+    }
+
+    /// Registers a font supplied by the host application as a device font with the given name.
+    /// The style of the font is taken from the font itself.
+    ///
+    /// Any previously registered font with the same name and style is replaced.
+    pub fn register_device_font(&mut self, name: &str, font: Font<'gc>) {
+        let font_descriptor = font.descriptor();
+        let descriptor =
+            FontDescriptor::from_parts(name, font_descriptor.bold(), font_descriptor.italic());
+        self.registered_device_fonts.insert(descriptor, font);
+    }
+
+    /// Returns the registered device font for the given descriptor.
+    ///
+    /// If no font with the exact style was registered, the regular style of the same family is
+    /// used instead, rather than falling back to a system font with different metrics.
+    fn registered_device_font(&self, descriptor: &FontDescriptor) -> Option<Font<'gc>> {
+        if let Some(font) = self.registered_device_fonts.get(descriptor) {
+            return Some(*font);
+        }
+
+        [(true, false), (false, true), (false, false)]
+            .iter()
+            .filter(|&&(is_bold, is_italic)| {
+                (!is_bold || descriptor.bold()) && (!is_italic || descriptor.italic())
+            })
+            .find_map(|&(is_bold, is_italic)| {
+                let fallback = FontDescriptor::from_parts(descriptor.class(), is_bold, is_italic);
+                self.registered_device_fonts.get(&fallback).copied()
+            })
+    }
 }
 
 impl<'gc> Default for Library<'gc> {
     fn default() -> Self {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
+            device_fonts: HashMap::new(),
+            registered_device_fonts: HashMap::new(),
         }
     }
 }
@@ -2,9 +2,12 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
+use crate::avm2::{Avm2, Domain as Avm2Domain, LoadedContentInfo, Object as Avm2Object};
 use crate::backend::navigator::OwnedFuture;
+use crate::backend::render::{decode_define_bits_jpeg, determine_jpeg_tag_format, JpegTagFormat};
 use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::display_object::{Bitmap, DisplayObject, MorphShape, MovieClip, TDisplayObject};
+use crate::events::ClipEvent;
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::tag_utils::SwfMovie;
 use crate::xml::XMLNode;
@@ -28,6 +31,9 @@ pub enum Error {
     #[error("Non-movie loader spawned as movie loader")]
     NotMovieLoader,
 
+    #[error("Non-AVM2 movie loader spawned as AVM2 movie loader")]
+    NotAvm2MovieLoader,
+
     #[error("Non-form loader spawned as form loader")]
     NotFormLoader,
 
@@ -43,18 +49,36 @@ pub enum Error {
     #[error("Invalid SWF")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
     #[error("Invalid XML encoding")]
     InvalidXmlEncoding(#[from] FromUtf8Error),
 
     #[error("Network error")]
     NetworkError(#[from] std::io::Error),
 
+    #[error("HTTP status {1} when fetching {0}")]
+    HttpNotOk(String, u16),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
     Avm1Error(String),
 }
 
+impl Error {
+    /// The HTTP status code to report to scripts for a failed load.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Error::HttpNotOk(_, status) => *status,
+            // Simulate 404 HTTP status. This should probably be fired elsewhere
+            // because a failed local load doesn't fire a 404.
+            _ => 404,
+        }
+    }
+}
+
 pub type FormLoadHandler<'gc> =
     fn(&mut Activation<'_, 'gc, '_>, Object<'gc>, data: &[u8]) -> Result<(), Error>;
 
@@ -109,6 +133,11 @@ impl<'gc> LoadManager<'gc> {
         self.0.get_mut(handle)
     }
 
+    /// Remove a loader whose load operation has finished.
+    pub fn remove_loader(&mut self, handle: Handle) -> Option<Loader<'gc>> {
+        self.0.remove(handle)
+    }
+
     /// Kick off the root movie load.
     ///
     /// The root movie is special because it determines a few bits of player
@@ -146,6 +175,8 @@ impl<'gc> LoadManager<'gc> {
             target_clip,
             target_broadcaster,
             load_complete: false,
+            bytes_loaded: 0,
+            bytes_total: 0,
         };
         let handle = self.add_loader(loader);
 
@@ -155,6 +186,48 @@ impl<'gc> LoadManager<'gc> {
         loader.movie_loader(player, fetch, url)
     }
 
+    /// Kick off a movie load into an AVM2 `Loader`.
+    ///
+    /// The movie's scripts are loaded into `domain`, and the load is reported
+    /// to the loader's `LoaderInfo`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_movie_into_loader_info(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_loader_info: Avm2Object<'gc>,
+        domain: Avm2Domain<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::Avm2Movie {
+            self_handle: None,
+            target_loader_info,
+            domain,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.avm2_movie_loader(player, fetch, url)
+    }
+
+    /// Returns the bytes loaded and total bytes of a load into the given clip,
+    /// if one is in progress.
+    pub fn movie_clip_progress(&self, clip: DisplayObject<'gc>) -> Option<(usize, usize)> {
+        self.0.iter().find_map(|(_, loader)| match loader {
+            Loader::Movie {
+                target_clip,
+                load_complete: false,
+                bytes_loaded,
+                bytes_total,
+                ..
+            } if DisplayObject::ptr_eq(*target_clip, clip) => Some((*bytes_loaded, *bytes_total)),
+            _ => None,
+        })
+    }
+
     /// Indicates that a movie clip has initialized (ran it's first frame).
     ///
     /// Interested loaders will be invoked from here.
@@ -250,6 +323,37 @@ impl<'gc> Default for LoadManager<'gc> {
     }
 }
 
+/// The content fetched by a movie loader.
+enum LoadedContent {
+    /// A movie, and the size of its file.
+    Movie(Arc<SwfMovie>, usize),
+
+    /// The data of a JPEG, PNG or GIF image.
+    Image(Vec<u8>),
+}
+
+impl LoadedContent {
+    /// The size of the loaded file.
+    fn len(&self) -> usize {
+        match self {
+            LoadedContent::Movie(_, length) => *length,
+            LoadedContent::Image(data) => data.len(),
+        }
+    }
+}
+
+/// The content fetched by an AVM2 movie loader.
+enum Avm2Content {
+    Movie(Arc<SwfMovie>),
+
+    /// An image, which has no scripts to run.
+    Image {
+        format: JpegTagFormat,
+        width: u32,
+        height: u32,
+    },
+}
+
 /// A struct that holds garbage-collected pointers for asynchronous code.
 pub enum Loader<'gc> {
     /// Loader that is loading the root movie of a player.
@@ -279,6 +383,24 @@ pub enum Loader<'gc> {
         /// or an error has occured (in which case we don't care about the
         /// loader anymore).
         load_complete: bool,
+
+        /// The number of bytes that have been loaded so far.
+        bytes_loaded: usize,
+
+        /// The size of the loaded file, or 0 if it is not yet known.
+        bytes_total: usize,
+    },
+
+    /// Loader that is loading a movie or image for an AVM2 `Loader`.
+    Avm2Movie {
+        /// The handle to refer to this loader instance.
+        self_handle: Option<Handle>,
+
+        /// The `LoaderInfo` of the `Loader` that started the load.
+        target_loader_info: Avm2Object<'gc>,
+
+        /// The domain that the movie's scripts are loaded into.
+        domain: Avm2Domain<'gc>,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -329,6 +451,14 @@ unsafe impl<'gc> Collect for Loader<'gc> {
                 target_clip.trace(cc);
                 target_broadcaster.trace(cc);
             }
+            Loader::Avm2Movie {
+                target_loader_info,
+                domain,
+                ..
+            } => {
+                target_loader_info.trace(cc);
+                domain.trace(cc);
+            }
             Loader::Form { target_object, .. } => target_object.trace(cc),
             Loader::LoadVars { target_object, .. } => target_object.trace(cc),
             Loader::XML { target_node, .. } => target_node.trace(cc),
@@ -345,6 +475,7 @@ impl<'gc> Loader<'gc> {
         match self {
             Loader::RootMovie { self_handle, .. } => *self_handle = Some(handle),
             Loader::Movie { self_handle, .. } => *self_handle = Some(handle),
+            Loader::Avm2Movie { self_handle, .. } => *self_handle = Some(handle),
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
             Loader::XML { self_handle, .. } => *self_handle = Some(handle),
@@ -399,6 +530,9 @@ impl<'gc> Loader<'gc> {
     ///
     /// If the loader is not a movie then the returned future will yield an
     /// error immediately once spawned.
+    ///
+    /// JPEG, PNG and GIF images are loaded as well as movies; the target clip
+    /// is emptied and the image is placed inside it as a bitmap.
     pub fn movie_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
@@ -431,13 +565,8 @@ impl<'gc> Loader<'gc> {
                         _ => unreachable!(),
                     };
 
-                    clip.as_movie_clip().unwrap().unload(uc);
-
-                    clip.as_movie_clip()
-                        .unwrap()
-                        .replace_with_movie(uc.gc_context, None);
-
-                    dbg!("movie_loader 440");
+                    // Any previous content of the clip is unloaded first.
+                    clip.as_movie_clip().unwrap().unload_movie(uc);
 
                     if let Some(broadcaster) = broadcaster {
                         Avm1::run_stack_frame_for_method(
@@ -446,28 +575,40 @@ impl<'gc> Loader<'gc> {
                             NEWEST_PLAYER_VERSION,
                             uc,
                             "broadcastMessage",
-                            &["onLoadStart".into(), Value::Object(broadcaster)],
+                            &["onLoadStart".into(), clip.object()],
                         );
                     }
 
                     Ok(())
                 })?;
 
-            let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
-            if let Ok((length, movie)) = data {
-                let movie = Arc::new(movie);
-
-                player
+            let content = match fetch.await {
+                Ok(data) if determine_jpeg_tag_format(&data) != JpegTagFormat::Unknown => {
+                    Ok(LoadedContent::Image(data))
+                }
+                Ok(data) => SwfMovie::from_data(&data, Some(url.clone()))
+                    .map(|movie| LoadedContent::Movie(Arc::new(movie), data.len()))
+                    .map_err(|_| ("LoadNeverCompleted", 200)),
+                Err(e) => Err(("URLNotFound", e.http_status())),
+            };
+
+            match content {
+                Ok(content) => player
                     .lock()
                     .expect("Could not lock player!!")
                     .update(|uc| {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+                        let (clip, broadcaster) = match uc.load_manager.get_loader_mut(handle) {
                             Some(Loader::Movie {
                                 target_clip,
                                 target_broadcaster,
+                                bytes_loaded,
+                                bytes_total,
                                 ..
-                            }) => (*target_clip, *target_broadcaster),
+                            }) => {
+                                *bytes_loaded = content.len();
+                                *bytes_total = content.len();
+                                (*target_clip, *target_broadcaster)
+                            }
                             None => return Err(Error::Cancelled),
                             _ => unreachable!(),
                         };
@@ -481,9 +622,9 @@ impl<'gc> Loader<'gc> {
                                 "broadcastMessage",
                                 &[
                                     "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
+                                    clip.object(),
+                                    content.len().into(),
+                                    content.len().into(),
                                 ],
                             );
                         }
@@ -492,21 +633,53 @@ impl<'gc> Loader<'gc> {
                             .as_movie_clip()
                             .expect("Attempted to load movie into not movie clip");
 
-                        mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
-                        mc.post_instantiation(uc, clip, None, false, false);
-
-                        let mut morph_shapes = fnv::FnvHashMap::default();
-                        mc.preload(uc, &mut morph_shapes);
-
-                        // Finalize morph shapes.
-                        for (id, static_data) in morph_shapes {
-                            let morph_shape = MorphShape::new(uc.gc_context, static_data);
-                            uc.library
-                                .library_for_movie_mut(movie.clone())
-                                .register_character(
-                                    id,
-                                    crate::character::Character::MorphShape(morph_shape),
-                                );
+                        match &content {
+                            LoadedContent::Movie(movie, _) => {
+                                // A movie replacing `_level0` takes over the stage, frame rate included.
+                                if uc
+                                    .levels
+                                    .get(&0)
+                                    .map_or(false, |level| DisplayObject::ptr_eq(*level, clip))
+                                {
+                                    *uc.frame_rate =
+                                        Player::clamp_frame_rate(movie.header().frame_rate.into());
+                                }
+
+                                mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
+                                mc.post_instantiation(uc, clip, None, false, false);
+
+                                let mut morph_shapes = fnv::FnvHashMap::default();
+                                mc.preload(uc, &mut morph_shapes);
+
+                                // Finalize morph shapes.
+                                for (id, static_data) in morph_shapes {
+                                    let morph_shape = MorphShape::new(uc.gc_context, static_data);
+                                    uc.library
+                                        .library_for_movie_mut(movie.clone())
+                                        .register_character(
+                                            id,
+                                            crate::character::Character::MorphShape(morph_shape),
+                                        );
+                                }
+                            }
+                            LoadedContent::Image(data) => {
+                                mc.replace_with_movie(uc.gc_context, None);
+                                mc.post_instantiation(uc, clip, None, false, false);
+
+                                match uc.renderer.register_bitmap_jpeg_2(0, data) {
+                                    Ok(info) => {
+                                        let bitmap = Bitmap::new(
+                                            uc,
+                                            0,
+                                            info.handle,
+                                            info.width,
+                                            info.height,
+                                        );
+                                        mc.add_child_from_avm(uc, bitmap.into(), 0);
+                                    }
+                                    Err(e) => log::error!("Unable to decode image {}: {}", url, e),
+                                }
+                            }
                         }
 
                         if let Some(broadcaster) = broadcaster {
@@ -516,10 +689,11 @@ impl<'gc> Loader<'gc> {
                                 NEWEST_PLAYER_VERSION,
                                 uc,
                                 "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
+                                &["onLoadComplete".into(), clip.object(), 200.into()],
                             );
                         }
 
+                        // `onLoadInit` is sent once the new content has run its first frame.
                         if let Some(Loader::Movie { load_complete, .. }) =
                             uc.load_manager.get_loader_mut(handle)
                         {
@@ -527,14 +701,11 @@ impl<'gc> Loader<'gc> {
                         };
 
                         Ok(())
-                    })
-            } else {
+                    }),
                 //TODO: Inspect the fetch error.
                 //This requires cooperation from the backend to send abstract
                 //error types we can actually inspect.
-                //This also can get errors from decoding an invalid SWF file,
-                //too. We should distinguish those to player code.
-                player
+                Err((error_code, status)) => player
                     .lock()
                     .expect("Could not lock player!!")
                     .update(|uc| -> Result<(), Error> {
@@ -557,18 +728,175 @@ impl<'gc> Loader<'gc> {
                                 "broadcastMessage",
                                 &[
                                     "onLoadError".into(),
-                                    Value::Object(broadcaster),
-                                    "LoadNeverCompleted".into(),
+                                    clip.object(),
+                                    error_code.into(),
+                                    status.into(),
                                 ],
                             );
                         }
 
-                        if let Some(Loader::Movie { load_complete, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *load_complete = true;
+                        // Nothing was loaded, so there is no `onLoadInit` to wait for.
+                        uc.load_manager.remove_loader(handle);
+
+                        Ok(())
+                    }),
+            }
+        })
+    }
+
+    /// Construct a future for the given AVM2 movie loader.
+    ///
+    /// The `LoaderInfo` is sent `open` and `progress` once the file has been
+    /// fetched. A movie is then preloaded into a movie clip that is not part
+    /// of the display list, which runs its scripts in the loader's domain.
+    /// Once they have run, or once an image has been decoded, the
+    /// `LoaderInfo` is told that the load has completed.
+    pub fn avm2_movie_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        mut url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::Avm2Movie { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotAvm2MovieLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    url = uc.navigator.resolve_relative_url(&url).into_owned();
+
+                    Ok(())
+                })?;
+
+            let data = (fetch.await).and_then(|data| {
+                let content = match determine_jpeg_tag_format(&data) {
+                    JpegTagFormat::Unknown => {
+                        let movie = Arc::new(SwfMovie::from_data(&data, Some(url.clone()))?);
+                        Avm2Content::Movie(movie)
+                    }
+                    format => {
+                        let bitmap = decode_define_bits_jpeg(&data, None)
+                            .map_err(|e| Error::InvalidImage(e.to_string()))?;
+                        Avm2Content::Image {
+                            format,
+                            width: bitmap.width,
+                            height: bitmap.height,
+                        }
+                    }
+                };
+                Ok((data, content))
+            });
+            if let Ok((data, content)) = data {
+                player
+                    .lock()
+                    .expect("Could not lock player!!")
+                    .update(|uc| {
+                        let (loader_info, domain) = match uc.load_manager.get_loader(handle) {
+                            Some(Loader::Avm2Movie {
+                                target_loader_info,
+                                domain,
+                                ..
+                            }) => (*target_loader_info, *domain),
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        };
+
+                        Avm2::loader_info_open(uc, loader_info, &url, data.len());
+
+                        if let Avm2Content::Movie(movie) = &content {
+                            uc.library
+                                .library_for_movie_mut(movie.clone())
+                                .set_avm2_domain(domain);
+
+                            // TODO: The loaded movie should become the `content`
+                            // of the `Loader`, once AVM2 display objects are
+                            // backed by real display objects.
+                            let mc = MovieClip::from_movie(uc.gc_context, movie.clone());
+                            let mut morph_shapes = fnv::FnvHashMap::default();
+                            mc.preload(uc, &mut morph_shapes);
+
+                            // Finalize morph shapes.
+                            for (id, static_data) in morph_shapes {
+                                let morph_shape = MorphShape::new(uc.gc_context, static_data);
+                                uc.library
+                                    .library_for_movie_mut(movie.clone())
+                                    .register_character(
+                                        id,
+                                        crate::character::Character::MorphShape(morph_shape),
+                                    );
+                            }
+                        }
+
+                        Ok(())
+                    })?;
+
+                // The movie's scripts run at the end of the previous update,
+                // so its definitions are available by now.
+                player
+                    .lock()
+                    .expect("Could not lock player!!")
+                    .update(|uc| {
+                        let (loader_info, domain) = match uc.load_manager.get_loader(handle) {
+                            Some(Loader::Avm2Movie {
+                                target_loader_info,
+                                domain,
+                                ..
+                            }) => (*target_loader_info, *domain),
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
                         };
 
+                        let info = match &content {
+                            Avm2Content::Movie(movie) => LoadedContentInfo {
+                                data: &data,
+                                content_type: "application/x-shockwave-flash",
+                                width: movie.width(),
+                                height: movie.height(),
+                            },
+                            Avm2Content::Image {
+                                format,
+                                width,
+                                height,
+                            } => LoadedContentInfo {
+                                data: &data,
+                                content_type: match format {
+                                    JpegTagFormat::Png => "image/png",
+                                    JpegTagFormat::Gif => "image/gif",
+                                    _ => "image/jpeg",
+                                },
+                                width: *width,
+                                height: *height,
+                            },
+                        };
+                        Avm2::loader_info_complete(uc, loader_info, &url, info, domain);
+
+                        Ok(())
+                    })
+            } else {
+                player
+                    .lock()
+                    .expect("Could not lock player!!")
+                    .update(|uc| {
+                        let loader_info = match uc.load_manager.get_loader(handle) {
+                            Some(Loader::Avm2Movie {
+                                target_loader_info, ..
+                            }) => *target_loader_info,
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        };
+
+                        Avm2::loader_info_error(uc, loader_info, &url);
+
                         Ok(())
                     })
             }
@@ -606,14 +934,20 @@ impl<'gc> Loader<'gc> {
                     ActivationIdentifier::root("[Form Loader]"),
                 );
 
-                for (k, v) in form_urlencoded::parse(&data) {
+                let use_codepage = activation.context.system.use_codepage;
+                for (k, v) in parse_form_data(&data, use_codepage) {
                     that.set(
                         &k,
-                        AvmString::new(activation.context.gc_context, v.into_owned()).into(),
+                        AvmString::new(activation.context.gc_context, v).into(),
                         &mut activation,
                     )?;
                 }
 
+                // Clips that variables are loaded into receive `onClipEvent(data)`.
+                if let Some(clip) = that.as_display_object() {
+                    clip.handle_clip_event(&mut activation.context, ClipEvent::Data);
+                }
+
                 Ok(())
             })
         })
@@ -656,17 +990,17 @@ impl<'gc> Loader<'gc> {
                 match data {
                     Ok(data) => {
                         // Fire the onData method with the loaded string.
+                        let use_codepage = activation.context.system.use_codepage;
                         let string_data = AvmString::new(
                             activation.context.gc_context,
-                            String::from_utf8_lossy(&data),
+                            decode_text(&data, use_codepage),
                         );
                         let _ = that.call_method("onData", &[string_data.into()], &mut activation);
                     }
-                    Err(_) => {
+                    Err(e) => {
                         // TODO: Log "Error opening URL" trace similar to the Flash Player?
-                        // Simulate 404 HTTP status. This should probably be fired elsewhere
-                        // because a failed local load doesn't fire a 404.
-                        let _ = that.call_method("onHTTPStatus", &[404.into()], &mut activation);
+                        let status = e.http_status();
+                        let _ = that.call_method("onHTTPStatus", &[status.into()], &mut activation);
 
                         // Fire the onData method with no data to indicate an unsuccessful load.
                         let _ = that.call_method("onData", &[Value::Undefined], &mut activation);
@@ -738,84 +1072,200 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let data = fetch.await;
-            if let Ok(data) = data {
-                let xmlstring = String::from_utf8(data)?;
-
-                player.lock().expect("Could not lock player!!").update(
-                    |uc| -> Result<(), Error> {
-                        let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::XML {
-                                target_node,
+            match data {
+                Ok(data) => {
+                    let xmlstring = String::from_utf8(data)?;
+
+                    player.lock().expect("Could not lock player!!").update(
+                        |uc| -> Result<(), Error> {
+                            let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
+                                Some(Loader::XML {
+                                    target_node,
+                                    active_clip,
+                                    ..
+                                }) => (*target_node, *active_clip),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+
+                            let object = node
+                                .script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
+                            Avm1::run_stack_frame_for_method(
                                 active_clip,
-                                ..
-                            }) => (*target_node, *active_clip),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        let object =
-                            node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onHTTPStatus",
-                            &[200.into()],
-                        );
-
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onData",
-                            &[AvmString::new(uc.gc_context, xmlstring).into()],
-                        );
+                                object,
+                                NEWEST_PLAYER_VERSION,
+                                uc,
+                                "onHTTPStatus",
+                                &[200.into()],
+                            );
 
-                        Ok(())
-                    },
-                )?;
-            } else {
-                player.lock().expect("Could not lock player!!").update(
-                    |uc| -> Result<(), Error> {
-                        let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::XML {
-                                target_node,
+                            Avm1::run_stack_frame_for_method(
                                 active_clip,
-                                ..
-                            }) => (*target_node, *active_clip),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
+                                object,
+                                NEWEST_PLAYER_VERSION,
+                                uc,
+                                "onData",
+                                &[AvmString::new(uc.gc_context, xmlstring).into()],
+                            );
 
-                        let object =
-                            node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
+                            Ok(())
+                        },
+                    )?;
+                }
+                Err(e) => {
+                    let status = e.http_status();
+                    player.lock().expect("Could not lock player!!").update(
+                        |uc| -> Result<(), Error> {
+                            let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
+                                Some(Loader::XML {
+                                    target_node,
+                                    active_clip,
+                                    ..
+                                }) => (*target_node, *active_clip),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+
+                            let object = node
+                                .script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
 
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onHTTPStatus",
-                            &[404.into()],
-                        );
+                            Avm1::run_stack_frame_for_method(
+                                active_clip,
+                                object,
+                                NEWEST_PLAYER_VERSION,
+                                uc,
+                                "onHTTPStatus",
+                                &[status.into()],
+                            );
 
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onData",
-                            &[],
-                        );
+                            Avm1::run_stack_frame_for_method(
+                                active_clip,
+                                object,
+                                NEWEST_PLAYER_VERSION,
+                                uc,
+                                "onData",
+                                &[],
+                            );
 
-                        Ok(())
-                    },
-                )?;
+                            Ok(())
+                        },
+                    )?;
+                }
             }
 
             Ok(())
         })
     }
 }
+
+/// Decodes loaded text, which is UTF-8 unless `System.useCodepage` is set.
+///
+/// The system codepage is always taken to be Latin-1.
+fn decode_text(data: &[u8], use_codepage: bool) -> String {
+    if use_codepage {
+        data.iter().map(|&byte| byte as char).collect()
+    } else {
+        String::from_utf8_lossy(data).into_owned()
+    }
+}
+
+/// Parses loaded variables in `application/x-www-form-urlencoded` format, decoding escaped
+/// text as in `decode_text`.
+fn parse_form_data(data: &[u8], use_codepage: bool) -> Vec<(String, String)> {
+    if !use_codepage {
+        return form_urlencoded::parse(data)
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+    }
+
+    let decode = |text: &[u8]| {
+        let text: Vec<u8> = text
+            .iter()
+            .map(|&byte| if byte == b'+' { b' ' } else { byte })
+            .collect();
+        let text: Vec<u8> = percent_encoding::percent_decode(&text).collect();
+        decode_text(&text, true)
+    };
+
+    data.split(|&byte| byte == b'&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, |&byte| byte == b'=');
+            let name = parts.next().unwrap_or_default();
+            let value = parts.next().unwrap_or_default();
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+/// Encodes variables in `application/x-www-form-urlencoded` format, to be sent with a request.
+///
+/// With `System.useCodepage`, text is encoded in the system codepage (Latin-1) rather than
+/// UTF-8, and characters outside of it are replaced with `?`.
+pub fn encode_form_data<'a>(
+    vars: impl IntoIterator<Item = (&'a String, &'a String)>,
+    use_codepage: bool,
+) -> String {
+    if !use_codepage {
+        return form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(vars)
+            .finish();
+    }
+
+    let encode = |text: &str| {
+        let bytes: Vec<u8> = text
+            .chars()
+            .map(|c| if (c as u32) <= 0xFF { c as u8 } else { b'?' })
+            .collect();
+        form_urlencoded::byte_serialize(&bytes).collect::<String>()
+    };
+
+    vars.into_iter()
+        .map(|(name, value)| format!("{}={}", encode(name), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_data_encoding() {
+        let vars = vec![
+            ("name".to_string(), "café crème".to_string()),
+            ("score".to_string(), "10 & up".to_string()),
+            ("check".to_string(), "✓".to_string()),
+        ];
+        let vars = vars.iter().map(|(k, v)| (k, v));
+        assert_eq!(
+            encode_form_data(vars.clone(), false),
+            "name=caf%C3%A9+cr%C3%A8me&score=10+%26+up&check=%E2%9C%93"
+        );
+        assert_eq!(
+            encode_form_data(vars, true),
+            "name=caf%E9+cr%E8me&score=10+%26+up&check=%3F"
+        );
+    }
+
+    #[test]
+    fn form_data_in_codepage() {
+        let data = b"name=caf%E9+cr%C3%A8me&empty=&flag";
+        assert_eq!(
+            parse_form_data(data, false),
+            vec![
+                ("name".to_string(), "caf\u{fffd} crème".to_string()),
+                ("empty".to_string(), "".to_string()),
+                ("flag".to_string(), "".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_form_data(data, true),
+            vec![
+                ("name".to_string(), "café crÃ¨me".to_string()),
+                ("empty".to_string(), "".to_string()),
+                ("flag".to_string(), "".to_string()),
+            ]
+        );
+    }
+}
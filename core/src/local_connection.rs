@@ -0,0 +1,232 @@
+//! Message passing between movies for `LocalConnection`.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::{Avm1, AvmString, Object, ScriptObject, TObject, Value};
+use crate::context::UpdateContext;
+use gc_arena::{Collect, CollectionContext};
+use std::collections::HashMap;
+
+/// A message sent with `LocalConnection.send`, waiting to be delivered.
+struct Message<'gc> {
+    /// The `LocalConnection` object that sent this message.
+    sender: Object<'gc>,
+
+    /// The domain of the movie that sent this message.
+    sender_domain: String,
+
+    /// The full name of the receiving connection.
+    connection_name: String,
+
+    /// The method to call on the receiver.
+    method: String,
+
+    args: Vec<Value<'gc>>,
+}
+
+/// An object listening for messages with `LocalConnection.connect`.
+struct Connection<'gc> {
+    receiver: Object<'gc>,
+
+    /// The domain of the movie that opened this connection.
+    domain: String,
+}
+
+/// Manages the connections opened by `LocalConnection` objects, and the
+/// messages sent between them.
+pub struct LocalConnections<'gc> {
+    /// Open connections, keyed by their full name.
+    connections: HashMap<String, Connection<'gc>>,
+
+    /// Messages to be delivered on the next update.
+    messages: Vec<Message<'gc>>,
+}
+
+unsafe impl<'gc> Collect for LocalConnections<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for connection in self.connections.values() {
+            connection.receiver.trace(cc);
+        }
+        for message in &self.messages {
+            message.sender.trace(cc);
+            message.args.trace(cc);
+        }
+    }
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn new() -> Self {
+        Self {
+            connections: HashMap::new(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Start listening for messages sent to `name` on `receiver`.
+    ///
+    /// Returns `false` if another object is already listening on `name`, or
+    /// if `receiver` is already connected.
+    pub fn connect(&mut self, name: String, receiver: Object<'gc>, domain: String) -> bool {
+        if self.connections.contains_key(&name) || self.find(receiver).is_some() {
+            return false;
+        }
+
+        self.connections
+            .insert(name, Connection { receiver, domain });
+        true
+    }
+
+    /// Stop listening for messages on `receiver`.
+    ///
+    /// Returns `false` if `receiver` was not connected.
+    pub fn close(&mut self, receiver: Object<'gc>) -> bool {
+        match self.find(receiver) {
+            Some(name) => {
+                self.connections.remove(&name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Queue a call of `method` on the receiver of `connection_name`.
+    ///
+    /// The call happens on the next update, after which the `onStatus` method
+    /// of `sender` is told whether the message was delivered.
+    pub fn send(
+        &mut self,
+        sender: Object<'gc>,
+        sender_domain: String,
+        connection_name: String,
+        method: String,
+        args: Vec<Value<'gc>>,
+    ) {
+        self.messages.push(Message {
+            sender,
+            sender_domain,
+            connection_name,
+            method,
+            args,
+        });
+    }
+
+    /// Find the connection name used by the given receiver.
+    fn find(&self, receiver: Object<'gc>) -> Option<String> {
+        self.connections
+            .iter()
+            .find(|(_, connection)| Object::ptr_eq(connection.receiver, receiver))
+            .map(|(name, _)| name.clone())
+    }
+
+    /// Deliver all messages sent since the last update.
+    pub fn update_local_connections(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let root = match context.levels.get(&0).copied() {
+            Some(root) => root,
+            None => return,
+        };
+        if context.avm1.halted() {
+            return;
+        }
+
+        let messages = std::mem::take(&mut context.local_connections.messages);
+        for message in messages {
+            let connection = context
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+                .map(|connection| (connection.receiver, connection.domain.clone()));
+            let swf_version = context.swf.version();
+            let globals = context.avm1.global_object_cell();
+
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[LocalConnection]"),
+                swf_version,
+                globals,
+                root,
+            );
+
+            let delivered = match connection {
+                Some((receiver, domain)) => {
+                    let allowed = domain == message.sender_domain
+                        || allow_domain(&mut activation, receiver, &message.sender_domain);
+                    if allowed {
+                        let _ =
+                            receiver.call_method(&message.method, &message.args, &mut activation);
+                    }
+                    allowed
+                }
+                None => false,
+            };
+
+            let info: Object<'gc> = ScriptObject::object(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            )
+            .into();
+            let level = if delivered { "status" } else { "error" };
+            let _ = info.set("level", level.into(), &mut activation);
+
+            drop(activation);
+            Avm1::run_stack_frame_for_method(
+                root,
+                message.sender,
+                swf_version,
+                context,
+                "onStatus",
+                &[info.into()],
+            );
+        }
+    }
+}
+
+impl<'gc> Default for LocalConnections<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ask the `allowDomain` method of `receiver` whether it accepts messages
+/// from `domain`.
+fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    receiver: Object<'gc>,
+    domain: &str,
+) -> bool {
+    let domain = AvmString::new(activation.context.gc_context, domain.to_string());
+    match receiver.call_method("allowDomain", &[domain.into()], activation) {
+        Ok(Value::Undefined) | Err(_) => false,
+        Ok(result) => result.as_bool(activation.current_swf_version()),
+    }
+}
+
+/// The full name of a connection opened or addressed from a movie on
+/// `domain`.
+///
+/// Names starting with an underscore are shared across all domains. When
+/// `allow_prefixed` is set, names already containing a domain prefix are
+/// used as-is.
+pub fn qualified_name(domain: &str, name: &str, allow_prefixed: bool) -> String {
+    if name.starts_with('_') || (allow_prefixed && name.contains(':')) {
+        name.to_string()
+    } else {
+        format!("{}:{}", domain, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connection_names() {
+        assert_eq!(
+            qualified_name("example.com", "foo", false),
+            "example.com:foo"
+        );
+        assert_eq!(qualified_name("example.com", "_foo", false), "_foo");
+        assert_eq!(
+            qualified_name("example.com", "other.com:foo", true),
+            "other.com:foo"
+        );
+    }
+}
@@ -0,0 +1,745 @@
+//! Playback of external video files for `NetStream`.
+//!
+//! The same playback engine is shared by the AVM1 and AVM2 `NetStream` classes. They only
+//! differ in how the stream's events are sent to the movie.
+
+use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
+use crate::avm1::{
+    AvmString as Avm1String, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::{send_net_stream_events, Object as Avm2Object};
+use crate::backend::navigator::{OwnedFuture, RequestOptions};
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use crate::backend::video::{EncodedFrame, VideoBackend, VideoStreamHandle};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, Video};
+use crate::flv::{self, ScriptDataValue, TagKind, VideoTag};
+use crate::loader;
+use gc_arena::{Collect, CollectionContext, MutationContext};
+use generational_arena::{Arena, Index};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use swf::VideoDeblocking;
+
+pub type NetStreamHandle = Index;
+
+/// The result of downloading the file played by a stream.
+struct LoadResult {
+    handle: NetStreamHandle,
+
+    /// The `play_id` of the stream when the download was started.
+    play_id: u32,
+
+    data: Result<Vec<u8>, loader::Error>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PlaybackState {
+    /// `play` hasn't been called yet.
+    Idle,
+
+    /// The file is being downloaded.
+    Loading,
+
+    Playing,
+
+    /// Playback has reached the end of the file, or the file failed to load.
+    Stopped,
+}
+
+/// A tag of the file being played.
+struct StreamTag {
+    kind: TagKind,
+
+    /// The time at which this tag is played, in milliseconds.
+    timestamp: u32,
+
+    /// The location of the tag's data in the file.
+    start: usize,
+    end: usize,
+
+    /// Whether this is a video keyframe, where playback can start after a seek.
+    keyframe: bool,
+}
+
+/// An event to be sent to the movie on the next update.
+pub enum StreamEvent {
+    /// Report a status change with the given code and level, like `NetStream.Play.Start`.
+    ///
+    /// AVM1 calls the `onStatus` method of the stream, while AVM2 dispatches a
+    /// `NetStatusEvent`.
+    Status(&'static str, &'static str),
+
+    /// Call a method of the stream's client, as stored in a script data tag.
+    Call(String, ScriptDataValue),
+}
+
+/// The ActionScript object of a `NetStream`, which receives its events.
+#[derive(Clone, Copy, Debug, Collect)]
+#[collect(no_drop)]
+pub enum NetStreamObject<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+impl<'gc> NetStreamObject<'gc> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (NetStreamObject::Avm1(a), NetStreamObject::Avm1(b)) => Avm1Object::ptr_eq(a, b),
+            (NetStreamObject::Avm2(a), NetStreamObject::Avm2(b)) => Avm2Object::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl<'gc> From<Avm1Object<'gc>> for NetStreamObject<'gc> {
+    fn from(object: Avm1Object<'gc>) -> Self {
+        NetStreamObject::Avm1(object)
+    }
+}
+
+impl<'gc> From<Avm2Object<'gc>> for NetStreamObject<'gc> {
+    fn from(object: Avm2Object<'gc>) -> Self {
+        NetStreamObject::Avm2(object)
+    }
+}
+
+/// The playback state of a single `NetStream`.
+struct NetStream<'gc> {
+    /// The `NetStream` object that receives this stream's events.
+    target: NetStreamObject<'gc>,
+
+    /// The object whose methods are called by script data tags, like `onMetaData`.
+    /// `None` if they are called on the `NetStream` itself, which is always the case in AVM1.
+    client: Option<Avm2Object<'gc>>,
+
+    state: PlaybackState,
+    paused: bool,
+
+    /// Incremented whenever `play` is called, so that downloads of older files can be ignored.
+    play_id: u32,
+
+    /// The contents of the file being played.
+    data: Vec<u8>,
+
+    /// The tags of the file, in the order they are played.
+    tags: Vec<StreamTag>,
+
+    /// The index of the first tag that hasn't been played yet.
+    next_tag: usize,
+
+    /// The current playback position, in milliseconds.
+    time: f64,
+
+    /// The amount of video to buffer before playback starts, in seconds.
+    buffer_time: f64,
+
+    /// The size of the video, as given by the `onMetaData` information.
+    video_size: (u16, u16),
+
+    /// The decoder of the video, created when the first frame is played.
+    /// `Err` if the video backend can't decode this video.
+    video_stream: Option<Result<VideoStreamHandle, ()>>,
+
+    /// The last decoded frame of the video.
+    bitmap: Option<BitmapInfo>,
+
+    /// Whether a new frame was decoded since the attached videos were last updated.
+    frame_changed: bool,
+
+    /// The `Video` display objects that show this stream.
+    videos: Vec<Video<'gc>>,
+
+    /// Events waiting to be sent to the movie.
+    events: Vec<StreamEvent>,
+}
+
+impl<'gc> NetStream<'gc> {
+    /// Forget the file being played.
+    fn reset(&mut self, video: &mut dyn VideoBackend) {
+        if let Some(Ok(stream)) = self.video_stream.take() {
+            video.remove_video_stream(stream);
+        }
+        self.state = PlaybackState::Idle;
+        self.paused = false;
+        self.data = Vec::new();
+        self.tags.clear();
+        self.next_tag = 0;
+        self.time = 0.0;
+        self.video_size = (0, 0);
+    }
+
+    /// Start playing a downloaded file.
+    fn load(&mut self, data: Result<Vec<u8>, loader::Error>) {
+        let data = match data {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!("Unable to load NetStream video: {}", e);
+                self.state = PlaybackState::Stopped;
+                self.events.push(StreamEvent::Status(
+                    "NetStream.Play.StreamNotFound",
+                    "error",
+                ));
+                return;
+            }
+        };
+
+        let header = match flv::Header::parse(&data) {
+            Ok(header) => header,
+            Err(e) => {
+                // TODO: MP4 files need an H.264 decoder, which no video backend has yet.
+                log::warn!("Unable to play NetStream video: {}", e);
+                self.state = PlaybackState::Stopped;
+                self.events.push(StreamEvent::Status(
+                    "NetStream.Play.FileStructureInvalid",
+                    "error",
+                ));
+                return;
+            }
+        };
+        if header.has_audio {
+            log::warn!("NetStream audio playback is not yet implemented");
+        }
+
+        let base = data.as_ptr() as usize;
+        self.tags = flv::Reader::new(&data, &header)
+            .map(|tag| {
+                let start = tag.data.as_ptr() as usize - base;
+                let keyframe = tag.kind == TagKind::Video
+                    && VideoTag::parse(tag.data).map_or(false, |video| video.is_keyframe());
+                StreamTag {
+                    kind: tag.kind,
+                    timestamp: tag.timestamp,
+                    start,
+                    end: start + tag.data.len(),
+                    keyframe,
+                }
+            })
+            .collect();
+        self.data = data;
+        self.state = PlaybackState::Playing;
+        self.events
+            .push(StreamEvent::Status("NetStream.Buffer.Full", "status"));
+    }
+
+    /// Play every tag up to the current time.
+    fn advance(&mut self, dt: f64, video: &mut dyn VideoBackend, renderer: &mut dyn RenderBackend) {
+        if self.state != PlaybackState::Playing || self.paused {
+            return;
+        }
+
+        self.time += dt;
+        while let Some(tag) = self.tags.get(self.next_tag) {
+            if f64::from(tag.timestamp) > self.time {
+                break;
+            }
+
+            let index = self.next_tag;
+            self.next_tag += 1;
+            match tag.kind {
+                TagKind::Video => self.decode_video_tag(index, video, renderer),
+                TagKind::ScriptData => {
+                    match flv::parse_script_data(&self.data[tag.start..tag.end]) {
+                        Ok((name, value)) => {
+                            if name == "onMetaData" {
+                                self.read_metadata(&value);
+                            }
+                            self.events.push(StreamEvent::Call(name, value));
+                        }
+                        Err(e) => log::warn!("Invalid NetStream script data: {}", e),
+                    }
+                }
+                // Audio isn't played yet.
+                TagKind::Audio | TagKind::Unknown(_) => {}
+            }
+        }
+
+        if self.next_tag >= self.tags.len() {
+            if let Some(last) = self.tags.last() {
+                self.time = f64::from(last.timestamp);
+            }
+            self.state = PlaybackState::Stopped;
+            self.events
+                .push(StreamEvent::Status("NetStream.Play.Stop", "status"));
+            self.events
+                .push(StreamEvent::Status("NetStream.Buffer.Empty", "status"));
+        }
+    }
+
+    fn read_metadata(&mut self, metadata: &ScriptDataValue) {
+        let dimension = |name| {
+            metadata
+                .get(name)
+                .and_then(|value| value.as_number())
+                .map_or(0, |n| n as u16)
+        };
+        self.video_size = (dimension("width"), dimension("height"));
+    }
+
+    fn decode_video_tag(
+        &mut self,
+        index: usize,
+        video: &mut dyn VideoBackend,
+        renderer: &mut dyn RenderBackend,
+    ) {
+        let tag = &self.tags[index];
+        let video_tag = match VideoTag::parse(&self.data[tag.start..tag.end]) {
+            Some(video_tag) if video_tag.frame_type != 5 => video_tag,
+            _ => return,
+        };
+        let codec = match video_tag.codec {
+            Some(codec) => codec,
+            None => {
+                if self.video_stream.is_none() {
+                    log::warn!("Unsupported NetStream video codec {}", video_tag.codec_id);
+                    self.video_stream = Some(Err(()));
+                }
+                return;
+            }
+        };
+
+        let stream = match self.video_stream {
+            Some(Ok(stream)) => stream,
+            Some(Err(())) => return,
+            None => {
+                let num_frames = self
+                    .tags
+                    .iter()
+                    .filter(|tag| tag.kind == TagKind::Video)
+                    .count();
+                match video.register_video_stream(
+                    num_frames as u32,
+                    self.video_size,
+                    codec,
+                    VideoDeblocking::UseVideoPacketValue,
+                ) {
+                    Ok(stream) => {
+                        self.video_stream = Some(Ok(stream));
+                        stream
+                    }
+                    Err(e) => {
+                        log::warn!("Unable to play NetStream video: {}", e);
+                        self.video_stream = Some(Err(()));
+                        return;
+                    }
+                }
+            }
+        };
+
+        let encoded_frame = EncodedFrame {
+            codec,
+            data: video_tag.data,
+            frame_id: index as u16,
+        };
+        match video.decode_video_stream_frame(stream, encoded_frame, renderer) {
+            Ok(bitmap) => {
+                self.bitmap = Some(bitmap);
+                self.frame_changed = true;
+            }
+            Err(e) => log::warn!("Unable to decode NetStream video frame: {}", e),
+        }
+    }
+
+    /// Move the playback position to the keyframe at or before the given time.
+    fn seek(&mut self, time: f64) {
+        if self.state == PlaybackState::Idle || self.state == PlaybackState::Loading {
+            return;
+        }
+
+        self.next_tag = self
+            .tags
+            .iter()
+            .rposition(|tag| tag.keyframe && f64::from(tag.timestamp) <= time)
+            .unwrap_or(0);
+        self.time = self
+            .tags
+            .get(self.next_tag)
+            .map_or(0.0, |tag| f64::from(tag.timestamp));
+        self.state = PlaybackState::Playing;
+        self.events
+            .push(StreamEvent::Status("NetStream.Seek.Notify", "status"));
+    }
+
+    /// The amount of video that is loaded but hasn't been played yet, in milliseconds.
+    fn buffer_length(&self) -> f64 {
+        match (self.state, self.tags.last()) {
+            (PlaybackState::Playing, Some(last)) => {
+                (f64::from(last.timestamp) - self.time).max(0.0)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Manages the playback of all `NetStream`s created by the movie.
+pub struct NetStreams<'gc> {
+    streams: Arena<NetStream<'gc>>,
+
+    /// Finished downloads, to be processed on the next update.
+    receiver: Receiver<LoadResult>,
+    sender: Sender<LoadResult>,
+}
+
+unsafe impl<'gc> Collect for NetStreams<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for (_, stream) in self.streams.iter() {
+            stream.target.trace(cc);
+            stream.client.trace(cc);
+            stream.videos.trace(cc);
+        }
+    }
+}
+
+impl<'gc> NetStreams<'gc> {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            streams: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Start tracking the playback state of a newly constructed `NetStream`.
+    pub fn add_stream(&mut self, target: NetStreamObject<'gc>) {
+        self.streams.insert(NetStream {
+            target,
+            client: None,
+            state: PlaybackState::Idle,
+            paused: false,
+            play_id: 0,
+            data: Vec::new(),
+            tags: Vec::new(),
+            next_tag: 0,
+            time: 0.0,
+            buffer_time: 0.1,
+            video_size: (0, 0),
+            video_stream: None,
+            bitmap: None,
+            frame_changed: false,
+            videos: Vec::new(),
+            events: Vec::new(),
+        });
+    }
+
+    /// Find the stream of the given `NetStream` object.
+    fn find(&self, target: NetStreamObject<'gc>) -> Option<NetStreamHandle> {
+        self.streams
+            .iter()
+            .find(|(_, stream)| stream.target.ptr_eq(target))
+            .map(|(handle, _)| handle)
+    }
+
+    fn get(&self, target: NetStreamObject<'gc>) -> Option<&NetStream<'gc>> {
+        self.find(target)
+            .and_then(|handle| self.streams.get(handle))
+    }
+
+    fn get_mut(&mut self, target: NetStreamObject<'gc>) -> Option<&mut NetStream<'gc>> {
+        let handle = self.find(target)?;
+        self.streams.get_mut(handle)
+    }
+
+    /// Download and start playing the given file.
+    pub fn play(context: &mut UpdateContext<'_, 'gc, '_>, target: NetStreamObject<'gc>, url: &str) {
+        let handle = match context.net_streams.find(target) {
+            Some(handle) => handle,
+            None => return,
+        };
+        let stream = &mut context.net_streams.streams[handle];
+        stream.reset(context.video);
+        stream.play_id = stream.play_id.wrapping_add(1);
+        stream.state = PlaybackState::Loading;
+        stream
+            .events
+            .push(StreamEvent::Status("NetStream.Play.Start", "status"));
+
+        let fetch = context.navigator.fetch(url, RequestOptions::get());
+        let process = Self::load(
+            context.net_streams.sender.clone(),
+            handle,
+            stream.play_id,
+            fetch,
+        );
+        context.navigator.spawn_future(process);
+    }
+
+    fn load(
+        sender: Sender<LoadResult>,
+        handle: NetStreamHandle,
+        play_id: u32,
+        fetch: OwnedFuture<Vec<u8>, loader::Error>,
+    ) -> OwnedFuture<(), loader::Error> {
+        Box::pin(async move {
+            let data = fetch.await;
+            // The player may have been dropped in the meantime.
+            let _ = sender.send(LoadResult {
+                handle,
+                play_id,
+                data,
+            });
+            Ok(())
+        })
+    }
+
+    pub fn pause(&mut self, target: NetStreamObject<'gc>) {
+        if let Some(stream) = self.get_mut(target) {
+            if !stream.paused {
+                stream.paused = true;
+                stream
+                    .events
+                    .push(StreamEvent::Status("NetStream.Pause.Notify", "status"));
+            }
+        }
+    }
+
+    pub fn resume(&mut self, target: NetStreamObject<'gc>) {
+        if let Some(stream) = self.get_mut(target) {
+            if stream.paused {
+                stream.paused = false;
+                stream
+                    .events
+                    .push(StreamEvent::Status("NetStream.Unpause.Notify", "status"));
+            }
+        }
+    }
+
+    pub fn toggle_pause(&mut self, target: NetStreamObject<'gc>) {
+        match self.get(target) {
+            Some(stream) if stream.paused => self.resume(target),
+            Some(_) => self.pause(target),
+            None => {}
+        }
+    }
+
+    /// Seek to the keyframe closest to the given time, in seconds.
+    pub fn seek(&mut self, target: NetStreamObject<'gc>, time: f64) {
+        if let Some(stream) = self.get_mut(target) {
+            stream.seek(time * 1000.0);
+        }
+    }
+
+    /// Stop playing and forget the file being played.
+    pub fn close(&mut self, video: &mut dyn VideoBackend, target: NetStreamObject<'gc>) {
+        if let Some(stream) = self.get_mut(target) {
+            stream.reset(video);
+        }
+    }
+
+    /// The playback position, in seconds.
+    pub fn time(&self, target: NetStreamObject<'gc>) -> f64 {
+        self.get(target).map_or(0.0, |stream| stream.time / 1000.0)
+    }
+
+    /// The amount of loaded video ahead of the playback position, in seconds.
+    pub fn buffer_length(&self, target: NetStreamObject<'gc>) -> f64 {
+        self.get(target)
+            .map_or(0.0, |stream| stream.buffer_length() / 1000.0)
+    }
+
+    pub fn buffer_time(&self, target: NetStreamObject<'gc>) -> f64 {
+        self.get(target).map_or(0.0, |stream| stream.buffer_time)
+    }
+
+    pub fn set_buffer_time(&mut self, target: NetStreamObject<'gc>, buffer_time: f64) {
+        if let Some(stream) = self.get_mut(target) {
+            stream.buffer_time = buffer_time;
+        }
+    }
+
+    /// The number of bytes of the file that have been downloaded.
+    pub fn bytes_loaded(&self, target: NetStreamObject<'gc>) -> usize {
+        self.get(target).map_or(0, |stream| stream.data.len())
+    }
+
+    /// The object whose methods are called by script data tags of an AVM2 `NetStream`.
+    pub fn client(&self, target: Avm2Object<'gc>) -> Option<Avm2Object<'gc>> {
+        self.get(target.into())
+            .map(|stream| stream.client.unwrap_or(target))
+    }
+
+    pub fn set_client(&mut self, target: Avm2Object<'gc>, client: Avm2Object<'gc>) {
+        if let Some(stream) = self.get_mut(target.into()) {
+            stream.client = Some(client);
+        }
+    }
+
+    /// Show the video of a stream in a `Video` display object.
+    ///
+    /// If `target` is `None`, or isn't a `NetStream`, the video stops showing any stream.
+    pub fn attach_video(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        video: Video<'gc>,
+        target: Option<NetStreamObject<'gc>>,
+    ) {
+        self.detach_video(video);
+
+        match target.and_then(|target| self.get_mut(target)) {
+            Some(stream) => {
+                stream.videos.push(video);
+                video.attach_stream(gc_context, true);
+                video.show_stream_frame(gc_context, stream.bitmap);
+            }
+            None => video.attach_stream(gc_context, false),
+        }
+    }
+
+    /// Stop updating a `Video` display object with the frames of its stream.
+    pub fn detach_video(&mut self, video: Video<'gc>) {
+        for (_, stream) in self.streams.iter_mut() {
+            stream
+                .videos
+                .retain(|attached| !DisplayObject::ptr_eq((*attached).into(), video.into()));
+        }
+    }
+
+    /// Process finished downloads, advance the playback of all streams by `dt` milliseconds,
+    /// and send the resulting events to the movie.
+    pub fn update_net_streams(context: &mut UpdateContext<'_, 'gc, '_>, dt: f64) {
+        let results: Vec<LoadResult> = context.net_streams.receiver.try_iter().collect();
+        for result in results {
+            if let Some(stream) = context.net_streams.streams.get_mut(result.handle) {
+                if stream.play_id == result.play_id && stream.state == PlaybackState::Loading {
+                    stream.load(result.data);
+                }
+            }
+        }
+
+        let mut pending = Vec::new();
+        for (_, stream) in context.net_streams.streams.iter_mut() {
+            stream.advance(dt, context.video, context.renderer);
+            if stream.frame_changed {
+                stream.frame_changed = false;
+                for video in &stream.videos {
+                    video.show_stream_frame(context.gc_context, stream.bitmap);
+                }
+            }
+            if !stream.events.is_empty() {
+                let events = std::mem::take(&mut stream.events);
+                pending.push((stream.target, stream.client, events));
+            }
+        }
+
+        for (target, client, events) in pending {
+            match target {
+                NetStreamObject::Avm1(target) => send_avm1_events(context, target, events),
+                NetStreamObject::Avm2(target) => {
+                    send_net_stream_events(context, target, client.unwrap_or(target), events)
+                }
+            }
+        }
+    }
+}
+
+impl<'gc> Default for NetStreams<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call the `onStatus` and script data methods of an AVM1 `NetStream`.
+///
+/// Unlike AVM2, AVM1 has no separate client object, so `onMetaData` and the like are called on
+/// the stream itself.
+fn send_avm1_events<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    target: Avm1Object<'gc>,
+    events: Vec<StreamEvent>,
+) {
+    let root = match context.levels.get(&0).copied() {
+        Some(root) => root,
+        None => return,
+    };
+    if context.avm1.halted() {
+        return;
+    }
+
+    let swf_version = context.swf.version();
+    let globals = context.avm1.global_object_cell();
+    let mut activation = Avm1Activation::from_nothing(
+        context.reborrow(),
+        ActivationIdentifier::root("[NetStream]"),
+        swf_version,
+        globals,
+        root,
+    );
+
+    for event in events {
+        let result = match event {
+            StreamEvent::Status(code, level) => {
+                let info = net_status_info(&mut activation, code, level);
+                target.call_method("onStatus", &[info.into()], &mut activation)
+            }
+            StreamEvent::Call(name, value) => {
+                let value = script_data_to_avm1_value(&mut activation, value);
+                target.call_method(&name, &[value], &mut activation)
+            }
+        };
+        if let Err(e) = result {
+            log::error!("Unhandled error in NetStream event: {}", e);
+        }
+    }
+}
+
+/// Create the `info` object passed to an AVM1 `onStatus` method.
+pub fn net_status_info<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc, '_>,
+    code: &'static str,
+    level: &'static str,
+) -> Avm1Object<'gc> {
+    let info = Avm1ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    info.define_value(
+        activation.context.gc_context,
+        "code",
+        code.into(),
+        Default::default(),
+    );
+    info.define_value(
+        activation.context.gc_context,
+        "level",
+        level.into(),
+        Default::default(),
+    );
+    info.into()
+}
+
+fn script_data_to_avm1_value<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc, '_>,
+    value: ScriptDataValue,
+) -> Avm1Value<'gc> {
+    let gc_context = activation.context.gc_context;
+    match value {
+        ScriptDataValue::Number(n) | ScriptDataValue::Date(n) => n.into(),
+        ScriptDataValue::Boolean(b) => b.into(),
+        ScriptDataValue::String(s) => Avm1String::new(gc_context, s).into(),
+        ScriptDataValue::Null => Avm1Value::Null,
+        ScriptDataValue::Undefined => Avm1Value::Undefined,
+        ScriptDataValue::Object(properties) => {
+            let object = Avm1ScriptObject::object(
+                gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            for (name, value) in properties {
+                let value = script_data_to_avm1_value(activation, value);
+                object.define_value(gc_context, &name, value, Default::default());
+            }
+            object.into()
+        }
+        ScriptDataValue::StrictArray(values) => {
+            let array = Avm1ScriptObject::array(
+                gc_context,
+                Some(activation.context.avm1.prototypes().array),
+            );
+            for (i, value) in values.into_iter().enumerate() {
+                let value = script_data_to_avm1_value(activation, value);
+                array.set_array_element(i, value, gc_context);
+            }
+            array.into()
+        }
+    }
+}
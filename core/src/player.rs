@@ -1,23 +1,44 @@
+use crate::audio_manager::AudioManager;
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::debug::VariableDumper;
-use crate::avm1::globals::system::SystemProperties;
+use crate::avm1::globals::system::{Language, PlayerType, SystemCapabilities, SystemProperties};
 use crate::avm1::object::Object;
-use crate::avm1::{Avm1, AvmString, TObject, Timers, Value};
+use crate::avm1::{Avm1, AvmString, TObject, Value};
 use crate::avm2::Avm2;
+use crate::backend::font::FontProvider;
 use crate::backend::input::{InputBackend, MouseCursor};
 use crate::backend::locale::LocaleBackend;
 use crate::backend::navigator::{NavigatorBackend, RequestOptions};
 use crate::backend::storage::StorageBackend;
+use crate::backend::video::VideoBackend;
 use crate::backend::{audio::AudioBackend, render::Letterbox, render::RenderBackend};
+use crate::bitmap_cache::BitmapCaches;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
-use crate::display_object::{EditText, MorphShape, MovieClip};
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
+use crate::context_menu::{ContextMenuCallback, ContextMenuItem, ContextMenuState};
+use crate::debug_overlay::{DebugCounts, DebugOverlay};
+use crate::display_object::{render_display_object, EditText, MorphShape, MovieClip};
+use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent, TouchPhase};
 use crate::external::Value as ExternalValue;
-use crate::external::{ExternalInterface, ExternalInterfaceProvider};
+use crate::external::{ExternalInterface, ExternalInterfaceProvider, FsCommandProvider};
+use crate::focus_tracker::FocusTracker;
+use crate::font_file::FontFile;
+use crate::keyboard::Keyboard;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
+use crate::net_stream::NetStreams;
 use crate::prelude::*;
+use crate::print_job::{self, PrintJobs};
+use crate::replay::{
+    Replay, ReplayClock, ReplayEntry, ReplayError, ReplayHeader, ReplayLocaleBackend,
+    ReplayRecorder,
+};
+use crate::socket::Sockets;
+use crate::sol::{ObjectEncoding, Sol, SolError};
+use crate::stage::{StageAlign, StageQuality, StageScaleMode};
 use crate::tag_utils::SwfMovie;
+use crate::timer::Timers;
+use crate::touch::TouchPoints;
 use crate::transform::TransformStack;
 use enumset::EnumSet;
 use gc_arena::{make_arena, ArenaParameters, Collect, GcCell};
@@ -25,7 +46,9 @@ use log::info;
 use rand::{rngs::SmallRng, SeedableRng};
 use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
+use std::io::{Read, Write};
 use std::ops::DerefMut;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex, Weak};
 
 pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefont3.bin");
@@ -34,6 +57,36 @@ pub static DEVICE_FONT_TAG: &[u8] = include_bytes!("../assets/noto-sans-definefo
 /// `player_version`.
 pub const NEWEST_PLAYER_VERSION: u8 = 32;
 
+/// The frame rate that a hidden player is slowed down to, by default.
+const DEFAULT_BACKGROUND_FRAME_RATE: f64 = 8.0;
+
+/// The frame rate that a player is slowed down to while its window is
+/// completely covered.
+const OCCLUDED_FRAME_RATE: f64 = 2.0;
+
+/// The lowest frame rate that a movie can run at, in FPS.
+pub const MIN_FRAME_RATE: f64 = 0.01;
+
+/// The highest frame rate that a movie can run at, in FPS.
+pub const MAX_FRAME_RATE: f64 = 1000.0;
+
+/// Whether the player can be seen, as reported by the frontend.
+///
+/// Like Flash Player, a player that can't be seen keeps running its frames,
+/// timers and audio at a reduced rate, but doesn't render.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackgroundThrottle {
+    /// The player is visible and runs at the movie's frame rate.
+    Visible,
+
+    /// The player is hidden, such as in a background tab or a minimized
+    /// window. It runs at the background frame rate.
+    Hidden,
+
+    /// The player's window is completely covered by other windows.
+    Occluded,
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct GcRoot<'gc>(GcCell<'gc, GcRootData<'gc>>);
@@ -52,6 +105,9 @@ struct GcRootData<'gc> {
 
     mouse_hovered_object: Option<DisplayObject<'gc>>, // TODO: Remove GcCell wrapped inside GcCell.
 
+    /// The object that has keyboard focus.
+    focus_tracker: FocusTracker<'gc>,
+
     /// The object being dragged via a `startDrag` action.
     drag_object: Option<DragObject<'gc>>,
 
@@ -75,8 +131,26 @@ struct GcRootData<'gc> {
     /// Timed callbacks created with `setInterval`/`setTimeout`.
     timers: Timers<'gc>,
 
+    /// Sockets opened by `XMLSocket`.
+    sockets: Sockets<'gc>,
+
+    /// Connections opened by `LocalConnection`.
+    local_connections: LocalConnections<'gc>,
+
+    /// Print jobs started by `PrintJob`.
+    print_jobs: PrintJobs<'gc>,
+
+    /// Videos played by `NetStream`.
+    net_streams: NetStreams<'gc>,
+
     /// External interface for (for example) Javascript <-> Actionscript interaction
     external_interface: ExternalInterface<'gc>,
+
+    /// The sounds that are currently playing, and the display objects that own them.
+    audio_manager: AudioManager<'gc>,
+
+    /// The context menu that is currently open, if any.
+    current_context_menu: Option<ContextMenuState<'gc>>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -96,7 +170,13 @@ impl<'gc> GcRootData<'gc> {
         &mut HashMap<String, Object<'gc>>,
         &mut Vec<EditText<'gc>>,
         &mut Timers<'gc>,
+        &mut Sockets<'gc>,
+        &mut LocalConnections<'gc>,
+        &mut PrintJobs<'gc>,
+        &mut NetStreams<'gc>,
         &mut ExternalInterface<'gc>,
+        &mut AudioManager<'gc>,
+        &mut Option<ContextMenuState<'gc>>,
     ) {
         (
             &mut self.levels,
@@ -109,7 +189,13 @@ impl<'gc> GcRootData<'gc> {
             &mut self.shared_objects,
             &mut self.unbound_text_fields,
             &mut self.timers,
+            &mut self.sockets,
+            &mut self.local_connections,
+            &mut self.print_jobs,
+            &mut self.net_streams,
             &mut self.external_interface,
+            &mut self.audio_manager,
+            &mut self.current_context_menu,
         )
     }
 }
@@ -123,6 +209,8 @@ type Renderer = Box<dyn RenderBackend>;
 type Input = Box<dyn InputBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Locale = Box<dyn LocaleBackend>;
+type Fonts = Box<dyn FontProvider>;
+type Video = Box<dyn VideoBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -147,31 +235,86 @@ pub struct Player {
     pub navigator: Navigator,
     input: Input,
     locale: Locale,
+    font_provider: Fonts,
+    video: Video,
     transform_stack: TransformStack,
     view_matrix: Matrix,
     inverse_view_matrix: Matrix,
 
+    /// The offscreen bitmaps of display objects with `cacheAsBitmap` set.
+    bitmap_caches: BitmapCaches,
+
     storage: Storage,
 
+    /// The source of all randomness in the movie, such as `Math.random`.
     rng: SmallRng,
 
+    /// The seed that `rng` was created from.
+    rng_seed: u64,
+
+    /// The session being recorded with `start_recording`, if any.
+    recorder: Option<ReplayRecorder>,
+
+    /// The session being played back with `replay`, if any.
+    replay: Option<Replay>,
+
+    /// The wall clock reported to the movie while recording or replaying.
+    replay_clock: Option<Rc<ReplayClock>>,
+
     gc_arena: GcArena,
     background_color: Color,
 
     frame_rate: f64,
     frame_accumulator: f64,
 
+    /// Whether the player is being slowed down because it can't be seen.
+    background_throttle: BackgroundThrottle,
+
+    /// The frame rate of a hidden player.
+    background_frame_rate: f64,
+
     viewport_width: u32,
     viewport_height: u32,
     movie_width: u32,
     movie_height: u32,
     letterbox: Letterbox,
 
+    /// Whether the margins around a `ShowAll` stage are hidden.
+    letterbox_enabled: bool,
+
+    /// Whether the movie is scaled to fit the viewport.
+    /// If not, the stage behaves as if its scale mode was `NoScale`.
+    allow_scale: bool,
+
+    /// How the movie is scaled to fit the viewport.
+    scale_mode: StageScaleMode,
+
+    /// The edges of the viewport that the stage is anchored to.
+    stage_align: EnumSet<StageAlign>,
+
+    /// How finely the movie is rendered.
+    quality: StageQuality,
+
+    /// Whether the context menu should include the built-in player items.
+    show_menu: bool,
+
+    /// The diagnostics overlay, if it is shown.
+    debug_overlay: Option<DebugOverlay>,
+
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
 
-    /// The current mouse cursor icon.
-    mouse_cursor: MouseCursor,
+    /// Whether a user-initiated event is currently being handled.
+    is_user_event: bool,
+
+    /// The keys held down and the last key event.
+    keyboard: Keyboard,
+
+    /// The fingers touching the screen.
+    touch_points: TouchPoints,
+
+    /// The automatic and script-chosen mouse cursors.
+    mouse_cursor: MouseCursorState,
 
     system: SystemProperties,
 
@@ -197,6 +340,8 @@ impl Player {
         input: Input,
         storage: Storage,
         locale: Locale,
+        font_provider: Fonts,
+        video: Video,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let fake_movie = Arc::new(SwfMovie::empty(NEWEST_PLAYER_VERSION));
         let movie_width = 550;
@@ -220,8 +365,13 @@ impl Player {
             transform_stack: TransformStack::new(),
             view_matrix: Default::default(),
             inverse_view_matrix: Default::default(),
+            bitmap_caches: BitmapCaches::new(),
 
-            rng: SmallRng::from_seed([0u8; 16]), // TODO(Herschel): Get a proper seed on all platforms.
+            rng: seeded_rng(0), // TODO(Herschel): Get a proper seed on all platforms.
+            rng_seed: 0,
+            recorder: None,
+            replay: None,
+            replay_clock: None,
 
             gc_arena: GcArena::new(ArenaParameters::default(), |gc_context| {
                 GcRoot(GcCell::allocate(
@@ -230,6 +380,7 @@ impl Player {
                         library: Library::default(),
                         levels: BTreeMap::new(),
                         mouse_hovered_object: None,
+                        focus_tracker: FocusTracker::new(gc_context),
                         drag_object: None,
                         avm1: Avm1::new(gc_context, NEWEST_PLAYER_VERSION),
                         avm2: Avm2::new(gc_context),
@@ -238,29 +389,49 @@ impl Player {
                         shared_objects: HashMap::new(),
                         unbound_text_fields: Vec::new(),
                         timers: Timers::new(),
+                        sockets: Sockets::new(),
+                        local_connections: LocalConnections::new(),
+                        print_jobs: PrintJobs::new(),
+                        net_streams: NetStreams::new(),
                         external_interface: ExternalInterface::new(),
+                        audio_manager: AudioManager::new(),
+                        current_context_menu: None,
                     },
                 ))
             }),
 
             frame_rate,
             frame_accumulator: 0.0,
+            background_throttle: BackgroundThrottle::Visible,
+            background_frame_rate: DEFAULT_BACKGROUND_FRAME_RATE,
 
             movie_width,
             movie_height,
             viewport_width: movie_width,
             viewport_height: movie_height,
             letterbox: Letterbox::None,
+            letterbox_enabled: true,
+            allow_scale: true,
+            scale_mode: StageScaleMode::default(),
+            stage_align: EnumSet::empty(),
+            quality: StageQuality::default(),
+            show_menu: true,
+            debug_overlay: None,
 
             mouse_pos: (Twips::new(0), Twips::new(0)),
             is_mouse_down: false,
-            mouse_cursor: MouseCursor::Arrow,
+            is_user_event: false,
+            keyboard: Keyboard::new(),
+            touch_points: TouchPoints::default(),
+            mouse_cursor: MouseCursorState::default(),
 
             renderer,
             audio,
             navigator,
             input,
             locale,
+            font_provider,
+            video,
             self_reference: None,
             system: SystemProperties::default(),
             instance_counter: 0,
@@ -320,7 +491,7 @@ impl Player {
 
         self.movie_width = movie.width();
         self.movie_height = movie.height();
-        self.frame_rate = movie.header().frame_rate.into();
+        self.frame_rate = Self::clamp_frame_rate(movie.header().frame_rate.into());
         self.swf = movie;
         self.instance_counter = 0;
 
@@ -372,6 +543,17 @@ impl Player {
     }
 
     pub fn tick(&mut self, dt: f64) {
+        // While replaying, the recording decides how much time passes and what input arrives.
+        let dt = if self.replay.is_some() {
+            self.run_replay_until_tick().unwrap_or(dt)
+        } else {
+            dt
+        };
+        self.record(ReplayEntry::Tick(dt));
+        if let Some(clock) = &self.replay_clock {
+            clock.advance(dt);
+        }
+
         // Don't run until preloading is complete.
         // TODO: Eventually we want to stream content similar to the Flash player.
         if !self.audio.is_loading_complete() {
@@ -380,11 +562,18 @@ impl Player {
 
         if self.is_playing() {
             self.frame_accumulator += dt;
-            let frame_time = 1000.0 / self.frame_rate;
+            let frame_time = 1000.0 / self.effective_frame_rate();
 
             const MAX_FRAMES_PER_TICK: u32 = 5; // Sanity cap on frame tick.
+            let max_frames = if self.throttled_frame_rate().is_some() {
+                // A throttled movie runs slower rather than catching up.
+                1
+            } else {
+                MAX_FRAMES_PER_TICK
+            };
+            let script_start = self.debug_time();
             let mut frame = 0;
-            while frame < MAX_FRAMES_PER_TICK && self.frame_accumulator >= frame_time {
+            while frame < max_frames && self.frame_accumulator >= frame_time {
                 self.frame_accumulator -= frame_time;
                 self.run_frame();
                 frame += 1;
@@ -397,14 +586,38 @@ impl Player {
             }
 
             self.update_timers(dt);
+            self.update_sockets();
+            self.update_local_connections();
+            self.update_net_streams(dt);
+
+            let audio_start = self.debug_time();
             self.audio.tick();
+            let audio_end = self.debug_time();
+
+            if let (Some(overlay), Some(script_start), Some(audio_start), Some(audio_end)) = (
+                &mut self.debug_overlay,
+                script_start,
+                audio_start,
+                audio_end,
+            ) {
+                overlay.record_script_time(audio_start - script_start);
+                overlay.record_audio_time(audio_end - audio_start);
+            }
         }
     }
 
+    /// Reads the navigator's clock, if the diagnostics overlay is shown.
+    fn debug_time(&mut self) -> Option<std::time::Duration> {
+        let navigator = &mut self.navigator;
+        self.debug_overlay
+            .as_ref()
+            .map(|_| navigator.time_since_launch())
+    }
+
     /// Returns the approximate duration of time until the next frame is due to run.
     /// This is only an approximation to be used for sleep durations.
     pub fn time_til_next_frame(&self) -> std::time::Duration {
-        let frame_time = 1000.0 / self.frame_rate;
+        let frame_time = 1000.0 / self.effective_frame_rate();
         let mut dt = if self.frame_accumulator <= 0.0 {
             frame_time
         } else if self.frame_accumulator >= frame_time {
@@ -435,7 +648,62 @@ impl Player {
     }
 
     pub fn needs_render(&self) -> bool {
-        self.needs_render
+        self.needs_render && self.background_throttle == BackgroundThrottle::Visible
+    }
+
+    pub fn background_throttle(&self) -> BackgroundThrottle {
+        self.background_throttle
+    }
+
+    /// Tells the player whether it can be seen.
+    ///
+    /// While hidden, the frontend should keep calling `tick` at the interval
+    /// given by `time_til_next_frame` even if it normally ticks on display
+    /// refresh, since browsers stop that for hidden pages.
+    pub fn set_background_throttle(&mut self, state: BackgroundThrottle) {
+        if state == self.background_throttle {
+            return;
+        }
+
+        if state == BackgroundThrottle::Visible {
+            // Pick up at the normal rate, rather than running a burst of frames.
+            self.frame_accumulator = 0.0;
+            self.needs_render = true;
+        }
+        self.background_throttle = state;
+    }
+
+    /// The frame rate that hidden players run at, in FPS.
+    pub fn background_frame_rate(&self) -> f64 {
+        self.background_frame_rate
+    }
+
+    pub fn set_background_frame_rate(&mut self, frame_rate: f64) {
+        self.background_frame_rate = frame_rate;
+    }
+
+    /// The reduced frame rate that the player is running at while it can't be
+    /// seen, if any.
+    fn throttled_frame_rate(&self) -> Option<f64> {
+        match self.background_throttle {
+            BackgroundThrottle::Visible => None,
+            BackgroundThrottle::Hidden => Some(self.background_frame_rate),
+            BackgroundThrottle::Occluded => {
+                Some(self.background_frame_rate.min(OCCLUDED_FRAME_RATE))
+            }
+        }
+    }
+
+    /// The rate that frames are currently run at, in FPS.
+    ///
+    /// Throttling never speeds up a movie with a low frame rate. Frontends
+    /// should re-read this after every tick to schedule the next one, as
+    /// movies may change their frame rate at any time.
+    pub fn effective_frame_rate(&self) -> f64 {
+        match self.throttled_frame_rate() {
+            Some(throttled) => self.frame_rate.min(throttled),
+            None => self.frame_rate,
+        }
     }
 
     pub fn movie_width(&self) -> u32 {
@@ -451,20 +719,420 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        let old_stage_size = self.stage_size();
         self.viewport_width = width;
         self.viewport_height = height;
+        self.relayout_stage(old_stage_size);
+    }
+
+    /// Sets whether the movie is scaled to fit the viewport, as with the
+    /// standalone player's `allowscale` FSCommand.
+    pub fn set_allow_scale(&mut self, allow_scale: bool) {
+        let old_stage_size = self.stage_size();
+        self.allow_scale = allow_scale;
+        self.relayout_stage(old_stage_size);
+    }
+
+    /// Sets whether the margins around the stage are hidden when the movie is
+    /// shown in full. If not, content outside of the stage stays visible.
+    pub fn set_letterbox_enabled(&mut self, letterbox_enabled: bool) {
+        self.letterbox_enabled = letterbox_enabled;
         self.build_matrices();
     }
 
+    pub fn scale_mode(&self) -> StageScaleMode {
+        self.scale_mode
+    }
+
+    /// Sets how the movie is scaled to fit the viewport.
+    ///
+    /// Movies may change this themselves with `Stage.scaleMode`.
+    pub fn set_scale_mode(&mut self, scale_mode: StageScaleMode) {
+        let old_stage_size = self.stage_size();
+        self.scale_mode = scale_mode;
+        self.relayout_stage(old_stage_size);
+    }
+
+    pub fn stage_align(&self) -> EnumSet<StageAlign> {
+        self.stage_align
+    }
+
+    /// Sets the edges of the viewport that the stage is anchored to.
+    ///
+    /// Movies may change this themselves with `Stage.align`.
+    pub fn set_stage_align(&mut self, stage_align: EnumSet<StageAlign>) {
+        self.stage_align = stage_align;
+        self.build_matrices();
+    }
+
+    pub fn quality(&self) -> StageQuality {
+        self.quality
+    }
+
+    /// Sets how finely the movie is rendered.
+    ///
+    /// Movies may change this themselves with `_quality`.
+    pub fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    /// The scale mode in effect, taking the `allowscale` FSCommand into account.
+    fn effective_scale_mode(&self) -> StageScaleMode {
+        if self.allow_scale {
+            self.scale_mode
+        } else {
+            StageScaleMode::NoScale
+        }
+    }
+
+    /// The size of the stage in pixels, as seen by scripts.
+    ///
+    /// This is the size of the movie, unless the movie is not scaled, in which
+    /// case the stage fills the viewport.
+    fn stage_size(&self) -> (u32, u32) {
+        if self.effective_scale_mode() == StageScaleMode::NoScale {
+            (self.viewport_width, self.viewport_height)
+        } else {
+            (self.movie_width, self.movie_height)
+        }
+    }
+
+    /// Whether the context menu should show the built-in player items.
+    pub fn show_menu(&self) -> bool {
+        self.show_menu
+    }
+
+    /// Sets whether the context menu should show the built-in player items,
+    /// as with the standalone player's `showmenu` FSCommand.
+    pub fn set_show_menu(&mut self, show_menu: bool) {
+        self.show_menu = show_menu;
+    }
+
+    /// Whether the diagnostics overlay is shown.
+    pub fn debug_overlay(&self) -> bool {
+        self.debug_overlay.is_some()
+    }
+
+    /// Shows or hides the diagnostics overlay, a panel over the movie with the
+    /// frame rate, the time spent in each part of a frame, and counts of live
+    /// objects. Timings are only measured while it is shown.
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        if enabled != self.debug_overlay() {
+            self.debug_overlay = if enabled {
+                Some(DebugOverlay::new())
+            } else {
+                None
+            };
+            self.needs_render = true;
+        }
+    }
+
+    /// Build the items of the context menu, as it is about to be opened.
+    ///
+    /// The menu is taken from the topmost object under the mouse, or the
+    /// closest of its parents that has a menu set. The frontend should call
+    /// `run_context_menu_callback` with the index of the chosen item, or
+    /// `clear_custom_menu_items` if the menu is closed without a choice.
+    pub fn prepare_context_menu(&mut self) -> Vec<ContextMenuItem> {
+        let mouse_pos = self.mouse_pos;
+        let show_built_in = self.show_menu;
+        let show_debug_overlay = self.debug_overlay.is_some();
+
+        self.update(|context| {
+            let mut picked = None;
+            for (_depth, level) in context.levels.clone().iter().rev() {
+                picked = level.mouse_pick(context, *level, mouse_pos);
+                if picked.is_some() {
+                    break;
+                }
+            }
+
+            let root = context.levels.get(&0).copied();
+            let mut activation = Activation::from_stub(
+                context.reborrow(),
+                ActivationIdentifier::root("[Context Menu]"),
+            );
+
+            let mut menu = None;
+            let mut node = picked.or(root);
+            while let Some(display_object) = node {
+                if let Value::Object(object) = display_object.object() {
+                    if let Ok(Value::Object(menu_object)) = object.get("menu", &mut activation) {
+                        menu = Some((display_object, menu_object));
+                        break;
+                    }
+                }
+                node = display_object.parent();
+            }
+
+            let mut menu_state = crate::avm1::globals::context_menu::make_context_menu_state(
+                menu,
+                root.and_then(|root| root.as_movie_clip()),
+                show_built_in,
+                &mut activation,
+            );
+            if show_built_in {
+                menu_state.push(
+                    ContextMenuItem {
+                        caption: "Show Diagnostics".to_string(),
+                        enabled: true,
+                        separator_before: !menu_state.info().is_empty(),
+                        checked: show_debug_overlay,
+                    },
+                    ContextMenuCallback::ToggleDebugOverlay,
+                );
+            }
+            let info = menu_state.info().to_vec();
+            *activation.context.current_context_menu = Some(menu_state);
+
+            info
+        })
+    }
+
+    /// Run the action of the context menu item at the given index, and close
+    /// the menu.
+    pub fn run_context_menu_callback(&mut self, index: usize) {
+        let toggle_debug_overlay = self.update(|context| {
+            let menu_state = context.current_context_menu.take();
+            let callback = match menu_state.as_ref().and_then(|state| state.callback(index)) {
+                Some(callback) => callback,
+                None => return false,
+            };
+            let root = context.levels.get(&0).and_then(|root| root.as_movie_clip());
+
+            match (callback, root) {
+                (
+                    ContextMenuCallback::Avm1 {
+                        target,
+                        item,
+                        callback,
+                    },
+                    _,
+                ) => {
+                    let mut activation = Activation::from_stub(
+                        context.reborrow(),
+                        ActivationIdentifier::root("[Context Menu Callback]"),
+                    );
+                    let _ = callback.call(
+                        "[Context Menu Callback]",
+                        &mut activation,
+                        *item,
+                        None,
+                        &[target.object(), (*item).into()],
+                    );
+                }
+                (ContextMenuCallback::Play, Some(root)) => {
+                    if root.playing() {
+                        root.stop(context);
+                    } else {
+                        root.play(context);
+                    }
+                }
+                (ContextMenuCallback::Rewind, Some(root)) => root.goto_frame(context, 1, true),
+                (ContextMenuCallback::Forward, Some(root)) => root.next_frame(context),
+                (ContextMenuCallback::Back, Some(root)) => root.prev_frame(context),
+                (ContextMenuCallback::ToggleDebugOverlay, _) => return true,
+                _ => (),
+            }
+
+            false
+        });
+
+        if toggle_debug_overlay {
+            self.set_debug_overlay(self.debug_overlay.is_none());
+        }
+    }
+
+    /// Close the context menu without choosing an item.
+    pub fn clear_custom_menu_items(&mut self) {
+        self.update(|context| {
+            *context.current_context_menu = None;
+        });
+    }
+
+    /// Sets the version of Flash Player reported to content, such as through
+    /// `$version`.
+    ///
+    /// This must be called before the root movie is set.
+    pub fn set_player_version(&mut self, player_version: u8) {
+        self.player_version = player_version;
+        self.mutate_with_update_context(|context| {
+            context.avm1.set_player_version(player_version);
+        });
+    }
+
+    /// Sets the type of player reported to content, such as through
+    /// `System.capabilities.playerType`.
+    ///
+    /// Frontends should set this to match how they embed the player, e.g. a
+    /// browser plugin versus a standalone projector.
+    pub fn set_player_type(&mut self, player_type: PlayerType) {
+        self.system.player_type = player_type;
+    }
+
+    /// Sets the resolution of the screen the player is being displayed on.
+    pub fn set_screen_resolution(&mut self, width: u32, height: u32) {
+        self.system.screen_resolution = (width, height);
+    }
+
+    /// Sets whether the player reports itself as a debugger player, through
+    /// `Capabilities.isDebugger`.
+    ///
+    /// Debugger players keep the call stack of every AVM2 `Error` they create,
+    /// which is returned by `Error.getStackTrace`.
+    pub fn set_is_debugger(&mut self, is_debugger: bool) {
+        if is_debugger {
+            self.system
+                .capabilities
+                .insert(SystemCapabilities::Debugger);
+        } else {
+            self.system
+                .capabilities
+                .remove(SystemCapabilities::Debugger);
+        }
+    }
+
+    /// Sets the language of the host, as reported by `System.capabilities.language`.
+    pub fn set_language(&mut self, language: Language) {
+        self.system.language = language;
+    }
+
+    /// Seeds the random number generator used by the movie, such as for `Math.random`.
+    ///
+    /// Movies that are given the same seed and the same input behave identically.
+    pub fn set_rng_seed(&mut self, seed: u64) {
+        self.rng = seeded_rng(seed);
+        self.rng_seed = seed;
+    }
+
+    /// Starts recording every tick and event to `writer`, so that the session can later be
+    /// played back with `replay`.
+    ///
+    /// This should be called before the movie is loaded. The random number generator is
+    /// reseeded, and the movie sees a wall clock that only advances as the player ticks.
+    pub fn start_recording(&mut self, writer: Box<dyn Write>) -> Result<(), ReplayError> {
+        let header = ReplayHeader {
+            seed: self.rng_seed,
+            start_time: self.locale.get_current_date_time(),
+            timezone: self.locale.get_timezone(),
+        };
+        self.recorder = Some(ReplayRecorder::new(writer, &header)?);
+        self.start_replay_clock(&header);
+        Ok(())
+    }
+
+    /// Stops recording, and flushes the recording to its writer.
+    pub fn stop_recording(&mut self) -> Result<(), ReplayError> {
+        match self.recorder.take() {
+            Some(mut recorder) => recorder.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Plays back a session recorded with `start_recording`.
+    ///
+    /// This should be called before the movie is loaded. Each call to `tick` then runs the
+    /// next recorded tick, with the events that arrived before it, and other events passed to
+    /// `handle_event` are ignored until the recording ends.
+    pub fn replay(&mut self, reader: impl Read) -> Result<(), ReplayError> {
+        let replay = Replay::read(reader)?;
+        self.start_replay_clock(&replay.header);
+        self.replay = Some(replay);
+        Ok(())
+    }
+
+    /// Returns whether a recording is being played back.
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    fn start_replay_clock(&mut self, header: &ReplayHeader) {
+        self.set_rng_seed(header.seed);
+        let clock = ReplayClock::new(header.start_time, header.timezone);
+        self.locale = Box::new(ReplayLocaleBackend::new(clock.clone()));
+        self.replay_clock = Some(clock);
+    }
+
+    /// Handles the recorded events up to the next tick, and returns the length of that tick.
+    /// Returns `None` once the recording has ended.
+    fn run_replay_until_tick(&mut self) -> Option<f64> {
+        loop {
+            let entry = self.replay.as_mut().and_then(|replay| replay.next_entry());
+            match entry {
+                Some(ReplayEntry::Tick(dt)) => return Some(dt),
+                Some(ReplayEntry::Event(event)) => self.run_event(event),
+                None => {
+                    info!("Replay finished");
+                    self.replay = None;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Adds an entry to the recording, if one is being made.
+    fn record(&mut self, entry: ReplayEntry) {
+        if let Some(recorder) = &mut self.recorder {
+            if let Err(e) = recorder.record(&entry) {
+                log::error!("Unable to write recording: {}", e);
+                self.recorder = None;
+            }
+        }
+    }
+
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        if self.replay.is_some() {
+            return;
+        }
+        self.run_event(event);
+    }
+
+    fn run_event(&mut self, event: PlayerEvent) {
+        self.record(ReplayEntry::Event(event.clone()));
+
+        let was_user_event = self.is_user_event;
+        self.is_user_event = event.is_user_gesture();
+        self.dispatch_event(event);
+        self.is_user_event = was_user_event;
+    }
+
+    /// Whether a user-initiated event, such as a mouse click or key press, is
+    /// currently being handled.
+    pub fn is_user_event(&self) -> bool {
+        self.is_user_event
+    }
+
+    /// Mark the scripts run from now on as being (or not being) triggered by
+    /// the user.
+    ///
+    /// This is set automatically while input events are handled; frontends
+    /// may use it for interactions the player doesn't see as events.
+    pub fn set_is_user_event(&mut self, is_user_event: bool) {
+        self.is_user_event = is_user_event;
+    }
+
+    fn dispatch_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
 
+        match event {
+            PlayerEvent::KeyDown {
+                key_code, key_char, ..
+            } => self.keyboard.key_down(key_code, key_char),
+            PlayerEvent::KeyUp {
+                key_code, key_char, ..
+            } => self.keyboard.key_up(key_code, key_char),
+            PlayerEvent::TextInput { codepoint } => self.keyboard.text_input(codepoint),
+            _ => (),
+        }
+
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::V,
+                ..
             } = event
             {
-                if self.input.is_key_down(KeyCode::Control) && self.input.is_key_down(KeyCode::Alt)
+                if self.keyboard.is_key_held(KeyCode::Control)
+                    && self.keyboard.is_key_held(KeyCode::Alt)
                 {
                     self.mutate_with_update_context(|context| {
                         let mut dumper = VariableDumper::new("  ");
@@ -498,9 +1166,11 @@ impl Player {
 
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::D,
+                ..
             } = event
             {
-                if self.input.is_key_down(KeyCode::Control) && self.input.is_key_down(KeyCode::Alt)
+                if self.keyboard.is_key_held(KeyCode::Control)
+                    && self.keyboard.is_key_held(KeyCode::Alt)
                 {
                     self.mutate_with_update_context(|context| {
                         if context.avm1.show_debug_output() {
@@ -521,6 +1191,30 @@ impl Player {
             }
         }
 
+        if let PlayerEvent::Touch {
+            phase,
+            id,
+            x,
+            y,
+            pressure,
+        } = event
+        {
+            // TODO: Once display objects have AVM2 objects, dispatch `TouchEvent`s
+            // and `TransformGestureEvent`s to them according to `Multitouch.inputMode`.
+            // Until then, the primary touch point drives the mouse like it does in
+            // Flash Player, and other fingers are ignored.
+            let (point, _gesture) = self.touch_points.update(phase, id, x, y, pressure);
+            if let Some(point) = point.filter(|point| point.is_primary) {
+                let (x, y) = (point.x, point.y);
+                self.handle_event(match phase {
+                    TouchPhase::Begin => PlayerEvent::MouseDown { x, y },
+                    TouchPhase::Move => PlayerEvent::MouseMove { x, y },
+                    TouchPhase::End => PlayerEvent::MouseUp { x, y },
+                });
+            }
+            return;
+        }
+
         // Update mouse position from mouse events.
         if let PlayerEvent::MouseMove { x, y }
         | PlayerEvent::MouseDown { x, y }
@@ -545,7 +1239,7 @@ impl Player {
             }
 
             // Special keys have custom values for keyPress.
-            PlayerEvent::KeyDown { key_code } => {
+            PlayerEvent::KeyDown { key_code, .. } => {
                 if let Some(key_code) = crate::events::key_code_to_button_key_code(key_code) {
                     Some(ClipEvent::KeyPress { key_code })
                 } else {
@@ -569,7 +1263,7 @@ impl Player {
             });
         }
         // Propagte clip events.
-
+        let mouse_pos = self.mouse_pos;
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
                 PlayerEvent::KeyDown { .. } => {
@@ -591,8 +1285,11 @@ impl Player {
                     Some(("Mouse", "onMouseDown", vec![])),
                 ),
                 PlayerEvent::MouseWheel { delta } => {
+                    let target = Self::find_topmost_target(context, mouse_pos)
+                        .map(|target| target.object())
+                        .unwrap_or(Value::Undefined);
                     let delta = Value::from(delta.lines());
-                    (None, Some(("Mouse", "onMouseWheel", vec![delta])))
+                    (None, Some(("Mouse", "onMouseWheel", vec![delta, target])))
                 }
                 _ => (None, None),
             };
@@ -631,9 +1328,21 @@ impl Player {
                 PlayerEvent::MouseDown { .. } => {
                     is_mouse_down = true;
                     needs_render = true;
+
+                    // Clicking moves focus without showing the focus rectangle.
+                    let focus_tracker = context.focus_tracker;
+                    let new_focus = context
+                        .mouse_hovered_object
+                        .filter(|node| FocusTracker::is_focusable(context, *node));
+                    focus_tracker.set(new_focus, context, false);
+
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Press);
                     }
+
+                    if let Some(root) = Self::avm2_root(context) {
+                        crate::avm2::mouse_down(context, root, mouse_pos);
+                    }
                 }
 
                 PlayerEvent::MouseUp { .. } => {
@@ -642,12 +1351,74 @@ impl Player {
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Release);
                     }
+
+                    if let Some(root) = Self::avm2_root(context) {
+                        crate::avm2::mouse_up(context, root, mouse_pos);
+                    }
+
+                    if let Some(text) = Self::find_topmost_target(context, mouse_pos)
+                        .and_then(|target| target.as_edit_text())
+                    {
+                        text.click_link(context, mouse_pos);
+                    }
+                }
+
+                PlayerEvent::KeyDown {
+                    key_code: KeyCode::Tab,
+                    ..
+                } => {
+                    let reverse = context.keyboard.is_key_held(KeyCode::Shift);
+                    let focus_tracker = context.focus_tracker;
+                    focus_tracker.cycle(context, reverse);
+                }
+
+                // Typed characters go into the focused text field.
+                PlayerEvent::TextInput { codepoint } => {
+                    if let Some(text) = context
+                        .focus_tracker
+                        .get()
+                        .and_then(|focus| focus.as_edit_text())
+                    {
+                        let mut buffer = [0; 4];
+                        text.text_input(codepoint.encode_utf8(&mut buffer), context);
+                        needs_render = true;
+                    }
+                }
+
+                // Enter and Space click the object that has keyboard focus.
+                PlayerEvent::KeyDown {
+                    key_code: KeyCode::Return,
+                    ..
+                }
+                | PlayerEvent::KeyDown {
+                    key_code: KeyCode::Space,
+                    ..
+                } => {
+                    let focus_tracker = context.focus_tracker;
+                    if focus_tracker.is_highlighted() {
+                        if let Some(node) = focus_tracker.get() {
+                            node.handle_clip_event(context, ClipEvent::Press);
+                            node.handle_clip_event(context, ClipEvent::Release);
+                        }
+                    }
                 }
 
                 _ => (),
             }
 
             Self::run_actions(context);
+
+            // Text fields only scroll once scripts have seen the wheel event.
+            if let PlayerEvent::MouseWheel { delta } = event {
+                if let Some(text) = Self::find_topmost_target(context, mouse_pos)
+                    .and_then(|target| target.as_edit_text())
+                {
+                    if text.is_mouse_wheel_enabled() {
+                        let scroll = text.scroll() as f64 - delta.lines().round();
+                        text.set_scroll(scroll, context);
+                    }
+                }
+            }
         });
         self.is_mouse_down = is_mouse_down;
         if needs_render {
@@ -655,6 +1426,64 @@ impl Player {
         }
     }
 
+    /// The main timeline of the root movie, if it is driven by AVM2.
+    ///
+    /// ActionScript 3 movies construct a document class on their main
+    /// timeline, so this is the case once it has an AVM2 object.
+    fn avm2_root<'gc>(context: &mut UpdateContext<'_, 'gc, '_>) -> Option<DisplayObject<'gc>> {
+        context
+            .levels
+            .get(&0)
+            .copied()
+            .filter(|root| root.object2().is_some())
+    }
+
+    /// Find the object that receives mouse wheel events and link clicks at
+    /// the given point, searching the levels from the top down.
+    fn find_topmost_target<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        point: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        let levels: Vec<DisplayObject<'gc>> = context.levels.values().rev().copied().collect();
+        levels
+            .into_iter()
+            .find_map(|level| Self::topmost_target(level, point))
+    }
+
+    /// Find the topmost movie clip, button or text field underneath the given
+    /// point.
+    ///
+    /// Other objects, such as shapes, can't be scripted, so a hit on one of
+    /// them targets the clip containing it.
+    fn topmost_target<'gc>(
+        object: DisplayObject<'gc>,
+        point: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        if !object.visible() {
+            return None;
+        }
+
+        if object.as_movie_clip().is_none() {
+            return Some(object).filter(|object| object.hit_test_shape(point));
+        }
+
+        let children: Vec<DisplayObject<'gc>> = object.children().collect();
+        for child in children.into_iter().rev() {
+            let is_scriptable = child.as_movie_clip().is_some()
+                || child.as_button().is_some()
+                || child.as_edit_text().is_some();
+            if is_scriptable {
+                if let Some(target) = Self::topmost_target(child, point) {
+                    return Some(target);
+                }
+            } else if child.visible() && child.hit_test_shape(point) {
+                return Some(object);
+            }
+        }
+
+        Some(object).filter(|object| object.hit_test_shape(point))
+    }
+
     /// Update dragged object, if any.
     fn update_drag(&mut self) {
         let mouse_pos = self.mouse_pos;
@@ -692,7 +1521,7 @@ impl Player {
         }
         let mouse_pos = self.mouse_pos;
 
-        let mut new_cursor = self.mouse_cursor;
+        let mut new_cursor = self.mouse_cursor.automatic.clone();
         let hover_changed = self.mutate_with_update_context(|context| {
             // Check hovered object.
             let mut new_hovered = None;
@@ -731,9 +1560,12 @@ impl Player {
         });
 
         // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.input.set_mouse_cursor(new_cursor)
+        // A cursor chosen by script takes precedence over the automatic one.
+        if new_cursor != self.mouse_cursor.automatic {
+            self.mouse_cursor.automatic = new_cursor.clone();
+            if self.mouse_cursor.script_cursor().is_none() {
+                self.input.set_mouse_cursor(new_cursor)
+            }
         }
 
         hover_changed
@@ -763,6 +1595,12 @@ impl Player {
     }
 
     pub fn run_frame(&mut self) {
+        if let Some(now) = self.debug_time() {
+            if let Some(overlay) = &mut self.debug_overlay {
+                overlay.record_frame_start(now);
+            }
+        }
+
         self.update(|update_context| {
             // TODO: In what order are levels run?
             // NOTE: We have to copy all the layer pointers into a separate list
@@ -773,45 +1611,127 @@ impl Player {
             for level in levels {
                 level.run_frame(update_context);
             }
+
+            // AVM2 movies go through the phases of a frame once every
+            // timeline has advanced: `enterFrame`, then the constructors
+            // queued by the new frames, `frameConstructed`, the frame
+            // scripts, and finally `exitFrame`.
+            if let Some(root) = Self::avm2_root(update_context) {
+                crate::avm2::broadcast_frame_event(update_context, "enterFrame");
+                Self::run_actions(update_context);
+                crate::avm2::broadcast_frame_event(update_context, "frameConstructed");
+                crate::avm2::run_frame_scripts(update_context, root);
+                crate::avm2::broadcast_frame_event(update_context, "exitFrame");
+            }
         });
+        self.keyboard.end_frame();
         self.needs_render = true;
     }
 
     pub fn render(&mut self) {
+        if self.background_throttle != BackgroundThrottle::Visible {
+            return;
+        }
+
+        let render_start = self.debug_time();
+
+        // The area of the stage that is visible in the viewport.
+        let (x_min, y_min) = self.inverse_view_matrix * (Twips::new(0), Twips::new(0));
+        let (x_max, y_max) = self.inverse_view_matrix
+            * (
+                Twips::from_pixels(self.viewport_width.into()),
+                Twips::from_pixels(self.viewport_height.into()),
+            );
         let view_bounds = BoundingBox {
-            x_min: Twips::new(0),
-            y_min: Twips::new(0),
-            x_max: Twips::from_pixels(self.movie_width.into()),
-            y_max: Twips::from_pixels(self.movie_height.into()),
+            x_min,
+            y_min,
+            x_max,
+            y_max,
             valid: true,
         };
 
+        self.renderer.set_quality(self.quality, self.swf.version());
         self.renderer.begin_frame(self.background_color.clone());
 
-        let (renderer, transform_stack) = (&mut self.renderer, &mut self.transform_stack);
+        let (renderer, video, transform_stack, bitmap_caches, debug_overlay) = (
+            &mut self.renderer,
+            &mut self.video,
+            &mut self.transform_stack,
+            &mut self.bitmap_caches,
+            &mut self.debug_overlay,
+        );
+        let (swf, frame_rate, inverse_view_matrix) =
+            (&self.swf, self.frame_rate, self.inverse_view_matrix);
+        let gc_heap_size = self.gc_arena.total_allocated();
 
         transform_stack.push(&crate::transform::Transform {
             matrix: self.view_matrix,
             ..Default::default()
         });
-        self.gc_arena.mutate(|_gc_context, gc_root| {
+        let print_jobs = self.gc_arena.mutate(|gc_context, gc_root| {
+            let sent_print_jobs = gc_root.0.write(gc_context).print_jobs.take_sent();
             let root_data = gc_root.0.read();
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
+                video: video.deref_mut(),
                 library: &root_data.library,
                 transform_stack,
                 view_bounds,
                 clip_depth_stack: vec![],
+                gc_context,
+                bitmap_caches,
             };
 
             for (_depth, level) in root_data.levels.iter() {
-                level.render(&mut render_context);
+                render_display_object(&mut render_context, *level);
+            }
+
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context);
+
+            if let Some(overlay) = debug_overlay {
+                if overlay.needs_counts() {
+                    let mut counts = DebugCounts {
+                        gc_heap_size,
+                        timers: root_data.timers.num_timers(),
+                        sounds: root_data.audio_manager.num_sounds(),
+                        display_objects: 0,
+                    };
+                    for level in root_data.levels.values() {
+                        counts.count_display_objects(*level);
+                    }
+                    overlay.set_counts(counts);
+                }
+
+                let font = root_data
+                    .library
+                    .library_for_movie(swf.clone())
+                    .and_then(|library| library.device_font());
+                overlay.render(&mut render_context, font, frame_rate, inverse_view_matrix);
             }
+
+            print_job::render_jobs(&mut render_context, sent_print_jobs)
         });
         transform_stack.pop();
 
         self.renderer.draw_letterbox(self.letterbox);
         self.renderer.end_frame();
+        self.bitmap_caches.end_frame(self.renderer.deref_mut());
+
+        if let (Some(render_start), Some(render_end)) = (render_start, self.debug_time()) {
+            let draw_calls = self.renderer.last_frame_draw_count();
+            if let Some(overlay) = &mut self.debug_overlay {
+                overlay.record_render(render_end - render_start, draw_calls);
+            }
+        }
+
+        for pages in print_jobs {
+            let pages = print_job::read_pages(self.renderer.deref_mut(), pages);
+            if !pages.is_empty() {
+                self.input.print_pages(pages);
+            }
+        }
         self.needs_render = false;
     }
 
@@ -828,6 +1748,25 @@ impl Player {
         self.frame_rate
     }
 
+    /// Overrides the frame rate of the current movie, in FPS.
+    ///
+    /// The rate is clamped to the range that Flash Player accepts.
+    pub fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = Self::clamp_frame_rate(frame_rate);
+        self.audio.set_frame_rate(self.frame_rate);
+    }
+
+    /// Clamps a frame rate to the range that Flash Player accepts.
+    ///
+    /// `NaN` is treated as the lowest frame rate.
+    pub fn clamp_frame_rate(frame_rate: f64) -> f64 {
+        if frame_rate.is_nan() {
+            MIN_FRAME_RATE
+        } else {
+            frame_rate.max(MIN_FRAME_RATE).min(MAX_FRAME_RATE)
+        }
+    }
+
     pub fn renderer(&self) -> &Renderer {
         &self.renderer
     }
@@ -862,7 +1801,7 @@ impl Player {
                     Avm1::run_stack_frame_for_action(
                         actions.clip,
                         "[Frame]",
-                        context.swf.header().version,
+                        actions.clip.swf_version(),
                         bytecode,
                         context,
                     );
@@ -872,7 +1811,7 @@ impl Player {
                     constructor: Some(constructor),
                     events,
                 } => {
-                    let version = context.swf.version();
+                    let version = actions.clip.swf_version();
                     let globals = context.avm1.global_object_cell();
 
                     let mut activation = Activation::from_nothing(
@@ -892,7 +1831,7 @@ impl Player {
                                 let _ = activation.run_child_frame_for_action(
                                     "[Actions]",
                                     actions.clip,
-                                    activation.context.swf.header().version,
+                                    actions.clip.swf_version(),
                                     event,
                                 );
                             }
@@ -910,7 +1849,7 @@ impl Player {
                         Avm1::run_stack_frame_for_action(
                             actions.clip,
                             "[Construct]",
-                            context.swf.header().version,
+                            actions.clip.swf_version(),
                             event,
                             context,
                         );
@@ -921,7 +1860,7 @@ impl Player {
                     Avm1::run_stack_frame_for_method(
                         actions.clip,
                         object,
-                        context.swf.header().version,
+                        actions.clip.swf_version(),
                         context,
                         name,
                         &args,
@@ -938,7 +1877,7 @@ impl Player {
                     // so this doesn't require any further execution.
                     Avm1::notify_system_listeners(
                         actions.clip,
-                        context.swf.version(),
+                        actions.clip.swf_version(),
                         context,
                         listener,
                         method,
@@ -952,33 +1891,89 @@ impl Player {
                     is_lazy_initialize,
                     abc,
                 } => {
-                    if let Err(e) = Avm2::load_abc(abc, &name, is_lazy_initialize, context) {
+                    let movie = actions.clip.movie().unwrap_or_else(|| context.swf.clone());
+                    let domain = Avm2::movie_domain(context, movie);
+                    if let Err(e) = Avm2::load_abc(abc, &name, is_lazy_initialize, domain, context)
+                    {
                         log::warn!("Error loading ABC file: {}", e);
                     }
                 }
+
+                ActionType::ConstructDocumentClass { class_name } => {
+                    crate::avm2::construct_document_class(context, actions.clip, &class_name);
+                }
             }
         }
     }
 
+    /// Rebuilds the view matrices after the viewport or the stage layout changed.
+    ///
+    /// If this resized the stage, movies are notified with `Stage.onResize`.
+    fn relayout_stage(&mut self, old_stage_size: (u32, u32)) {
+        self.build_matrices();
+
+        if self.stage_size() != old_stage_size {
+            self.mutate_with_update_context(|context| {
+                // Nothing to notify before a movie is loaded.
+                if let Some(root) = context.levels.get(&0).copied() {
+                    context.action_queue.queue_actions(
+                        root,
+                        ActionType::NotifyListeners {
+                            listener: "Stage",
+                            method: "onResize",
+                            args: vec![],
+                        },
+                        false,
+                    );
+                    Self::run_actions(context);
+                }
+            });
+        }
+    }
+
     fn build_matrices(&mut self) {
-        // Create  view matrix to scale stage into viewport area.
+        // Create view matrix to scale stage into viewport area.
         let (movie_width, movie_height) = (self.movie_width as f32, self.movie_height as f32);
         let (viewport_width, viewport_height) =
             (self.viewport_width as f32, self.viewport_height as f32);
-        let movie_aspect = movie_width / movie_height;
-        let viewport_aspect = viewport_width / viewport_height;
-        let (scale, margin_width, margin_height) = if viewport_aspect > movie_aspect {
-            let scale = viewport_height / movie_height;
-            (scale, (viewport_width - movie_width * scale) / 2.0, 0.0)
-        } else {
-            let scale = viewport_width / movie_width;
-            (scale, 0.0, (viewport_height - movie_height * scale) / 2.0)
+        let scale_mode = self.effective_scale_mode();
+        let (scale_x, scale_y) = match scale_mode {
+            StageScaleMode::ExactFit => {
+                (viewport_width / movie_width, viewport_height / movie_height)
+            }
+            StageScaleMode::NoBorder => {
+                let scale = (viewport_width / movie_width).max(viewport_height / movie_height);
+                (scale, scale)
+            }
+            StageScaleMode::NoScale => (1.0, 1.0),
+            StageScaleMode::ShowAll => {
+                let scale = (viewport_width / movie_width).min(viewport_height / movie_height);
+                (scale, scale)
+            }
         };
+
+        // Position the stage within the leftover space according to its alignment.
+        let (extra_width, extra_height) = (
+            viewport_width - movie_width * scale_x,
+            viewport_height - movie_height * scale_y,
+        );
+        let align = self.stage_align;
+        let margin_width = StageAlign::offset(
+            extra_width,
+            align.contains(StageAlign::Left),
+            align.contains(StageAlign::Right),
+        );
+        let margin_height = StageAlign::offset(
+            extra_height,
+            align.contains(StageAlign::Top),
+            align.contains(StageAlign::Bottom),
+        );
+
         self.view_matrix = Matrix {
-            a: scale,
+            a: scale_x,
             b: 0.0,
             c: 0.0,
-            d: scale,
+            d: scale_y,
             tx: Twips::from_pixels(margin_width.into()),
             ty: Twips::from_pixels(margin_height.into()),
         };
@@ -986,11 +1981,14 @@ impl Player {
         self.inverse_view_matrix.invert();
 
         // Calculate letterbox dimensions.
-        // TODO: Letterbox should be an option; the original Flash Player defaults to showing content
-        // in the extra margins.
-        self.letterbox = if margin_width > 0.0 {
+        // Only `ShowAll` hides the margins, and only when the stage is centered within them;
+        // otherwise, content outside of the stage is visible, as in Flash Player.
+        let centered = |start, end| align.contains(start) == align.contains(end);
+        self.letterbox = if !self.letterbox_enabled || scale_mode != StageScaleMode::ShowAll {
+            Letterbox::None
+        } else if extra_width > 0.0 && centered(StageAlign::Left, StageAlign::Right) {
             Letterbox::Pillarbox(margin_width)
-        } else if margin_height > 0.0 {
+        } else if extra_height > 0.0 && centered(StageAlign::Top, StageAlign::Bottom) {
             Letterbox::Letterbox(margin_height)
         } else {
             Letterbox::None
@@ -1003,6 +2001,10 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        let stage_size = self.stage_size();
+        let is_user_event = self.is_user_event;
+        let view_matrix = self.view_matrix;
+
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
         let (
@@ -1013,6 +2015,7 @@ impl Player {
             audio,
             navigator,
             input,
+            keyboard,
             rng,
             mouse_position,
             stage_width,
@@ -1022,7 +2025,14 @@ impl Player {
             instance_counter,
             storage,
             locale,
+            font_provider,
+            video,
             needs_render,
+            mouse_cursor,
+            scale_mode,
+            stage_align,
+            quality,
+            frame_rate,
         ) = (
             self.player_version,
             &self.swf,
@@ -1031,21 +2041,32 @@ impl Player {
             self.audio.deref_mut(),
             self.navigator.deref_mut(),
             self.input.deref_mut(),
+            &mut self.keyboard,
             &mut self.rng,
             &self.mouse_pos,
-            Twips::from_pixels(self.movie_width.into()),
-            Twips::from_pixels(self.movie_height.into()),
+            Twips::from_pixels(stage_size.0.into()),
+            Twips::from_pixels(stage_size.1.into()),
             self.self_reference.clone(),
             &mut self.system,
             &mut self.instance_counter,
             self.storage.deref_mut(),
             self.locale.deref_mut(),
+            self.font_provider.deref_mut(),
+            self.video.deref_mut(),
             &mut self.needs_render,
+            &mut self.mouse_cursor,
+            &mut self.scale_mode,
+            &mut self.stage_align,
+            &mut self.quality,
+            &mut self.frame_rate,
         );
+        let old_stage_layout = (*scale_mode, *stage_align);
+        let old_frame_rate = *frame_rate;
 
-        self.gc_arena.mutate(|gc_context, gc_root| {
+        let ret = self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
+            let focus_tracker = root_data.focus_tracker;
             let (
                 levels,
                 library,
@@ -1057,7 +2078,13 @@ impl Player {
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                sockets,
+                local_connections,
+                print_jobs,
+                net_streams,
                 external_interface,
+                audio_manager,
+                current_context_menu,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1068,15 +2095,19 @@ impl Player {
                 rng,
                 renderer,
                 audio,
+                audio_manager,
                 navigator,
                 input,
+                keyboard,
                 action_queue,
                 gc_context,
                 levels,
                 mouse_hovered_object,
+                focus_tracker,
                 mouse_position,
                 drag_object,
                 stage_size: (stage_width, stage_height),
+                view_matrix,
                 system_prototypes: avm1.prototypes().clone(),
                 player,
                 load_manager,
@@ -1084,13 +2115,26 @@ impl Player {
                 instance_counter,
                 storage,
                 locale,
+                font_provider,
+                video,
                 shared_objects,
                 unbound_text_fields,
                 timers,
+                sockets,
+                local_connections,
+                print_jobs,
+                net_streams,
                 needs_render,
+                mouse_cursor,
+                scale_mode,
+                stage_align,
+                quality,
+                frame_rate,
                 avm1,
                 avm2,
                 external_interface,
+                current_context_menu,
+                is_user_event,
             };
 
             let ret = f(&mut update_context);
@@ -1098,7 +2142,19 @@ impl Player {
             // Hovered object may have been updated; copy it back to the GC root.
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
             ret
-        })
+        });
+
+        // Scripts may have changed how the stage is scaled.
+        if (self.scale_mode, self.stage_align) != old_stage_layout {
+            self.relayout_stage(stage_size);
+        }
+
+        // Scripts or loaded movies may have changed the frame rate.
+        if self.frame_rate != old_frame_rate {
+            self.audio.set_frame_rate(self.frame_rate);
+        }
+
+        ret
     }
 
     /// Loads font data from the given buffer.
@@ -1115,6 +2171,40 @@ impl Player {
         Ok(device_font)
     }
 
+    /// Registers a TrueType or OpenType font file as a device font.
+    ///
+    /// The font is available to text fields under the given name, as well as
+    /// under the family names stored in the font file. Registered fonts take
+    /// priority over the fonts of the font provider. If only the regular style
+    /// of a family is registered, it is also used for its bold and italic
+    /// styles.
+    pub fn register_device_font(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Result<(), Error> {
+        let font_file = FontFile::parse(data, name, is_bold, is_italic)?;
+        let renderer = self.renderer.deref_mut();
+
+        self.gc_arena.mutate(|gc_context, gc_root| {
+            let font = crate::font::Font::from_swf_tag(gc_context, renderer, &font_file.font)?;
+            let mut root_data = gc_root.0.write(gc_context);
+            root_data.library.register_device_font(name, font);
+            for family_name in &font_file.family_names {
+                root_data.library.register_device_font(family_name, font);
+            }
+
+            info!(
+                "Registered device font {} ({})",
+                name,
+                font_file.family_names.join(", ")
+            );
+            Ok(())
+        })
+    }
+
     /// Update the current state of the player.
     ///
     /// The given function will be called with the current stage root, current
@@ -1132,6 +2222,9 @@ impl Player {
 
             Self::run_actions(context);
 
+            // Drop finished sounds and apply any changed sound transforms.
+            context.audio_manager.update_sounds(context.audio);
+
             rval
         });
 
@@ -1153,14 +2246,102 @@ impl Player {
             for so in shared_objects.values() {
                 let _ = crate::avm1::globals::shared_object::flush(&mut activation, *so, &[]);
             }
+            drop(activation);
+
+            Avm2::flush_shared_objects(context);
         });
     }
 
+    /// Import a shared object from the contents of a `.sol` file.
+    ///
+    /// `name` is the key the shared object is stored under, which for AVM2
+    /// movies includes its local path.
+    /// If the movie currently has the shared object open, the imported
+    /// properties are merged into its `data` and written back to storage.
+    /// Otherwise, the stored shared object is replaced.
+    pub fn import_shared_object(&mut self, name: &str, data: &[u8]) -> Result<(), SolError> {
+        let json = Sol::read(data)?.to_json();
+
+        self.update(|context| {
+            if Avm2::import_shared_object(context, name, &json) {
+                return;
+            }
+
+            if let Some(so) = context.shared_objects.get(name).copied() {
+                let mut activation = Activation::from_stub(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[Import Shared Object]"),
+                );
+                if let Ok(Value::Object(data)) = so.get("data", &mut activation) {
+                    crate::avm1::globals::shared_object::recursive_deserialize(
+                        json,
+                        &mut activation,
+                        data,
+                    );
+                }
+                let _ = crate::avm1::globals::shared_object::flush(&mut activation, so, &[]);
+            } else {
+                context.storage.put_string(name, json.dump());
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Export a shared object as the contents of a `.sol` file.
+    ///
+    /// If the movie has the shared object open, the file is written with its
+    /// name and, for AVM2 movies, its `objectEncoding`. Otherwise the name is
+    /// taken from the end of the key and the file is encoded as AMF0.
+    /// Any pending changes to open shared objects are flushed first.
+    pub fn export_shared_object(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.flush_shared_objects();
+
+        let (sol_name, encoding) = self
+            .update(|context| {
+                Avm2::shared_object_header(context, name).or_else(|| {
+                    let so = context.shared_objects.get(name)?.as_shared_object()?;
+                    Some((so.get_name(), ObjectEncoding::Amf0))
+                })
+            })
+            .unwrap_or_else(|| {
+                // A shared object that isn't open is only known by its key, which
+                // ends with its name.
+                let sol_name = name.rsplit('/').next().unwrap_or(name);
+                (sol_name.to_string(), ObjectEncoding::Amf0)
+            });
+
+        let saved = self.storage.get_string(name)?;
+        let json = json::parse(&saved).ok()?;
+        Some(Sol::from_json(&sol_name, &json, encoding).write())
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
-        self.time_til_next_timer =
-            self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
+        // While throttled, no timer catches up on missed ticks, so each fires at most once per tick.
+        let frame_time = if self.throttled_frame_rate().is_some() {
+            0.0
+        } else {
+            1000.0 / self.frame_rate
+        };
+        self.time_til_next_timer = self
+            .mutate_with_update_context(|context| Timers::update_timers(context, dt, frame_time));
+    }
+
+    /// Fire events for any socket activity since the last update.
+    pub fn update_sockets(&mut self) {
+        self.update(|context| Sockets::update_sockets(context));
+    }
+
+    /// Deliver any messages sent with `LocalConnection` since the last update.
+    pub fn update_local_connections(&mut self) {
+        self.update(|context| LocalConnections::update_local_connections(context));
+    }
+
+    /// Advance the playback of all `NetStream`s by `dt` milliseconds.
+    pub fn update_net_streams(&mut self, dt: f64) {
+        self.update(|context| NetStreams::update_net_streams(context, dt));
     }
 
     /// Returns whether this player consumes mouse wheel events.
@@ -1175,6 +2356,15 @@ impl Player {
         });
     }
 
+    /// Sets the handler for FSCommands issued by the movie.
+    ///
+    /// Any command that the provider doesn't recognise is logged as unhandled.
+    pub fn set_fs_command_provider(&mut self, provider: Box<dyn FsCommandProvider>) {
+        self.mutate_with_update_context(|context| {
+            context.external_interface.set_fs_command_provider(provider)
+        });
+    }
+
     pub fn call_internal_interface(
         &mut self,
         name: &str,
@@ -1206,3 +2396,58 @@ unsafe impl<'gc> gc_arena::Collect for DragObject<'gc> {
         self.display_object.trace(cc);
     }
 }
+
+/// The state of the mouse cursor.
+///
+/// The cursor is normally chosen automatically based on what the mouse is
+/// hovering over, but scripts may override it by setting `Mouse.cursor`.
+pub struct MouseCursorState {
+    /// The cursor chosen automatically from the hovered object.
+    pub automatic: MouseCursor,
+
+    /// The name of the cursor chosen by script, or `None` if the cursor is
+    /// chosen automatically.
+    pub script_cursor_name: Option<String>,
+
+    /// Custom cursors registered by `Mouse.registerCursor`.
+    pub registered: HashMap<String, MouseCursor>,
+}
+
+impl MouseCursorState {
+    /// Get the cursor chosen by script, if any.
+    pub fn script_cursor(&self) -> Option<MouseCursor> {
+        let name = self.script_cursor_name.as_deref()?;
+        match name {
+            "arrow" => Some(MouseCursor::Arrow),
+            "button" => Some(MouseCursor::Hand),
+            "hand" => Some(MouseCursor::Grab),
+            "ibeam" => Some(MouseCursor::IBeam),
+            _ => self.registered.get(name).cloned(),
+        }
+    }
+
+    /// Get the cursor that should currently be displayed.
+    pub fn cursor(&self) -> MouseCursor {
+        self.script_cursor()
+            .unwrap_or_else(|| self.automatic.clone())
+    }
+}
+
+impl Default for MouseCursorState {
+    fn default() -> Self {
+        Self {
+            automatic: MouseCursor::Arrow,
+            script_cursor_name: None,
+            registered: HashMap::new(),
+        }
+    }
+}
+
+/// Creates the random number generator for the given seed.
+///
+/// A seed of 0 gives the generator that the player has always started with.
+fn seeded_rng(seed: u64) -> SmallRng {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&seed.to_le_bytes());
+    SmallRng::from_seed(bytes)
+}
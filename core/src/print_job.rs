@@ -0,0 +1,262 @@
+//! Printing for `PrintJob`.
+//!
+//! Pages are rendered with the next frame after a job is sent, each into its own offscreen
+//! bitmap, and then read back and handed to the input backend to be printed.
+
+use crate::avm1::Object;
+use crate::backend::input::{PrintPageFormat, PrintedPage};
+use crate::backend::render::{unmultiply_alpha_rgba, BitmapFormat, BitmapHandle, RenderBackend};
+use crate::bounding_box::BoundingBox;
+use crate::context::RenderContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::prelude::*;
+use crate::transform::{Transform, TransformStack};
+use gc_arena::{Collect, CollectionContext};
+
+/// The resolution that pages are printed at, in pixels per point (288 DPI).
+const PIXELS_PER_POINT: f64 = 4.0;
+
+/// The largest width or height of a rendered page, in pixels.
+const MAX_DIMENSION: u32 = 8191;
+
+/// A page added with `PrintJob.addPage`.
+pub struct PrintPage<'gc> {
+    /// The display object to print.
+    pub target: DisplayObject<'gc>,
+
+    /// The area of `target` to print, in its own coordinate space.
+    /// Defaults to the bounds of `target`.
+    pub area: Option<BoundingBox>,
+
+    /// Whether to print at screen resolution instead of printer resolution, as with the
+    /// `printAsBitmap` option.
+    pub print_as_bitmap: bool,
+}
+
+/// A print job and the pages added to it.
+pub struct PrintJob<'gc> {
+    /// The `PrintJob` object that started this job.
+    object: Object<'gc>,
+
+    format: PrintPageFormat,
+    pages: Vec<PrintPage<'gc>>,
+}
+
+/// A page that has been rendered into an offscreen bitmap, but not yet read back.
+pub struct RenderedPage {
+    handle: BitmapHandle,
+    pixels_per_point: f64,
+}
+
+/// Manages the print jobs of the movie.
+pub struct PrintJobs<'gc> {
+    /// Jobs that have been started, but not yet sent.
+    started: Vec<PrintJob<'gc>>,
+
+    /// Jobs that have been sent, to be printed with the next rendered frame.
+    sent: Vec<PrintJob<'gc>>,
+}
+
+unsafe impl<'gc> Collect for PrintJobs<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for job in self.started.iter().chain(&self.sent) {
+            job.object.trace(cc);
+            for page in &job.pages {
+                page.target.trace(cc);
+            }
+        }
+    }
+}
+
+impl<'gc> PrintJobs<'gc> {
+    pub fn new() -> Self {
+        Self {
+            started: Vec::new(),
+            sent: Vec::new(),
+        }
+    }
+
+    /// Start a print job for `object`, discarding any unsent job it already had.
+    pub fn start(&mut self, object: Object<'gc>, format: PrintPageFormat) {
+        self.started
+            .retain(|job| !Object::ptr_eq(job.object, object));
+        self.started.push(PrintJob {
+            object,
+            format,
+            pages: Vec::new(),
+        });
+    }
+
+    /// Add a page to the job of `object`.
+    ///
+    /// Returns `false` if `object` has not started a job.
+    pub fn add_page(&mut self, object: Object<'gc>, page: PrintPage<'gc>) -> bool {
+        match self
+            .started
+            .iter_mut()
+            .find(|job| Object::ptr_eq(job.object, object))
+        {
+            Some(job) => {
+                job.pages.push(page);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Send the job of `object` to the printer with the next rendered frame.
+    ///
+    /// Returns `false` if `object` has not started a job.
+    pub fn send(&mut self, object: Object<'gc>) -> bool {
+        match self
+            .started
+            .iter()
+            .position(|job| Object::ptr_eq(job.object, object))
+        {
+            Some(index) => {
+                let job = self.started.remove(index);
+                if !job.pages.is_empty() {
+                    self.sent.push(job);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take the jobs that have been sent since the last rendered frame.
+    pub fn take_sent(&mut self) -> Vec<PrintJob<'gc>> {
+        std::mem::take(&mut self.sent)
+    }
+}
+
+impl<'gc> Default for PrintJobs<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render the pages of sent jobs into offscreen bitmaps.
+///
+/// This must be called while a frame is being rendered. The pages can be read back with
+/// `read_pages` once the frame has ended.
+pub fn render_jobs<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    jobs: Vec<PrintJob<'gc>>,
+) -> Vec<Vec<RenderedPage>> {
+    jobs.iter()
+        .map(|job| {
+            job.pages
+                .iter()
+                .filter_map(|page| render_page(context, page, &job.format))
+                .collect()
+        })
+        .collect()
+}
+
+/// Render a single page into a new offscreen bitmap.
+fn render_page<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    page: &PrintPage<'gc>,
+    format: &PrintPageFormat,
+) -> Option<RenderedPage> {
+    let area = page
+        .area
+        .clone()
+        .unwrap_or_else(|| page.target.bounds_with_transform(&Matrix::default()));
+    if !area.valid {
+        return None;
+    }
+
+    // One pixel of the movie is printed as one point. Anything outside the printable area of
+    // the paper is cut off.
+    let width = (area.x_max - area.x_min)
+        .to_pixels()
+        .min(format.page_width.into());
+    let height = (area.y_max - area.y_min)
+        .to_pixels()
+        .min(format.page_height.into());
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+
+    let mut pixels_per_point = if page.print_as_bitmap {
+        1.0
+    } else {
+        PIXELS_PER_POINT
+    };
+    pixels_per_point = pixels_per_point.min(f64::from(MAX_DIMENSION) / width.max(height));
+    let bitmap_width = (width * pixels_per_point).ceil() as u32;
+    let bitmap_height = (height * pixels_per_point).ceil() as u32;
+
+    let handle = context
+        .renderer
+        .create_offscreen_bitmap(bitmap_width, bitmap_height)?;
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: Matrix {
+            a: pixels_per_point as f32,
+            d: pixels_per_point as f32,
+            tx: Twips::from_pixels(-area.x_min.to_pixels() * pixels_per_point),
+            ty: Twips::from_pixels(-area.y_min.to_pixels() * pixels_per_point),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    context.renderer.push_offscreen_target(handle);
+    let mut page_context = RenderContext {
+        renderer: &mut *context.renderer,
+        video: &mut *context.video,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        // The whole page is printed, even the parts outside of the stage.
+        view_bounds: page.target.world_bounds(),
+        clip_depth_stack: vec![],
+        gc_context: context.gc_context,
+        bitmap_caches: &mut *context.bitmap_caches,
+    };
+    page.target.render_self(&mut page_context);
+    page_context.renderer.pop_offscreen_target();
+
+    Some(RenderedPage {
+        handle,
+        pixels_per_point,
+    })
+}
+
+/// Read back the pixels of rendered pages, releasing their offscreen bitmaps.
+///
+/// This must be called after the frame the pages were rendered in has ended.
+pub fn read_pages(renderer: &mut dyn RenderBackend, pages: Vec<RenderedPage>) -> Vec<PrintedPage> {
+    let mut printed = Vec::with_capacity(pages.len());
+    for page in pages {
+        let bitmap = renderer.offscreen_bitmap_pixels(page.handle);
+        renderer.remove_offscreen_bitmap(page.handle);
+
+        let bitmap = match bitmap {
+            Some(bitmap) => bitmap,
+            None => {
+                log::warn!("PrintJob: The renderer can't read back printed pages");
+                continue;
+            }
+        };
+        let mut pixels = match bitmap.data {
+            BitmapFormat::Rgba(pixels) => pixels,
+            BitmapFormat::Rgb(pixels) => pixels
+                .chunks_exact(3)
+                .flat_map(|rgb| vec![rgb[0], rgb[1], rgb[2], 255])
+                .collect(),
+        };
+        unmultiply_alpha_rgba(&mut pixels);
+
+        printed.push(PrintedPage {
+            width: bitmap.width,
+            height: bitmap.height,
+            pixels,
+            pixels_per_point: page.pixels_per_point,
+        });
+    }
+    printed
+}
@@ -0,0 +1,468 @@
+//! Recording and replaying player sessions
+//!
+//! A recording is a journal of every `PlayerEvent` given to the player and
+//! every tick it ran, along with the random seed and the wall clock time when
+//! the recording started. Replaying it feeds the same input at the same points
+//! of the movie's timeline, so that a movie runs identically every time.
+//!
+//! The file starts with a header:
+//!
+//! * The magic bytes `RRP` and a format version byte.
+//! * The random seed, as a little-endian `u64`.
+//! * The start time, in milliseconds since the Unix epoch, as a little-endian `i64`.
+//! * The offset of the local timezone from UTC, in seconds, as a little-endian `i32`.
+//!
+//! followed by entries, each a tag byte and the fields of the tick or event.
+
+use crate::backend::locale::LocaleBackend;
+use crate::events::{KeyCode, KeyLocation, MouseWheelDelta, PlayerEvent, TouchPhase};
+use chrono::{DateTime, Duration, FixedOffset, LocalResult, TimeZone, Utc};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
+use std::rc::Rc;
+use thiserror::Error;
+
+const MAGIC: &[u8; 3] = b"RRP";
+const VERSION: u8 = 1;
+
+const TAG_TICK: u8 = 0;
+const TAG_KEY_DOWN: u8 = 1;
+const TAG_KEY_UP: u8 = 2;
+const TAG_MOUSE_MOVE: u8 = 3;
+const TAG_MOUSE_UP: u8 = 4;
+const TAG_MOUSE_DOWN: u8 = 5;
+const TAG_MOUSE_LEFT: u8 = 6;
+const TAG_MOUSE_WHEEL: u8 = 7;
+const TAG_TEXT_INPUT: u8 = 8;
+const TAG_TOUCH: u8 = 9;
+
+/// Stored in place of the character of a key event that has none.
+const NO_CHAR: u32 = u32::MAX;
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("Not a recording")]
+    InvalidHeader,
+
+    #[error("Unsupported recording version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Invalid recording data")]
+    InvalidData,
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// The state of the player when a recording was started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayHeader {
+    /// The seed of the player's random number generator.
+    pub seed: u64,
+
+    /// The wall clock time.
+    pub start_time: DateTime<Utc>,
+
+    /// The offset of the local timezone from UTC.
+    pub timezone: FixedOffset,
+}
+
+/// Something that happened while recording.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayEntry {
+    /// The player ticked by the given number of milliseconds.
+    Tick(f64),
+
+    /// The player was given an event.
+    Event(PlayerEvent),
+}
+
+/// Writes a recording as the player runs.
+pub struct ReplayRecorder {
+    writer: Box<dyn Write>,
+}
+
+impl ReplayRecorder {
+    pub fn new(mut writer: Box<dyn Write>, header: &ReplayHeader) -> Result<Self, ReplayError> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&header.seed.to_le_bytes())?;
+        writer.write_all(&header.start_time.timestamp_millis().to_le_bytes())?;
+        writer.write_all(&header.timezone.local_minus_utc().to_le_bytes())?;
+        Ok(Self { writer })
+    }
+
+    pub fn record(&mut self, entry: &ReplayEntry) -> Result<(), ReplayError> {
+        let mut buf = Vec::with_capacity(32);
+        match entry {
+            ReplayEntry::Tick(dt) => {
+                buf.push(TAG_TICK);
+                buf.extend_from_slice(&dt.to_le_bytes());
+            }
+            ReplayEntry::Event(event) => write_event(&mut buf, event),
+        }
+        self.writer.write_all(&buf)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), ReplayError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A recording that is being played back.
+pub struct Replay {
+    pub header: ReplayHeader,
+    entries: VecDeque<ReplayEntry>,
+}
+
+impl Replay {
+    /// Reads a whole recording.
+    pub fn read(mut reader: impl Read) -> Result<Self, ReplayError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let mut reader = &data[..];
+
+        let mut magic = [0; 3];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| ReplayError::InvalidHeader)?;
+        if &magic != MAGIC {
+            return Err(ReplayError::InvalidHeader);
+        }
+        let version = read_u8(&mut reader)?;
+        if version != VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let seed = u64::from_le_bytes(read_array(&mut reader)?);
+        let start_time =
+            match Utc.timestamp_millis_opt(i64::from_le_bytes(read_array(&mut reader)?)) {
+                LocalResult::Single(time) => time,
+                _ => return Err(ReplayError::InvalidData),
+            };
+        let timezone = FixedOffset::east_opt(i32::from_le_bytes(read_array(&mut reader)?))
+            .ok_or(ReplayError::InvalidData)?;
+
+        let mut entries = VecDeque::new();
+        while !reader.is_empty() {
+            entries.push_back(read_entry(&mut reader)?);
+        }
+
+        Ok(Self {
+            header: ReplayHeader {
+                seed,
+                start_time,
+                timezone,
+            },
+            entries,
+        })
+    }
+
+    /// Takes the next entry, or `None` once the recording has ended.
+    pub fn next_entry(&mut self) -> Option<ReplayEntry> {
+        self.entries.pop_front()
+    }
+}
+
+/// The wall clock seen by a movie while recording or replaying.
+///
+/// It starts at the recorded time and only advances as the player ticks.
+pub struct ReplayClock {
+    start_time: DateTime<Utc>,
+    timezone: FixedOffset,
+    elapsed: Cell<f64>,
+}
+
+impl ReplayClock {
+    pub fn new(start_time: DateTime<Utc>, timezone: FixedOffset) -> Rc<Self> {
+        Rc::new(Self {
+            start_time,
+            timezone,
+            elapsed: Cell::new(0.0),
+        })
+    }
+
+    /// Advances the clock by `dt` milliseconds.
+    pub fn advance(&self, dt: f64) {
+        self.elapsed.set(self.elapsed.get() + dt);
+    }
+}
+
+/// A locale backend that reads the time from a `ReplayClock`.
+pub struct ReplayLocaleBackend {
+    clock: Rc<ReplayClock>,
+}
+
+impl ReplayLocaleBackend {
+    pub fn new(clock: Rc<ReplayClock>) -> Self {
+        Self { clock }
+    }
+}
+
+impl LocaleBackend for ReplayLocaleBackend {
+    fn get_current_date_time(&self) -> DateTime<Utc> {
+        self.clock.start_time + Duration::milliseconds(self.clock.elapsed.get() as i64)
+    }
+
+    fn get_timezone(&self) -> FixedOffset {
+        self.clock.timezone
+    }
+}
+
+fn write_event(buf: &mut Vec<u8>, event: &PlayerEvent) {
+    fn write_key(
+        buf: &mut Vec<u8>,
+        key_code: KeyCode,
+        key_char: Option<char>,
+        key_location: KeyLocation,
+    ) {
+        buf.push(key_code.into());
+        buf.extend_from_slice(&key_char.map_or(NO_CHAR, u32::from).to_le_bytes());
+        buf.push(u32::from(key_location) as u8);
+    }
+
+    fn write_position(buf: &mut Vec<u8>, x: f64, y: f64) {
+        buf.extend_from_slice(&x.to_le_bytes());
+        buf.extend_from_slice(&y.to_le_bytes());
+    }
+
+    match *event {
+        PlayerEvent::KeyDown {
+            key_code,
+            key_char,
+            key_location,
+        } => {
+            buf.push(TAG_KEY_DOWN);
+            write_key(buf, key_code, key_char, key_location);
+        }
+        PlayerEvent::KeyUp {
+            key_code,
+            key_char,
+            key_location,
+        } => {
+            buf.push(TAG_KEY_UP);
+            write_key(buf, key_code, key_char, key_location);
+        }
+        PlayerEvent::MouseMove { x, y } => {
+            buf.push(TAG_MOUSE_MOVE);
+            write_position(buf, x, y);
+        }
+        PlayerEvent::MouseUp { x, y } => {
+            buf.push(TAG_MOUSE_UP);
+            write_position(buf, x, y);
+        }
+        PlayerEvent::MouseDown { x, y } => {
+            buf.push(TAG_MOUSE_DOWN);
+            write_position(buf, x, y);
+        }
+        PlayerEvent::MouseLeft => buf.push(TAG_MOUSE_LEFT),
+        PlayerEvent::MouseWheel { delta } => {
+            buf.push(TAG_MOUSE_WHEEL);
+            let (kind, amount) = match delta {
+                MouseWheelDelta::Lines(lines) => (0, lines),
+                MouseWheelDelta::Pixels(pixels) => (1, pixels),
+            };
+            buf.push(kind);
+            buf.extend_from_slice(&amount.to_le_bytes());
+        }
+        PlayerEvent::TextInput { codepoint } => {
+            buf.push(TAG_TEXT_INPUT);
+            buf.extend_from_slice(&u32::from(codepoint).to_le_bytes());
+        }
+        PlayerEvent::Touch {
+            phase,
+            id,
+            x,
+            y,
+            pressure,
+        } => {
+            buf.push(TAG_TOUCH);
+            buf.push(match phase {
+                TouchPhase::Begin => 0,
+                TouchPhase::Move => 1,
+                TouchPhase::End => 2,
+            });
+            buf.extend_from_slice(&id.to_le_bytes());
+            write_position(buf, x, y);
+            buf.extend_from_slice(&pressure.to_le_bytes());
+        }
+    }
+}
+
+fn read_entry(reader: &mut &[u8]) -> Result<ReplayEntry, ReplayError> {
+    let event = match read_u8(reader)? {
+        TAG_TICK => return Ok(ReplayEntry::Tick(read_f64(reader)?)),
+        TAG_KEY_DOWN => {
+            let (key_code, key_char, key_location) = read_key(reader)?;
+            PlayerEvent::KeyDown {
+                key_code,
+                key_char,
+                key_location,
+            }
+        }
+        TAG_KEY_UP => {
+            let (key_code, key_char, key_location) = read_key(reader)?;
+            PlayerEvent::KeyUp {
+                key_code,
+                key_char,
+                key_location,
+            }
+        }
+        TAG_MOUSE_MOVE => PlayerEvent::MouseMove {
+            x: read_f64(reader)?,
+            y: read_f64(reader)?,
+        },
+        TAG_MOUSE_UP => PlayerEvent::MouseUp {
+            x: read_f64(reader)?,
+            y: read_f64(reader)?,
+        },
+        TAG_MOUSE_DOWN => PlayerEvent::MouseDown {
+            x: read_f64(reader)?,
+            y: read_f64(reader)?,
+        },
+        TAG_MOUSE_LEFT => PlayerEvent::MouseLeft,
+        TAG_MOUSE_WHEEL => {
+            let delta = match read_u8(reader)? {
+                0 => MouseWheelDelta::Lines(read_f64(reader)?),
+                1 => MouseWheelDelta::Pixels(read_f64(reader)?),
+                _ => return Err(ReplayError::InvalidData),
+            };
+            PlayerEvent::MouseWheel { delta }
+        }
+        TAG_TEXT_INPUT => PlayerEvent::TextInput {
+            codepoint: read_char(reader)?.ok_or(ReplayError::InvalidData)?,
+        },
+        TAG_TOUCH => {
+            let phase = match read_u8(reader)? {
+                0 => TouchPhase::Begin,
+                1 => TouchPhase::Move,
+                2 => TouchPhase::End,
+                _ => return Err(ReplayError::InvalidData),
+            };
+            PlayerEvent::Touch {
+                phase,
+                id: i32::from_le_bytes(read_array(reader)?),
+                x: read_f64(reader)?,
+                y: read_f64(reader)?,
+                pressure: read_f64(reader)?,
+            }
+        }
+        _ => return Err(ReplayError::InvalidData),
+    };
+    Ok(ReplayEntry::Event(event))
+}
+
+fn read_key(reader: &mut &[u8]) -> Result<(KeyCode, Option<char>, KeyLocation), ReplayError> {
+    let key_code = KeyCode::try_from(read_u8(reader)?).map_err(|_| ReplayError::InvalidData)?;
+    let key_char = read_char(reader)?;
+    let key_location =
+        KeyLocation::try_from(u32::from(read_u8(reader)?)).map_err(|_| ReplayError::InvalidData)?;
+    Ok((key_code, key_char, key_location))
+}
+
+fn read_char(reader: &mut &[u8]) -> Result<Option<char>, ReplayError> {
+    match u32::from_le_bytes(read_array(reader)?) {
+        NO_CHAR => Ok(None),
+        c => std::char::from_u32(c)
+            .map(Some)
+            .ok_or(ReplayError::InvalidData),
+    }
+}
+
+fn read_u8(reader: &mut &[u8]) -> Result<u8, ReplayError> {
+    let [value] = read_array::<[u8; 1]>(reader)?;
+    Ok(value)
+}
+
+fn read_f64(reader: &mut &[u8]) -> Result<f64, ReplayError> {
+    Ok(f64::from_le_bytes(read_array(reader)?))
+}
+
+fn read_array<T: Default + AsMut<[u8]>>(reader: &mut &[u8]) -> Result<T, ReplayError> {
+    let mut array = T::default();
+    reader
+        .read_exact(array.as_mut())
+        .map_err(|_| ReplayError::InvalidData)?;
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let header = ReplayHeader {
+            seed: 0x1234_5678_9abc_def0,
+            start_time: Utc.ymd(2001, 2, 3).and_hms_milli(4, 5, 6, 789),
+            timezone: FixedOffset::east(20700),
+        };
+        let entries = vec![
+            ReplayEntry::Tick(16.5),
+            ReplayEntry::Event(PlayerEvent::MouseMove { x: 10.0, y: 20.5 }),
+            ReplayEntry::Event(PlayerEvent::KeyDown {
+                key_code: KeyCode::A,
+                key_char: Some('a'),
+                key_location: KeyLocation::Standard,
+            }),
+            ReplayEntry::Event(PlayerEvent::KeyUp {
+                key_code: KeyCode::Shift,
+                key_char: None,
+                key_location: KeyLocation::Right,
+            }),
+            ReplayEntry::Event(PlayerEvent::MouseWheel {
+                delta: MouseWheelDelta::Pixels(-3.0),
+            }),
+            ReplayEntry::Event(PlayerEvent::TextInput { codepoint: 'é' }),
+            ReplayEntry::Event(PlayerEvent::Touch {
+                phase: TouchPhase::Move,
+                id: 7,
+                x: 1.0,
+                y: 2.0,
+                pressure: 0.5,
+            }),
+            ReplayEntry::Event(PlayerEvent::MouseLeft),
+            ReplayEntry::Tick(33.0),
+        ];
+
+        let data = Rc::new(std::cell::RefCell::new(Vec::new()));
+        struct SharedWriter(Rc<std::cell::RefCell<Vec<u8>>>);
+        impl Write for SharedWriter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut recorder =
+            ReplayRecorder::new(Box::new(SharedWriter(data.clone())), &header).unwrap();
+        for entry in &entries {
+            recorder.record(entry).unwrap();
+        }
+
+        let data = data.borrow();
+        let mut replay = Replay::read(&data[..]).unwrap();
+        assert_eq!(replay.header, header);
+        for entry in entries {
+            assert_eq!(replay.next_entry(), Some(entry));
+        }
+        assert_eq!(replay.next_entry(), None);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            Replay::read(&b"FWS\x0a"[..]),
+            Err(ReplayError::InvalidHeader)
+        ));
+        assert!(matches!(
+            Replay::read(&b"RRP\x01\x00"[..]),
+            Err(ReplayError::InvalidData)
+        ));
+    }
+}
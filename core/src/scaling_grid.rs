@@ -0,0 +1,322 @@
+//! 9-slice scaling of movie clips and buttons (`scale9Grid`).
+//!
+//! The grid splits the artwork of an object into nine parts. When the object is scaled, the
+//! corners keep their size, the edges are only stretched along their length, and the centre is
+//! stretched both ways. Only the vector art directly inside the object is sliced; nested clips,
+//! bitmaps and text are scaled normally, as in Flash Player. The grid is ignored while the
+//! object is rotated or skewed.
+//!
+//! Each part is rendered through a rectangular mask, with its own transform that maps the
+//! part of the artwork onto the space it takes up once scaled.
+
+use crate::context::RenderContext;
+use crate::drawing::Drawing;
+use crate::prelude::*;
+use crate::shape_utils::DrawCommand;
+use crate::transform::Transform;
+use swf::{FillStyle, Rectangle};
+
+/// The size of the square used to mask each slice, in twips.
+const MASK_SIZE: i32 = 1000;
+
+/// The `scale9Grid` of a display object.
+#[derive(Clone, Debug)]
+pub struct ScalingGrid {
+    /// The centre rectangle of the grid, in the coordinate space of the object.
+    rect: Rectangle,
+
+    /// A square that is transformed onto each slice to clip it.
+    mask: Drawing,
+}
+
+/// One of the nine parts of a grid.
+#[derive(Clone, Debug, PartialEq)]
+struct Slice {
+    /// Maps the artwork of this slice to where it is drawn.
+    matrix: Matrix,
+
+    /// Maps the mask square onto the area this slice is drawn in.
+    mask_matrix: Matrix,
+}
+
+impl ScalingGrid {
+    pub fn new(rect: Rectangle) -> Self {
+        let mut mask = Drawing::new();
+        mask.set_fill_style(Some(FillStyle::Color(Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        })));
+        let size = Twips::new(MASK_SIZE);
+        mask.draw_command(DrawCommand::MoveTo {
+            x: Twips::zero(),
+            y: Twips::zero(),
+        });
+        for &(x, y) in &[
+            (size, Twips::zero()),
+            (size, size),
+            (Twips::zero(), size),
+            (Twips::zero(), Twips::zero()),
+        ] {
+            mask.draw_command(DrawCommand::LineTo { x, y });
+        }
+        Self { rect, mask }
+    }
+
+    /// The centre rectangle of the grid.
+    pub fn rect(&self) -> &Rectangle {
+        &self.rect
+    }
+
+    /// Splits the artwork of an object with the given matrix into slices, if it is scaled.
+    ///
+    /// `bounds` is the untransformed bounds of all of the object's content. Returns `None` if the
+    /// content should be rendered normally.
+    pub fn slices(&self, matrix: &Matrix, bounds: &BoundingBox) -> Option<GridSlices<'_>> {
+        Some(GridSlices {
+            grid: self,
+            slices: self.compute_slices(matrix, bounds)?,
+        })
+    }
+
+    /// Splits content with the given bounds into slices, for an object with the given matrix.
+    ///
+    /// Returns `None` if the content should be rendered normally, because the object is not
+    /// scaled, or is rotated or skewed.
+    fn compute_slices(&self, matrix: &Matrix, bounds: &BoundingBox) -> Option<Vec<Slice>> {
+        if !bounds.valid || matrix.b != 0.0 || matrix.c != 0.0 {
+            return None;
+        }
+        let (scale_x, scale_y) = (f64::from(matrix.a.abs()), f64::from(matrix.d.abs()));
+        if scale_x == 0.0 || scale_y == 0.0 || (scale_x == 1.0 && scale_y == 1.0) {
+            return None;
+        }
+
+        let columns = axis_segments(
+            bounds.x_min.get().into(),
+            bounds.x_max.get().into(),
+            self.rect.x_min.get().into(),
+            self.rect.x_max.get().into(),
+            scale_x,
+        );
+        let rows = axis_segments(
+            bounds.y_min.get().into(),
+            bounds.y_max.get().into(),
+            self.rect.y_min.get().into(),
+            self.rect.y_max.get().into(),
+            scale_y,
+        );
+
+        let mut slices = Vec::with_capacity(9);
+        for row in rows.iter().filter(|row| !row.is_empty()) {
+            for column in columns.iter().filter(|column| !column.is_empty()) {
+                slices.push(Slice {
+                    matrix: Matrix {
+                        a: column.scale() as f32,
+                        b: 0.0,
+                        c: 0.0,
+                        d: row.scale() as f32,
+                        tx: Twips::new(column.offset().round() as i32),
+                        ty: Twips::new(row.offset().round() as i32),
+                    },
+                    mask_matrix: Matrix {
+                        a: ((column.target.1 - column.target.0) / f64::from(MASK_SIZE)) as f32,
+                        b: 0.0,
+                        c: 0.0,
+                        d: ((row.target.1 - row.target.0) / f64::from(MASK_SIZE)) as f32,
+                        tx: Twips::new(column.target.0.round() as i32),
+                        ty: Twips::new(row.target.0.round() as i32),
+                    },
+                });
+            }
+        }
+        Some(slices)
+    }
+}
+
+/// The slices of a scaling grid for one frame.
+pub struct GridSlices<'a> {
+    grid: &'a ScalingGrid,
+    slices: Vec<Slice>,
+}
+
+impl<'a> GridSlices<'a> {
+    /// Renders content once for each slice. `render` draws the content in the coordinate space
+    /// of the object that has the grid.
+    pub fn render<'gc>(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        mut render: impl FnMut(&mut RenderContext<'_, 'gc>),
+    ) {
+        for slice in &self.slices {
+            context.renderer.push_mask();
+            context.transform_stack.push(&Transform {
+                matrix: slice.mask_matrix,
+                color_transform: Default::default(),
+            });
+            self.grid.mask.render(context);
+            context.transform_stack.pop();
+            context.renderer.activate_mask();
+
+            context.transform_stack.push(&Transform {
+                matrix: slice.matrix,
+                color_transform: Default::default(),
+            });
+            render(context);
+            context.transform_stack.pop();
+            context.renderer.pop_mask();
+        }
+    }
+}
+
+/// A span of content along one axis, and the span it is stretched to, in twips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Segment {
+    source: (f64, f64),
+    target: (f64, f64),
+}
+
+impl Segment {
+    fn is_empty(&self) -> bool {
+        self.source.1 <= self.source.0 || self.target.1 <= self.target.0
+    }
+
+    fn scale(&self) -> f64 {
+        (self.target.1 - self.target.0) / (self.source.1 - self.source.0)
+    }
+
+    fn offset(&self) -> f64 {
+        self.target.0 - self.source.0 * self.scale()
+    }
+}
+
+/// Splits the content between `min` and `max` at the grid lines `grid_min` and `grid_max`.
+///
+/// The outer segments are shrunk by `scale` so that they keep their size once the object is
+/// scaled, and the centre segment takes up the remaining space. If there isn't enough space,
+/// the outer segments share it and the centre segment disappears.
+fn axis_segments(min: f64, max: f64, grid_min: f64, grid_max: f64, scale: f64) -> [Segment; 3] {
+    let grid_min = grid_min.max(min).min(max);
+    let grid_max = grid_max.max(grid_min).min(max);
+
+    let mut start = (grid_min - min) / scale;
+    let mut end = (max - grid_max) / scale;
+    let length = max - min;
+    if start + end > length {
+        let shrink = length / (start + end);
+        start *= shrink;
+        end *= shrink;
+    }
+
+    [
+        Segment {
+            source: (min, grid_min),
+            target: (min, min + start),
+        },
+        Segment {
+            source: (grid_min, grid_max),
+            target: (min + start, max - end),
+        },
+        Segment {
+            source: (grid_max, max),
+            target: (max - end, max),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x_min: i32, x_max: i32, y_min: i32, y_max: i32) -> BoundingBox {
+        BoundingBox {
+            x_min: Twips::new(x_min),
+            x_max: Twips::new(x_max),
+            y_min: Twips::new(y_min),
+            y_max: Twips::new(y_max),
+            valid: true,
+        }
+    }
+
+    fn scale(scale_x: f32, scale_y: f32) -> Matrix {
+        Matrix {
+            a: scale_x,
+            d: scale_y,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn corners_keep_their_size() {
+        let [start, centre, end] = axis_segments(0.0, 100.0, 10.0, 90.0, 2.0);
+        assert_eq!(start.target, (0.0, 5.0));
+        assert_eq!(centre.target, (5.0, 95.0));
+        assert_eq!(end.target, (95.0, 100.0));
+
+        // Once the object is scaled by 2, the edges are 10 twips wide again.
+        assert_eq!(start.scale(), 0.5);
+        assert_eq!((end.target.1 - end.target.0) * 2.0, 10.0);
+        // The centre is stretched to fill the rest.
+        assert_eq!(centre.scale(), 90.0 / 80.0);
+        assert_eq!(centre.offset(), 5.0 - 10.0 * 90.0 / 80.0);
+    }
+
+    #[test]
+    fn corners_share_space_when_shrunk() {
+        let [start, centre, end] = axis_segments(0.0, 100.0, 20.0, 40.0, 0.25);
+        assert_eq!(start.target, (0.0, 25.0));
+        assert!(centre.is_empty());
+        assert_eq!(end.target, (25.0, 100.0));
+    }
+
+    #[test]
+    fn grid_is_clamped_to_bounds() {
+        let [start, centre, end] = axis_segments(0.0, 100.0, -50.0, 150.0, 3.0);
+        assert!(start.is_empty());
+        assert_eq!(centre.source, (0.0, 100.0));
+        assert_eq!(centre.target, (0.0, 100.0));
+        assert!(end.is_empty());
+    }
+
+    #[test]
+    fn unscaled_or_rotated_objects_are_not_sliced() {
+        let grid = ScalingGrid::new(Rectangle {
+            x_min: Twips::new(10),
+            x_max: Twips::new(90),
+            y_min: Twips::new(10),
+            y_max: Twips::new(90),
+        });
+        let content = bounds(0, 100, 0, 100);
+
+        assert_eq!(grid.compute_slices(&Matrix::default(), &content), None);
+        let rotated = Matrix {
+            a: 0.0,
+            b: 2.0,
+            c: -2.0,
+            d: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(grid.compute_slices(&rotated, &content), None);
+        assert_eq!(
+            grid.compute_slices(&scale(2.0, 2.0), &content)
+                .unwrap()
+                .len(),
+            9
+        );
+
+        // A grid along the edge of the content has no corners on that side.
+        let grid = ScalingGrid::new(Rectangle {
+            x_min: Twips::new(0),
+            x_max: Twips::new(90),
+            y_min: Twips::new(10),
+            y_max: Twips::new(90),
+        });
+        assert_eq!(
+            grid.compute_slices(&scale(2.0, 1.5), &content)
+                .unwrap()
+                .len(),
+            6
+        );
+    }
+}
@@ -73,9 +73,28 @@ pub enum DrawPath<'a> {
     Fill {
         style: &'a FillStyle,
         commands: Vec<DrawCommand>,
+        fill_rule: FillRule,
     },
 }
 
+/// The rule deciding which of the areas enclosed by a fill's path are painted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FillRule {
+    /// Paint the areas that a ray leaving them crosses the path an odd
+    /// number of times. SWF shapes use this unless they say otherwise.
+    EvenOdd,
+
+    /// Paint the areas that the path winds around at least once, counting
+    /// each direction separately.
+    NonZero,
+}
+
+impl Default for FillRule {
+    fn default() -> Self {
+        FillRule::EvenOdd
+    }
+}
+
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
 /// that has been converted down from another source (such as SWF's `swf::Shape` format).
 #[derive(Debug, PartialEq, Clone)]
@@ -338,6 +357,7 @@ pub struct ShapeConverter<'a> {
     // These change from StyleChangeRecords, and a flush occurs when these change.
     fill_styles: &'a [swf::FillStyle],
     line_styles: &'a [swf::LineStyle],
+    fill_rule: FillRule,
 
     fill_style0: Option<ActivePath>,
     fill_style1: Option<ActivePath>,
@@ -364,6 +384,11 @@ impl<'a> ShapeConverter<'a> {
 
             fill_styles: &shape.styles.fill_styles,
             line_styles: &shape.styles.line_styles,
+            fill_rule: if shape.has_fill_winding_rule {
+                FillRule::NonZero
+            } else {
+                FillRule::EvenOdd
+            },
 
             fill_style0: None,
             fill_style1: None,
@@ -538,6 +563,7 @@ impl<'a> ShapeConverter<'a> {
             self.commands.push(DrawPath::Fill {
                 style,
                 commands: path.into_draw_commands().collect(),
+                fill_rule: self.fill_rule,
             });
         }
 
@@ -559,6 +585,389 @@ impl<'a> ShapeConverter<'a> {
     }
 }
 
+/// Interpolates the start and end states of a morph shape into a shape at the given ratio.
+/// A ratio of 0 is the start state, and a ratio of 65535 is the end state.
+///
+/// Edges, fill styles and line styles are interpolated pairwise, including the matrices and
+/// records of gradient and bitmap fills.
+pub fn interpolate_morph_shape(
+    start: &swf::MorphShape,
+    end: &swf::MorphShape,
+    ratio: u16,
+) -> swf::Shape {
+    use swf::ShapeStyles;
+    let b = f32::from(ratio) / 65535.0;
+    let a = 1.0 - b;
+    let fill_styles: Vec<FillStyle> = start
+        .fill_styles
+        .iter()
+        .zip(end.fill_styles.iter())
+        .map(|(start, end)| lerp_fill(start, end, a, b))
+        .collect();
+    let line_styles: Vec<LineStyle> = start
+        .line_styles
+        .iter()
+        .zip(end.line_styles.iter())
+        .map(|(start, end)| LineStyle {
+            width: lerp_twips(start.width, end.width, a, b),
+            color: lerp_color(&start.color, &end.color, a, b),
+            start_cap: start.start_cap,
+            end_cap: start.end_cap,
+            join_style: start.join_style,
+            fill_style: match (&start.fill_style, &end.fill_style) {
+                (Some(start), Some(end)) => Some(lerp_fill(start, end, a, b)),
+                _ => None,
+            },
+            allow_scale_x: start.allow_scale_x,
+            allow_scale_y: start.allow_scale_y,
+            is_pixel_hinted: start.is_pixel_hinted,
+            allow_close: start.allow_close,
+        })
+        .collect();
+
+    let mut shape = Vec::with_capacity(start.shape.len());
+    let mut start_iter = start.shape.iter();
+    let mut end_iter = end.shape.iter();
+    let mut start = start_iter.next();
+    let mut end = end_iter.next();
+    let mut start_x = Twips::new(0);
+    let mut start_y = Twips::new(0);
+    let mut end_x = Twips::new(0);
+    let mut end_y = Twips::new(0);
+    // We step through both the start records and end records, interpolating edges pairwise.
+    // Fill style/line style changes should only appear in the start records.
+    // However, StyleChangeRecord move_to can appear it both start and end records,
+    // and not necessarily in matching pairs; therefore, we have to keep track of the pen position
+    // in case one side is missing a move_to; it will implicitly use the last pen position.
+    while let (Some(s), Some(e)) = (start, end) {
+        match (s, e) {
+            (ShapeRecord::StyleChange(start_change), ShapeRecord::StyleChange(end_change)) => {
+                let mut style_change = start_change.clone();
+                if let Some((s_x, s_y)) = start_change.move_to {
+                    start_x = s_x;
+                    start_y = s_y;
+                }
+                if let Some((e_x, e_y)) = end_change.move_to {
+                    end_x = e_x;
+                    end_y = e_y;
+                }
+                if start_change.move_to.is_some() || end_change.move_to.is_some() {
+                    style_change.move_to = Some((
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                start = start_iter.next();
+                end = end_iter.next();
+            }
+            (ShapeRecord::StyleChange(start_change), _) => {
+                let mut style_change = start_change.clone();
+                if let Some((s_x, s_y)) = start_change.move_to {
+                    start_x = s_x;
+                    start_y = s_y;
+                    style_change.move_to = Some((
+                        lerp_twips(start_x, end_x, a, b),
+                        lerp_twips(start_y, end_y, a, b),
+                    ));
+                }
+                shape.push(ShapeRecord::StyleChange(style_change));
+                start = start_iter.next();
+            }
+            (_, ShapeRecord::StyleChange(end_change)) => {
+                if let Some((e_x, e_y)) = end_change.move_to {
+                    end_x = e_x;
+                    end_y = e_y;
+                    // Style changes only come from the start records.
+                    shape.push(ShapeRecord::StyleChange(swf::StyleChangeData {
+                        move_to: Some((
+                            lerp_twips(start_x, end_x, a, b),
+                            lerp_twips(start_y, end_y, a, b),
+                        )),
+                        fill_style_0: None,
+                        fill_style_1: None,
+                        line_style: None,
+                        new_styles: None,
+                    }));
+                }
+                end = end_iter.next();
+            }
+            _ => {
+                shape.push(lerp_edges(s, e, a, b));
+                update_pen_position(&mut start_x, &mut start_y, s);
+                update_pen_position(&mut end_x, &mut end_y, e);
+                start = start_iter.next();
+                end = end_iter.next();
+            }
+        }
+    }
+
+    let bounds = calculate_shape_bounds(&shape[..]);
+    swf::Shape {
+        version: 4,
+        id: 0,
+        shape_bounds: bounds.clone(),
+        edge_bounds: bounds,
+        has_fill_winding_rule: false,
+        has_non_scaling_strokes: false,
+        has_scaling_strokes: true,
+        styles: ShapeStyles {
+            fill_styles,
+            line_styles,
+        },
+        shape,
+    }
+}
+
+/// Interpolates the bounds of a morph shape at the given ratio.
+pub fn interpolate_morph_bounds(
+    start: &swf::MorphShape,
+    end: &swf::MorphShape,
+    ratio: u16,
+) -> swf::Rectangle {
+    let b = f32::from(ratio) / 65535.0;
+    let a = 1.0 - b;
+    swf::Rectangle {
+        x_min: lerp_twips(start.shape_bounds.x_min, end.shape_bounds.x_min, a, b),
+        y_min: lerp_twips(start.shape_bounds.y_min, end.shape_bounds.y_min, a, b),
+        x_max: lerp_twips(start.shape_bounds.x_max, end.shape_bounds.x_max, a, b),
+        y_max: lerp_twips(start.shape_bounds.y_max, end.shape_bounds.y_max, a, b),
+    }
+}
+
+fn update_pen_position(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
+    match record {
+        ShapeRecord::StraightEdge { delta_x, delta_y } => {
+            *x += *delta_x;
+            *y += *delta_y;
+        }
+        ShapeRecord::CurvedEdge {
+            control_delta_x,
+            control_delta_y,
+            anchor_delta_x,
+            anchor_delta_y,
+        } => {
+            *x += *control_delta_x + *anchor_delta_x;
+            *y += *control_delta_y + *anchor_delta_y;
+        }
+        ShapeRecord::StyleChange(ref style_change) => {
+            if let Some((move_x, move_y)) = style_change.move_to {
+                *x = move_x;
+                *y = move_y;
+            }
+        }
+    }
+}
+
+// Morph shape interpolation functions.
+// These interpolate between two SWF shape structures.
+// a + b should = 1.0
+
+fn lerp_color(start: &swf::Color, end: &swf::Color, a: f32, b: f32) -> swf::Color {
+    // f32 -> u8 cast is defined to saturate for out of bounds values,
+    // so we don't have to worry about clamping.
+    swf::Color {
+        r: (a * f32::from(start.r) + b * f32::from(end.r)) as u8,
+        g: (a * f32::from(start.g) + b * f32::from(end.g)) as u8,
+        b: (a * f32::from(start.b) + b * f32::from(end.b)) as u8,
+        a: (a * f32::from(start.a) + b * f32::from(end.a)) as u8,
+    }
+}
+
+fn lerp_twips(start: Twips, end: Twips, a: f32, b: f32) -> Twips {
+    Twips::new((start.get() as f32 * a + end.get() as f32 * b).round() as i32)
+}
+
+fn lerp_fill(start: &swf::FillStyle, end: &swf::FillStyle, a: f32, b: f32) -> swf::FillStyle {
+    match (start, end) {
+        // Color-to-color
+        (FillStyle::Color(start), FillStyle::Color(end)) => {
+            FillStyle::Color(lerp_color(start, end, a, b))
+        }
+
+        // Bitmap-to-bitmap
+        // ID should be the same.
+        (
+            FillStyle::Bitmap {
+                id: start_id,
+                matrix: start,
+                is_smoothed,
+                is_repeating,
+            },
+            FillStyle::Bitmap { matrix: end, .. },
+        ) => FillStyle::Bitmap {
+            id: *start_id,
+            matrix: lerp_matrix(start, end, a, b),
+            is_smoothed: *is_smoothed,
+            is_repeating: *is_repeating,
+        },
+
+        // Linear-to-linear
+        (FillStyle::LinearGradient(start), FillStyle::LinearGradient(end)) => {
+            FillStyle::LinearGradient(lerp_gradient(start, end, a, b))
+        }
+
+        // Radial-to-radial
+        (FillStyle::RadialGradient(start), FillStyle::RadialGradient(end)) => {
+            FillStyle::RadialGradient(lerp_gradient(start, end, a, b))
+        }
+
+        // Focal gradients also interpolate focal point.
+        (
+            FillStyle::FocalGradient {
+                gradient: start,
+                focal_point: start_focal,
+            },
+            FillStyle::FocalGradient {
+                gradient: end,
+                focal_point: end_focal,
+            },
+        ) => FillStyle::FocalGradient {
+            gradient: lerp_gradient(start, end, a, b),
+            focal_point: a * start_focal + b * end_focal,
+        },
+
+        // All other combinations should not occur, because SWF stores the start/end fill as the same type, always.
+        // If you happened to make, say, a solid color-to-radial gradient tween in the IDE, this would get baked down into
+        // a radial-to-radial gradient on export.
+        _ => {
+            log::warn!(
+                "Unexpected morph shape fill style combination: {:#?}, {:#?}",
+                start,
+                end
+            );
+            start.clone()
+        }
+    }
+}
+
+fn lerp_edges(
+    start: &swf::ShapeRecord,
+    end: &swf::ShapeRecord,
+    a: f32,
+    b: f32,
+) -> swf::ShapeRecord {
+    match (start, end) {
+        (
+            &ShapeRecord::StraightEdge {
+                delta_x: start_dx,
+                delta_y: start_dy,
+            },
+            &ShapeRecord::StraightEdge {
+                delta_x: end_dx,
+                delta_y: end_dy,
+            },
+        ) => ShapeRecord::StraightEdge {
+            delta_x: lerp_twips(start_dx, end_dx, a, b),
+            delta_y: lerp_twips(start_dy, end_dy, a, b),
+        },
+
+        (
+            &ShapeRecord::CurvedEdge {
+                control_delta_x: start_cdx,
+                control_delta_y: start_cdy,
+                anchor_delta_x: start_adx,
+                anchor_delta_y: start_ady,
+            },
+            &ShapeRecord::CurvedEdge {
+                control_delta_x: end_cdx,
+                control_delta_y: end_cdy,
+                anchor_delta_x: end_adx,
+                anchor_delta_y: end_ady,
+            },
+        ) => ShapeRecord::CurvedEdge {
+            control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
+            control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
+            anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
+            anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
+        },
+
+        (
+            &ShapeRecord::StraightEdge {
+                delta_x: start_dx,
+                delta_y: start_dy,
+            },
+            &ShapeRecord::CurvedEdge {
+                control_delta_x: end_cdx,
+                control_delta_y: end_cdy,
+                anchor_delta_x: end_adx,
+                anchor_delta_y: end_ady,
+            },
+        ) => {
+            let start_cdx = start_dx / 2;
+            let start_cdy = start_dy / 2;
+            let start_adx = start_cdx;
+            let start_ady = start_cdy;
+            ShapeRecord::CurvedEdge {
+                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
+                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
+                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
+                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
+            }
+        }
+
+        (
+            &ShapeRecord::CurvedEdge {
+                control_delta_x: start_cdx,
+                control_delta_y: start_cdy,
+                anchor_delta_x: start_adx,
+                anchor_delta_y: start_ady,
+            },
+            &ShapeRecord::StraightEdge {
+                delta_x: end_dx,
+                delta_y: end_dy,
+            },
+        ) => {
+            let end_cdx = end_dx / 2;
+            let end_cdy = end_dy / 2;
+            let end_adx = end_cdx;
+            let end_ady = end_cdy;
+            ShapeRecord::CurvedEdge {
+                control_delta_x: lerp_twips(start_cdx, end_cdx, a, b),
+                control_delta_y: lerp_twips(start_cdy, end_cdy, a, b),
+                anchor_delta_x: lerp_twips(start_adx, end_adx, a, b),
+                anchor_delta_y: lerp_twips(start_ady, end_ady, a, b),
+            }
+        }
+        _ => unreachable!("{:?} {:?}", start, end),
+    }
+}
+
+fn lerp_matrix(start: &swf::Matrix, end: &swf::Matrix, a: f32, b: f32) -> swf::Matrix {
+    // TODO: Lerping a matrix element-wise is geometrically wrong,
+    // but I doubt Flash is decomposing the matrix into scale-rotate-translate?
+    swf::Matrix {
+        a: start.a * a + end.a * b,
+        b: start.b * a + end.b * b,
+        c: start.c * a + end.c * b,
+        d: start.d * a + end.d * b,
+        tx: lerp_twips(start.tx, end.tx, a, b),
+        ty: lerp_twips(start.ty, end.ty, a, b),
+    }
+}
+
+fn lerp_gradient(start: &swf::Gradient, end: &swf::Gradient, a: f32, b: f32) -> swf::Gradient {
+    use swf::{Gradient, GradientRecord};
+    // Morph gradients are guaranteed to have the same number of records in the start/end gradient.
+    debug_assert!(start.records.len() == end.records.len());
+    let records: Vec<GradientRecord> = start
+        .records
+        .iter()
+        .zip(end.records.iter())
+        .map(|(start, end)| swf::GradientRecord {
+            ratio: (f32::from(start.ratio) * a + f32::from(end.ratio) * b) as u8,
+            color: lerp_color(&start.color, &end.color, a, b),
+        })
+        .collect();
+
+    Gradient {
+        matrix: lerp_matrix(&start.matrix, &end.matrix, a, b),
+        spread: start.spread,
+        interpolation: start.interpolation,
+        records,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -644,6 +1053,7 @@ mod tests {
                     y: Twips::from_pixels(100.0),
                 },
             ],
+            fill_rule: FillRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
@@ -708,9 +1118,169 @@ mod tests {
                     y: Twips::from_pixels(200.0),
                 },
             ],
+            fill_rule: FillRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
+
+    /// A morph shape halfway between a straight edge and a curve, with a color fill.
+    #[test]
+    fn interpolate_morph_shape_midpoint() {
+        let morph_shape = |x: f64, color: u8, edge: ShapeRecord| swf::MorphShape {
+            shape_bounds: Default::default(),
+            edge_bounds: Default::default(),
+            fill_styles: vec![FillStyle::Color(swf::Color {
+                r: color,
+                g: 0,
+                b: 0,
+                a: 255,
+            })],
+            line_styles: vec![],
+            shape: vec![
+                ShapeRecord::StyleChange(swf::StyleChangeData {
+                    move_to: Some((Twips::from_pixels(x), Twips::from_pixels(0.0))),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                }),
+                edge,
+            ],
+        };
+        let start = morph_shape(
+            0.0,
+            0,
+            ShapeRecord::StraightEdge {
+                delta_x: Twips::from_pixels(100.0),
+                delta_y: Twips::from_pixels(0.0),
+            },
+        );
+        let end = morph_shape(
+            100.0,
+            200,
+            ShapeRecord::CurvedEdge {
+                control_delta_x: Twips::from_pixels(50.0),
+                control_delta_y: Twips::from_pixels(50.0),
+                anchor_delta_x: Twips::from_pixels(50.0),
+                anchor_delta_y: Twips::from_pixels(-50.0),
+            },
+        );
+
+        let shape = interpolate_morph_shape(&start, &end, 32768);
+        assert_eq!(
+            shape.styles.fill_styles,
+            vec![FillStyle::Color(swf::Color {
+                r: 100,
+                g: 0,
+                b: 0,
+                a: 255
+            })]
+        );
+        assert_eq!(
+            shape.shape,
+            vec![
+                ShapeRecord::StyleChange(swf::StyleChangeData {
+                    move_to: Some((Twips::from_pixels(50.0), Twips::from_pixels(0.0))),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                }),
+                ShapeRecord::CurvedEdge {
+                    control_delta_x: Twips::from_pixels(50.0),
+                    control_delta_y: Twips::from_pixels(25.0),
+                    anchor_delta_x: Twips::from_pixels(50.0),
+                    anchor_delta_y: Twips::from_pixels(-25.0),
+                },
+            ]
+        );
+    }
+
+    /// A DefineShape4 shape asking for the non-zero winding rule.
+    #[test]
+    fn shape_fill_winding_rule() {
+        let mut shape = build_shape(vec![
+            ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some((Twips::from_pixels(100.0), Twips::from_pixels(100.0))),
+                fill_style_0: None,
+                fill_style_1: Some(1),
+                line_style: None,
+                new_styles: None,
+            }),
+            ShapeRecord::StraightEdge {
+                delta_x: Twips::from_pixels(100.0),
+                delta_y: Twips::from_pixels(100.0),
+            },
+            ShapeRecord::StraightEdge {
+                delta_x: Twips::from_pixels(-100.0),
+                delta_y: Twips::from_pixels(0.0),
+            },
+        ]);
+        shape.version = 4;
+        shape.has_fill_winding_rule = true;
+
+        let commands = ShapeConverter::from_shape(&shape).into_commands();
+        assert!(matches!(
+            commands[..],
+            [DrawPath::Fill {
+                fill_rule: FillRule::NonZero,
+                ..
+            }]
+        ));
+    }
+
+    /// Two overlapping squares wound the same way: the overlap is a hole
+    /// with the even-odd rule, but is filled with the non-zero rule.
+    #[test]
+    fn fill_hit_test_rules() {
+        let square = |x: f64, y: f64| {
+            vec![
+                DrawCommand::MoveTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(y),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x + 100.0),
+                    y: Twips::from_pixels(y),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x + 100.0),
+                    y: Twips::from_pixels(y + 100.0),
+                },
+                DrawCommand::LineTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(y + 100.0),
+                },
+            ]
+        };
+        let mut commands = square(0.0, 0.0);
+        commands.extend(square(50.0, 50.0));
+
+        let overlap = (Twips::from_pixels(75.0), Twips::from_pixels(75.0));
+        let single = (Twips::from_pixels(25.0), Twips::from_pixels(25.0));
+        let outside = (Twips::from_pixels(125.0), Twips::from_pixels(25.0));
+
+        assert!(!draw_command_fill_hit_test(
+            &commands,
+            FillRule::EvenOdd,
+            overlap
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            FillRule::NonZero,
+            overlap
+        ));
+        assert!(draw_command_fill_hit_test(
+            &commands,
+            FillRule::EvenOdd,
+            single
+        ));
+        assert!(!draw_command_fill_hit_test(
+            &commands,
+            FillRule::NonZero,
+            outside
+        ));
+    }
 }
 
 /* SHAPEFLAG HITTEST (point-in-contour)
@@ -854,18 +1424,23 @@ pub fn shape_hit_test(
 /// Test whether the given point is contained with in the paths specified by the draw commands.
 pub fn draw_command_fill_hit_test(
     commands: &[DrawCommand],
+    fill_rule: FillRule,
     (point_x, point_y): (Twips, Twips),
 ) -> bool {
     let mut x = Twips::new(0);
     let mut y = Twips::new(0);
+    let mut start = (x, y);
     let mut winding = 0;
 
     // Draw command only contains a single fill, so don't have to worry about fill styles.
+    // Fills are implicitly closed, so each subpath is closed back to its start point.
     for command in commands {
         match *command {
             DrawCommand::MoveTo { x: x1, y: y1 } => {
+                winding += winding_number_line((point_x, point_y), (x, y), start);
                 x = x1;
                 y = y1;
+                start = (x, y);
             }
             DrawCommand::LineTo { x: x1, y: y1 } => {
                 winding += winding_number_line((point_x, point_y), (x, y), (x1, y1));
@@ -879,7 +1454,11 @@ pub fn draw_command_fill_hit_test(
             }
         }
     }
-    winding & 0b1 != 0
+    winding += winding_number_line((point_x, point_y), (x, y), start);
+    match fill_rule {
+        FillRule::EvenOdd => winding & 0b1 != 0,
+        FillRule::NonZero => winding != 0,
+    }
 }
 
 /// Test whether the given point is contained with in the strokes specified by the draw commands.
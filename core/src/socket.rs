@@ -0,0 +1,224 @@
+//! Socket connections for `XMLSocket`.
+
+use crate::avm1::{Avm1, AvmString, Object, Value};
+use crate::backend::navigator::NavigatorBackend;
+use crate::context::UpdateContext;
+use gc_arena::{Collect, CollectionContext};
+use generational_arena::{Arena, Index};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub type SocketHandle = Index;
+
+/// The outcome of a connection attempt.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConnectionState {
+    Connected,
+    Failed,
+}
+
+/// An event reported by the navigator backend about an open socket.
+#[derive(Debug)]
+pub enum SocketAction {
+    /// The connection attempt for the given socket has finished.
+    Connect(SocketHandle, ConnectionState),
+
+    /// The given socket has received data.
+    Data(SocketHandle, Vec<u8>),
+
+    /// The given socket was closed by the remote end, or failed.
+    Close(SocketHandle),
+}
+
+/// A single open socket.
+struct Socket<'gc> {
+    /// The `XMLSocket` object that receives this socket's events.
+    target: Object<'gc>,
+
+    /// Data to be written to the socket. Dropping this closes the connection.
+    sender: Sender<Vec<u8>>,
+
+    /// Received data that has not yet been terminated by a null byte.
+    buffer: Vec<u8>,
+
+    /// Whether the connection has been established.
+    connected: bool,
+}
+
+/// Manages all sockets opened by the movie.
+pub struct Sockets<'gc> {
+    sockets: Arena<Socket<'gc>>,
+
+    /// Events sent by the navigator backend, to be processed on the next update.
+    receiver: Receiver<SocketAction>,
+    sender: Sender<SocketAction>,
+}
+
+unsafe impl<'gc> Collect for Sockets<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for (_, socket) in self.sockets.iter() {
+            socket.target.trace(cc);
+        }
+    }
+}
+
+impl<'gc> Sockets<'gc> {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            sockets: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Begin connecting `target` to the given host and port.
+    ///
+    /// Any existing connection of `target` is closed first. The outcome of
+    /// the connection attempt is reported to the `onConnect` method of
+    /// `target`.
+    pub fn connect(
+        &mut self,
+        navigator: &mut dyn NavigatorBackend,
+        target: Object<'gc>,
+        host: String,
+        port: u16,
+    ) {
+        self.close(target);
+
+        let (sender, receiver) = channel();
+        let handle = self.sockets.insert(Socket {
+            target,
+            sender,
+            buffer: Vec::new(),
+            connected: false,
+        });
+
+        navigator.connect_socket(host, port, handle, receiver, self.sender.clone());
+    }
+
+    /// Send data over the socket of `target`.
+    ///
+    /// Returns `false` if `target` is not connected.
+    pub fn send(&mut self, target: Object<'gc>, data: Vec<u8>) -> bool {
+        match self
+            .find(target)
+            .and_then(|handle| self.sockets.get(handle))
+        {
+            Some(socket) if socket.connected => socket.sender.send(data).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Close the socket of `target`, if it has one.
+    pub fn close(&mut self, target: Object<'gc>) {
+        if let Some(handle) = self.find(target) {
+            // Dropping the sender tells the backend to close the connection.
+            self.sockets.remove(handle);
+        }
+    }
+
+    /// Find the socket opened by the given object.
+    fn find(&self, target: Object<'gc>) -> Option<SocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Process all events reported by the navigator backend since the last
+    /// update, and fire the corresponding `XMLSocket` events.
+    pub fn update_sockets(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let actions: Vec<SocketAction> = context.sockets.receiver.try_iter().collect();
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(handle, state) => {
+                    let target = match context.sockets.sockets.get_mut(handle) {
+                        Some(socket) => {
+                            socket.connected = state == ConnectionState::Connected;
+                            socket.target
+                        }
+                        None => continue,
+                    };
+                    if state == ConnectionState::Failed {
+                        context.sockets.sockets.remove(handle);
+                    }
+
+                    let success = state == ConnectionState::Connected;
+                    Self::run_method(context, target, "onConnect", &[success.into()]);
+                }
+                SocketAction::Data(handle, data) => {
+                    let (target, messages) = match context.sockets.sockets.get_mut(handle) {
+                        Some(socket) => {
+                            socket.buffer.extend(data);
+                            (socket.target, split_messages(&mut socket.buffer))
+                        }
+                        None => continue,
+                    };
+
+                    for message in messages {
+                        let message = AvmString::new(
+                            context.gc_context,
+                            String::from_utf8_lossy(&message).into_owned(),
+                        );
+                        Self::run_method(context, target, "onData", &[message.into()]);
+                    }
+                }
+                SocketAction::Close(handle) => {
+                    if let Some(socket) = context.sockets.sockets.remove(handle) {
+                        Self::run_method(context, socket.target, "onClose", &[]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn run_method(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        name: &str,
+        args: &[Value<'gc>],
+    ) {
+        if let Some(root) = context.levels.get(&0).copied() {
+            let swf_version = context.swf.version();
+            Avm1::run_stack_frame_for_method(root, target, swf_version, context, name, args);
+        }
+    }
+}
+
+impl<'gc> Default for Sockets<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Remove all null-terminated messages from the start of `buffer`.
+fn split_messages(buffer: &mut Vec<u8>) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    while let Some(end) = buffer.iter().position(|&b| b == 0) {
+        let mut message: Vec<u8> = buffer.drain(..=end).collect();
+        message.pop();
+        messages.push(message);
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_null_terminated_messages() {
+        let mut buffer = b"<a/>\0<b/>\0<c".to_vec();
+        assert_eq!(
+            split_messages(&mut buffer),
+            vec![b"<a/>".to_vec(), b"<b/>".to_vec()]
+        );
+        assert_eq!(buffer, b"<c".to_vec());
+
+        buffer.extend(b"/>\0");
+        assert_eq!(split_messages(&mut buffer), vec![b"<c/>".to_vec()]);
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,835 @@
+//! Reading and writing of Flash Local Shared Object (`.sol`) files.
+//!
+//! Shared objects are stored by the storage backend as JSON, so this module
+//! only deals with converting between the binary `.sol` format and that JSON
+//! representation. Both AMF0 bodies, as produced by AVM1 movies, and AMF3
+//! bodies, as produced by AVM2 movies, can be read and written.
+
+pub use crate::avm2::ObjectEncoding;
+use json::JsonValue;
+use std::convert::TryFrom;
+use thiserror::Error;
+
+/// The magic bytes at the start of every `.sol` file.
+const HEADER_MAGIC: [u8; 2] = [0x00, 0xBF];
+
+/// The signature following the file length.
+const SIGNATURE: &[u8] = b"TCSO\x00\x04\x00\x00\x00\x00";
+
+#[derive(Error, Debug)]
+pub enum SolError {
+    #[error("Unexpected end of shared object data")]
+    UnexpectedEof,
+
+    #[error("Invalid shared object header")]
+    InvalidHeader,
+
+    #[error("Unsupported AMF version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Unsupported AMF{0} type marker {1:#x}")]
+    UnsupportedType(u8, u8),
+
+    #[error("Invalid AMF reference {0}")]
+    InvalidReference(usize),
+
+    #[error("Invalid UTF-8 string")]
+    InvalidString,
+}
+
+/// A single value stored in a shared object.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Object(Vec<(String, SolValue)>),
+    Array(Vec<SolValue>),
+}
+
+/// The contents of a shared object: its name and the properties of `data`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sol {
+    pub name: String,
+    pub body: Vec<(String, SolValue)>,
+
+    /// The AMF version the body is encoded with.
+    pub encoding: ObjectEncoding,
+}
+
+impl Sol {
+    /// Parse a `.sol` file.
+    pub fn read(data: &[u8]) -> Result<Self, SolError> {
+        let mut reader = Reader::new(data);
+
+        if reader.read_bytes(2)? != HEADER_MAGIC {
+            return Err(SolError::InvalidHeader);
+        }
+        let _length = reader.read_u32()?;
+        if reader.read_bytes(SIGNATURE.len())? != SIGNATURE {
+            return Err(SolError::InvalidHeader);
+        }
+
+        let name = reader.read_amf0_string()?;
+        reader.read_bytes(3)?;
+        let version = reader.read_u8()?;
+
+        let encoding = match version {
+            0 => ObjectEncoding::Amf0,
+            3 => ObjectEncoding::Amf3,
+            _ => return Err(SolError::UnsupportedVersion(version)),
+        };
+
+        let mut body = Vec::new();
+        while !reader.is_empty() {
+            let (key, value) = match encoding {
+                ObjectEncoding::Amf0 => (reader.read_amf0_string()?, reader.read_amf0_value()?),
+                ObjectEncoding::Amf3 => (reader.read_amf3_string()?, reader.read_amf3_value()?),
+            };
+            // Every top-level property is followed by a padding byte.
+            reader.read_u8()?;
+            body.push((key, value));
+        }
+
+        Ok(Self {
+            name,
+            body,
+            encoding,
+        })
+    }
+
+    /// Serialize this shared object as a `.sol` file, in its encoding.
+    pub fn write(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_amf0_string(&mut body, &self.name);
+        body.extend_from_slice(&[0, 0, 0]);
+        match self.encoding {
+            ObjectEncoding::Amf0 => {
+                body.push(0);
+                for (key, value) in &self.body {
+                    write_amf0_string(&mut body, key);
+                    write_amf0_value(&mut body, value);
+                    body.push(0);
+                }
+            }
+            ObjectEncoding::Amf3 => {
+                body.push(3);
+                let mut writer = Amf3Writer::new(&mut body);
+                for (key, value) in &self.body {
+                    writer.write_string(key);
+                    writer.write_value(value);
+                    writer.out.push(0);
+                }
+            }
+        }
+
+        let mut out = Vec::with_capacity(body.len() + 16);
+        out.extend_from_slice(&HEADER_MAGIC);
+        out.extend_from_slice(&((body.len() + SIGNATURE.len()) as u32).to_be_bytes());
+        out.extend_from_slice(SIGNATURE);
+        out.extend_from_slice(&body);
+        out
+    }
+
+    /// Build a shared object from the JSON representation used by the
+    /// storage backend.
+    pub fn from_json(name: &str, json: &JsonValue, encoding: ObjectEncoding) -> Self {
+        let body = json
+            .entries()
+            .map(|(key, value)| (key.to_string(), SolValue::from_json(value)))
+            .collect();
+
+        Self {
+            name: name.to_string(),
+            body,
+            encoding,
+        }
+    }
+
+    /// Convert this shared object into the JSON representation used by the
+    /// storage backend.
+    pub fn to_json(&self) -> JsonValue {
+        object_to_json(&self.body)
+    }
+}
+
+impl SolValue {
+    fn from_json(json: &JsonValue) -> Self {
+        match json {
+            JsonValue::Null => SolValue::Null,
+            JsonValue::Short(s) => SolValue::String(s.as_str().to_string()),
+            JsonValue::String(s) => SolValue::String(s.clone()),
+            JsonValue::Number(n) => SolValue::Number((*n).into()),
+            JsonValue::Boolean(b) => SolValue::Bool(*b),
+            JsonValue::Object(o) => SolValue::Object(
+                o.iter()
+                    .map(|(key, value)| (key.to_string(), SolValue::from_json(value)))
+                    .collect(),
+            ),
+            JsonValue::Array(a) => SolValue::Array(a.iter().map(SolValue::from_json).collect()),
+        }
+    }
+
+    /// Convert this value to JSON, returning `None` for values that the
+    /// storage backend doesn't keep.
+    fn to_json(&self) -> Option<JsonValue> {
+        match self {
+            SolValue::Undefined => None,
+            SolValue::Null => Some(JsonValue::Null),
+            SolValue::Bool(b) => Some((*b).into()),
+            SolValue::Number(n) => Some((*n).into()),
+            SolValue::String(s) => Some(s.as_str().into()),
+            SolValue::Object(properties) => Some(object_to_json(properties)),
+            // Arrays are stored like any other object, keyed by index.
+            SolValue::Array(elements) => {
+                let mut json = JsonValue::new_object();
+                for (i, element) in elements.iter().enumerate() {
+                    if let Some(element) = element.to_json() {
+                        json[i.to_string().as_str()] = element;
+                    }
+                }
+                Some(json)
+            }
+        }
+    }
+}
+
+fn object_to_json(properties: &[(String, SolValue)]) -> JsonValue {
+    let mut json = JsonValue::new_object();
+    for (key, value) in properties {
+        if let Some(value) = value.to_json() {
+            json[key.as_str()] = value;
+        }
+    }
+    json
+}
+
+fn write_amf0_string(out: &mut Vec<u8>, s: &str) {
+    out.extend_from_slice(&(s.len() as u16).to_be_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn write_amf0_value(out: &mut Vec<u8>, value: &SolValue) {
+    match value {
+        SolValue::Number(n) => {
+            out.push(0x00);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+        SolValue::Bool(b) => {
+            out.push(0x01);
+            out.push(*b as u8);
+        }
+        SolValue::String(s) if s.len() <= u16::MAX as usize => {
+            out.push(0x02);
+            write_amf0_string(out, s);
+        }
+        SolValue::String(s) => {
+            out.push(0x0C);
+            out.extend_from_slice(&(s.len() as u32).to_be_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+        SolValue::Object(properties) => {
+            out.push(0x03);
+            for (key, value) in properties {
+                write_amf0_string(out, key);
+                write_amf0_value(out, value);
+            }
+            out.extend_from_slice(&[0x00, 0x00, 0x09]);
+        }
+        SolValue::Null => out.push(0x05),
+        SolValue::Undefined => out.push(0x06),
+        SolValue::Array(elements) => {
+            out.push(0x0A);
+            out.extend_from_slice(&(elements.len() as u32).to_be_bytes());
+            for element in elements {
+                write_amf0_value(out, element);
+            }
+        }
+    }
+}
+
+/// Serializes values as AMF3, keeping track of the strings that later
+/// occurrences can refer back to.
+///
+/// Objects are always written inline, as shared objects can't hold the same
+/// object twice once they have been through the storage backend.
+struct Amf3Writer<'a> {
+    out: &'a mut Vec<u8>,
+    strings: Vec<String>,
+}
+
+impl<'a> Amf3Writer<'a> {
+    fn new(out: &'a mut Vec<u8>) -> Self {
+        Self {
+            out,
+            strings: Vec::new(),
+        }
+    }
+
+    fn write_u29(&mut self, value: u32) {
+        let value = value & 0x1FFF_FFFF;
+        if value < 0x80 {
+            self.out.push(value as u8);
+        } else if value < 0x4000 {
+            self.out
+                .extend_from_slice(&[(value >> 7) as u8 | 0x80, value as u8 & 0x7F]);
+        } else if value < 0x20_0000 {
+            self.out.extend_from_slice(&[
+                (value >> 14) as u8 | 0x80,
+                (value >> 7) as u8 | 0x80,
+                value as u8 & 0x7F,
+            ]);
+        } else {
+            self.out.extend_from_slice(&[
+                (value >> 22) as u8 | 0x80,
+                (value >> 15) as u8 | 0x80,
+                (value >> 8) as u8 | 0x80,
+                value as u8,
+            ]);
+        }
+    }
+
+    fn write_string(&mut self, s: &str) {
+        if let Some(index) = self.strings.iter().position(|other| other == s) {
+            self.write_u29((index as u32) << 1);
+            return;
+        }
+
+        self.write_u29(((s.len() as u32) << 1) | 1);
+        self.out.extend_from_slice(s.as_bytes());
+        if !s.is_empty() {
+            self.strings.push(s.to_string());
+        }
+    }
+
+    fn write_value(&mut self, value: &SolValue) {
+        match value {
+            SolValue::Undefined => self.out.push(0x00),
+            SolValue::Null => self.out.push(0x01),
+            SolValue::Bool(false) => self.out.push(0x02),
+            SolValue::Bool(true) => self.out.push(0x03),
+            SolValue::Number(n)
+                if n.fract() == 0.0
+                    && *n >= -(1 << 28) as f64
+                    && *n < (1 << 28) as f64
+                    && !(*n == 0.0 && n.is_sign_negative()) =>
+            {
+                self.out.push(0x04);
+                self.write_u29(*n as i32 as u32);
+            }
+            SolValue::Number(n) => {
+                self.out.push(0x05);
+                self.out.extend_from_slice(&n.to_be_bytes());
+            }
+            SolValue::String(s) => {
+                self.out.push(0x06);
+                self.write_string(s);
+            }
+            SolValue::Object(properties) => {
+                // An anonymous, dynamic object with inline traits and no
+                // sealed members.
+                self.out.push(0x0A);
+                self.write_u29(0x0B);
+                self.write_string("");
+                for (key, value) in properties {
+                    self.write_string(key);
+                    self.write_value(value);
+                }
+                self.write_string("");
+            }
+            SolValue::Array(elements) => {
+                self.out.push(0x09);
+                self.write_u29(((elements.len() as u32) << 1) | 1);
+                self.write_string("");
+                for element in elements {
+                    self.write_value(element);
+                }
+            }
+        }
+    }
+}
+
+/// A set of sealed property names shared between AMF3 objects.
+struct Traits {
+    sealed: Vec<String>,
+    dynamic: bool,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+
+    /// Previously read AMF0 objects, for back-references.
+    amf0_objects: Vec<SolValue>,
+
+    /// Previously read AMF3 strings, objects and traits, for back-references.
+    amf3_strings: Vec<String>,
+    amf3_objects: Vec<SolValue>,
+    amf3_traits: Vec<Traits>,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            amf0_objects: Vec::new(),
+            amf3_strings: Vec::new(),
+            amf3_objects: Vec::new(),
+            amf3_traits: Vec::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SolError> {
+        if self.data.len() < len {
+            return Err(SolError::UnexpectedEof);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, SolError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, SolError> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, SolError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes(<[u8; 4]>::try_from(bytes).unwrap()))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, SolError> {
+        let bytes = self.read_bytes(8)?;
+        Ok(f64::from_be_bytes(<[u8; 8]>::try_from(bytes).unwrap()))
+    }
+
+    fn read_utf8(&mut self, len: usize) -> Result<String, SolError> {
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| SolError::InvalidString)
+    }
+
+    fn read_amf0_string(&mut self) -> Result<String, SolError> {
+        let len = self.read_u16()?;
+        self.read_utf8(len.into())
+    }
+
+    fn read_amf0_long_string(&mut self) -> Result<String, SolError> {
+        let len = self.read_u32()?;
+        self.read_utf8(len as usize)
+    }
+
+    /// Read the properties of an AMF0 object, up to the object end marker.
+    fn read_amf0_properties(&mut self) -> Result<Vec<(String, SolValue)>, SolError> {
+        let mut properties = Vec::new();
+        loop {
+            let key = self.read_amf0_string()?;
+            if key.is_empty() && self.data.first() == Some(&0x09) {
+                self.read_u8()?;
+                return Ok(properties);
+            }
+            properties.push((key, self.read_amf0_value()?));
+        }
+    }
+
+    /// Read an AMF0 object, registering it so that later references to it
+    /// can be resolved.
+    fn read_amf0_object(&mut self) -> Result<SolValue, SolError> {
+        let index = self.amf0_objects.len();
+        self.amf0_objects.push(SolValue::Null);
+        let object = SolValue::Object(self.read_amf0_properties()?);
+        self.amf0_objects[index] = object.clone();
+        Ok(object)
+    }
+
+    fn read_amf0_value(&mut self) -> Result<SolValue, SolError> {
+        let marker = self.read_u8()?;
+        Ok(match marker {
+            0x00 => SolValue::Number(self.read_f64()?),
+            0x01 => SolValue::Bool(self.read_u8()? != 0),
+            0x02 => SolValue::String(self.read_amf0_string()?),
+            0x03 => self.read_amf0_object()?,
+            0x05 => SolValue::Null,
+            0x06 | 0x0D => SolValue::Undefined,
+            0x07 => {
+                let index = self.read_u16()?.into();
+                self.amf0_objects
+                    .get(index)
+                    .cloned()
+                    .ok_or(SolError::InvalidReference(index))?
+            }
+            0x08 => {
+                // ECMA arrays are prefixed with an (unreliable) length.
+                self.read_u32()?;
+                self.read_amf0_object()?
+            }
+            0x0A => {
+                let len = self.read_u32()?;
+                let index = self.amf0_objects.len();
+                self.amf0_objects.push(SolValue::Null);
+                let elements = (0..len)
+                    .map(|_| self.read_amf0_value())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let array = SolValue::Array(elements);
+                self.amf0_objects[index] = array.clone();
+                array
+            }
+            0x0B => {
+                // Dates are stored as their timestamp, followed by an unused timezone.
+                let time = self.read_f64()?;
+                self.read_u16()?;
+                SolValue::Number(time)
+            }
+            0x0C | 0x0F => SolValue::String(self.read_amf0_long_string()?),
+            0x10 => {
+                let _class_name = self.read_amf0_string()?;
+                self.read_amf0_object()?
+            }
+            0x11 => self.read_amf3_value()?,
+            _ => return Err(SolError::UnsupportedType(0, marker)),
+        })
+    }
+
+    /// Read a variable-length 29-bit unsigned integer.
+    fn read_u29(&mut self) -> Result<u32, SolError> {
+        let mut value = 0;
+        for _ in 0..3 {
+            let byte = self.read_u8()?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Ok((value << 8) | u32::from(self.read_u8()?))
+    }
+
+    fn read_amf3_string(&mut self) -> Result<String, SolError> {
+        let header = self.read_u29()? as usize;
+        if header & 1 == 0 {
+            let index = header >> 1;
+            return self
+                .amf3_strings
+                .get(index)
+                .cloned()
+                .ok_or(SolError::InvalidReference(index));
+        }
+
+        let string = self.read_utf8(header >> 1)?;
+        if !string.is_empty() {
+            self.amf3_strings.push(string.clone());
+        }
+        Ok(string)
+    }
+
+    /// Read the header of a by-reference AMF3 value.
+    ///
+    /// Returns either a previously read object, or the remaining header bits
+    /// of an inline value.
+    fn read_amf3_reference(&mut self) -> Result<Result<SolValue, usize>, SolError> {
+        let header = self.read_u29()? as usize;
+        if header & 1 == 0 {
+            let index = header >> 1;
+            return self
+                .amf3_objects
+                .get(index)
+                .cloned()
+                .map(Ok)
+                .ok_or(SolError::InvalidReference(index));
+        }
+        Ok(Err(header >> 1))
+    }
+
+    fn read_amf3_object(&mut self) -> Result<SolValue, SolError> {
+        let header = match self.read_amf3_reference()? {
+            Ok(object) => return Ok(object),
+            Err(header) => header,
+        };
+
+        let traits_index = if header & 1 == 0 {
+            let index = header >> 1;
+            if index >= self.amf3_traits.len() {
+                return Err(SolError::InvalidReference(index));
+            }
+            index
+        } else if header & 2 != 0 {
+            // Externalizable objects have a class-defined encoding.
+            return Err(SolError::UnsupportedType(3, 0x0A));
+        } else {
+            let dynamic = header & 4 != 0;
+            let sealed_count = header >> 3;
+            let _class_name = self.read_amf3_string()?;
+            let sealed = (0..sealed_count)
+                .map(|_| self.read_amf3_string())
+                .collect::<Result<Vec<_>, _>>()?;
+            self.amf3_traits.push(Traits { sealed, dynamic });
+            self.amf3_traits.len() - 1
+        };
+
+        let index = self.amf3_objects.len();
+        self.amf3_objects.push(SolValue::Null);
+
+        let sealed = self.amf3_traits[traits_index].sealed.clone();
+        let dynamic = self.amf3_traits[traits_index].dynamic;
+        let mut properties = Vec::new();
+        for key in sealed {
+            properties.push((key, self.read_amf3_value()?));
+        }
+        if dynamic {
+            loop {
+                let key = self.read_amf3_string()?;
+                if key.is_empty() {
+                    break;
+                }
+                properties.push((key, self.read_amf3_value()?));
+            }
+        }
+
+        let object = SolValue::Object(properties);
+        self.amf3_objects[index] = object.clone();
+        Ok(object)
+    }
+
+    fn read_amf3_array(&mut self) -> Result<SolValue, SolError> {
+        let dense_len = match self.read_amf3_reference()? {
+            Ok(array) => return Ok(array),
+            Err(len) => len,
+        };
+
+        let index = self.amf3_objects.len();
+        self.amf3_objects.push(SolValue::Null);
+
+        let mut associative = Vec::new();
+        loop {
+            let key = self.read_amf3_string()?;
+            if key.is_empty() {
+                break;
+            }
+            associative.push((key, self.read_amf3_value()?));
+        }
+        let elements = (0..dense_len)
+            .map(|_| self.read_amf3_value())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Arrays with named properties can only be represented as objects.
+        let array = if associative.is_empty() {
+            SolValue::Array(elements)
+        } else {
+            let mut properties: Vec<_> = elements
+                .into_iter()
+                .enumerate()
+                .map(|(i, element)| (i.to_string(), element))
+                .collect();
+            properties.extend(associative);
+            SolValue::Object(properties)
+        };
+        self.amf3_objects[index] = array.clone();
+        Ok(array)
+    }
+
+    fn read_amf3_value(&mut self) -> Result<SolValue, SolError> {
+        let marker = self.read_u8()?;
+        Ok(match marker {
+            0x00 => SolValue::Undefined,
+            0x01 => SolValue::Null,
+            0x02 => SolValue::Bool(false),
+            0x03 => SolValue::Bool(true),
+            0x04 => {
+                // Sign-extend the 29-bit integer.
+                let value = self.read_u29()?;
+                SolValue::Number(f64::from(((value << 3) as i32) >> 3))
+            }
+            0x05 => SolValue::Number(self.read_f64()?),
+            0x06 => SolValue::String(self.read_amf3_string()?),
+            0x07 | 0x0B => match self.read_amf3_reference()? {
+                Ok(xml) => xml,
+                Err(len) => {
+                    let xml = SolValue::String(self.read_utf8(len)?);
+                    self.amf3_objects.push(xml.clone());
+                    xml
+                }
+            },
+            0x08 => match self.read_amf3_reference()? {
+                Ok(date) => date,
+                Err(_) => {
+                    let date = SolValue::Number(self.read_f64()?);
+                    self.amf3_objects.push(date.clone());
+                    date
+                }
+            },
+            0x09 => self.read_amf3_array()?,
+            0x0A => self.read_amf3_object()?,
+            _ => return Err(SolError::UnsupportedType(3, marker)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amf0_round_trip() {
+        let sol = Sol {
+            name: "save".to_string(),
+            body: vec![
+                ("level".to_string(), SolValue::Number(3.0)),
+                ("name".to_string(), SolValue::String("Ruffle".to_string())),
+                ("cleared".to_string(), SolValue::Bool(true)),
+                ("nothing".to_string(), SolValue::Null),
+                (
+                    "inventory".to_string(),
+                    SolValue::Object(vec![
+                        ("coins".to_string(), SolValue::Number(99.0)),
+                        (
+                            "items".to_string(),
+                            SolValue::Array(vec![SolValue::String("sword".to_string())]),
+                        ),
+                    ]),
+                ),
+            ],
+            encoding: ObjectEncoding::Amf0,
+        };
+
+        let data = sol.write();
+        assert_eq!(&data[0..2], &HEADER_MAGIC);
+        assert_eq!(
+            data.len() - 6,
+            u32::from_be_bytes([data[2], data[3], data[4], data[5]]) as usize
+        );
+        assert_eq!(Sol::read(&data).unwrap(), sol);
+    }
+
+    #[test]
+    fn amf3_body() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&HEADER_MAGIC);
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(SIGNATURE);
+        data.extend_from_slice(&[0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x03]);
+        // score = -1 (integer)
+        data.extend_from_slice(&[
+            0x0B, b's', b'c', b'o', b'r', b'e', 0x04, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+        ]);
+        // best = "score" (string reference)
+        data.extend_from_slice(&[0x09, b'b', b'e', b's', b't', 0x06, 0x00, 0x00]);
+        // list = [true] (dense array)
+        data.extend_from_slice(&[0x09, b'l', b'i', b's', b't', 0x09, 0x03, 0x01, 0x03, 0x00]);
+
+        let sol = Sol::read(&data).unwrap();
+        assert_eq!(sol.name, "a");
+        assert_eq!(sol.encoding, ObjectEncoding::Amf3);
+        assert_eq!(
+            sol.body,
+            vec![
+                ("score".to_string(), SolValue::Number(-1.0)),
+                ("best".to_string(), SolValue::String("score".to_string())),
+                (
+                    "list".to_string(),
+                    SolValue::Array(vec![SolValue::Bool(true)])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn amf3_round_trip() {
+        let sol = Sol {
+            name: "save".to_string(),
+            body: vec![
+                ("level".to_string(), SolValue::Number(3.0)),
+                ("lives".to_string(), SolValue::Number(-2.0)),
+                ("ratio".to_string(), SolValue::Number(0.5)),
+                ("name".to_string(), SolValue::String("level".to_string())),
+                ("cleared".to_string(), SolValue::Bool(false)),
+                ("nothing".to_string(), SolValue::Null),
+                (
+                    "inventory".to_string(),
+                    SolValue::Object(vec![
+                        ("coins".to_string(), SolValue::Number(1e10)),
+                        (
+                            "items".to_string(),
+                            SolValue::Array(vec![SolValue::String("sword".to_string())]),
+                        ),
+                    ]),
+                ),
+            ],
+            encoding: ObjectEncoding::Amf3,
+        };
+
+        let data = sol.write();
+        assert_eq!(Sol::read(&data).unwrap(), sol);
+    }
+
+    #[test]
+    fn amf3_layout() {
+        // `so.data.score = -1; so.data.best = "score";`, with the integer
+        // and the string back-reference written the way Flash Player does.
+        let sol = Sol {
+            name: "a".to_string(),
+            body: vec![
+                ("score".to_string(), SolValue::Number(-1.0)),
+                ("best".to_string(), SolValue::String("score".to_string())),
+            ],
+            encoding: ObjectEncoding::Amf3,
+        };
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&HEADER_MAGIC);
+        expected.extend_from_slice(&[0, 0, 0, 37]);
+        expected.extend_from_slice(SIGNATURE);
+        expected.extend_from_slice(&[0x00, 0x01, b'a', 0x00, 0x00, 0x00, 0x03]);
+        expected.extend_from_slice(&[
+            0x0B, b's', b'c', b'o', b'r', b'e', 0x04, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+        ]);
+        expected.extend_from_slice(&[0x09, b'b', b'e', b's', b't', 0x06, 0x00, 0x00]);
+        assert_eq!(sol.write(), expected);
+    }
+
+    #[test]
+    fn json_conversion() {
+        let sol = Sol {
+            name: "save".to_string(),
+            body: vec![
+                ("gone".to_string(), SolValue::Undefined),
+                (
+                    "list".to_string(),
+                    SolValue::Array(vec![SolValue::Number(1.0), SolValue::Number(2.0)]),
+                ),
+            ],
+            encoding: ObjectEncoding::Amf0,
+        };
+
+        let json = sol.to_json();
+        assert!(!json.has_key("gone"));
+        assert_eq!(json["list"]["1"], 2.0);
+
+        let sol = Sol::from_json("save", &json, ObjectEncoding::Amf0);
+        assert_eq!(
+            sol.body,
+            vec![(
+                "list".to_string(),
+                SolValue::Object(vec![
+                    ("0".to_string(), SolValue::Number(1.0)),
+                    ("1".to_string(), SolValue::Number(2.0)),
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert!(matches!(Sol::read(&[]), Err(SolError::UnexpectedEof)));
+
+        let mut data = vec![0x00, 0xBF, 0, 0, 0, 0];
+        data.extend_from_slice(b"TCSP\x00\x04\x00\x00\x00\x00");
+        assert!(matches!(Sol::read(&data), Err(SolError::InvalidHeader)));
+    }
+}
@@ -0,0 +1,227 @@
+//! Stage scaling, alignment and quality
+
+use enumset::{EnumSet, EnumSetType};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the movie is scaled to fit the viewport, as set by `Stage.scaleMode`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StageScaleMode {
+    /// The movie is stretched to fill the viewport, ignoring its aspect ratio.
+    ExactFit,
+
+    /// The movie is scaled to cover the whole viewport while keeping its
+    /// aspect ratio, cropping whatever doesn't fit.
+    NoBorder,
+
+    /// The movie is not scaled, and the stage takes the size of the viewport.
+    NoScale,
+
+    /// The movie is scaled to fit inside the viewport while keeping its
+    /// aspect ratio, leaving margins on either side.
+    ShowAll,
+}
+
+impl Default for StageScaleMode {
+    fn default() -> Self {
+        StageScaleMode::ShowAll
+    }
+}
+
+impl FromStr for StageScaleMode {
+    type Err = ();
+
+    /// Parses a scale mode as named by ActionScript; case is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "exactfit" => Ok(StageScaleMode::ExactFit),
+            "noborder" => Ok(StageScaleMode::NoBorder),
+            "noscale" => Ok(StageScaleMode::NoScale),
+            "showall" => Ok(StageScaleMode::ShowAll),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for StageScaleMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StageScaleMode::ExactFit => "exactFit",
+            StageScaleMode::NoBorder => "noBorder",
+            StageScaleMode::NoScale => "noScale",
+            StageScaleMode::ShowAll => "showAll",
+        })
+    }
+}
+
+/// How finely the movie is rendered, as set by `_quality`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StageQuality {
+    /// Nothing is anti-aliased, and bitmaps are never smoothed.
+    Low,
+
+    /// Bitmaps are only smoothed in SWF 8 and later, when their fill asks
+    /// for it.
+    Medium,
+
+    /// The default. Bitmaps are smoothed as for `Medium`.
+    High,
+
+    /// Bitmaps are always smoothed in SWF 7 and earlier, and as for
+    /// `Medium` otherwise.
+    Best,
+}
+
+impl StageQuality {
+    /// Whether a bitmap fill is smoothed when scaled.
+    ///
+    /// `is_smoothed` is the flag of the fill itself, and `swf_version` is the
+    /// version of the movie being rendered. Movies before SWF 8 have no
+    /// control over smoothing, and their bitmaps are only smoothed at `Best`.
+    pub fn smooth_bitmaps(self, is_smoothed: bool, swf_version: u8) -> bool {
+        match self {
+            StageQuality::Low => false,
+            StageQuality::Best if swf_version <= 7 => true,
+            _ if swf_version <= 7 => false,
+            _ => is_smoothed,
+        }
+    }
+}
+
+impl Default for StageQuality {
+    fn default() -> Self {
+        StageQuality::High
+    }
+}
+
+impl FromStr for StageQuality {
+    type Err = ();
+
+    /// Parses a quality as named by ActionScript; case is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "low" => Ok(StageQuality::Low),
+            "medium" => Ok(StageQuality::Medium),
+            "high" => Ok(StageQuality::High),
+            "best" => Ok(StageQuality::Best),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for StageQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+        })
+    }
+}
+
+/// The edges of the viewport that the stage is anchored to, as set by
+/// `Stage.align`.
+///
+/// The stage is centered along any axis without an edge set.
+#[derive(EnumSetType, Debug)]
+pub enum StageAlign {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl StageAlign {
+    fn letter(self) -> char {
+        match self {
+            StageAlign::Top => 'T',
+            StageAlign::Bottom => 'B',
+            StageAlign::Left => 'L',
+            StageAlign::Right => 'R',
+        }
+    }
+
+    /// Parses an alignment string such as `"TL"`.
+    ///
+    /// Case is ignored, as are any characters other than `T`, `B`, `L` and
+    /// `R`.
+    pub fn parse(s: &str) -> EnumSet<StageAlign> {
+        s.chars()
+            .filter_map(|c| match c.to_ascii_uppercase() {
+                'T' => Some(StageAlign::Top),
+                'B' => Some(StageAlign::Bottom),
+                'L' => Some(StageAlign::Left),
+                'R' => Some(StageAlign::Right),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Formats an alignment as returned by `Stage.align`, such as `"TL"`.
+    pub fn format(align: EnumSet<StageAlign>) -> String {
+        align.iter().map(StageAlign::letter).collect()
+    }
+
+    /// The offset of the stage along one axis, given the space left over
+    /// after scaling, and whether the stage is anchored to the start or end.
+    pub(crate) fn offset(extra: f32, start: bool, end: bool) -> f32 {
+        match (start, end) {
+            (true, false) => 0.0,
+            (false, true) => extra,
+            _ => extra / 2.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scale_mode() {
+        assert_eq!("showAll".parse(), Ok(StageScaleMode::ShowAll));
+        assert_eq!("NOSCALE".parse(), Ok(StageScaleMode::NoScale));
+        assert_eq!("exactfit".parse(), Ok(StageScaleMode::ExactFit));
+        assert_eq!("noBorder".parse(), Ok(StageScaleMode::NoBorder));
+        assert_eq!("stretch".parse::<StageScaleMode>(), Err(()));
+    }
+
+    #[test]
+    fn parse_quality() {
+        assert_eq!("low".parse(), Ok(StageQuality::Low));
+        assert_eq!("BEST".parse(), Ok(StageQuality::Best));
+        assert_eq!("Medium".parse(), Ok(StageQuality::Medium));
+        assert_eq!("ultra".parse::<StageQuality>(), Err(()));
+        assert_eq!(StageQuality::High.to_string(), "HIGH");
+    }
+
+    #[test]
+    fn bitmap_smoothing() {
+        // Low never smooths.
+        assert!(!StageQuality::Low.smooth_bitmaps(true, 6));
+        assert!(!StageQuality::Low.smooth_bitmaps(true, 10));
+
+        // Before SWF 8, only Best smooths.
+        assert!(!StageQuality::High.smooth_bitmaps(true, 7));
+        assert!(StageQuality::Best.smooth_bitmaps(true, 7));
+        assert!(StageQuality::Best.smooth_bitmaps(false, 6));
+
+        // SWF 8 and later follow the fill.
+        assert!(StageQuality::Medium.smooth_bitmaps(true, 8));
+        assert!(StageQuality::High.smooth_bitmaps(true, 8));
+        assert!(!StageQuality::High.smooth_bitmaps(false, 8));
+        assert!(!StageQuality::Best.smooth_bitmaps(false, 9));
+    }
+
+    #[test]
+    fn parse_align() {
+        assert_eq!(StageAlign::parse("tl"), StageAlign::Top | StageAlign::Left);
+        assert_eq!(
+            StageAlign::parse("RB"),
+            StageAlign::Bottom | StageAlign::Right
+        );
+        assert_eq!(StageAlign::parse("xyz"), EnumSet::empty());
+        assert_eq!(StageAlign::format(StageAlign::parse("RB")), "BR");
+    }
+}
@@ -21,6 +21,10 @@ pub struct SwfMovie {
 
     /// The URL the SWF was downloaded from.
     url: Option<String>,
+
+    /// The uncompressed length of the SWF file as stated in its header,
+    /// including the header itself.
+    uncompressed_len: usize,
 }
 
 impl SwfMovie {
@@ -36,6 +40,7 @@ impl SwfMovie {
             },
             data: vec![],
             url: None,
+            uncompressed_len: 0,
         }
     }
 
@@ -47,6 +52,7 @@ impl SwfMovie {
     pub fn from_movie_and_subdata(&self, data: Vec<u8>, source: &SwfMovie) -> Self {
         Self {
             header: self.header.clone(),
+            uncompressed_len: data.len(),
             data,
             url: source.url.clone(),
         }
@@ -68,6 +74,7 @@ impl SwfMovie {
     pub fn from_data(swf_data: &[u8], url: Option<String>) -> Result<Self, Error> {
         let swf_stream = swf::read::read_swf_header(&swf_data[..])?;
         let header = swf_stream.header;
+        let uncompressed_len = swf_stream.uncompressed_length + 8;
         let mut reader = swf_stream.reader;
 
         // Decompress the entire SWF in memory.
@@ -90,7 +97,12 @@ impl SwfMovie {
             data
         };
 
-        Ok(Self { header, data, url })
+        Ok(Self {
+            header,
+            data,
+            url,
+            uncompressed_len,
+        })
     }
 
     pub fn header(&self) -> &Header {
@@ -118,6 +130,11 @@ impl SwfMovie {
     pub fn url(&self) -> Option<&str> {
         self.url.as_deref()
     }
+
+    /// The uncompressed length of the SWF file, including its header.
+    pub fn uncompressed_len(&self) -> usize {
+        self.uncompressed_len
+    }
 }
 
 /// A shared-ownership reference to some portion of an SWF datastream.
@@ -0,0 +1,578 @@
+//! Timer handling for `setInterval`/`setTimeout` and `flash.utils.Timer`.
+//!
+//! We tick the timers during our normal frame loop for deterministic operation.
+//! The deadlines of the timers are stored in a priority queue, and every timer
+//! whose deadline has passed is fired on each update, in deadline order. The
+//! timers share their clock with `getTimer`.
+//!
+//! A repeating timer is rescheduled relative to its previous deadline rather
+//! than to the time it actually fired, so it doesn't drift: a 16ms timer in a
+//! 30fps movie fires twice per frame, as it does in Flash Player. A timer that
+//! is no faster than the frame rate fires at most once per update, even if
+//! the player has fallen behind.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::object::search_prototype;
+use crate::avm1::{Object, TObject, Value};
+use crate::avm2::{Avm2, Object as Avm2Object};
+use crate::context::UpdateContext;
+use crate::tag_utils::SwfMovie;
+use gc_arena::Collect;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+
+/// Manages the collection of timers.
+pub struct Timers<'gc> {
+    /// The active timers, by ID.
+    timers: HashMap<i32, Timer<'gc>>,
+
+    /// The deadlines of the active timers, earliest first.
+    ///
+    /// Deadlines are never removed from the middle of the queue; instead, a
+    /// deadline is skipped when it's popped if its timer has since been removed
+    /// or rescheduled.
+    deadlines: BinaryHeap<Reverse<Deadline>>,
+
+    /// An increasing ID used for created timers.
+    timer_counter: i32,
+
+    /// An increasing ID used each time a timer is scheduled.
+    schedule_counter: u64,
+
+    /// The current global time, in microseconds.
+    cur_time: u64,
+
+    /// The time between frames of the movie, in microseconds.
+    frame_time: u64,
+}
+
+impl<'gc> Timers<'gc> {
+    /// Ticks all timers and runs necessary callbacks.
+    ///
+    /// `frame_time` is the time between frames of the movie, in milliseconds.
+    /// Returns the approximate amount of time until the next timer tick.
+    pub fn update_timers(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        dt: f64,
+        frame_time: f64,
+    ) -> Option<f64> {
+        context.timers.advance(dt);
+        context.timers.frame_time = (frame_time * Self::TIMER_SCALE) as u64;
+
+        // We have to be careful because the timer list can be mutated while updating;
+        // a timer callback could add more timers, clear timers, etc.
+        while let Some(fired) = context.timers.fire_next() {
+            match fired.callback {
+                TimerCallback::Avm2Timer(target) => Avm2::timer_tick(context, target),
+                callback => Self::run_avm1_callback(context, callback, &fired.params),
+            }
+        }
+
+        context.timers.time_til_next_timer()
+    }
+
+    fn run_avm1_callback(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        callback: TimerCallback<'gc>,
+        params: &[Value<'gc>],
+    ) {
+        let version = context.swf.header().version;
+        let globals = context.avm1.global_object_cell();
+        let level0 = context.levels.get(&0).copied().unwrap();
+
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Timer Callback]"),
+            version,
+            globals,
+            level0,
+        );
+
+        // TODO: `this` is undefined for non-method timer callbacks, but our VM
+        // currently doesn't allow `this` to be a Value.
+        let undefined = Value::Undefined.coerce_to_object(&mut activation);
+
+        let callback = match callback {
+            TimerCallback::Avm1Function(f) => Some((undefined, None, f)),
+            TimerCallback::Avm1Method { this, method_name } => {
+                // Fetch the callback method from the object.
+                if let Ok((f, base_proto)) =
+                    search_prototype(Some(this), &method_name, &mut activation, this)
+                {
+                    let f = f.coerce_to_object(&mut activation);
+                    Some((this, base_proto, f))
+                } else {
+                    None
+                }
+            }
+            TimerCallback::Avm2Timer(_) => None,
+        };
+
+        if let Some((this, base_proto, function)) = callback {
+            let _ = function.call(
+                "[Timer Callback]",
+                &mut activation,
+                this,
+                base_proto,
+                params,
+            );
+        }
+    }
+
+    /// The minimum interval we allow for timers.
+    const MIN_INTERVAL: i32 = 10;
+
+    /// The maximum number of times a single timer fires per update.
+    ///
+    /// A timer that has fallen further behind than this skips the ticks it
+    /// missed, so that a long stall doesn't cause a burst of callbacks.
+    const MAX_CATCH_UP_TICKS: u64 = 10;
+
+    /// The scale of the timers (microseconds).
+    const TIMER_SCALE: f64 = 1000.0;
+
+    /// Creates a new `Timers` collection.
+    pub fn new() -> Self {
+        Self {
+            timers: HashMap::new(),
+            deadlines: BinaryHeap::new(),
+            timer_counter: 0,
+            schedule_counter: 0,
+            cur_time: 0,
+            frame_time: 0,
+        }
+    }
+
+    /// The number of timers currently active.
+    pub fn num_timers(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// The current time in milliseconds, as returned by `getTimer`.
+    pub fn time(&self) -> u32 {
+        (self.cur_time / Self::TIMER_SCALE as u64) as u32
+    }
+
+    /// Registers a new `setInterval`/`setTimeout` timer created by code from the given movie,
+    /// and returns the timer ID.
+    pub fn add_timer(
+        &mut self,
+        callback: TimerCallback<'gc>,
+        interval: i32,
+        params: Vec<Value<'gc>>,
+        is_timeout: bool,
+        movie: Option<Arc<SwfMovie>>,
+    ) -> i32 {
+        // SANITY: Set a minimum interval so we don't spam too much.
+        let interval = interval.max(Self::MIN_INTERVAL) as u64 * (Self::TIMER_SCALE as u64);
+        self.insert(callback, interval, params, is_timeout, movie)
+    }
+
+    /// Removes a timer.
+    pub fn remove(&mut self, id: i32) -> bool {
+        self.timers.remove(&id).is_some()
+    }
+
+    /// Removes all timers created by code from a movie, such as when it is unloaded.
+    pub fn remove_movie_timers(&mut self, movie: &Arc<SwfMovie>) {
+        self.timers.retain(|_, timer| match &timer.movie {
+            Some(timer_movie) => !Arc::ptr_eq(timer_movie, movie),
+            None => true,
+        });
+    }
+
+    /// Advances the clock by `dt` milliseconds.
+    fn advance(&mut self, dt: f64) {
+        self.cur_time = self.cur_time.wrapping_add((dt * Self::TIMER_SCALE) as u64);
+    }
+
+    fn insert(
+        &mut self,
+        callback: TimerCallback<'gc>,
+        interval: u64,
+        params: Vec<Value<'gc>>,
+        is_timeout: bool,
+        movie: Option<Arc<SwfMovie>>,
+    ) -> i32 {
+        self.timer_counter = self.timer_counter.wrapping_add(1);
+        let id = self.timer_counter;
+        let timer = Timer {
+            callback,
+            params,
+            tick_time: self.cur_time + interval,
+            interval,
+            is_timeout,
+            schedule_id: 0,
+            movie,
+        };
+        self.timers.insert(id, timer);
+        self.schedule(id);
+        id
+    }
+
+    /// Queues the deadline of a timer, superseding any deadline queued before.
+    fn schedule(&mut self, id: i32) {
+        if let Some(timer) = self.timers.get_mut(&id) {
+            self.schedule_counter = self.schedule_counter.wrapping_add(1);
+            timer.schedule_id = self.schedule_counter;
+            self.deadlines.push(Reverse(Deadline {
+                tick_time: timer.tick_time,
+                schedule_id: timer.schedule_id,
+                id,
+            }));
+        }
+    }
+
+    /// Finds the next timer whose deadline has passed, and schedules its next
+    /// tick, or removes it if it doesn't tick again.
+    ///
+    /// Returns the callback to run for the timer.
+    fn fire_next(&mut self) -> Option<FiredTimer<'gc>> {
+        loop {
+            let deadline = match self.deadlines.peek() {
+                Some(Reverse(deadline)) if deadline.tick_time < self.cur_time => {
+                    self.deadlines.pop().unwrap().0
+                }
+                _ => return None,
+            };
+
+            let timer = match self.timers.get_mut(&deadline.id) {
+                Some(timer) if timer.schedule_id == deadline.schedule_id => timer,
+                // The timer was removed or rescheduled.
+                _ => continue,
+            };
+
+            let fired = FiredTimer {
+                callback: timer.callback.clone(),
+                params: timer.params.clone(),
+            };
+
+            if timer.is_timeout {
+                // Timeouts only fire once.
+                self.timers.remove(&deadline.id);
+            } else {
+                // Only timers that are faster than the frame rate catch up on missed ticks.
+                let max_catch_up_ticks = if timer.interval < self.frame_time {
+                    Self::MAX_CATCH_UP_TICKS
+                } else {
+                    1
+                };
+                timer.tick_time = next_tick_time(
+                    timer.tick_time,
+                    timer.interval,
+                    self.cur_time,
+                    max_catch_up_ticks,
+                );
+                self.schedule(deadline.id);
+            }
+
+            return Some(fired);
+        }
+    }
+
+    /// The estimated time until the next timer tick, in milliseconds.
+    fn time_til_next_timer(&self) -> Option<f64> {
+        self.deadlines
+            .iter()
+            .map(|Reverse(deadline)| deadline)
+            .filter(|deadline| {
+                self.timers
+                    .get(&deadline.id)
+                    .map_or(false, |timer| timer.schedule_id == deadline.schedule_id)
+            })
+            .map(|deadline| deadline.tick_time)
+            .min()
+            .map(|tick_time| tick_time.saturating_sub(self.cur_time) as f64 / Self::TIMER_SCALE)
+    }
+
+    /// The ID of the scheduled timer of a `flash.utils.Timer`, if it is running.
+    fn avm2_timer_id(&self, target: Avm2Object<'gc>) -> Option<i32> {
+        self.timers
+            .iter()
+            .find_map(|(id, timer)| match timer.callback {
+                TimerCallback::Avm2Timer(timer_target)
+                    if Avm2Object::ptr_eq(timer_target, target) =>
+                {
+                    Some(*id)
+                }
+                _ => None,
+            })
+    }
+
+    /// Start a `flash.utils.Timer` created by code from the given movie, if it
+    /// isn't running already.
+    ///
+    /// The first tick happens one delay from now, in milliseconds.
+    pub fn start_avm2_timer(&mut self, target: Avm2Object<'gc>, delay: f64, movie: Arc<SwfMovie>) {
+        if self.avm2_timer_running(target) {
+            return;
+        }
+
+        self.insert(
+            TimerCallback::Avm2Timer(target),
+            Self::avm2_interval(delay),
+            vec![],
+            false,
+            Some(movie),
+        );
+    }
+
+    /// Change the delay of a running `flash.utils.Timer`, so that its next
+    /// tick is one new delay from now.
+    pub fn restart_avm2_timer(&mut self, target: Avm2Object<'gc>, delay: f64) {
+        if let Some(id) = self.avm2_timer_id(target) {
+            let interval = Self::avm2_interval(delay);
+            let cur_time = self.cur_time;
+            if let Some(timer) = self.timers.get_mut(&id) {
+                timer.interval = interval;
+                timer.tick_time = cur_time + interval;
+            }
+            self.schedule(id);
+        }
+    }
+
+    /// The interval of a `flash.utils.Timer` with the given delay in milliseconds.
+    fn avm2_interval(delay: f64) -> u64 {
+        ((delay * Self::TIMER_SCALE) as u64)
+            .max(Self::MIN_INTERVAL as u64 * Self::TIMER_SCALE as u64)
+    }
+
+    /// Stop a `flash.utils.Timer`.
+    pub fn stop_avm2_timer(&mut self, target: Avm2Object<'gc>) {
+        if let Some(id) = self.avm2_timer_id(target) {
+            self.timers.remove(&id);
+        }
+    }
+
+    pub fn avm2_timer_running(&self, target: Avm2Object<'gc>) -> bool {
+        self.avm2_timer_id(target).is_some()
+    }
+}
+
+/// The deadline after a repeating timer fires at `tick_time`.
+///
+/// If the timer has fallen more than `max_catch_up_ticks` intervals behind
+/// `cur_time`, the ticks it missed beyond that are skipped. The timer keeps
+/// its phase either way.
+fn next_tick_time(tick_time: u64, interval: u64, cur_time: u64, max_catch_up_ticks: u64) -> u64 {
+    // The number of later ticks that are also due already.
+    let pending = cur_time.saturating_sub(tick_time).saturating_sub(1) / interval;
+    let skipped = pending.saturating_sub(max_catch_up_ticks.saturating_sub(1));
+    tick_time + (skipped + 1) * interval
+}
+
+impl Default for Timers<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<'gc> Collect for Timers<'gc> {
+    fn trace(&self, cc: gc_arena::CollectionContext) {
+        for timer in self.timers.values() {
+            timer.trace(cc);
+        }
+    }
+}
+
+/// A timer created via `setInterval`/`setTimeout`, or a running `flash.utils.Timer`.
+/// Runs a callback when it ticks.
+#[derive(Debug, Collect)]
+#[collect(no_drop)]
+struct Timer<'gc> {
+    /// The callback that this timer runs when it fires.
+    callback: TimerCallback<'gc>,
+
+    /// The parameters to pass to the callback function.
+    params: Vec<Value<'gc>>,
+
+    /// The time when this timer should fire.
+    tick_time: u64,
+
+    /// The interval between timer ticks, in microseconds.
+    interval: u64,
+
+    /// This timer only fires once if `is_timeout` is true.
+    is_timeout: bool,
+
+    /// The ID of the deadline most recently queued for this timer.
+    schedule_id: u64,
+
+    /// The movie whose code created this timer, if it should be removed when
+    /// that movie is unloaded.
+    movie: Option<Arc<SwfMovie>>,
+}
+
+/// A queued timer deadline.
+///
+/// Deadlines are ordered by time, and then by the order in which they were
+/// queued.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Deadline {
+    tick_time: u64,
+    schedule_id: u64,
+    id: i32,
+}
+
+/// A timer that has just fired.
+struct FiredTimer<'gc> {
+    callback: TimerCallback<'gc>,
+    params: Vec<Value<'gc>>,
+}
+
+/// A callback fired by a timer.
+#[derive(Debug, Collect, Clone)]
+#[collect(no_drop)]
+pub enum TimerCallback<'gc> {
+    /// A function passed to `setInterval`/`setTimeout`.
+    Avm1Function(Object<'gc>),
+
+    /// A method passed to `setInterval`/`setTimeout` by name.
+    Avm1Method {
+        this: Object<'gc>,
+        method_name: String,
+    },
+
+    /// A `flash.utils.Timer`, which dispatches `TimerEvent`s to this object.
+    Avm2Timer(Avm2Object<'gc>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+    use crate::avm1::ScriptObject;
+
+    /// Count how many times a timer with the given interval fires over one
+    /// second of a movie running at the given frame rate.
+    fn firings_per_second(frame_rate: f64, interval: i32) -> u32 {
+        let mut count = 0;
+        with_avm(
+            19,
+            |activation, _root| -> Result<(), crate::avm1::error::Error> {
+                let callback = ScriptObject::object(activation.context.gc_context, None).into();
+                let timers = &mut *activation.context.timers;
+                timers.add_timer(
+                    TimerCallback::Avm1Function(callback),
+                    interval,
+                    vec![],
+                    false,
+                    None,
+                );
+
+                let frame_time = 1000.0 / frame_rate;
+                timers.frame_time = (frame_time * Timers::TIMER_SCALE) as u64;
+                let num_frames = frame_rate.round() as u32;
+                for _ in 0..num_frames {
+                    timers.advance(frame_time);
+                    while timers.fire_next().is_some() {
+                        count += 1;
+                    }
+                }
+                Ok(())
+            },
+        );
+        count
+    }
+
+    #[test]
+    fn timers_fire_without_drift() {
+        // A 16ms timer fires about twice per frame at 30fps, and four times at 15fps,
+        // but still around 62 times per second either way.
+        assert_eq!(firings_per_second(30.0, 16), 62);
+        assert_eq!(firings_per_second(15.0, 16), 62);
+        assert_eq!(firings_per_second(60.0, 16), 62);
+
+        // Timers slower than the frame rate still fire on time.
+        assert_eq!(firings_per_second(24.0, 100), 9);
+        assert_eq!(firings_per_second(12.0, 250), 3);
+    }
+
+    #[test]
+    fn timers_catch_up_is_capped() {
+        // A timer more than `MAX_CATCH_UP_TICKS` behind skips the extra ticks.
+        assert_eq!(
+            firings_per_second(1.0, 10),
+            Timers::MAX_CATCH_UP_TICKS as u32
+        );
+    }
+
+    #[test]
+    fn slow_timers_do_not_catch_up() {
+        with_avm(
+            19,
+            |activation, _root| -> Result<(), crate::avm1::error::Error> {
+                let callback = ScriptObject::object(activation.context.gc_context, None).into();
+                let timers = &mut *activation.context.timers;
+                timers.frame_time = 33_333;
+                timers.add_timer(
+                    TimerCallback::Avm1Function(callback),
+                    100,
+                    vec![],
+                    false,
+                    None,
+                );
+
+                // After a one second stall, a timer slower than the frame rate fires once.
+                timers.advance(1050.0);
+                assert!(timers.fire_next().is_some());
+                assert!(timers.fire_next().is_none());
+
+                // It keeps its phase for the next tick.
+                timers.advance(49.0);
+                assert!(timers.fire_next().is_none());
+                timers.advance(2.0);
+                assert!(timers.fire_next().is_some());
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn unloading_a_movie_removes_its_timers() {
+        with_avm(
+            19,
+            |activation, _root| -> Result<(), crate::avm1::error::Error> {
+                let callback: Object<'_> =
+                    ScriptObject::object(activation.context.gc_context, None).into();
+                let parent = Arc::new(SwfMovie::empty(19));
+                let child = Arc::new(SwfMovie::empty(19));
+                let timers = &mut *activation.context.timers;
+                for movie in &[&parent, &child, &child] {
+                    timers.add_timer(
+                        TimerCallback::Avm1Function(callback),
+                        100,
+                        vec![],
+                        false,
+                        Some(Arc::clone(movie)),
+                    );
+                }
+                timers.add_timer(
+                    TimerCallback::Avm1Function(callback),
+                    100,
+                    vec![],
+                    false,
+                    None,
+                );
+                let avm2_timer = activation.context.avm2.globals();
+                let timers = &mut *activation.context.timers;
+                timers.start_avm2_timer(avm2_timer, 100.0, Arc::clone(&child));
+
+                timers.remove_movie_timers(&child);
+                assert_eq!(timers.num_timers(), 2);
+                assert!(!timers.avm2_timer_running(avm2_timer));
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn next_tick_time_keeps_phase() {
+        assert_eq!(next_tick_time(16_000, 16_000, 33_333, 10), 32_000);
+        assert_eq!(next_tick_time(32_000, 16_000, 33_333, 10), 48_000);
+        // 100 ticks behind; only the last `max_catch_up_ticks` are kept.
+        assert_eq!(next_tick_time(0, 1_000, 100_500, 10), 92_000);
+        assert_eq!(next_tick_time(0, 1_000, 100_500, 1), 101_000);
+    }
+}
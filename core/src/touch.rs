@@ -0,0 +1,391 @@
+//! Touch points and the gestures made with them
+
+use crate::events::TouchPhase;
+use gc_arena::Collect;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the movie wants touch input, as set by `Multitouch.inputMode`.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum MultitouchInputMode {
+    /// Touches are only reported as mouse events.
+    None,
+
+    /// Each touch point is reported with its own touch events.
+    TouchPoint,
+
+    /// Movements of several fingers are reported as gesture events.
+    Gesture,
+}
+
+impl Default for MultitouchInputMode {
+    fn default() -> Self {
+        MultitouchInputMode::Gesture
+    }
+}
+
+impl FromStr for MultitouchInputMode {
+    type Err = ();
+
+    /// Parses an input mode as named by `MultitouchInputMode`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(MultitouchInputMode::None),
+            "touchPoint" => Ok(MultitouchInputMode::TouchPoint),
+            "gesture" => Ok(MultitouchInputMode::Gesture),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for MultitouchInputMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MultitouchInputMode::None => "none",
+            MultitouchInputMode::TouchPoint => "touchPoint",
+            MultitouchInputMode::Gesture => "gesture",
+        })
+    }
+}
+
+/// A finger in contact with the screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TouchPoint {
+    pub id: i32,
+    pub x: f64,
+    pub y: f64,
+    pub pressure: f64,
+
+    /// Whether this was the first finger to touch the screen, which also acts
+    /// as the mouse.
+    pub is_primary: bool,
+}
+
+/// The stage of a gesture, as in `GesturePhase`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GesturePhase {
+    Begin,
+    Update,
+    End,
+}
+
+/// A change made with two fingers, as reported by `TransformGestureEvent`.
+///
+/// Positions are the point halfway between both fingers. Scales and offsets
+/// are relative to the previous event of the same gesture.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransformGesture {
+    /// The fingers moved apart or together.
+    Zoom {
+        phase: GesturePhase,
+        x: f64,
+        y: f64,
+        scale: f64,
+    },
+
+    /// The fingers moved together in the same direction.
+    Pan {
+        phase: GesturePhase,
+        x: f64,
+        y: f64,
+        offset_x: f64,
+        offset_y: f64,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum GestureKind {
+    Zoom,
+    Pan,
+}
+
+/// A two finger gesture in progress.
+#[derive(Clone, Debug)]
+struct Gesture {
+    /// Which gesture the fingers are making, once they have moved far enough
+    /// to tell.
+    kind: Option<GestureKind>,
+
+    /// The point halfway between both fingers at the last event.
+    centre: (f64, f64),
+
+    /// The distance between both fingers at the last event.
+    distance: f64,
+}
+
+/// Tracks the fingers touching the screen.
+#[derive(Clone, Debug, Default)]
+pub struct TouchPoints {
+    /// The fingers currently touching, in the order they touched.
+    points: Vec<TouchPoint>,
+
+    gesture: Option<Gesture>,
+}
+
+impl TouchPoints {
+    /// How much the distance between two fingers must change, as a ratio,
+    /// before they are considered to be zooming.
+    const ZOOM_THRESHOLD: f64 = 0.05;
+
+    /// How far two fingers must move together, in pixels, before they are
+    /// considered to be panning.
+    const PAN_THRESHOLD: f64 = 10.0;
+
+    /// Record a touch event, returning the touch point it affected and any
+    /// gesture it made.
+    ///
+    /// The point is `None` if a move or end event was received for a finger
+    /// that never began touching.
+    pub fn update(
+        &mut self,
+        phase: TouchPhase,
+        id: i32,
+        x: f64,
+        y: f64,
+        pressure: f64,
+    ) -> (Option<TouchPoint>, Option<TransformGesture>) {
+        let index = self.points.iter().position(|point| point.id == id);
+        let point = match (phase, index) {
+            (TouchPhase::Begin, None) => {
+                // The primary point stays primary until every finger is lifted.
+                let point = TouchPoint {
+                    id,
+                    x,
+                    y,
+                    pressure,
+                    is_primary: self.points.is_empty(),
+                };
+                self.points.push(point);
+                point
+            }
+            (TouchPhase::Begin, Some(index)) | (TouchPhase::Move, Some(index)) => {
+                let point = &mut self.points[index];
+                point.x = x;
+                point.y = y;
+                point.pressure = pressure;
+                *point
+            }
+            (TouchPhase::End, Some(index)) => {
+                let mut point = self.points.remove(index);
+                point.x = x;
+                point.y = y;
+                point.pressure = pressure;
+                point
+            }
+            (_, None) => return (None, None),
+        };
+
+        let gesture = match (phase, index) {
+            (TouchPhase::End, Some(index)) if index < 2 => self.end_gesture(),
+            (TouchPhase::Begin, None) if self.points.len() == 2 => {
+                self.begin_gesture();
+                None
+            }
+            (TouchPhase::Move, Some(index)) if index < 2 => self.update_gesture(),
+            _ => None,
+        };
+
+        (Some(point), gesture)
+    }
+
+    /// The point halfway between the first two fingers, and the distance
+    /// between them.
+    fn span(&self) -> ((f64, f64), f64) {
+        let (a, b) = (&self.points[0], &self.points[1]);
+        let centre = ((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+        let distance = (a.x - b.x).hypot(a.y - b.y);
+        (centre, distance)
+    }
+
+    fn begin_gesture(&mut self) {
+        let (centre, distance) = self.span();
+        self.gesture = Some(Gesture {
+            kind: None,
+            centre,
+            distance,
+        });
+    }
+
+    fn update_gesture(&mut self) -> Option<TransformGesture> {
+        if self.gesture.is_none() || self.points.len() < 2 {
+            return None;
+        }
+
+        let (centre, distance) = self.span();
+        let gesture = self.gesture.as_mut()?;
+
+        let scale = if gesture.distance > 0.0 {
+            distance / gesture.distance
+        } else {
+            1.0
+        };
+        let offset = (centre.0 - gesture.centre.0, centre.1 - gesture.centre.1);
+
+        let phase = match gesture.kind {
+            Some(_) => GesturePhase::Update,
+            None if (scale - 1.0).abs() > Self::ZOOM_THRESHOLD => {
+                gesture.kind = Some(GestureKind::Zoom);
+                GesturePhase::Begin
+            }
+            None if offset.0.hypot(offset.1) > Self::PAN_THRESHOLD => {
+                gesture.kind = Some(GestureKind::Pan);
+                GesturePhase::Begin
+            }
+            // Too small to tell yet; keep measuring from where the fingers began.
+            None => return None,
+        };
+
+        gesture.centre = centre;
+        gesture.distance = distance;
+
+        Some(match gesture.kind {
+            Some(GestureKind::Zoom) => TransformGesture::Zoom {
+                phase,
+                x: centre.0,
+                y: centre.1,
+                scale,
+            },
+            _ => TransformGesture::Pan {
+                phase,
+                x: centre.0,
+                y: centre.1,
+                offset_x: offset.0,
+                offset_y: offset.1,
+            },
+        })
+    }
+
+    /// Finish the gesture when one of its fingers is lifted.
+    fn end_gesture(&mut self) -> Option<TransformGesture> {
+        let gesture = self.gesture.take()?;
+        let (x, y) = gesture.centre;
+        match gesture.kind? {
+            GestureKind::Zoom => Some(TransformGesture::Zoom {
+                phase: GesturePhase::End,
+                x,
+                y,
+                scale: 1.0,
+            }),
+            GestureKind::Pan => Some(TransformGesture::Pan {
+                phase: GesturePhase::End,
+                x,
+                y,
+                offset_x: 0.0,
+                offset_y: 0.0,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_mode() {
+        assert_eq!("touchPoint".parse(), Ok(MultitouchInputMode::TouchPoint));
+        assert_eq!("gesture".parse(), Ok(MultitouchInputMode::Gesture));
+        assert_eq!("none".parse(), Ok(MultitouchInputMode::None));
+        assert_eq!("GESTURE".parse::<MultitouchInputMode>(), Err(()));
+        assert_eq!(MultitouchInputMode::TouchPoint.to_string(), "touchPoint");
+    }
+
+    #[test]
+    fn primary_point() {
+        let mut touches = TouchPoints::default();
+        let (first, _) = touches.update(TouchPhase::Begin, 7, 0.0, 0.0, 1.0);
+        assert!(first.unwrap().is_primary);
+        let (second, _) = touches.update(TouchPhase::Begin, 8, 10.0, 0.0, 1.0);
+        assert!(!second.unwrap().is_primary);
+
+        // Lifting the primary finger doesn't promote the other one.
+        touches.update(TouchPhase::End, 7, 0.0, 0.0, 0.0);
+        let (second, _) = touches.update(TouchPhase::Move, 8, 20.0, 0.0, 1.0);
+        assert!(!second.unwrap().is_primary);
+        touches.update(TouchPhase::End, 8, 20.0, 0.0, 0.0);
+
+        let (third, _) = touches.update(TouchPhase::Begin, 9, 0.0, 0.0, 1.0);
+        assert!(third.unwrap().is_primary);
+
+        // Unknown fingers are ignored.
+        assert_eq!(
+            touches.update(TouchPhase::Move, 1, 0.0, 0.0, 1.0),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn zoom_gesture() {
+        let mut touches = TouchPoints::default();
+        touches.update(TouchPhase::Begin, 1, 0.0, 0.0, 1.0);
+        touches.update(TouchPhase::Begin, 2, 100.0, 0.0, 1.0);
+
+        let (_, gesture) = touches.update(TouchPhase::Move, 2, 200.0, 0.0, 1.0);
+        assert_eq!(
+            gesture,
+            Some(TransformGesture::Zoom {
+                phase: GesturePhase::Begin,
+                x: 100.0,
+                y: 0.0,
+                scale: 2.0,
+            })
+        );
+
+        let (_, gesture) = touches.update(TouchPhase::Move, 1, 100.0, 0.0, 1.0);
+        assert_eq!(
+            gesture,
+            Some(TransformGesture::Zoom {
+                phase: GesturePhase::Update,
+                x: 150.0,
+                y: 0.0,
+                scale: 0.5,
+            })
+        );
+
+        let (_, gesture) = touches.update(TouchPhase::End, 1, 100.0, 0.0, 0.0);
+        assert_eq!(
+            gesture,
+            Some(TransformGesture::Zoom {
+                phase: GesturePhase::End,
+                x: 150.0,
+                y: 0.0,
+                scale: 1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn pan_gesture() {
+        let mut touches = TouchPoints::default();
+        touches.update(TouchPhase::Begin, 1, 0.0, 0.0, 1.0);
+        touches.update(TouchPhase::Begin, 2, 100.0, 0.0, 1.0);
+
+        // Too small a movement to be a gesture yet.
+        let (_, gesture) = touches.update(TouchPhase::Move, 1, 0.0, 4.0, 1.0);
+        assert_eq!(gesture, None);
+
+        let (_, gesture) = touches.update(TouchPhase::Move, 2, 100.0, 24.0, 1.0);
+        match gesture {
+            Some(TransformGesture::Pan {
+                phase: GesturePhase::Begin,
+                offset_x,
+                offset_y,
+                ..
+            }) => {
+                assert_eq!(offset_x, 0.0);
+                assert_eq!(offset_y, 14.0);
+            }
+            gesture => panic!("Expected a pan to begin, got {:?}", gesture),
+        }
+
+        let (_, gesture) = touches.update(TouchPhase::End, 2, 100.0, 24.0, 0.0);
+        assert!(matches!(
+            gesture,
+            Some(TransformGesture::Pan {
+                phase: GesturePhase::End,
+                ..
+            })
+        ));
+    }
+}
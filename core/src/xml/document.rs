@@ -249,14 +249,19 @@ impl<'gc> XMLDocument<'gc> {
     ) -> Result<O, ParseError> {
         match maybe_error {
             Ok(v) => Ok(v),
-            Err(e) => {
-                let new_error = ParseError::from_quickxml_error(e);
+            Err(e) => Err(self.log_parse_error(gc_context, ParseError::from_quickxml_error(e))),
+        }
+    }
 
-                self.0.write(gc_context).last_parse_error = Some(new_error.clone());
+    /// Save a parse error for later inspection.
+    pub fn log_parse_error(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        error: ParseError,
+    ) -> ParseError {
+        self.0.write(gc_context).last_parse_error = Some(error.clone());
 
-                Err(new_error)
-            }
-        }
+        error
     }
 
     /// Get the last parse error within this document, if any.
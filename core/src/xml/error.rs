@@ -42,6 +42,9 @@ pub enum Error {
     #[error("Cannot insert child into itself")]
     CannotInsertIntoSelf,
 
+    #[error("Cannot insert node into its own descendant")]
+    CannotInsertIntoDescendant,
+
     #[error("Not an element")]
     NotAnElement,
 
@@ -72,13 +75,22 @@ impl From<QXError> for Error {
     }
 }
 
-/// Boxed `quick_xml` error
+/// An error encountered while parsing an XML document.
 ///
 /// We can't clone `quick_xml` errors, nor can we clone several of the error
-/// types it wraps over, so this creates an RC boxed version of the error that
+/// types it wraps over, so errors from the reader are RC boxed so that they
 /// can then be used elsewhere.
 #[derive(Clone, Debug)]
-pub struct ParseError(Rc<QXError>);
+pub enum ParseError {
+    /// The reader rejected the document.
+    Reader(Rc<QXError>),
+
+    /// The document ended while the named element was still open.
+    UnclosedElement(String),
+
+    /// The named end tag was found without a matching start tag.
+    UnmatchedEndTag(String),
+}
 
 unsafe impl Collect for ParseError {
     /// ParseError does not contain GC pointers.
@@ -88,27 +100,35 @@ unsafe impl Collect for ParseError {
 impl ParseError {
     ///Convert a quick_xml error into a `ParseError`.
     pub fn from_quickxml_error(err: QXError) -> Self {
-        ParseError(Rc::new(err))
-    }
-
-    pub fn ref_error(&self) -> &QXError {
-        &*self.0
+        ParseError::Reader(Rc::new(err))
     }
 }
 
 impl Display for ParseError {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), FmtError> {
-        self.0.fmt(fmt)
+        match self {
+            ParseError::Reader(err) => err.fmt(fmt),
+            ParseError::UnclosedElement(name) => write!(fmt, "Element <{}> was never closed", name),
+            ParseError::UnmatchedEndTag(name) => {
+                write!(fmt, "End tag </{}> has no matching start tag", name)
+            }
+        }
     }
 }
 
 impl StdError for ParseError {
     #[allow(deprecated)]
     fn cause(&self) -> Option<&dyn StdError> {
-        self.0.cause()
+        match self {
+            ParseError::Reader(err) => err.cause(),
+            _ => None,
+        }
     }
 
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
-        self.0.source()
+        match self {
+            ParseError::Reader(err) => err.source(),
+            _ => None,
+        }
     }
 }
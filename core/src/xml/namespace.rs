@@ -12,7 +12,7 @@ use std::fmt;
 ///
 /// The special namespace `xmlns` is used to map namespace strings to URIs; it
 /// should not be used for user-specified namespaces.
-#[derive(Clone, Collect, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Collect, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[collect(no_drop)]
 pub struct XMLName {
     /// The name of the XML namespace this name is scoped to.
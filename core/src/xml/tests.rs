@@ -1,7 +1,7 @@
 //! XML tests
 
 use crate::xml;
-use crate::xml::{XMLDocument, XMLName};
+use crate::xml::{ParseError, XMLDocument, XMLName};
 use gc_arena::rootless_arena;
 
 /// Tests very basic parsing of a single-element document.
@@ -10,7 +10,7 @@ fn parse_single_element() {
     rootless_arena(|mc| {
         let xml = XMLDocument::new(mc);
         xml.as_node()
-            .replace_with_str(mc, "<test></test>", true)
+            .replace_with_str(mc, "<test></test>", true, false)
             .expect("Parsed document");
         let mut roots = xml
             .as_node()
@@ -38,6 +38,7 @@ fn double_ended_children() {
                 mc,
                 "<test></test><test2></test2><test3></test3><test4></test4><test5></test5>",
                 true,
+                false,
             )
             .expect("Parsed document");
 
@@ -82,6 +83,7 @@ fn walk() {
                 mc,
                 "<test><test2></test2></test><test3>test</test3><test4><test5></test5></test4>",
                 true,
+                false,
             )
             .expect("Parsed document");
 
@@ -163,7 +165,7 @@ fn round_trip_tostring() {
     rootless_arena(|mc| {
         let xml = XMLDocument::new(mc);
         xml.as_node()
-            .replace_with_str(mc, test_string, true)
+            .replace_with_str(mc, test_string, true, false)
             .expect("Parsed document");
 
         let result = xml
@@ -183,7 +185,7 @@ fn round_trip_filtered_tostring() {
     rootless_arena(|mc| {
         let xml = XMLDocument::new(mc);
         xml.as_node()
-            .replace_with_str(mc, test_string, true)
+            .replace_with_str(mc, test_string, true, false)
             .expect("Parsed document");
 
         let result = xml
@@ -194,3 +196,120 @@ fn round_trip_filtered_tostring() {
         assert_eq!("<test>This is a text node</test>", result);
     })
 }
+
+/// Tests that whitespace-only text nodes are only dropped with `ignore_white`.
+#[test]
+fn ignore_white() {
+    let test_string = "<test>\n\t<test2> text </test2>\r\n</test>";
+
+    rootless_arena(|mc| {
+        let xml = XMLDocument::new(mc);
+        xml.as_node()
+            .replace_with_str(mc, test_string, true, false)
+            .expect("Parsed document");
+
+        let result = xml
+            .as_node()
+            .into_string(&mut |_| true)
+            .expect("Successful toString");
+
+        assert_eq!(test_string, result);
+
+        let xml = XMLDocument::new(mc);
+        xml.as_node()
+            .replace_with_str(mc, test_string, true, true)
+            .expect("Parsed document");
+
+        let result = xml
+            .as_node()
+            .into_string(&mut |_| true)
+            .expect("Successful toString");
+
+        assert_eq!("<test><test2> text </test2></test>", result);
+    })
+}
+
+/// Tests that unclosed elements and unmatched end tags are reported.
+#[test]
+fn unbalanced_tags() {
+    rootless_arena(|mc| {
+        let xml = XMLDocument::new(mc);
+        assert!(xml
+            .as_node()
+            .replace_with_str(mc, "<test><test2></test2>", true, false)
+            .is_err());
+        assert!(matches!(
+            xml.last_parse_error(),
+            Some(ParseError::UnclosedElement(name)) if name == "test"
+        ));
+
+        // Parsing again clears the previous error.
+        let xml = XMLDocument::new(mc);
+        xml.as_node()
+            .replace_with_str(mc, "<test></test>", true, false)
+            .expect("Parsed document");
+        assert!(xml.last_parse_error().is_none());
+
+        let xml = XMLDocument::new(mc);
+        assert!(xml
+            .as_node()
+            .replace_with_str(mc, "<test></test></test2>", true, false)
+            .is_err());
+        assert!(xml.last_parse_error().is_some());
+    })
+}
+
+/// Tests that attributes keep the order they were defined in, and are
+/// printed in that order.
+#[test]
+fn attribute_order() {
+    let test_string = "<test z=\"1\" a=\"2\" m=\"3\" />";
+
+    rootless_arena(|mc| {
+        let xml = XMLDocument::new(mc);
+        xml.as_node()
+            .replace_with_str(mc, test_string, true, false)
+            .expect("Parsed document");
+
+        let root = xml.as_node().get_child_by_index(0).expect("Root element");
+        assert_eq!(root.attribute_keys(), vec!["z", "a", "m"]);
+
+        root.delete_attribute(mc, &XMLName::from_str("z"));
+        root.set_attribute_value(mc, &XMLName::from_str("b"), "4");
+        assert_eq!(root.attribute_keys(), vec!["a", "m", "b"]);
+        assert_eq!(
+            xml.as_node()
+                .into_string(&mut |_| true)
+                .expect("Successful toString"),
+            "<test a=\"2\" m=\"3\" b=\"4\" />"
+        );
+    })
+}
+
+/// Tests that deeply nested documents can be parsed, printed and cloned.
+#[test]
+fn deep_nesting() {
+    let depth = 50_000;
+    let test_string = format!("{}{}", "<a>".repeat(depth), "</a>".repeat(depth));
+
+    rootless_arena(|mc| {
+        let xml = XMLDocument::new(mc);
+        xml.as_node()
+            .replace_with_str(mc, &test_string, true, false)
+            .expect("Parsed document");
+
+        let clone = xml.as_node().duplicate(mc, true);
+        let result = clone
+            .into_string(&mut |_| true)
+            .expect("Successful toString");
+
+        assert_eq!(
+            format!(
+                "{}<a />{}",
+                "<a>".repeat(depth - 1),
+                "</a>".repeat(depth - 1)
+            ),
+            result
+        );
+    })
+}
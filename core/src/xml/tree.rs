@@ -4,16 +4,36 @@ use crate::avm1::object::xml_attributes_object::XMLAttributesObject;
 use crate::avm1::object::xml_object::XMLObject;
 use crate::avm1::{Object, TObject};
 use crate::xml;
-use crate::xml::{Error, Step, XMLDocument, XMLName};
+use crate::xml::{Error, ParseError, Step, XMLDocument, XMLName};
 use gc_arena::{Collect, GcCell, MutationContext};
+use indexmap::IndexMap;
 use quick_xml::events::attributes::Attribute;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Reader, Writer};
 use smallvec::alloc::borrow::Cow;
-use std::collections::BTreeMap;
 use std::fmt;
 use std::io::{Cursor, Write};
 use std::mem::swap;
+use std::ops::{Deref, DerefMut};
+
+/// The attributes of an element, in the order they were defined.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct XMLAttributes(IndexMap<XMLName, String>);
+
+impl Deref for XMLAttributes {
+    type Target = IndexMap<XMLName, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for XMLAttributes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
 
 /// Represents a node in the XML tree.
 #[derive(Copy, Clone, Collect)]
@@ -66,7 +86,7 @@ pub enum XMLNodeData<'gc> {
         tag_name: XMLName,
 
         /// Attributes of the element.
-        attributes: BTreeMap<XMLName, String>,
+        attributes: XMLAttributes,
 
         /// Child nodes of this element.
         children: Vec<XMLNode<'gc>>,
@@ -175,7 +195,7 @@ impl<'gc> XMLNode<'gc> {
                 prev_sibling: None,
                 next_sibling: None,
                 tag_name: XMLName::from_str(element_name),
-                attributes: BTreeMap::new(),
+                attributes: XMLAttributes::default(),
                 attributes_script_object: None,
                 children: Vec::new(),
             },
@@ -212,6 +232,28 @@ impl<'gc> XMLNode<'gc> {
         Ok(())
     }
 
+    /// Add a newly-encountered text node to an ongoing parsing stack, unless
+    /// it is empty, or consists entirely of whitespace and `ignore_white` is
+    /// set.
+    fn add_text_to_tree(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        open_tags: &mut Vec<XMLNode<'gc>>,
+        child: XMLNode<'gc>,
+        ignore_white: bool,
+    ) -> Result<(), Error> {
+        let contents = child.node_value().unwrap_or_default();
+        let is_white = contents
+            .chars()
+            .all(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+
+        if contents.is_empty() || (ignore_white && is_white) {
+            return Ok(());
+        }
+
+        self.add_child_to_tree(mc, open_tags, child)
+    }
+
     /// Replace the contents of this node with the result of parsing a string.
     ///
     /// Node replacements are only supported on document root nodes; elements
@@ -222,11 +264,18 @@ impl<'gc> XMLNode<'gc> {
     /// If `process_entity` is `true`, then entities will be processed by this
     /// function. Invalid or unrecognized entities will cause parsing to fail
     /// with an `Err`.
+    ///
+    /// If `ignore_white` is `true`, then text nodes consisting entirely of
+    /// whitespace will be discarded.
+    ///
+    /// Parsing stops at the first error. Any nodes parsed before that point
+    /// remain in the tree, and the error is saved on the document.
     pub fn replace_with_str(
         &mut self,
         mc: MutationContext<'gc, '_>,
         data: &str,
         process_entity: bool,
+        ignore_white: bool,
     ) -> Result<(), Error> {
         let mut parser = Reader::from_str(data);
         let mut buf = Vec::new();
@@ -252,14 +301,21 @@ impl<'gc> XMLNode<'gc> {
                     self.document().update_idmap(mc, child);
                     self.add_child_to_tree(mc, &mut open_tags, child)?;
                 }
-                Event::End(_) => {
-                    open_tags.pop();
+                Event::End(be) => {
+                    if open_tags.pop().is_none() {
+                        let name = String::from_utf8_lossy(be.name()).into_owned();
+                        return Err(document
+                            .log_parse_error(mc, ParseError::UnmatchedEndTag(name))
+                            .into());
+                    }
                 }
                 Event::Text(bt) => {
                     let child = XMLNode::text_from_text_event(mc, bt, document, process_entity)?;
-                    if child.node_value().as_deref() != Some("") {
-                        self.add_child_to_tree(mc, &mut open_tags, child)?;
-                    }
+                    self.add_text_to_tree(mc, &mut open_tags, child, ignore_white)?;
+                }
+                Event::CData(bt) => {
+                    let child = XMLNode::text_from_text_event(mc, bt, document, false)?;
+                    self.add_text_to_tree(mc, &mut open_tags, child, ignore_white)?;
                 }
                 Event::Comment(bt) => {
                     let child = XMLNode::comment_from_text_event(mc, bt, document)?;
@@ -273,7 +329,19 @@ impl<'gc> XMLNode<'gc> {
                         self.add_child_to_tree(mc, &mut open_tags, child)?;
                     }
                 }
-                Event::Eof => break,
+                Event::Eof => {
+                    if let Some(unclosed) = open_tags.last() {
+                        let name = unclosed
+                            .tag_name()
+                            .map(|name| name.node_name().into_owned())
+                            .unwrap_or_default();
+                        return Err(document
+                            .log_parse_error(mc, ParseError::UnclosedElement(name))
+                            .into());
+                    }
+
+                    break;
+                }
                 _ => {}
             }
         }
@@ -291,7 +359,7 @@ impl<'gc> XMLNode<'gc> {
         document: XMLDocument<'gc>,
     ) -> Result<Self, Error> {
         let tag_name = XMLName::from_bytes(bs.name())?;
-        let mut attributes = BTreeMap::new();
+        let mut attributes = XMLAttributes::default();
 
         for a in bs.attributes() {
             let attribute = a?;
@@ -662,6 +730,16 @@ impl<'gc> XMLNode<'gc> {
             return Err(Error::CannotInsertIntoSelf);
         }
 
+        // Only nodes with children can be our ancestors. Skipping the check
+        // otherwise keeps parsing and cloning linear in the depth of the tree.
+        if child.children_len() > 0 {
+            if let Some(mut ancestors) = self.ancestors() {
+                if ancestors.any(|ancestor| GcCell::ptr_eq(ancestor.0, child.0)) {
+                    return Err(Error::CannotInsertIntoDescendant);
+                }
+            }
+        }
+
         match &mut *self.0.write(mc) {
             XMLNodeData::Element {
                 ref mut children, ..
@@ -793,7 +871,10 @@ impl<'gc> XMLNode<'gc> {
     /// Document roots and elements can yield children, while all other
     /// elements are structurally prohibited from adopting child `XMLNode`s.
     pub fn has_children(self) -> bool {
-        matches!(*self.0.read(), XMLNodeData::Element { .. } | XMLNodeData::DocumentRoot { .. })
+        matches!(
+            *self.0.read(),
+            XMLNodeData::Element { .. } | XMLNodeData::DocumentRoot { .. }
+        )
     }
 
     /// Returns an iterator that yields child nodes.
@@ -932,6 +1013,11 @@ impl<'gc> XMLNode<'gc> {
         }
     }
 
+    /// Check if two handles refer to the same node.
+    pub fn ptr_eq(a: XMLNode<'gc>, b: XMLNode<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     /// Check if this XML node constitutes the root of a whole document.
     pub fn is_document_root(self) -> bool {
         matches!(*self.0.read(), XMLNodeData::DocumentRoot { .. })
@@ -948,7 +1034,6 @@ impl<'gc> XMLNode<'gc> {
     }
 
     /// Check if this XML node constitutes text.
-    #[allow(dead_code)]
     pub fn is_comment(self) -> bool {
         matches!(*self.0.read(), XMLNodeData::Comment { .. })
     }
@@ -961,10 +1046,33 @@ impl<'gc> XMLNode<'gc> {
     /// Create a duplicate copy of this node.
     ///
     /// If the `deep` flag is set true, then the entire node tree will be
+    /// cloned. Trees are walked iteratively, so arbitrarily deep trees can be
     /// cloned.
     pub fn duplicate(self, gc_context: MutationContext<'gc, '_>, deep: bool) -> XMLNode<'gc> {
+        let clone = self.duplicate_node(gc_context);
+
+        if deep {
+            let mut pending = vec![(self, clone)];
+            while let Some((original, mut copy)) = pending.pop() {
+                if let Some(children) = original.children() {
+                    for (position, child) in children.enumerate() {
+                        let child_copy = child.duplicate_node(gc_context);
+                        copy.insert_child(gc_context, position, child_copy).expect(
+                            "If I can see my children then my clone should accept children",
+                        );
+                        pending.push((child, child_copy));
+                    }
+                }
+            }
+        }
+
+        clone
+    }
+
+    /// Create a duplicate copy of this node, without any of its children.
+    fn duplicate_node(self, gc_context: MutationContext<'gc, '_>) -> XMLNode<'gc> {
         let mut document = self.document().duplicate(gc_context);
-        let mut clone = XMLNode(GcCell::allocate(
+        let clone = XMLNode(GcCell::allocate(
             gc_context,
             match &*self.0.read() {
                 XMLNodeData::DocumentRoot { .. } => XMLNodeData::DocumentRoot {
@@ -1018,16 +1126,6 @@ impl<'gc> XMLNode<'gc> {
 
         document.link_root_node(gc_context, clone);
 
-        if deep {
-            if let Some(children) = self.children() {
-                for (position, child) in children.enumerate() {
-                    clone
-                        .insert_child(gc_context, position, child.duplicate(gc_context, deep))
-                        .expect("If I can see my children then my clone should accept children");
-                }
-            }
-        }
-
         clone
     }
 
@@ -1085,7 +1183,7 @@ impl<'gc> XMLNode<'gc> {
     /// If the node does not contain attributes, then this function silently fails.
     pub fn delete_attribute(self, gc_context: MutationContext<'gc, '_>, name: &XMLName) {
         if let XMLNodeData::Element { attributes, .. } = &mut *self.0.write(gc_context) {
-            attributes.remove(name);
+            attributes.shift_remove(name);
         }
     }
 
@@ -1165,30 +1263,43 @@ impl<'gc> XMLNode<'gc> {
     /// resulting string. It will be called at least once for each node
     /// encountered in the tree (other than this one) if specified; only nodes
     /// that yield `true` shall be printed.
+    ///
+    /// Trees are walked iteratively, so arbitrarily deep trees can be
+    /// printed.
     pub fn into_string<F>(self, filter: &mut F) -> Result<String, Error>
     where
         F: FnMut(XMLNode<'gc>) -> bool,
     {
         let mut buf = Vec::new();
         let mut writer = Writer::new(Cursor::new(&mut buf));
-        self.write_node_to_event_writer(&mut writer, filter)?;
+
+        let children = self.filtered_children(filter);
+        let has_children = !children.is_empty();
+        self.write_start_to_event_writer(&mut writer, has_children)?;
+
+        let mut open_nodes = vec![(self, has_children, children.into_iter())];
+        loop {
+            let next_child = match open_nodes.last_mut() {
+                Some((_, _, children)) => children.next(),
+                None => break,
+            };
+
+            if let Some(child) = next_child {
+                let children = child.filtered_children(filter);
+                let has_children = !children.is_empty();
+                child.write_start_to_event_writer(&mut writer, has_children)?;
+                open_nodes.push((child, has_children, children.into_iter()));
+            } else if let Some((node, has_children, _)) = open_nodes.pop() {
+                node.write_end_to_event_writer(&mut writer, has_children)?;
+            }
+        }
+
         Ok(String::from_utf8(buf)?)
     }
 
-    /// Write the contents of this node, including it's children, to the given
-    /// writer.
-    ///
-    /// The given filter function allows filtering specific children out of the
-    /// resulting write stream. It will be called at least once for each node
-    /// encountered in the tree (other than this one) if specified; only nodes
-    /// that yield `true` shall be printed.
-    fn write_node_to_event_writer<W, F>(
-        self,
-        writer: &mut Writer<W>,
-        filter: &mut F,
-    ) -> Result<(), Error>
+    /// List the children of this node that pass the given filter function.
+    fn filtered_children<F>(self, filter: &mut F) -> Vec<XMLNode<'gc>>
     where
-        W: Write,
         F: FnMut(XMLNode<'gc>) -> bool,
     {
         let mut children = Vec::new();
@@ -1200,8 +1311,21 @@ impl<'gc> XMLNode<'gc> {
             }
         }
 
-        let children_len = children.len();
+        children
+    }
 
+    /// Write the start of this node to the given writer.
+    ///
+    /// Elements without children are written as empty elements, and have no
+    /// end.
+    fn write_start_to_event_writer<W>(
+        self,
+        writer: &mut Writer<W>,
+        has_children: bool,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
         match &*self.0.read() {
             XMLNodeData::DocumentRoot { .. } => Ok(0),
             XMLNodeData::Element {
@@ -1209,13 +1333,9 @@ impl<'gc> XMLNode<'gc> {
                 attributes,
                 ..
             } => {
-                let mut bs = if children_len > 0 {
-                    match tag_name.node_name() {
-                        Cow::Borrowed(name) => BytesStart::borrowed_name(name.as_bytes()),
-                        Cow::Owned(name) => BytesStart::owned_name(name),
-                    }
-                } else {
-                    BytesStart::owned_name(format!("{} ", tag_name.node_name()))
+                let mut bs = match tag_name.node_name() {
+                    Cow::Borrowed(name) => BytesStart::borrowed_name(name.as_bytes()),
+                    Cow::Owned(name) => BytesStart::owned_name(name),
                 };
                 let key_values: Vec<(Cow<str>, &str)> = attributes
                     .iter()
@@ -1228,10 +1348,15 @@ impl<'gc> XMLNode<'gc> {
                         .map(|(name, value)| Attribute::from((name.as_ref(), *value))),
                 );
 
-                if children_len > 0 {
+                if has_children {
                     writer.write_event(&Event::Start(bs))
                 } else {
-                    writer.write_event(&Event::Empty(bs))
+                    // Flash writes empty elements as `<foo a="1" />`, with a
+                    // space after the attributes.
+                    let name_len = bs.name().len();
+                    let mut content = bs.to_vec();
+                    content.push(b' ');
+                    writer.write_event(&Event::Empty(BytesStart::owned(content, name_len)))
                 }
             }
             XMLNodeData::Text { contents, .. } => {
@@ -1245,26 +1370,27 @@ impl<'gc> XMLNode<'gc> {
             )),
         }?;
 
-        for child in children {
-            child.write_node_to_event_writer(writer, filter)?;
-        }
+        Ok(())
+    }
 
+    /// Write the end of this node to the given writer.
+    fn write_end_to_event_writer<W>(
+        self,
+        writer: &mut Writer<W>,
+        has_children: bool,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+    {
         match &*self.0.read() {
-            XMLNodeData::DocumentRoot { .. } => Ok(0),
-            XMLNodeData::Element { tag_name, .. } => {
-                if children_len > 0 {
-                    let bs = match tag_name.node_name() {
-                        Cow::Borrowed(name) => BytesEnd::borrowed(name.as_bytes()),
-                        Cow::Owned(name) => BytesEnd::owned(name.into()),
-                    };
-                    writer.write_event(&Event::End(bs))
-                } else {
-                    Ok(0)
-                }
+            XMLNodeData::Element { tag_name, .. } if has_children => {
+                let bs = match tag_name.node_name() {
+                    Cow::Borrowed(name) => BytesEnd::borrowed(name.as_bytes()),
+                    Cow::Owned(name) => BytesEnd::owned(name.into()),
+                };
+                writer.write_event(&Event::End(bs))
             }
-            XMLNodeData::Text { .. } => Ok(0),
-            XMLNodeData::Comment { .. } => Ok(0),
-            XMLNodeData::DocType { .. } => Ok(0),
+            _ => Ok(0),
         }?;
 
         Ok(())
@@ -4,17 +4,20 @@
 
 use approx::assert_abs_diff_eq;
 use log::{Metadata, Record};
+use ruffle_core::backend::font::NullFontProvider;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::video::NullVideoBackend;
 use ruffle_core::backend::{
     audio::NullAudioBackend, input::NullInputBackend, render::NullRenderer,
 };
 use ruffle_core::context::UpdateContext;
+use ruffle_core::events::{KeyCode, KeyLocation};
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{BackgroundThrottle, Player, PlayerEvent, StageScaleMode};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -70,7 +73,9 @@ swf_tests! {
     (as_transformed_flag, "avm1/as_transformed_flag", 3),
     (as_broadcaster, "avm1/as_broadcaster", 1),
     (as_broadcaster_initialize, "avm1/as_broadcaster_initialize", 1),
+    (as_broadcaster_snapshot, "avm1/as_broadcaster_snapshot", 1),
     (attach_movie, "avm1/attach_movie", 1),
+    (attach_movie_order, "avm1/attach_movie_order", 1),
     (function_base_clip, "avm1/function_base_clip", 2),
     (call, "avm1/call", 2),
     (color, "avm1/color", 1),
@@ -154,6 +159,7 @@ swf_tests! {
     (equals2_swf7, "avm1/equals2_swf7", 1),
     (register_class, "avm1/register_class", 1),
     (register_and_init_order, "avm1/register_and_init_order", 1),
+    (register_class_timeline, "avm1/register_class_timeline", 2),
     (on_construct, "avm1/on_construct", 1),
     (set_variable_scope, "avm1/set_variable_scope", 1),
     (slash_syntax, "avm1/slash_syntax", 2),
@@ -185,7 +191,9 @@ swf_tests! {
     #[ignore] (xml_inspect_xmldecl, "avm1/xml_inspect_xmldecl", 1),
     (xml_inspect_createmethods, "avm1/xml_inspect_createmethods", 1),
     (xml_inspect_parsexml, "avm1/xml_inspect_parsexml", 1),
+    (xml_ignore_white_status, "avm1/xml_ignore_white_status", 1),
     (funky_function_calls, "avm1/funky_function_calls", 1),
+    (function_arguments_caller, "avm1/function_arguments_caller", 1),
     (undefined_to_string_swf6, "avm1/undefined_to_string_swf6", 1),
     (define_function2_preload, "avm1/define_function2_preload", 1),
     (define_function2_preload_order, "avm1/define_function2_preload_order", 1),
@@ -203,7 +211,9 @@ swf_tests! {
     (unloadmovie_method, "avm1/unloadmovie_method", 11),
     (mcl_loadclip, "avm1/mcl_loadclip", 11),
     (mcl_unloadclip, "avm1/mcl_unloadclip", 11),
+    (unload_stops_timers, "avm1/unload_stops_timers", 10),
     (mcl_getprogress, "avm1/mcl_getprogress", 6),
+    (mcl_events, "avm1/mcl_events", 20),
     (load_vars, "avm1/load_vars", 2),
     (loadvariables, "avm1/loadvariables", 3),
     (loadvariablesnum, "avm1/loadvariablesnum", 3),
@@ -216,6 +226,11 @@ swf_tests! {
     (roots_and_levels, "avm1/roots_and_levels", 1),
     (swf6_case_insensitive, "avm1/swf6_case_insensitive", 1),
     (swf7_case_sensitive, "avm1/swf7_case_sensitive", 1),
+    (case_sensitivity_per_movie, "avm1/case_sensitivity_per_movie", 2),
+    (stage_quality, "avm1/stage_quality", 1),
+    (system_calls, "avm1/system_calls", 1),
+    (selection_text_field, "avm1/selection_text_field", 1),
+    (scale9_grid, "avm1/scale9_grid", 1),
     (prototype_enumerate, "avm1/prototype_enumerate", 1),
     (stage_object_enumerate, "avm1/stage_object_enumerate", 1),
     (new_object_enumerate, "avm1/new_object_enumerate", 1),
@@ -232,6 +247,7 @@ swf_tests! {
     (infinite_recursion_function_in_setter, "avm1/infinite_recursion_function_in_setter", 1),
     (infinite_recursion_virtual_property, "avm1/infinite_recursion_virtual_property", 1),
     (edittext_font_size, "avm1/edittext_font_size", 1),
+    (define_font_4, "avm1/define_font_4", 1),
     (edittext_default_format, "avm1/edittext_default_format", 1),
     (edittext_leading, "avm1/edittext_leading", 1),
     #[ignore] (edittext_newlines, "avm1/edittext_newlines", 1),
@@ -241,11 +257,13 @@ swf_tests! {
     (textfield_variable, "avm1/textfield_variable", 8),
     (error, "avm1/error", 1),
     (color_transform, "avm1/color_transform", 1),
+    (color_reentrant, "avm1/color_reentrant", 1),
     (with, "avm1/with", 1),
     (arguments, "avm1/arguments", 1),
     (prototype_properties, "avm1/prototype_properties", 1),
     (stage_object_properties_get_var, "avm1/stage_object_properties_get_var", 1),
     (set_interval, "avm1/set_interval", 20),
+    (set_interval_ids, "avm1/set_interval_ids", 6),
     (context_menu, "avm1/context_menu", 1),
     (context_menu_item, "avm1/context_menu_item", 1),
     (constructor_function, "avm1/constructor_function", 1),
@@ -327,7 +345,16 @@ swf_tests! {
     (as3_greaterthan, "avm2/greaterthan", 1),
     (as3_lessequals, "avm2/lessequals", 1),
     (as3_lessthan, "avm2/lessthan", 1),
+    (as3_dictionary, "avm2/dictionary", 1),
+    (as3_e4x, "avm2/e4x", 1),
+    (as3_json_arrays, "avm2/json_arrays", 1),
+    (as3_event_phases, "avm2/event_phases", 1),
+    (as3_proxy, "avm2/proxy", 1),
+    (as3_qualified_class_name, "avm2/qualified_class_name", 1),
+    (as3_scenes, "avm2/scenes", 6),
+    (as3_timer, "avm2/timer", 10),
     (nested_textfields_in_buttons, "avm1/nested_textfields_in_buttons", 1),
+    (netstream, "avm1/netstream", 4),
     (conflicting_instance_names, "avm1/conflicting_instance_names", 6),
     (button_children, "avm1/button_children", 1),
     (transform, "avm1/transform", 1),
@@ -340,6 +367,8 @@ swf_tests! {
     (as3_declocal_i, "avm2/declocal_i", 1),
     (as3_decrement, "avm2/decrement", 1),
     (as3_decrement_i, "avm2/decrement_i", 1),
+    (as3_domain_memory, "avm2/domain_memory", 1),
+    (as3_geom_classes, "avm2/geom_classes", 1),
     (as3_inclocal, "avm2/inclocal", 1),
     (as3_inclocal_i, "avm2/inclocal_i", 1),
     (as3_increment, "avm2/increment", 1),
@@ -351,6 +380,8 @@ swf_tests! {
     (as3_rshift, "avm2/rshift", 1),
     (as3_subtract, "avm2/subtract", 1),
     (as3_urshift, "avm2/urshift", 1),
+    (as3_worker_fallback, "avm2/worker_fallback", 1),
+    (as3_do_abc_at_end, "avm2/do_abc_at_end", 1),
 }
 
 // TODO: These tests have some inaccuracies currently, so we use approx_eq to test that numeric values are close enough.
@@ -414,6 +445,265 @@ fn external_interface_avm1() -> Result<(), Error> {
     )
 }
 
+#[test]
+fn stage_resize_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/stage_resize/test.swf",
+        1,
+        "tests/swfs/avm1/stage_resize/output.txt",
+        |player| {
+            player.lock().unwrap().set_viewport_dimensions(800, 600);
+            Ok(())
+        },
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+
+            log::info!(target: "avm_trace", "// set_viewport_dimensions(1024, 768)");
+            player_locked.set_viewport_dimensions(1024, 768);
+            log::info!(target: "avm_trace", "// set_viewport_dimensions(1024, 768)");
+            player_locked.set_viewport_dimensions(1024, 768);
+            log::info!(target: "avm_trace", "// set_scale_mode(ShowAll)");
+            player_locked.set_scale_mode(StageScaleMode::ShowAll);
+            log::info!(target: "avm_trace", "// set_viewport_dimensions(640, 480)");
+            player_locked.set_viewport_dimensions(640, 480);
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn text_input_restrict_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/text_input_restrict/test.swf",
+        1,
+        "tests/swfs/avm1/text_input_restrict/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player_locked = player.lock().unwrap();
+
+            for text in &["a1b2345", "aqZ!b"] {
+                log::info!(target: "avm_trace", "// Typing {:?}", text);
+                for codepoint in text.chars() {
+                    player_locked.handle_event(PlayerEvent::TextInput { codepoint });
+                }
+            }
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn key_latch_avm1() -> Result<(), Error> {
+    fn key_down(key_code: KeyCode, key_char: Option<char>) -> PlayerEvent {
+        PlayerEvent::KeyDown {
+            key_code,
+            key_char,
+            key_location: KeyLocation::Standard,
+        }
+    }
+
+    fn key_up(key_code: KeyCode, key_char: Option<char>) -> PlayerEvent {
+        PlayerEvent::KeyUp {
+            key_code,
+            key_char,
+            key_location: KeyLocation::Standard,
+        }
+    }
+
+    test_swf(
+        "tests/swfs/avm1/key_latch/test.swf",
+        1,
+        "tests/swfs/avm1/key_latch/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+
+            log::info!(target: "avm_trace", "// Tapping LEFT between frames");
+            player.handle_event(key_down(KeyCode::Left, None));
+            player.handle_event(key_up(KeyCode::Left, None));
+            player.run_frame();
+            player.run_frame();
+
+            log::info!(target: "avm_trace", "// Holding RIGHT");
+            player.handle_event(key_down(KeyCode::Right, None));
+            player.run_frame();
+            player.handle_event(key_down(KeyCode::Right, None));
+            player.run_frame();
+            player.handle_event(key_up(KeyCode::Right, None));
+            player.run_frame();
+            player.run_frame();
+
+            log::info!(target: "avm_trace", "// Typing a");
+            player.handle_event(key_down(KeyCode::A, Some('a')));
+            player.handle_event(key_up(KeyCode::A, Some('a')));
+
+            log::info!(target: "avm_trace", "// Pressing CAPS LOCK");
+            player.handle_event(key_down(KeyCode::CapsLock, None));
+            player.handle_event(key_up(KeyCode::CapsLock, None));
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn background_throttle_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/background_throttle/test.swf",
+        1,
+        "tests/swfs/avm1/background_throttle/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.set_is_playing(true);
+
+            // A hidden player runs at 8 FPS, and its timers don't catch up.
+            log::info!(target: "avm_trace", "// Hidden for 1 second");
+            player.set_background_throttle(BackgroundThrottle::Hidden);
+            for _ in 0..8 {
+                player.tick(125.0);
+            }
+
+            log::info!(target: "avm_trace", "// Visible for 1/3 of a second");
+            player.set_background_throttle(BackgroundThrottle::Visible);
+            for _ in 0..10 {
+                player.tick(1000.0 / 30.0);
+            }
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn frame_rate_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/frame_rate/test.swf",
+        1,
+        "tests/swfs/avm1/frame_rate/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.set_is_playing(true);
+
+            log::info!(target: "avm_trace", "// 5 FPS for 1 second");
+            player.set_frame_rate(5.0);
+            assert_eq!(player.frame_rate(), 5.0);
+            for _ in 0..50 {
+                player.tick(20.0);
+            }
+
+            log::info!(target: "avm_trace", "// 60 FPS for 1/4 of a second");
+            player.set_frame_rate(60.0);
+            for _ in 0..15 {
+                player.tick(1000.0 / 60.0);
+            }
+
+            // Frame rates outside of Flash Player's range are clamped.
+            player.set_frame_rate(0.0);
+            assert_eq!(player.frame_rate(), 0.01);
+            player.set_frame_rate(5000.0);
+            assert_eq!(player.frame_rate(), 1000.0);
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn text_link_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/text_link/test.swf",
+        1,
+        "tests/swfs/avm1/text_link/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+
+            for (line, y) in &[("first", 8.0), ("second", 22.0), ("third", 36.0)] {
+                log::info!(target: "avm_trace", "// Clicking the {} line", line);
+                player.handle_event(PlayerEvent::MouseMove { x: 10.0, y: *y });
+                player.handle_event(PlayerEvent::MouseDown { x: 10.0, y: *y });
+                player.handle_event(PlayerEvent::MouseUp { x: 10.0, y: *y });
+            }
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn shared_object_export_avm1() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm1/shared_object_export/test.swf",
+        1,
+        "tests/swfs/avm1/shared_object_export/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+
+            let sol = player
+                .export_shared_object("games/save")
+                .expect("shared object should be exported");
+            // The name follows the 16 byte header as a length-prefixed string.
+            let len = u16::from_be_bytes([sol[16], sol[17]]) as usize;
+            let name = String::from_utf8_lossy(&sol[18..18 + len]);
+            log::info!(target: "avm_trace", "// Exported name: {}", name);
+
+            let imported = player.import_shared_object("games/save", &sol);
+            log::info!(target: "avm_trace", "// Imported: {}", imported.is_ok());
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn as3_text_input() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/text_input/test.swf",
+        1,
+        "tests/swfs/avm2/text_input/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+
+            player.handle_event(PlayerEvent::KeyDown {
+                key_code: KeyCode::Tab,
+                key_char: None,
+                key_location: KeyLocation::Standard,
+            });
+            log::info!(target: "avm_trace", "// Typing \"axbcd\"");
+            for codepoint in "axbcd".chars() {
+                player.handle_event(PlayerEvent::TextInput { codepoint });
+            }
+
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn as3_clipboard_user_gesture() -> Result<(), Error> {
+    test_swf(
+        "tests/swfs/avm2/clipboard_user_gesture/test.swf",
+        1,
+        "tests/swfs/avm2/clipboard_user_gesture/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+
+            // Writes to the clipboard are only allowed during user events.
+            log::info!(target: "avm_trace", "// User event");
+            player.set_is_user_event(true);
+            player.update_timers(40.0);
+            player.set_is_user_event(false);
+
+            Ok(())
+        },
+    )
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -519,6 +809,8 @@ fn run_swf(
         Box::new(NullInputBackend::new()),
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
+        Box::new(NullFontProvider::new()),
+        Box::new(NullVideoBackend::new()),
     )?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
 
@@ -527,6 +819,7 @@ fn run_swf(
     for _ in 0..num_frames {
         player.lock().unwrap().run_frame();
         player.lock().unwrap().update_timers(frame_time);
+        player.lock().unwrap().update_net_streams(frame_time);
         executor.poll_all().unwrap();
     }
 
@@ -0,0 +1,46 @@
+// Listeners added or removed during a broadcast don't change who receives it.
+var source = new Object();
+AsBroadcaster.initialize(source);
+var c = new Object();
+c.onPing = function(value) {
+	trace("c.onPing " + value);
+};
+var a = new Object();
+a.onPing = function(value) {
+	trace("a.onPing " + value);
+	source.removeListener(this);
+};
+var b = new Object();
+b.onPing = function(value) {
+	trace("b.onPing " + value);
+	source.addListener(c);
+};
+source.addListener(a);
+source.addListener(b);
+source.broadcastMessage("onPing", 1);
+trace("listeners: " + source._listeners.length);
+source.broadcastMessage("onPing", 2);
+trace("listeners: " + source._listeners.length);
+
+// Built-in broadcasters use the same functions.
+var stageListener = new Object();
+stageListener.onCustom = function(x, y) {
+	trace("onCustom " + x + " " + y);
+};
+Stage.addListener(stageListener);
+Stage.broadcastMessage("onCustom", "x", 2);
+trace(Stage.broadcastMessage == AsBroadcaster.broadcastMessage);
+Stage.removeListener(stageListener);
+
+// Component frameworks mix the broadcaster into their own objects.
+function Dispatcher() {
+}
+Dispatcher.initialize = function(o) {
+	AsBroadcaster.initialize(o);
+};
+var button = new Object();
+Dispatcher.initialize(button);
+trace(typeof button.addListener);
+trace(button._listeners.length);
+button.addListener(c);
+button.broadcastMessage("onPing", "button");
@@ -0,0 +1,32 @@
+// Compiled as SWF 8.
+// The exported "Clip" symbol has `trace("frame 1: " + this.x);` on its first frame.
+
+function C() {
+	trace("constructor: " + this.x);
+}
+C.prototype = new MovieClip();
+C.prototype.onLoad = function() {
+	trace("onLoad: " + this.x);
+};
+Object.registerClass("Clip", C);
+
+trace("// mc = _root.attachMovie(\"Clip\", \"clip\", 1, {x: 5})");
+mc = _root.attachMovie("Clip", "clip", 1, {x: 5});
+trace("// mc._name");
+trace(mc._name);
+trace("// mc.attachAudio(null)");
+trace(mc.attachAudio(null));
+
+trace("// _root.attachMovie(\"Clip\", \"high\", 2130690045)");
+trace(_root.attachMovie("Clip", "high", 2130690045));
+trace("// _root.attachMovie(\"Clip\", \"low\", -16385)");
+trace(_root.attachMovie("Clip", "low", -16385));
+
+trace("// timeline = _root.createEmptyMovieClip(\"timeline\", -10)");
+timeline = _root.createEmptyMovieClip("timeline", -10);
+trace("// timeline.removeMovieClip()");
+timeline.removeMovieClip();
+trace("// typeof _root.timeline");
+trace(typeof _root.timeline);
+trace("// end of frame 1");
+stop();
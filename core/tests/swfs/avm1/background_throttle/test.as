@@ -0,0 +1,8 @@
+// Counts frames and interval ticks while the player is throttled.
+onEnterFrame = function() {
+	trace("frame");
+};
+setInterval(function() {
+	trace("timer");
+}, 50);
+stop();
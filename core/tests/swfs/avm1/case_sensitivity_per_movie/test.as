@@ -0,0 +1,17 @@
+// Compiled as SWF 7: variable names are case sensitive.
+Foo = "host upper";
+foo = "host lower";
+trace("// host");
+trace(Foo);
+trace(foo);
+trace(typeof _ROOT);
+this.createEmptyMovieClip("child", 1);
+child.loadMovie("child.swf");
+
+// child.swf, compiled as SWF 6: variable names are case insensitive,
+// even when loaded into an SWF 7 movie.
+// Bar = "child";
+// trace("// child");
+// trace(bar);
+// trace(BAR);
+// trace(typeof _ROOT);
@@ -0,0 +1,25 @@
+// Compiled with a hand-written AS1 compiler. The expected output follows the
+// ActionScript 2.0 language reference for Color; it was not recorded from
+// Flash Player.
+// Color's setters read their arguments before changing the clip, so the
+// getters and valueOf calls below can inspect the clip while they run.
+this.createEmptyMovieClip("clip", 1);
+var c = new Color(clip);
+
+var t = {};
+t.addProperty("ra", function() {
+	trace("ra getter: " + c.getTransform().ra);
+	return 50;
+}, null);
+t.rb = 10;
+c.setTransform(t);
+var r = c.getTransform();
+trace("ra: " + r.ra + ", rb: " + r.rb + ", ga: " + r.ga);
+
+var n = {};
+n.valueOf = function() {
+	trace("valueOf: " + c.getRGB());
+	return 0xff0000;
+};
+c.setRGB(n);
+trace("rgb: " + c.getRGB().toString(16));
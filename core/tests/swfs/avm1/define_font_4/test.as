@@ -0,0 +1,17 @@
+// The SWF embeds font.otf, which has CFF outlines, as "TestCFF" in a
+// DefineFont4 tag. Only its regular style is embedded.
+createTextField("field", 1, 0, 0, 400, 100);
+field.embedFonts = true;
+field.setNewTextFormat(new TextFormat("TestCFF", 20));
+field.text = "AOA";
+var regularWidth = field.textWidth;
+trace("textWidth > 0: " + (regularWidth > 0));
+trace("textHeight > 0: " + (field.textHeight > 0));
+
+field.text = "AOAOA";
+trace("wider with more text: " + (field.textWidth > regularWidth));
+
+// Bold isn't embedded, so the regular style is used without synthesizing bold.
+field.text = "AOA";
+field.setTextFormat(new TextFormat("TestCFF", 20, null, true));
+trace("bold matches regular: " + (field.textWidth == regularWidth));
@@ -0,0 +1,5 @@
+// Counts frames while the player's frame rate is changed.
+onEnterFrame = function() {
+	trace("frame");
+};
+stop();
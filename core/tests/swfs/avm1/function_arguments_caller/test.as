@@ -0,0 +1,34 @@
+function inner(a, b) {
+	trace(a + "," + b + "," + arguments.length);
+	trace(arguments.caller == middle);
+	return a + b;
+}
+function middle() {
+	return inner.apply(this, arguments);
+}
+function outer() {
+	return middle.apply(null, arguments);
+}
+function fact(n) {
+	if (n <= 1) return 1;
+	return n * arguments.callee(n - 1);
+}
+function top() {
+	trace(arguments.caller);
+}
+function count() {
+	var n = 0;
+	for (var k in arguments) n++;
+	trace(n);
+}
+
+trace("// outer(1, 2)");
+trace(outer(1, 2));
+trace("// fact(5)");
+trace(fact(5));
+trace("// top()");
+top();
+trace("// count(\"x\", \"y\")");
+count("x", "y");
+trace("// inner.apply(null, {length: 2, 0: \"x\", 1: \"y\"})");
+trace(inner.apply(null, {length: 2, 0: "x", 1: "y"}));
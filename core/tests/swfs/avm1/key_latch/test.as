@@ -0,0 +1,15 @@
+// The test harness sends key events between frames.
+stop();
+trace("isToggled(20): " + Key.isToggled(20));
+trace("isToggled(37): " + Key.isToggled(37));
+var listener = new Object();
+listener.onKeyDown = function() {
+	trace("onKeyDown: " + Key.getCode() + " " + Key.getAscii() + " capsLock " + Key.isToggled(20));
+};
+listener.onKeyUp = function() {
+	trace("onKeyUp: " + Key.getCode() + " " + Key.getAscii() + " capsLock " + Key.isToggled(20));
+};
+Key.addListener(listener);
+this.onEnterFrame = function() {
+	trace("onEnterFrame: left " + Key.isDown(37) + ", right " + Key.isDown(39));
+};
@@ -0,0 +1 @@
+trace("Child frame 1");
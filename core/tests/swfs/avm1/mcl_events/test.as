@@ -0,0 +1,51 @@
+// MovieClipLoader passes the target clip to its listeners, and can load
+// images, and load into levels and clips given by path.
+var mcl = new MovieClipLoader();
+var listener = new Object();
+listener.onLoadStart = function(target) {
+	trace("onLoadStart " + target);
+};
+listener.onLoadProgress = function(target, loaded, total) {
+	trace("onLoadProgress " + target + " " + loaded + "/" + total);
+};
+listener.onLoadComplete = function(target, status) {
+	trace("onLoadComplete " + target + " " + status);
+};
+listener.onLoadError = function(target, code, status) {
+	trace("onLoadError " + target + " " + code + " " + status);
+	step();
+};
+listener.onLoadInit = function(target) {
+	trace("onLoadInit " + target);
+	step();
+};
+mcl.addListener(listener);
+
+this.createEmptyMovieClip("holder", 1);
+holder.createEmptyMovieClip("inner", 1);
+
+var steps = 0;
+function step() {
+	steps++;
+	if (steps == 1) {
+		trace("// Load a movie into a clip");
+		trace(mcl.loadClip("target.swf", holder));
+		var progress = mcl.getProgress(holder);
+		trace("getProgress while loading: " + progress.bytesLoaded + "/" + progress.bytesTotal);
+	} else if (steps == 2) {
+		trace("// Load a movie into a level by number");
+		mcl.loadClip("target.swf", 5);
+	} else if (steps == 3) {
+		trace("// Load a missing file");
+		mcl.loadClip("missing.swf", holder);
+	} else if (steps == 4) {
+		trace("// Replace the content of a level");
+		mcl.loadClip("image.png", "_level5");
+	} else {
+		trace("// Done");
+	}
+}
+
+trace("// Load an image into a nested clip by path");
+trace(mcl.loadClip("image.png", "holder.inner"));
+trace(mcl.loadClip("image.png", "nonexistent"));
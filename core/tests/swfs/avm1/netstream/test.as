@@ -0,0 +1,27 @@
+// Compiled as SWF 8, with a Video instance named `video` on the stage.
+// video.flv holds onMetaData information and three H.263 frames at 0, 40 and 80 ms.
+var nc = new NetConnection();
+nc.onStatus = function(info) {
+	trace("nc.onStatus: " + info.code + " " + info.level);
+};
+trace("connect: " + nc.connect(null));
+trace("isConnected: " + nc.isConnected);
+
+var ns = new NetStream(nc);
+ns.onStatus = function(info) {
+	trace("ns.onStatus: " + info.code);
+	if (info.code == "NetStream.Play.Stop") {
+		trace("time: " + ns.time);
+		trace("bytes: " + ns.bytesLoaded + "/" + ns.bytesTotal);
+	}
+};
+ns.onMetaData = function(info) {
+	trace("ns.onMetaData: " + info.width + "x" + info.height + " " + info.duration);
+};
+
+video.attachVideo(ns);
+video.smoothing = true;
+trace("smoothing: " + video.smoothing);
+trace("time before play: " + ns.time);
+ns.play("video.flv");
+stop();
@@ -0,0 +1,20 @@
+// #initclip for LibrarySymbol
+_global.Entity = function() {
+    super();
+    trace("Entity constructor: " + this._name);
+};
+Entity.prototype.__proto__ = MovieClip.prototype;
+Entity.prototype.__constructor__ = MovieClip;
+Entity.prototype.describe = function() {
+    return "entity " + this._name;
+};
+
+_global.Enemy = function() {
+    super();
+    trace("Enemy constructor: " + this._name);
+};
+Enemy.prototype.__proto__ = Entity.prototype;
+Enemy.prototype.__constructor__ = Entity;
+
+trace("registerClass: " + Object.registerClass("LibrarySymbol", Enemy));
+trace("registerClass unknown: " + Object.registerClass("Missing", Enemy));
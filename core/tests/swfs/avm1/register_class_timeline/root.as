@@ -0,0 +1,7 @@
+// Frame 2 of the root; "placed" was put on the timeline in frame 1
+var dup = placed.duplicateMovieClip("duplicate", 2);
+trace("duplicate instanceof Enemy: " + (dup instanceof Enemy));
+trace("registerClass null: " + Object.registerClass("LibrarySymbol", null));
+var plain = attachMovie("LibrarySymbol", "plain", 3);
+trace("plain instanceof Enemy: " + (plain instanceof Enemy));
+stop();
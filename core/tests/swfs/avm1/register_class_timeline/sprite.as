@@ -0,0 +1,5 @@
+// Frame 1 of LibrarySymbol
+trace("frame 1: " + this.describe());
+trace("instanceof Enemy: " + (this instanceof Enemy));
+trace("instanceof MovieClip: " + (this instanceof MovieClip));
+stop();
@@ -0,0 +1,23 @@
+// Compiled as SWF 8.
+
+_root.createEmptyMovieClip("mc", 1);
+
+trace("// mc.scale9Grid");
+trace(mc.scale9Grid);
+trace("// mc.scale9Grid = {x: 10, y: 20, width: 30, height: 40}");
+mc.scale9Grid = {x: 10, y: 20, width: 30, height: 40};
+trace("// mc.scale9Grid");
+trace(mc.scale9Grid);
+trace("// mc.scale9Grid.width");
+trace(mc.scale9Grid.width);
+trace("// mc.scale9Grid == mc.scale9Grid");
+trace(mc.scale9Grid == mc.scale9Grid);
+trace("// delete mc.scale9Grid");
+trace(delete mc.scale9Grid);
+trace(mc.scale9Grid);
+trace("// mc.scale9Grid = null");
+mc.scale9Grid = null;
+trace(mc.scale9Grid);
+trace("// mc.createTextField(\"tf\", 1, 0, 0, 10, 10).scale9Grid");
+mc.createTextField("tf", 1, 0, 0, 10, 10);
+trace(mc.tf.scale9Grid);
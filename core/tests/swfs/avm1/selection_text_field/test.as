@@ -0,0 +1,60 @@
+// Compiled as SWF 8.
+
+function traceIndices() {
+	trace("// Selection.getBeginIndex()");
+	trace(Selection.getBeginIndex());
+	trace("// Selection.getEndIndex()");
+	trace(Selection.getEndIndex());
+	trace("// Selection.getCaretIndex()");
+	trace(Selection.getCaretIndex());
+}
+
+_level0.createTextField("a", 1, 0, 0, 100, 20);
+_level0.a.text = "Hello world";
+_level0.createTextField("b", 2, 0, 30, 100, 20);
+_level0.b.text = "Hi";
+
+listener = {};
+listener.onSetFocus = function(oldFocus, newFocus) {
+	trace("onSetFocus: " + (oldFocus == null ? "null" : oldFocus._name) + " -> " + (newFocus == null ? "null" : newFocus._name));
+};
+Selection.addListener(listener);
+
+trace("// Selection.getFocus()");
+trace(Selection.getFocus());
+traceIndices();
+
+trace("// Selection.setFocus(\"_level0.a\")");
+trace(Selection.setFocus("_level0.a"));
+trace("// Selection.getFocus()");
+trace(Selection.getFocus());
+traceIndices();
+
+trace("// Selection.setSelection(2, 5)");
+trace(Selection.setSelection(2, 5));
+traceIndices();
+
+trace("// Selection.setSelection(8, 100)");
+trace(Selection.setSelection(8, 100));
+traceIndices();
+
+trace("// a.text = \"Hey\"");
+_level0.a.text = "Hey";
+traceIndices();
+
+trace("// Selection.setFocus(b)");
+trace(Selection.setFocus(_level0.b));
+trace("// Selection.getFocus()");
+trace(Selection.getFocus());
+traceIndices();
+
+trace("// Selection.setFocus(\"_level0.missing\")");
+trace(Selection.setFocus("_level0.missing"));
+trace("// Selection.getFocus()");
+trace(Selection.getFocus());
+
+trace("// Selection.setFocus(null)");
+trace(Selection.setFocus(null));
+trace("// Selection.getFocus()");
+trace(Selection.getFocus());
+traceIndices();
@@ -0,0 +1,34 @@
+// setInterval with an object and method name looks the method up each time
+// it fires, and interval IDs are not reused after clearInterval.
+var obj = new Object();
+obj.tick = function(a, b) {
+	trace("original tick " + a + " " + b);
+	obj.tick = function(a, b) {
+		trace("replaced tick " + a + " " + b);
+		clearInterval(methodId);
+		checkIds();
+	};
+};
+var methodId = setInterval(obj, "tick", 50, "x", "y");
+trace("typeof methodId: " + typeof methodId);
+
+var newId;
+function checkIds() {
+	var oldId = setInterval(onOld, 1000);
+	clearInterval(oldId);
+	newId = setInterval(onNew, 60);
+	trace("new ID differs: " + (newId != oldId));
+	trace("new ID is greater: " + (newId > oldId));
+
+	// Clearing a stale or unknown ID does nothing.
+	clearInterval(oldId);
+	clearInterval(methodId);
+	clearInterval(12345);
+}
+function onOld() {
+	trace("cleared interval fired");
+}
+function onNew() {
+	trace("new interval fired");
+	clearInterval(newId);
+}
@@ -0,0 +1,6 @@
+// Compiled with a hand-written AS1 compiler. The test harness exports the
+// shared object and traces the name in the `.sol` header, which should be the
+// name the movie opened it with.
+var so = SharedObject.getLocal("games/save");
+so.data.score = 10;
+trace("score: " + so.data.score);
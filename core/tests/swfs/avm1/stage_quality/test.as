@@ -0,0 +1,28 @@
+// Compiled as SWF 8.
+trace("// _quality");
+trace(_quality);
+trace(_highquality);
+trace("// _quality = \"low\"");
+_quality = "low";
+trace(_quality);
+trace(_highquality);
+trace("// _highquality = 2");
+_highquality = 2;
+trace(_quality);
+trace(_highquality);
+trace("// toggleHighQuality()");
+toggleHighQuality();
+trace(_quality);
+trace("// toggleHighQuality()");
+toggleHighQuality();
+trace(_quality);
+trace("// _quality = \"Medium\"");
+_quality = "Medium";
+trace(_quality);
+trace(_highquality);
+trace("// _quality = \"bogus\"");
+_quality = "bogus";
+trace(_quality);
+trace("// _highquality = 5");
+_highquality = 5;
+trace(_quality);
@@ -0,0 +1,10 @@
+// The test harness resizes the viewport to 800x600 before the first frame,
+// and changes the viewport and scale mode again after it.
+var listener = new Object();
+listener.onResize = function() {
+	trace("onResize: " + Stage.width + "x" + Stage.height);
+};
+Stage.addListener(listener);
+trace("showAll: " + Stage.width + "x" + Stage.height);
+trace("// Stage.scaleMode = \"noScale\"");
+Stage.scaleMode = "noScale";
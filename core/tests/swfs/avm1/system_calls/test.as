@@ -0,0 +1,21 @@
+// Compiled as SWF 8.
+
+trace("// System.security.allowDomain(\"*\")");
+trace(System.security.allowDomain("*"));
+trace("// System.security.allowInsecureDomain(\"example.com\", \"example.org\")");
+trace(System.security.allowInsecureDomain("example.com", "example.org"));
+trace("// System.security.loadPolicyFile(\"http://example.com/crossdomain.xml\")");
+trace(System.security.loadPolicyFile("http://example.com/crossdomain.xml"));
+trace("// System.setClipboard(\"copied\")");
+trace(System.setClipboard("copied"));
+trace("// System.showSettings(1)");
+trace(System.showSettings(1));
+trace("// System.useCodepage = true");
+System.useCodepage = true;
+trace("// System.useCodepage");
+trace(System.useCodepage);
+trace("// System.useCodepage = false");
+System.useCodepage = false;
+trace("// typeof System.capabilities.version");
+trace(typeof System.capabilities.version);
+trace("still running");
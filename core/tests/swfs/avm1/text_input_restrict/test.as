@@ -0,0 +1,26 @@
+// The test harness types "a1b2345", then "aqZ!b", into the focused field.
+this.createTextField("field", 1, 0, 0, 100, 20);
+this.createTextField("field2", 2, 0, 30, 100, 20);
+trace("field.type: " + field.type);
+trace("field.restrict: " + field.restrict);
+trace("field.maxChars: " + field.maxChars);
+field.type = "input";
+field.restrict = "0-9";
+field.maxChars = 4;
+trace("field.type: " + field.type);
+trace("field.restrict: " + field.restrict);
+trace("field.maxChars: " + field.maxChars);
+field2.type = "input";
+field2.restrict = "A-Z^Q";
+trace("field2.type: " + field2.type);
+trace("field2.restrict: " + field2.restrict);
+trace("field2.maxChars: " + field2.maxChars);
+
+// Once the first field is full, move on to the second one.
+field.onChanged = field2.onChanged = function(tf) {
+	trace("onChanged: " + tf._name + " " + tf.text);
+	if (tf.text == "1234") {
+		Selection.setFocus(field2);
+	}
+};
+Selection.setFocus(field);
@@ -0,0 +1,12 @@
+// The test harness clicks on the first line of the field, then the second,
+// then the third.
+this.createTextField("field", 1, 0, 0, 200, 100);
+field.html = true;
+field.multiline = true;
+field.htmlText = "<a href=\"asfunction:onLink,hello, world\">First</a><br>Second<br><a href=\"ASFUNCTION:onLink,3\">Third</a>";
+trace("field.length: " + field.length);
+
+onLink = function(arg) {
+	trace("onLink: " + arg);
+	trace("this == _root: " + (this == _root));
+};
@@ -0,0 +1,32 @@
+// Compiled as SWF 8.
+
+_level0.ticks = 0;
+_level0.parentTicks = 0;
+setInterval(function() { _level0.parentTicks = _level0.parentTicks + 1; }, 10);
+
+_level0.createEmptyMovieClip("holder", 1);
+_level0.holder.loadMovie("child.swf");
+
+onEnterFrame = function() {
+	if (_level0.saved == undefined) {
+		// Unload the child as soon as its interval has fired.
+		if (_level0.ticks > 0) {
+			_level0.holder.unloadMovie();
+			_level0.saved = _level0.ticks;
+			_level0.savedParent = _level0.parentTicks;
+			_level0.after = 0;
+			trace("Unloaded child");
+		}
+	} else {
+		_level0.after = _level0.after + 1;
+		if (_level0.after == 5) {
+			trace("Ticks after unloading: " + (_level0.ticks - _level0.saved));
+			trace("Parent interval still running: " + (_level0.parentTicks > _level0.savedParent));
+		}
+	}
+};
+
+// child.swf, compiled as SWF 8:
+//
+// trace("Child started");
+// setInterval(function() { _level0.ticks = _level0.ticks + 1; }, 10);
@@ -0,0 +1,31 @@
+var x = new XML();
+x.ignoreWhite = true;
+x.parseXML("<a>\n  <b> hi </b>\n</a>");
+trace("// ignoreWhite = true");
+trace(x.firstChild.childNodes.length);
+trace(x);
+trace(x.status);
+x.ignoreWhite = false;
+trace("// ignoreWhite = false, not reparsed");
+trace(x);
+x.parseXML("<a>\n  <b> hi </b>\n</a>");
+trace("// ignoreWhite = false, reparsed");
+trace(x.firstChild.childNodes.length);
+
+var u = new XML("<a><b></b>");
+trace("// unclosed element");
+trace(u.status);
+var m = new XML("<a></a></b>");
+trace("// unmatched end tag");
+trace(m.status);
+var d = new XML("<!DOCTYPE foo><foo/>");
+trace("// doctype");
+trace(d.docTypeDecl);
+trace(d);
+
+var o = new XML("<n z=\"1\" a=\"2\" m=\"3\"/>");
+trace("// attribute order");
+trace(o);
+for (var k in o.firstChild.attributes) {
+	trace(k);
+}
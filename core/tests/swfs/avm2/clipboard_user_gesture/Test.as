@@ -0,0 +1,24 @@
+package {
+	import flash.desktop.Clipboard;
+	import flash.desktop.ClipboardFormats;
+	import flash.events.TimerEvent;
+	import flash.utils.Timer;
+
+	// The first tick isn't triggered by the user, so writing to the
+	// clipboard throws. The test harness marks the second tick as a user
+	// event, which allows the write.
+	var timer:Timer = new Timer(40);
+	timer.addEventListener("timer", function(e:TimerEvent):void {
+		trace("tick");
+		var clipboard:Clipboard = Clipboard.generalClipboard;
+		trace("getData(HTML_FORMAT): " + clipboard.getData(ClipboardFormats.HTML_FORMAT));
+		trace("hasFormat(TEXT_FORMAT): " + clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+		trace("setData(TEXT_FORMAT): " + clipboard.setData(ClipboardFormats.TEXT_FORMAT, "copied"));
+		trace("getData(TEXT_FORMAT): " + clipboard.getData(ClipboardFormats.TEXT_FORMAT));
+		trace("hasFormat(TEXT_FORMAT): " + clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+		trace("setData(RICH_TEXT_FORMAT): " + clipboard.setData(ClipboardFormats.RICH_TEXT_FORMAT, "{\\rtf1}"));
+		clipboard.clear();
+		trace("after clear: " + clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+	});
+	timer.start();
+}
@@ -0,0 +1,39 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The expected output follows the ActionScript 3.0 language reference for
+// flash.utils.Dictionary; it was not recorded from Flash Player, which does
+// not guarantee an enumeration order. Ruffle enumerates object keys in
+// insertion order, followed by primitive keys.
+package {
+	import flash.utils.Dictionary;
+
+	var d:Dictionary = new Dictionary(true);
+	var a:Object = {};
+	var b:Object = {};
+	d[a] = "first";
+	d[b] = "second";
+	d["a"] = "string key";
+	trace(d[a]);
+	trace(d[b]);
+	trace(d["a"]);
+	trace("[object Object]" in d);
+	trace(d[{}]);
+	trace(a in d);
+	trace("a" in d);
+	trace({} in d);
+	d[a] = "replaced";
+	trace(d[a]);
+	for (var k:* in d) {
+		trace(d[k]);
+	}
+	for each (var v:* in d) {
+		trace(v);
+	}
+	trace(delete d[a]);
+	trace(delete d[a]);
+	trace(a in d);
+	trace(d[a]);
+	trace(d[b]);
+	for each (v in d) {
+		trace(v);
+	}
+}
@@ -0,0 +1,5 @@
+package {
+	// The DoABC tag is the last tag before the end of the movie, so its ABC
+	// data runs right up to the end of the file.
+	trace("ABC ran");
+}
@@ -0,0 +1,43 @@
+package {
+	public class Test {
+	}
+}
+
+import avm2.intrinsics.memory.*;
+import flash.system.ApplicationDomain;
+import flash.utils.ByteArray;
+
+trace("MIN_DOMAIN_MEMORY_LENGTH: " + ApplicationDomain.MIN_DOMAIN_MEMORY_LENGTH);
+trace("domainMemory (unset): " + ApplicationDomain.currentDomain.domainMemory);
+
+var memory: ByteArray = new ByteArray();
+memory.length = 1024;
+ApplicationDomain.currentDomain.domainMemory = memory;
+trace("domainMemory == memory: " + (ApplicationDomain.currentDomain.domainMemory == memory));
+
+si32(0x12345678, 0);
+trace("li8(0): " + li8(0));
+trace("li16(0): " + li16(0));
+trace("li32(0): " + li32(0));
+trace("li8(3): " + li8(3));
+
+si8(-1, 8);
+trace("li8(8): " + li8(8));
+si16(0x18000, 16);
+trace("li16(16): " + li16(16));
+sf32(1.5, 32);
+trace("lf32(32): " + lf32(32));
+sf64(-0.25, 40);
+trace("lf64(40): " + lf64(40));
+trace("li32(1020): " + li32(1020));
+
+trace("sxi1(1): " + sxi1(1));
+trace("sxi1(2): " + sxi1(2));
+trace("sxi8(255): " + sxi8(255));
+trace("sxi8(127): " + sxi8(127));
+trace("sxi16(32768): " + sxi16(32768));
+
+// Growing the array makes the new bytes addressable.
+memory.length = 2048;
+si32(7, 2044);
+trace("li32(2044): " + li32(2044));
@@ -0,0 +1,51 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The bytecode follows what compilers emit for these E4X expressions; the
+// expected output follows the ECMA-357 (E4X) specification and was not
+// recorded from Flash Player.
+package {
+	var xml:XML = new XML('<root xmlns:n="http://n" b="2" a="1"><list><item id="1">one</item><item id="3">three</item></list><n:node>ns</n:node><deep><item id="2"><item id="3">nested</item></item></deep></root>');
+	trace(xml.toXMLString());
+	trace(xml..item.@id);
+	trace(xml..item.(@id == "3").toXMLString());
+
+	var n:Namespace = new Namespace("http://n");
+	trace(xml.n::node);
+	trace(xml.node.length());
+
+	trace(xml.@*.length());
+	trace(xml.attributes().toXMLString());
+	trace(xml.@a);
+
+	xml.list.item += <item id="4">four</item>;
+	trace(xml.list.item.length());
+	trace(xml.list.item.@id);
+
+	trace(delete xml.list.item[0]);
+	trace(xml.list.item.@id);
+
+	xml.list.item[0].@id = "30";
+	xml.extra = "added";
+	delete xml.deep.item;
+	trace(xml.deep.length());
+	trace(xml.deep.children().length());
+	trace(xml.toXMLString());
+
+	XML.prettyIndent = 4;
+	trace(xml.list.toXMLString());
+	XML.prettyPrinting = false;
+	trace(xml.toXMLString());
+	trace(XML.prettyPrinting);
+	trace(XML.prettyIndent);
+	XML.prettyPrinting = true;
+	XML.prettyIndent = 2;
+
+	var d:XML = new XML('<r xmlns="http://d"><foo>default</foo></r>');
+	trace(d.foo.length());
+	default xml namespace = new Namespace("http://d");
+	trace(d.foo);
+
+	trace(xml.nodeKind());
+	trace(xml.localName());
+	trace(xml.@a.nodeKind());
+	trace(xml.n::node.name());
+}
@@ -0,0 +1,45 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The timeline holds a sprite named "child". The expected output follows the
+// ActionScript 3.0 language reference for the event flow; it was not
+// recorded from Flash Player.
+package {
+	import flash.display.DisplayObject;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var child:DisplayObject = getChildByName("child");
+			var events:Array = [["bubbles", true], ["flat", false], ["stop", true], ["stopImmediate", true], ["captureStop", true]];
+
+			for each (var event:Array in events) {
+				addEventListener(event[0], function(e:Event):void {
+					trace("main capture " + e.eventPhase + " " + e.target.name);
+					if (e.type == "captureStop") {
+						e.stopPropagation();
+					}
+				}, true);
+				child.addEventListener(event[0], function(e:Event):void {
+					trace("child first " + e.eventPhase + " " + e.currentTarget.name);
+					if (e.type == "stop") {
+						e.stopPropagation();
+					}
+					if (e.type == "stopImmediate") {
+						e.stopImmediatePropagation();
+					}
+				});
+				child.addEventListener(event[0], function(e:Event):void {
+					trace("child second " + e.eventPhase);
+				});
+				addEventListener(event[0], function(e:Event):void {
+					trace("main bubble " + e.eventPhase);
+				});
+			}
+
+			for each (event in events) {
+				trace("// " + event[0]);
+				trace(child.dispatchEvent(new Event(event[0], event[1])));
+			}
+		}
+	}
+}
@@ -0,0 +1,33 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.geom.Matrix;
+import flash.geom.Point;
+import flash.geom.Rectangle;
+
+trace("length: " + new Point(3, 4).length);
+trace("polar: " + Point.polar(2, 0));
+trace("interpolate: " + Point.interpolate(new Point(10, 20), new Point(0, 0), 0.25));
+trace("intersection: " + new Rectangle(0, 0, 10, 10).intersection(new Rectangle(20, 20, 5, 5)));
+trace("union with empty: " + new Rectangle(0, 0, 0, 0).union(new Rectangle(1, 2, 3, 4)));
+
+var rect: Rectangle = new Rectangle(0, 0, 10, 10);
+trace("containsRect: " + rect.containsRect(new Rectangle(2, 2, 4, 4)));
+rect.inflatePoint(new Point(1, 2));
+trace("inflatePoint: " + rect);
+rect.offsetPoint(new Point(1, 2));
+trace("offsetPoint: " + rect);
+trace("equals: " + rect.equals(new Rectangle(0, 0, 12, 14)));
+
+var matrix: Matrix = new Matrix(2, 0, 0, 2, 5, 5);
+trace("deltaTransformPoint: " + matrix.deltaTransformPoint(new Point(1, 1)));
+trace("transformPoint: " + matrix.transformPoint(new Point(1, 1)));
+matrix.createGradientBox(1638.4, 3276.8, 0, -819.2, -1638.4);
+trace("createGradientBox: " + matrix);
+trace("clone: " + matrix.clone());
+
+var point: Point = new Point();
+point.copyFrom(new Point(1, 2));
+trace("copyFrom: " + point);
@@ -0,0 +1,13 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The expected output follows the ActionScript 3.0 language reference for
+// JSON; it was not recorded from Flash Player.
+package {
+	var o:Object = JSON.parse('{"list":[1,[2,"three"],{"empty":[]}],"n":null}');
+	trace(o.list.length);
+	trace(o.list[1][1]);
+	trace(o.list[2].empty.length);
+	trace(JSON.stringify(o));
+	trace(JSON.stringify([1, undefined, "a", [true]], null, 2));
+	trace(JSON.stringify({k: [1, 2], skipped: true}, ["k"]));
+	trace(JSON.parse("[[],[[]]]")[1].length);
+}
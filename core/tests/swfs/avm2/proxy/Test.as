@@ -0,0 +1,63 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The expected output follows the ActionScript 3.0 language reference for
+// flash.utils.Proxy; it was not recorded from Flash Player.
+package {
+	import flash.utils.Proxy;
+	import flash.utils.flash_proxy;
+
+	var p:TestProxy = new TestProxy();
+	trace(p.foo);
+	p.bar = 5;
+	trace(p.baz(1, 2));
+	trace("yes" in p);
+	trace("no" in p);
+	trace(delete p.qux);
+	for (var name:String in p) {
+		trace(name);
+	}
+	for each (var value:String in p) {
+		trace(value);
+	}
+}
+
+import flash.utils.Proxy;
+import flash.utils.flash_proxy;
+
+class TestProxy extends Proxy {
+	override flash_proxy function getProperty(name:*):* {
+		trace("getProperty " + name);
+		return "value of " + name;
+	}
+
+	override flash_proxy function setProperty(name:*, value:*):void {
+		trace("setProperty " + name + " " + value);
+	}
+
+	override flash_proxy function callProperty(name:*, a:*, b:*):* {
+		trace("callProperty " + name + " " + a + " " + b);
+		return a + b;
+	}
+
+	override flash_proxy function hasProperty(name:*):Boolean {
+		trace("hasProperty " + name);
+		return name == "yes";
+	}
+
+	override flash_proxy function deleteProperty(name:*):Boolean {
+		trace("deleteProperty " + name);
+		return true;
+	}
+
+	// Enumerates the names "n1" and "n2", whose values are "v1" and "v2".
+	override flash_proxy function nextNameIndex(index:int):int {
+		return index < 2 ? index + 1 : 0;
+	}
+
+	override flash_proxy function nextName(index:int):String {
+		return "n" + index;
+	}
+
+	override flash_proxy function nextValue(index:int):* {
+		return "v" + index;
+	}
+}
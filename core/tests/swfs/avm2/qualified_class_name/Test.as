@@ -0,0 +1,21 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The expected output follows the ActionScript 3.0 language reference for
+// flash.utils; it was not recorded from Flash Player.
+package {
+	import flash.display.Sprite;
+	import flash.utils.getDefinitionByName;
+	import flash.utils.getQualifiedClassName;
+	import flash.utils.getQualifiedSuperclassName;
+
+	trace(getQualifiedClassName(1));
+	trace(getQualifiedClassName(1.5));
+	trace(getQualifiedClassName(4294967295));
+	trace(getQualifiedClassName(null));
+	trace(getQualifiedClassName(undefined));
+	trace(getQualifiedClassName("s"));
+	trace(getQualifiedClassName(true));
+	trace(getQualifiedClassName(Sprite));
+	trace(getQualifiedClassName(new Sprite()));
+	trace(getQualifiedSuperclassName(Sprite));
+	trace(getDefinitionByName("flash.display::Sprite") === Sprite);
+}
@@ -0,0 +1,42 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The timeline has the scenes "Intro" (frames 1-2) and "Main" (frames 3-4),
+// with the label "start" on frames 1 and 3 and "end" on frame 4. The expected
+// output follows the ActionScript 3.0 language reference for scenes and frame
+// labels; it was not recorded from Flash Player.
+package {
+	import flash.display.FrameLabel;
+	import flash.display.MovieClip;
+	import flash.display.Scene;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("scenes " + scenes.length);
+			trace(scenes[0].name + " " + scenes[0].numFrames + " " + describe(scenes[0].labels));
+			trace(scenes[1].name + " " + scenes[1].numFrames + " " + describe(scenes[1].labels));
+
+			var labels:Array = [new FrameLabel("label", 5)];
+			trace(describe(labels));
+			var scene:Scene = new Scene("scene", labels, 7);
+			trace(scene.name + " " + scene.numFrames + " " + describe(scene.labels));
+
+			// The constructor runs on frame 1, so it reports that frame itself.
+			frameScript();
+			addFrameScript(1, frameScript, 2, frameScript, 3, frameScript);
+		}
+
+		private function describe(labels:Array):String {
+			var s:String = "";
+			for (var i:int = 0; i < labels.length; i++) {
+				s += labels[i].name + "@" + labels[i].frame + " ";
+			}
+			return s;
+		}
+
+		private function frameScript():void {
+			trace(currentFrame + " " + currentScene.name + " " + currentLabel + " " + currentFrameLabel + " " + describe(currentLabels));
+			if (currentFrame == 4) {
+				stop();
+			}
+		}
+	}
+}
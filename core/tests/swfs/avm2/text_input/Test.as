@@ -0,0 +1,25 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The stage holds an input text field named "field" with maxChars set to 3.
+// The test harness tabs to it and types "axbcd". The expected output follows
+// the ActionScript 3.0 language reference for TextEvent.TEXT_INPUT; it was
+// not recorded from Flash Player.
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.TextEvent;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			addEventListener(TextEvent.TEXT_INPUT, function(e:TextEvent):void {
+				trace("textInput " + e.text + " " + e.bubbles + " " + e.cancelable + " " + e.target.name);
+				if (e.text == "x") {
+					e.preventDefault();
+					trace("preventDefault " + e.isDefaultPrevented());
+				}
+			});
+			addEventListener(Event.CHANGE, function(e:Event):void {
+				trace("change " + e.bubbles + " " + e.target.name);
+			});
+		}
+	}
+}
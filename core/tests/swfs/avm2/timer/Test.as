@@ -0,0 +1,33 @@
+// Compiled with a hand-written ABC assembler, as no Flex SDK was available.
+// The expected output follows the ActionScript 3.0 language reference for
+// flash.utils.Timer; it was not recorded from Flash Player.
+package {
+	import flash.events.TimerEvent;
+	import flash.utils.Timer;
+
+	var t:Timer = new Timer(10, 3);
+	trace(t.delay + " " + t.repeatCount + " " + t.currentCount + " " + t.running);
+	t.delay = 20;
+	trace(t.delay);
+
+	t.addEventListener(TimerEvent.TIMER, function(e:TimerEvent):void {
+		trace("timer " + t.currentCount + " " + t.running);
+		if (t.repeatCount == 0 && t.currentCount == 2) {
+			t.stop();
+			trace("stopped " + t.currentCount + " " + t.running);
+		}
+	});
+	t.addEventListener(TimerEvent.TIMER_COMPLETE, function(e:TimerEvent):void {
+		trace("timerComplete " + t.currentCount + " " + t.running);
+		if (t.repeatCount == 3) {
+			t.reset();
+			trace("reset " + t.currentCount + " " + t.running);
+			t.repeatCount = 0;
+			t.start();
+		}
+	});
+
+	t.start();
+	t.start();
+	trace("started " + t.currentCount + " " + t.running);
+}
@@ -0,0 +1,34 @@
+package {
+	public class Test {
+	}
+}
+
+import flash.system.MessageChannel;
+import flash.system.Worker;
+import flash.system.WorkerDomain;
+
+trace("Worker.isSupported: " + Worker.isSupported);
+trace("WorkerDomain.isSupported: " + WorkerDomain.isSupported);
+
+var worker: Worker;
+if (Worker.isSupported) {
+	trace("// Starting a background worker");
+	worker = WorkerDomain.current.createWorker("Background.swf");
+} else {
+	trace("// Falling back to the primordial worker");
+}
+
+worker = Worker.current;
+trace("Worker.current.isPrimordial: " + worker.isPrimordial);
+trace("Worker.current.state: " + worker.state);
+trace("Worker.current == Worker.current: " + (Worker.current == Worker.current));
+trace("WorkerDomain.current == WorkerDomain.current: " + (WorkerDomain.current == WorkerDomain.current));
+
+worker.setSharedProperty("level", 3);
+trace("getSharedProperty(\"level\"): " + worker.getSharedProperty("level"));
+trace("getSharedProperty(\"missing\"): " + worker.getSharedProperty("missing"));
+
+var channel: MessageChannel = new MessageChannel();
+trace("MessageChannel.messageAvailable: " + channel.messageAvailable);
+trace("MessageChannel.state: " + channel.state);
+trace("// Fallback complete");
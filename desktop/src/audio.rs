@@ -4,7 +4,7 @@ use ruffle_core::backend::audio::decoders::{
     self, AdpcmDecoder, Mp3Decoder, PcmDecoder, SeekableDecoder,
 };
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
+    swf, AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::tag_utils::SwfSlice;
 use std::io::Cursor;
@@ -66,6 +66,77 @@ struct SoundInstance {
     /// If this flag is false, the sound will be cleaned up during the
     /// next loop of the sound thread.
     active: bool,
+
+    /// The number of sample frames played so far, at the output sample rate.
+    sample_frames_played: u64,
+
+    /// The position in the sound that playback started at, in milliseconds.
+    start_position: f64,
+
+    /// The length of one loop in milliseconds, if the sound loops.
+    loop_duration: Option<f64>,
+
+    /// Tracks the recent peak amplitudes, for `SoundChannel.leftPeak` and `rightPeak`.
+    peak_meter: PeakMeter,
+
+    /// The volume and panning applied to this sound.
+    transform: SoundTransform,
+}
+
+impl SoundInstance {
+    fn new(
+        handle: Option<SoundHandle>,
+        clip_id: Option<swf::CharacterId>,
+        signal: Signal,
+        output_sample_rate: u32,
+    ) -> Self {
+        Self {
+            handle,
+            signal,
+            clip_id,
+            active: true,
+            sample_frames_played: 0,
+            start_position: 0.0,
+            loop_duration: None,
+            peak_meter: PeakMeter::new(output_sample_rate / PeakMeter::WINDOWS_PER_SECOND),
+            transform: SoundTransform::default(),
+        }
+    }
+}
+
+/// Measures the peak amplitude of each channel over a short window of sample frames.
+struct PeakMeter {
+    window_len: u32,
+    window_pos: u32,
+    window_peak: [f32; 2],
+
+    /// The peak amplitudes of the last complete window.
+    peak: [f32; 2],
+}
+
+impl PeakMeter {
+    const WINDOWS_PER_SECOND: u32 = 20;
+
+    fn new(window_len: u32) -> Self {
+        Self {
+            window_len: window_len.max(1),
+            window_pos: 0,
+            window_peak: [0.0, 0.0],
+            peak: [0.0, 0.0],
+        }
+    }
+
+    fn add(&mut self, frame: [i16; 2]) {
+        for (peak, &sample) in self.window_peak.iter_mut().zip(frame.iter()) {
+            *peak = peak.max(f32::from(sample).abs() / 32768.0);
+        }
+        self.window_pos += 1;
+        if self.window_pos >= self.window_len {
+            self.peak = self.window_peak;
+            self.window_peak = [0.0, 0.0];
+            self.window_pos = 0;
+        }
+    }
 }
 
 impl CpalAudioBackend {
@@ -277,7 +348,14 @@ impl CpalAudioBackend {
             let mut output_frame = Stereo::<T::Signed>::equilibrium();
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.signal.is_exhausted() {
-                    let sound_frame = sound.signal.next();
+                    let [left, right] = sound.signal.next();
+                    let [left, right] = sound.transform.apply([left.into(), right.into()]);
+                    let sound_frame = [
+                        left.max(i16::MIN.into()).min(i16::MAX.into()) as i16,
+                        right.max(i16::MIN.into()).min(i16::MAX.into()) as i16,
+                    ];
+                    sound.sample_frames_played += 1;
+                    sound.peak_meter.add(sound_frame);
                     let sound_frame: Stereo<T::Signed> = Frame::map(sound_frame, Sample::to_sample);
                     output_frame = output_frame.add_amp(sound_frame);
                 } else {
@@ -306,10 +384,19 @@ impl AudioBackend for CpalAudioBackend {
             (0, &swf_sound.data[..])
         };
 
+        // Some encoders leave the sample count of MP3 sounds empty; count the MP3 frames instead.
+        let num_sample_frames = if swf_sound.num_samples == 0
+            && swf_sound.format.compression == AudioCompression::Mp3
+        {
+            decoders::count_mp3_sample_frames(data)
+        } else {
+            swf_sound.num_samples
+        };
+
         let sound = Sound {
             format: swf_sound.format.clone(),
             data: Arc::new(data.to_vec()),
-            num_sample_frames: swf_sound.num_samples,
+            num_sample_frames,
             skip_sample_frames,
         };
         Ok(self.sounds.insert(sound))
@@ -330,12 +417,12 @@ impl AudioBackend for CpalAudioBackend {
         let signal = self.make_signal_from_stream(format, clip_data)?;
 
         let mut sound_instances = self.sound_instances.lock().unwrap();
-        let handle = sound_instances.insert(SoundInstance {
-            handle: None,
-            clip_id: Some(clip_id),
+        let handle = sound_instances.insert(SoundInstance::new(
+            None,
+            Some(clip_id),
             signal,
-            active: true,
-        });
+            self.output_config.sample_rate.0,
+        ));
         Ok(handle)
     }
 
@@ -365,14 +452,26 @@ impl AudioBackend for CpalAudioBackend {
             self.make_signal_from_event_sound(&sound, settings, data)?
         };
 
+        let mut instance = SoundInstance::new(
+            Some(sound_handle),
+            None,
+            signal,
+            self.output_config.sample_rate.0,
+        );
+        // Start and end points are in 44.1kHz samples regardless of the sound's sample rate.
+        instance.start_position = f64::from(settings.in_sample.unwrap_or(0)) / 44.1;
+        if settings.num_loops > 1 {
+            let end_position = if let Some(out_sample) = settings.out_sample {
+                f64::from(out_sample) / 44.1
+            } else {
+                f64::from(sound.num_sample_frames) * 1000.0 / f64::from(sound.format.sample_rate)
+            };
+            instance.loop_duration = Some(end_position - instance.start_position);
+        }
+
         // Add sound instance to active list.
         let mut sound_instances = self.sound_instances.lock().unwrap();
-        let handle = sound_instances.insert(SoundInstance {
-            handle: Some(sound_handle),
-            clip_id: None,
-            signal,
-            active: true,
-        });
+        let handle = sound_instances.insert(instance);
         Ok(handle)
     }
 
@@ -381,6 +480,21 @@ impl AudioBackend for CpalAudioBackend {
         sound_instances.remove(sound);
     }
 
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        sound_instances
+            .get(instance)
+            .map(|instance| instance.active)
+            .unwrap_or(false)
+    }
+
+    fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform) {
+        let mut sound_instances = self.sound_instances.lock().unwrap();
+        if let Some(instance) = sound_instances.get_mut(instance) {
+            instance.transform = transform;
+        }
+    }
+
     fn stop_all_sounds(&mut self) {
         let mut sound_instances = self.sound_instances.lock().unwrap();
         sound_instances.clear();
@@ -403,6 +517,24 @@ impl AudioBackend for CpalAudioBackend {
         }
     }
 
+    fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        let instance = sound_instances.get(instance)?;
+        let mut played = instance.sample_frames_played as f64 * 1000.0
+            / f64::from(self.output_config.sample_rate.0);
+        if let Some(loop_duration) = instance.loop_duration.filter(|&d| d > 0.0) {
+            played %= loop_duration;
+        }
+        Some((instance.start_position + played) as u32)
+    }
+
+    fn get_sound_peak(&self, instance: SoundInstanceHandle) -> Option<[f32; 2]> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        sound_instances
+            .get(instance)
+            .map(|instance| instance.peak_meter.peak)
+    }
+
     fn is_sound_playing_with_handle(&mut self, handle: SoundHandle) -> bool {
         let sound_instances = self.sound_instances.lock().unwrap();
         let handle = Some(handle);
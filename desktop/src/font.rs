@@ -0,0 +1,230 @@
+use font_kit::family_name::FamilyName;
+use font_kit::hinting::HintingOptions;
+use font_kit::outline::OutlineSink;
+use font_kit::properties::{Properties, Style, Weight};
+use font_kit::source::SystemSource;
+use pathfinder_geometry::line_segment::LineSegment2F;
+use pathfinder_geometry::vector::Vector2F;
+use ruffle_core::backend::font::FontProvider;
+use ruffle_core::swf::{self, ShapeRecord, StyleChangeData, Twips};
+
+/// The size of the EM square of a `DefineFont3` glyph, in twips.
+const EM_SQUARE: f32 = 20480.0;
+
+/// The characters converted from each system font.
+/// Converting every glyph of a large font up front would be slow, so only the characters that
+/// Western text commonly uses are included.
+const CHARACTER_RANGES: &[(u32, u32)] = &[
+    (0x20, 0x7e),
+    (0xa0, 0x17f),
+    (0x2010, 0x2027),
+    (0x2030, 0x203a),
+    (0x20ac, 0x20ac),
+    (0x2122, 0x2122),
+];
+
+pub struct DesktopFontProvider {
+    source: SystemSource,
+}
+
+impl DesktopFontProvider {
+    pub fn new() -> Self {
+        Self {
+            source: SystemSource::new(),
+        }
+    }
+}
+
+impl FontProvider for DesktopFontProvider {
+    fn load_device_font(
+        &mut self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<swf::Font> {
+        // Flash's device font aliases, falling back to a sans-serif font for missing families.
+        let family_names = match name {
+            "_sans" => vec![FamilyName::SansSerif],
+            "_serif" => vec![FamilyName::Serif, FamilyName::SansSerif],
+            "_typewriter" => vec![FamilyName::Monospace, FamilyName::SansSerif],
+            _ => vec![FamilyName::Title(name.to_string()), FamilyName::SansSerif],
+        };
+        let mut properties = Properties::new();
+        if is_bold {
+            properties.weight(Weight::BOLD);
+        }
+        if is_italic {
+            properties.style(Style::Italic);
+        }
+
+        let font = match self
+            .source
+            .select_best_match(&family_names, &properties)
+            .map_err(|e| e.to_string())
+            .and_then(|handle| handle.load().map_err(|e| e.to_string()))
+        {
+            Ok(font) => font,
+            Err(e) => {
+                log::warn!("Unable to load system font {}: {}", name, e);
+                return None;
+            }
+        };
+        log::info!("Using system font {} for {}", font.full_name(), name);
+
+        let metrics = font.metrics();
+        let scale = EM_SQUARE / metrics.units_per_em as f32;
+        let mut glyphs = vec![];
+        for c in CHARACTER_RANGES
+            .iter()
+            .flat_map(|&(start, end)| start..=end)
+            .filter_map(std::char::from_u32)
+        {
+            let glyph_id = match font.glyph_for_char(c) {
+                Some(glyph_id) => glyph_id,
+                None => continue,
+            };
+            let mut outline = GlyphOutline::new(scale);
+            if font
+                .outline(glyph_id, HintingOptions::None, &mut outline)
+                .is_err()
+            {
+                continue;
+            }
+            let advance = font
+                .advance(glyph_id)
+                .map(|advance| (advance.x() * scale).round().min(i16::MAX.into()) as i16)
+                .unwrap_or(0);
+            glyphs.push(swf::Glyph {
+                shape_records: outline.records,
+                code: c as u16,
+                advance: Some(advance),
+                bounds: None,
+            });
+        }
+
+        Some(swf::Font {
+            version: 3,
+            id: 0,
+            name: name.to_string(),
+            language: swf::Language::Unknown,
+            layout: Some(swf::FontLayout {
+                ascent: (metrics.ascent * scale) as u16,
+                descent: (-metrics.descent * scale) as u16,
+                leading: (metrics.line_gap * scale) as i16,
+                kerning: vec![],
+            }),
+            glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold,
+            is_italic,
+        })
+    }
+}
+
+/// Converts a glyph outline in font units into SWF shape records.
+struct GlyphOutline {
+    records: Vec<ShapeRecord>,
+    scale: f32,
+
+    /// The pen position, in twips.
+    pen: (i32, i32),
+
+    /// The start of the current contour, in twips.
+    contour_start: (i32, i32),
+}
+
+impl GlyphOutline {
+    fn new(scale: f32) -> Self {
+        Self {
+            records: vec![],
+            scale,
+            pen: (0, 0),
+            contour_start: (0, 0),
+        }
+    }
+
+    /// Converts a point to twips. Font units point up, while SWF coordinates point down.
+    fn to_twips(&self, point: Vector2F) -> (i32, i32) {
+        (
+            (point.x() * self.scale).round() as i32,
+            (-point.y() * self.scale).round() as i32,
+        )
+    }
+
+    fn straight_edge(&mut self, to: (i32, i32)) {
+        self.records.push(ShapeRecord::StraightEdge {
+            delta_x: Twips::new(to.0 - self.pen.0),
+            delta_y: Twips::new(to.1 - self.pen.1),
+        });
+        self.pen = to;
+    }
+
+    fn curved_edge(&mut self, control: (i32, i32), to: (i32, i32)) {
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(control.0 - self.pen.0),
+            control_delta_y: Twips::new(control.1 - self.pen.1),
+            anchor_delta_x: Twips::new(to.0 - control.0),
+            anchor_delta_y: Twips::new(to.1 - control.1),
+        });
+        self.pen = to;
+    }
+}
+
+impl OutlineSink for GlyphOutline {
+    fn move_to(&mut self, to: Vector2F) {
+        let to = self.to_twips(to);
+        // Glyphs are drawn with the font's only fill style, set by the first contour.
+        let fill_style_1 = if self.records.is_empty() {
+            Some(1)
+        } else {
+            None
+        };
+        self.records.push(ShapeRecord::StyleChange(StyleChangeData {
+            move_to: Some((Twips::new(to.0), Twips::new(to.1))),
+            fill_style_0: None,
+            fill_style_1,
+            line_style: None,
+            new_styles: None,
+        }));
+        self.pen = to;
+        self.contour_start = to;
+    }
+
+    fn line_to(&mut self, to: Vector2F) {
+        let to = self.to_twips(to);
+        self.straight_edge(to);
+    }
+
+    fn quadratic_curve_to(&mut self, ctrl: Vector2F, to: Vector2F) {
+        let control = self.to_twips(ctrl);
+        let to = self.to_twips(to);
+        self.curved_edge(control, to);
+    }
+
+    fn cubic_curve_to(&mut self, ctrl: LineSegment2F, to: Vector2F) {
+        // SWF shapes only have quadratic curves, so the cubic curve is split in half and each
+        // half is approximated by a quadratic curve.
+        let from = Vector2F::new(self.pen.0 as f32, -self.pen.1 as f32) * (1.0 / self.scale);
+        let (c1, c2) = (ctrl.from(), ctrl.to());
+        let mid_01 = (from + c1) * 0.5;
+        let mid_12 = (c1 + c2) * 0.5;
+        let mid_23 = (c2 + to) * 0.5;
+        let mid_012 = (mid_01 + mid_12) * 0.5;
+        let mid_123 = (mid_12 + mid_23) * 0.5;
+        let mid = (mid_012 + mid_123) * 0.5;
+        for &(p0, p1, p2, p3) in &[(from, mid_01, mid_012, mid), (mid, mid_123, mid_23, to)] {
+            let control = ((p1 + p2) * 3.0 - p0 - p3) * 0.25;
+            let control = self.to_twips(control);
+            let p3 = self.to_twips(p3);
+            self.curved_edge(control, p3);
+        }
+    }
+
+    fn close(&mut self) {
+        if self.pen != self.contour_start {
+            self.straight_edge(self.contour_start);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! FSCommand handling for the desktop player
+
+use crate::custom_event::RuffleEvent;
+use ruffle_core::external::FsCommandProvider;
+use winit::event_loop::EventLoopProxy;
+
+/// A standalone player FSCommand that affects the window or player.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FsCommand {
+    /// Enter or leave fullscreen mode.
+    Fullscreen(bool),
+
+    /// Show or hide the built-in context menu items.
+    ShowMenu(bool),
+
+    /// Scale the movie to the window, or show it at its original size.
+    AllowScale(bool),
+
+    /// Pass every key to the movie, including those used for player shortcuts.
+    TrapAllKeys(bool),
+
+    /// Close the player.
+    Quit,
+}
+
+impl FsCommand {
+    /// Parse one of the FSCommands understood by the standalone player.
+    pub fn parse(command: &str, args: &str) -> Option<Self> {
+        let enabled = args.eq_ignore_ascii_case("true");
+        Some(match command.to_ascii_lowercase().as_str() {
+            "fullscreen" => FsCommand::Fullscreen(enabled),
+            "showmenu" => FsCommand::ShowMenu(enabled),
+            "allowscale" => FsCommand::AllowScale(enabled),
+            "trapallkeys" => FsCommand::TrapAllKeys(enabled),
+            "quit" => FsCommand::Quit,
+            _ => return None,
+        })
+    }
+}
+
+/// Forwards standalone player FSCommands to the event loop, which owns the window.
+pub struct DesktopFsCommandProvider {
+    event_loop: EventLoopProxy<RuffleEvent>,
+}
+
+impl DesktopFsCommandProvider {
+    pub fn new(event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        Self { event_loop }
+    }
+}
+
+impl FsCommandProvider for DesktopFsCommandProvider {
+    fn on_fs_command(&self, command: &str, args: &str) -> bool {
+        if let Some(command) = FsCommand::parse(command, args) {
+            if self
+                .event_loop
+                .send_event(RuffleEvent::FsCommand(command))
+                .is_err()
+            {
+                log::warn!(
+                    "Unable to handle FSCommand {:?}: event loop closed",
+                    command
+                );
+            }
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -1,25 +1,25 @@
 use clipboard::{ClipboardContext, ClipboardProvider};
-use ruffle_core::backend::input::{InputBackend, MouseCursor};
-use ruffle_core::events::{KeyCode, PlayerEvent};
-use std::collections::HashSet;
+use image::RgbaImage;
+use ruffle_core::backend::input::{InputBackend, MouseCursor, PrintPageFormat, PrintedPage};
+use ruffle_core::events::{KeyCode, KeyLocation, PlayerEvent};
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use winit::event::{ElementState, VirtualKeyCode, WindowEvent};
 use winit::window::Window;
 
 pub struct WinitInputBackend {
-    keys_down: HashSet<VirtualKeyCode>,
     window: Rc<Window>,
     cursor_visible: bool,
-    last_key: KeyCode,
+    last_key_char: Option<char>,
     clipboard: ClipboardContext,
 }
 
 impl WinitInputBackend {
     pub fn new(window: Rc<Window>) -> Self {
         Self {
-            keys_down: HashSet::new(),
             cursor_visible: true,
-            last_key: KeyCode::Unknown,
+            last_key_char: None,
             window,
             clipboard: ClipboardProvider::new().unwrap(),
         }
@@ -31,28 +31,32 @@ impl WinitInputBackend {
             WindowEvent::KeyboardInput { input, .. } => match input.state {
                 ElementState::Pressed => {
                     if let Some(key) = input.virtual_keycode {
-                        self.keys_down.insert(key);
+                        // winit only sends the typed character after the key press, as a
+                        // separate `ReceivedCharacter` event.
+                        self.last_key_char = None;
                         if let Some(key_code) = winit_to_ruffle_key_code(key) {
-                            self.last_key = key_code;
-                            return Some(PlayerEvent::KeyDown { key_code });
-                        } else {
-                            self.last_key = KeyCode::Unknown;
+                            return Some(PlayerEvent::KeyDown {
+                                key_code,
+                                key_char: None,
+                                key_location: winit_key_location(key),
+                            });
                         }
                     }
                 }
                 ElementState::Released => {
                     if let Some(key) = input.virtual_keycode {
-                        self.keys_down.remove(&key);
                         if let Some(key_code) = winit_to_ruffle_key_code(key) {
-                            self.last_key = key_code;
-                            return Some(PlayerEvent::KeyUp { key_code });
-                        } else {
-                            self.last_key = KeyCode::Unknown;
+                            return Some(PlayerEvent::KeyUp {
+                                key_code,
+                                key_char: self.last_key_char,
+                                key_location: winit_key_location(key),
+                            });
                         }
                     }
                 }
             },
             WindowEvent::ReceivedCharacter(codepoint) => {
+                self.last_key_char = Some(codepoint);
                 return Some(PlayerEvent::TextInput { codepoint });
             }
             _ => (),
@@ -62,119 +66,6 @@ impl WinitInputBackend {
 }
 
 impl InputBackend for WinitInputBackend {
-    fn is_key_down(&self, key: KeyCode) -> bool {
-        match key {
-            KeyCode::Unknown => false,
-            KeyCode::Backspace => self.keys_down.contains(&VirtualKeyCode::Back),
-            KeyCode::Return => self.keys_down.contains(&VirtualKeyCode::Return),
-            KeyCode::Shift => {
-                self.keys_down.contains(&VirtualKeyCode::LShift)
-                    || self.keys_down.contains(&VirtualKeyCode::RShift)
-            }
-            KeyCode::Control => {
-                self.keys_down.contains(&VirtualKeyCode::LControl)
-                    || self.keys_down.contains(&VirtualKeyCode::RControl)
-            }
-            KeyCode::Alt => {
-                self.keys_down.contains(&VirtualKeyCode::LAlt)
-                    || self.keys_down.contains(&VirtualKeyCode::RAlt)
-            }
-            KeyCode::CapsLock => self.keys_down.contains(&VirtualKeyCode::Capital),
-            KeyCode::Escape => self.keys_down.contains(&VirtualKeyCode::Escape),
-            KeyCode::Space => self.keys_down.contains(&VirtualKeyCode::Space),
-            KeyCode::Key0 => self.keys_down.contains(&VirtualKeyCode::Key0),
-            KeyCode::Key1 => self.keys_down.contains(&VirtualKeyCode::Key1),
-            KeyCode::Key2 => self.keys_down.contains(&VirtualKeyCode::Key2),
-            KeyCode::Key3 => self.keys_down.contains(&VirtualKeyCode::Key3),
-            KeyCode::Key4 => self.keys_down.contains(&VirtualKeyCode::Key4),
-            KeyCode::Key5 => self.keys_down.contains(&VirtualKeyCode::Key5),
-            KeyCode::Key6 => self.keys_down.contains(&VirtualKeyCode::Key6),
-            KeyCode::Key7 => self.keys_down.contains(&VirtualKeyCode::Key7),
-            KeyCode::Key8 => self.keys_down.contains(&VirtualKeyCode::Key8),
-            KeyCode::Key9 => self.keys_down.contains(&VirtualKeyCode::Key9),
-            KeyCode::A => self.keys_down.contains(&VirtualKeyCode::A),
-            KeyCode::B => self.keys_down.contains(&VirtualKeyCode::B),
-            KeyCode::C => self.keys_down.contains(&VirtualKeyCode::C),
-            KeyCode::D => self.keys_down.contains(&VirtualKeyCode::D),
-            KeyCode::E => self.keys_down.contains(&VirtualKeyCode::E),
-            KeyCode::F => self.keys_down.contains(&VirtualKeyCode::F),
-            KeyCode::G => self.keys_down.contains(&VirtualKeyCode::G),
-            KeyCode::H => self.keys_down.contains(&VirtualKeyCode::H),
-            KeyCode::I => self.keys_down.contains(&VirtualKeyCode::I),
-            KeyCode::J => self.keys_down.contains(&VirtualKeyCode::J),
-            KeyCode::K => self.keys_down.contains(&VirtualKeyCode::K),
-            KeyCode::L => self.keys_down.contains(&VirtualKeyCode::L),
-            KeyCode::M => self.keys_down.contains(&VirtualKeyCode::M),
-            KeyCode::N => self.keys_down.contains(&VirtualKeyCode::N),
-            KeyCode::O => self.keys_down.contains(&VirtualKeyCode::O),
-            KeyCode::P => self.keys_down.contains(&VirtualKeyCode::P),
-            KeyCode::Q => self.keys_down.contains(&VirtualKeyCode::Q),
-            KeyCode::R => self.keys_down.contains(&VirtualKeyCode::R),
-            KeyCode::S => self.keys_down.contains(&VirtualKeyCode::S),
-            KeyCode::T => self.keys_down.contains(&VirtualKeyCode::T),
-            KeyCode::U => self.keys_down.contains(&VirtualKeyCode::U),
-            KeyCode::V => self.keys_down.contains(&VirtualKeyCode::V),
-            KeyCode::W => self.keys_down.contains(&VirtualKeyCode::W),
-            KeyCode::X => self.keys_down.contains(&VirtualKeyCode::X),
-            KeyCode::Y => self.keys_down.contains(&VirtualKeyCode::Y),
-            KeyCode::Z => self.keys_down.contains(&VirtualKeyCode::Z),
-            KeyCode::Semicolon => self.keys_down.contains(&VirtualKeyCode::Semicolon),
-            KeyCode::Equals => self.keys_down.contains(&VirtualKeyCode::Equals),
-            KeyCode::Comma => self.keys_down.contains(&VirtualKeyCode::Comma),
-            KeyCode::Minus => self.keys_down.contains(&VirtualKeyCode::Minus),
-            KeyCode::Period => self.keys_down.contains(&VirtualKeyCode::Period),
-            KeyCode::Slash => self.keys_down.contains(&VirtualKeyCode::Slash),
-            KeyCode::Grave => self.keys_down.contains(&VirtualKeyCode::Grave),
-            KeyCode::LBracket => self.keys_down.contains(&VirtualKeyCode::LBracket),
-            KeyCode::Backslash => self.keys_down.contains(&VirtualKeyCode::Backslash),
-            KeyCode::RBracket => self.keys_down.contains(&VirtualKeyCode::RBracket),
-            KeyCode::Apostrophe => self.keys_down.contains(&VirtualKeyCode::Apostrophe),
-            KeyCode::Numpad0 => self.keys_down.contains(&VirtualKeyCode::Numpad0),
-            KeyCode::Numpad1 => self.keys_down.contains(&VirtualKeyCode::Numpad1),
-            KeyCode::Numpad2 => self.keys_down.contains(&VirtualKeyCode::Numpad2),
-            KeyCode::Numpad3 => self.keys_down.contains(&VirtualKeyCode::Numpad3),
-            KeyCode::Numpad4 => self.keys_down.contains(&VirtualKeyCode::Numpad4),
-            KeyCode::Numpad5 => self.keys_down.contains(&VirtualKeyCode::Numpad5),
-            KeyCode::Numpad6 => self.keys_down.contains(&VirtualKeyCode::Numpad6),
-            KeyCode::Numpad7 => self.keys_down.contains(&VirtualKeyCode::Numpad7),
-            KeyCode::Numpad8 => self.keys_down.contains(&VirtualKeyCode::Numpad8),
-            KeyCode::Numpad9 => self.keys_down.contains(&VirtualKeyCode::Numpad9),
-            KeyCode::Multiply => self.keys_down.contains(&VirtualKeyCode::Multiply),
-            KeyCode::Plus => self.keys_down.contains(&VirtualKeyCode::Add),
-            KeyCode::NumpadMinus => self.keys_down.contains(&VirtualKeyCode::Subtract),
-            KeyCode::NumpadPeriod => self.keys_down.contains(&VirtualKeyCode::Decimal),
-            KeyCode::NumpadSlash => self.keys_down.contains(&VirtualKeyCode::Divide),
-            KeyCode::PgUp => self.keys_down.contains(&VirtualKeyCode::PageUp),
-            KeyCode::PgDown => self.keys_down.contains(&VirtualKeyCode::PageDown),
-            KeyCode::End => self.keys_down.contains(&VirtualKeyCode::End),
-            KeyCode::Home => self.keys_down.contains(&VirtualKeyCode::Home),
-            KeyCode::Left => self.keys_down.contains(&VirtualKeyCode::Left),
-            KeyCode::Up => self.keys_down.contains(&VirtualKeyCode::Up),
-            KeyCode::Right => self.keys_down.contains(&VirtualKeyCode::Right),
-            KeyCode::Down => self.keys_down.contains(&VirtualKeyCode::Down),
-            KeyCode::Insert => self.keys_down.contains(&VirtualKeyCode::Insert),
-            KeyCode::Delete => self.keys_down.contains(&VirtualKeyCode::Delete),
-            KeyCode::Pause => self.keys_down.contains(&VirtualKeyCode::Pause),
-            KeyCode::ScrollLock => self.keys_down.contains(&VirtualKeyCode::Scroll),
-            KeyCode::F1 => self.keys_down.contains(&VirtualKeyCode::F1),
-            KeyCode::F2 => self.keys_down.contains(&VirtualKeyCode::F2),
-            KeyCode::F3 => self.keys_down.contains(&VirtualKeyCode::F3),
-            KeyCode::F4 => self.keys_down.contains(&VirtualKeyCode::F4),
-            KeyCode::F5 => self.keys_down.contains(&VirtualKeyCode::F5),
-            KeyCode::F6 => self.keys_down.contains(&VirtualKeyCode::F6),
-            KeyCode::F7 => self.keys_down.contains(&VirtualKeyCode::F7),
-            KeyCode::F8 => self.keys_down.contains(&VirtualKeyCode::F8),
-            KeyCode::F9 => self.keys_down.contains(&VirtualKeyCode::F9),
-            KeyCode::F10 => self.keys_down.contains(&VirtualKeyCode::F10),
-            KeyCode::F11 => self.keys_down.contains(&VirtualKeyCode::F11),
-            KeyCode::F12 => self.keys_down.contains(&VirtualKeyCode::F12),
-        }
-    }
-
-    fn get_last_key_code(&self) -> KeyCode {
-        self.last_key
-    }
-
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
     }
@@ -196,12 +87,93 @@ impl InputBackend for WinitInputBackend {
             MouseCursor::Hand => CursorIcon::Hand,
             MouseCursor::IBeam => CursorIcon::Text,
             MouseCursor::Grab => CursorIcon::Grab,
+            // TODO: winit can't display custom cursor images yet.
+            MouseCursor::Custom(_) => CursorIcon::Arrow,
         };
         self.window.set_cursor_icon(icon);
     }
 
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.get_contents().unwrap_or_else(|e| {
+            log::warn!("Couldn't get the clipboard contents: {}", e);
+            String::new()
+        })
+    }
+
     fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard.set_contents(content).unwrap();
+        if let Err(e) = self.clipboard.set_contents(content) {
+            log::warn!("Couldn't set the clipboard contents: {}", e);
+        }
+    }
+
+    fn start_print_job(&mut self) -> Option<PrintPageFormat> {
+        // There is no print dialog; pages are saved as images instead.
+        Some(PrintPageFormat::default())
+    }
+
+    fn print_pages(&mut self, pages: Vec<PrintedPage>) {
+        let dir = dirs::picture_dir()
+            .or_else(dirs::document_dir)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+
+        for (i, page) in pages.into_iter().enumerate() {
+            let mut image = match RgbaImage::from_raw(page.width, page.height, page.pixels) {
+                Some(image) => image,
+                None => continue,
+            };
+            // Print onto white paper.
+            for pixel in image.pixels_mut() {
+                let alpha = u16::from(pixel[3]);
+                for channel in 0..3 {
+                    let color = u16::from(pixel[channel]);
+                    pixel[channel] = ((color * alpha + 255 * (255 - alpha)) / 255) as u8;
+                }
+                pixel[3] = 255;
+            }
+
+            let path = dir.join(format!("ruffle-print-{}-{}.png", timestamp, i + 1));
+            match image.save(&path) {
+                Ok(()) => log::info!("Saved printed page to {}", path.display()),
+                Err(e) => log::warn!("Couldn't save printed page to {}: {}", path.display(), e),
+            }
+        }
+    }
+}
+
+/// Returns where on the keyboard a winit `VirtualKeyCode` is.
+fn winit_key_location(key_code: VirtualKeyCode) -> KeyLocation {
+    match key_code {
+        VirtualKeyCode::LShift
+        | VirtualKeyCode::LControl
+        | VirtualKeyCode::LAlt
+        | VirtualKeyCode::LWin => KeyLocation::Left,
+        VirtualKeyCode::RShift
+        | VirtualKeyCode::RControl
+        | VirtualKeyCode::RAlt
+        | VirtualKeyCode::RWin => KeyLocation::Right,
+        VirtualKeyCode::Numpad0
+        | VirtualKeyCode::Numpad1
+        | VirtualKeyCode::Numpad2
+        | VirtualKeyCode::Numpad3
+        | VirtualKeyCode::Numpad4
+        | VirtualKeyCode::Numpad5
+        | VirtualKeyCode::Numpad6
+        | VirtualKeyCode::Numpad7
+        | VirtualKeyCode::Numpad8
+        | VirtualKeyCode::Numpad9
+        | VirtualKeyCode::Add
+        | VirtualKeyCode::Subtract
+        | VirtualKeyCode::Multiply
+        | VirtualKeyCode::Divide
+        | VirtualKeyCode::Decimal
+        | VirtualKeyCode::NumpadEnter
+        | VirtualKeyCode::NumpadComma
+        | VirtualKeyCode::NumpadEquals => KeyLocation::NumPad,
+        _ => KeyLocation::Standard,
     }
 }
 
@@ -210,6 +182,7 @@ impl InputBackend for WinitInputBackend {
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
     let out = match key_code {
         VirtualKeyCode::Back => KeyCode::Backspace,
+        VirtualKeyCode::Tab => KeyCode::Tab,
         VirtualKeyCode::Return => KeyCode::Return,
         VirtualKeyCode::LShift | VirtualKeyCode::RShift => KeyCode::Shift,
         VirtualKeyCode::LControl | VirtualKeyCode::RControl => KeyCode::Control,
@@ -290,6 +263,7 @@ fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> Option<KeyCode> {
         VirtualKeyCode::Insert => KeyCode::Insert,
         VirtualKeyCode::Delete => KeyCode::Delete,
         VirtualKeyCode::Pause => KeyCode::Pause,
+        VirtualKeyCode::Numlock => KeyCode::NumLock,
         VirtualKeyCode::Scroll => KeyCode::ScrollLock,
         VirtualKeyCode::F1 => KeyCode::F1,
         VirtualKeyCode::F2 => KeyCode::F2,
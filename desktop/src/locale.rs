@@ -1,5 +1,5 @@
 use ruffle_core::backend::locale::LocaleBackend;
-use ruffle_core::chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use ruffle_core::chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
 
 pub struct DesktopLocaleBackend();
 
@@ -17,4 +17,8 @@ impl LocaleBackend for DesktopLocaleBackend {
     fn get_timezone(&self) -> FixedOffset {
         Local::now().offset().fix()
     }
+
+    fn get_timezone_at(&self, date: DateTime<Utc>) -> FixedOffset {
+        Local.offset_from_utc_datetime(&date.naive_utc()).fix()
+    }
 }
@@ -3,6 +3,8 @@
 mod audio;
 mod custom_event;
 mod executor;
+mod font;
+mod fscommand;
 mod input;
 mod locale;
 mod navigator;
@@ -11,25 +13,31 @@ mod task;
 
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
+use crate::fscommand::{DesktopFsCommandProvider, FsCommand};
 use clap::Clap;
 use ruffle_core::{
     backend::audio::{AudioBackend, NullAudioBackend},
-    Player,
+    backend::video::NullVideoBackend,
+    BackgroundThrottle, Language, Player, PlayerType, StageAlign, StageQuality, StageScaleMode,
 };
 use ruffle_render_wgpu::WgpuRenderBackend;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use crate::storage::DiskStorageBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use std::rc::Rc;
+use walkdir::WalkDir;
 use winit::dpi::{LogicalSize, PhysicalPosition};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
-use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::{Icon, WindowBuilder};
+use winit::event::{
+    ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, VirtualKeyCode,
+    WindowEvent,
+};
+use winit::event_loop::{ControlFlow, EventLoop, EventLoopProxy};
+use winit::window::{Fullscreen, Icon, Window, WindowBuilder};
 
-#[derive(Clap, PartialEq, Debug)]
+#[derive(Clap, Clone, Copy, PartialEq, Debug)]
 pub enum GraphicsBackend {
     Default,
     Vulkan,
@@ -50,7 +58,7 @@ impl From<GraphicsBackend> for ruffle_render_wgpu::wgpu::BackendBit {
     }
 }
 
-#[derive(Clap, PartialEq, Debug)]
+#[derive(Clap, Clone, Copy, PartialEq, Debug)]
 pub enum PowerPreference {
     Default = 0,
     Low = 1,
@@ -67,6 +75,50 @@ impl From<PowerPreference> for ruffle_render_wgpu::wgpu::PowerPreference {
     }
 }
 
+#[derive(Clap, Clone, Copy, PartialEq, Debug)]
+pub enum ScaleMode {
+    ShowAll,
+    ExactFit,
+    NoBorder,
+    NoScale,
+}
+
+impl From<ScaleMode> for StageScaleMode {
+    fn from(scale_mode: ScaleMode) -> Self {
+        match scale_mode {
+            ScaleMode::ShowAll => StageScaleMode::ShowAll,
+            ScaleMode::ExactFit => StageScaleMode::ExactFit,
+            ScaleMode::NoBorder => StageScaleMode::NoBorder,
+            ScaleMode::NoScale => StageScaleMode::NoScale,
+        }
+    }
+}
+
+#[derive(Clap, Clone, Copy, PartialEq, Debug)]
+pub enum Quality {
+    Low,
+    Medium,
+    High,
+    Best,
+}
+
+impl From<Quality> for StageQuality {
+    fn from(quality: Quality) -> Self {
+        match quality {
+            Quality::Low => StageQuality::Low,
+            Quality::Medium => StageQuality::Medium,
+            Quality::High => StageQuality::High,
+            Quality::Best => StageQuality::Best,
+        }
+    }
+}
+
+#[derive(Clap, Clone, Copy, PartialEq, Debug)]
+pub enum Letterbox {
+    On,
+    Off,
+}
+
 #[derive(Clap, Debug)]
 #[clap(
     name = "Ruffle",
@@ -78,6 +130,11 @@ struct Opt {
     #[clap(name = "FILE", parse(from_os_str))]
     input_path: PathBuf,
 
+    /// Treat FILE as a directory, and play every movie (swf) inside it and its subdirectories.
+    /// Page Down and Page Up switch to the next and previous movie.
+    #[clap(long)]
+    directory: bool,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(
@@ -101,6 +158,205 @@ struct Opt {
         arg_enum
     )]
     power: PowerPreference,
+
+    /// How the movie is scaled to fit the window, until the movie changes it with `Stage.scaleMode`.
+    #[clap(long, case_insensitive = true, default_value = "showall", arg_enum)]
+    scale_mode: ScaleMode,
+
+    /// Where the stage is anchored in the window, until the movie changes it with `Stage.align`.
+    /// Written like `Stage.align`, as any of the letters T, B, L and R, e.g. `TL` for the top left.
+    #[clap(long, default_value = "", parse(try_from_str = parse_align))]
+    align: String,
+
+    /// Whether the margins around the stage are hidden when the movie is shown in full.
+    #[clap(long, case_insensitive = true, default_value = "on", arg_enum)]
+    letterbox: Letterbox,
+
+    /// How finely the movie is rendered, until the movie changes it with `_quality`.
+    #[clap(long, case_insensitive = true, default_value = "high", arg_enum)]
+    quality: Quality,
+
+    /// The version of Flash Player to report to the movie, such as through `$version`.
+    #[clap(long)]
+    player_version: Option<u8>,
+
+    /// Play the movie at this many frames per second, instead of its own frame rate.
+    #[clap(long, parse(try_from_str = parse_frame_rate))]
+    frame_rate: Option<f64>,
+
+    /// Directory to load relative URLs from, instead of the movie's own directory.
+    #[clap(long, parse(from_os_str))]
+    base: Option<PathBuf>,
+
+    /// URL to report as the movie's own, such as through `_url`, instead of its file path.
+    #[clap(long)]
+    spoof_url: Option<url::Url>,
+
+    /// Font file to use for device text in the given family, instead of the system font.
+    /// Written as `NAME=PATH`, where `NAME` may be followed by `:bold` and/or `:italic` to
+    /// register that style of the family. May be given multiple times.
+    #[clap(long = "font", number_of_values = 1, parse(try_from_str = parse_font_arg))]
+    fonts: Vec<FontArg>,
+
+    /// Report the player as a debugger player through `Capabilities.isDebugger`.
+    /// AS3 errors then keep the call stack they were created in, for `Error.getStackTrace`.
+    #[clap(long)]
+    debugger: bool,
+
+    /// Record the mouse and keyboard input and the timing of the session to this file,
+    /// so that it can be played back with `--replay`.
+    #[clap(long, parse(from_os_str), conflicts_with_all = &["replay", "directory"])]
+    record: Option<PathBuf>,
+
+    /// Play back a session recorded with `--record`, instead of taking input from the window.
+    #[clap(long, parse(from_os_str), conflicts_with = "directory")]
+    replay: Option<PathBuf>,
+
+    /// Measure how long the GPU spends on each frame, where the graphics device supports it.
+    #[clap(long)]
+    gpu_timing: bool,
+}
+
+/// A font file given on the command line.
+#[derive(Debug)]
+struct FontArg {
+    name: String,
+    path: PathBuf,
+    is_bold: bool,
+    is_italic: bool,
+}
+
+fn parse_font_arg(arg: &str) -> Result<FontArg, String> {
+    let mut parts = arg.splitn(2, '=');
+    let (name, path) = match (parts.next(), parts.next()) {
+        (Some(name), Some(path)) if !path.is_empty() => (name, path),
+        _ => return Err("expected NAME=PATH".to_string()),
+    };
+
+    let mut styles = name.split(':');
+    let name = styles.next().unwrap_or_default();
+    if name.is_empty() {
+        return Err("font name is empty".to_string());
+    }
+    let mut is_bold = false;
+    let mut is_italic = false;
+    for style in styles {
+        match style {
+            "bold" => is_bold = true,
+            "italic" => is_italic = true,
+            _ => return Err(format!("unknown font style {}", style)),
+        }
+    }
+
+    Ok(FontArg {
+        name: name.to_string(),
+        path: PathBuf::from(path),
+        is_bold,
+        is_italic,
+    })
+}
+
+fn parse_align(arg: &str) -> Result<String, String> {
+    if let Some(c) = arg.chars().find(|c| !"TBLRtblr".contains(*c)) {
+        return Err(format!("unknown edge {}, expected any of T, B, L and R", c));
+    }
+    Ok(arg.to_string())
+}
+
+fn parse_frame_rate(arg: &str) -> Result<f64, String> {
+    match arg.parse::<f64>() {
+        Ok(frame_rate) if frame_rate > 0.0 && frame_rate.is_finite() => Ok(frame_rate),
+        _ => Err("expected a positive number of frames per second".to_string()),
+    }
+}
+
+/// Loads a movie from disk, reporting `spoof_url` as its URL if given.
+fn load_movie(
+    path: &Path,
+    spoof_url: Option<&url::Url>,
+) -> Result<SwfMovie, Box<dyn std::error::Error>> {
+    match spoof_url {
+        Some(url) => SwfMovie::from_data(&std::fs::read(path)?, Some(url.to_string())),
+        None => SwfMovie::from_path(path),
+    }
+}
+
+/// The movies played during a session, in the order they are played.
+struct Playlist {
+    files: Vec<PathBuf>,
+    current: usize,
+}
+
+impl Playlist {
+    /// A playlist of a single movie.
+    fn single(path: PathBuf) -> Self {
+        Self {
+            files: vec![path],
+            current: 0,
+        }
+    }
+
+    /// A playlist of every movie in the given directory and its subdirectories, sorted by path.
+    fn from_directory(root: &Path) -> Self {
+        let mut files: Vec<PathBuf> = WalkDir::new(root)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && entry
+                        .path()
+                        .extension()
+                        .map_or(false, |ext| ext.eq_ignore_ascii_case("swf"))
+            })
+            .map(|entry| entry.into_path())
+            .collect();
+        files.sort();
+        Self { files, current: 0 }
+    }
+
+    fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    fn current_path(&self) -> &Path {
+        &self.files[self.current]
+    }
+
+    /// Loads the first movie that parses successfully, starting at `start` and moving through
+    /// the playlist by `step`, wrapping around at either end.
+    ///
+    /// Movies that fail to parse are logged and skipped. Returns `None` if no movie could be
+    /// loaded.
+    fn load_from(
+        &mut self,
+        start: isize,
+        step: isize,
+        spoof_url: Option<&url::Url>,
+    ) -> Option<SwfMovie> {
+        let len = self.files.len() as isize;
+        let mut index = start;
+        for _ in 0..len {
+            let i = index.rem_euclid(len) as usize;
+            match load_movie(&self.files[i], spoof_url) {
+                Ok(movie) => {
+                    self.current = i;
+                    return Some(movie);
+                }
+                Err(e) => log::error!("Skipping {}: {}", self.files[i].display(), e),
+            }
+            index += step;
+        }
+        None
+    }
+}
+
+/// A player for a single movie, along with the executor running its tasks.
+///
+/// Every movie gets its own session, so dropping it tears down all of the movie's backends.
+struct PlayerSession {
+    player: Arc<Mutex<Player>>,
+    executor: Arc<Mutex<GlutinAsyncExecutor>>,
 }
 
 fn main() {
@@ -110,7 +366,7 @@ fn main() {
 
     let opt = Opt::parse();
 
-    let ret = run_player(opt.input_path, opt.graphics, opt.power);
+    let ret = run_player(opt);
 
     if let Err(e) = ret {
         eprintln!("Fatal error:\n{}", e);
@@ -118,29 +374,22 @@ fn main() {
     }
 }
 
-fn run_player(
-    input_path: PathBuf,
-    graphics: GraphicsBackend,
-    power_preference: PowerPreference,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let movie = SwfMovie::from_path(&input_path)?;
-    let movie_size = LogicalSize::new(movie.width(), movie.height());
-
-    let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
-    let icon = Icon::from_rgba(icon_bytes.to_vec(), 32, 32)?;
-
-    let event_loop: EventLoop<RuffleEvent> = EventLoop::with_user_event();
-    let window = Rc::new(
-        WindowBuilder::new()
-            .with_title(format!(
-                "Ruffle - {}",
-                input_path.file_name().unwrap_or_default().to_string_lossy()
-            ))
-            .with_window_icon(Some(icon))
-            .with_inner_size(movie_size)
-            .build(&event_loop)?,
-    );
-    let viewport_size = movie_size.to_physical(window.scale_factor());
+/// Creates a player for the given movie, rendering to `window`.
+///
+/// The window is retitled and resized to fit the movie.
+fn create_player(
+    movie: SwfMovie,
+    movie_path: &Path,
+    window: &Rc<Window>,
+    event_loop: &EventLoopProxy<RuffleEvent>,
+    opt: &Opt,
+) -> Result<PlayerSession, Box<dyn std::error::Error>> {
+    let file_name = movie_path.file_name().unwrap_or_default();
+    window.set_title(&format!("Ruffle - {}", file_name.to_string_lossy()));
+    if window.fullscreen().is_none() {
+        window.set_inner_size(LogicalSize::new(movie.width(), movie.height()));
+    }
+    let viewport_size = window.inner_size();
 
     let audio: Box<dyn AudioBackend> = match audio::CpalAudioBackend::new() {
         Ok(audio) => Box::new(audio),
@@ -152,24 +401,76 @@ fn run_player(
     let renderer = Box::new(WgpuRenderBackend::for_window(
         window.as_ref(),
         (viewport_size.width, viewport_size.height),
-        graphics.into(),
-        power_preference.into(),
+        opt.graphics.into(),
+        opt.power.into(),
+        opt.gpu_timing,
     )?);
-    let (executor, chan) = GlutinAsyncExecutor::new(event_loop.create_proxy());
+    let (executor, chan) = GlutinAsyncExecutor::new(event_loop.clone());
+    let base_path = match &opt.base {
+        Some(base) => base.as_path(),
+        None => movie_path.parent().unwrap_or_else(|| Path::new("")),
+    };
     let navigator = Box::new(navigator::ExternalNavigatorBackend::with_base_path(
-        input_path
-            .parent()
-            .unwrap_or_else(|| std::path::Path::new("")),
+        base_path,
         chan,
-        event_loop.create_proxy(),
+        event_loop.clone(),
     )); //TODO: actually implement this backend type
     let input = Box::new(input::WinitInputBackend::new(window.clone()));
-    let storage = Box::new(DiskStorageBackend::new(
-        input_path.file_name().unwrap_or_default().as_ref(),
-    ));
+    let storage = Box::new(DiskStorageBackend::new(file_name.as_ref()));
     let locale = Box::new(locale::DesktopLocaleBackend::new());
-    let player = Player::new(renderer, audio, navigator, input, storage, locale)?;
+    let font_provider = Box::new(font::DesktopFontProvider::new());
+    let video = Box::new(NullVideoBackend::new());
+    let player = Player::new(
+        renderer,
+        audio,
+        navigator,
+        input,
+        storage,
+        locale,
+        font_provider,
+        video,
+    )?;
+    {
+        let mut player_lock = player.lock().unwrap();
+        player_lock.set_player_type(PlayerType::StandAlone);
+        player_lock.set_is_debugger(opt.debugger);
+        if let Some(player_version) = opt.player_version {
+            player_lock.set_player_version(player_version);
+        }
+        player_lock.set_scale_mode(opt.scale_mode.into());
+        player_lock.set_stage_align(StageAlign::parse(&opt.align));
+        player_lock.set_letterbox_enabled(opt.letterbox == Letterbox::On);
+        player_lock.set_quality(opt.quality.into());
+        let monitor_size = window.current_monitor().size();
+        player_lock.set_screen_resolution(monitor_size.width, monitor_size.height);
+        if let Ok(lang) = std::env::var("LANG") {
+            player_lock.set_language(Language::from_locale(&lang));
+        }
+        player_lock
+            .set_fs_command_provider(Box::new(DesktopFsCommandProvider::new(event_loop.clone())));
+        for font in &opt.fonts {
+            let data = std::fs::read(&font.path)?;
+            player_lock
+                .register_device_font(&font.name, &data, font.is_bold, font.is_italic)
+                .map_err(|e| format!("Unable to load font {}: {}", font.path.display(), e))?;
+        }
+        if let Some(path) = &opt.record {
+            let file = std::fs::File::create(path)?;
+            player_lock
+                .start_recording(Box::new(std::io::BufWriter::new(file)))
+                .map_err(|e| format!("Unable to record to {}: {}", path.display(), e))?;
+        }
+        if let Some(path) = &opt.replay {
+            let file = std::fs::File::open(path)?;
+            player_lock
+                .replay(std::io::BufReader::new(file))
+                .map_err(|e| format!("Unable to replay {}: {}", path.display(), e))?;
+        }
+    }
     player.lock().unwrap().set_root_movie(Arc::new(movie));
+    if let Some(frame_rate) = opt.frame_rate {
+        player.lock().unwrap().set_frame_rate(frame_rate);
+    }
     player.lock().unwrap().set_is_playing(true); // Desktop player will auto-play.
 
     player
@@ -177,15 +478,62 @@ fn run_player(
         .unwrap()
         .set_viewport_dimensions(viewport_size.width, viewport_size.height);
 
+    Ok(PlayerSession { player, executor })
+}
+
+fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
+    let (mut playlist, movie) = if opt.directory {
+        let mut playlist = Playlist::from_directory(&opt.input_path);
+        let movie = playlist
+            .load_from(0, 1, opt.spoof_url.as_ref())
+            .ok_or_else(|| format!("No playable movies found in {}", opt.input_path.display()))?;
+        (playlist, movie)
+    } else {
+        let movie = load_movie(&opt.input_path, opt.spoof_url.as_ref())?;
+        (Playlist::single(opt.input_path.clone()), movie)
+    };
+    let movie_size = LogicalSize::new(movie.width(), movie.height());
+
+    let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
+    let icon = Icon::from_rgba(icon_bytes.to_vec(), 32, 32)?;
+
+    let event_loop: EventLoop<RuffleEvent> = EventLoop::with_user_event();
+    let window = Rc::new(
+        WindowBuilder::new()
+            .with_title("Ruffle")
+            .with_window_icon(Some(icon))
+            .with_inner_size(movie_size)
+            .build(&event_loop)?,
+    );
+    let proxy = event_loop.create_proxy();
+    let mut session = Some(create_player(
+        movie,
+        playlist.current_path(),
+        &window,
+        &proxy,
+        &opt,
+    )?);
+
     let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
     let mut time = Instant::now();
     let mut next_frame_time = Instant::now();
+    let mut trap_all_keys = false;
+    let mut modifiers = ModifiersState::empty();
     loop {
         // Poll UI events
         event_loop.run(move |event, _window_target, control_flow| {
+            let (player, executor) = match &session {
+                Some(session) => (session.player.clone(), session.executor.clone()),
+                None => return,
+            };
+
             match event {
                 winit::event::Event::LoopDestroyed => {
-                    player.lock().unwrap().flush_shared_objects();
+                    let mut player_lock = player.lock().unwrap();
+                    player_lock.flush_shared_objects();
+                    if let Err(e) = player_lock.stop_recording() {
+                        log::error!("Unable to write recording: {}", e);
+                    }
                     return;
                 }
 
@@ -210,6 +558,14 @@ fn run_player(
                 winit::event::Event::WindowEvent { event, .. } => match event {
                     WindowEvent::Resized(size) => {
                         let mut player_lock = player.lock().unwrap();
+                        // Minimized windows are resized to nothing.
+                        player_lock.set_background_throttle(
+                            if size.width == 0 || size.height == 0 {
+                                BackgroundThrottle::Hidden
+                            } else {
+                                BackgroundThrottle::Visible
+                            },
+                        );
                         player_lock.set_viewport_dimensions(size.width, size.height);
                         player_lock
                             .renderer_mut()
@@ -271,6 +627,85 @@ fn run_player(
                         }
                     }
                     WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+                    // Escape leaves fullscreen, unless the movie has asked for all keys.
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::Escape),
+                                ..
+                            },
+                        ..
+                    } if !trap_all_keys && window.fullscreen().is_some() => {
+                        window.set_fullscreen(None)
+                    }
+                    WindowEvent::ModifiersChanged(new_modifiers) => modifiers = new_modifiers,
+                    // Ctrl+Shift+F toggles the diagnostics overlay.
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::F),
+                                ..
+                            },
+                        ..
+                    } if modifiers.ctrl() && modifiers.shift() => {
+                        let mut player_lock = player.lock().unwrap();
+                        let show_overlay = !player_lock.debug_overlay();
+                        player_lock.set_debug_overlay(show_overlay);
+                        window.request_redraw();
+                    }
+                    // Page Down and Page Up switch between the movies in the playlist.
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(key),
+                                ..
+                            },
+                        ..
+                    } if playlist.len() > 1
+                        && (key == VirtualKeyCode::PageDown || key == VirtualKeyCode::PageUp) =>
+                    {
+                        let step = if key == VirtualKeyCode::PageDown {
+                            1
+                        } else {
+                            -1
+                        };
+                        if let Some(movie) = playlist.load_from(
+                            playlist.current as isize + step,
+                            step,
+                            opt.spoof_url.as_ref(),
+                        ) {
+                            player.lock().unwrap().flush_shared_objects();
+
+                            // Drop the old player and all of its backends before creating the
+                            // next one, so that nothing from the previous movie is kept alive.
+                            drop(player);
+                            drop(executor);
+                            session = None;
+
+                            match create_player(
+                                movie,
+                                playlist.current_path(),
+                                &window,
+                                &proxy,
+                                &opt,
+                            ) {
+                                Ok(new_session) => session = Some(new_session),
+                                Err(e) => {
+                                    log::error!(
+                                        "Unable to play {}: {}",
+                                        playlist.current_path().display(),
+                                        e
+                                    );
+                                    *control_flow = ControlFlow::Exit;
+                                }
+                            }
+                            time = Instant::now();
+                            window.request_redraw();
+                        }
+                    }
                     WindowEvent::KeyboardInput { .. } | WindowEvent::ReceivedCharacter(_) => {
                         let mut player_lock = player.lock().unwrap();
                         if let Some(event) = player_lock
@@ -291,6 +726,20 @@ fn run_player(
                     .lock()
                     .expect("active executor reference")
                     .poll_all(),
+                winit::event::Event::UserEvent(RuffleEvent::FsCommand(command)) => match command {
+                    FsCommand::Fullscreen(true) => window
+                        .set_fullscreen(Some(Fullscreen::Borderless(window.current_monitor()))),
+                    FsCommand::Fullscreen(false) => window.set_fullscreen(None),
+                    FsCommand::ShowMenu(show_menu) => {
+                        player.lock().unwrap().set_show_menu(show_menu)
+                    }
+                    FsCommand::AllowScale(allow_scale) => {
+                        player.lock().unwrap().set_allow_scale(allow_scale);
+                        window.request_redraw();
+                    }
+                    FsCommand::TrapAllKeys(trap) => trap_all_keys = trap,
+                    FsCommand::Quit => *control_flow = ControlFlow::Exit,
+                },
                 _ => (),
             }
 
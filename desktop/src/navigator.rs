@@ -6,10 +6,14 @@ use ruffle_core::backend::navigator::{
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use std::borrow::Cow;
 use std::fs;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{Receiver, Sender};
+use std::thread;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -139,4 +143,63 @@ impl NavigatorBackend for ExternalNavigatorBackend {
             url.into()
         }
     }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        thread::spawn(move || {
+            let stream = match TcpStream::connect((host.as_str(), port)) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Could not connect to {}:{}: {}", host, port, e);
+                    let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+                    return;
+                }
+            };
+            let mut read_stream = match stream.try_clone() {
+                Ok(read_stream) => read_stream,
+                Err(e) => {
+                    log::warn!("Could not connect to {}:{}: {}", host, port, e);
+                    let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Failed));
+                    return;
+                }
+            };
+            let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Connected));
+
+            // Incoming data is read on its own thread, while this one writes.
+            let read_sender = sender.clone();
+            thread::spawn(move || {
+                let mut buffer = [0; 4096];
+                loop {
+                    match read_stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => {
+                            let _ = read_sender.send(SocketAction::Close(handle));
+                            break;
+                        }
+                        Ok(len) => {
+                            let data = buffer[..len].to_vec();
+                            if read_sender.send(SocketAction::Data(handle, data)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            });
+
+            let mut write_stream = stream;
+            for data in receiver {
+                if write_stream.write_all(&data).is_err() {
+                    break;
+                }
+            }
+
+            // Either the movie closed the socket, or writing failed.
+            let _ = write_stream.shutdown(Shutdown::Both);
+        });
+    }
 }
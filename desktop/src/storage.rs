@@ -51,6 +51,13 @@ impl StorageBackend for DiskStorageBackend {
     fn put_string(&mut self, name: &str, value: String) -> bool {
         let full_path = self.base_path.join(Path::new(name));
 
+        // AVM2 shared objects are stored in a directory for their local path.
+        if let Some(parent) = full_path.parent() {
+            if let Err(r) = fs::create_dir_all(parent) {
+                log::warn!("Unable to create storage dir {}", r);
+            }
+        }
+
         match File::create(full_path) {
             Ok(mut file) => {
                 if let Err(r) = file.write_all(value.as_bytes()) {
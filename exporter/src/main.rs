@@ -3,14 +3,16 @@ use futures::executor::block_on;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::font::NullFontProvider;
 use ruffle_core::backend::input::NullInputBackend;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
+use ruffle_core::backend::video::NullVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
 use ruffle_render_wgpu::target::TextureTarget;
-use ruffle_render_wgpu::WgpuRenderBackend;
+use ruffle_render_wgpu::{WgpuRenderBackend, MAX_TEXTURE_DIMENSION};
 use std::error::Error;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
@@ -81,14 +83,23 @@ fn take_screenshot(
     let height = size.height.unwrap_or_else(|| movie.height());
     let height = (height as f32 * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&device, (width, height));
+    // Captures larger than a single texture are rendered in tiles, see `capture_frame`.
+    let target = TextureTarget::new(
+        &device,
+        (
+            width.min(MAX_TEXTURE_DIMENSION),
+            height.min(MAX_TEXTURE_DIMENSION),
+        ),
+    );
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(device, queue, target)?),
+        Box::new(WgpuRenderBackend::new(device, queue, target, false)?),
         Box::new(NullAudioBackend::new()),
         Box::new(NullNavigatorBackend::new()),
         Box::new(NullInputBackend::new()),
         Box::new(MemoryStorageBackend::default()),
         Box::new(NullLocaleBackend::new()),
+        Box::new(NullFontProvider::new()),
+        Box::new(NullVideoBackend::new()),
     )?;
 
     player
@@ -110,14 +121,7 @@ fn take_screenshot(
         }
         player.lock().unwrap().run_frame();
         if i >= skipframes {
-            player.lock().unwrap().render();
-            let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
+            if let Some(image) = capture_frame(&mut player.lock().unwrap(), width, height) {
                 result.push(image);
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
@@ -132,6 +136,37 @@ fn take_screenshot(
     Ok(result)
 }
 
+fn renderer(player: &mut Player) -> &mut WgpuRenderBackend<TextureTarget> {
+    player
+        .renderer_mut()
+        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+        .unwrap()
+}
+
+/// Renders the current frame of the player and reads it back.
+///
+/// Frames larger than the largest texture are rendered one tile at a time into the same
+/// target, and stitched together.
+fn capture_frame(player: &mut Player, width: u32, height: u32) -> Option<RgbaImage> {
+    let tiles = renderer(player).tiles();
+    if tiles.len() == 1 {
+        player.render();
+        let renderer = renderer(player);
+        return renderer.target().capture(renderer.device());
+    }
+
+    let mut image = RgbaImage::new(width, height);
+    for tile in tiles {
+        renderer(player).set_tile(tile);
+        player.render();
+        let renderer = renderer(player);
+        let tile_image = renderer.target().capture(renderer.device())?;
+        // Tiles along the right and bottom edges overhang the frame, and are clipped here.
+        image::imageops::replace(&mut image, &tile_image, tile.x, tile.y);
+    }
+    Some(image)
+}
+
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     let progress = if with_progress {
         Some(ProgressBar::new_spinner())
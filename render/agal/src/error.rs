@@ -0,0 +1,93 @@
+use crate::program::{Opcode, RegisterType};
+use std::{error, fmt};
+
+/// An error encountered while parsing or validating an AGAL program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The program does not start with an AGAL header.
+    InvalidHeader,
+
+    /// The program is written in a version of AGAL other than 1.
+    UnsupportedVersion(u32),
+
+    /// The program ends partway through an instruction.
+    Truncated,
+
+    /// The program has more instructions than AGAL 1 allows.
+    TooManyInstructions(usize),
+
+    /// An instruction has an unknown opcode.
+    UnknownOpcode(u32),
+
+    /// An operand names an unknown register type.
+    UnknownRegisterType(u8),
+
+    /// An instruction uses an operation that is unavailable to its shader
+    /// type.
+    InvalidOpcode { instruction: usize, opcode: Opcode },
+
+    /// An operand uses a register that is not allowed where it appears.
+    InvalidOperand {
+        instruction: usize,
+        opcode: Opcode,
+        register_type: RegisterType,
+    },
+
+    /// An operand uses a register beyond the end of its register file.
+    RegisterOutOfRange {
+        instruction: usize,
+        register_type: RegisterType,
+        index: u16,
+    },
+
+    /// The program never writes to its output register.
+    MissingOutput,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidHeader => write!(f, "Invalid AGAL header"),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported AGAL version {}", version)
+            }
+            Error::Truncated => write!(f, "AGAL program ends partway through an instruction"),
+            Error::TooManyInstructions(count) => {
+                write!(f, "AGAL program has too many instructions ({})", count)
+            }
+            Error::UnknownOpcode(opcode) => write!(f, "Unknown AGAL opcode {:#x}", opcode),
+            Error::UnknownRegisterType(register_type) => {
+                write!(f, "Unknown AGAL register type {}", register_type)
+            }
+            Error::InvalidOpcode {
+                instruction,
+                opcode,
+            } => write!(
+                f,
+                "Instruction {} ({:?}) is not allowed in this kind of program",
+                instruction, opcode
+            ),
+            Error::InvalidOperand {
+                instruction,
+                opcode,
+                register_type,
+            } => write!(
+                f,
+                "Instruction {} ({:?}) cannot use a {:?} register there",
+                instruction, opcode, register_type
+            ),
+            Error::RegisterOutOfRange {
+                instruction,
+                register_type,
+                index,
+            } => write!(
+                f,
+                "Instruction {} uses {:?} register {}, which is out of range",
+                instruction, register_type, index
+            ),
+            Error::MissingOutput => write!(f, "AGAL program never writes its output register"),
+        }
+    }
+}
+
+impl error::Error for Error {}
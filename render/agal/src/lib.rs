@@ -0,0 +1,16 @@
+//! Parsing and translation of AGAL, the shader bytecode uploaded to
+//! `Program3D` by Stage3D content.
+//!
+//! `Program::parse` reads and validates an AGAL 1 program against the
+//! register limits of its shader type, and `Program::to_wgsl` translates it
+//! into a WGSL shader module for a backend to build a render pipeline from.
+
+mod error;
+mod program;
+mod wgsl;
+
+pub use error::Error;
+pub use program::{
+    Destination, Filter, Index, Instruction, Mipmap, Opcode, Program, RegisterType, Sampler,
+    SamplerDimension, ShaderType, Source, Source2, Wrap,
+};
@@ -0,0 +1,835 @@
+use crate::error::Error;
+
+/// The most instructions an AGAL 1 program may contain.
+const MAX_INSTRUCTIONS: usize = 200;
+
+/// The length of a single encoded instruction, in bytes.
+const INSTRUCTION_LEN: usize = 24;
+
+/// Whether a program runs per vertex or per fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderType {
+    Vertex,
+    Fragment,
+}
+
+/// A register file, such as `va` or `ft`.
+///
+/// The same register type is spelled differently depending on the shader it
+/// is used in; `vc` and `fc` are both `Constant` registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterType {
+    /// `va`, the vertex attributes.
+    Attribute,
+
+    /// `vc` and `fc`, the program constants.
+    Constant,
+
+    /// `vt` and `ft`, the temporaries.
+    Temporary,
+
+    /// `op` and `oc`, the position and color outputs.
+    Output,
+
+    /// `v`, the values passed from the vertex program to the fragment
+    /// program.
+    Varying,
+
+    /// `fs`, the texture samplers.
+    Sampler,
+}
+
+impl RegisterType {
+    fn from_u8(value: u8) -> Result<Self, Error> {
+        Ok(match value {
+            0 => RegisterType::Attribute,
+            1 => RegisterType::Constant,
+            2 => RegisterType::Temporary,
+            3 => RegisterType::Output,
+            4 => RegisterType::Varying,
+            5 => RegisterType::Sampler,
+            _ => return Err(Error::UnknownRegisterType(value)),
+        })
+    }
+
+    /// The number of registers of this type available to a shader.
+    pub fn limit(self, shader_type: ShaderType) -> u16 {
+        match (self, shader_type) {
+            (RegisterType::Attribute, ShaderType::Vertex) => 8,
+            (RegisterType::Attribute, ShaderType::Fragment) => 0,
+            (RegisterType::Constant, ShaderType::Vertex) => 128,
+            (RegisterType::Constant, ShaderType::Fragment) => 28,
+            (RegisterType::Temporary, _) => 8,
+            (RegisterType::Output, _) => 1,
+            (RegisterType::Varying, _) => 8,
+            (RegisterType::Sampler, ShaderType::Vertex) => 0,
+            (RegisterType::Sampler, ShaderType::Fragment) => 8,
+        }
+    }
+
+    /// Whether an instruction may read from this register type.
+    fn is_readable(self, shader_type: ShaderType) -> bool {
+        match self {
+            RegisterType::Attribute => shader_type == ShaderType::Vertex,
+            RegisterType::Constant | RegisterType::Temporary => true,
+            RegisterType::Varying => shader_type == ShaderType::Fragment,
+            RegisterType::Output | RegisterType::Sampler => false,
+        }
+    }
+
+    /// Whether an instruction may write to this register type.
+    fn is_writable(self, shader_type: ShaderType) -> bool {
+        match self {
+            RegisterType::Temporary | RegisterType::Output => true,
+            RegisterType::Varying => shader_type == ShaderType::Vertex,
+            RegisterType::Attribute | RegisterType::Constant | RegisterType::Sampler => false,
+        }
+    }
+}
+
+/// An AGAL 1 operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Mov,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rcp,
+    Min,
+    Max,
+    Frc,
+    Sqt,
+    Rsq,
+    Pow,
+    Log,
+    Exp,
+    Nrm,
+    Sin,
+    Cos,
+    Crs,
+    Dp3,
+    Dp4,
+    Abs,
+    Neg,
+    Sat,
+    M33,
+    M44,
+    M34,
+    Kil,
+    Tex,
+    Sge,
+    Slt,
+    Seq,
+    Sne,
+}
+
+impl Opcode {
+    fn from_u32(value: u32) -> Result<Self, Error> {
+        Ok(match value {
+            0x00 => Opcode::Mov,
+            0x01 => Opcode::Add,
+            0x02 => Opcode::Sub,
+            0x03 => Opcode::Mul,
+            0x04 => Opcode::Div,
+            0x05 => Opcode::Rcp,
+            0x06 => Opcode::Min,
+            0x07 => Opcode::Max,
+            0x08 => Opcode::Frc,
+            0x09 => Opcode::Sqt,
+            0x0a => Opcode::Rsq,
+            0x0b => Opcode::Pow,
+            0x0c => Opcode::Log,
+            0x0d => Opcode::Exp,
+            0x0e => Opcode::Nrm,
+            0x0f => Opcode::Sin,
+            0x10 => Opcode::Cos,
+            0x11 => Opcode::Crs,
+            0x12 => Opcode::Dp3,
+            0x13 => Opcode::Dp4,
+            0x14 => Opcode::Abs,
+            0x15 => Opcode::Neg,
+            0x16 => Opcode::Sat,
+            0x17 => Opcode::M33,
+            0x18 => Opcode::M44,
+            0x19 => Opcode::M34,
+            0x27 => Opcode::Kil,
+            0x28 => Opcode::Tex,
+            0x29 => Opcode::Sge,
+            0x2a => Opcode::Slt,
+            0x2c => Opcode::Seq,
+            0x2d => Opcode::Sne,
+            _ => return Err(Error::UnknownOpcode(value)),
+        })
+    }
+
+    /// Whether this operation reads a second source operand.
+    pub fn has_source2(self) -> bool {
+        matches!(
+            self,
+            Opcode::Add
+                | Opcode::Sub
+                | Opcode::Mul
+                | Opcode::Div
+                | Opcode::Min
+                | Opcode::Max
+                | Opcode::Pow
+                | Opcode::Crs
+                | Opcode::Dp3
+                | Opcode::Dp4
+                | Opcode::M33
+                | Opcode::M44
+                | Opcode::M34
+                | Opcode::Tex
+                | Opcode::Sge
+                | Opcode::Slt
+                | Opcode::Seq
+                | Opcode::Sne
+        )
+    }
+
+    /// Whether this operation writes to its destination operand.
+    pub fn writes_destination(self) -> bool {
+        self != Opcode::Kil
+    }
+
+    /// The number of consecutive registers read through the second source
+    /// operand, which is more than one for the matrix operations.
+    pub fn source2_rows(self) -> u16 {
+        match self {
+            Opcode::M33 | Opcode::M34 => 3,
+            Opcode::M44 => 4,
+            _ => 1,
+        }
+    }
+
+    /// Whether this operation may only be used in fragment programs.
+    fn is_fragment_only(self) -> bool {
+        matches!(self, Opcode::Kil | Opcode::Tex)
+    }
+}
+
+/// The register an instruction writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Destination {
+    pub register_type: RegisterType,
+    pub index: u16,
+
+    /// The components written, with bit 0 standing for `x` through bit 3 for
+    /// `w`.
+    pub mask: u8,
+}
+
+impl Destination {
+    fn from_u32(value: u32) -> Result<Self, Error> {
+        Ok(Self {
+            register_type: RegisterType::from_u8(((value >> 24) & 0xf) as u8)?,
+            index: (value & 0xffff) as u16,
+            mask: ((value >> 16) & 0xf) as u8,
+        })
+    }
+}
+
+/// The register used to index a register file in an indirect source
+/// operand, as in `vc[va0.x + 4]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Index {
+    pub register_type: RegisterType,
+    pub index: u16,
+
+    /// The component of the index register to read, from 0 for `x` to 3 for
+    /// `w`.
+    pub component: u8,
+}
+
+/// A register an instruction reads from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source {
+    pub register_type: RegisterType,
+
+    /// The register number, or the offset added to the index register if
+    /// this operand is indirect.
+    pub index: u16,
+
+    /// The components read, two bits per output component, starting with
+    /// `x` in the lowest bits.
+    pub swizzle: u8,
+
+    /// The index register of an indirect operand.
+    pub indirect: Option<Index>,
+}
+
+impl Source {
+    fn from_u64(value: u64) -> Result<Self, Error> {
+        let register_type = RegisterType::from_u8(((value >> 32) & 0xf) as u8)?;
+        let swizzle = ((value >> 24) & 0xff) as u8;
+        let register = (value & 0xffff) as u16;
+
+        if value >> 63 != 0 {
+            Ok(Self {
+                register_type,
+                index: ((value >> 16) & 0xff) as u16,
+                swizzle,
+                indirect: Some(Index {
+                    register_type: RegisterType::from_u8(((value >> 40) & 0xf) as u8)?,
+                    index: register,
+                    component: ((value >> 48) & 0x3) as u8,
+                }),
+            })
+        } else {
+            Ok(Self {
+                register_type,
+                index: register,
+                swizzle,
+                indirect: None,
+            })
+        }
+    }
+}
+
+/// Whether a sampler reads a flat texture or a cube map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplerDimension {
+    TwoD,
+    Cube,
+}
+
+/// How a sampler filters between texels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    Nearest,
+    Linear,
+}
+
+/// How a sampler chooses between mipmap levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mipmap {
+    Disable,
+    Nearest,
+    Linear,
+}
+
+/// How a sampler treats coordinates outside of the texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    Clamp,
+    Repeat,
+}
+
+/// A texture sampler, along with the sampling state the `tex` instruction
+/// sets for it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampler {
+    pub index: u16,
+    pub lod_bias: f32,
+    pub dimension: SamplerDimension,
+    pub filter: Filter,
+    pub mipmap: Mipmap,
+    pub wrap: Wrap,
+}
+
+impl Sampler {
+    fn from_u64(value: u64, instruction: usize) -> Result<Self, Error> {
+        let register_type = RegisterType::from_u8(((value >> 32) & 0xf) as u8)?;
+        let invalid = Error::InvalidOperand {
+            instruction,
+            opcode: Opcode::Tex,
+            register_type,
+        };
+        if register_type != RegisterType::Sampler {
+            return Err(invalid);
+        }
+
+        Ok(Self {
+            index: (value & 0xffff) as u16,
+            lod_bias: f32::from(((value >> 16) & 0xff) as u8 as i8) / 8.0,
+            dimension: match (value >> 44) & 0xf {
+                0 => SamplerDimension::TwoD,
+                1 => SamplerDimension::Cube,
+                _ => return Err(invalid),
+            },
+            wrap: match (value >> 52) & 0xf {
+                0 => Wrap::Clamp,
+                1 => Wrap::Repeat,
+                _ => return Err(invalid),
+            },
+            mipmap: match (value >> 56) & 0xf {
+                0 => Mipmap::Disable,
+                1 => Mipmap::Nearest,
+                2 => Mipmap::Linear,
+                _ => return Err(invalid),
+            },
+            filter: match (value >> 60) & 0xf {
+                0 => Filter::Nearest,
+                1 => Filter::Linear,
+                _ => return Err(invalid),
+            },
+        })
+    }
+}
+
+/// The second source operand of an instruction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source2 {
+    /// The operation takes a single source operand.
+    None,
+
+    /// A register, as for every operation but `tex`.
+    Register(Source),
+
+    /// The sampler read by `tex`.
+    Sampler(Sampler),
+}
+
+/// A single AGAL instruction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub destination: Destination,
+    pub source1: Source,
+    pub source2: Source2,
+}
+
+/// A parsed and validated AGAL program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub shader_type: ShaderType,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Program {
+    /// Parse the bytecode passed to `Program3D.upload` for one shader.
+    ///
+    /// The program is rejected if any instruction uses a register that is
+    /// out of range or unavailable to its shader type, or if the program
+    /// never writes its output.
+    pub fn parse(data: &[u8]) -> Result<Self, Error> {
+        if data.len() < 7 || data[0] != 0xa0 || data[5] != 0xa1 {
+            return Err(Error::InvalidHeader);
+        }
+
+        let version = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+        if version != 1 {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let shader_type = match data[6] {
+            0 => ShaderType::Vertex,
+            1 => ShaderType::Fragment,
+            _ => return Err(Error::InvalidHeader),
+        };
+
+        let tokens = data[7..].chunks_exact(INSTRUCTION_LEN);
+        if !tokens.remainder().is_empty() {
+            return Err(Error::Truncated);
+        }
+
+        if tokens.len() > MAX_INSTRUCTIONS {
+            return Err(Error::TooManyInstructions(tokens.len()));
+        }
+
+        let instructions = tokens
+            .enumerate()
+            .map(|(i, token)| Self::parse_instruction(token, i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let program = Self {
+            shader_type,
+            instructions,
+        };
+        program.validate()?;
+
+        Ok(program)
+    }
+
+    fn parse_instruction(token: &[u8], instruction: usize) -> Result<Instruction, Error> {
+        let read_u32 = |at: usize| {
+            u32::from_le_bytes([token[at], token[at + 1], token[at + 2], token[at + 3]])
+        };
+        let read_u64 = |at: usize| u64::from(read_u32(at)) | (u64::from(read_u32(at + 4)) << 32);
+
+        let opcode = Opcode::from_u32(read_u32(0))?;
+        let source2 = match opcode {
+            Opcode::Tex => Source2::Sampler(Sampler::from_u64(read_u64(16), instruction)?),
+            _ if opcode.has_source2() => Source2::Register(Source::from_u64(read_u64(16))?),
+            _ => Source2::None,
+        };
+
+        Ok(Instruction {
+            opcode,
+            destination: Destination::from_u32(read_u32(4))?,
+            source1: Source::from_u64(read_u64(8))?,
+            source2,
+        })
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let shader_type = self.shader_type;
+        let mut writes_output = false;
+
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            let opcode = instruction.opcode;
+            let invalid = |register_type| Error::InvalidOperand {
+                instruction: i,
+                opcode,
+                register_type,
+            };
+            let check_range = |register_type: RegisterType, index: u16, rows: u16| {
+                if u32::from(index) + u32::from(rows) > u32::from(register_type.limit(shader_type))
+                {
+                    Err(Error::RegisterOutOfRange {
+                        instruction: i,
+                        register_type,
+                        index,
+                    })
+                } else {
+                    Ok(())
+                }
+            };
+            let check_source = |source: &Source, rows: u16| {
+                if !source.register_type.is_readable(shader_type) {
+                    return Err(invalid(source.register_type));
+                }
+
+                if let Some(index) = source.indirect {
+                    if source.register_type != RegisterType::Constant {
+                        return Err(invalid(source.register_type));
+                    }
+                    if !index.register_type.is_readable(shader_type) {
+                        return Err(invalid(index.register_type));
+                    }
+                    check_range(index.register_type, index.index, 1)
+                } else {
+                    check_range(source.register_type, source.index, rows)
+                }
+            };
+
+            if opcode.is_fragment_only() && shader_type != ShaderType::Fragment {
+                return Err(Error::InvalidOpcode {
+                    instruction: i,
+                    opcode,
+                });
+            }
+
+            if opcode.writes_destination() {
+                let destination = &instruction.destination;
+                if !destination.register_type.is_writable(shader_type) {
+                    return Err(invalid(destination.register_type));
+                }
+                check_range(destination.register_type, destination.index, 1)?;
+                writes_output |= destination.register_type == RegisterType::Output;
+            }
+
+            check_source(&instruction.source1, 1)?;
+
+            match &instruction.source2 {
+                Source2::None => {}
+                Source2::Register(source) => check_source(source, opcode.source2_rows())?,
+                Source2::Sampler(sampler) => {
+                    check_range(RegisterType::Sampler, sampler.index, 1)?;
+                }
+            }
+        }
+
+        if !writes_output {
+            return Err(Error::MissingOutput);
+        }
+
+        Ok(())
+    }
+
+    /// The sampling state of each texture read by this program, in the
+    /// order the `tex` instructions appear.
+    pub fn samplers(&self) -> impl Iterator<Item = &Sampler> {
+        self.instructions.iter().filter_map(|i| match &i.source2 {
+            Source2::Sampler(sampler) => Some(sampler),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const VERTEX: u8 = 0;
+    pub const FRAGMENT: u8 = 1;
+    pub const XYZW: u8 = 0b11_10_01_00;
+
+    /// Start a program of the given shader type.
+    pub fn program(shader_type: u8) -> Vec<u8> {
+        vec![0xa0, 1, 0, 0, 0, 0xa1, shader_type]
+    }
+
+    /// Append an instruction to a program.
+    pub fn op(program: &mut Vec<u8>, opcode: u32, dest: u32, source1: u64, source2: u64) {
+        program.extend_from_slice(&opcode.to_le_bytes());
+        program.extend_from_slice(&dest.to_le_bytes());
+        program.extend_from_slice(&source1.to_le_bytes());
+        program.extend_from_slice(&source2.to_le_bytes());
+    }
+
+    pub fn dest(register_type: u8, index: u16, mask: u8) -> u32 {
+        u32::from(index) | (u32::from(mask) << 16) | (u32::from(register_type) << 24)
+    }
+
+    pub fn src(register_type: u8, index: u16, swizzle: u8) -> u64 {
+        u64::from(index) | (u64::from(swizzle) << 24) | (u64::from(register_type) << 32)
+    }
+
+    pub fn indirect(offset: u8, index_type: u8, index: u16, component: u8) -> u64 {
+        u64::from(index)
+            | (u64::from(offset) << 16)
+            | (u64::from(XYZW) << 24)
+            | (1 << 32)
+            | (u64::from(index_type) << 40)
+            | (u64::from(component) << 48)
+            | (1 << 63)
+    }
+
+    /// A 2D sampler with linear filtering, nearest mipmapping and repeat
+    /// wrapping.
+    pub fn sampler(index: u16, lod_bias: i8) -> u64 {
+        u64::from(index)
+            | (u64::from(lod_bias as u8) << 16)
+            | (5 << 32)
+            | (1 << 52)
+            | (1 << 56)
+            | (1 << 60)
+    }
+
+    /// `m44 op, va0, vc0` and `mov v0, va1`.
+    pub fn textured_quad_vertex() -> Vec<u8> {
+        let mut data = program(VERTEX);
+        op(
+            &mut data,
+            0x18,
+            dest(3, 0, 0xf),
+            src(0, 0, XYZW),
+            src(1, 0, XYZW),
+        );
+        op(&mut data, 0x00, dest(4, 0, 0xf), src(0, 1, XYZW), 0);
+        data
+    }
+
+    /// `tex ft0, v0, fs0 <2d, linear, mipnearest, repeat>` and
+    /// `mov oc, ft0`.
+    pub fn textured_quad_fragment() -> Vec<u8> {
+        let mut data = program(FRAGMENT);
+        op(
+            &mut data,
+            0x28,
+            dest(2, 0, 0xf),
+            src(4, 0, XYZW),
+            sampler(0, 0),
+        );
+        op(&mut data, 0x00, dest(3, 0, 0xf), src(2, 0, XYZW), 0);
+        data
+    }
+
+    #[test]
+    fn parse_vertex_program() {
+        let program = Program::parse(&textured_quad_vertex()).unwrap();
+        assert_eq!(program.shader_type, ShaderType::Vertex);
+        assert_eq!(
+            program.instructions,
+            vec![
+                Instruction {
+                    opcode: Opcode::M44,
+                    destination: Destination {
+                        register_type: RegisterType::Output,
+                        index: 0,
+                        mask: 0xf,
+                    },
+                    source1: Source {
+                        register_type: RegisterType::Attribute,
+                        index: 0,
+                        swizzle: XYZW,
+                        indirect: None,
+                    },
+                    source2: Source2::Register(Source {
+                        register_type: RegisterType::Constant,
+                        index: 0,
+                        swizzle: XYZW,
+                        indirect: None,
+                    }),
+                },
+                Instruction {
+                    opcode: Opcode::Mov,
+                    destination: Destination {
+                        register_type: RegisterType::Varying,
+                        index: 0,
+                        mask: 0xf,
+                    },
+                    source1: Source {
+                        register_type: RegisterType::Attribute,
+                        index: 1,
+                        swizzle: XYZW,
+                        indirect: None,
+                    },
+                    source2: Source2::None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_sampler() {
+        let mut data = program(FRAGMENT);
+        op(
+            &mut data,
+            0x28,
+            dest(3, 0, 0xf),
+            src(4, 0, XYZW),
+            sampler(3, -4),
+        );
+        let program = Program::parse(&data).unwrap();
+        let samplers: Vec<_> = program.samplers().collect();
+        assert_eq!(
+            samplers,
+            vec![&Sampler {
+                index: 3,
+                lod_bias: -0.5,
+                dimension: SamplerDimension::TwoD,
+                filter: Filter::Linear,
+                mipmap: Mipmap::Nearest,
+                wrap: Wrap::Repeat,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_indirect_source() {
+        let mut data = program(VERTEX);
+        op(&mut data, 0x00, dest(3, 0, 0xf), indirect(4, 0, 2, 1), 0);
+        let program = Program::parse(&data).unwrap();
+        assert_eq!(
+            program.instructions[0].source1,
+            Source {
+                register_type: RegisterType::Constant,
+                index: 4,
+                swizzle: XYZW,
+                indirect: Some(Index {
+                    register_type: RegisterType::Attribute,
+                    index: 2,
+                    component: 1,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn reject_malformed_programs() {
+        assert_eq!(Program::parse(&[0xa0, 1]), Err(Error::InvalidHeader));
+        assert_eq!(
+            Program::parse(&[0xa0, 2, 0, 0, 0, 0xa1, 0]),
+            Err(Error::UnsupportedVersion(2))
+        );
+
+        let mut data = textured_quad_vertex();
+        data.pop();
+        assert_eq!(Program::parse(&data), Err(Error::Truncated));
+
+        let mut data = program(VERTEX);
+        op(&mut data, 0x1a, dest(3, 0, 0xf), src(0, 0, XYZW), 0);
+        assert_eq!(Program::parse(&data), Err(Error::UnknownOpcode(0x1a)));
+
+        let mut data = program(VERTEX);
+        for _ in 0..201 {
+            op(&mut data, 0x00, dest(3, 0, 0xf), src(0, 0, XYZW), 0);
+        }
+        assert_eq!(Program::parse(&data), Err(Error::TooManyInstructions(201)));
+    }
+
+    #[test]
+    fn reject_registers_out_of_range() {
+        // The last row of the matrix would be vc128.
+        let mut data = program(VERTEX);
+        op(
+            &mut data,
+            0x18,
+            dest(3, 0, 0xf),
+            src(0, 0, XYZW),
+            src(1, 125, XYZW),
+        );
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::RegisterOutOfRange {
+                instruction: 0,
+                register_type: RegisterType::Constant,
+                index: 125,
+            })
+        );
+
+        let mut data = program(FRAGMENT);
+        op(&mut data, 0x00, dest(3, 0, 0xf), src(1, 28, XYZW), 0);
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::RegisterOutOfRange {
+                instruction: 0,
+                register_type: RegisterType::Constant,
+                index: 28,
+            })
+        );
+
+        let mut data = program(VERTEX);
+        op(&mut data, 0x00, dest(2, 8, 0xf), src(0, 0, XYZW), 0);
+        op(&mut data, 0x00, dest(3, 0, 0xf), src(0, 0, XYZW), 0);
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::RegisterOutOfRange {
+                instruction: 0,
+                register_type: RegisterType::Temporary,
+                index: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_registers_unavailable_to_shader() {
+        let mut data = program(VERTEX);
+        op(
+            &mut data,
+            0x28,
+            dest(3, 0, 0xf),
+            src(0, 0, XYZW),
+            sampler(0, 0),
+        );
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::InvalidOpcode {
+                instruction: 0,
+                opcode: Opcode::Tex,
+            })
+        );
+
+        let mut data = program(VERTEX);
+        op(&mut data, 0x00, dest(3, 0, 0xf), src(4, 0, XYZW), 0);
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::InvalidOperand {
+                instruction: 0,
+                opcode: Opcode::Mov,
+                register_type: RegisterType::Varying,
+            })
+        );
+
+        let mut data = program(FRAGMENT);
+        op(&mut data, 0x00, dest(1, 0, 0xf), src(4, 0, XYZW), 0);
+        assert_eq!(
+            Program::parse(&data),
+            Err(Error::InvalidOperand {
+                instruction: 0,
+                opcode: Opcode::Mov,
+                register_type: RegisterType::Constant,
+            })
+        );
+    }
+
+    #[test]
+    fn reject_missing_output() {
+        let mut data = program(FRAGMENT);
+        op(&mut data, 0x00, dest(2, 0, 0xf), src(4, 0, XYZW), 0);
+        assert_eq!(Program::parse(&data), Err(Error::MissingOutput));
+    }
+}
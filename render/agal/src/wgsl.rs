@@ -0,0 +1,422 @@
+use crate::program::{
+    Instruction, Opcode, Program, RegisterType, SamplerDimension, ShaderType, Source, Source2,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+const COMPONENTS: [char; 4] = ['x', 'y', 'z', 'w'];
+
+/// The swizzle that reads every component in order, which needs no
+/// explicit swizzle in WGSL.
+const IDENTITY_SWIZZLE: u8 = 0b11_10_01_00;
+
+impl Program {
+    /// Translate this program into a WGSL shader module with a single entry
+    /// point named `main`.
+    ///
+    /// Every resource lives in bind group 0. The vertex constants are bound
+    /// at 0 and the fragment constants at 1, each as an array of `vec4<f32>`
+    /// registers. Sampler `fsN` binds its texture at `2 + 2N` and its sampler
+    /// at `3 + 2N`. Attribute `vaN` is read from location `N` as a
+    /// `vec4<f32>`, so vertex formats with fewer components are extended as
+    /// usual. Both stages share a `Varyings` struct holding the position and
+    /// all eight varyings, so any vertex module links with any fragment
+    /// module.
+    pub fn to_wgsl(&self) -> String {
+        let mut out = String::new();
+        let shader_type = self.shader_type;
+        let binding = match shader_type {
+            ShaderType::Vertex => 0,
+            ShaderType::Fragment => 1,
+        };
+
+        let _ = writeln!(out, "struct Constants {{");
+        let _ = writeln!(
+            out,
+            "    registers: array<vec4<f32>, {}>,",
+            RegisterType::Constant.limit(shader_type)
+        );
+        let _ = writeln!(out, "}};\n");
+        let _ = writeln!(out, "struct Varyings {{");
+        let _ = writeln!(out, "    @builtin(position) op: vec4<f32>,");
+        for i in 0..RegisterType::Varying.limit(shader_type) {
+            let _ = writeln!(out, "    @location({}) v{}: vec4<f32>,", i, i);
+        }
+        let _ = writeln!(out, "}};\n");
+        let _ = writeln!(
+            out,
+            "@group(0) @binding({}) var<uniform> {}: Constants;",
+            binding,
+            self.constants()
+        );
+
+        let mut samplers = BTreeMap::new();
+        for sampler in self.samplers() {
+            samplers.entry(sampler.index).or_insert(sampler.dimension);
+        }
+        for (index, dimension) in &samplers {
+            let texture = match dimension {
+                SamplerDimension::TwoD => "texture_2d<f32>",
+                SamplerDimension::Cube => "texture_cube<f32>",
+            };
+            let _ = writeln!(
+                out,
+                "@group(0) @binding({}) var fs{}: {};",
+                2 + 2 * index,
+                index,
+                texture
+            );
+            let _ = writeln!(
+                out,
+                "@group(0) @binding({}) var fs{}_sampler: sampler;",
+                3 + 2 * index,
+                index
+            );
+        }
+        out.push('\n');
+
+        match shader_type {
+            ShaderType::Vertex => {
+                let attributes: Vec<String> = self
+                    .registers_read(RegisterType::Attribute)
+                    .iter()
+                    .map(|i| format!("@location({}) va{}: vec4<f32>", i, i))
+                    .collect();
+                let _ = writeln!(out, "@vertex");
+                let _ = writeln!(out, "fn main({}) -> Varyings {{", attributes.join(", "));
+                let _ = writeln!(out, "    var output: Varyings;");
+            }
+            ShaderType::Fragment => {
+                let _ = writeln!(out, "@fragment");
+                let _ = writeln!(out, "fn main(input: Varyings) -> @location(0) vec4<f32> {{");
+                let _ = writeln!(out, "    var oc = vec4<f32>(0.0);");
+            }
+        }
+
+        for i in self.temporaries() {
+            let _ = writeln!(
+                out,
+                "    var {} = vec4<f32>(0.0);",
+                self.register(RegisterType::Temporary, i)
+            );
+        }
+
+        for instruction in &self.instructions {
+            self.write_instruction(&mut out, instruction);
+        }
+
+        match shader_type {
+            ShaderType::Vertex => out.push_str("    return output;\n}\n"),
+            ShaderType::Fragment => out.push_str("    return oc;\n}\n"),
+        }
+
+        out
+    }
+
+    /// The indices of every register of a given type read by this program.
+    fn registers_read(&self, register_type: RegisterType) -> BTreeSet<u16> {
+        let mut registers = BTreeSet::new();
+        let mut add = |source: &Source, rows: u16| match source.indirect {
+            Some(index) if index.register_type == register_type => {
+                registers.insert(index.index);
+            }
+            None if source.register_type == register_type => {
+                registers.extend(source.index..source.index + rows);
+            }
+            _ => {}
+        };
+
+        for instruction in &self.instructions {
+            add(&instruction.source1, 1);
+            if let Source2::Register(source) = &instruction.source2 {
+                add(source, instruction.opcode.source2_rows());
+            }
+        }
+
+        registers
+    }
+
+    /// The indices of every temporary register used by this program.
+    fn temporaries(&self) -> BTreeSet<u16> {
+        let mut temporaries = self.registers_read(RegisterType::Temporary);
+        temporaries.extend(
+            self.instructions
+                .iter()
+                .filter(|i| i.opcode.writes_destination())
+                .map(|i| i.destination)
+                .filter(|d| d.register_type == RegisterType::Temporary)
+                .map(|d| d.index),
+        );
+
+        temporaries
+    }
+
+    /// The name of the uniform holding this program's constants.
+    fn constants(&self) -> &'static str {
+        match self.shader_type {
+            ShaderType::Vertex => "vc",
+            ShaderType::Fragment => "fc",
+        }
+    }
+
+    /// The WGSL expression naming a register.
+    fn register(&self, register_type: RegisterType, index: u16) -> String {
+        let vertex = self.shader_type == ShaderType::Vertex;
+        match register_type {
+            RegisterType::Attribute => format!("va{}", index),
+            RegisterType::Constant => format!("{}.registers[{}]", self.constants(), index),
+            RegisterType::Temporary if vertex => format!("vt{}", index),
+            RegisterType::Temporary => format!("ft{}", index),
+            RegisterType::Output if vertex => "output.op".to_string(),
+            RegisterType::Output => "oc".to_string(),
+            RegisterType::Varying if vertex => format!("output.v{}", index),
+            RegisterType::Varying => format!("input.v{}", index),
+            RegisterType::Sampler => format!("fs{}", index),
+        }
+    }
+
+    /// The WGSL expression reading a source operand, or the register `row`
+    /// places after it for the matrix operations.
+    fn source(&self, source: &Source, row: u16) -> String {
+        let register = match source.indirect {
+            // Only constants can be indexed indirectly.
+            Some(index) => format!(
+                "{}.registers[i32({}.{}) + {}]",
+                self.constants(),
+                self.register(index.register_type, index.index),
+                COMPONENTS[usize::from(index.component)],
+                source.index + row
+            ),
+            None => self.register(source.register_type, source.index + row),
+        };
+
+        if source.swizzle == IDENTITY_SWIZZLE {
+            register
+        } else {
+            let swizzle: String = (0..4)
+                .map(|i| COMPONENTS[usize::from((source.swizzle >> (i * 2)) & 0b11)])
+                .collect();
+            format!("{}.{}", register, swizzle)
+        }
+    }
+
+    fn write_instruction(&self, out: &mut String, instruction: &Instruction) {
+        let a = self.source(&instruction.source1, 0);
+        let b = |row| match &instruction.source2 {
+            Source2::Register(source) => self.source(source, row),
+            _ => "vec4<f32>(0.0)".to_string(),
+        };
+        let compare = |op| {
+            format!(
+                "select(vec4<f32>(0.0), vec4<f32>(1.0), {} {} {})",
+                a,
+                op,
+                b(0)
+            )
+        };
+        let rows = |count, dot: &dyn Fn(u16) -> String| {
+            let mut rows: Vec<String> = (0..count).map(dot).collect();
+            rows.resize(4, "0.0".to_string());
+            format!("vec4<f32>({})", rows.join(", "))
+        };
+
+        let expression = match instruction.opcode {
+            Opcode::Mov => a.clone(),
+            Opcode::Add => format!("{} + {}", a, b(0)),
+            Opcode::Sub => format!("{} - {}", a, b(0)),
+            Opcode::Mul => format!("{} * {}", a, b(0)),
+            Opcode::Div => format!("{} / {}", a, b(0)),
+            Opcode::Rcp => format!("vec4<f32>(1.0) / {}", a),
+            Opcode::Min => format!("min({}, {})", a, b(0)),
+            Opcode::Max => format!("max({}, {})", a, b(0)),
+            Opcode::Frc => format!("fract({})", a),
+            Opcode::Sqt => format!("sqrt({})", a),
+            Opcode::Rsq => format!("inverseSqrt({})", a),
+            Opcode::Pow => format!("pow({}, {})", a, b(0)),
+            Opcode::Log => format!("log2({})", a),
+            Opcode::Exp => format!("exp2({})", a),
+            Opcode::Nrm => format!("vec4<f32>(normalize(({}).xyz), 0.0)", a),
+            Opcode::Sin => format!("sin({})", a),
+            Opcode::Cos => format!("cos({})", a),
+            Opcode::Crs => format!("vec4<f32>(cross(({}).xyz, ({}).xyz), 0.0)", a, b(0)),
+            Opcode::Dp3 => format!("vec4<f32>(dot(({}).xyz, ({}).xyz))", a, b(0)),
+            Opcode::Dp4 => format!("vec4<f32>(dot({}, {}))", a, b(0)),
+            Opcode::Abs => format!("abs({})", a),
+            Opcode::Neg => format!("-{}", a),
+            Opcode::Sat => format!("clamp({}, vec4<f32>(0.0), vec4<f32>(1.0))", a),
+            Opcode::M33 => rows(3, &|row| format!("dot(({}).xyz, ({}).xyz)", a, b(row))),
+            Opcode::M44 => rows(4, &|row| format!("dot({}, {})", a, b(row))),
+            Opcode::M34 => rows(3, &|row| format!("dot({}, {})", a, b(row))),
+            Opcode::Sge => compare(">="),
+            Opcode::Slt => compare("<"),
+            Opcode::Seq => compare("=="),
+            Opcode::Sne => compare("!="),
+            Opcode::Kil => {
+                let _ = writeln!(out, "    if (({}).x < 0.0) {{", a);
+                let _ = writeln!(out, "        discard;");
+                let _ = writeln!(out, "    }}");
+                return;
+            }
+            Opcode::Tex => match &instruction.source2 {
+                Source2::Sampler(sampler) => {
+                    let coordinates = match sampler.dimension {
+                        SamplerDimension::TwoD => format!("({}).xy", a),
+                        SamplerDimension::Cube => format!("({}).xyz", a),
+                    };
+                    if sampler.lod_bias == 0.0 {
+                        format!(
+                            "textureSample(fs{}, fs{}_sampler, {})",
+                            sampler.index, sampler.index, coordinates
+                        )
+                    } else {
+                        format!(
+                            "textureSampleBias(fs{}, fs{}_sampler, {}, {:?})",
+                            sampler.index, sampler.index, coordinates, sampler.lod_bias
+                        )
+                    }
+                }
+                _ => return,
+            },
+        };
+
+        let destination = &instruction.destination;
+        let register = self.register(destination.register_type, destination.index);
+        if destination.mask == 0b1111 {
+            let _ = writeln!(out, "    {} = {};", register, expression);
+        } else {
+            let _ = writeln!(out, "    {{");
+            let _ = writeln!(out, "        let r = {};", expression);
+            for (i, component) in COMPONENTS.iter().enumerate() {
+                if destination.mask & (1 << i) != 0 {
+                    let _ = writeln!(out, "        {}.{} = r.{};", register, component, component);
+                }
+            }
+            let _ = writeln!(out, "    }}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::program::tests::*;
+    use crate::Program;
+
+    #[test]
+    fn translate_vertex_program() {
+        let program = Program::parse(&textured_quad_vertex()).unwrap();
+        assert_eq!(
+            program.to_wgsl(),
+            r#"struct Constants {
+    registers: array<vec4<f32>, 128>,
+};
+
+struct Varyings {
+    @builtin(position) op: vec4<f32>,
+    @location(0) v0: vec4<f32>,
+    @location(1) v1: vec4<f32>,
+    @location(2) v2: vec4<f32>,
+    @location(3) v3: vec4<f32>,
+    @location(4) v4: vec4<f32>,
+    @location(5) v5: vec4<f32>,
+    @location(6) v6: vec4<f32>,
+    @location(7) v7: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> vc: Constants;
+
+@vertex
+fn main(@location(0) va0: vec4<f32>, @location(1) va1: vec4<f32>) -> Varyings {
+    var output: Varyings;
+    output.op = vec4<f32>(dot(va0, vc.registers[0]), dot(va0, vc.registers[1]), dot(va0, vc.registers[2]), dot(va0, vc.registers[3]));
+    output.v0 = va1;
+    return output;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn translate_fragment_program() {
+        let program = Program::parse(&textured_quad_fragment()).unwrap();
+        assert_eq!(
+            program.to_wgsl(),
+            r#"struct Constants {
+    registers: array<vec4<f32>, 28>,
+};
+
+struct Varyings {
+    @builtin(position) op: vec4<f32>,
+    @location(0) v0: vec4<f32>,
+    @location(1) v1: vec4<f32>,
+    @location(2) v2: vec4<f32>,
+    @location(3) v3: vec4<f32>,
+    @location(4) v4: vec4<f32>,
+    @location(5) v5: vec4<f32>,
+    @location(6) v6: vec4<f32>,
+    @location(7) v7: vec4<f32>,
+};
+
+@group(0) @binding(1) var<uniform> fc: Constants;
+@group(0) @binding(2) var fs0: texture_2d<f32>;
+@group(0) @binding(3) var fs0_sampler: sampler;
+
+@fragment
+fn main(input: Varyings) -> @location(0) vec4<f32> {
+    var oc = vec4<f32>(0.0);
+    var ft0 = vec4<f32>(0.0);
+    ft0 = textureSample(fs0, fs0_sampler, (input.v0).xy);
+    oc = ft0;
+    return oc;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn translate_masks_swizzles_and_indirect_sources() {
+        let mut data = program(VERTEX);
+        op(&mut data, 0x00, dest(2, 1, 0b0101), indirect(4, 0, 2, 1), 0);
+        op(
+            &mut data,
+            0x12,
+            dest(3, 0, 0xf),
+            src(2, 1, 0b00_00_01_01),
+            src(1, 3, XYZW),
+        );
+        let wgsl = Program::parse(&data).unwrap().to_wgsl();
+        assert!(wgsl.contains("fn main(@location(2) va2: vec4<f32>) -> Varyings {\n"));
+        assert!(wgsl.contains(
+            r#"    var vt1 = vec4<f32>(0.0);
+    {
+        let r = vc.registers[i32(va2.y) + 4];
+        vt1.x = r.x;
+        vt1.z = r.z;
+    }
+    output.op = vec4<f32>(dot((vt1.yyxx).xyz, (vc.registers[3]).xyz));
+"#
+        ));
+    }
+
+    #[test]
+    fn translate_kil_and_lod_bias() {
+        let mut data = program(FRAGMENT);
+        op(&mut data, 0x27, 0, src(4, 1, 0b11_11_11_11), 0);
+        op(
+            &mut data,
+            0x28,
+            dest(3, 0, 0xf),
+            src(4, 0, XYZW),
+            sampler(1, 4),
+        );
+        let wgsl = Program::parse(&data).unwrap().to_wgsl();
+        assert!(wgsl.contains("@group(0) @binding(4) var fs1: texture_2d<f32>;\n"));
+        assert!(wgsl.contains("@group(0) @binding(5) var fs1_sampler: sampler;\n"));
+        assert!(wgsl.contains(
+            r#"    if ((input.v1.wwww).x < 0.0) {
+        discard;
+    }
+    oc = textureSampleBias(fs1, fs1_sampler, (input.v0).xy, 0.5);
+"#
+        ));
+    }
+}
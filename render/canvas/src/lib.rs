@@ -4,14 +4,15 @@ use ruffle_core::backend::render::{
     ShapeHandle, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
-use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, FillRule};
+use ruffle_core::StageQuality;
 use ruffle_web_common::JsResult;
 use std::collections::HashMap;
 use std::convert::TryInto;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    CanvasGradient, CanvasPattern, CanvasRenderingContext2d, Element, HtmlCanvasElement,
-    HtmlImageElement, Path2d, SvgsvgElement,
+    CanvasGradient, CanvasPattern, CanvasRenderingContext2d, CanvasWindingRule, Element,
+    HtmlCanvasElement, HtmlImageElement, Path2d, SvgsvgElement,
 };
 
 type Error = Box<dyn std::error::Error>;
@@ -30,6 +31,9 @@ pub struct WebCanvasRenderBackend {
     viewport_height: u32,
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
+
+    /// Whether the page shows through where nothing is drawn (`wmode="transparent"`).
+    is_transparent: bool,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -77,6 +81,7 @@ enum CanvasDrawCommand {
     Fill {
         path: Path2d,
         fill_style: CanvasFillStyle,
+        winding_rule: CanvasWindingRule,
     },
 
     /// A command to draw a particular image (such as an SVG)
@@ -113,16 +118,14 @@ struct BitmapData {
 }
 
 impl WebCanvasRenderBackend {
-    pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(
+        canvas: &HtmlCanvasElement,
+        is_transparent: bool,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
         // Request the CanvasRenderingContext2d.
-        // Disable alpha for possible speedup.
-        // TODO: Allow user to enable transparent background (transparent wmode in legacy Flash).
+        // Disable alpha for possible speedup, unless the page should show through.
         let context_options = js_sys::Object::new();
-        let _ = js_sys::Reflect::set(
-            &context_options,
-            &"alpha".into(),
-            &wasm_bindgen::JsValue::FALSE,
-        );
+        let _ = js_sys::Reflect::set(&context_options, &"alpha".into(), &is_transparent.into());
         let context: CanvasRenderingContext2d = canvas
             .get_context_with_context_options("2d", &context_options)
             .into_js_result()?
@@ -220,6 +223,7 @@ impl WebCanvasRenderBackend {
             } else {
                 "pixelated"
             },
+            is_transparent,
         };
         Ok(renderer)
     }
@@ -570,6 +574,11 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
+    fn set_quality(&mut self, _quality: StageQuality, _swf_version: u8) {
+        // Bitmap smoothing is baked into the SVG of each shape when it is registered, so it
+        // can't follow the stage quality yet.
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         // Reset canvas transform in case it was left in a dirty state.
         self.context.reset_transform().unwrap();
@@ -577,10 +586,15 @@ impl RenderBackend for WebCanvasRenderBackend {
         let width = self.canvas.width();
         let height = self.canvas.height();
 
-        let color = format!("rgb({}, {}, {})", clear.r, clear.g, clear.b);
-        self.context.set_fill_style(&color.into());
-        self.context
-            .fill_rect(0.0, 0.0, width.into(), height.into());
+        if self.is_transparent {
+            self.context
+                .clear_rect(0.0, 0.0, width.into(), height.into());
+        } else {
+            let color = format!("rgb({}, {}, {})", clear.r, clear.g, clear.b);
+            self.context.set_fill_style(&color.into());
+            self.context
+                .fill_rect(0.0, 0.0, width.into(), height.into());
+        }
     }
 
     fn end_frame(&mut self) {
@@ -603,7 +617,11 @@ impl RenderBackend for WebCanvasRenderBackend {
         if let Some(shape) = self.shapes.get(shape.0) {
             for command in shape.0.iter() {
                 match command {
-                    CanvasDrawCommand::Fill { path, fill_style } => {
+                    CanvasDrawCommand::Fill {
+                        path,
+                        fill_style,
+                        winding_rule,
+                    } => {
                         let xformed_fill_style =
                             fill_style.color_transform(&transform.color_transform);
                         if xformed_fill_style.is_none() {
@@ -618,7 +636,8 @@ impl RenderBackend for WebCanvasRenderBackend {
                             CanvasFillStyle::Pattern(patt) => self.context.set_fill_style(patt),
                         };
 
-                        self.context.fill_with_path_2d(&path);
+                        self.context
+                            .fill_with_path_2d_and_winding(&path, *winding_rule);
 
                         if xformed_fill_style.is_none() {
                             self.clear_color_filter();
@@ -662,27 +681,32 @@ impl RenderBackend for WebCanvasRenderBackend {
         self.context.reset_transform().unwrap();
         self.context.set_fill_style(&"black".into());
 
-        match letterbox {
-            Letterbox::None => (),
+        let width = f64::from(self.viewport_width);
+        let height = f64::from(self.viewport_height);
+        let margins = match letterbox {
+            Letterbox::None => return,
             Letterbox::Letterbox(margin_height) => {
-                self.context
-                    .fill_rect(0.0, 0.0, self.viewport_width.into(), margin_height.into());
-                self.context.fill_rect(
-                    0.0,
-                    (self.viewport_height as f32 - margin_height).into(),
-                    self.viewport_width.into(),
-                    self.viewport_height.into(),
-                );
+                let margin_height = f64::from(margin_height);
+                [
+                    (0.0, 0.0, width, margin_height),
+                    (0.0, height - margin_height, width, height),
+                ]
             }
             Letterbox::Pillarbox(margin_width) => {
-                self.context
-                    .fill_rect(0.0, 0.0, margin_width.into(), self.viewport_height.into());
-                self.context.fill_rect(
-                    (self.viewport_width as f32 - margin_width).into(),
-                    0.0,
-                    margin_width.into(),
-                    self.viewport_height.into(),
-                );
+                let margin_width = f64::from(margin_width);
+                [
+                    (0.0, 0.0, margin_width, height),
+                    (width - margin_width, 0.0, margin_width, height),
+                ]
+            }
+        };
+
+        // The page shows through the margins of transparent movies.
+        for &(x, y, w, h) in &margins {
+            if self.is_transparent {
+                self.context.clear_rect(x, y, w, h);
+            } else {
+                self.context.fill_rect(x, y, w, h);
             }
         }
     }
@@ -736,6 +760,27 @@ impl RenderBackend for WebCanvasRenderBackend {
             .draw_image_with_html_canvas_element(&maskee_canvas, 0.0, 0.0)
             .unwrap();
     }
+
+    fn create_offscreen_bitmap(&mut self, _width: u32, _height: u32) -> Option<BitmapHandle> {
+        // TODO: Offscreen rendering is not supported yet; cached objects render normally.
+        None
+    }
+
+    fn push_offscreen_target(&mut self, _bitmap: BitmapHandle) {}
+
+    fn pop_offscreen_target(&mut self) {}
+
+    fn remove_offscreen_bitmap(&mut self, _bitmap: BitmapHandle) {}
+
+    fn offscreen_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
+        None
+    }
+
+    fn push_blend_mode(&mut self, _blend_mode: swf::BlendMode) {
+        // TODO: Blend modes are not supported yet; objects are drawn with normal blending.
+    }
+
+    fn pop_blend_mode(&mut self) {}
 }
 
 #[allow(clippy::cognitive_complexity)]
@@ -795,7 +840,11 @@ fn swf_shape_to_svg(
     let mut svg_paths = vec![];
     for path in shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                fill_rule,
+            } => {
                 let mut svg_path = SvgPath::new();
 
                 let fill = match style {
@@ -1058,6 +1107,13 @@ fn swf_shape_to_svg(
                     }
                 };
                 svg_path = svg_path.set("fill", fill);
+                svg_path = svg_path.set(
+                    "fill-rule",
+                    match fill_rule {
+                        FillRule::EvenOdd => "evenodd",
+                        FillRule::NonZero => "nonzero",
+                    },
+                );
 
                 let mut data = Data::new();
                 for command in commands {
@@ -1256,7 +1312,11 @@ fn swf_shape_to_canvas_commands(
 
     for path in &shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                fill_rule,
+            } => {
                 let fill_style = match style {
                     FillStyle::Color(Color { r, g, b, a }) => CanvasFillStyle::Color(CanvasColor(
                         format!("rgba({},{},{},{})", r, g, b, f32::from(*a) / 255.0),
@@ -1324,9 +1384,14 @@ fn swf_shape_to_canvas_commands(
                     &bounds_viewbox_matrix,
                 );
 
-                canvas_data
-                    .0
-                    .push(CanvasDrawCommand::Fill { path, fill_style });
+                canvas_data.0.push(CanvasDrawCommand::Fill {
+                    path,
+                    fill_style,
+                    winding_rule: match fill_rule {
+                        FillRule::EvenOdd => CanvasWindingRule::Evenodd,
+                        FillRule::NonZero => CanvasWindingRule::Nonzero,
+                    },
+                });
             }
             DrawPath::Stroke {
                 style,
@@ -6,7 +6,7 @@ use lyon::tessellation::{
 };
 use lyon::tessellation::{FillOptions, StrokeOptions};
 use ruffle_core::backend::render::swf::{self, FillStyle, GradientInterpolation, Twips};
-use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
 
 pub struct ShapeTessellator {
     fill_tess: FillTessellator,
@@ -44,7 +44,11 @@ impl ShapeTessellator {
 
         for path in shape.paths {
             match path {
-                DrawPath::Fill { style, commands } => match style {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    fill_rule,
+                } => match style {
                     FillStyle::Color(color) => {
                         let color = ((color.a as u32) << 24)
                             | ((color.b as u32) << 16)
@@ -56,152 +60,15 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
-                            &mut buffers_builder,
-                        ) {
-                            // This may just be a degenerate path; skip it.
-                            log::error!("Tessellation failure: {:?}", e);
-                            continue;
-                        }
-                    }
-                    FillStyle::LinearGradient(gradient) => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
-
-                        let mut buffers_builder = BuffersBuilder::new(
-                            &mut lyon_mesh,
-                            RuffleVertexCtor { color: 0xffff_ffff },
-                        );
-
-                        if let Err(e) = self.fill_tess.tessellate_path(
-                            &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
-                            &mut buffers_builder,
-                        ) {
-                            // This may just be a degenerate path; skip it.
-                            log::error!("Tessellation failure: {:?}", e);
-                            continue;
-                        }
-
-                        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(8);
-                        let mut ratios: Vec<f32> = Vec::with_capacity(8);
-                        for record in &gradient.records {
-                            colors.push([
-                                f32::from(record.color.r) / 255.0,
-                                f32::from(record.color.g) / 255.0,
-                                f32::from(record.color.b) / 255.0,
-                                f32::from(record.color.a) / 255.0,
-                            ]);
-                            ratios.push(f32::from(record.ratio) / 255.0);
-                        }
-
-                        let gradient = Gradient {
-                            gradient_type: GradientType::Linear,
-                            ratios,
-                            colors,
-                            num_colors: gradient.records.len() as u32,
-                            matrix: swf_to_gl_matrix(gradient.matrix),
-                            repeat_mode: gradient.spread,
-                            focal_point: 0.0,
-                            interpolation: gradient.interpolation,
-                        };
-
-                        flush_draw(DrawType::Gradient(gradient), &mut mesh, &mut lyon_mesh);
-                    }
-                    FillStyle::RadialGradient(gradient) => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
-
-                        let mut buffers_builder = BuffersBuilder::new(
-                            &mut lyon_mesh,
-                            RuffleVertexCtor { color: 0xffff_ffff },
-                        );
-
-                        if let Err(e) = self.fill_tess.tessellate_path(
-                            &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
-                            &mut buffers_builder,
-                        ) {
-                            // This may just be a degenerate path; skip it.
-                            log::error!("Tessellation failure: {:?}", e);
-                            continue;
-                        }
-
-                        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(8);
-                        let mut ratios: Vec<f32> = Vec::with_capacity(8);
-                        for record in &gradient.records {
-                            colors.push([
-                                f32::from(record.color.r) / 255.0,
-                                f32::from(record.color.g) / 255.0,
-                                f32::from(record.color.b) / 255.0,
-                                f32::from(record.color.a) / 255.0,
-                            ]);
-                            ratios.push(f32::from(record.ratio) / 255.0);
-                        }
-
-                        let gradient = Gradient {
-                            gradient_type: GradientType::Radial,
-                            ratios,
-                            colors,
-                            num_colors: gradient.records.len() as u32,
-                            matrix: swf_to_gl_matrix(gradient.matrix),
-                            repeat_mode: gradient.spread,
-                            focal_point: 0.0,
-                            interpolation: gradient.interpolation,
-                        };
-
-                        flush_draw(DrawType::Gradient(gradient), &mut mesh, &mut lyon_mesh);
-                    }
-                    FillStyle::FocalGradient {
-                        gradient,
-                        focal_point,
-                    } => {
-                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
-
-                        let mut buffers_builder = BuffersBuilder::new(
-                            &mut lyon_mesh,
-                            RuffleVertexCtor { color: 0xffff_ffff },
-                        );
-
-                        if let Err(e) = self.fill_tess.tessellate_path(
-                            &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
                             log::error!("Tessellation failure: {:?}", e);
                             continue;
                         }
-
-                        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(8);
-                        let mut ratios: Vec<f32> = Vec::with_capacity(8);
-                        for record in &gradient.records {
-                            colors.push([
-                                f32::from(record.color.r) / 255.0,
-                                f32::from(record.color.g) / 255.0,
-                                f32::from(record.color.b) / 255.0,
-                                f32::from(record.color.a) / 255.0,
-                            ]);
-                            ratios.push(f32::from(record.ratio) / 255.0);
-                        }
-
-                        let gradient = Gradient {
-                            gradient_type: GradientType::Focal,
-                            ratios,
-                            colors,
-                            num_colors: gradient.records.len() as u32,
-                            matrix: swf_to_gl_matrix(gradient.matrix),
-                            repeat_mode: gradient.spread,
-                            focal_point: *focal_point,
-                            interpolation: gradient.interpolation,
-                        };
-
-                        flush_draw(DrawType::Gradient(gradient), &mut mesh, &mut lyon_mesh);
                     }
-                    FillStyle::Bitmap {
-                        id,
-                        matrix,
-                        is_smoothed,
-                        is_repeating,
-                    } => {
+                    style => {
                         flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
 
                         let mut buffers_builder = BuffersBuilder::new(
@@ -211,7 +78,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -219,17 +86,8 @@ impl ShapeTessellator {
                             continue;
                         }
 
-                        let (bitmap_width, bitmap_height) =
-                            (get_bitmap_dimensions)(*id).unwrap_or((1, 1));
-
-                        let bitmap = Bitmap {
-                            matrix: swf_bitmap_to_gl_matrix(*matrix, bitmap_width, bitmap_height),
-                            id: *id,
-                            is_smoothed: *is_smoothed,
-                            is_repeating: *is_repeating,
-                        };
-
-                        flush_draw(DrawType::Bitmap(bitmap), &mut mesh, &mut lyon_mesh);
+                        let draw_type = fill_style_to_draw_type(style, &get_bitmap_dimensions);
+                        flush_draw(draw_type, &mut mesh, &mut lyon_mesh);
                     }
                 },
                 DrawPath::Stroke {
@@ -237,10 +95,24 @@ impl ShapeTessellator {
                     commands,
                     is_closed,
                 } => {
-                    let color = ((style.color.a as u32) << 24)
-                        | ((style.color.b as u32) << 16)
-                        | ((style.color.g as u32) << 8)
-                        | (style.color.r as u32);
+                    // Strokes with a gradient or bitmap fill are drawn like fills of that style,
+                    // in their own draw call.
+                    let draw_type = match &style.fill_style {
+                        Some(FillStyle::Color(_)) | None => None,
+                        Some(fill_style) => {
+                            Some(fill_style_to_draw_type(fill_style, &get_bitmap_dimensions))
+                        }
+                    };
+
+                    let color = if draw_type.is_some() {
+                        flush_draw(DrawType::Color, &mut mesh, &mut lyon_mesh);
+                        0xffff_ffff
+                    } else {
+                        ((style.color.a as u32) << 24)
+                            | ((style.color.b as u32) << 16)
+                            | ((style.color.g as u32) << 8)
+                            | (style.color.r as u32)
+                    };
 
                     let mut buffers_builder =
                         BuffersBuilder::new(&mut lyon_mesh, RuffleVertexCtor { color });
@@ -283,6 +155,10 @@ impl ShapeTessellator {
                         log::error!("Tessellation failure: {:?}", e);
                         continue;
                     }
+
+                    if let Some(draw_type) = draw_type {
+                        flush_draw(draw_type, &mut mesh, &mut lyon_mesh);
+                    }
                 }
             }
         }
@@ -340,6 +216,66 @@ pub struct Bitmap {
     pub is_repeating: bool,
 }
 
+/// Converts a fill style into the draw call that paints it. Vertices drawn with a gradient or
+/// bitmap draw type should be white.
+fn fill_style_to_draw_type<F>(style: &FillStyle, get_bitmap_dimensions: &F) -> DrawType
+where
+    F: Fn(swf::CharacterId) -> Option<(u32, u32)>,
+{
+    fn gradient(
+        gradient: &swf::Gradient,
+        gradient_type: GradientType,
+        focal_point: f32,
+    ) -> DrawType {
+        let mut colors: Vec<[f32; 4]> = Vec::with_capacity(8);
+        let mut ratios: Vec<f32> = Vec::with_capacity(8);
+        for record in &gradient.records {
+            colors.push([
+                f32::from(record.color.r) / 255.0,
+                f32::from(record.color.g) / 255.0,
+                f32::from(record.color.b) / 255.0,
+                f32::from(record.color.a) / 255.0,
+            ]);
+            ratios.push(f32::from(record.ratio) / 255.0);
+        }
+
+        DrawType::Gradient(Gradient {
+            gradient_type,
+            ratios,
+            colors,
+            num_colors: gradient.records.len() as u32,
+            matrix: swf_to_gl_matrix(gradient.matrix),
+            repeat_mode: gradient.spread,
+            focal_point,
+            interpolation: gradient.interpolation,
+        })
+    }
+
+    match style {
+        FillStyle::Color(_) => DrawType::Color,
+        FillStyle::LinearGradient(g) => gradient(g, GradientType::Linear, 0.0),
+        FillStyle::RadialGradient(g) => gradient(g, GradientType::Radial, 0.0),
+        FillStyle::FocalGradient {
+            gradient: g,
+            focal_point,
+        } => gradient(g, GradientType::Focal, *focal_point),
+        FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            let (bitmap_width, bitmap_height) = (get_bitmap_dimensions)(*id).unwrap_or((1, 1));
+            DrawType::Bitmap(Bitmap {
+                matrix: swf_bitmap_to_gl_matrix(*matrix, bitmap_width, bitmap_height),
+                id: *id,
+                is_smoothed: *is_smoothed,
+                is_repeating: *is_repeating,
+            })
+        }
+    }
+}
+
 #[allow(clippy::many_single_char_names)]
 fn swf_to_gl_matrix(m: swf::Matrix) -> [[f32; 3]; 3] {
     let tx = m.tx.get() as f32;
@@ -390,6 +326,13 @@ fn swf_bitmap_to_gl_matrix(m: swf::Matrix, bitmap_width: u32, bitmap_height: u32
     [[a, d, 0.0], [b, e, 0.0], [c, f, 1.0]]
 }
 
+fn fill_options(fill_rule: FillRule) -> FillOptions {
+    match fill_rule {
+        FillRule::EvenOdd => FillOptions::even_odd(),
+        FillRule::NonZero => FillOptions::non_zero(),
+    }
+}
+
 fn ruffle_path_to_lyon_path(commands: Vec<DrawCommand>, is_closed: bool) -> Path {
     fn point(x: Twips, y: Twips) -> lyon::math::Point {
         lyon::math::Point::new(x.to_pixels() as f32, y.to_pixels() as f32)
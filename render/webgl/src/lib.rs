@@ -4,6 +4,7 @@ use ruffle_core::backend::render::{
     RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
+use ruffle_core::StageQuality;
 use ruffle_render_common_tess::{GradientSpread, GradientType, ShapeTessellator, Vertex};
 use ruffle_web_common::JsResult;
 use wasm_bindgen::{JsCast, JsValue};
@@ -36,6 +37,10 @@ pub struct WebGlRenderBackend {
     msaa_buffers: Option<MsaaBuffers>,
     msaa_sample_count: u32,
 
+    /// Whether the page shows through where nothing is drawn (`wmode="transparent"`).
+    /// The canvas then has an alpha channel, which holds premultiplied alpha.
+    is_transparent: bool,
+
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
@@ -63,14 +68,23 @@ pub struct WebGlRenderBackend {
     viewport_width: f32,
     viewport_height: f32,
     view_matrix: [[f32; 4]; 4],
+
+    /// The number of draws made in the current frame and in the last frame.
+    frame_draws: usize,
+    last_frame_draws: usize,
+
+    /// The stage quality and SWF version of the movie, which decide whether bitmaps are smoothed.
+    quality: StageQuality,
+    swf_version: u8,
 }
 
 impl WebGlRenderBackend {
-    pub fn new(canvas: &HtmlCanvasElement) -> Result<Self, Error> {
+    pub fn new(canvas: &HtmlCanvasElement, is_transparent: bool) -> Result<Self, Error> {
         // Create WebGL context.
         let options = [
             ("stencil", JsValue::TRUE),
-            ("alpha", JsValue::FALSE),
+            ("alpha", JsValue::from_bool(is_transparent)),
+            ("premultipliedAlpha", JsValue::TRUE),
             ("antialias", JsValue::FALSE),
             ("depth", JsValue::FALSE),
         ];
@@ -150,7 +164,7 @@ impl WebGlRenderBackend {
         let gradient_program = ShaderProgram::new(&gl, &texture_vertex, &gradient_fragment)?;
 
         gl.enable(Gl::BLEND);
-        gl.blend_func(Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA);
+        Self::set_blend_func(&gl, (Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA));
 
         // Necessary to load RGB textures (alignment defaults to 4).
         gl.pixel_storei(Gl::UNPACK_ALIGNMENT, 1);
@@ -162,6 +176,7 @@ impl WebGlRenderBackend {
 
             msaa_buffers: None,
             msaa_sample_count,
+            is_transparent,
 
             color_program,
             gradient_program,
@@ -175,6 +190,10 @@ impl WebGlRenderBackend {
             viewport_width: 500.0,
             viewport_height: 500.0,
             view_matrix: [[0.0; 4]; 4],
+            quality: StageQuality::default(),
+            swf_version: 0,
+            frame_draws: 0,
+            last_frame_draws: 0,
             num_masks: 0,
             num_masks_active: 0,
             write_stencil_mask: 0,
@@ -294,6 +313,14 @@ impl WebGlRenderBackend {
         Ok(quad_mesh)
     }
 
+    /// Set how the colors of a draw are blended with the framebuffer.
+    ///
+    /// Alpha is always accumulated as premultiplied alpha, so that transparent movies
+    /// composite correctly with the page. `blend_func` only affects the color channels.
+    fn set_blend_func(gl: &Gl, (src_blend, dst_blend): (u32, u32)) {
+        gl.blend_func_separate(src_blend, dst_blend, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+    }
+
     fn compile_shader(gl: &Gl, shader_type: u32, glsl_src: &str) -> Result<WebGlShader, Error> {
         let shader = gl.create_shader(shader_type).unwrap();
         gl.shader_source(&shader, glsl_src);
@@ -331,10 +358,12 @@ impl WebGlRenderBackend {
             .create_framebuffer()
             .ok_or("Unable to create framebuffer")?;
 
-        // Note for future self:
-        // Whenever we support playing transparent movies,
-        // switch this to RGBA and probably need to change shaders to all
-        // be premultiplied alpha.
+        // Transparent movies need an alpha channel to composite with the page.
+        let (internal_format, format) = if self.is_transparent {
+            (Gl2::RGBA8, Gl2::RGBA)
+        } else {
+            (Gl2::RGB8, Gl2::RGB)
+        };
         let color_renderbuffer = gl
             .create_renderbuffer()
             .ok_or("Unable to create renderbuffer")?;
@@ -342,7 +371,7 @@ impl WebGlRenderBackend {
         gl.renderbuffer_storage_multisample(
             Gl2::RENDERBUFFER,
             4,
-            Gl2::RGB8,
+            internal_format,
             self.viewport_width as i32,
             self.viewport_height as i32,
         );
@@ -390,11 +419,11 @@ impl WebGlRenderBackend {
         gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
             Gl2::TEXTURE_2D,
             0,
-            Gl2::RGB as i32,
+            format as i32,
             self.viewport_width as i32,
             self.viewport_height as i32,
             0,
-            Gl2::RGB,
+            format,
             Gl2::UNSIGNED_BYTE,
             None,
         )
@@ -795,6 +824,11 @@ impl RenderBackend for WebGlRenderBackend {
         self.register_bitmap(swf_tag.id, bitmap)
     }
 
+    fn set_quality(&mut self, quality: StageQuality, swf_version: u8) {
+        self.quality = quality;
+        self.swf_version = swf_version;
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         self.num_masks = 0;
         self.num_masks_active = 0;
@@ -815,17 +849,23 @@ impl RenderBackend for WebGlRenderBackend {
         }
 
         self.set_stencil_state();
-        self.gl.clear_color(
-            clear.r as f32 / 255.0,
-            clear.g as f32 / 255.0,
-            clear.b as f32 / 255.0,
-            clear.a as f32 / 255.0,
-        );
+        if self.is_transparent {
+            self.gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        } else {
+            self.gl.clear_color(
+                clear.r as f32 / 255.0,
+                clear.g as f32 / 255.0,
+                clear.b as f32 / 255.0,
+                clear.a as f32 / 255.0,
+            );
+        }
         self.gl.stencil_mask(0xff);
         self.gl.clear(Gl::COLOR_BUFFER_BIT | Gl::STENCIL_BUFFER_BIT);
     }
 
     fn end_frame(&mut self) {
+        self.last_frame_draws = std::mem::replace(&mut self.frame_draws, 0);
+
         // Resolve MSAA, if we're using it (WebGL2).
         if let (Some(ref gl), Some(ref msaa_buffers)) = (&self.gl2, &self.msaa_buffers) {
             self.gl.disable(Gl::STENCIL_TEST);
@@ -850,7 +890,13 @@ impl RenderBackend for WebGlRenderBackend {
             );
 
             // Render the resolved framebuffer texture to a quad on the screen.
+            // Its colors are already premultiplied.
             gl.bind_framebuffer(Gl2::FRAMEBUFFER, None);
+            let blend_func = (Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+            if blend_func != self.blend_func {
+                Self::set_blend_func(&self.gl, blend_func);
+                self.blend_func = blend_func;
+            }
             let program = &self.bitmap_program;
             self.gl.use_program(Some(&program.program));
 
@@ -990,7 +1036,7 @@ impl RenderBackend for WebGlRenderBackend {
                 self.add_color = None;
 
                 if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
+                    Self::set_blend_func(&self.gl, (src_blend, dst_blend));
                     self.blend_func = (src_blend, dst_blend);
                 }
             }
@@ -1064,7 +1110,10 @@ impl RenderBackend for WebGlRenderBackend {
                     program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
 
                     // Set texture parameters.
-                    let filter = if bitmap.is_smoothed {
+                    let filter = if self
+                        .quality
+                        .smooth_bitmaps(bitmap.is_smoothed, self.swf_version)
+                    {
                         Gl::LINEAR as i32
                     } else {
                         Gl::NEAREST as i32
@@ -1089,9 +1138,14 @@ impl RenderBackend for WebGlRenderBackend {
             // Draw the triangles.
             self.gl
                 .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_SHORT, 0);
+            self.frame_draws += 1;
         }
     }
 
+    fn last_frame_draw_count(&self) -> Option<usize> {
+        Some(self.last_frame_draws)
+    }
+
     fn draw_letterbox(&mut self, letterbox: Letterbox) {
         self.set_stencil_state();
 
@@ -1180,6 +1234,27 @@ impl RenderBackend for WebGlRenderBackend {
             log::warn!("Mask stack underflow\n");
         }
     }
+
+    fn create_offscreen_bitmap(&mut self, _width: u32, _height: u32) -> Option<BitmapHandle> {
+        // TODO: Offscreen rendering is not supported yet; cached objects render normally.
+        None
+    }
+
+    fn push_offscreen_target(&mut self, _bitmap: BitmapHandle) {}
+
+    fn pop_offscreen_target(&mut self) {}
+
+    fn remove_offscreen_bitmap(&mut self, _bitmap: BitmapHandle) {}
+
+    fn offscreen_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
+        None
+    }
+
+    fn push_blend_mode(&mut self, _blend_mode: swf::BlendMode) {
+        // TODO: Blend modes are not supported yet; objects are drawn with normal blending.
+    }
+
+    fn pop_blend_mode(&mut self) {}
 }
 
 struct Texture {
@@ -9,24 +9,28 @@ use ruffle_core::backend::render::{
     RenderBackend, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::{DistilledShape, DrawPath};
+use ruffle_core::StageQuality;
 use std::convert::TryInto;
-use swf::{CharacterId, DefineBitsLossless, Glyph, GradientInterpolation};
+use swf::{BlendMode, CharacterId, DefineBitsLossless, Glyph, GradientInterpolation};
 
 use bytemuck::{Pod, Zeroable};
 use futures::executor::block_on;
 use raw_window_handle::HasRawWindowHandle;
 
-use crate::pipelines::Pipelines;
+use crate::pipelines::{blend_descriptors, Pipelines, ShapePipeline};
 use crate::shapes::{Draw, DrawType, GradientUniforms, IncompleteDrawType, Mesh};
 use crate::target::{RenderTarget, RenderTargetFrame, SwapChainTarget};
 use crate::utils::{
-    build_view_matrix, create_buffer_with_data, format_list, get_backend_names,
+    build_view_matrix, create_buffer_with_data, fill_options, format_list, get_backend_names,
     gradient_spread_mode_index, ruffle_path_to_lyon_path, swf_bitmap_to_gl_matrix,
-    swf_to_gl_matrix,
+    swf_to_gl_matrix, BufferDimensions,
 };
 use ruffle_core::color_transform::ColorTransform;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::mem::replace;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 type Error = Box<dyn std::error::Error>;
 
@@ -49,11 +53,22 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     depth_texture_view: wgpu::TextureView,
     current_frame: Option<(T::Frame, wgpu::CommandEncoder)>,
     register_encoder: wgpu::CommandEncoder,
-    meshes: Vec<Mesh>,
+    meshes: Vec<Rc<Mesh>>,
     viewport_width: f32,
     viewport_height: f32,
     view_matrix: [[f32; 4]; 4],
-    textures: Vec<(swf::CharacterId, Texture)>,
+    /// The texture of each bitmap handle. Bitmaps with identical pixels share a texture.
+    textures: Vec<Rc<Texture>>,
+
+    /// The bitmap handle of each registered bitmap character.
+    bitmap_handles: HashMap<swf::CharacterId, BitmapHandle>,
+
+    /// The textures of registered bitmaps, keyed by their dimensions and a hash of their pixels,
+    /// so that bitmaps with identical pixels can share a texture.
+    texture_cache: HashMap<(u32, u32, u64), Weak<Texture>>,
+
+    /// The number of bytes of texture memory saved by sharing textures between bitmaps.
+    deduplicated_bytes: usize,
     num_masks: u32,
     num_masks_active: u32,
     write_stencil_mask: u32,
@@ -63,6 +78,274 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     quad_vbo: wgpu::Buffer,
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
+    bitmap_sampler: wgpu::Sampler,
+    nearest_bitmap_sampler: wgpu::Sampler,
+    offscreen_targets: HashMap<usize, Rc<OffscreenTarget>>,
+    offscreen_stack: Vec<OffscreenState>,
+    free_offscreen_bitmaps: Vec<usize>,
+    blend_modes: Vec<BlendMode>,
+    unsupported_blend_modes: HashSet<BlendMode>,
+
+    /// The buffer holding the `DrawUniforms` of every draw since the last flush.
+    /// Draws select their uniforms from it with a dynamic offset.
+    uniform_buffer: wgpu::Buffer,
+
+    /// The uniforms of the draws recorded since the last flush, uploaded to the uniform buffer
+    /// all at once.
+    uniforms: Vec<DrawUniforms>,
+
+    /// The bind group used to draw solid rectangles.
+    color_bind_group: wgpu::BindGroup,
+
+    /// The render passes recorded since the last flush.
+    render_passes: Vec<RenderPassCommands>,
+
+    /// Whether the viewport has no area, e.g. because the window is minimized.
+    /// Nothing is rendered until it is resized again.
+    is_minimized: bool,
+
+    /// The number of frames that were skipped because the render target couldn't provide a frame.
+    dropped_frames: u64,
+
+    /// The number of draws recorded in the current frame, in the last frame, and at most in any
+    /// frame. Frames with more than `MAX_DRAWS_PER_FLUSH` draws are submitted in several parts.
+    frame_draws: usize,
+    last_frame_draws: usize,
+    peak_frame_draws: usize,
+
+    /// The stage quality and SWF version of the movie, which decide whether bitmaps are smoothed.
+    quality: StageQuality,
+    swf_version: u8,
+
+    /// Whether the time the GPU spends on each frame is measured.
+    gpu_timing: GpuTiming,
+}
+
+/// The state of GPU timing, as requested when the backend is created.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum GpuTiming {
+    /// Timing was not requested.
+    Off,
+
+    /// Timing was requested, but can't be done on this device.
+    ///
+    /// Measuring GPU time needs timestamp queries, which the version of wgpu in use doesn't
+    /// expose on any adapter, so every request currently ends up here.
+    Unavailable,
+}
+
+impl GpuTiming {
+    fn new(requested: bool) -> Self {
+        if requested {
+            log::info!("GPU timing is unavailable: timestamp queries are not supported");
+            GpuTiming::Unavailable
+        } else {
+            GpuTiming::Off
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            GpuTiming::Off => "off",
+            GpuTiming::Unavailable => "unavailable",
+        }
+    }
+}
+
+/// A rectangle of the viewport that is rendered on its own, in pixels.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Tile {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The largest width or height of a texture that we render into.
+///
+/// wgpu 0.6 doesn't report the device's own limit, so this is the limit that every WebGPU device
+/// is guaranteed to support.
+pub const MAX_TEXTURE_DIMENSION: u32 = 8192;
+
+/// The maximum number of draws recorded before they must be submitted to the GPU, limited by the
+/// size of the uniform buffer.
+const MAX_DRAWS_PER_FLUSH: usize = 4096;
+
+/// The offset of the `Transforms` of a draw within its `DrawUniforms`.
+const TRANSFORMS_OFFSET: u64 = 0;
+
+/// The offset of the `ColorAdjustments` of a draw within its `DrawUniforms`.
+const COLORS_OFFSET: u64 = 256;
+
+/// The attachment that a render pass draws into.
+#[derive(Clone, Debug)]
+enum PassTarget {
+    Frame,
+    Offscreen(Rc<OffscreenTarget>),
+}
+
+/// The draws of a single render pass, recorded during the frame and replayed when flushed.
+///
+/// A new render pass is only started when the attachments change, or one of them needs to be
+/// cleared.
+#[derive(Debug)]
+struct RenderPassCommands {
+    target: PassTarget,
+    color_load: wgpu::LoadOp<wgpu::Color>,
+    depth_load: wgpu::LoadOp<f32>,
+    stencil_load: wgpu::LoadOp<u32>,
+    commands: Vec<DrawCommand>,
+}
+
+impl RenderPassCommands {
+    /// A render pass that continues drawing into the given target.
+    fn load(target: PassTarget) -> Self {
+        Self {
+            target,
+            color_load: wgpu::LoadOp::Load,
+            depth_load: wgpu::LoadOp::Load,
+            stencil_load: wgpu::LoadOp::Load,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Whether the render pass would have no effect.
+    fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+            && matches!(self.color_load, wgpu::LoadOp::Load)
+            && matches!(self.depth_load, wgpu::LoadOp::Load)
+            && matches!(self.stencil_load, wgpu::LoadOp::Load)
+    }
+}
+
+#[derive(Debug)]
+enum DrawCommand {
+    Shape {
+        mesh: Rc<Mesh>,
+        uniforms: wgpu::DynamicOffset,
+        mask_state: MaskState,
+    },
+    Bitmap {
+        bind_group: wgpu::BindGroup,
+        blend_mode: Option<BlendMode>,
+        uniforms: wgpu::DynamicOffset,
+        mask_state: MaskState,
+    },
+    Rect {
+        uniforms: wgpu::DynamicOffset,
+        mask_state: MaskState,
+    },
+}
+
+/// The masking state a draw was recorded with, which selects its pipeline and stencil reference.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct MaskState {
+    num_masks: u32,
+    num_masks_active: u32,
+    test_stencil_mask: u32,
+    write_stencil_mask: u32,
+}
+
+impl MaskState {
+    fn pipeline(self, pipeline: &ShapePipeline) -> &wgpu::RenderPipeline {
+        pipeline.pipeline_for(
+            self.num_masks,
+            self.num_masks_active,
+            self.test_stencil_mask,
+            self.write_stencil_mask,
+        )
+    }
+
+    /// Whether draws write a masker into the stencil buffer, rather than drawing visible content.
+    fn is_writing_mask(self) -> bool {
+        self.num_masks_active < self.num_masks
+    }
+
+    fn stencil_reference(self) -> u32 {
+        if self.is_writing_mask() {
+            self.write_stencil_mask
+        } else {
+            self.test_stencil_mask
+        }
+    }
+}
+
+/// The kinds of pipeline a draw can use.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PipelineKind {
+    Color,
+    Gradient,
+    Bitmap,
+    BitmapBlend(BlendMode),
+}
+
+/// The state of a render pass while it is replayed, so that it is only changed between draws
+/// when necessary.
+#[derive(Default)]
+struct PassState {
+    pipeline: Option<(PipelineKind, MaskState)>,
+    stencil_reference: Option<u32>,
+}
+
+impl PassState {
+    fn set_pipeline<'a>(
+        &mut self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        pipelines: &'a Pipelines,
+        kind: PipelineKind,
+        mask_state: MaskState,
+    ) {
+        if self.pipeline != Some((kind, mask_state)) {
+            let pipeline = match kind {
+                PipelineKind::Color => mask_state.pipeline(&pipelines.color),
+                PipelineKind::Gradient => mask_state.pipeline(&pipelines.gradient),
+                PipelineKind::Bitmap => mask_state.pipeline(&pipelines.bitmap),
+                // Maskers never write colour, so they can't be blended.
+                PipelineKind::BitmapBlend(_) if mask_state.is_writing_mask() => {
+                    mask_state.pipeline(&pipelines.bitmap)
+                }
+                PipelineKind::BitmapBlend(blend_mode) => pipelines
+                    .bitmap_blend
+                    .get(blend_mode, mask_state.test_stencil_mask)
+                    .unwrap_or_else(|| mask_state.pipeline(&pipelines.bitmap)),
+            };
+            render_pass.set_pipeline(pipeline);
+            self.pipeline = Some((kind, mask_state));
+        }
+
+        let stencil_reference = mask_state.stencil_reference();
+        if self.stencil_reference != Some(stencil_reference) {
+            render_pass.set_stencil_reference(stencil_reference);
+            self.stencil_reference = Some(stencil_reference);
+        }
+    }
+}
+
+/// The buffers needed to render into an offscreen bitmap.
+#[derive(Debug)]
+struct OffscreenTarget {
+    /// The view of the bitmap texture itself.
+    view: wgpu::TextureView,
+
+    /// The multisampled buffer that is resolved into the bitmap, if MSAA is enabled.
+    frame_buffer_view: Option<wgpu::TextureView>,
+
+    depth_texture_view: wgpu::TextureView,
+    view_matrix: [[f32; 4]; 4],
+}
+
+/// The state saved when rendering switches to an offscreen target, restored when it is popped.
+#[derive(Debug)]
+struct OffscreenState {
+    bitmap: usize,
+    view_matrix: [[f32; 4]; 4],
+    num_masks: u32,
+    num_masks_active: u32,
+    write_stencil_mask: u32,
+    test_stencil_mask: u32,
+    next_stencil_mask: u32,
+    mask_stack: Vec<(u32, u32)>,
+    blend_modes: Vec<BlendMode>,
 }
 
 #[repr(C)]
@@ -113,6 +396,48 @@ impl From<ColorTransform> for ColorAdjustments {
 unsafe impl Pod for ColorAdjustments {}
 unsafe impl Zeroable for ColorAdjustments {}
 
+/// The uniforms of a single draw.
+/// Each part is aligned to `wgpu::BIND_BUFFER_ALIGNMENT` so that it can be bound with a dynamic
+/// offset.
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct DrawUniforms {
+    transforms: Transforms,
+    _transforms_padding: [u8; 128],
+    colors: ColorAdjustments,
+    _colors_padding: [u8; 224],
+}
+
+unsafe impl Pod for DrawUniforms {}
+unsafe impl Zeroable for DrawUniforms {}
+
+impl DrawUniforms {
+    fn new(transforms: Transforms, colors: ColorAdjustments) -> Self {
+        Self {
+            transforms,
+            _transforms_padding: [0; 128],
+            colors,
+            _colors_padding: [0; 224],
+        }
+    }
+}
+
+/// Binds the `Transforms` of the draw selected by the dynamic offset.
+fn transforms_binding(uniform_buffer: &wgpu::Buffer) -> wgpu::BindingResource {
+    wgpu::BindingResource::Buffer(
+        uniform_buffer
+            .slice(TRANSFORMS_OFFSET..TRANSFORMS_OFFSET + std::mem::size_of::<Transforms>() as u64),
+    )
+}
+
+/// Binds the `ColorAdjustments` of the draw selected by the dynamic offset.
+fn colors_binding(uniform_buffer: &wgpu::Buffer) -> wgpu::BindingResource {
+    wgpu::BindingResource::Buffer(
+        uniform_buffer
+            .slice(COLORS_OFFSET..COLORS_OFFSET + std::mem::size_of::<ColorAdjustments>() as u64),
+    )
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
 struct GPUVertex {
@@ -129,6 +454,7 @@ impl WgpuRenderBackend<SwapChainTarget> {
         size: (u32, u32),
         backend: wgpu::BackendBit,
         power_preference: wgpu::PowerPreference,
+        gpu_timing: bool,
     ) -> Result<Self, Error> {
         if wgpu::BackendBit::SECONDARY.contains(backend) {
             log::warn!(
@@ -164,15 +490,35 @@ impl WgpuRenderBackend<SwapChainTarget> {
         ))?;
 
         let target = SwapChainTarget::new(surface, size, &device);
-        Self::new(Rc::new(device), Rc::new(queue), target)
+        Self::new(Rc::new(device), Rc::new(queue), target, gpu_timing)
     }
 }
 
 impl<T: RenderTarget> WgpuRenderBackend<T> {
-    pub fn new(device: Rc<wgpu::Device>, queue: Rc<wgpu::Queue>, target: T) -> Result<Self, Error> {
+    /// Create a backend drawing into `target`.
+    ///
+    /// `gpu_timing` requests that the GPU time of each frame is measured and reported by
+    /// `debug_info`. It is off by default, as the measurements have a small cost of their own.
+    pub fn new(
+        device: Rc<wgpu::Device>,
+        queue: Rc<wgpu::Queue>,
+        target: T,
+        gpu_timing: bool,
+    ) -> Result<Self, Error> {
         // TODO: Allow this to be set from command line/settings file.
         let msaa_sample_count = 4;
 
+        if target.width() > MAX_TEXTURE_DIMENSION || target.height() > MAX_TEXTURE_DIMENSION {
+            return Err(format!(
+                "Render target of {}x{} exceeds the maximum texture size of {}x{}",
+                target.width(),
+                target.height(),
+                MAX_TEXTURE_DIMENSION,
+                MAX_TEXTURE_DIMENSION
+            )
+            .into());
+        }
+
         let pipelines = Pipelines::new(&device, msaa_sample_count)?;
 
         let extent = wgpu::Extent3d {
@@ -213,6 +559,60 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         let (quad_vbo, quad_ibo, quad_tex_transforms) = create_quad_buffers(&device);
 
+        let bitmap_sampler_label = create_debug_label!("Bitmap sampler");
+        let bitmap_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: bitmap_sampler_label.as_deref(),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 100.0,
+            compare: None,
+            anisotropy_clamp: None,
+        });
+
+        let nearest_bitmap_sampler_label = create_debug_label!("Nearest bitmap sampler");
+        let nearest_bitmap_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: nearest_bitmap_sampler_label.as_deref(),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 100.0,
+            compare: None,
+            anisotropy_clamp: None,
+        });
+
+        let uniform_buffer_label = create_debug_label!("Draw uniforms buffer");
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: uniform_buffer_label.as_deref(),
+            size: (MAX_DRAWS_PER_FLUSH * std::mem::size_of::<DrawUniforms>()) as u64,
+            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let color_bind_group_label = create_debug_label!("Rectangle bind group");
+        let color_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &pipelines.color.bind_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: transforms_binding(&uniform_buffer),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: colors_binding(&uniform_buffer),
+                },
+            ],
+            label: color_bind_group_label.as_deref(),
+        });
+
         let viewport_width = target.width() as f32;
         let viewport_height = target.height() as f32;
         let view_matrix = build_view_matrix(target.width(), target.height());
@@ -232,6 +632,9 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             viewport_height,
             view_matrix,
             textures: Vec::new(),
+            bitmap_handles: HashMap::new(),
+            texture_cache: HashMap::new(),
+            deduplicated_bytes: 0,
             num_masks: 0,
             num_masks_active: 0,
             write_stencil_mask: 0,
@@ -241,30 +644,31 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             quad_vbo,
             quad_ibo,
             quad_tex_transforms,
+            bitmap_sampler,
+            nearest_bitmap_sampler,
+            offscreen_targets: HashMap::new(),
+            offscreen_stack: Vec::new(),
+            free_offscreen_bitmaps: Vec::new(),
+            blend_modes: Vec::new(),
+            unsupported_blend_modes: HashSet::new(),
+            uniform_buffer,
+            uniforms: Vec::with_capacity(MAX_DRAWS_PER_FLUSH),
+            color_bind_group,
+            render_passes: Vec::new(),
+            is_minimized: false,
+            dropped_frames: 0,
+            frame_draws: 0,
+            last_frame_draws: 0,
+            peak_frame_draws: 0,
+            quality: StageQuality::default(),
+            swf_version: 0,
+            gpu_timing: GpuTiming::new(gpu_timing),
         })
     }
 
     #[allow(clippy::cognitive_complexity)]
     fn register_shape_internal(&mut self, shape: DistilledShape) -> Mesh {
-        use lyon::tessellation::{FillOptions, StrokeOptions};
-
-        let transforms_label = create_debug_label!("Shape {} transforms ubo", shape.id);
-        let transforms_ubo = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: transforms_label.as_deref(),
-            size: std::mem::size_of::<Transforms>() as u64,
-            usage: wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
-            mapped_at_creation: false,
-        });
-
-        let colors_ubo = create_buffer_with_data(
-            &self.device,
-            bytemuck::cast_slice(&[ColorAdjustments {
-                mult_color: [1.0, 1.0, 1.0, 1.0],
-                add_color: [0.0, 0.0, 0.0, 0.0],
-            }]),
-            wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
-            create_debug_label!("Shape {} colors ubo", shape.id),
-        );
+        use lyon::tessellation::StrokeOptions;
 
         let mut draws = Vec::new();
 
@@ -279,8 +683,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             draws: &mut Vec<Draw>,
             lyon_mesh: &mut VertexBuffers<GPUVertex, u16>,
             device: &wgpu::Device,
-            transforms_ubo: &wgpu::Buffer,
-            colors_ubo: &wgpu::Buffer,
+            uniform_buffer: &wgpu::Buffer,
             pipelines: &Pipelines,
         ) {
             if lyon_mesh.vertices.is_empty() || lyon_mesh.indices.len() < 3 {
@@ -305,8 +708,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
             draws.push(draw.build(
                 device,
-                transforms_ubo,
-                colors_ubo,
+                uniform_buffer,
                 vbo,
                 ibo,
                 lyon_mesh.indices.len() as u32,
@@ -320,7 +722,11 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         for path in shape.paths {
             match path {
-                DrawPath::Fill { style, commands } => match style {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    fill_rule,
+                } => match style {
                     FillStyle::Color(color) => {
                         let color = [
                             f32::from(color.r) / 255.0,
@@ -334,7 +740,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
                         if let Err(e) = fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -349,8 +755,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
 
@@ -363,7 +768,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
                         if let Err(e) = fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -383,8 +788,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
                     }
@@ -395,8 +799,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
 
@@ -409,7 +812,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
                         if let Err(e) = fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -429,8 +832,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
                     }
@@ -444,8 +846,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
 
@@ -458,7 +859,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
                         if let Err(e) = fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -478,8 +879,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
                     }
@@ -495,8 +895,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
 
@@ -509,7 +908,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
                         if let Err(e) = fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(fill_rule),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -518,15 +917,15 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                         }
 
                         let texture = match self
-                            .textures
-                            .iter()
-                            .find(|(other_id, _tex)| *other_id == *id)
+                            .bitmap_handles
+                            .get(id)
+                            .and_then(|handle| self.textures.get(handle.0))
                         {
                             None => {
                                 log::error!("Couldn't fill shape with unknown bitmap {}", id);
                                 continue;
                             }
-                            Some(t) => &t.1,
+                            Some(texture) => texture,
                         };
                         let texture_view = texture.texture.create_view(&Default::default());
 
@@ -546,8 +945,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                             &mut draws,
                             &mut lyon_mesh,
                             &self.device,
-                            &transforms_ubo,
-                            &colors_ubo,
+                            &self.uniform_buffer,
                             &self.pipelines,
                         );
                     }
@@ -615,18 +1013,11 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             &mut draws,
             &mut lyon_mesh,
             &self.device,
-            &transforms_ubo,
-            &colors_ubo,
+            &self.uniform_buffer,
             &self.pipelines,
         );
 
-        Mesh {
-            draws,
-            transforms: transforms_ubo,
-            colors_buffer: colors_ubo,
-            colors_last: ColorTransform::default(),
-            shape_id: shape.id,
-        }
+        Mesh { draws }
     }
 
     fn register_bitmap(
@@ -657,6 +1048,14 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             }
         };
 
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        let key = (bitmap.width, bitmap.height, hasher.finish());
+        if let Some(texture) = self.texture_cache.get(&key).and_then(Weak::upgrade) {
+            self.deduplicated_bytes += data.len();
+            return Ok(self.add_bitmap_texture(id, texture));
+        }
+
         let texture_label = create_debug_label!("{} Texture {}", debug_str, id);
         let texture = self.device.create_texture(&wgpu::TextureDescriptor {
             label: texture_label.as_deref(),
@@ -683,21 +1082,26 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             extent,
         );
 
-        let handle = BitmapHandle(self.textures.len());
-        self.textures.push((
-            id,
-            Texture {
-                texture,
-                width: bitmap.width,
-                height: bitmap.height,
-            },
-        ));
+        let texture = Rc::new(Texture {
+            texture,
+            width: bitmap.width,
+            height: bitmap.height,
+        });
+        self.texture_cache.insert(key, Rc::downgrade(&texture));
+        Ok(self.add_bitmap_texture(id, texture))
+    }
 
-        Ok(BitmapInfo {
+    /// Gives the bitmap character with the given ID a new handle to the given texture.
+    fn add_bitmap_texture(&mut self, id: swf::CharacterId, texture: Rc<Texture>) -> BitmapInfo {
+        let handle = BitmapHandle(self.textures.len());
+        let info = BitmapInfo {
             handle,
-            width: bitmap.width.try_into().unwrap(),
-            height: bitmap.height.try_into().unwrap(),
-        })
+            width: texture.width.try_into().unwrap(),
+            height: texture.height.try_into().unwrap(),
+        };
+        self.textures.push(texture);
+        self.bitmap_handles.entry(id).or_insert(handle);
+        info
     }
 
     pub fn target(&self) -> &T {
@@ -708,13 +1112,45 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         &self.device
     }
 
+    /// The tiles that cover the viewport, row by row.
+    ///
+    /// This is a single tile unless the viewport is larger than `MAX_TEXTURE_DIMENSION`. Each
+    /// tile is drawn into the same target, so the viewport is rendered by drawing the frame once
+    /// per tile after selecting it with `set_tile`, and capturing the target in between.
+    pub fn tiles(&self) -> Vec<Tile> {
+        let viewport_width = self.viewport_width as u32;
+        let viewport_height = self.viewport_height as u32;
+        let (tile_width, tile_height) = (self.target.width(), self.target.height());
+        let mut tiles = Vec::new();
+        for y in (0..viewport_height).step_by(tile_height as usize) {
+            for x in (0..viewport_width).step_by(tile_width as usize) {
+                tiles.push(Tile {
+                    x,
+                    y,
+                    width: tile_width.min(viewport_width - x),
+                    height: tile_height.min(viewport_height - y),
+                });
+            }
+        }
+        tiles
+    }
+
+    /// Selects the part of the viewport that following frames draw into the target, until the
+    /// viewport is resized.
+    ///
+    /// The tile's top left corner is moved to the target's origin. Tiles are offset by whole
+    /// pixels, so shapes crossing their edges are rasterized, antialiased and masked exactly as
+    /// they would be in a single large target, and the tiles can be stitched without seams.
+    pub fn set_tile(&mut self, tile: Tile) {
+        self.view_matrix = build_view_matrix(self.target.width(), self.target.height());
+        self.view_matrix[3][0] -= tile.x as f32 * self.view_matrix[0][0];
+        self.view_matrix[3][1] -= tile.y as f32 * self.view_matrix[1][1];
+    }
+
     fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
-        let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
-        {
-            (frame_output, encoder)
-        } else {
+        if self.current_frame.is_none() {
             return;
-        };
+        }
 
         let world_matrix = [
             [width, 0.0, 0.0, 0.0],
@@ -732,107 +1168,288 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
         let add_color = [0.0, 0.0, 0.0, 0.0];
 
-        let transforms_ubo = create_buffer_with_data(
-            &self.device,
-            bytemuck::cast_slice(&[Transforms {
-                view_matrix: self.view_matrix,
-                world_matrix,
-            }]),
-            wgpu::BufferUsage::UNIFORM,
-            create_debug_label!("Rectangle transfer buffer"),
-        );
-
-        let colors_ubo = create_buffer_with_data(
-            &self.device,
-            bytemuck::cast_slice(&[ColorAdjustments {
+        let uniforms = self.push_uniforms(
+            world_matrix,
+            ColorAdjustments {
                 mult_color,
                 add_color,
-            }]),
-            wgpu::BufferUsage::UNIFORM,
-            create_debug_label!("Rectangle colors transfer buffer"),
+            },
         );
-
-        let bind_group_label = create_debug_label!("Rectangle bind group");
-        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &self.pipelines.color.bind_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::Buffer(
-                        transforms_ubo.slice(0..std::mem::size_of::<Transforms>() as u64),
-                    ),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Buffer(
-                        colors_ubo.slice(0..std::mem::size_of::<ColorAdjustments>() as u64),
-                    ),
-                },
-            ],
-            label: bind_group_label.as_deref(),
+        let mask_state = self.mask_state();
+        self.push_command(DrawCommand::Rect {
+            uniforms,
+            mask_state,
         });
+    }
+
+    fn mask_state(&self) -> MaskState {
+        MaskState {
+            num_masks: self.num_masks,
+            num_masks_active: self.num_masks_active,
+            test_stencil_mask: self.test_stencil_mask,
+            write_stencil_mask: self.write_stencil_mask,
+        }
+    }
 
-        let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_output.view()))
+    /// The target that draws currently go to: the innermost offscreen target, or else the frame.
+    fn current_target(&self) -> PassTarget {
+        match self
+            .offscreen_stack
+            .last()
+            .and_then(|state| self.offscreen_targets.get(&state.bitmap))
+        {
+            Some(target) => PassTarget::Offscreen(target.clone()),
+            None => PassTarget::Frame,
+        }
+    }
+
+    /// Starts a new render pass, if a frame is being rendered.
+    fn begin_render_pass(&mut self, render_pass: RenderPassCommands) {
+        if self.current_frame.is_some() {
+            self.render_passes.push(render_pass);
+        }
+    }
+
+    /// Records a draw into the current render pass.
+    fn push_command(&mut self, command: DrawCommand) {
+        if let Some(render_pass) = self.render_passes.last_mut() {
+            render_pass.commands.push(command);
+        }
+    }
+
+    /// Adds the uniforms of a draw, returning the dynamic offset to draw with.
+    ///
+    /// If the uniform buffer is full, the draws recorded so far are submitted first.
+    fn push_uniforms(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        colors: ColorAdjustments,
+    ) -> wgpu::DynamicOffset {
+        if self.uniforms.len() >= MAX_DRAWS_PER_FLUSH {
+            self.flush_render_passes();
+            self.submit_frame_commands();
+        }
+
+        self.frame_draws += 1;
+        let offset = self.uniforms.len() * std::mem::size_of::<DrawUniforms>();
+        self.uniforms.push(DrawUniforms::new(
+            Transforms {
+                view_matrix: self.view_matrix,
+                world_matrix,
+            },
+            colors,
+        ));
+        offset as wgpu::DynamicOffset
+    }
+
+    /// Uploads the uniforms of the recorded draws, and encodes the recorded render passes into
+    /// the frame's command encoder.
+    ///
+    /// Drawing continues into the same target afterwards.
+    fn flush_render_passes(&mut self) {
+        let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
+        {
+            (frame_output, encoder)
         } else {
-            (frame_output.view(), None)
+            return;
         };
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                attachment: color_attachment,
-                resolve_target,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
-                },
-            }],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
+
+        if !self.uniforms.is_empty() {
+            self.queue.write_buffer(
+                &self.uniform_buffer,
+                0,
+                bytemuck::cast_slice(&self.uniforms),
+            );
+            self.uniforms.clear();
+        }
+
+        let render_passes = std::mem::take(&mut self.render_passes);
+        for pass in &render_passes {
+            if pass.is_empty() {
+                continue;
+            }
+
+            let offscreen_target = match &pass.target {
+                PassTarget::Frame => None,
+                PassTarget::Offscreen(target) => Some(target.as_ref()),
+            };
+            let (color_attachment, resolve_target, depth_attachment) = render_attachments(
+                frame_output.view(),
+                &self.frame_buffer_view,
+                &self.depth_texture_view,
+                self.msaa_sample_count,
+                offscreen_target,
+            );
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                    attachment: color_attachment,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: pass.color_load,
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                    attachment: depth_attachment,
+                    depth_ops: Some(wgpu::Operations {
+                        load: pass.depth_load,
+                        store: true,
+                    }),
+                    stencil_ops: Some(wgpu::Operations {
+                        load: pass.stencil_load,
+                        store: true,
+                    }),
                 }),
-            }),
-        });
+            });
 
-        render_pass.set_pipeline(&self.pipelines.color.pipeline_for(
-            self.num_masks,
-            self.num_masks_active,
-            self.test_stencil_mask,
-            self.write_stencil_mask,
-        ));
-        render_pass.set_bind_group(0, &bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
-        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+            let mut state = PassState::default();
+            for command in &pass.commands {
+                match command {
+                    DrawCommand::Shape {
+                        mesh,
+                        uniforms,
+                        mask_state,
+                    } => {
+                        for draw in &mesh.draws {
+                            let kind = match &draw.draw_type {
+                                DrawType::Color => PipelineKind::Color,
+                                DrawType::Gradient { .. } => PipelineKind::Gradient,
+                                DrawType::Bitmap { .. } => PipelineKind::Bitmap,
+                            };
+                            state.set_pipeline(
+                                &mut render_pass,
+                                &self.pipelines,
+                                kind,
+                                *mask_state,
+                            );
+                            render_pass.set_bind_group(
+                                0,
+                                draw.bind_group_for(self.quality, self.swf_version),
+                                &[*uniforms, *uniforms],
+                            );
+                            render_pass.set_vertex_buffer(0, draw.vertex_buffer.slice(..));
+                            render_pass.set_index_buffer(draw.index_buffer.slice(..));
+                            render_pass.draw_indexed(0..draw.index_count, 0, 0..1);
+                        }
+                    }
+                    DrawCommand::Bitmap {
+                        bind_group,
+                        blend_mode,
+                        uniforms,
+                        mask_state,
+                    } => {
+                        let kind = match blend_mode {
+                            Some(blend_mode) => PipelineKind::BitmapBlend(*blend_mode),
+                            None => PipelineKind::Bitmap,
+                        };
+                        state.set_pipeline(&mut render_pass, &self.pipelines, kind, *mask_state);
+                        render_pass.set_bind_group(0, bind_group, &[*uniforms, *uniforms]);
+                        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+                        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+                        render_pass.draw_indexed(0..6, 0, 0..1);
+                    }
+                    DrawCommand::Rect {
+                        uniforms,
+                        mask_state,
+                    } => {
+                        state.set_pipeline(
+                            &mut render_pass,
+                            &self.pipelines,
+                            PipelineKind::Color,
+                            *mask_state,
+                        );
+                        render_pass.set_bind_group(
+                            0,
+                            &self.color_bind_group,
+                            &[*uniforms, *uniforms],
+                        );
+                        render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
+                        render_pass.set_index_buffer(self.quad_ibo.slice(..));
+                        render_pass.draw_indexed(0..6, 0, 0..1);
+                    }
+                }
+            }
+        }
 
-        if self.num_masks_active < self.num_masks {
-            render_pass.set_stencil_reference(self.write_stencil_mask);
-        } else {
-            render_pass.set_stencil_reference(self.test_stencil_mask);
+        if let Some(pass) = render_passes.last() {
+            self.render_passes
+                .push(RenderPassCommands::load(pass.target.clone()));
         }
+    }
+
+    /// Submits the flushed render passes before the end of the frame, so that the uniform
+    /// buffer can be reused.
+    fn submit_frame_commands(&mut self) {
+        if let Some((_frame, encoder)) = &mut self.current_frame {
+            let label = create_debug_label!("Frame encoder");
+            let new_encoder = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+            let frame_buffer = replace(encoder, new_encoder).finish();
+
+            let register_encoder_label = create_debug_label!("Register encoder");
+            let new_register_encoder =
+                self.device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: register_encoder_label.as_deref(),
+                    });
+            let register_buffer =
+                replace(&mut self.register_encoder, new_register_encoder).finish();
 
-        render_pass.draw_indexed(0..6, 0, 0..1);
+            self.queue.submit(vec![register_buffer, frame_buffer]);
+        }
+    }
+
+    /// Acquires the next frame from the render target.
+    ///
+    /// A swap chain becomes outdated or lost when the window is minimized or restored, and on
+    /// some Wayland compositors; it is recreated and the frame is requested once more. If that
+    /// fails too, the frame is skipped.
+    fn acquire_frame(&mut self) -> Option<T::Frame> {
+        let error = match self.target.get_next_texture() {
+            Ok(frame) => return Some(frame),
+            Err(wgpu::SwapChainError::Outdated) | Err(wgpu::SwapChainError::Lost) => {
+                let (width, height) = (self.target.width(), self.target.height());
+                self.target.resize(&self.device, width, height);
+                match self.target.get_next_texture() {
+                    Ok(frame) => return Some(frame),
+                    Err(e) => e,
+                }
+            }
+            Err(e) => e,
+        };
+
+        log::warn!("Couldn't begin new render frame: {}", error);
+        self.dropped_frames += 1;
+        None
     }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
-        // Avoid panics from creating 0-sized framebuffers.
-        let width = std::cmp::max(width, 1);
-        let height = std::cmp::max(height, 1);
+        // Creating 0-sized swap chains or framebuffers panics, so keep the current ones around
+        // and stop rendering until the window is restored.
+        self.is_minimized = width == 0 || height == 0;
+        if self.is_minimized {
+            return;
+        }
 
-        self.target.resize(&self.device, width, height);
+        // Viewports too large for a single texture are drawn a tile at a time, see `tiles`.
+        let (target_width, target_height) = (
+            width.min(MAX_TEXTURE_DIMENSION),
+            height.min(MAX_TEXTURE_DIMENSION),
+        );
+        self.target
+            .resize(&self.device, target_width, target_height);
 
         let label = create_debug_label!("Framebuffer texture");
         let frame_buffer = self.device.create_texture(&wgpu::TextureDescriptor {
             label: label.as_deref(),
             size: wgpu::Extent3d {
-                width,
-                height,
+                width: target_width,
+                height: target_height,
                 depth: 1,
             },
             mip_level_count: 1,
@@ -847,8 +1464,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         let depth_texture = self.device.create_texture(&wgpu::TextureDescriptor {
             label: label.as_deref(),
             size: wgpu::Extent3d {
-                width,
-                height,
+                width: target_width,
+                height: target_height,
                 depth: 1,
             },
             mip_level_count: 1,
@@ -861,19 +1478,25 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         self.viewport_width = width as f32;
         self.viewport_height = height as f32;
-        self.view_matrix = build_view_matrix(width, height);
+        self.set_tile(Tile {
+            x: 0,
+            y: 0,
+            width: target_width,
+            height: target_height,
+        });
     }
 
     fn register_shape(&mut self, shape: DistilledShape) -> ShapeHandle {
         let handle = ShapeHandle(self.meshes.len());
         let mesh = self.register_shape_internal(shape);
-        self.meshes.push(mesh);
+        self.meshes.push(Rc::new(mesh));
         handle
     }
 
     fn replace_shape(&mut self, shape: DistilledShape, handle: ShapeHandle) {
+        // Draws of the old shape that were already recorded this frame keep using it.
         let mesh = self.register_shape_internal(shape);
-        self.meshes[handle.0] = mesh;
+        self.meshes[handle.0] = Rc::new(mesh);
     }
 
     fn register_glyph_shape(&mut self, glyph: &Glyph) -> ShapeHandle {
@@ -898,7 +1521,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
         let handle = ShapeHandle(self.meshes.len());
         let mesh = self.register_shape_internal((&shape).into());
-        self.meshes.push(mesh);
+        self.meshes.push(Rc::new(mesh));
         handle
     }
 
@@ -933,73 +1556,70 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.register_bitmap(swf_tag.id, bitmap, "PNG")
     }
 
+    fn set_quality(&mut self, quality: StageQuality, swf_version: u8) {
+        self.quality = quality;
+        self.swf_version = swf_version;
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         assert!(self.current_frame.is_none());
-        self.current_frame = match self.target.get_next_texture() {
-            Ok(frame) => {
-                let label = create_debug_label!("Frame encoder");
-                Some((
-                    frame,
-                    self.device
-                        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                            label: label.as_deref(),
-                        }),
-                ))
-            }
-            Err(e) => {
-                log::warn!("Couldn't begin new render frame: {}", e);
-                None
-            }
+        let frame = if self.is_minimized {
+            None
+        } else {
+            self.acquire_frame()
         };
+        self.current_frame = frame.map(|frame| {
+            let label = create_debug_label!("Frame encoder");
+            (
+                frame,
+                self.device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: label.as_deref(),
+                    }),
+            )
+        });
         self.num_masks = 0;
         self.num_masks_active = 0;
         self.write_stencil_mask = 0;
         self.test_stencil_mask = 0;
         self.next_stencil_mask = 1;
 
-        if let Some((frame_output, encoder)) = &mut self.current_frame {
-            let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                (&self.frame_buffer_view, Some(frame_output.view()))
-            } else {
-                (frame_output.view(), None)
-            };
-            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                    attachment: color_attachment,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: f64::from(clear.r) / 255.0,
-                            g: f64::from(clear.g) / 255.0,
-                            b: f64::from(clear.b) / 255.0,
-                            a: f64::from(clear.a) / 255.0,
-                        }),
-                        store: true,
-                    },
-                    resolve_target,
-                }],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                    attachment: &self.depth_texture_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(0.0),
-                        store: true,
-                    }),
-                    stencil_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(0),
-                        store: true,
-                    }),
-                }),
-            });
-        }
+        self.begin_render_pass(RenderPassCommands {
+            target: PassTarget::Frame,
+            color_load: wgpu::LoadOp::Clear(wgpu::Color {
+                r: f64::from(clear.r) / 255.0,
+                g: f64::from(clear.g) / 255.0,
+                b: f64::from(clear.b) / 255.0,
+                a: f64::from(clear.a) / 255.0,
+            }),
+            depth_load: wgpu::LoadOp::Clear(0.0),
+            stencil_load: wgpu::LoadOp::Clear(0),
+            commands: Vec::new(),
+        });
     }
 
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform) {
-        if let Some((_id, texture)) = self.textures.get(bitmap.0) {
-            let (frame_output, encoder) =
-                if let Some((frame_output, encoder)) = &mut self.current_frame {
-                    (frame_output, encoder)
-                } else {
-                    return;
-                };
+        if self.current_frame.is_none() {
+            return;
+        }
+
+        if let Some(texture) = self.textures.get(bitmap.0) {
+            // Blend modes only apply when drawing, not when writing a mask.
+            let blend_mode = match self.blend_modes.last() {
+                Some(&blend_mode)
+                    if blend_mode != BlendMode::Normal
+                        && blend_mode != BlendMode::Layer
+                        && self.num_masks_active >= self.num_masks =>
+                {
+                    self.pipelines.bitmap_blend.prepare(
+                        &self.device,
+                        blend_mode,
+                        self.test_stencil_mask,
+                    );
+                    Some(blend_mode)
+                }
+                _ => None,
+            };
 
             use ruffle_core::swf::Matrix;
             let transform = Transform {
@@ -1024,38 +1644,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 ],
             ];
 
-            let transforms_ubo = create_buffer_with_data(
-                &self.device,
-                bytemuck::cast_slice(&[Transforms {
-                    view_matrix: self.view_matrix,
-                    world_matrix,
-                }]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} transforms transfer buffer", bitmap.0),
-            );
-
-            let colors_ubo = create_buffer_with_data(
-                &self.device,
-                bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
-                wgpu::BufferUsage::UNIFORM,
-                create_debug_label!("Bitmap {} colors transfer buffer", bitmap.0),
-            );
-
             let texture_view = texture.texture.create_view(&Default::default());
-            let sampler_label = create_debug_label!("Bitmap {} sampler", bitmap.0);
-            let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
-                label: sampler_label.as_deref(),
-                address_mode_u: wgpu::AddressMode::ClampToEdge,
-                address_mode_v: wgpu::AddressMode::ClampToEdge,
-                address_mode_w: wgpu::AddressMode::ClampToEdge,
-                mag_filter: wgpu::FilterMode::Linear,
-                min_filter: wgpu::FilterMode::Linear,
-                mipmap_filter: wgpu::FilterMode::Linear,
-                lod_min_clamp: 0.0,
-                lod_max_clamp: 100.0,
-                compare: None,
-                anisotropy_clamp: None,
-            });
+
+            // Bitmaps drawn on their own are smoothed like a bitmap fill that asks to be.
+            let sampler = if self.quality.smooth_bitmaps(true, self.swf_version) {
+                &self.bitmap_sampler
+            } else {
+                &self.nearest_bitmap_sampler
+            };
 
             let bind_group_label = create_debug_label!("Bitmap {} bind group", bitmap.0);
             let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -1063,9 +1659,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 entries: &[
                     wgpu::BindGroupEntry {
                         binding: 0,
-                        resource: wgpu::BindingResource::Buffer(
-                            transforms_ubo.slice(0..std::mem::size_of::<Transforms>() as u64),
-                        ),
+                        resource: transforms_binding(&self.uniform_buffer),
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
@@ -1076,9 +1670,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     },
                     wgpu::BindGroupEntry {
                         binding: 2,
-                        resource: wgpu::BindingResource::Buffer(
-                            colors_ubo.slice(0..std::mem::size_of::<ColorAdjustments>() as u64),
-                        ),
+                        resource: colors_binding(&self.uniform_buffer),
                     },
                     wgpu::BindGroupEntry {
                         binding: 3,
@@ -1086,68 +1678,30 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     },
                     wgpu::BindGroupEntry {
                         binding: 4,
-                        resource: wgpu::BindingResource::Sampler(&sampler),
+                        resource: wgpu::BindingResource::Sampler(sampler),
                     },
                 ],
                 label: bind_group_label.as_deref(),
             });
 
-            let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                (&self.frame_buffer_view, Some(frame_output.view()))
-            } else {
-                (frame_output.view(), None)
-            };
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                    attachment: color_attachment,
-                    resolve_target,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    },
-                }],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                    attachment: &self.depth_texture_view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    }),
-                    stencil_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Load,
-                        store: true,
-                    }),
-                }),
+            let uniforms = self.push_uniforms(
+                world_matrix,
+                ColorAdjustments::from(transform.color_transform),
+            );
+            let mask_state = self.mask_state();
+            self.push_command(DrawCommand::Bitmap {
+                bind_group,
+                blend_mode,
+                uniforms,
+                mask_state,
             });
-
-            render_pass.set_pipeline(&self.pipelines.bitmap.pipeline_for(
-                self.num_masks,
-                self.num_masks_active,
-                self.test_stencil_mask,
-                self.write_stencil_mask,
-            ));
-            render_pass.set_bind_group(0, &bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.quad_vbo.slice(..));
-            render_pass.set_index_buffer(self.quad_ibo.slice(..));
-
-            if self.num_masks_active < self.num_masks {
-                render_pass.set_stencil_reference(self.write_stencil_mask);
-            } else {
-                render_pass.set_stencil_reference(self.test_stencil_mask);
-            }
-
-            render_pass.draw_indexed(0..6, 0, 0..1);
         }
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
-        let (frame_output, encoder) = if let Some((frame_output, encoder)) = &mut self.current_frame
-        {
-            (frame_output, encoder)
-        } else {
+        if self.current_frame.is_none() {
             return;
-        };
-
-        let mesh = &mut self.meshes[shape.0];
+        }
 
         let world_matrix = [
             [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
@@ -1161,113 +1715,26 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             ],
         ];
 
-        if transform.color_transform != mesh.colors_last {
-            let colors_temp = create_buffer_with_data(
-                &self.device,
-                bytemuck::cast_slice(&[ColorAdjustments::from(transform.color_transform)]),
-                wgpu::BufferUsage::COPY_SRC,
-                create_debug_label!("Shape {} colors transfer buffer", mesh.shape_id),
-            );
-
-            encoder.copy_buffer_to_buffer(
-                &colors_temp,
-                0,
-                &mesh.colors_buffer,
-                0,
-                std::mem::size_of::<ColorAdjustments>() as u64,
-            );
-
-            mesh.colors_last = transform.color_transform;
-        }
-
-        let transforms_temp = create_buffer_with_data(
-            &self.device,
-            bytemuck::cast_slice(&[Transforms {
-                view_matrix: self.view_matrix,
-                world_matrix,
-            }]),
-            wgpu::BufferUsage::COPY_SRC,
-            create_debug_label!("Shape {} transforms transfer buffer", mesh.shape_id),
+        let uniforms = self.push_uniforms(
+            world_matrix,
+            ColorAdjustments::from(transform.color_transform),
         );
-
-        encoder.copy_buffer_to_buffer(
-            &transforms_temp,
-            0,
-            &mesh.transforms,
-            0,
-            std::mem::size_of::<Transforms>() as u64,
-        );
-
-        let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_output.view()))
-        } else {
-            (frame_output.view(), None)
-        };
-        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                attachment: color_attachment,
-                resolve_target,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
-                },
-            }],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: true,
-                }),
-            }),
+        let mesh = self.meshes[shape.0].clone();
+        let mask_state = self.mask_state();
+        self.push_command(DrawCommand::Shape {
+            mesh,
+            uniforms,
+            mask_state,
         });
-
-        for draw in &mesh.draws {
-            match &draw.draw_type {
-                DrawType::Color => {
-                    render_pass.set_pipeline(&self.pipelines.color.pipeline_for(
-                        self.num_masks,
-                        self.num_masks_active,
-                        self.test_stencil_mask,
-                        self.write_stencil_mask,
-                    ));
-                }
-                DrawType::Gradient { .. } => {
-                    render_pass.set_pipeline(&self.pipelines.gradient.pipeline_for(
-                        self.num_masks,
-                        self.num_masks_active,
-                        self.test_stencil_mask,
-                        self.write_stencil_mask,
-                    ));
-                }
-                DrawType::Bitmap { .. } => {
-                    render_pass.set_pipeline(&self.pipelines.bitmap.pipeline_for(
-                        self.num_masks,
-                        self.num_masks_active,
-                        self.test_stencil_mask,
-                        self.write_stencil_mask,
-                    ));
-                }
-            }
-
-            render_pass.set_bind_group(0, &draw.bind_group, &[]);
-            render_pass.set_vertex_buffer(0, draw.vertex_buffer.slice(..));
-            render_pass.set_index_buffer(draw.index_buffer.slice(..));
-
-            if self.num_masks_active < self.num_masks {
-                render_pass.set_stencil_reference(self.write_stencil_mask);
-            } else {
-                render_pass.set_stencil_reference(self.test_stencil_mask);
-            }
-
-            render_pass.draw_indexed(0..draw.index_count, 0, 0..1);
-        }
     }
 
     fn end_frame(&mut self) {
+        self.flush_render_passes();
+        self.render_passes.clear();
+
+        self.last_frame_draws = replace(&mut self.frame_draws, 0);
+        self.peak_frame_draws = self.peak_frame_draws.max(self.last_frame_draws);
+
         if let Some((_frame, encoder)) = self.current_frame.take() {
             let register_encoder_label = create_debug_label!("Register encoder");
             let new_register_encoder =
@@ -1356,36 +1823,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 );
             }
             self.next_stencil_mask = 1;
-            if let Some((frame_output, encoder)) = &mut self.current_frame {
-                let (color_attachment, resolve_target) = if self.msaa_sample_count >= 2 {
-                    (&self.frame_buffer_view, Some(frame_output.view()))
-                } else {
-                    (frame_output.view(), None)
-                };
-                encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                        attachment: color_attachment,
-                        resolve_target,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: true,
-                        },
-                    }],
-                    depth_stencil_attachment: Some(
-                        wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                            attachment: &self.depth_texture_view,
-                            depth_ops: Some(wgpu::Operations {
-                                load: wgpu::LoadOp::Load,
-                                store: true,
-                            }),
-                            stencil_ops: Some(wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(self.test_stencil_mask),
-                                store: true,
-                            }),
-                        },
-                    ),
-                });
-            }
+            let target = self.current_target();
+            self.begin_render_pass(RenderPassCommands {
+                stencil_load: wgpu::LoadOp::Clear(self.test_stencil_mask),
+                ..RenderPassCommands::load(target)
+            });
         }
         self.num_masks += 1;
         self.mask_stack
@@ -1408,6 +1850,297 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             self.test_stencil_mask = test;
         }
     }
+
+    fn create_offscreen_bitmap(&mut self, width: u32, height: u32) -> Option<BitmapHandle> {
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+
+        let texture_label = create_debug_label!("Offscreen texture");
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: texture_label.as_deref(),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.target.format(),
+            usage: wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::COPY_SRC,
+        });
+        let view = texture.create_view(&Default::default());
+
+        let frame_buffer_view = if self.msaa_sample_count >= 2 {
+            let label = create_debug_label!("Offscreen framebuffer texture");
+            let frame_buffer = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: self.msaa_sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.target.format(),
+                usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            });
+            Some(frame_buffer.create_view(&Default::default()))
+        } else {
+            None
+        };
+
+        let depth_label = create_debug_label!("Offscreen depth texture");
+        let depth_texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: depth_label.as_deref(),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: self.msaa_sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Depth24PlusStencil8,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+        });
+
+        let texture = Rc::new(Texture {
+            width,
+            height,
+            texture,
+        });
+        let index = if let Some(index) = self.free_offscreen_bitmaps.pop() {
+            self.textures[index] = texture;
+            index
+        } else {
+            self.textures.push(texture);
+            self.textures.len() - 1
+        };
+        self.offscreen_targets.insert(
+            index,
+            Rc::new(OffscreenTarget {
+                view,
+                frame_buffer_view,
+                depth_texture_view: depth_texture.create_view(&Default::default()),
+                view_matrix: build_view_matrix(width, height),
+            }),
+        );
+        Some(BitmapHandle(index))
+    }
+
+    fn offscreen_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        if !self.offscreen_targets.contains_key(&bitmap.0) {
+            log::warn!("Tried to read back a bitmap that is not an offscreen target");
+            return None;
+        }
+        let texture = self.textures.get(bitmap.0)?.clone();
+        let dimensions = BufferDimensions::new(texture.width as usize, texture.height as usize);
+
+        let buffer_label = create_debug_label!("Offscreen readback buffer");
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: buffer_label.as_deref(),
+            size: (dimensions.padded_bytes_per_row * dimensions.height) as u64,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let label = create_debug_label!("Offscreen readback encoder");
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: label.as_deref(),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::TextureCopyView {
+                texture: &texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::BufferCopyView {
+                buffer: &buffer,
+                layout: wgpu::TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: dimensions.padded_bytes_per_row as u32,
+                    rows_per_image: 0,
+                },
+            },
+            wgpu::Extent3d {
+                width: texture.width,
+                height: texture.height,
+                depth: 1,
+            },
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let buffer_future = slice.map_async(wgpu::MapMode::Read);
+        self.device.poll(wgpu::Maintain::Wait);
+        if let Err(e) = block_on(buffer_future) {
+            log::error!("Unknown error reading offscreen bitmap: {:?}", e);
+            return None;
+        }
+
+        let mut data = Vec::with_capacity(dimensions.height * dimensions.unpadded_bytes_per_row);
+        for chunk in slice
+            .get_mapped_range()
+            .chunks(dimensions.padded_bytes_per_row)
+        {
+            data.extend_from_slice(&chunk[..dimensions.unpadded_bytes_per_row]);
+        }
+
+        // Offscreen bitmaps share the format of the render target.
+        if let wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb =
+            self.target.format()
+        {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Some(Bitmap {
+            width: texture.width,
+            height: texture.height,
+            data: BitmapFormat::Rgba(data),
+        })
+    }
+
+    fn push_offscreen_target(&mut self, bitmap: BitmapHandle) {
+        let target = if let Some(target) = self.offscreen_targets.get(&bitmap.0) {
+            target.clone()
+        } else {
+            log::warn!("Tried to render into a bitmap that is not an offscreen target");
+            return;
+        };
+
+        let view_matrix = replace(&mut self.view_matrix, target.view_matrix);
+        self.begin_render_pass(RenderPassCommands {
+            target: PassTarget::Offscreen(target),
+            color_load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+            depth_load: wgpu::LoadOp::Clear(0.0),
+            stencil_load: wgpu::LoadOp::Clear(0),
+            commands: Vec::new(),
+        });
+        self.offscreen_stack.push(OffscreenState {
+            bitmap: bitmap.0,
+            view_matrix,
+            num_masks: replace(&mut self.num_masks, 0),
+            num_masks_active: replace(&mut self.num_masks_active, 0),
+            write_stencil_mask: replace(&mut self.write_stencil_mask, 0),
+            test_stencil_mask: replace(&mut self.test_stencil_mask, 0),
+            next_stencil_mask: replace(&mut self.next_stencil_mask, 1),
+            mask_stack: replace(&mut self.mask_stack, Vec::new()),
+            blend_modes: replace(&mut self.blend_modes, Vec::new()),
+        });
+    }
+
+    fn pop_offscreen_target(&mut self) {
+        if let Some(state) = self.offscreen_stack.pop() {
+            self.view_matrix = state.view_matrix;
+            self.num_masks = state.num_masks;
+            self.num_masks_active = state.num_masks_active;
+            self.write_stencil_mask = state.write_stencil_mask;
+            self.test_stencil_mask = state.test_stencil_mask;
+            self.next_stencil_mask = state.next_stencil_mask;
+            self.mask_stack = state.mask_stack;
+            self.blend_modes = state.blend_modes;
+
+            let target = self.current_target();
+            self.begin_render_pass(RenderPassCommands::load(target));
+        } else {
+            log::warn!("Offscreen target stack underflow");
+        }
+    }
+
+    fn remove_offscreen_bitmap(&mut self, bitmap: BitmapHandle) {
+        if self.offscreen_targets.remove(&bitmap.0).is_some() {
+            // Bitmap handles are indices, so the slot is kept for the next offscreen bitmap.
+            // Replace the texture with a tiny one to free its memory in the meantime.
+            let label = create_debug_label!("Removed offscreen texture");
+            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: label.as_deref(),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.target.format(),
+                usage: wgpu::TextureUsage::SAMPLED,
+            });
+            self.textures[bitmap.0] = Rc::new(Texture {
+                width: 1,
+                height: 1,
+                texture,
+            });
+            self.free_offscreen_bitmaps.push(bitmap.0);
+        }
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: BlendMode) {
+        // Only bitmaps are blended, which covers blended objects drawn through an offscreen bitmap.
+        if blend_mode != BlendMode::Layer
+            && blend_descriptors(blend_mode).is_none()
+            && self.unsupported_blend_modes.insert(blend_mode)
+        {
+            log::warn!("Blend mode {:?} is not supported yet", blend_mode);
+        }
+        self.blend_modes.push(blend_mode);
+    }
+
+    fn pop_blend_mode(&mut self) {
+        if self.blend_modes.pop().is_none() {
+            log::warn!("Blend mode stack underflow");
+        }
+    }
+
+    fn last_frame_draw_count(&self) -> Option<usize> {
+        Some(self.last_frame_draws)
+    }
+
+    fn debug_info(&self) -> String {
+        let unique_textures = self
+            .texture_cache
+            .values()
+            .filter(|t| t.upgrade().is_some());
+        format!(
+            "Bitmaps: {} ({} unique textures), deduplicated bytes: {}, dropped frames: {}, tile size: {}x{}, draws: {} (peak {}, {} per submit, uniform buffer: {} KiB), GPU timing: {}",
+            self.bitmap_handles.len(),
+            unique_textures.count(),
+            self.deduplicated_bytes,
+            self.dropped_frames,
+            self.target.width(),
+            self.target.height(),
+            self.last_frame_draws,
+            self.peak_frame_draws,
+            MAX_DRAWS_PER_FLUSH,
+            MAX_DRAWS_PER_FLUSH * std::mem::size_of::<DrawUniforms>() / 1024,
+            self.gpu_timing.description()
+        )
+    }
+}
+
+/// Returns the color attachment, resolve target and depth-stencil attachment to draw into:
+/// the innermost offscreen target if there is one, or else the frame.
+fn render_attachments<'a>(
+    frame_view: &'a wgpu::TextureView,
+    frame_buffer_view: &'a wgpu::TextureView,
+    depth_texture_view: &'a wgpu::TextureView,
+    msaa_sample_count: u32,
+    offscreen_target: Option<&'a OffscreenTarget>,
+) -> (
+    &'a wgpu::TextureView,
+    Option<&'a wgpu::TextureView>,
+    &'a wgpu::TextureView,
+) {
+    match offscreen_target {
+        Some(target) => match &target.frame_buffer_view {
+            Some(target_frame_buffer_view) => (
+                target_frame_buffer_view,
+                Some(&target.view),
+                &target.depth_texture_view,
+            ),
+            None => (&target.view, None, &target.depth_texture_view),
+        },
+        None if msaa_sample_count >= 2 => (frame_buffer_view, Some(frame_view), depth_texture_view),
+        None => (frame_view, None, depth_texture_view),
+    }
 }
 
 fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wgpu::Buffer) {
@@ -1,4 +1,6 @@
 use crate::{Error, GPUVertex};
+use ruffle_core::backend::render::swf::BlendMode;
+use std::collections::HashMap;
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
@@ -8,11 +10,23 @@ pub struct ShapePipeline {
     pub bind_layout: wgpu::BindGroupLayout,
 }
 
+/// Bitmap pipelines for blend modes other than normal, created the first time each blend mode is
+/// drawn with a given mask.
+#[derive(Debug)]
+pub struct BitmapBlendPipelines {
+    vertex_shader: wgpu::ShaderModule,
+    fragment_shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    msaa_sample_count: u32,
+    pipelines: HashMap<(BlendMode, u32), wgpu::RenderPipeline>,
+}
+
 #[derive(Debug)]
 pub struct Pipelines {
     pub color: ShapePipeline,
     pub bitmap: ShapePipeline,
     pub gradient: ShapePipeline,
+    pub bitmap_blend: BitmapBlendPipelines,
 }
 
 impl ShapePipeline {
@@ -53,32 +67,216 @@ impl Pipelines {
             ],
         }];
 
+        let color = create_color_pipelines(
+            &device,
+            &color_vs,
+            &color_fs,
+            msaa_sample_count,
+            &vertex_buffers_description,
+        );
+        let bitmap = create_bitmap_pipeline(
+            &device,
+            &texture_vs,
+            &bitmap_fs,
+            msaa_sample_count,
+            &vertex_buffers_description,
+        );
+        let gradient = create_gradient_pipeline(
+            &device,
+            &texture_vs,
+            &gradient_fs,
+            msaa_sample_count,
+            &vertex_buffers_description,
+        );
+
+        let pipeline_layout_label = create_debug_label!("Bitmap blend pipeline layout");
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: pipeline_layout_label.as_deref(),
+            bind_group_layouts: &[&bitmap.bind_layout],
+            push_constant_ranges: &[],
+        });
+        let bitmap_blend = BitmapBlendPipelines {
+            vertex_shader: texture_vs,
+            fragment_shader: bitmap_fs,
+            pipeline_layout,
+            msaa_sample_count,
+            pipelines: HashMap::new(),
+        };
+
         Ok(Self {
-            color: create_color_pipelines(
-                &device,
-                &color_vs,
-                &color_fs,
-                msaa_sample_count,
-                &vertex_buffers_description,
-            ),
-            bitmap: create_bitmap_pipeline(
-                &device,
-                &texture_vs,
-                &bitmap_fs,
-                msaa_sample_count,
-                &vertex_buffers_description,
-            ),
-            gradient: create_gradient_pipeline(
-                &device,
-                &texture_vs,
-                &gradient_fs,
-                msaa_sample_count,
-                &vertex_buffers_description,
-            ),
+            color,
+            bitmap,
+            gradient,
+            bitmap_blend,
         })
     }
 }
 
+impl BitmapBlendPipelines {
+    /// Creates the pipeline for drawing a bitmap with the given blend mode and stencil read mask,
+    /// if it doesn't exist yet.
+    pub fn prepare(&mut self, device: &wgpu::Device, blend_mode: BlendMode, read_mask: u32) {
+        let (color_blend, alpha_blend) = match blend_descriptors(blend_mode) {
+            Some(descriptors) => descriptors,
+            None => return,
+        };
+        if self.pipelines.contains_key(&(blend_mode, read_mask)) {
+            return;
+        }
+
+        let vertex_buffers_description = [wgpu::VertexBufferDescriptor {
+            stride: std::mem::size_of::<GPUVertex>() as u64,
+            step_mode: wgpu::InputStepMode::Vertex,
+            attributes: &vertex_attr_array![
+                0 => Float2,
+                1 => Float4
+            ],
+        }];
+
+        let label = create_debug_label!(
+            "Bitmap pipeline blend mode {:?} read mask {}",
+            blend_mode,
+            read_mask
+        );
+        let pipeline = device.create_render_pipeline(&create_pipeline_descriptor(
+            label.as_deref(),
+            &self.vertex_shader,
+            &self.fragment_shader,
+            &self.pipeline_layout,
+            Some(wgpu::DepthStencilStateDescriptor {
+                format: wgpu::TextureFormat::Depth24PlusStencil8,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilStateDescriptor {
+                    front: wgpu::StencilStateFaceDescriptor {
+                        compare: wgpu::CompareFunction::Equal,
+                        fail_op: wgpu::StencilOperation::Keep,
+                        depth_fail_op: wgpu::StencilOperation::Keep,
+                        pass_op: wgpu::StencilOperation::Keep,
+                    },
+                    back: wgpu::StencilStateFaceDescriptor {
+                        compare: wgpu::CompareFunction::Equal,
+                        fail_op: wgpu::StencilOperation::Keep,
+                        depth_fail_op: wgpu::StencilOperation::Keep,
+                        pass_op: wgpu::StencilOperation::Keep,
+                    },
+                    read_mask,
+                    write_mask: 0,
+                },
+            }),
+            &[wgpu::ColorStateDescriptor {
+                format: wgpu::TextureFormat::Bgra8Unorm,
+                color_blend,
+                alpha_blend,
+                write_mask: wgpu::ColorWrite::ALL,
+            }],
+            &vertex_buffers_description,
+            self.msaa_sample_count,
+        ));
+        self.pipelines.insert((blend_mode, read_mask), pipeline);
+    }
+
+    /// Returns the pipeline created by `prepare`, if the blend mode is supported.
+    pub fn get(&self, blend_mode: BlendMode, read_mask: u32) -> Option<&wgpu::RenderPipeline> {
+        self.pipelines.get(&(blend_mode, read_mask))
+    }
+}
+
+/// Returns the colour and alpha blending used to draw a premultiplied bitmap with the given blend
+/// mode, or `None` if the blend mode needs to read the backdrop in the shader.
+pub fn blend_descriptors(
+    blend_mode: BlendMode,
+) -> Option<(wgpu::BlendDescriptor, wgpu::BlendDescriptor)> {
+    let (src_factor, dst_factor, operation) = match blend_mode {
+        BlendMode::Normal | BlendMode::Layer => (
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::OneMinusSrcAlpha,
+            wgpu::BlendOperation::Add,
+        ),
+        BlendMode::Add => (
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::One,
+            wgpu::BlendOperation::Add,
+        ),
+        // Subtracts the bitmap from the backdrop.
+        BlendMode::Subtract => (
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::One,
+            wgpu::BlendOperation::ReverseSubtract,
+        ),
+        // src * dst + dst * (1 - src_alpha)
+        BlendMode::Multiply => (
+            wgpu::BlendFactor::DstColor,
+            wgpu::BlendFactor::OneMinusSrcAlpha,
+            wgpu::BlendOperation::Add,
+        ),
+        // src + dst * (1 - src)
+        BlendMode::Screen => (
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::OneMinusSrcColor,
+            wgpu::BlendOperation::Add,
+        ),
+        // Transparent pixels of the bitmap are black, which leaves the backdrop unchanged.
+        BlendMode::Lighten => (
+            wgpu::BlendFactor::One,
+            wgpu::BlendFactor::One,
+            wgpu::BlendOperation::Max,
+        ),
+        // Darken would make the backdrop behind transparent pixels black, and the others need
+        // the backdrop in the shader.
+        BlendMode::Darken
+        | BlendMode::Difference
+        | BlendMode::Invert
+        | BlendMode::Alpha
+        | BlendMode::Erase
+        | BlendMode::Overlay
+        | BlendMode::HardLight => return None,
+    };
+    Some((
+        wgpu::BlendDescriptor {
+            src_factor,
+            dst_factor,
+            operation,
+        },
+        wgpu::BlendDescriptor {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+            operation: wgpu::BlendOperation::Add,
+        },
+    ))
+}
+
+/// The depth-stencil state used to draw a masker, which sets the given bit of the stencil buffer
+/// wherever the masker's shape covers, however it is filled.
+fn mask_writing_depth_stencil_state(write_mask: u32) -> wgpu::DepthStencilStateDescriptor {
+    let face = wgpu::StencilStateFaceDescriptor {
+        compare: wgpu::CompareFunction::Always,
+        fail_op: wgpu::StencilOperation::Keep,
+        depth_fail_op: wgpu::StencilOperation::Keep,
+        pass_op: wgpu::StencilOperation::Replace,
+    };
+    wgpu::DepthStencilStateDescriptor {
+        format: wgpu::TextureFormat::Depth24PlusStencil8,
+        depth_write_enabled: true,
+        depth_compare: wgpu::CompareFunction::Always,
+        stencil: wgpu::StencilStateDescriptor {
+            front: face.clone(),
+            back: face,
+            read_mask: 0xff,
+            write_mask,
+        },
+    }
+}
+
+/// The colour state used to draw a masker. Only the masker's silhouette is used, so none of its
+/// colour is written, whatever its fill or colour transform.
+const MASK_WRITING_COLOR_STATE: wgpu::ColorStateDescriptor = wgpu::ColorStateDescriptor {
+    format: wgpu::TextureFormat::Bgra8Unorm,
+    color_blend: wgpu::BlendDescriptor::REPLACE,
+    alpha_blend: wgpu::BlendDescriptor::REPLACE,
+    write_mask: wgpu::ColorWrite::empty(),
+};
+
 #[allow(clippy::too_many_arguments)]
 fn create_pipeline_descriptor<'a>(
     label: Option<&'a str>,
@@ -136,7 +334,7 @@ fn create_color_pipelines(
                 binding: 0,
                 visibility: wgpu::ShaderStage::VERTEX,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -145,7 +343,7 @@ fn create_color_pipelines(
                 binding: 1,
                 visibility: wgpu::ShaderStage::VERTEX,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -171,41 +369,8 @@ fn create_color_pipelines(
             vertex_shader,
             fragment_shader,
             &pipeline_layout,
-            Some(wgpu::DepthStencilStateDescriptor {
-                format: wgpu::TextureFormat::Depth24PlusStencil8,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Always,
-                stencil: wgpu::StencilStateDescriptor {
-                    front: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    back: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    read_mask: 0xff,
-                    write_mask: 1 << i,
-                },
-            }),
-            &[wgpu::ColorStateDescriptor {
-                format: wgpu::TextureFormat::Bgra8Unorm,
-                color_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                write_mask: wgpu::ColorWrite::empty(),
-            }],
+            Some(mask_writing_depth_stencil_state(1 << i)),
+            &[MASK_WRITING_COLOR_STATE],
             vertex_buffers_description,
             msaa_sample_count,
         )));
@@ -247,7 +412,7 @@ fn create_color_pipelines(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -279,7 +444,7 @@ fn create_bitmap_pipeline(
                 binding: 0,
                 visibility: wgpu::ShaderStage::VERTEX,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -297,7 +462,7 @@ fn create_bitmap_pipeline(
                 binding: 2,
                 visibility: wgpu::ShaderStage::FRAGMENT,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -339,41 +504,8 @@ fn create_bitmap_pipeline(
             vertex_shader,
             fragment_shader,
             &pipeline_layout,
-            Some(wgpu::DepthStencilStateDescriptor {
-                format: wgpu::TextureFormat::Depth24PlusStencil8,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Always,
-                stencil: wgpu::StencilStateDescriptor {
-                    front: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    back: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    read_mask: 0xff,
-                    write_mask: 1 << i,
-                },
-            }),
-            &[wgpu::ColorStateDescriptor {
-                format: wgpu::TextureFormat::Bgra8Unorm,
-                color_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                write_mask: wgpu::ColorWrite::empty(),
-            }],
+            Some(mask_writing_depth_stencil_state(1 << i)),
+            &[MASK_WRITING_COLOR_STATE],
             vertex_buffers_description,
             msaa_sample_count,
         )));
@@ -415,7 +547,7 @@ fn create_bitmap_pipeline(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -447,7 +579,7 @@ fn create_gradient_pipeline(
                 binding: 0,
                 visibility: wgpu::ShaderStage::VERTEX,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -465,7 +597,7 @@ fn create_gradient_pipeline(
                 binding: 2,
                 visibility: wgpu::ShaderStage::FRAGMENT,
                 ty: wgpu::BindingType::UniformBuffer {
-                    dynamic: false,
+                    dynamic: true,
                     min_binding_size: None,
                 },
                 count: None,
@@ -501,41 +633,8 @@ fn create_gradient_pipeline(
             vertex_shader,
             fragment_shader,
             &pipeline_layout,
-            Some(wgpu::DepthStencilStateDescriptor {
-                format: wgpu::TextureFormat::Depth24PlusStencil8,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Always,
-                stencil: wgpu::StencilStateDescriptor {
-                    front: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    back: wgpu::StencilStateFaceDescriptor {
-                        compare: wgpu::CompareFunction::Always,
-                        fail_op: wgpu::StencilOperation::Keep,
-                        depth_fail_op: wgpu::StencilOperation::Keep,
-                        pass_op: wgpu::StencilOperation::Replace,
-                    },
-                    read_mask: 0xff,
-                    write_mask: 1 << i,
-                },
-            }),
-            &[wgpu::ColorStateDescriptor {
-                format: wgpu::TextureFormat::Bgra8Unorm,
-                color_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
-                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                    operation: wgpu::BlendOperation::Add,
-                },
-                write_mask: wgpu::ColorWrite::empty(),
-            }],
+            Some(mask_writing_depth_stencil_state(1 << i)),
+            &[MASK_WRITING_COLOR_STATE],
             vertex_buffers_description,
             msaa_sample_count,
         )));
@@ -577,7 +676,7 @@ fn create_gradient_pipeline(
                     operation: wgpu::BlendOperation::Add,
                 },
                 alpha_blend: wgpu::BlendDescriptor {
-                    src_factor: wgpu::BlendFactor::SrcAlpha,
+                    src_factor: wgpu::BlendFactor::One,
                     dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
                     operation: wgpu::BlendOperation::Add,
                 },
@@ -1,9 +1,9 @@
 use crate::pipelines::Pipelines;
 use crate::utils::create_buffer_with_data;
-use crate::{ColorAdjustments, TextureTransforms, Transforms};
+use crate::{colors_binding, transforms_binding, TextureTransforms};
 use bytemuck::{Pod, Zeroable};
 use ruffle_core::backend::audio::swf::CharacterId;
-use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::StageQuality;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug)]
@@ -23,10 +23,6 @@ unsafe impl Zeroable for GradientUniforms {}
 #[derive(Debug)]
 pub struct Mesh {
     pub draws: Vec<Draw>,
-    pub transforms: wgpu::Buffer,
-    pub colors_buffer: wgpu::Buffer,
-    pub colors_last: ColorTransform,
-    pub shape_id: CharacterId,
 }
 
 #[derive(Debug)]
@@ -49,9 +45,28 @@ pub enum DrawType {
         texture_transforms: wgpu::Buffer,
         texture_view: wgpu::TextureView,
         id: CharacterId,
+        is_smoothed: bool,
+
+        /// Samples the bitmap without smoothing. `Draw::bind_group` samples it with smoothing.
+        nearest_bind_group: wgpu::BindGroup,
     },
 }
 
+impl Draw {
+    /// The bind group to draw with at the given stage quality and SWF version, which decide
+    /// whether bitmaps are smoothed.
+    pub fn bind_group_for(&self, quality: StageQuality, swf_version: u8) -> &wgpu::BindGroup {
+        match &self.draw_type {
+            DrawType::Bitmap {
+                is_smoothed,
+                nearest_bind_group,
+                ..
+            } if !quality.smooth_bitmaps(*is_smoothed, swf_version) => nearest_bind_group,
+            _ => &self.bind_group,
+        }
+    }
+}
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum IncompleteDrawType {
@@ -82,8 +97,7 @@ impl IncompleteDrawType {
     pub fn build(
         self,
         device: &wgpu::Device,
-        transforms_ubo: &wgpu::Buffer,
-        colors_ubo: &wgpu::Buffer,
+        uniform_buffer: &wgpu::Buffer,
         vertex_buffer: wgpu::Buffer,
         index_buffer: wgpu::Buffer,
         index_count: u32,
@@ -100,15 +114,11 @@ impl IncompleteDrawType {
                     entries: &[
                         wgpu::BindGroupEntry {
                             binding: 0,
-                            resource: wgpu::BindingResource::Buffer(
-                                transforms_ubo.slice(0..std::mem::size_of::<Transforms>() as u64),
-                            ),
+                            resource: transforms_binding(uniform_buffer),
                         },
                         wgpu::BindGroupEntry {
                             binding: 1,
-                            resource: wgpu::BindingResource::Buffer(
-                                colors_ubo.slice(0..std::mem::size_of::<ColorAdjustments>() as u64),
-                            ),
+                            resource: colors_binding(uniform_buffer),
                         },
                     ],
                     label: bind_group_label.as_deref(),
@@ -155,9 +165,7 @@ impl IncompleteDrawType {
                     entries: &[
                         wgpu::BindGroupEntry {
                             binding: 0,
-                            resource: wgpu::BindingResource::Buffer(
-                                transforms_ubo.slice(0..std::mem::size_of::<Transforms>() as u64),
-                            ),
+                            resource: transforms_binding(uniform_buffer),
                         },
                         wgpu::BindGroupEntry {
                             binding: 1,
@@ -168,9 +176,7 @@ impl IncompleteDrawType {
                         },
                         wgpu::BindGroupEntry {
                             binding: 2,
-                            resource: wgpu::BindingResource::Buffer(
-                                colors_ubo.slice(0..std::mem::size_of::<ColorAdjustments>() as u64),
-                            ),
+                            resource: colors_binding(uniform_buffer),
                         },
                         wgpu::BindGroupEntry {
                             binding: 3,
@@ -218,69 +224,75 @@ impl IncompleteDrawType {
                     wgpu::AddressMode::ClampToEdge
                 };
 
-                let filter = if is_smoothed {
-                    wgpu::FilterMode::Linear
-                } else {
-                    wgpu::FilterMode::Nearest
-                };
-
-                let sampler_label =
-                    create_debug_label!("Shape {} (bitmap) draw {} sampler", shape_id, draw_id);
-                let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-                    label: sampler_label.as_deref(),
-                    address_mode_u: address_mode,
-                    address_mode_v: address_mode,
-                    address_mode_w: address_mode,
-                    mag_filter: filter,
-                    min_filter: filter,
-                    mipmap_filter: filter,
-                    lod_min_clamp: 0.0,
-                    lod_max_clamp: 100.0,
-                    compare: None,
-                    anisotropy_clamp: None,
-                });
+                // Whether the bitmap is smoothed depends on the stage quality at the time it is
+                // drawn, so bind groups for both filters are created up front.
+                let create_bind_group = |filter: wgpu::FilterMode, filter_name: &str| {
+                    let sampler_label = create_debug_label!(
+                        "Shape {} (bitmap) draw {} {} sampler",
+                        shape_id,
+                        draw_id,
+                        filter_name
+                    );
+                    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+                        label: sampler_label.as_deref(),
+                        address_mode_u: address_mode,
+                        address_mode_v: address_mode,
+                        address_mode_w: address_mode,
+                        mag_filter: filter,
+                        min_filter: filter,
+                        mipmap_filter: filter,
+                        lod_min_clamp: 0.0,
+                        lod_max_clamp: 100.0,
+                        compare: None,
+                        anisotropy_clamp: None,
+                    });
 
-                let bind_group_label =
-                    create_debug_label!("Shape {} (bitmap) draw {} bindgroup", shape_id, draw_id);
-                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    layout: &pipelines.bitmap.bind_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::Buffer(
-                                transforms_ubo.slice(0..std::mem::size_of::<Transforms>() as u64),
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Buffer(
-                                tex_transforms_ubo
-                                    .slice(0..std::mem::size_of::<TextureTransforms>() as u64),
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 2,
-                            resource: wgpu::BindingResource::Buffer(
-                                colors_ubo.slice(0..std::mem::size_of::<ColorAdjustments>() as u64),
-                            ),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 3,
-                            resource: wgpu::BindingResource::TextureView(&texture_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 4,
-                            resource: wgpu::BindingResource::Sampler(&sampler),
-                        },
-                    ],
-                    label: bind_group_label.as_deref(),
-                });
+                    let bind_group_label = create_debug_label!(
+                        "Shape {} (bitmap) draw {} {} bindgroup",
+                        shape_id,
+                        draw_id,
+                        filter_name
+                    );
+                    device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        layout: &pipelines.bitmap.bind_layout,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: transforms_binding(uniform_buffer),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::Buffer(
+                                    tex_transforms_ubo
+                                        .slice(0..std::mem::size_of::<TextureTransforms>() as u64),
+                                ),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 2,
+                                resource: colors_binding(uniform_buffer),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 3,
+                                resource: wgpu::BindingResource::TextureView(&texture_view),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 4,
+                                resource: wgpu::BindingResource::Sampler(&sampler),
+                            },
+                        ],
+                        label: bind_group_label.as_deref(),
+                    })
+                };
+                let bind_group = create_bind_group(wgpu::FilterMode::Linear, "linear");
+                let nearest_bind_group = create_bind_group(wgpu::FilterMode::Nearest, "nearest");
 
                 Draw {
                     draw_type: DrawType::Bitmap {
                         texture_transforms: tex_transforms_ubo,
                         texture_view,
                         id,
+                        is_smoothed,
+                        nearest_bind_group,
                     },
                     vertex_buffer,
                     index_buffer,
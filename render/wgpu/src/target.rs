@@ -160,16 +160,23 @@ impl TextureTarget {
         device.poll(wgpu::Maintain::Wait);
         match block_on(buffer_future) {
             Ok(()) => {
-                let map = self.buffer.slice(..).get_mapped_range();
                 let mut buffer = Vec::with_capacity(
                     self.buffer_dimensions.height * self.buffer_dimensions.unpadded_bytes_per_row,
                 );
 
-                for chunk in map.chunks(self.buffer_dimensions.padded_bytes_per_row) {
-                    buffer
-                        .extend_from_slice(&chunk[..self.buffer_dimensions.unpadded_bytes_per_row]);
+                {
+                    let map = self.buffer.slice(..).get_mapped_range();
+                    for chunk in map.chunks(self.buffer_dimensions.padded_bytes_per_row) {
+                        buffer.extend_from_slice(
+                            &chunk[..self.buffer_dimensions.unpadded_bytes_per_row],
+                        );
+                    }
                 }
 
+                // The target may be captured again, e.g. once per tile of a large viewport,
+                // and the buffer can't be copied into while it is mapped.
+                self.buffer.unmap();
+
                 let bgra = BgraImage::from_raw(self.size.width, self.size.height, buffer);
                 bgra.map(|image| image.convert())
             }
@@ -199,10 +206,12 @@ impl RenderTarget for TextureTarget {
             usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT | wgpu::TextureUsage::COPY_SRC,
         });
 
+        self.buffer_dimensions = BufferDimensions::new(width as usize, height as usize);
         let buffer_label = create_debug_label!("Render target buffer");
         self.buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: buffer_label.as_deref(),
-            size: width as u64 * height as u64 * 4,
+            size: (self.buffer_dimensions.padded_bytes_per_row * self.buffer_dimensions.height)
+                as u64,
             usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
             mapped_at_creation: false,
         });
@@ -1,5 +1,6 @@
 use lyon::lyon_algorithms::path::Path;
-use ruffle_core::shape_utils::DrawCommand;
+use lyon::tessellation::FillOptions;
+use ruffle_core::shape_utils::{DrawCommand, FillRule};
 use ruffle_core::swf;
 use std::borrow::Cow;
 use std::mem::size_of;
@@ -71,6 +72,13 @@ pub fn point(x: Twips, y: Twips) -> lyon::math::Point {
     lyon::math::Point::new(x.to_pixels() as f32, y.to_pixels() as f32)
 }
 
+pub fn fill_options(fill_rule: FillRule) -> FillOptions {
+    match fill_rule {
+        FillRule::EvenOdd => FillOptions::even_odd(),
+        FillRule::NonZero => FillOptions::non_zero(),
+    }
+}
+
 pub fn ruffle_path_to_lyon_path(commands: Vec<DrawCommand>, is_closed: bool) -> Path {
     let mut builder = Path::builder();
     let mut cmds = commands.into_iter().peekable();
@@ -93,6 +93,11 @@ pub enum OpCode {
     Label = 0x09,
     LessEquals = 0xae,
     LessThan = 0xad,
+    Lf32 = 0x38,
+    Lf64 = 0x39,
+    Li16 = 0x36,
+    Li32 = 0x37,
+    Li8 = 0x35,
     LookupSwitch = 0x1b,
     LShift = 0xa5,
     Modulo = 0xa4,
@@ -138,10 +143,18 @@ pub enum OpCode {
     SetProperty = 0x61,
     SetSlot = 0x6d,
     SetSuper = 0x05,
+    Sf32 = 0x3d,
+    Sf64 = 0x3e,
+    Si16 = 0x3b,
+    Si32 = 0x3c,
+    Si8 = 0x3a,
     StrictEquals = 0xac,
     Subtract = 0xa1,
     SubtractI = 0xc6,
     Swap = 0x2b,
+    Sxi1 = 0x50,
+    Sxi16 = 0x52,
+    Sxi8 = 0x51,
     Throw = 0x03,
     TypeOf = 0x95,
     URShift = 0xa7,
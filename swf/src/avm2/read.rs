@@ -106,23 +106,10 @@ impl<R: Read> Reader<R> {
             | (i32::from(self.read_u8()? as i8) << 16))
     }
     fn read_i32(&mut self) -> Result<i32> {
-        let mut n: i32 = 0;
-        let mut i = 0;
-        loop {
-            let byte: i32 = self.read_u8()?.into();
-            n |= (byte & 0b0111_1111) << i;
-            i += 7;
-
-            if byte & 0b1000_0000 == 0 {
-                if i < 32 {
-                    n <<= 32 - i;
-                    n >>= 32 - i;
-                }
-
-                break;
-            }
-        }
-        Ok(n)
+        // Flash Player reads an s32 exactly like a u32, and doesn't
+        // sign-extend short encodings: 0x7f is 127, and negative values
+        // always take five bytes.
+        Ok(self.read_u32()? as i32)
     }
 
     fn read_string(&mut self) -> Result<String> {
@@ -754,6 +741,11 @@ impl<R: Read> Reader<R> {
             OpCode::Label => Op::Label,
             OpCode::LessEquals => Op::LessEquals,
             OpCode::LessThan => Op::LessThan,
+            OpCode::Lf32 => Op::Lf32,
+            OpCode::Lf64 => Op::Lf64,
+            OpCode::Li16 => Op::Li16,
+            OpCode::Li32 => Op::Li32,
+            OpCode::Li8 => Op::Li8,
             OpCode::LookupSwitch => Op::LookupSwitch {
                 default_offset: self.read_i24()?,
                 case_offsets: {
@@ -843,10 +835,18 @@ impl<R: Read> Reader<R> {
             OpCode::SetSuper => Op::SetSuper {
                 index: self.read_index()?,
             },
+            OpCode::Sf32 => Op::Sf32,
+            OpCode::Sf64 => Op::Sf64,
+            OpCode::Si16 => Op::Si16,
+            OpCode::Si32 => Op::Si32,
+            OpCode::Si8 => Op::Si8,
             OpCode::StrictEquals => Op::StrictEquals,
             OpCode::Subtract => Op::Subtract,
             OpCode::SubtractI => Op::SubtractI,
             OpCode::Swap => Op::Swap,
+            OpCode::Sxi1 => Op::Sxi1,
+            OpCode::Sxi16 => Op::Sxi16,
+            OpCode::Sxi8 => Op::Sxi8,
             OpCode::Throw => Op::Throw,
             OpCode::TypeOf => Op::TypeOf,
             OpCode::URShift => Op::URShift,
@@ -901,4 +901,17 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn read_s32() {
+        let read = |bytes: &[u8]| Reader::new(bytes).read_i32().unwrap();
+        assert_eq!(read(&[0x00]), 0);
+        assert_eq!(read(&[0x7f]), 127);
+        assert_eq!(read(&[0x80, 0x01]), 128);
+        assert_eq!(read(&[0xff, 0x7f]), 16383);
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x07]), i32::MAX);
+        assert_eq!(read(&[0x80, 0x80, 0x80, 0x80, 0x08]), i32::MIN);
+        assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x0f]), -1);
+        assert_eq!(read(&[0x80, 0xff, 0xff, 0xff, 0x0f]), -128);
+    }
 }
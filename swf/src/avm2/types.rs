@@ -427,6 +427,11 @@ pub enum Op {
     Label,
     LessEquals,
     LessThan,
+    Lf32,
+    Lf64,
+    Li16,
+    Li32,
+    Li8,
     LookupSwitch {
         default_offset: i32,
         case_offsets: Vec<i32>,
@@ -505,10 +510,18 @@ pub enum Op {
     SetSuper {
         index: Index<Multiname>,
     },
+    Sf32,
+    Sf64,
+    Si16,
+    Si32,
+    Si8,
     StrictEquals,
     Subtract,
     SubtractI,
     Swap,
+    Sxi1,
+    Sxi16,
+    Sxi8,
     Throw,
     TypeOf,
     URShift,
@@ -84,19 +84,10 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
-    fn write_i32(&mut self, mut n: i32) -> Result<()> {
-        loop {
-            let byte = (n as u8) & 0x7f;
-            n >>= 7;
-            if n != 0 && n != -1 {
-                self.write_u8(0b1_0000000 | byte)?;
-            } else {
-                self.write_u8(byte)?;
-                break;
-            }
-        }
-
-        Ok(())
+    fn write_i32(&mut self, n: i32) -> Result<()> {
+        // An s32 is written with the same bits as a u32, so that it reads
+        // back the same in Flash Player.
+        self.write_u32(n as u32)
     }
 
     fn write_index<T>(&mut self, i: &Index<T>) -> Result<()> {
@@ -841,6 +832,11 @@ impl<W: Write> Writer<W> {
             Op::Label => self.write_opcode(OpCode::Label)?,
             Op::LessEquals => self.write_opcode(OpCode::LessEquals)?,
             Op::LessThan => self.write_opcode(OpCode::LessThan)?,
+            Op::Lf32 => self.write_opcode(OpCode::Lf32)?,
+            Op::Lf64 => self.write_opcode(OpCode::Lf64)?,
+            Op::Li16 => self.write_opcode(OpCode::Li16)?,
+            Op::Li32 => self.write_opcode(OpCode::Li32)?,
+            Op::Li8 => self.write_opcode(OpCode::Li8)?,
             Op::LookupSwitch {
                 default_offset,
                 ref case_offsets,
@@ -949,10 +945,18 @@ impl<W: Write> Writer<W> {
                 self.write_opcode(OpCode::SetSuper)?;
                 self.write_index(index)?;
             }
+            Op::Sf32 => self.write_opcode(OpCode::Sf32)?,
+            Op::Sf64 => self.write_opcode(OpCode::Sf64)?,
+            Op::Si16 => self.write_opcode(OpCode::Si16)?,
+            Op::Si32 => self.write_opcode(OpCode::Si32)?,
+            Op::Si8 => self.write_opcode(OpCode::Si8)?,
             Op::StrictEquals => self.write_opcode(OpCode::StrictEquals)?,
             Op::Subtract => self.write_opcode(OpCode::Subtract)?,
             Op::SubtractI => self.write_opcode(OpCode::SubtractI)?,
             Op::Swap => self.write_opcode(OpCode::Swap)?,
+            Op::Sxi1 => self.write_opcode(OpCode::Sxi1)?,
+            Op::Sxi16 => self.write_opcode(OpCode::Sxi16)?,
+            Op::Sxi8 => self.write_opcode(OpCode::Sxi8)?,
             Op::Throw => self.write_opcode(OpCode::Throw)?,
             Op::TypeOf => self.write_opcode(OpCode::TypeOf)?,
             Op::URShift => self.write_opcode(OpCode::URShift)?,
@@ -988,4 +992,18 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn write_s32() {
+        let write = |n: i32| {
+            let mut out = vec![];
+            Writer::new(&mut out).write_i32(n).unwrap();
+            out
+        };
+        assert_eq!(write(0), [0x00]);
+        assert_eq!(write(127), [0x7f]);
+        assert_eq!(write(128), [0x80, 0x01]);
+        assert_eq!(write(-1), [0xff, 0xff, 0xff, 0xff, 0x0f]);
+        assert_eq!(write(-128), [0x80, 0xff, 0xff, 0xff, 0x0f]);
+    }
 }
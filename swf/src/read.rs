@@ -456,7 +456,9 @@ impl<R: Read> Reader<R> {
             Some(TagCode::DefineText2) => {
                 Tag::DefineText(Box::new(tag_reader.read_define_text(2)?))
             }
-            Some(TagCode::DefineVideoStream) => tag_reader.read_define_video_stream()?,
+            Some(TagCode::DefineVideoStream) => {
+                Tag::DefineVideoStream(tag_reader.read_define_video_stream()?)
+            }
             Some(TagCode::EnableTelemetry) => {
                 tag_reader.read_u16()?; // Reserved
                 let password_hash = if length > 2 {
@@ -647,7 +649,7 @@ impl<R: Read> Reader<R> {
 
             Some(TagCode::RemoveObject2) => Tag::RemoveObject(tag_reader.read_remove_object_2()?),
 
-            Some(TagCode::VideoFrame) => tag_reader.read_video_frame()?,
+            Some(TagCode::VideoFrame) => Tag::VideoFrame(tag_reader.read_video_frame()?),
             Some(TagCode::ProductInfo) => Tag::ProductInfo(tag_reader.read_product_info()?),
             _ => {
                 let size = length as usize;
@@ -2741,7 +2743,7 @@ impl<R: Read> Reader<R> {
         })
     }
 
-    fn read_define_video_stream(&mut self) -> Result<Tag> {
+    pub fn read_define_video_stream(&mut self) -> Result<DefineVideoStream> {
         let id = self.read_character_id()?;
         let num_frames = self.read_u16()?;
         let width = self.read_u16()?;
@@ -2755,7 +2757,7 @@ impl<R: Read> Reader<R> {
             5 => VideoCodec::VP6WithAlpha,
             _ => return Err(Error::invalid_data("Invalid video codec.")),
         };
-        Ok(Tag::DefineVideoStream(DefineVideoStream {
+        Ok(DefineVideoStream {
             id,
             num_frames,
             width,
@@ -2771,19 +2773,19 @@ impl<R: Read> Reader<R> {
                 0b101_0 => VideoDeblocking::Level4,
                 _ => return Err(Error::invalid_data("Invalid video deblocking value.")),
             },
-        }))
+        })
     }
 
-    fn read_video_frame(&mut self) -> Result<Tag> {
+    pub fn read_video_frame(&mut self) -> Result<VideoFrame> {
         let stream_id = self.read_character_id()?;
         let frame_num = self.read_u16()?;
         let mut data = vec![];
         self.input.read_to_end(&mut data)?;
-        Ok(Tag::VideoFrame(VideoFrame {
+        Ok(VideoFrame {
             stream_id,
             frame_num,
             data,
-        }))
+        })
     }
 
     fn read_define_bits_jpeg_3(&mut self, version: u8) -> Result<Tag> {
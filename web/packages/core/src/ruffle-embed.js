@@ -17,6 +17,9 @@ module.exports = class RuffleEmbed extends RufflePlayer {
 
     connectedCallback() {
         super.connectedCallback();
+        if (this.attributes.wmode) {
+            this.wmode = this.attributes.wmode.value;
+        }
         this.stream_swf_url(this.attributes.src.value);
     }
 
@@ -27,6 +27,12 @@ module.exports = class RuffleObject extends RufflePlayer {
         let url = null;
         console.log("AllowScriptAccess: " + allowScriptAccess);
 
+        this.wmode = RuffleObject.find_case_insensitive(
+            this.params,
+            "wmode",
+            null
+        );
+
         if (this.attributes.data) {
             url = this.attributes.data.value;
         } else if (this.params.movie) {
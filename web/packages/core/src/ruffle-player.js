@@ -27,8 +27,18 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
             );
         }
 
+        self.context_menu_element = self.shadow.getElementById("context_menu");
+        self.container.addEventListener(
+            "contextmenu",
+            self.open_context_menu.bind(self)
+        );
+        window.addEventListener("click", self.hide_context_menu.bind(self));
+
         self.instance = null;
         self.allow_script_access = false;
+        self.wmode = null;
+        self.fonts = [];
+        self.fetch_hook = null;
 
         self.Ruffle = load_ruffle();
 
@@ -138,9 +148,71 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
         this.instance = Ruffle.new(
             this.container,
             this,
-            this.allow_script_access
+            this.allow_script_access,
+            this.wmode,
+            this.fetch_hook
         );
         console.log("New Ruffle instance created.");
+
+        for (let font of this.fonts.slice()) {
+            try {
+                this.instance.add_font(
+                    font.name,
+                    font.data,
+                    font.bold,
+                    font.italic
+                );
+            } catch (err) {
+                // Don't fail every future load on the same bad font.
+                this.fonts.splice(this.fonts.indexOf(font), 1);
+                throw err;
+            }
+        }
+    }
+
+    /**
+     * Add a font to be used for text that uses device fonts.
+     *
+     * The font is used for text in the given family, or in any of the family
+     * names stored in the font file, that doesn't embed its font. Fonts added
+     * before a movie is loaded are registered with every new instance.
+     *
+     * @param {String} name The family name to register the font as.
+     * @param {Uint8Array} data The contents of a TrueType or OpenType file.
+     * @param {Object} style Whether this is the `bold` and/or `italic` style
+     * of the family.
+     * @throws An error if the font data is invalid and a movie is already
+     * loaded. Otherwise, the error is thrown when the next movie is loaded.
+     */
+    add_font(name, data, { bold = false, italic = false } = {}) {
+        let font = { name, data: new Uint8Array(data), bold, italic };
+        if (this.instance) {
+            this.instance.add_font(
+                font.name,
+                font.data,
+                font.bold,
+                font.italic
+            );
+        }
+        this.fonts.push(font);
+    }
+
+    /**
+     * Intercept every URL that movies in this player load.
+     *
+     * The hook is called as `hook(url, { method, headers })` before the root
+     * movie, child movies, variables, images and any other data are fetched.
+     * It may return a string to fetch a different URL instead, an
+     * `ArrayBuffer` or `Uint8Array` (or a promise of one) to use as the
+     * response, or `null` to fetch the URL as usual. If the hook throws or
+     * its promise is rejected, the load fails as if by a network error.
+     *
+     * The hook takes effect from the next movie that is loaded.
+     *
+     * @param {Function|null} hook The function to call, or `null` to remove it.
+     */
+    set_fetch_hook(hook) {
+        this.fetch_hook = hook;
     }
 
     /**
@@ -184,6 +256,74 @@ exports.RufflePlayer = class RufflePlayer extends HTMLElement {
         }
     }
 
+    /**
+     * Open the context menu of the movie at the position of the given
+     * `contextmenu` event, in place of the browser's menu.
+     */
+    open_context_menu(e) {
+        if (!this.instance || !this.context_menu_element) {
+            return;
+        }
+
+        e.preventDefault();
+        this.hide_context_menu();
+
+        const items = this.instance.prepare_context_menu();
+        if (items.length === 0) {
+            return;
+        }
+
+        this.context_menu_element.textContent = "";
+        items.forEach((item, index) => {
+            if (item.separatorBefore && index > 0) {
+                const separator = document.createElement("li");
+                separator.className = "menu_separator";
+                separator.appendChild(document.createElement("hr"));
+                this.context_menu_element.appendChild(separator);
+            }
+
+            const element = document.createElement("li");
+            element.className = "menu_item";
+            element.textContent = item.caption;
+            if (item.checked) {
+                element.classList.add("checked");
+            }
+            if (item.enabled) {
+                element.addEventListener("click", (event) => {
+                    event.stopPropagation();
+                    this.context_menu_element.style.display = "none";
+                    this.instance.run_context_menu_callback(index);
+                });
+            } else {
+                element.classList.add("disabled");
+                element.addEventListener("click", (event) => {
+                    event.stopPropagation();
+                });
+            }
+            this.context_menu_element.appendChild(element);
+        });
+
+        const rect = this.getBoundingClientRect();
+        this.context_menu_element.style.left = e.clientX - rect.left + "px";
+        this.context_menu_element.style.top = e.clientY - rect.top + "px";
+        this.context_menu_element.style.display = "block";
+    }
+
+    /**
+     * Close the context menu without choosing an item.
+     */
+    hide_context_menu() {
+        if (
+            this.context_menu_element &&
+            this.context_menu_element.style.display === "block"
+        ) {
+            this.context_menu_element.style.display = "none";
+            if (this.instance) {
+                this.instance.clear_custom_menu_items();
+            }
+        }
+    }
+
     /**
      * Load a movie's data into this Ruffle Player instance.
      *
@@ -1,6 +1,6 @@
 use fnv::FnvHashMap;
 use generational_arena::Arena;
-use ruffle_core::backend::audio::decoders::{AdpcmDecoder, Mp3Decoder};
+use ruffle_core::backend::audio::decoders::{count_mp3_sample_frames, AdpcmDecoder, Mp3Decoder};
 use ruffle_core::backend::audio::swf::{self, AudioCompression};
 use ruffle_core::backend::audio::{
     AudioBackend, AudioStreamHandle, SoundHandle, SoundInstanceHandle,
@@ -554,15 +554,23 @@ impl AudioBackend for WebAudioBackend {
             (0, &sound.data[..])
         };
 
+        // Some encoders leave the sample count of MP3 sounds empty; count the MP3 frames instead.
+        let num_sample_frames =
+            if sound.num_samples == 0 && sound.format.compression == AudioCompression::Mp3 {
+                count_mp3_sample_frames(data)
+            } else {
+                sound.num_samples
+            };
+
         let sound = Sound {
             format: sound.format.clone(),
             source: SoundSource::AudioBuffer(self.decompress_to_audio_buffer(
                 &sound.format,
                 data,
-                sound.num_samples,
+                num_sample_frames,
                 None,
             )?),
-            num_sample_frames: sound.num_samples,
+            num_sample_frames,
             skip_sample_frames,
         };
         Ok(self.sounds.insert(sound))
@@ -787,6 +795,10 @@ impl AudioBackend for WebAudioBackend {
         })
     }
 
+    fn is_sound_playing(&self, instance: SoundInstanceHandle) -> bool {
+        SOUND_INSTANCES.with(|instances| instances.borrow().contains(instance))
+    }
+
     fn is_sound_playing_with_handle(&mut self, handle: SoundHandle) -> bool {
         SOUND_INSTANCES.with(|instances| {
             let instances = instances.borrow();
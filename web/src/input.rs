@@ -1,171 +1,57 @@
-use ruffle_core::backend::input::{InputBackend, MouseCursor};
-use ruffle_core::events::KeyCode;
+use ruffle_core::backend::input::{
+    CustomCursor, InputBackend, MouseCursor, PrintPageFormat, PrintedPage,
+};
+use ruffle_core::events::{KeyCode, KeyLocation};
 use ruffle_web_common::JsResult;
-use std::collections::HashSet;
-use web_sys::HtmlCanvasElement;
+use std::convert::TryFrom;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
 
 /// An implementation of `InputBackend` utilizing `web_sys` bindings to input
 /// APIs
 pub struct WebInputBackend {
-    keys_down: HashSet<String>,
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
-    last_key: KeyCode,
+
+    /// The text last copied by the movie.
+    ///
+    /// Browsers only allow reading the clipboard asynchronously and after
+    /// asking the user, so movies can only read back their own writes.
+    clipboard: String,
 }
 
 impl WebInputBackend {
     pub fn new(canvas: &HtmlCanvasElement) -> Self {
         Self {
-            keys_down: HashSet::new(),
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
-            last_key: KeyCode::Unknown,
+            clipboard: String::new(),
         }
     }
 
-    /// Register a key press for a given code string.
-    pub fn keydown(&mut self, code: String) {
-        self.last_key = web_to_ruffle_key_code(&code).unwrap_or_else(|| KeyCode::Unknown);
-        self.keys_down.insert(code);
-    }
-
-    /// Register a key release for a given code string.
-    pub fn keyup(&mut self, code: String) {
-        self.last_key = web_to_ruffle_key_code(&code).unwrap_or_else(|| KeyCode::Unknown);
-        self.keys_down.remove(&code);
-    }
-
     fn update_mouse_cursor(&self) {
         let cursor = if self.cursor_visible {
-            match self.cursor {
-                MouseCursor::Arrow => "auto",
-                MouseCursor::Hand => "pointer",
-                MouseCursor::IBeam => "text",
-                MouseCursor::Grab => "grab",
+            match &self.cursor {
+                MouseCursor::Arrow => "auto".to_string(),
+                MouseCursor::Hand => "pointer".to_string(),
+                MouseCursor::IBeam => "text".to_string(),
+                MouseCursor::Grab => "grab".to_string(),
+                MouseCursor::Custom(cursor) => custom_cursor_to_css(cursor),
             }
         } else {
-            "none"
+            "none".to_string()
         };
         self.canvas
             .style()
-            .set_property("cursor", cursor)
+            .set_property("cursor", &cursor)
             .warn_on_error();
     }
 }
 
 impl InputBackend for WebInputBackend {
-    fn is_key_down(&self, key: KeyCode) -> bool {
-        match key {
-            KeyCode::Unknown => false,
-            KeyCode::Backspace => self.keys_down.contains("Backspace"),
-            KeyCode::Return => self.keys_down.contains("Enter"),
-            KeyCode::Shift => {
-                self.keys_down.contains("ShiftLeft") || self.keys_down.contains("ShiftRight")
-            }
-            KeyCode::Control => {
-                self.keys_down.contains("ControlLeft") || self.keys_down.contains("ControlRight")
-            }
-            KeyCode::Alt => {
-                self.keys_down.contains("AltLeft") || self.keys_down.contains("AltRight")
-            }
-            KeyCode::CapsLock => self.keys_down.contains("CapsLock"),
-            KeyCode::Escape => self.keys_down.contains("Escape"),
-            KeyCode::Space => self.keys_down.contains("Space"),
-            KeyCode::Key0 => self.keys_down.contains("Digit0"),
-            KeyCode::Key1 => self.keys_down.contains("Digit1"),
-            KeyCode::Key2 => self.keys_down.contains("Digit2"),
-            KeyCode::Key3 => self.keys_down.contains("Digit3"),
-            KeyCode::Key4 => self.keys_down.contains("Digit4"),
-            KeyCode::Key5 => self.keys_down.contains("Digit5"),
-            KeyCode::Key6 => self.keys_down.contains("Digit6"),
-            KeyCode::Key7 => self.keys_down.contains("Digit7"),
-            KeyCode::Key8 => self.keys_down.contains("Digit8"),
-            KeyCode::Key9 => self.keys_down.contains("Digit9"),
-            KeyCode::A => self.keys_down.contains("KeyA"),
-            KeyCode::B => self.keys_down.contains("KeyB"),
-            KeyCode::C => self.keys_down.contains("KeyC"),
-            KeyCode::D => self.keys_down.contains("KeyD"),
-            KeyCode::E => self.keys_down.contains("KeyE"),
-            KeyCode::F => self.keys_down.contains("KeyF"),
-            KeyCode::G => self.keys_down.contains("KeyG"),
-            KeyCode::H => self.keys_down.contains("KeyH"),
-            KeyCode::I => self.keys_down.contains("KeyI"),
-            KeyCode::J => self.keys_down.contains("KeyJ"),
-            KeyCode::K => self.keys_down.contains("KeyK"),
-            KeyCode::L => self.keys_down.contains("KeyL"),
-            KeyCode::M => self.keys_down.contains("KeyM"),
-            KeyCode::N => self.keys_down.contains("KeyN"),
-            KeyCode::O => self.keys_down.contains("KeyO"),
-            KeyCode::P => self.keys_down.contains("KeyP"),
-            KeyCode::Q => self.keys_down.contains("KeyQ"),
-            KeyCode::R => self.keys_down.contains("KeyR"),
-            KeyCode::S => self.keys_down.contains("KeyS"),
-            KeyCode::T => self.keys_down.contains("KeyT"),
-            KeyCode::U => self.keys_down.contains("KeyU"),
-            KeyCode::V => self.keys_down.contains("KeyV"),
-            KeyCode::W => self.keys_down.contains("KeyW"),
-            KeyCode::X => self.keys_down.contains("KeyX"),
-            KeyCode::Y => self.keys_down.contains("KeyY"),
-            KeyCode::Z => self.keys_down.contains("KeyZ"),
-            KeyCode::Semicolon => self.keys_down.contains("Semicolon"),
-            KeyCode::Equals => self.keys_down.contains("Equal"),
-            KeyCode::Comma => self.keys_down.contains("Comma"),
-            KeyCode::Minus => self.keys_down.contains("Minus"),
-            KeyCode::Period => self.keys_down.contains("Period"),
-            KeyCode::Slash => self.keys_down.contains("Slash"),
-            KeyCode::Grave => self.keys_down.contains("Backquote"),
-            KeyCode::LBracket => self.keys_down.contains("BracketLeft"),
-            KeyCode::Backslash => self.keys_down.contains("Backslash"),
-            KeyCode::RBracket => self.keys_down.contains("BracketRight"),
-            KeyCode::Apostrophe => self.keys_down.contains("Quote"),
-            KeyCode::Numpad0 => self.keys_down.contains("Numpad0"),
-            KeyCode::Numpad1 => self.keys_down.contains("Numpad1"),
-            KeyCode::Numpad2 => self.keys_down.contains("Numpad2"),
-            KeyCode::Numpad3 => self.keys_down.contains("Numpad3"),
-            KeyCode::Numpad4 => self.keys_down.contains("Numpad4"),
-            KeyCode::Numpad5 => self.keys_down.contains("Numpad5"),
-            KeyCode::Numpad6 => self.keys_down.contains("Numpad6"),
-            KeyCode::Numpad7 => self.keys_down.contains("Numpad7"),
-            KeyCode::Numpad8 => self.keys_down.contains("Numpad8"),
-            KeyCode::Numpad9 => self.keys_down.contains("Numpad9"),
-            KeyCode::Multiply => self.keys_down.contains("NumpadMultiply"),
-            KeyCode::Plus => self.keys_down.contains("NumpadAdd"),
-            KeyCode::NumpadMinus => self.keys_down.contains("NumpadSubtract"),
-            KeyCode::NumpadPeriod => self.keys_down.contains("NumpadDecimal"),
-            KeyCode::NumpadSlash => self.keys_down.contains("NumpadDivide"),
-            KeyCode::PgUp => self.keys_down.contains("PageUp"),
-            KeyCode::PgDown => self.keys_down.contains("PageDown"),
-            KeyCode::End => self.keys_down.contains("End"),
-            KeyCode::Home => self.keys_down.contains("Home"),
-            KeyCode::Left => self.keys_down.contains("ArrowLeft"),
-            KeyCode::Up => self.keys_down.contains("ArrowUp"),
-            KeyCode::Right => self.keys_down.contains("ArrowRight"),
-            KeyCode::Down => self.keys_down.contains("ArrowDown"),
-            KeyCode::Insert => self.keys_down.contains("Insert"),
-            KeyCode::Delete => self.keys_down.contains("Delete"),
-            KeyCode::Pause => self.keys_down.contains("Pause"),
-            KeyCode::ScrollLock => self.keys_down.contains("ScrollLock"),
-            KeyCode::F1 => self.keys_down.contains("F1"),
-            KeyCode::F2 => self.keys_down.contains("F2"),
-            KeyCode::F3 => self.keys_down.contains("F3"),
-            KeyCode::F4 => self.keys_down.contains("F4"),
-            KeyCode::F5 => self.keys_down.contains("F5"),
-            KeyCode::F6 => self.keys_down.contains("F6"),
-            KeyCode::F7 => self.keys_down.contains("F7"),
-            KeyCode::F8 => self.keys_down.contains("F8"),
-            KeyCode::F9 => self.keys_down.contains("F9"),
-            KeyCode::F10 => self.keys_down.contains("F10"),
-            KeyCode::F11 => self.keys_down.contains("F11"),
-            KeyCode::F12 => self.keys_down.contains("F12"),
-        }
-    }
-
-    fn get_last_key_code(&self) -> KeyCode {
-        self.last_key
-    }
-
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
     }
@@ -185,16 +71,181 @@ impl InputBackend for WebInputBackend {
         self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, _content: String) {
-        log::warn!("set clipboard not implemented");
+    fn clipboard_content(&mut self) -> String {
+        self.clipboard.clone()
+    }
+
+    fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard = content.clone();
+
+        // Prefer the asynchronous Clipboard API. It's only available in secure
+        // contexts, and the write may be rejected without permission or a
+        // recent user gesture, in which case we fall back to `execCommand`.
+        let clipboard = web_sys::window()
+            .and_then(|window| js_sys::Reflect::get(&window.navigator(), &"clipboard".into()).ok())
+            .filter(|clipboard| !clipboard.is_undefined());
+        let write_text = clipboard
+            .as_ref()
+            .and_then(|clipboard| js_sys::Reflect::get(clipboard, &"writeText".into()).ok())
+            .and_then(|write_text| write_text.dyn_into::<js_sys::Function>().ok());
+
+        if let (Some(clipboard), Some(write_text)) = (clipboard, write_text) {
+            let promise = write_text
+                .call1(&clipboard, &JsValue::from_str(&content))
+                .and_then(|promise| promise.dyn_into::<js_sys::Promise>().map_err(JsValue::from));
+            match promise {
+                Ok(promise) => {
+                    spawn_local(async move {
+                        if let Err(e) = JsFuture::from(promise).await {
+                            log::info!(
+                                "Clipboard write was rejected ({:?}), retrying with execCommand",
+                                e
+                            );
+                            copy_with_exec_command(&content).warn_on_error();
+                        }
+                    });
+                    return;
+                }
+                Err(e) => log::info!(
+                    "Clipboard API failed ({:?}), falling back to execCommand",
+                    e
+                ),
+            }
+        }
+
+        copy_with_exec_command(&content).warn_on_error();
+    }
+
+    fn start_print_job(&mut self) -> Option<PrintPageFormat> {
+        // The browser shows its print dialog once the pages are sent.
+        Some(PrintPageFormat::default())
+    }
+
+    fn print_pages(&mut self, pages: Vec<PrintedPage>) {
+        print_in_new_window(&pages).warn_on_error();
+    }
+}
+
+/// Prints pages by opening a window containing their images, and printing that window.
+fn print_in_new_window(pages: &[PrintedPage]) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or("Couldn't get the window")?;
+    let print_window = window
+        .open_with_url("")?
+        .ok_or("Couldn't open a window for printing")?;
+    let document = print_window
+        .document()
+        .ok_or("Couldn't get the document of the print window")?;
+    let body = document
+        .body()
+        .ok_or("Couldn't get the body of the print window")?;
+    body.style().set_property("margin", "0")?;
+
+    for page in pages {
+        let data_url = match rgba_to_png_data_url(page.width, page.height, &page.pixels) {
+            Ok(data_url) => data_url,
+            Err(e) => {
+                log::error!("Unable to encode printed page: {}", e);
+                continue;
+            }
+        };
+
+        let image = document.create_element("img")?;
+        image.set_attribute("src", &data_url)?;
+        // Size the image in points, so that it prints at its intended resolution.
+        image.set_attribute(
+            "style",
+            &format!(
+                "display: block; width: {}pt; height: {}pt; page-break-after: always",
+                f64::from(page.width) / page.pixels_per_point,
+                f64::from(page.height) / page.pixels_per_point
+            ),
+        )?;
+        body.append_child(&image)?;
+    }
+
+    print_window.print()?;
+    print_window.close()
+}
+
+/// Copies text to the clipboard with the legacy `document.execCommand("copy")`,
+/// by selecting it in a temporary, invisible text area.
+fn copy_with_exec_command(content: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("Couldn't get the document")?;
+    let body = document.body().ok_or("Couldn't get the document body")?;
+
+    let text_area: HtmlTextAreaElement = document.create_element("textarea")?.dyn_into()?;
+    text_area.set_value(content);
+    let style = text_area.style();
+    style.set_property("position", "fixed")?;
+    style.set_property("opacity", "0")?;
+    body.append_child(&text_area)?;
+    text_area.select();
+
+    let result = document
+        .dyn_into::<HtmlDocument>()
+        .map_err(JsValue::from)
+        .and_then(|document| document.exec_command("copy"));
+    body.remove_child(&text_area)?;
+
+    match result {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("The copy command was rejected".into()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Converts a custom cursor into a CSS `cursor` value, using the first frame
+/// as a PNG data URI. CSS cursors cannot be animated, so later frames are
+/// ignored.
+fn custom_cursor_to_css(cursor: &CustomCursor) -> String {
+    let frame = if let Some(frame) = cursor.frames.first() {
+        frame
+    } else {
+        return "auto".to_string();
+    };
+
+    match rgba_to_png_data_url(cursor.width, cursor.height, &frame[..]) {
+        Ok(data_url) => format!(
+            "url({}) {} {}, auto",
+            data_url, cursor.hotspot.0, cursor.hotspot.1
+        ),
+        Err(e) => {
+            log::error!("Unable to encode custom cursor: {}", e);
+            "auto".to_string()
+        }
     }
 }
 
+/// Encodes unmultiplied RGBA pixels as a PNG `data:` URL.
+fn rgba_to_png_data_url(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+) -> Result<String, png::EncodingError> {
+    let mut png_data: Vec<u8> = vec![];
+    {
+        let mut encoder = png::Encoder::new(&mut png_data, width, height);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(rgba))?;
+    }
+
+    Ok(format!(
+        "data:image/png;base64,{}",
+        base64::encode(&png_data[..])
+    ))
+}
+
 /// Converts a Web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
 /// Returns `None` if there is no matching Flash key key.
 pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
     let out = match key_code {
         "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
         "Enter" => KeyCode::Return,
         "ShiftLeft" | "ShiftRight" => KeyCode::Shift,
         "ControlLeft" | "ControlRight" => KeyCode::Control,
@@ -275,6 +326,7 @@ pub fn web_to_ruffle_key_code(key_code: &str) -> Option<KeyCode> {
         "Insert" => KeyCode::Insert,
         "Delete" => KeyCode::Delete,
         "Pause" => KeyCode::Pause,
+        "NumLock" => KeyCode::NumLock,
         "ScrollLock" => KeyCode::ScrollLock,
         "F1" => KeyCode::F1,
         "F2" => KeyCode::F2,
@@ -300,9 +352,17 @@ pub fn web_key_to_codepoint(key: &str) -> Option<char> {
     // Single character strings will be an actual printable char that we can use as text input.
     // All the other special values are multiple characters (e.g. "ArrowLeft").
     // It's probably better to explicitly match on all the variants.
-    if key.len() == 1 {
-        key.chars().next()
-    } else {
-        None
+    // The browser has already applied the keyboard layout, Shift, AltGr and any dead key, so
+    // this is the final character, which may be outside ASCII (e.g. "é" or "€").
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
     }
 }
+
+/// Converts a Web `KeyboardEvent.location` value into a key location.
+pub fn web_to_ruffle_key_location(location: u32) -> KeyLocation {
+    // The DOM and Flash number the locations the same way.
+    KeyLocation::try_from(location).unwrap_or_default()
+}
@@ -14,24 +14,27 @@ use crate::{
 };
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Uint8Array};
+use ruffle_core::backend::font::NullFontProvider;
 use ruffle_core::backend::render::RenderBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::video::NullVideoBackend;
 use ruffle_core::context::UpdateContext;
-use ruffle_core::events::MouseWheelDelta;
+use ruffle_core::context_menu::ContextMenuItem;
+use ruffle_core::events::{MouseWheelDelta, TouchPhase};
 use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, Value as ExternalValue, Value,
 };
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::PlayerEvent;
+use ruffle_core::{BackgroundThrottle, Language, PlayerEvent, PlayerType};
 use ruffle_web_common::JsResult;
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, Element, EventTarget, HtmlCanvasElement, HtmlElement, KeyboardEvent,
-    PointerEvent, WheelEvent,
+    AddEventListenerOptions, CompositionEvent, Element, Event, EventTarget, HtmlCanvasElement,
+    HtmlElement, HtmlInputElement, KeyboardEvent, PointerEvent, WheelEvent,
 };
 
 thread_local! {
@@ -45,6 +48,9 @@ thread_local! {
 
 type AnimationHandler = Closure<dyn FnMut(f64)>;
 
+/// How early, in milliseconds, an animation frame may arrive and still tick the player.
+const TICK_TOLERANCE_MS: f64 = 4.0;
+
 struct RuffleInstance {
     core: Arc<Mutex<ruffle_core::Player>>,
     js_player: JavascriptPlayer,
@@ -53,8 +59,19 @@ struct RuffleInstance {
     canvas_height: i32,
     device_pixel_ratio: f64,
     timestamp: Option<f64>,
+
+    /// When the player next needs to be ticked, in the same clock as
+    /// `timestamp`. Animation frames before this are skipped, so that movies
+    /// with a lower frame rate than the display don't tick needlessly.
+    next_tick_timestamp: Option<f64>,
     animation_handler: Option<AnimationHandler>, // requestAnimationFrame callback
     animation_handler_id: Option<NonZeroI32>,    // requestAnimationFrame id
+
+    /// Ticks the player while the page is hidden, when browsers stop calling
+    /// `requestAnimationFrame` callbacks.
+    background_tick_callback: Option<Closure<dyn FnMut()>>,
+    background_tick_id: Option<i32>, // setTimeout id
+    visibility_change_callback: Option<Closure<dyn FnMut(Event)>>,
     #[allow(dead_code)]
     mouse_move_callback: Option<Closure<dyn FnMut(PointerEvent)>>,
     mouse_down_callback: Option<Closure<dyn FnMut(PointerEvent)>>,
@@ -63,7 +80,17 @@ struct RuffleInstance {
     mouse_wheel_callback: Option<Closure<dyn FnMut(WheelEvent)>>,
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    composition_start_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
+    composition_end_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
     has_focus: bool,
+
+    /// A hidden text input that is focused along with the player, so that the
+    /// browser's input method editor can be used to type text.
+    ime_input: HtmlInputElement,
+
+    /// Whether the input method editor is composing text. Key presses are
+    /// not sent to the player while composing.
+    is_composing: bool,
 }
 
 #[wasm_bindgen(module = "/packages/core/src/ruffle-player.js")]
@@ -92,8 +119,10 @@ impl Ruffle {
         parent: HtmlElement,
         js_player: JavascriptPlayer,
         allow_script_access: bool,
+        wmode: Option<String>,
+        fetch_hook: Option<Function>,
     ) -> Result<Ruffle, JsValue> {
-        Ruffle::new_internal(parent, js_player, allow_script_access)
+        Ruffle::new_internal(parent, js_player, allow_script_access, wmode, fetch_hook)
             .map_err(|_| "Error creating player".into())
     }
 
@@ -137,6 +166,97 @@ impl Ruffle {
         });
     }
 
+    /// Import a shared object from the contents of a `.sol` file.
+    pub fn import_shared_object(&mut self, name: &str, data: Uint8Array) -> Result<(), JsValue> {
+        let data = data.to_vec();
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            instance
+                .core
+                .lock()
+                .unwrap()
+                .import_shared_object(name, &data)
+                .map_err(|e| format!("Error importing shared object: {}", e).into())
+        })
+    }
+
+    /// Register a TrueType or OpenType font file as a device font.
+    ///
+    /// The font is used for text in the given family, or in any of the family
+    /// names stored in the font file, that doesn't embed its font. Fonts
+    /// should be added before a movie is loaded.
+    pub fn add_font(
+        &mut self,
+        name: &str,
+        data: Uint8Array,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Result<(), JsValue> {
+        let data = data.to_vec();
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            instance
+                .core
+                .lock()
+                .unwrap()
+                .register_device_font(name, &data, is_bold, is_italic)
+                .map_err(|e| format!("Error loading font {}: {}", name, e).into())
+        })
+    }
+
+    /// Export a shared object as the contents of a `.sol` file.
+    ///
+    /// Returns `undefined` if no such shared object exists.
+    pub fn export_shared_object(&mut self, name: &str) -> Option<Uint8Array> {
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            let data = instance.core.lock().unwrap().export_shared_object(name)?;
+            Some(Uint8Array::from(&data[..]))
+        })
+    }
+
+    /// Build the items of the context menu, as it is about to be opened.
+    ///
+    /// Returns an array of `{ caption, enabled, separatorBefore, checked }`
+    /// objects.
+    pub fn prepare_context_menu(&mut self) -> JsValue {
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            let items = instance.core.lock().unwrap().prepare_context_menu();
+            let array = Array::new();
+            for item in items {
+                array.push(&context_menu_item_to_js(item));
+            }
+            array.into()
+        })
+    }
+
+    /// Run the action of the context menu item at the given index.
+    pub fn run_context_menu_callback(&mut self, index: usize) {
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            instance
+                .core
+                .lock()
+                .unwrap()
+                .run_context_menu_callback(index);
+        });
+    }
+
+    /// Close the context menu without choosing an item.
+    pub fn clear_custom_menu_items(&mut self) {
+        INSTANCES.with(|instances| {
+            let mut instances = instances.borrow_mut();
+            let instance = instances.get_mut(self.0).unwrap();
+            instance.core.lock().unwrap().clear_custom_menu_items();
+        });
+    }
+
     pub fn destroy(&mut self) -> Result<(), JsValue> {
         // Remove instance from the active list.
         if let Some(mut instance) = INSTANCES.with(|instances| {
@@ -144,6 +264,7 @@ impl Ruffle {
             instances.remove(self.0)
         }) {
             instance.canvas.remove();
+            instance.ime_input.remove();
 
             // Stop all audio playing from the instance
             let mut player = instance.core.lock().unwrap();
@@ -153,14 +274,31 @@ impl Ruffle {
             // Clean up all event listeners.
             instance.key_down_callback = None;
             instance.key_up_callback = None;
+            instance.composition_start_callback = None;
+            instance.composition_end_callback = None;
             instance.mouse_down_callback = None;
             instance.mouse_move_callback = None;
             instance.mouse_up_callback = None;
             instance.window_mouse_down_callback = None;
 
-            // Cancel the animation handler, if it's still active.
-            if let Some(id) = instance.animation_handler_id {
-                if let Some(window) = web_sys::window() {
+            if let Some(window) = web_sys::window() {
+                if let (Some(document), Some(callback)) = (
+                    window.document(),
+                    instance.visibility_change_callback.take(),
+                ) {
+                    document.remove_event_listener_with_callback(
+                        "visibilitychange",
+                        callback.as_ref().unchecked_ref(),
+                    )?;
+                }
+
+                // Cancel the background tick, if it's still pending.
+                if let Some(id) = instance.background_tick_id {
+                    window.clear_timeout_with_handle(id);
+                }
+
+                // Cancel the animation handler, if it's still active.
+                if let Some(id) = instance.animation_handler_id {
                     return window.cancel_animation_frame(id.into());
                 }
             }
@@ -204,6 +342,8 @@ impl Ruffle {
         parent: HtmlElement,
         js_player: JavascriptPlayer,
         allow_script_access: bool,
+        wmode: Option<String>,
+        fetch_hook: Option<Function>,
     ) -> Result<Ruffle, Box<dyn Error>> {
         console_error_panic_hook::set_once();
         let _ = console_log::init_with_level(log::Level::Trace);
@@ -211,15 +351,25 @@ impl Ruffle {
         let window = web_sys::window().ok_or_else(|| "Expected window")?;
         let document = window.document().ok_or("Expected document")?;
 
-        let (canvas, renderer) = create_renderer(&document)?;
+        // With `wmode="transparent"`, the page shows through where the movie has no content.
+        let is_transparent = wmode.map_or(false, |wmode| wmode.eq_ignore_ascii_case("transparent"));
+        let (canvas, renderer) = create_renderer(&document, is_transparent)?;
         parent
             .append_child(&canvas.clone().into())
             .into_js_result()?;
+        let ime_input = create_ime_input(&document)?;
+        parent
+            .append_child(&ime_input.clone().into())
+            .into_js_result()?;
 
         let audio = Box::new(WebAudioBackend::new()?);
-        let navigator = Box::new(WebNavigatorBackend::new());
+        let navigator = Box::new(WebNavigatorBackend::new(fetch_hook));
         let input = Box::new(WebInputBackend::new(&canvas));
         let locale = Box::new(WebLocaleBackend::new());
+        // Device fonts are drawn with the built-in device font on the web.
+        let font_provider = Box::new(NullFontProvider::new());
+        // There is no software decoder for embedded video yet.
+        let video = Box::new(NullVideoBackend::new());
 
         let current_domain = window.location().href().unwrap();
 
@@ -231,8 +381,28 @@ impl Ruffle {
             })
             .unwrap_or_else(|| Box::new(MemoryStorageBackend::default()));
 
-        let core =
-            ruffle_core::Player::new(renderer, audio, navigator, input, local_storage, locale)?;
+        let core = ruffle_core::Player::new(
+            renderer,
+            audio,
+            navigator,
+            input,
+            local_storage,
+            locale,
+            font_provider,
+            video,
+        )?;
+        {
+            let mut core_lock = core.lock().unwrap();
+            core_lock.set_player_type(PlayerType::PlugIn);
+            if let Ok(screen) = window.screen() {
+                if let (Ok(width), Ok(height)) = (screen.width(), screen.height()) {
+                    core_lock.set_screen_resolution(width as u32, height as u32);
+                }
+            }
+            if let Some(language) = window.navigator().language() {
+                core_lock.set_language(Language::from_locale(&language));
+            }
+        }
 
         // Create instance.
         let instance = RuffleInstance {
@@ -244,6 +414,9 @@ impl Ruffle {
             device_pixel_ratio: window.device_pixel_ratio(),
             animation_handler: None,
             animation_handler_id: None,
+            background_tick_callback: None,
+            background_tick_id: None,
+            visibility_change_callback: None,
             mouse_move_callback: None,
             mouse_down_callback: None,
             window_mouse_down_callback: None,
@@ -251,8 +424,13 @@ impl Ruffle {
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            composition_start_callback: None,
+            composition_end_callback: None,
             timestamp: None,
+            next_tick_timestamp: None,
             has_focus: false,
+            ime_input,
+            is_composing: false,
         };
 
         // Prevent touch-scrolling on canvas.
@@ -281,21 +459,86 @@ impl Ruffle {
                 let mut ruffle = ruffle.clone();
                 let instance = instances.get_mut(index).unwrap();
                 instance.animation_handler = Some(Closure::wrap(Box::new(move |timestamp: f64| {
+                    INSTANCES.with(|instances| {
+                        if let Some(instance) = instances.borrow_mut().get_mut(index) {
+                            instance.animation_handler_id = None;
+                        }
+                    });
                     ruffle.tick(timestamp);
                 })
                     as Box<dyn FnMut(f64)>));
             }
 
+            // Create the closure that ticks the player while the page is hidden.
+            {
+                let mut ruffle = ruffle.clone();
+                let instance = instances.get_mut(index).unwrap();
+                instance.background_tick_callback = Some(Closure::wrap(Box::new(move || {
+                    INSTANCES.with(|instances| {
+                        if let Some(instance) = instances.borrow_mut().get_mut(index) {
+                            instance.background_tick_id = None;
+                        }
+                    });
+                    if let Some(performance) = web_sys::window().and_then(|w| w.performance()) {
+                        ruffle.tick(performance.now());
+                    }
+                })
+                    as Box<dyn FnMut()>));
+            }
+
+            // Create page visibility handler.
+            {
+                let mut ruffle = ruffle.clone();
+                let visibility_change_callback =
+                    Closure::wrap(Box::new(move |_js_event: Event| {
+                        let is_hidden = web_sys::window()
+                            .and_then(|w| w.document())
+                            .map(|d| d.hidden())
+                            .unwrap_or(false);
+                        INSTANCES.with(|instances| {
+                            if let Some(instance) = instances.borrow_mut().get_mut(index) {
+                                instance.core.lock().unwrap().set_background_throttle(
+                                    if is_hidden {
+                                        BackgroundThrottle::Hidden
+                                    } else {
+                                        BackgroundThrottle::Visible
+                                    },
+                                );
+                            }
+                        });
+                        // Switch to ticking on a timer, or back to animation frames.
+                        if let Some(performance) = web_sys::window().and_then(|w| w.performance()) {
+                            ruffle.tick(performance.now());
+                        }
+                    }) as Box<dyn FnMut(Event)>);
+                document
+                    .add_event_listener_with_callback(
+                        "visibilitychange",
+                        visibility_change_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                let instance = instances.get_mut(index).unwrap();
+                instance.visibility_change_callback = Some(visibility_change_callback);
+                if document.hidden() {
+                    instance
+                        .core
+                        .lock()
+                        .unwrap()
+                        .set_background_throttle(BackgroundThrottle::Hidden);
+                }
+            }
+
             // Create mouse move handler.
             {
                 let mouse_move_callback = Closure::wrap(Box::new(move |js_event: PointerEvent| {
                     INSTANCES.with(move |instances| {
                         let mut instances = instances.borrow_mut();
                         if let Some(instance) = instances.get_mut(index) {
-                            let event = PlayerEvent::MouseMove {
-                                x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                                y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
-                            };
+                            let event = pointer_event(
+                                &js_event,
+                                TouchPhase::Move,
+                                instance.device_pixel_ratio,
+                            );
                             instance.core.lock().unwrap().handle_event(event);
                             if instance.has_focus {
                                 js_event.prevent_default();
@@ -322,15 +565,17 @@ impl Ruffle {
                         let mut instances = instances.borrow_mut();
                         if let Some(instance) = instances.get_mut(index) {
                             instance.has_focus = true;
+                            let _ = instance.ime_input.focus();
                             if let Some(target) = js_event.current_target() {
                                 let _ = target
                                     .unchecked_ref::<Element>()
                                     .set_pointer_capture(js_event.pointer_id());
                             }
-                            let event = PlayerEvent::MouseDown {
-                                x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                                y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
-                            };
+                            let event = pointer_event(
+                                &js_event,
+                                TouchPhase::Begin,
+                                instance.device_pixel_ratio,
+                            );
                             instance.core.lock().unwrap().handle_event(event);
                             js_event.prevent_default();
                         }
@@ -384,10 +629,11 @@ impl Ruffle {
                                     .unchecked_ref::<Element>()
                                     .release_pointer_capture(js_event.pointer_id());
                             }
-                            let event = PlayerEvent::MouseUp {
-                                x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
-                                y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
-                            };
+                            let event = pointer_event(
+                                &js_event,
+                                TouchPhase::End,
+                                instance.device_pixel_ratio,
+                            );
                             instance.core.lock().unwrap().handle_event(event);
                             if instance.has_focus {
                                 js_event.prevent_default();
@@ -397,12 +643,14 @@ impl Ruffle {
                 })
                     as Box<dyn FnMut(PointerEvent)>);
                 let canvas_events: &EventTarget = canvas.as_ref();
-                canvas_events
-                    .add_event_listener_with_callback(
-                        "pointerup",
-                        mouse_up_callback.as_ref().unchecked_ref(),
-                    )
-                    .unwrap();
+                for event_name in &["pointerup", "pointercancel"] {
+                    canvas_events
+                        .add_event_listener_with_callback(
+                            event_name,
+                            mouse_up_callback.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                }
                 let instance = instances.get_mut(index).unwrap();
                 instance.mouse_up_callback = Some(mouse_up_callback);
             }
@@ -450,20 +698,29 @@ impl Ruffle {
                 let key_down_callback = Closure::wrap(Box::new(move |js_event: KeyboardEvent| {
                     INSTANCES.with(|instances| {
                         if let Some(instance) = instances.borrow_mut().get_mut(index) {
-                            if instance.has_focus {
+                            // Keys pressed while composing belong to the input method editor,
+                            // and its default action must not be prevented. The key that starts
+                            // a composition is reported with the special key code 229.
+                            // Ctrl+Shift+F toggles the diagnostics overlay.
+                            if instance.has_focus
+                                && js_event.ctrl_key()
+                                && js_event.shift_key()
+                                && js_event.code() == "KeyF"
+                            {
+                                let mut core = instance.core.lock().unwrap();
+                                let show_overlay = !core.debug_overlay();
+                                core.set_debug_overlay(show_overlay);
+                                js_event.prevent_default();
+                            } else if instance.has_focus
+                                && !instance.is_composing
+                                && !js_event.is_composing()
+                                && js_event.key_code() != 229
+                            {
                                 let code = js_event.code();
-                                instance
-                                    .core
-                                    .lock()
-                                    .unwrap()
-                                    .input_mut()
-                                    .downcast_mut::<WebInputBackend>()
-                                    .unwrap()
-                                    .keydown(code.clone());
-
-                                if let Some(codepoint) =
-                                    input::web_key_to_codepoint(&js_event.key())
-                                {
+                                let key_char = input::web_key_to_codepoint(&js_event.key());
+                                let key_location =
+                                    input::web_to_ruffle_key_location(js_event.location());
+                                if let Some(codepoint) = key_char {
                                     instance
                                         .core
                                         .lock()
@@ -472,11 +729,13 @@ impl Ruffle {
                                 }
 
                                 if let Some(key_code) = input::web_to_ruffle_key_code(&code) {
-                                    instance
-                                        .core
-                                        .lock()
-                                        .unwrap()
-                                        .handle_event(PlayerEvent::KeyDown { key_code });
+                                    instance.core.lock().unwrap().handle_event(
+                                        PlayerEvent::KeyDown {
+                                            key_code,
+                                            key_char,
+                                            key_location,
+                                        },
+                                    );
                                 }
 
                                 js_event.prevent_default();
@@ -503,21 +762,17 @@ impl Ruffle {
                         if let Some(instance) = instances.borrow_mut().get_mut(index) {
                             if instance.has_focus {
                                 let code = js_event.code();
-                                instance
-                                    .core
-                                    .lock()
-                                    .unwrap()
-                                    .input_mut()
-                                    .downcast_mut::<WebInputBackend>()
-                                    .unwrap()
-                                    .keyup(code.clone());
-
+                                let key_char = input::web_key_to_codepoint(&js_event.key());
+                                let key_location =
+                                    input::web_to_ruffle_key_location(js_event.location());
                                 if let Some(key_code) = input::web_to_ruffle_key_code(&code) {
-                                    instance
-                                        .core
-                                        .lock()
-                                        .unwrap()
-                                        .handle_event(PlayerEvent::KeyUp { key_code });
+                                    instance.core.lock().unwrap().handle_event(
+                                        PlayerEvent::KeyUp {
+                                            key_code,
+                                            key_char,
+                                            key_location,
+                                        },
+                                    );
                                 }
 
                                 js_event.prevent_default();
@@ -536,6 +791,52 @@ impl Ruffle {
                 instance.key_up_callback = Some(key_up_callback);
             }
 
+            // Create input method editor composition handlers.
+            {
+                let composition_start_callback =
+                    Closure::wrap(Box::new(move |_js_event: CompositionEvent| {
+                        INSTANCES.with(|instances| {
+                            if let Some(instance) = instances.borrow_mut().get_mut(index) {
+                                instance.is_composing = true;
+                            }
+                        });
+                    }) as Box<dyn FnMut(CompositionEvent)>);
+
+                let composition_end_callback =
+                    Closure::wrap(Box::new(move |js_event: CompositionEvent| {
+                        INSTANCES.with(|instances| {
+                            if let Some(instance) = instances.borrow_mut().get_mut(index) {
+                                instance.is_composing = false;
+                                instance.ime_input.set_value("");
+
+                                // Deliver the composed text as if it was typed.
+                                let text = js_event.data().unwrap_or_default();
+                                let mut core = instance.core.lock().unwrap();
+                                for codepoint in text.chars() {
+                                    core.handle_event(PlayerEvent::TextInput { codepoint });
+                                }
+                            }
+                        });
+                    }) as Box<dyn FnMut(CompositionEvent)>);
+
+                let instance = instances.get_mut(index).unwrap();
+                let ime_input_events: &EventTarget = instance.ime_input.as_ref();
+                ime_input_events
+                    .add_event_listener_with_callback(
+                        "compositionstart",
+                        composition_start_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                ime_input_events
+                    .add_event_listener_with_callback(
+                        "compositionend",
+                        composition_end_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                instance.composition_start_callback = Some(composition_start_callback);
+                instance.composition_end_callback = Some(composition_end_callback);
+            }
+
             ruffle
         });
 
@@ -550,23 +851,42 @@ impl Ruffle {
             let mut instances = instances.borrow_mut();
             if let Some(instance) = instances.get_mut(self.0) {
                 let window = web_sys::window().unwrap();
+                let mut core_lock = instance.core.lock().unwrap();
 
-                // Calculate the dt from last tick.
-                let dt = if let Some(prev_timestamp) = instance.timestamp {
-                    instance.timestamp = Some(timestamp);
-                    timestamp - prev_timestamp
-                } else {
-                    // Store the timestamp from the initial tick.
-                    // (I tried to use Performance.now() to get the initial timestamp,
-                    // but this didn't seem to be accurate and caused negative dts on
-                    // Chrome.)
-                    instance.timestamp = Some(timestamp);
-                    0.0
-                };
+                // Skip animation frames that arrive well before the player is due,
+                // allowing for some jitter in when the browser calls us.
+                let is_due = instance
+                    .next_tick_timestamp
+                    .map_or(true, |next| timestamp >= next - TICK_TOLERANCE_MS);
+                if is_due {
+                    // Calculate the dt from last tick.
+                    let dt = if let Some(prev_timestamp) = instance.timestamp {
+                        instance.timestamp = Some(timestamp);
+                        timestamp - prev_timestamp
+                    } else {
+                        // Store the timestamp from the initial tick.
+                        // (I tried to use Performance.now() to get the initial timestamp,
+                        // but this didn't seem to be accurate and caused negative dts on
+                        // Chrome.)
+                        instance.timestamp = Some(timestamp);
+                        0.0
+                    };
+
+                    core_lock.tick(dt);
+
+                    // The movie may have changed its frame rate, so always ask the player.
+                    let time_til_next_frame = core_lock.time_til_next_frame();
+                    instance.next_tick_timestamp =
+                        Some(timestamp + time_til_next_frame.as_secs_f64() * 1000.0);
+                }
 
-                let mut core_lock = instance.core.lock().unwrap();
-                core_lock.tick(dt);
-                let mut needs_render = core_lock.needs_render();
+                // Only draw when the player has ticked; anything else that needs a
+                // render, such as input, is drawn along with the next tick.
+                let mut needs_render = is_due && core_lock.needs_render();
+                let is_throttled = core_lock.background_throttle() != BackgroundThrottle::Visible;
+                let time_til_next_tick = instance
+                    .next_tick_timestamp
+                    .map_or(0.0, |next| (next - timestamp).max(0.0));
 
                 // Check for canvas resize.
                 let canvas_width = instance.canvas.client_width();
@@ -604,15 +924,28 @@ impl Ruffle {
                     core_lock.render();
                 }
 
-                // Request next animation frame.
-                if let Some(handler) = &instance.animation_handler {
-                    let window = web_sys::window().unwrap();
-                    let id = window
-                        .request_animation_frame(handler.as_ref().unchecked_ref())
-                        .unwrap();
-                    instance.animation_handler_id = NonZeroI32::new(id);
-                } else {
-                    instance.animation_handler_id = None;
+                if is_throttled {
+                    // Browsers stop animation frames for hidden pages, so tick on a timer instead.
+                    if let (Some(handler), None) = (
+                        &instance.background_tick_callback,
+                        instance.background_tick_id,
+                    ) {
+                        let id = window
+                            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                                handler.as_ref().unchecked_ref(),
+                                time_til_next_tick as i32,
+                            )
+                            .unwrap();
+                        instance.background_tick_id = Some(id);
+                    }
+                } else if instance.animation_handler_id.is_none() {
+                    // Request next animation frame.
+                    if let Some(handler) = &instance.animation_handler {
+                        let id = window
+                            .request_animation_frame(handler.as_ref().unchecked_ref())
+                            .unwrap();
+                        instance.animation_handler_id = NonZeroI32::new(id);
+                    }
                 }
             }
         });
@@ -697,18 +1030,60 @@ impl ExternalInterfaceProvider for JavascriptInterface {
     }
 }
 
+/// Convert a pointer event into a player event. Fingers on a touch screen are
+/// reported as touch points, and everything else as the mouse.
+fn pointer_event(
+    js_event: &PointerEvent,
+    phase: TouchPhase,
+    device_pixel_ratio: f64,
+) -> PlayerEvent {
+    let x = f64::from(js_event.offset_x()) * device_pixel_ratio;
+    let y = f64::from(js_event.offset_y()) * device_pixel_ratio;
+    if js_event.pointer_type() == "touch" {
+        PlayerEvent::Touch {
+            phase,
+            id: js_event.pointer_id(),
+            x,
+            y,
+            pressure: f64::from(js_event.pressure()),
+        }
+    } else {
+        match phase {
+            TouchPhase::Begin => PlayerEvent::MouseDown { x, y },
+            TouchPhase::Move => PlayerEvent::MouseMove { x, y },
+            TouchPhase::End => PlayerEvent::MouseUp { x, y },
+        }
+    }
+}
+
 fn js_to_external_value(js: &JsValue) -> ExternalValue {
+    js_to_external_value_visited(js, &mut Vec::new())
+}
+
+/// Convert a JS value, replacing any object in `visited` with `Null` so that cycles terminate.
+fn js_to_external_value_visited(js: &JsValue, visited: &mut Vec<JsValue>) -> ExternalValue {
+    if js.is_object() {
+        if visited.iter().any(|v| Object::is(v, js)) {
+            return ExternalValue::Null;
+        }
+        visited.push(js.clone());
+    }
+
     if let Some(value) = js.as_f64() {
         ExternalValue::Number(value)
     } else if let Some(value) = js.as_string() {
         ExternalValue::String(value)
     } else if let Some(value) = js.as_bool() {
         ExternalValue::Bool(value)
+    } else if let Some(date) = js.dyn_ref::<js_sys::Date>() {
+        ExternalValue::Date(date.get_time())
+    } else if let Some(bytes) = js.dyn_ref::<Uint8Array>() {
+        ExternalValue::Bytes(bytes.to_vec())
     } else if let Some(array) = js.dyn_ref::<Array>() {
         let mut values = Vec::new();
         for value in array.values() {
             if let Ok(value) = value {
-                values.push(js_to_external_value(&value));
+                values.push(js_to_external_value_visited(&value, visited));
             }
         }
         ExternalValue::List(values)
@@ -717,7 +1092,7 @@ fn js_to_external_value(js: &JsValue) -> ExternalValue {
         for entry in Object::entries(&object).values() {
             if let Ok(entry) = entry.and_then(|v| v.dyn_into::<Array>()) {
                 if let Some(key) = entry.get(0).as_string() {
-                    values.insert(key, js_to_external_value(&entry.get(1)));
+                    values.insert(key, js_to_external_value_visited(&entry.get(1), visited));
                 }
             }
         }
@@ -754,11 +1129,39 @@ fn external_to_js_value(external: ExternalValue) -> JsValue {
             }
             array.into()
         }
+        Value::Date(time) => js_sys::Date::new(&JsValue::from_f64(time)).into(),
+        Value::Bytes(bytes) => Uint8Array::from(&bytes[..]).into(),
+    }
+}
+
+fn context_menu_item_to_js(item: ContextMenuItem) -> JsValue {
+    let entries = Array::new();
+    entries.push(&Array::of2(
+        &JsValue::from_str("caption"),
+        &JsValue::from_str(&item.caption),
+    ));
+    entries.push(&Array::of2(
+        &JsValue::from_str("enabled"),
+        &JsValue::from_bool(item.enabled),
+    ));
+    entries.push(&Array::of2(
+        &JsValue::from_str("separatorBefore"),
+        &JsValue::from_bool(item.separator_before),
+    ));
+    entries.push(&Array::of2(
+        &JsValue::from_str("checked"),
+        &JsValue::from_bool(item.checked),
+    ));
+    if let Ok(result) = Object::from_entries(&entries) {
+        result.into()
+    } else {
+        JsValue::NULL
     }
 }
 
 fn create_renderer(
     document: &web_sys::Document,
+    is_transparent: bool,
 ) -> Result<(HtmlCanvasElement, Box<dyn RenderBackend>), Box<dyn Error>> {
     #[cfg(not(any(feature = "canvas", feature = "webgl")))]
     std::compile_error!("You must enable one of the render backend features (e.g., webgl).");
@@ -774,7 +1177,8 @@ fn create_renderer(
             .into_js_result()?
             .dyn_into()
             .map_err(|_| "Expected HtmlCanvasElement")?;
-        if let Ok(renderer) = ruffle_render_webgl::WebGlRenderBackend::new(&canvas) {
+        if let Ok(renderer) = ruffle_render_webgl::WebGlRenderBackend::new(&canvas, is_transparent)
+        {
             return Ok((canvas, Box::new(renderer)));
         }
     }
@@ -787,10 +1191,50 @@ fn create_renderer(
             .into_js_result()?
             .dyn_into()
             .map_err(|_| "Expected HtmlCanvasElement")?;
-        if let Ok(renderer) = ruffle_render_canvas::WebCanvasRenderBackend::new(&canvas) {
+        if let Ok(renderer) =
+            ruffle_render_canvas::WebCanvasRenderBackend::new(&canvas, is_transparent)
+        {
             return Ok((canvas, Box::new(renderer)));
         }
     }
 
     Err("Unable to create renderer".into())
 }
+
+/// Creates the hidden text input used to receive text from input method editors.
+///
+/// Browsers only start composing text when an editable element is focused.
+fn create_ime_input(document: &web_sys::Document) -> Result<HtmlInputElement, Box<dyn Error>> {
+    let ime_input: HtmlInputElement = document
+        .create_element("input")
+        .into_js_result()?
+        .dyn_into()
+        .map_err(|_| "Expected HtmlInputElement")?;
+    ime_input.set_type("text");
+    ime_input.set_tab_index(-1);
+    ime_input
+        .set_attribute("aria-hidden", "true")
+        .into_js_result()?;
+    ime_input
+        .set_attribute("autocomplete", "off")
+        .into_js_result()?;
+    // Don't bring up a virtual keyboard whenever the player is clicked on mobile.
+    ime_input
+        .set_attribute("inputmode", "none")
+        .into_js_result()?;
+
+    let style = ime_input.style();
+    for &(property, value) in &[
+        ("position", "absolute"),
+        ("left", "0"),
+        ("top", "0"),
+        ("width", "1px"),
+        ("height", "1px"),
+        ("opacity", "0"),
+        ("pointer-events", "none"),
+    ] {
+        style.set_property(property, value).into_js_result()?;
+    }
+
+    Ok(ime_input)
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
 use ruffle_core::backend::locale::LocaleBackend;
+use wasm_bindgen::JsValue;
 
 pub struct WebLocaleBackend();
 
@@ -17,4 +18,11 @@ impl LocaleBackend for WebLocaleBackend {
     fn get_timezone(&self) -> FixedOffset {
         Local::now().offset().fix()
     }
+
+    fn get_timezone_at(&self, date: DateTime<Utc>) -> FixedOffset {
+        // `getTimezoneOffset` is the number of minutes from local time to UTC.
+        let js_date = js_sys::Date::new(&JsValue::from_f64(date.timestamp_millis() as f64));
+        let offset = (js_date.get_timezone_offset() * 60.0) as i32;
+        FixedOffset::west_opt(offset).unwrap_or_else(|| self.get_timezone())
+    }
 }
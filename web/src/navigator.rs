@@ -1,6 +1,6 @@
 //! Navigator backend for web
 
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Function, Object, Promise, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
     url_from_relative_url, NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
 };
@@ -8,27 +8,102 @@ use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
 use std::time::Duration;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
-use web_sys::{window, Blob, BlobPropertyBag, Performance, Request, RequestInit, Response};
+use web_sys::{
+    window, Blob, BlobPropertyBag, Headers, Performance, Request, RequestInit, Response,
+};
 
 pub struct WebNavigatorBackend {
     performance: Performance,
     start_time: f64,
+
+    /// A function provided by the page that is consulted before every fetch.
+    ///
+    /// It is called as `fetchHook(url, { method, headers })`, and may return
+    /// a string to fetch a different URL instead, a promise (or value) of an
+    /// `ArrayBuffer` or `Uint8Array` to use as the response, or `null` to
+    /// fetch the URL as usual.
+    fetch_hook: Option<Function>,
 }
 
 impl WebNavigatorBackend {
-    pub fn new() -> Self {
+    pub fn new(fetch_hook: Option<Function>) -> Self {
         let window = web_sys::window().expect("window()");
         let performance = window.performance().expect("window.performance()");
 
         WebNavigatorBackend {
             start_time: performance.now(),
             performance,
+            fetch_hook,
         }
     }
 }
 
+/// What the page's fetch hook decided to do with a request.
+enum FetchHookResult {
+    /// Fetch the given URL, which may have been rewritten.
+    Fetch(String),
+
+    /// Use the given data as a successful response.
+    Respond(Vec<u8>),
+}
+
+/// Consult the page's fetch hook about a request.
+///
+/// Exceptions thrown by the hook, rejected promises and unexpected return
+/// values all fail the request, in the same way as a network error.
+async fn run_fetch_hook(
+    hook: &Function,
+    url: String,
+    options: &RequestOptions,
+) -> Result<FetchHookResult, Error> {
+    let original_url = url.clone();
+    let hook_error = move |message: &str| {
+        Error::NetworkError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Fetch hook failed for {}: {}", original_url, message),
+        ))
+    };
+
+    let js_options = Object::new();
+    let method = match options.method() {
+        NavigationMethod::GET => "GET",
+        NavigationMethod::POST => "POST",
+    };
+    let _ = Reflect::set(&js_options, &"method".into(), &method.into());
+    let headers = Object::new();
+    for (name, value) in options.headers() {
+        let _ = Reflect::set(&headers, &name.into(), &value.into());
+    }
+    let _ = Reflect::set(&js_options, &"headers".into(), &headers);
+
+    let result = hook
+        .call2(&JsValue::NULL, &JsValue::from_str(&url), &js_options)
+        .map_err(|_| hook_error("the hook threw an exception"))?;
+
+    if result.is_null() || result.is_undefined() {
+        return Ok(FetchHookResult::Fetch(url));
+    }
+
+    if let Some(new_url) = result.as_string() {
+        return Ok(FetchHookResult::Fetch(new_url));
+    }
+
+    let data = JsFuture::from(Promise::resolve(&result))
+        .await
+        .map_err(|_| hook_error("the returned promise was rejected"))?;
+    if let Some(bytes) = data.dyn_ref::<Uint8Array>() {
+        Ok(FetchHookResult::Respond(bytes.to_vec()))
+    } else if data.is_instance_of::<ArrayBuffer>() {
+        Ok(FetchHookResult::Respond(Uint8Array::new(&data).to_vec()))
+    } else {
+        Err(hook_error(
+            "expected a URL, an ArrayBuffer or a Uint8Array to be returned",
+        ))
+    }
+}
+
 impl NavigatorBackend for WebNavigatorBackend {
     fn navigate_to_url(
         &self,
@@ -67,7 +142,7 @@ impl NavigatorBackend for WebNavigatorBackend {
                     }
 
                     for (k, v) in formvars.iter() {
-                        let hidden = document.create_element("hidden").unwrap();
+                        let hidden = document.create_element("input").unwrap();
 
                         hidden.set_attribute("type", "hidden");
                         hidden.set_attribute("name", k);
@@ -76,8 +151,10 @@ impl NavigatorBackend for WebNavigatorBackend {
                         form.append_child(&hidden);
                     }
 
-                    document.body().unwrap().append_child(&form);
+                    let body = document.body().unwrap();
+                    body.append_child(&form);
                     form.submit();
+                    body.remove_child(&form);
                 }
                 (_, Some(ref window_name)) if window_name != "" => {
                     window.open_with_url_and_target(&url, window_name);
@@ -96,7 +173,16 @@ impl NavigatorBackend for WebNavigatorBackend {
 
     fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
         let url = url.to_string();
+        let fetch_hook = self.fetch_hook.clone();
         Box::pin(async move {
+            let url = match &fetch_hook {
+                Some(hook) => match run_fetch_hook(hook, url, &options).await? {
+                    FetchHookResult::Fetch(url) => url,
+                    FetchHookResult::Respond(data) => return Ok(data),
+                },
+                None => url,
+            };
+
             let mut init = RequestInit::new();
 
             init.method(match options.method() {
@@ -127,6 +213,15 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
+            if !options.headers().is_empty() {
+                let headers = Headers::new().unwrap();
+                for (name, value) in options.headers() {
+                    // Invalid header names are rejected by the browser; skip them.
+                    let _ = headers.append(name, value);
+                }
+                init.headers(&headers);
+            }
+
             let request = Request::new_with_str_and_init(&url, &init).unwrap();
 
             let window = web_sys::window().unwrap();
@@ -139,6 +234,10 @@ impl NavigatorBackend for WebNavigatorBackend {
             }
 
             let resp: Response = fetchval.unwrap().dyn_into().unwrap();
+            if !resp.ok() {
+                return Err(Error::HttpNotOk(url, resp.status()));
+            }
+
             let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
                 .await
                 .unwrap()